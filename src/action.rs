@@ -3,7 +3,10 @@ use std::sync::Arc;
 
 use crate::app_message::AppMessage;
 use crate::components::ComponentId;
-use crate::models::{Connection, Version};
+use crate::components::search::{SearchEntry, SearchTarget};
+use crate::config::Config;
+use crate::models::{Connection, Rule, Version};
+use crate::store::capture_file::{CaptureFileRequest, CaptureSnapshot};
 use crate::widgets::shortcut::Shortcut;
 
 #[derive(Debug, Clone)]
@@ -17,34 +20,116 @@ pub enum Action {
     Quit,
     Focus(ComponentId),
     Unfocus,
+    /// The terminal window gained (`true`) or lost focus.
+    TerminalFocus(bool),
     ClearScreen,
     Info(AppMessage),
-    Error(AppMessage),
+    /// Show an error, logged under the component that reported it.
+    Error(ComponentId, AppMessage),
     AppUpdateRequest,
     SelfUpdate(bool),
     RefreshVersion,
+    /// Sent by the config file watcher with the re-parsed config; handled by `App` only.
+    ConfigFileChanged(Arc<Config>),
+    /// The config that has been applied; components pick up new settings from it.
+    ConfigReloaded(Arc<Config>),
+    /// Ask the user to confirm reconnecting with the API endpoint of the given config.
+    ApiReconnectRequest(Arc<Config>),
+    /// Rebuild the API client from the given config and reload all components.
+    ApiReconnect(Arc<Config>),
+    /// Ask the user to confirm the message, the action is sent once confirmed.
+    ConfirmRequest(AppMessage, Box<Action>),
+    /// Update the GEO databases of the core, offered when a GeoIP mismatch hints one is stale.
+    GeoUpdate,
+    /// Ask for the controller secret after the API rejected the request with 401.
+    SecretPrompt,
+    /// Write the given secret into the config file; handled by `App` only.
+    SaveSecret(String),
     CoreVersionUpdated(Version),
     /// Spawn an external editor to edit a file. args: `(editor command, file path)`
     SpawnExternalEditor(String, PathBuf),
+    /// Open the given URL in the system browser.
+    OpenUrl(String),
     Help,
     TabSwitch(ComponentId),
+    TabSwitcher,
+    /// Open the popup searching the items of all loaded tabs.
+    GlobalSearch,
+    /// Items of the loaded tabs, collected for the global search popup.
+    GlobalSearchEntries(Arc<[SearchEntry]>),
+    /// Switch to the tab of a global search hit, then apply its target.
+    SearchJump(ComponentId, SearchTarget),
+    /// Scheduled background refresh of the given tab, delivered even if the tab is hidden.
+    AutoRefresh(ComponentId),
+    /// Open the background tasks debug popup.
+    Tasks,
+    /// Open the timeline popup of recent user actions and core events.
+    Timeline,
+    /// Open the popup reviewing recent errors.
+    ErrorLog,
+    /// Open the debug popup of recent API requests.
+    ApiCalls,
+    /// Show the release notes of the versions after the given last run one, once per upgrade.
+    WhatsNew(String),
     Shortcuts(Vec<Shortcut>),
+    /// Show the next page of footer shortcuts that don't fit the terminal width.
+    ShortcutsNextPage,
     ConnectionDetail(Arc<Connection>),
     ConnectionsSetting(Vec<String>),
     ConnectionsSettingChanged,
+    /// Open the popup saving the connections capture buffer to, or opening it from, a file.
+    CaptureFile(CaptureFileRequest),
+    /// A saved capture was opened, the connections tab shows it read-only.
+    CaptureLoaded(Arc<CaptureSnapshot>),
+    /// Stop capture mode, dropping the closed connections it kept.
+    CaptureStop,
     /// Sent when connection layout settings change without affecting the data view.
     ConnectionsLayoutChanged,
-    /// Sent when the filter pattern is changed via user input.
-    FilterChanged(Option<String>),
-    /// Programmatically sets the filter placeholder for the current tab.
-    FilterPlaceholder(Option<String>),
-    /// Programmatically sets the filter pattern without re-triggering `FilterChanged`.
-    FilterSet(Option<String>),
+    /// Sent when pane proportions are adjusted, persisted to the runtime config.
+    LayoutChanged,
+    /// Sent when the filter pattern of a tab is changed, via user input or programmatically.
+    FilterChanged(ComponentId, Option<String>),
+    /// Programmatically sets the filter placeholder of a tab.
+    FilterPlaceholder(ComponentId, Option<String>),
+    /// Sent when a persisted filter history gained an entry, saved to the runtime config.
+    FilterHistoryChanged,
     ConnectionTerminateRequest(Arc<Connection>),
     ConnectionBatchTerminateRequest(Vec<String>),
     ProxyDetail(String),
+    /// Select a node of a proxy group. args: `(group, node)`
+    ProxyUpdateRequest(String, String),
+    /// Open proxy detail showing only the failing (slow or timed out) nodes of the group.
+    ProxyDetailFailing(String),
     ProxySetting,
     ProxySettingChanged,
+    /// Open the popup suggesting latency thresholds from a test of several groups.
+    LatencyCalibration,
+    /// Open the Overview chart settings popup.
+    ChartSetting,
+    ChartSettingChanged,
     ProxyProviderDetail(String),
+    /// Open the rule type breakdown popup. args: `(total rule count, (rule type, count) pairs)`
+    RuleBreakdown(usize, Vec<(String, usize)>),
+    /// Open the rule matching tester over a snapshot of the loaded rules.
+    RuleMatch(Arc<[Arc<Rule>]>),
+    /// Rule of the [`Self::RuleMatch`] snapshot matched by the last test.
+    RuleMatched(Option<Arc<Rule>>),
     DnsQuery,
+    /// Open the TCP ping popup.
+    TcpPing,
+    /// Open the export popup with the core config converted to YAML.
+    CoreConfigExport(String),
+    /// Open the import popup, carries the running core config to diff against.
+    CoreConfigImport(serde_json::Value),
+    /// Run the config tab action button at the given index, sent once it was confirmed.
+    CoreActionRun(usize),
+    /// Changed fields of an imported config, validated in the config tab editor to be submitted.
+    /// Carries the name of the profile they come from, if any.
+    CoreConfigImported(serde_json::Value, Option<String>),
+    /// Turn sniffing of the running core on or off, sent once it was confirmed.
+    SniffingSet(bool),
+    /// Open the profile manager, carries the running core config to diff against.
+    Profiles(serde_json::Value),
+    /// A profile's changes were submitted by the config tab.
+    ProfileApplied(String),
 }
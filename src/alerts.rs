@@ -0,0 +1,281 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::Value as JsonValue;
+use time::OffsetDateTime;
+
+use crate::config::{AlertConfig, HookConfig};
+use crate::models::Connection;
+use crate::store::snapshot_delta::SnapshotDelta;
+use crate::utils::expr::{Expr, FieldResolver, Value};
+
+/// Evaluates user defined alert conditions and hook triggers against connection snapshots.
+///
+/// A rule fires once per matching connection, it fires again only after the connection stopped
+/// matching (or closed and reappeared).
+#[derive(Debug, Default)]
+pub struct AlertEngine {
+    rules: Vec<Rule>,
+    fired: HashSet<(usize, String)>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    name: String,
+    expr: Expr,
+    /// Command of a hook, `None` for an alert
+    run: Option<String>,
+}
+
+/// Alerts and hooks fired by a snapshot.
+#[derive(Debug, Default)]
+pub struct Fired {
+    /// Messages of the fired alerts
+    pub alerts: Vec<String>,
+    pub hooks: Vec<HookRun>,
+}
+
+/// A hook fired by a connection, see [`Self::spawn`].
+#[derive(Debug, PartialEq)]
+pub struct HookRun {
+    pub name: String,
+    pub command: String,
+    pub conn_id: String,
+    pub host: String,
+}
+
+impl HookRun {
+    /// Launch the command without waiting for it to exit. The hook name, connection id and host
+    /// are passed in `MIHOMO_TUI_HOOK`, `MIHOMO_TUI_CONN_ID` and `MIHOMO_TUI_CONN_HOST`.
+    pub fn spawn(&self) -> Result<()> {
+        let mut parts = self.command.split_whitespace();
+        let program = parts.next().ok_or_else(|| anyhow!("Empty command of hook"))?;
+        let mut child = Command::new(program)
+            .args(parts)
+            .env("MIHOMO_TUI_HOOK", &self.name)
+            .env("MIHOMO_TUI_CONN_ID", &self.conn_id)
+            .env("MIHOMO_TUI_CONN_HOST", &self.host)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Fail to spawn `{}` of hook {:?}", self.command, self.name))?;
+        // reap the child in background
+        std::thread::spawn(move || child.wait());
+        Ok(())
+    }
+}
+
+impl AlertEngine {
+    pub fn new(alerts: &[AlertConfig], hooks: &[HookConfig]) -> Result<Self> {
+        let alerts = alerts.iter().map(|c| {
+            let expr = Expr::parse(&c.when)
+                .with_context(|| format!("Invalid condition of alert {:?}", c.name))?;
+            Ok(Rule { name: c.name.clone(), expr, run: None })
+        });
+        let hooks = hooks.iter().map(|c| {
+            let expr = Expr::parse(&c.when)
+                .with_context(|| format!("Invalid condition of hook {:?}", c.name))?;
+            if c.run.trim().is_empty() {
+                bail!("Empty command of hook {:?}", c.name);
+            }
+            Ok(Rule { name: c.name.clone(), expr, run: Some(c.run.clone()) })
+        });
+        let rules = alerts.chain(hooks).collect::<Result<_>>()?;
+        Ok(Self { rules, ..Default::default() })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns the newly fired alerts and hooks.
+    pub fn check(&mut self, delta: &SnapshotDelta) -> Fired {
+        let now = OffsetDateTime::now_utc();
+        let mut result = Fired::default();
+        let mut fired = HashSet::with_capacity(self.fired.len());
+
+        for (conn, (up, down)) in delta.rates() {
+            let (up_rate, down_rate) = (delta.per_sec(up), delta.per_sec(down));
+            let fields = ConnFields { conn, up_rate, down_rate, now };
+            for (idx, rule) in self.rules.iter().enumerate() {
+                if !rule.expr.eval(&fields) {
+                    continue;
+                }
+                let key = (idx, conn.id.clone());
+                if !self.fired.contains(&key) {
+                    let host = match fields.resolve("conn.host") {
+                        Some(Value::Str(s)) => s.into_owned(),
+                        _ => String::new(),
+                    };
+                    match &rule.run {
+                        Some(command) => result.hooks.push(HookRun {
+                            name: rule.name.clone(),
+                            command: command.clone(),
+                            conn_id: conn.id.clone(),
+                            host,
+                        }),
+                        None => result.alerts.push(format!("[{}] {host}", rule.name)),
+                    }
+                }
+                fired.insert(key);
+            }
+        }
+
+        self.fired = fired;
+        result
+    }
+}
+
+/// Fields of a connection exposed to alert expressions, rates are bytes per second.
+struct ConnFields<'a> {
+    conn: &'a Connection,
+    up_rate: u64,
+    down_rate: u64,
+    now: OffsetDateTime,
+}
+
+impl FieldResolver for ConnFields<'_> {
+    fn resolve(&self, name: &str) -> Option<Value<'_>> {
+        let c = self.conn;
+        let num = |n: u64| Some(Value::Num(n as f64));
+        match name.strip_prefix("conn.")? {
+            "id" => Some(Value::Str(c.id.as_str().into())),
+            "host" => c
+                .metadata_str("host")
+                .or_else(|| c.metadata_str("sniffHost"))
+                .or_else(|| c.metadata_str("destinationIP"))
+                .map(|s| Value::Str(s.into())),
+            "destination_ip" => c.metadata_str("destinationIP").map(|s| Value::Str(s.into())),
+            "destination_port" => json_num(&c.metadata["destinationPort"]),
+            "source_ip" => c.metadata_str("sourceIP").map(|s| Value::Str(s.into())),
+            "source_port" => json_num(&c.metadata["sourcePort"]),
+            "network" => c.metadata_str("network").map(|s| Value::Str(s.into())),
+            "type" => c.metadata_str("type").map(|s| Value::Str(s.into())),
+            "process" => c.metadata_str("process").map(|s| Value::Str(s.into())),
+            "rule" => Some(Value::Str(c.rule.as_str().into())),
+            "rule_payload" => Some(Value::Str(c.rule_payload.as_str().into())),
+            "chains" => Some(Value::Str(Cow::Owned(c.chains.join(",")))),
+            "upload" => num(c.upload),
+            "download" => num(c.download),
+            "upload_rate" => num(self.up_rate),
+            "download_rate" => num(self.down_rate),
+            "duration" => c.start.map(|start| Value::Num((self.now - start).as_seconds_f64())),
+            _ => None,
+        }
+    }
+}
+
+fn json_num(value: &JsonValue) -> Option<Value<'_>> {
+    match value {
+        JsonValue::Number(n) => n.as_f64().map(Value::Num),
+        JsonValue::String(s) => s.parse().ok().map(Value::Num),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::store::snapshot_delta::SnapshotDiffer;
+    use crate::utils::test;
+
+    fn conn(id: &str, host: &str, download: u64) -> Connection {
+        test::conn(id)
+            .metadata(json!({ "host": host, "network": "tcp", "destinationPort": "443" }))
+            .bytes(0, download)
+            .chains(&["HK", "Proxy"])
+            .rule("Match", "")
+            .build()
+    }
+
+    #[test]
+    fn alert_fires_once_per_matching_connection() {
+        let configs = [AlertConfig {
+            name: "heavy youtube".into(),
+            when: r#"conn.download_rate > 1KB && conn.host contains "youtube""#.into(),
+        }];
+        let mut engine = AlertEngine::new(&configs, &[]).unwrap();
+        let mut differ = SnapshotDiffer::default();
+        // snapshots 2s apart
+        let mut check = |conns: &[Connection]| {
+            let mut delta = differ.diff(conns);
+            delta.elapsed = delta.elapsed.map(|_| Duration::from_secs(2));
+            engine.check(&delta).alerts
+        };
+
+        // rates are unknown on the first snapshot
+        assert!(check(&[conn("a", "www.youtube.com", 0)]).is_empty());
+        assert_eq!(
+            check(&[conn("a", "www.youtube.com", 4096), conn("b", "example.com", 4096)]),
+            vec!["[heavy youtube] www.youtube.com"]
+        );
+        assert!(check(&[conn("a", "www.youtube.com", 8192)]).is_empty());
+        // stopped matching, then matches again
+        assert!(check(&[conn("a", "www.youtube.com", 8192)]).is_empty());
+        assert_eq!(check(&[conn("a", "www.youtube.com", 16384)]).len(), 1);
+        // 1.5KB in 2s is below 1KB/s
+        assert!(check(&[conn("a", "www.youtube.com", 17920)]).is_empty());
+        assert_eq!(check(&[conn("a", "www.youtube.com", 26112)]).len(), 1);
+    }
+
+    #[test]
+    fn hooks_fire_with_the_matching_connection() {
+        let alerts = [AlertConfig { name: "any".into(), when: "conn.download >= 0".into() }];
+        let hooks = [HookConfig {
+            name: "youtube".into(),
+            when: r#"conn.host contains "youtube""#.into(),
+            run: "notify-send youtube".into(),
+        }];
+        let mut engine = AlertEngine::new(&alerts, &hooks).unwrap();
+        let mut differ = SnapshotDiffer::default();
+
+        let fired = engine.check(&differ.diff(&[conn("a", "www.youtube.com", 0)]));
+        assert_eq!(fired.alerts, ["[any] www.youtube.com"]);
+        assert_eq!(
+            fired.hooks,
+            [HookRun {
+                name: "youtube".into(),
+                command: "notify-send youtube".into(),
+                conn_id: "a".into(),
+                host: "www.youtube.com".into(),
+            }]
+        );
+        let fired = engine.check(&differ.diff(&[conn("a", "www.youtube.com", 0)]));
+        assert!(fired.alerts.is_empty() && fired.hooks.is_empty());
+    }
+
+    #[test]
+    fn exposed_fields() {
+        let conn = conn("a", "example.com", 0);
+        let fields =
+            ConnFields { conn: &conn, up_rate: 0, down_rate: 0, now: OffsetDateTime::now_utc() };
+        assert_eq!(fields.resolve("conn.destination_port"), Some(Value::Num(443.0)));
+        assert_eq!(fields.resolve("conn.chains"), Some(Value::Str("HK,Proxy".into())));
+        assert_eq!(fields.resolve("conn.unknown"), None);
+        assert_eq!(fields.resolve("host"), None);
+    }
+
+    #[test]
+    fn invalid_condition_is_rejected() {
+        let configs = [AlertConfig { name: "bad".into(), when: "conn.host ==".into() }];
+        let err = AlertEngine::new(&configs, &[]).unwrap_err();
+        assert!(format!("{err:#}").contains("Invalid condition of alert \"bad\""));
+
+        let hook = |when: &str, run: &str| HookConfig {
+            name: "bad".into(),
+            when: when.into(),
+            run: run.into(),
+        };
+        let err = AlertEngine::new(&[], &[hook("conn.host ==", "true")]).unwrap_err();
+        assert!(format!("{err:#}").contains("Invalid condition of hook \"bad\""));
+        let err = AlertEngine::new(&[], &[hook("conn.download > 0", " ")]).unwrap_err();
+        assert!(format!("{err:#}").contains("Empty command of hook \"bad\""));
+    }
+}
@@ -0,0 +1,82 @@
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Method, Request};
+use serde_json::Value;
+use time::OffsetDateTime;
+use url::Url;
+
+use crate::utils::redact::Redactor;
+
+/// Larger bodies, e.g. a whole submitted core config, are not kept.
+const BODY_LIMIT: usize = 16 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestBody {
+    Empty,
+    Text(Box<str>),
+    /// Size of a body above [`BODY_LIMIT`] or not valid UTF-8
+    Omitted(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiCall {
+    pub time: OffsetDateTime,
+    pub method: Method,
+    pub url: Url,
+    pub content_type: Option<Box<str>>,
+    pub body: RequestBody,
+}
+
+impl ApiCall {
+    pub fn new(req: &Request) -> Self {
+        let body = match req.body().and_then(|b| b.as_bytes()) {
+            None => RequestBody::Empty,
+            Some(bytes) if bytes.len() > BODY_LIMIT => RequestBody::Omitted(bytes.len()),
+            Some(bytes) => match std::str::from_utf8(bytes) {
+                Ok(text) => RequestBody::Text(redact_body(text).into()),
+                Err(_) => RequestBody::Omitted(bytes.len()),
+            },
+        };
+        Self {
+            time: OffsetDateTime::now_utc(),
+            method: req.method().clone(),
+            url: req.url().clone(),
+            content_type: req
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(Into::into),
+            body,
+        }
+    }
+}
+
+/// Redact secrets of a body before it is kept, e.g. `secret` and `authentication` of a
+/// `PATCH /configs`, see [`Redactor`].
+fn redact_body(text: &str) -> String {
+    match serde_json::from_str::<Value>(text) {
+        Ok(mut value) => {
+            Redactor::json(&mut value);
+            value.to_string()
+        }
+        Err(_) => Redactor::text(text).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Client;
+
+    use super::*;
+
+    #[test]
+    fn bodies_are_redacted() {
+        let req = Client::new()
+            .patch("http://127.0.0.1:9090/configs")
+            .body(r#"{"secret":"s3cret","authentication":["me:pass"],"mode":"rule"}"#)
+            .build()
+            .unwrap();
+        let RequestBody::Text(body) = ApiCall::new(&req).body else { panic!("text body") };
+        assert!(!body.contains("s3cret") && !body.contains("me:pass"), "{body}");
+        assert!(body.contains(r#""mode":"rule""#));
+    }
+}
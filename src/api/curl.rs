@@ -0,0 +1,98 @@
+use std::fmt::Write;
+
+use reqwest::Method;
+
+use super::Api;
+use super::{ApiCall, RequestBody};
+use crate::config::MihomoApiEndpoint;
+
+/// Placeholder of the secret in generated commands, export it before running them.
+const SECRET_PLACEHOLDER: &str = "${MIHOMO_SECRET}";
+
+impl Api {
+    /// Equivalent curl command of `call`, the secret is replaced by [`SECRET_PLACEHOLDER`].
+    pub fn curl_command(&self, call: &ApiCall) -> String {
+        let mut cmd = String::from("curl");
+        if call.method != Method::GET {
+            let _ = write!(cmd, " -X {}", call.method);
+        }
+        match &self.endpoint {
+            MihomoApiEndpoint::UnixSocket(path) => {
+                let _ = write!(cmd, " --unix-socket {}", quote(&path.to_string_lossy()));
+            }
+            // curl can't talk to named pipes, the URL at least names the endpoint
+            MihomoApiEndpoint::WindowsNamedPipe(_) | MihomoApiEndpoint::Http(_) => (),
+        }
+        if self.bearer_token.is_some() {
+            let _ = write!(cmd, " -H \"Authorization: Bearer {SECRET_PLACEHOLDER}\"");
+        }
+        if let Some(content_type) = &call.content_type {
+            let _ = write!(cmd, " -H {}", quote(&format!("Content-Type: {content_type}")));
+        }
+        match &call.body {
+            RequestBody::Empty => (),
+            RequestBody::Text(body) => {
+                let _ = write!(cmd, " --data-raw {}", quote(body));
+            }
+            RequestBody::Omitted(_) => cmd.push_str(" --data-binary @body.json"),
+        }
+        let _ = write!(cmd, " {}", quote(call.url.as_str()));
+        cmd
+    }
+}
+
+/// Quote `value` for POSIX shells.
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Client;
+    use url::Url;
+
+    use super::*;
+    use crate::api::test_support::test_api;
+
+    fn http() -> MihomoApiEndpoint {
+        MihomoApiEndpoint::Http(Url::parse("http://127.0.0.1:9090").unwrap())
+    }
+
+    #[test]
+    fn curl_command_redacts_secret() {
+        let api = test_api(http(), Some("s3cret"));
+        let req = Client::new()
+            .put("http://127.0.0.1:9090/proxies/it's")
+            .header("Content-Type", "application/json")
+            .body(r#"{"name":"a'b"}"#)
+            .build()
+            .unwrap();
+        let cmd = api.curl_command(&ApiCall::new(&req));
+        assert_eq!(
+            cmd,
+            concat!(
+                r#"curl -X PUT -H "Authorization: Bearer ${MIHOMO_SECRET}""#,
+                r#" -H 'Content-Type: application/json' --data-raw '{"name":"a'\''b"}'"#,
+                r#" 'http://127.0.0.1:9090/proxies/it'\''s'"#,
+            )
+        );
+        assert!(!cmd.contains("s3cret"));
+
+        let req = Client::new().get("http://127.0.0.1:9090/version").build().unwrap();
+        assert_eq!(
+            test_api(http(), None).curl_command(&ApiCall::new(&req)),
+            "curl 'http://127.0.0.1:9090/version'"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn curl_command_uses_unix_socket() {
+        let api = test_api(MihomoApiEndpoint::UnixSocket("/tmp/mihomo.sock".into()), None);
+        let req = Client::new().get("http://localhost/version").build().unwrap();
+        assert_eq!(
+            api.curl_command(&ApiCall::new(&req)),
+            "curl --unix-socket '/tmp/mihomo.sock' 'http://localhost/version'"
+        );
+    }
+}
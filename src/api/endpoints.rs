@@ -1,27 +1,28 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use indexmap::IndexMap;
+use reqwest::StatusCode;
 use reqwest::header::{CONTENT_TYPE, HeaderValue};
 use serde::Deserialize;
 use serde_json::json;
 
 use super::Api;
 use crate::models::dns::{DnsQueryRequest, DnsQueryResponse};
-use crate::models::proxy::Proxy;
+use crate::models::proxy::{NodeDetail, Proxy};
 use crate::models::proxy_provider::ProxyProvider;
 use crate::models::{ConnectionsWrapper, CoreConfig, Rule, RuleProvider, Version};
 
 impl Api {
     pub async fn get_version(&self) -> Result<Version> {
         let resp = self
-            .client
-            .get(self.api.join("/version")?)
-            .send()
+            .send(self.client.get(self.api.join("/version")?))
             .await
             .context("Fail to send `GET /version`")?;
 
-        let body = Self::check_status(resp)
+        let body = self
+            .check_status(resp)
             .await
             .context("Fail to request `GET /version`")?
             .json::<Version>()
@@ -33,13 +34,12 @@ impl Api {
 
     pub async fn get_connections(&self) -> Result<ConnectionsWrapper> {
         let resp = self
-            .client
-            .get(self.api.join("/connections")?)
-            .send()
+            .send(self.client.get(self.api.join("/connections")?))
             .await
             .context("Fail to send `GET /connections`")?;
 
-        let body = Self::check_status(resp)
+        let body = self
+            .check_status(resp)
             .await
             .context("Fail to request `GET /connections`")?
             .json::<ConnectionsWrapper>()
@@ -52,13 +52,12 @@ impl Api {
     pub async fn delete_connection(&self, id: &str) -> Result<()> {
         // NOTE `DELETE /connections/{id}` always returns empty body
         let resp = self
-            .client
-            .delete(self.api.join(&format!("/connections/{}", id))?)
-            .send()
+            .send(self.client.delete(self.api.join(&format!("/connections/{}", id))?))
             .await
             .context("Fail to send `DELETE /connections/<id>` request")?;
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `DELETE /connections/<id>`")?
             .bytes()
@@ -75,13 +74,12 @@ impl Api {
         }
 
         let resp = self
-            .client
-            .get(self.api.join("/proxies")?)
-            .send()
+            .send(self.client.get(self.api.join("/proxies")?))
             .await
             .context("Fail to send `GET /proxies`")?;
 
-        let body = Self::check_status(resp)
+        let body = self
+            .check_status(resp)
             .await
             .context("Fail to request `GET /proxies`")?
             .json::<Wrapper>()
@@ -95,14 +93,16 @@ impl Api {
         let body = serde_json::to_string(&json!({ "name": name.as_ref() }))
             .with_context(|| format!("Fail to create body with name `{}`", name.as_ref()))?;
         let resp = self
-            .client
-            .put(self.api.join(&format!("/proxies/{}", selector_name.as_ref()))?)
-            .body(body)
-            .send()
+            .send(
+                self.client
+                    .put(self.api.join(&format!("/proxies/{}", selector_name.as_ref()))?)
+                    .body(body),
+            )
             .await
             .context("Fail to send `PUT /proxies/<selector_name>` request")?;
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `PUT /proxies/<selector_name>`")?
             .bytes()
@@ -112,21 +112,56 @@ impl Api {
         Ok(())
     }
 
-    pub async fn test_proxy<S: AsRef<str>>(&self, name: S, url: S, timeout: usize) -> Result<u16> {
+    /// Drop the manual pick of an url-test or fallback group so the core selects by delay again.
+    pub async fn unfix_proxy<S: AsRef<str>>(&self, group_name: S) -> Result<()> {
+        let resp = self
+            .send(self.client.delete(self.api.join(&format!("/proxies/{}", group_name.as_ref()))?))
+            .await
+            .context("Fail to send `DELETE /proxies/<group_name>` request")?;
+
+        let _ = self
+            .check_status(resp)
+            .await
+            .context("Fail to request `DELETE /proxies/<group_name>`")?
+            .bytes()
+            .await
+            .context("Fail to read response of `DELETE /proxies/<group_name>`")?;
+
+        Ok(())
+    }
+
+    /// Query of delay tests, `expected` is omitted when empty so any status is accepted.
+    fn delay_query(url: &str, timeout: usize, expected: &str) -> Vec<(&'static str, String)> {
+        let mut query = vec![("url", url.to_string()), ("timeout", timeout.to_string())];
+        if !expected.is_empty() {
+            query.push(("expected", expected.to_string()));
+        }
+        query
+    }
+
+    pub async fn test_proxy<S: AsRef<str>>(
+        &self,
+        name: S,
+        url: S,
+        timeout: usize,
+        expected: &str,
+    ) -> Result<u16> {
         #[derive(Deserialize)]
         struct DelayResp {
             delay: u16,
         }
 
         let resp = self
-            .client
-            .get(self.api.join(&format!("/proxies/{}/delay", name.as_ref()))?)
-            .query(&[("url", url.as_ref()), ("timeout", timeout.to_string().as_ref())])
-            .send()
+            .send(
+                self.client
+                    .get(self.api.join(&format!("/proxies/{}/delay", name.as_ref()))?)
+                    .query(&Self::delay_query(url.as_ref(), timeout, expected)),
+            )
             .await
             .context("Fail to send `GET /proxies/<name>/delay`")?;
 
-        let body = Self::check_status(resp)
+        let body = self
+            .check_status(resp)
             .await
             .context("Fail to request `GET /proxies/<name>/delay`")?
             .json::<DelayResp>()
@@ -141,16 +176,19 @@ impl Api {
         name: S,
         url: S,
         timeout: usize,
+        expected: &str,
     ) -> Result<HashMap<String, u16>> {
         let resp = self
-            .client
-            .get(self.api.join(&format!("/group/{}/delay", name.as_ref()))?)
-            .query(&[("url", url.as_ref()), ("timeout", timeout.to_string().as_ref())])
-            .send()
+            .send(
+                self.client
+                    .get(self.api.join(&format!("/group/{}/delay", name.as_ref()))?)
+                    .query(&Self::delay_query(url.as_ref(), timeout, expected)),
+            )
             .await
             .context("Fail to send `GET /group/<name>/delay`")?;
 
-        let body = Self::check_status(resp)
+        let body = self
+            .check_status(resp)
             .await
             .context("Fail to request `GET /group/<name>/delay`")?
             .json()
@@ -167,13 +205,12 @@ impl Api {
         }
 
         let resp = self
-            .client
-            .get(self.api.join("/providers/proxies")?)
-            .send()
+            .send(self.client.get(self.api.join("/providers/proxies")?))
             .await
             .context("Fail to send `GET /providers/proxies`")?;
 
-        let body = Self::check_status(resp)
+        let body = self
+            .check_status(resp)
             .await
             .context("Fail to request `GET /providers/proxies`")?
             .json::<Wrapper>()
@@ -183,15 +220,44 @@ impl Api {
         Ok(body.providers)
     }
 
+    pub async fn get_provider_proxy<S: AsRef<str>>(
+        &self,
+        provider: S,
+        name: S,
+    ) -> Result<NodeDetail> {
+        let url = self.api.join(&format!(
+            "/providers/proxies/{}/{}",
+            provider.as_ref(),
+            name.as_ref()
+        ))?;
+        let resp = self
+            .send(self.client.get(url))
+            .await
+            .context("Fail to send `GET /providers/proxies/<provider>/<name>`")?;
+
+        let body = self
+            .check_status(resp)
+            .await
+            .context("Fail to request `GET /providers/proxies/<provider>/<name>`")?
+            .json::<NodeDetail>()
+            .await
+            .context("Fail to parse response of `GET /providers/proxies/<provider>/<name>`")?;
+
+        Ok(body)
+    }
+
     pub async fn health_check_provider<S: AsRef<str>>(&self, name: S) -> Result<()> {
         let resp = self
-            .client
-            .get(self.api.join(&format!("/providers/proxies/{}/healthcheck", name.as_ref()))?)
-            .send()
+            .send(
+                self.client.get(
+                    self.api.join(&format!("/providers/proxies/{}/healthcheck", name.as_ref()))?,
+                ),
+            )
             .await
             .context("Fail to send `GET /providers/proxies/<name>/healthcheck` request")?;
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `GET /providers/proxies/<name>/healthcheck`")?
             .bytes()
@@ -203,13 +269,12 @@ impl Api {
 
     pub async fn update_provider<S: AsRef<str>>(&self, name: S) -> Result<()> {
         let resp = self
-            .client
-            .put(self.api.join(&format!("/providers/proxies/{}", name.as_ref()))?)
-            .send()
+            .send(self.client.put(self.api.join(&format!("/providers/proxies/{}", name.as_ref()))?))
             .await
             .context("Fail to send `PUT /providers/proxies/<name>`")?;
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `PUT /providers/proxies/<name>`")?
             .bytes()
@@ -226,13 +291,12 @@ impl Api {
         }
 
         let resp = self
-            .client
-            .get(self.api.join("/rules")?)
-            .send()
+            .send(self.client.get(self.api.join("/rules")?))
             .await
             .context("Fail to send `GET /rules`")?;
 
-        let body = Self::check_status(resp)
+        let body = self
+            .check_status(resp)
             .await
             .context("Fail to request `GET /rules`")?
             .json::<Wrapper>()
@@ -244,14 +308,12 @@ impl Api {
 
     pub async fn update_rules_disabled_state(&self, body: IndexMap<usize, bool>) -> Result<()> {
         let resp = self
-            .client
-            .patch(self.api.join("/rules/disable")?)
-            .json(&body)
-            .send()
+            .send(self.client.patch(self.api.join("/rules/disable")?).json(&body))
             .await
             .context("Fail to send `PATCH /rules/disable` request")?;
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `PATCH /rules/disable`")?
             .bytes()
@@ -268,13 +330,12 @@ impl Api {
         }
 
         let resp = self
-            .client
-            .get(self.api.join("/providers/rules")?)
-            .send()
+            .send(self.client.get(self.api.join("/providers/rules")?))
             .await
             .context("Fail to send `GET /providers/rules`")?;
 
-        let body = Self::check_status(resp)
+        let body = self
+            .check_status(resp)
             .await
             .context("Fail to request `GET /providers/rules`")?
             .json::<Wrapper>()
@@ -286,13 +347,12 @@ impl Api {
 
     pub async fn update_rule_provider<S: AsRef<str>>(&self, name: S) -> Result<()> {
         let resp = self
-            .client
-            .put(self.api.join(&format!("/providers/rules/{}", name.as_ref()))?)
-            .send()
+            .send(self.client.put(self.api.join(&format!("/providers/rules/{}", name.as_ref()))?))
             .await
             .context("Fail to send `PUT /providers/rules/<name>` request")?;
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `PUT /providers/rules/<name>`")?
             .bytes()
@@ -303,34 +363,54 @@ impl Api {
     }
 
     pub async fn get_core_config(&self) -> Result<CoreConfig> {
+        self.get_core_config_with_progress(&AtomicUsize::new(0)).await
+    }
+
+    /// Same as [`Self::get_core_config`], the body is read in chunks counted into `received`.
+    /// Parsing huge configs takes a while, so it runs on a blocking worker thread.
+    pub async fn get_core_config_with_progress(
+        &self,
+        received: &AtomicUsize,
+    ) -> Result<CoreConfig> {
         let resp = self
-            .client
-            .get(self.api.join("/configs")?)
-            .send()
+            .send(self.client.get(self.api.join("/configs")?))
             .await
             .context("Fail to send `GET /configs`")?;
 
-        let body = Self::check_status(resp)
-            .await
-            .context("Fail to request `GET /configs`")?
-            .json::<CoreConfig>()
-            .await
-            .context("Fail to parse response of `GET /configs`")?;
+        let mut resp = self.check_status(resp).await.context("Fail to request `GET /configs`")?;
+        let mut body = Vec::with_capacity(resp.content_length().unwrap_or_default() as usize);
+        while let Some(chunk) =
+            resp.chunk().await.context("Fail to read response of `GET /configs`")?
+        {
+            body.extend_from_slice(&chunk);
+            received.store(body.len(), Ordering::Relaxed);
+        }
 
-        Ok(body)
+        tokio::task::spawn_blocking(move || serde_json::from_slice::<CoreConfig>(&body))
+            .await
+            .context("Fail to join parser of `GET /configs`")?
+            .context("Fail to parse response of `GET /configs`")
     }
 
     pub async fn update_core_config(&self, body: Vec<u8>) -> Result<()> {
         let resp = self
-            .client
-            .patch(self.api.join("/configs")?)
-            .body(body)
-            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
-            .send()
+            .send(
+                self.client
+                    .patch(self.api.join("/configs")?)
+                    .body(body)
+                    .header(CONTENT_TYPE, HeaderValue::from_static("application/json")),
+            )
             .await
             .context("Fail to send `PATCH /configs` request")?;
+        if resp.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            bail!(
+                "The controller rejected the config as too large (413), submit only the changed \
+                 fields instead"
+            );
+        }
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `PATCH /configs`")?
             .bytes()
@@ -343,16 +423,18 @@ impl Api {
     pub async fn reload_config(&self) -> Result<()> {
         let body = r#"{"path":"","payload":""}"#;
         let resp = self
-            .client
-            .put(self.api.join("/configs")?)
-            .body(body)
-            .query(&[("force", "true")])
-            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
-            .send()
+            .send(
+                self.client
+                    .put(self.api.join("/configs")?)
+                    .body(body)
+                    .query(&[("force", "true")])
+                    .header(CONTENT_TYPE, HeaderValue::from_static("application/json")),
+            )
             .await
             .context("Fail to send `PUT /configs` request")?;
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `PUT /configs`")?
             .bytes()
@@ -364,13 +446,12 @@ impl Api {
 
     pub async fn restart(&self) -> Result<()> {
         let resp = self
-            .client
-            .post(self.api.join("/restart")?)
-            .send()
+            .send(self.client.post(self.api.join("/restart")?))
             .await
             .context("Fail to send `POST /restart` request")?;
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `POST /restart`")?
             .bytes()
@@ -382,13 +463,12 @@ impl Api {
 
     pub async fn upgrade_core(&self) -> Result<()> {
         let resp = self
-            .client
-            .post(self.api.join("/upgrade")?)
-            .send()
+            .send(self.client.post(self.api.join("/upgrade")?))
             .await
             .context("Fail to send `POST /upgrade` request")?;
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `POST /upgrade`")?
             .bytes()
@@ -398,15 +478,32 @@ impl Api {
         Ok(())
     }
 
+    /// Download the latest web dashboard bundle into the core's `external-ui` directory.
+    pub async fn upgrade_ui(&self) -> Result<()> {
+        let resp = self
+            .send(self.client.post(self.api.join("/upgrade/ui")?))
+            .await
+            .context("Fail to send `POST /upgrade/ui` request")?;
+
+        let _ = self
+            .check_status(resp)
+            .await
+            .context("Fail to request `POST /upgrade/ui`")?
+            .bytes()
+            .await
+            .context("Fail to read response of `POST /upgrade/ui`")?;
+
+        Ok(())
+    }
+
     pub async fn flush_fake_ip_cache(&self) -> Result<()> {
         let resp = self
-            .client
-            .post(self.api.join("/cache/fakeip/flush")?)
-            .send()
+            .send(self.client.post(self.api.join("/cache/fakeip/flush")?))
             .await
             .context("Fail to send `POST /cache/fakeip/flush` request")?;
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `POST /cache/fakeip/flush`")?
             .bytes()
@@ -418,13 +515,12 @@ impl Api {
 
     pub async fn flush_dns_cache(&self) -> Result<()> {
         let resp = self
-            .client
-            .post(self.api.join("/cache/dns/flush")?)
-            .send()
+            .send(self.client.post(self.api.join("/cache/dns/flush")?))
             .await
             .context("Fail to send `POST /cache/dns/flush` request")?;
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `POST /cache/dns/flush`")?
             .bytes()
@@ -436,13 +532,12 @@ impl Api {
 
     pub async fn update_geo(&self) -> Result<()> {
         let resp = self
-            .client
-            .post(self.api.join("/configs/geo")?)
-            .send()
+            .send(self.client.post(self.api.join("/configs/geo")?))
             .await
             .context("Fail to send `POST /configs/geo` request")?;
 
-        let _ = Self::check_status(resp)
+        let _ = self
+            .check_status(resp)
             .await
             .context("Fail to request `POST /configs/geo`")?
             .bytes()
@@ -454,14 +549,12 @@ impl Api {
 
     pub async fn query_dns(&self, req: &DnsQueryRequest) -> Result<DnsQueryResponse> {
         let resp = self
-            .client
-            .get(self.api.join("/dns/query")?)
-            .query(req)
-            .send()
+            .send(self.client.get(self.api.join("/dns/query")?).query(req))
             .await
             .context("Fail to send `GET /dns/query`")?;
 
-        let body = Self::check_status(resp)
+        let body = self
+            .check_status(resp)
             .await
             .context("Fail to request `GET /dns/query`")?
             .json::<DnsQueryResponse>()
@@ -0,0 +1,83 @@
+use std::net::IpAddr;
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use reqwest::header::HeaderValue;
+use reqwest::{Client, Proxy, header};
+
+use super::{Api, USER_AGENT};
+use crate::config::MihomoApiEndpoint;
+
+/// "What is my IP" lookups sent through a proxy port of the core.
+#[derive(Debug)]
+pub struct ExitIpApi {
+    client: Client,
+}
+
+impl ExitIpApi {
+    pub fn new(proxy: &str, timeout: Duration) -> Result<Self> {
+        let default_headers =
+            [(header::USER_AGENT, HeaderValue::from_static(USER_AGENT))].into_iter().collect();
+        let proxy =
+            Proxy::all(proxy).with_context(|| format!("Invalid exit IP proxy `{proxy}`"))?;
+        let client = Client::builder()
+            .default_headers(default_headers)
+            .proxy(proxy)
+            .timeout(timeout)
+            // keeps the tunnel open after the lookup, so it can be found in the connections
+            .pool_idle_timeout(timeout)
+            .build()
+            .context("Fail to build exit IP client")?;
+        Ok(Self { client })
+    }
+
+    /// The IP address the service at `url` sees the request coming from.
+    pub async fn lookup(&self, url: &str) -> Result<IpAddr> {
+        let body = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .with_context(|| format!("Fail to request `{url}`"))?
+            .error_for_status()?
+            .text()
+            .await?;
+        parse_ip(&body).ok_or_else(|| anyhow!("`{url}` didn't answer with a plain-text IP"))
+    }
+}
+
+impl Api {
+    /// Proxy URL of the core's `mixed-port`, or `port` otherwise, on the controller host.
+    pub async fn local_proxy_url(&self) -> Result<Option<String>> {
+        let config = self.get_core_config().await?;
+        let port = ["mixed-port", "port"]
+            .iter()
+            .filter_map(|key| config.get(key).and_then(|v| v.as_u64()))
+            .find(|port| *port > 0);
+        let host = match &self.endpoint {
+            MihomoApiEndpoint::Http(url) => url.host_str().unwrap_or("127.0.0.1"),
+            MihomoApiEndpoint::UnixSocket(_) | MihomoApiEndpoint::WindowsNamedPipe(_) => {
+                "127.0.0.1"
+            }
+        };
+        Ok(port.map(|port| format!("http://{host}:{port}")))
+    }
+}
+
+/// The first word of `body` as an IP, services like `api.ipify.org` answer with the IP alone.
+fn parse_ip(body: &str) -> Option<IpAddr> {
+    body.split_whitespace().next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_plain_text_ip() {
+        assert_eq!(parse_ip("203.0.113.7\n"), Some(IpAddr::from([203, 0, 113, 7])));
+        assert_eq!(parse_ip(" 2001:db8::1 "), "2001:db8::1".parse().ok());
+        assert_eq!(parse_ip(r#"{"ip":"203.0.113.7"}"#), None);
+        assert_eq!(parse_ip(""), None);
+    }
+}
@@ -89,7 +89,7 @@ async fn test_test_proxy() {
     init_logger();
     let api = init_api();
     let delay = api
-        .test_proxy("美国-洛杉矶-自建", "https://www.gstatic.com/generate_204", 5000)
+        .test_proxy("美国-洛杉矶-自建", "https://www.gstatic.com/generate_204", 5000, "")
         .await
         .unwrap();
     debug!("delay: {delay}");
@@ -99,8 +99,10 @@ async fn test_test_proxy() {
 async fn test_test_proxy_group() {
     init_logger();
     let api = init_api();
-    let delay =
-        api.test_proxy_group("新加坡", "https://www.gstatic.com/generate_204", 5000).await.unwrap();
+    let delay = api
+        .test_proxy_group("新加坡", "https://www.gstatic.com/generate_204", 5000, "")
+        .await
+        .unwrap();
     debug!("delay: {delay:?}");
 }
 
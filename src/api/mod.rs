@@ -1,31 +1,83 @@
+use std::fmt;
+use std::sync::Arc;
+
 use anyhow::{Context, Result, anyhow};
 use reqwest::header::{HeaderMap, HeaderValue};
-use reqwest::{Client, header};
+use reqwest::{Client, Request, RequestBuilder, Response, StatusCode, header};
 use tracing::debug;
 use url::Url;
 
 use crate::config::{Config, MihomoApiEndpoint};
 
+mod call;
+mod curl;
 mod endpoints;
+mod exit_ip;
 mod github;
 #[cfg(all(test, feature = "local-api-test"))]
 mod local_api_tests;
-mod stream;
+pub mod stream;
 #[cfg(test)]
 mod test_support;
 #[cfg(test)]
 mod tests;
 
+pub use call::{ApiCall, RequestBody};
+pub use exit_ip::ExitIpApi;
 pub use github::GithubApi;
 
 const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
+/// Marks errors of requests rejected with `401 Unauthorized`, see [`Api::is_unauthorized`].
+#[derive(Debug)]
+pub struct Unauthorized;
+
+impl fmt::Display for Unauthorized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unauthorized, `mihomo-secret` is missing or wrong")
+    }
+}
+
+/// What happens while talking to the controller, reported to the [`ApiObserver`] of an [`Api`].
+#[derive(Debug)]
+pub enum ApiEvent<'a> {
+    /// A request is about to be sent
+    Request(&'a Request),
+    /// The controller accepted (`true`) or rejected (`false`) the secret
+    Authorized(bool),
+    /// A stream stopped receiving messages and is reconnecting
+    Stalled(&'a str),
+    /// A stream received a text message of `bytes` length
+    Message { path: &'a str, bytes: usize },
+}
+
+/// Callback receiving the [`ApiEvent`]s of an [`Api`], see [`Api::with_observer`].
+pub type ApiObserver = Arc<dyn Fn(ApiEvent) + Send + Sync>;
+
+#[derive(Clone, Default)]
+struct Observer(Option<ApiObserver>);
+
+impl Observer {
+    fn report(&self, event: ApiEvent) {
+        if let Some(observer) = &self.0 {
+            observer(event);
+        }
+    }
+}
+
+impl fmt::Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Observer").field(&self.0.is_some()).finish()
+    }
+}
+
 #[derive(Debug)]
 pub struct Api {
     api: Url,
     endpoint: MihomoApiEndpoint,
     bearer_token: Option<String>,
     client: Client,
+    observer: Observer,
 }
 
 impl Api {
@@ -48,7 +100,18 @@ impl Api {
         };
         let client = Self::create_client(&endpoint, &bearer_token)?;
 
-        Ok(Self { api, endpoint, bearer_token, client })
+        Ok(Self { api, endpoint, bearer_token, client, observer: Observer::default() })
+    }
+
+    /// Report the requests, authorization results and stream health of this client to `observer`.
+    pub fn with_observer(mut self, observer: ApiObserver) -> Self {
+        self.observer = Observer(Some(observer));
+        self
+    }
+
+    /// Returns `true` if the request failed because the controller rejected the secret.
+    pub fn is_unauthorized(err: &anyhow::Error) -> bool {
+        err.downcast_ref::<Unauthorized>().is_some()
     }
 
     /// Create default headers for the API client.
@@ -99,9 +162,18 @@ impl Api {
         Ok(client)
     }
 
-    async fn check_status(resp: reqwest::Response) -> Result<reqwest::Response> {
+    /// Send a request built from `self.client`, every request goes through here to be reported as
+    /// [`ApiEvent::Request`].
+    async fn send(&self, builder: RequestBuilder) -> reqwest::Result<Response> {
+        let req = builder.build()?;
+        self.observer.report(ApiEvent::Request(&req));
+        self.client.execute(req).await
+    }
+
+    async fn check_status(&self, resp: reqwest::Response) -> Result<reqwest::Response> {
         let status = resp.status();
         if status.is_success() {
+            self.observer.report(ApiEvent::Authorized(true));
             return Ok(resp);
         }
 
@@ -116,6 +188,10 @@ impl Api {
             }
         }
 
+        if status == StatusCode::UNAUTHORIZED {
+            self.observer.report(ApiEvent::Authorized(false));
+            return Err(anyhow!(msg).context(Unauthorized));
+        }
         Err(anyhow!(msg))
     }
 }
@@ -3,11 +3,11 @@ use std::pin::Pin;
 use std::time::Duration;
 
 use anyhow::{Context, Result, anyhow};
-use futures_util::{Stream, StreamExt, stream};
-use reqwest::header;
+use futures_util::{Sink, SinkExt, Stream, StreamExt, stream};
 use reqwest::header::HeaderValue;
+use reqwest::{StatusCode, header};
 use serde::de::DeserializeOwned;
-use tokio::time::sleep;
+use tokio::time::{Instant, sleep, timeout};
 use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::error::Error as WebSocketError;
@@ -15,22 +15,64 @@ use tokio_tungstenite::tungstenite::handshake::client::Request;
 use tokio_tungstenite::{client_async, connect_async};
 use tracing::{debug, warn};
 
-use super::{Api, USER_AGENT};
+use super::{Api, ApiEvent, Observer, USER_AGENT, Unauthorized};
 use crate::config::MihomoApiEndpoint;
 use crate::models::{ConnectionsWrapper, Log, LogLevel, Memory, Traffic};
 
 const DEFAULT_WS_RETRY_INTERVAL: Duration = Duration::from_secs(3);
 
-type WebSocketMessageStream =
-    Pin<Box<dyn Stream<Item = std::result::Result<Message, WebSocketError>> + Send>>;
+pub const LOGS_PATH: &str = "/logs";
+pub const CONNECTIONS_PATH: &str = "/connections";
+pub const MEMORY_PATH: &str = "/memory";
+pub const TRAFFIC_PATH: &str = "/traffic";
+
+/// Some NATs/proxies silently drop idle websocket connections, so the peer is pinged when a
+/// stream is quiet and reconnected when nothing (not even a pong) arrives in time.
+#[derive(Debug, Clone, Copy)]
+pub struct KeepAlive {
+    pub ping_interval: Duration,
+    pub stall_timeout: Duration,
+}
+
+impl Default for KeepAlive {
+    fn default() -> Self {
+        Self { ping_interval: Duration::from_secs(10), stall_timeout: Duration::from_secs(30) }
+    }
+}
+
+trait WebSocketConn:
+    Stream<Item = std::result::Result<Message, WebSocketError>>
+    + Sink<Message, Error = WebSocketError>
+    + Send
+{
+}
+
+impl<T> WebSocketConn for T where
+    T: Stream<Item = std::result::Result<Message, WebSocketError>>
+        + Sink<Message, Error = WebSocketError>
+        + Send
+{
+}
+
+type WebSocketMessageStream = Pin<Box<dyn WebSocketConn>>;
 
 async fn connect_websocket(
     endpoint: &MihomoApiEndpoint,
     request: Request,
+    observer: &Observer,
 ) -> Result<WebSocketMessageStream> {
     match endpoint {
         MihomoApiEndpoint::Http(_) => {
-            let (ws, _) = connect_async(request).await.context("Fail to connect websocket")?;
+            let (ws, _) = match connect_async(request).await {
+                Ok(ws) => ws,
+                Err(WebSocketError::Http(resp)) if resp.status() == StatusCode::UNAUTHORIZED => {
+                    observer.report(ApiEvent::Authorized(false));
+                    return Err(anyhow!("Websocket handshake rejected ({})", resp.status())
+                        .context(Unauthorized));
+                }
+                Err(e) => return Err(e).context("Fail to connect websocket"),
+            };
+            observer.report(ApiEvent::Authorized(true));
             Ok(Box::pin(ws))
         }
         MihomoApiEndpoint::UnixSocket(path) => {
@@ -98,26 +140,64 @@ impl Api {
         query_params: Option<HashMap<String, String>>,
         retry_interval: Duration,
     ) -> Result<impl Stream<Item = Result<T>>>
+    where
+        T: DeserializeOwned,
+    {
+        self.create_stream_with_keepalive(path, query_params, retry_interval, KeepAlive::default())
+    }
+
+    /// Reconnecting stream of `path`, its health is reported as [`ApiEvent::Stalled`] and
+    /// [`ApiEvent::Message`] of `path`.
+    pub fn create_stream_with_keepalive<T>(
+        &self,
+        path: &str,
+        query_params: Option<HashMap<String, String>>,
+        retry_interval: Duration,
+        keepalive: KeepAlive,
+    ) -> Result<impl Stream<Item = Result<T>>>
     where
         T: DeserializeOwned,
     {
         struct ReconnectState {
+            path: String,
             endpoint: MihomoApiEndpoint,
             request: Request,
             retry_interval: Duration,
+            keepalive: KeepAlive,
             ws: Option<WebSocketMessageStream>,
+            last_seen: Instant,
+            observer: Observer,
+        }
+
+        impl ReconnectState {
+            async fn disconnected(&mut self) {
+                self.observer.report(ApiEvent::Stalled(&self.path));
+                self.ws = None;
+                sleep(self.retry_interval).await;
+            }
         }
 
         let request = self.build_ws_request(path, query_params)?;
-        let state =
-            ReconnectState { endpoint: self.endpoint.clone(), request, retry_interval, ws: None };
+        let state = ReconnectState {
+            path: path.to_string(),
+            endpoint: self.endpoint.clone(),
+            request,
+            retry_interval,
+            keepalive,
+            ws: None,
+            last_seen: Instant::now(),
+            observer: self.observer.clone(),
+        };
 
         Ok(stream::unfold(state, |mut state| async move {
             loop {
                 if state.ws.is_none() {
-                    match connect_websocket(&state.endpoint, state.request.clone()).await {
+                    match connect_websocket(&state.endpoint, state.request.clone(), &state.observer)
+                        .await
+                    {
                         Ok(ws) => {
                             state.ws = Some(ws);
+                            state.last_seen = Instant::now();
                         }
                         Err(e) => {
                             warn!(
@@ -125,15 +205,42 @@ impl Api {
                                 retry_interval = ?state.retry_interval,
                                 "Failed to connect websocket stream, retrying"
                             );
-                            sleep(state.retry_interval).await;
+                            state.disconnected().await;
                             continue;
                         }
                     }
                 }
 
                 let ws = state.ws.as_mut().unwrap();
-                match ws.next().await {
+                let Ok(next) = timeout(state.keepalive.ping_interval, ws.next()).await else {
+                    if state.last_seen.elapsed() >= state.keepalive.stall_timeout {
+                        warn!(
+                            path = state.path,
+                            stall_timeout = ?state.keepalive.stall_timeout,
+                            "Websocket stream stalled, reconnecting"
+                        );
+                        state.observer.report(ApiEvent::Stalled(&state.path));
+                        state.ws = None;
+                        continue;
+                    }
+                    let ping = timeout(
+                        state.keepalive.ping_interval,
+                        ws.send(Message::Ping(Default::default())),
+                    );
+                    if let Ok(Err(e)) = ping.await {
+                        warn!(error = ?e, "Failed to ping websocket stream, retrying");
+                        state.disconnected().await;
+                    }
+                    continue;
+                };
+                if let Some(Ok(_)) = next {
+                    state.last_seen = Instant::now();
+                }
+
+                match next {
                     Some(Ok(Message::Text(txt))) => {
+                        let event = ApiEvent::Message { path: &state.path, bytes: txt.len() };
+                        state.observer.report(event);
                         let item = serde_json::from_str::<T>(&txt).map_err(anyhow::Error::from);
                         return Some((item, state));
                     }
@@ -143,8 +250,7 @@ impl Api {
                             retry_interval = ?state.retry_interval,
                             "Websocket stream closed by peer, retrying"
                         );
-                        state.ws = None;
-                        sleep(state.retry_interval).await;
+                        state.disconnected().await;
                     }
                     Some(Ok(_)) => {
                         continue;
@@ -155,16 +261,14 @@ impl Api {
                             retry_interval = ?state.retry_interval,
                             "Websocket stream disconnected, retrying"
                         );
-                        state.ws = None;
-                        sleep(state.retry_interval).await;
+                        state.disconnected().await;
                     }
                     None => {
                         warn!(
                             retry_interval = ?state.retry_interval,
                             "Websocket stream closed, retrying"
                         );
-                        state.ws = None;
-                        sleep(state.retry_interval).await;
+                        state.disconnected().await;
                     }
                 }
             }
@@ -176,26 +280,28 @@ impl Api {
         level: Option<LogLevel>,
     ) -> Result<impl Stream<Item = Result<Log>>> {
         let params = level.map(|l| HashMap::from([("level".to_string(), l.to_string())]));
-        self.create_stream::<Log>("/logs", params, DEFAULT_WS_RETRY_INTERVAL)
+        self.create_stream::<Log>(LOGS_PATH, params, DEFAULT_WS_RETRY_INTERVAL)
     }
 
     pub async fn stream_connections(
         &self,
     ) -> Result<impl Stream<Item = Result<ConnectionsWrapper>>> {
-        self.create_stream::<ConnectionsWrapper>("/connections", None, DEFAULT_WS_RETRY_INTERVAL)
+        self.create_stream::<ConnectionsWrapper>(CONNECTIONS_PATH, None, DEFAULT_WS_RETRY_INTERVAL)
     }
 
     pub async fn stream_memory(&self) -> Result<impl Stream<Item = Result<Memory>>> {
-        self.create_stream::<Memory>("/memory", None, DEFAULT_WS_RETRY_INTERVAL)
+        self.create_stream::<Memory>(MEMORY_PATH, None, DEFAULT_WS_RETRY_INTERVAL)
     }
 
     pub async fn stream_traffic(&self) -> Result<impl Stream<Item = Result<Traffic>>> {
-        self.create_stream::<Traffic>("/traffic", None, DEFAULT_WS_RETRY_INTERVAL)
+        self.create_stream::<Traffic>(TRAFFIC_PATH, None, DEFAULT_WS_RETRY_INTERVAL)
     }
 }
 
 #[cfg(test)]
 mod reconnecting_stream_tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::time::Duration;
 
     use futures_util::{SinkExt, StreamExt, pin_mut};
@@ -282,6 +388,60 @@ mod reconnecting_stream_tests {
         server.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn create_stream_reconnects_after_stall() {
+        init_logger();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let server = tokio::spawn({
+            let accepted = Arc::clone(&accepted);
+            async move {
+                let mut stalled = vec![];
+                for payload in TEST_CASES {
+                    let (tcp, _) = listener.accept().await.unwrap();
+                    accepted.fetch_add(1, Ordering::Relaxed);
+                    let mut ws = accept_async(tcp).await.unwrap();
+                    ws.send(log_message(payload)).await.unwrap();
+                    // keep the connection open without reading, pings are never answered
+                    stalled.push(ws);
+                }
+            }
+        });
+
+        let keepalive = KeepAlive {
+            ping_interval: Duration::from_millis(20),
+            stall_timeout: Duration::from_millis(100),
+        };
+        let stalled = Arc::new(AtomicUsize::new(0));
+        let observer = {
+            let stalled = Arc::clone(&stalled);
+            move |event: ApiEvent| {
+                if let ApiEvent::Stalled("/stall") = event {
+                    stalled.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        };
+        let api =
+            test_api(MihomoApiEndpoint::Http(format!("http://{addr}").parse().unwrap()), None)
+                .with_observer(Arc::new(observer));
+        let stream = api
+            .create_stream_with_keepalive::<Log>("/stall", None, RETRY_INTERVAL, keepalive)
+            .unwrap();
+        pin_mut!(stream);
+
+        for (i, payload) in TEST_CASES.iter().enumerate() {
+            let next = timeout(NEXT_TIMEOUT, stream.next()).await.unwrap();
+            assert_eq!(next.unwrap().unwrap().payload, *payload);
+            // every message after the first one comes from a new connection, opened once the
+            // previous one was reported stalled
+            assert!(stalled.load(Ordering::Relaxed) >= i, "stalled before message {i}");
+            assert!(accepted.load(Ordering::Relaxed) > i);
+        }
+        server.await.unwrap();
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn create_stream_uses_unix_socket() {
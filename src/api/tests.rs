@@ -27,6 +27,26 @@ where
     stream.write_all(response.as_bytes()).await.unwrap();
 }
 
+#[tokio::test]
+async fn rejected_secret_is_unauthorized() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut chunk = [0_u8; 1024];
+        let _ = socket.read(&mut chunk).await.unwrap();
+        let response =
+            "HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+        socket.write_all(response.as_bytes()).await.unwrap();
+    });
+
+    let err = test_api(MihomoApiEndpoint::Http(url), None).get_version().await.unwrap_err();
+    assert!(Api::is_unauthorized(&err), "{err:#}");
+    assert!(!Api::is_unauthorized(&anyhow!("connection refused")));
+    server.await.unwrap();
+}
+
 #[cfg(unix)]
 mod unix_socket {
     use tokio::net::UnixListener;
@@ -1,28 +1,44 @@
 use std::path::PathBuf;
 use std::process::Command;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use std::{env, thread};
 
 use anyhow::{Context, Result, anyhow};
 use ratatui::layout::Rect;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
 use crate::action::Action;
 use crate::api::Api;
 use crate::app_message::AppMessage;
 use crate::components::root_component::RootComponent;
-use crate::components::{Component, ComponentId};
-use crate::config::{Config, runtime};
+use crate::components::{Component, ComponentId, api_observer};
+use crate::config::runtime::RuntimeSettings;
+use crate::config::{self, Config, UnfocusedMode, runtime, watcher};
+use crate::store::chart_setting::ChartSetting;
 use crate::store::connections_setting::ConnectionsSetting;
+use crate::store::custom_columns;
+use crate::store::filter_history::FilterHistory;
+use crate::store::geoip::GeoIp;
+use crate::store::labels::Labels;
+use crate::store::layout_setting::LayoutSetting;
 use crate::store::proxy_setting::ProxySetting;
+use crate::store::timeline::{EventKind, Timeline};
 use crate::tui::{Event, Tui};
+use crate::utils::browser;
+use crate::utils::encryption::Encryption;
+use crate::utils::redact::Redactor;
 use crate::version_update;
 use crate::version_update::RestartOutcome;
 
+/// Redraw interval while the terminal window is not focused
+const UNFOCUSED_RENDER_INTERVAL: Duration = Duration::from_secs(1);
+
 pub struct App {
     config: Arc<Config>,
+    config_path: PathBuf,
     runtime_path: PathBuf,
     api: Arc<Api>,
     token: CancellationToken,
@@ -30,15 +46,24 @@ pub struct App {
 
     should_quit: bool,
     should_suspend: bool,
+    /// Whether the terminal window has focus, assumed until it reports otherwise
+    focused: bool,
+    last_render: Instant,
     action_tx: UnboundedSender<Action>,
     action_rx: UnboundedReceiver<Action>,
 }
 
 impl App {
-    pub fn new(config: Config, runtime_path: PathBuf, api: Api) -> Result<Self> {
+    pub fn new(
+        config: Config,
+        config_path: PathBuf,
+        runtime_path: PathBuf,
+        api: Api,
+    ) -> Result<Self> {
         let (action_tx, action_rx) = mpsc::unbounded_channel();
         Ok(Self {
             config: Arc::new(config),
+            config_path,
             runtime_path,
             api: Arc::new(api),
             token: CancellationToken::new(),
@@ -46,6 +71,8 @@ impl App {
 
             should_quit: false,
             should_suspend: false,
+            focused: true,
+            last_render: Instant::now(),
             action_tx,
             action_rx,
         })
@@ -56,18 +83,34 @@ impl App {
         tui.enter()?;
 
         // initialize global settings
-        *ProxySetting::global().write().unwrap() = self.config.proxy_setting.clone();
-        if let Some(connections) = self.config.ui.as_ref().and_then(|ui| ui.connections.as_ref()) {
-            *ConnectionsSetting::global().write().unwrap() = Arc::new(connections.try_into()?);
-        }
+        Self::init_global_settings(&self.config)?;
         // initialize root component
-        self.root.init(Arc::clone(&self.api))?;
+        // handlers first, init creates components eagerly
         self.root.register_action_handler(self.action_tx.clone())?;
         self.root.register_config_handler(Arc::clone(&self.config))?;
+        self.root.init(Arc::clone(&self.api))?;
+        // watch config file for hot-reload
+        if let Err(e) = watcher::spawn(
+            self.config_path.clone(),
+            self.runtime_path.clone(),
+            self.action_tx.clone(),
+            self.token.clone(),
+        ) {
+            warn!(error = ?e, "Failed to watch config file, hot-reload disabled");
+        }
 
         let action_tx = self.action_tx.clone();
         // send initial tab
         action_tx.send(Action::TabSwitch(ComponentId::default()))?;
+        // after the tab switch, so the popup keeps its shortcuts in the footer
+        match runtime::swap_last_version(&self.runtime_path) {
+            Ok(Some(last)) if last != env!("CARGO_PKG_VERSION") => {
+                info!(last, "App upgraded since the last run");
+                action_tx.send(Action::WhatsNew(last))?;
+            }
+            Ok(_) => {}
+            Err(e) => warn!(error = ?e, "Failed to record the app version"),
+        }
         loop {
             self.handle_events(&mut tui).await?;
             self.handle_actions(&mut tui)?;
@@ -95,8 +138,15 @@ impl App {
         match event {
             Event::Quit => action_tx.send(Action::Quit)?,
             Event::Tick => action_tx.send(Action::Tick)?,
+            Event::Render if self.is_render_throttled() => {}
             Event::Render => action_tx.send(Action::Render)?,
             Event::Resize(x, y) => action_tx.send(Action::Resize(x, y))?,
+            Event::FocusGained | Event::FocusLost => {
+                self.focused = matches!(event, Event::FocusGained);
+                action_tx.send(Action::TerminalFocus(self.focused))?;
+                // resume drawing right away instead of waiting for the next slow frame
+                action_tx.send(Action::Render)?;
+            }
             _ => {}
         }
         if let Some(action) = self.root.handle_events(Some(event.clone()))? {
@@ -123,15 +173,17 @@ impl App {
                 }
                 Action::ConnectionsSettingChanged
                 | Action::ConnectionsLayoutChanged
+                | Action::LayoutChanged
+                | Action::ChartSettingChanged
+                | Action::FilterHistoryChanged
                 | Action::ProxySettingChanged => {
-                    if let Err(e) = self.save_runtime_config() {
-                        error!(error = ?e, "Failed to save runtime config");
-                        self.action_tx.send(Action::Error(
-                            AppMessage::from(("Save runtime config", e)).msg_box_size(60, 30),
-                        ))?;
-                    }
+                    self.save_runtime_config();
                 }
+                Action::OpenUrl(ref url) => self.handle_open_url(url)?,
                 Action::SelfUpdate(restart) => self.handle_self_update(tui, restart)?,
+                Action::ConfigFileChanged(ref config) => self.handle_config_file_changed(config)?,
+                Action::ApiReconnect(ref config) => self.handle_api_reconnect(config)?,
+                Action::SaveSecret(ref secret) => self.handle_save_secret(secret)?,
                 _ => {}
             }
             if let Some(action) = self.root.update(action.clone())? {
@@ -141,10 +193,116 @@ impl App {
         Ok(())
     }
 
-    fn save_runtime_config(&self) -> Result<()> {
-        let connections = ConnectionsSetting::snapshot();
-        let proxy_setting = ProxySetting::global().read().unwrap().clone();
-        runtime::save(&self.runtime_path, &connections, &proxy_setting)
+    fn init_global_settings(config: &Config) -> Result<()> {
+        *ProxySetting::global().write().unwrap() = config.proxy_setting.clone();
+        Redactor::configure(&config.redact);
+        Encryption::configure(&config.encryption);
+        // custom columns first, the connections setting refers to them by index
+        custom_columns::register(
+            config.ui.as_ref().map(|ui| ui.custom_columns.as_slice()).unwrap_or_default(),
+        );
+        if let Some(connections) = config.ui.as_ref().and_then(|ui| ui.connections.as_ref()) {
+            *ConnectionsSetting::global().write().unwrap() = Arc::new(connections.try_into()?);
+        }
+        config.ui.as_ref().and_then(|ui| ui.thousands_separator).unwrap_or_default().apply();
+        config.ui.as_ref().and_then(|ui| ui.collation).unwrap_or_default().apply();
+        config.ui.as_ref().and_then(|ui| ui.table).unwrap_or_default().apply();
+        *LayoutSetting::global().write().unwrap() =
+            config.ui.as_ref().and_then(|ui| ui.layout).unwrap_or_default();
+        *ChartSetting::global().write().unwrap() =
+            config.ui.as_ref().and_then(|ui| ui.chart).unwrap_or_default();
+        FilterHistory::global().write().unwrap().configure(
+            &config.ui.as_ref().and_then(|ui| ui.filter_history.clone()).unwrap_or_default(),
+        );
+        Labels::configure(&config.labels);
+        GeoIp::configure(&config.geoip);
+        Ok(())
+    }
+
+    /// Apply settings of the changed config file live. API endpoint changes are only applied after
+    /// the user confirms reconnecting, until then the current connection is kept.
+    fn handle_config_file_changed(&mut self, config: &Arc<Config>) -> Result<()> {
+        info!("Config file changed, applying new settings");
+        Timeline::record(EventKind::Config, "Config file reloaded");
+        let applied = if self.config.same_api(config) {
+            Arc::clone(config)
+        } else {
+            self.action_tx.send(Action::ApiReconnectRequest(Arc::clone(config)))?;
+            let mut next = Config::clone(config);
+            next.mihomo_api = self.config.mihomo_api.clone();
+            next.mihomo_secret = self.config.mihomo_secret.clone();
+            Arc::new(next)
+        };
+        self.apply_config(applied)
+    }
+
+    fn apply_config(&mut self, config: Arc<Config>) -> Result<()> {
+        if let Err(e) = Self::init_global_settings(&config) {
+            error!(error = ?e, "Failed to apply reloaded config");
+            self.action_tx.send(Action::Error(ComponentId::Root, ("Reload config", e).into()))?;
+            return Ok(());
+        }
+        self.config = Arc::clone(&config);
+        self.action_tx.send(Action::ConfigReloaded(config))?;
+        Ok(())
+    }
+
+    fn handle_api_reconnect(&mut self, config: &Arc<Config>) -> Result<()> {
+        info!(endpoint = ?config.mihomo_api, "Reconnecting to mihomo API");
+        let api = match Api::new(config) {
+            Ok(api) => Arc::new(api.with_observer(api_observer())),
+            Err(e) => {
+                error!(error = ?e, "Failed to create API client");
+                self.action_tx
+                    .send(Action::Error(ComponentId::Root, ("Reconnect API", e).into()))?;
+                return Ok(());
+            }
+        };
+        Timeline::record(EventKind::Core, format!("Reconnected to {}", config.mihomo_api));
+        self.api = Arc::clone(&api);
+        self.root.reset_api(api, Arc::clone(config))?;
+        self.apply_config(Arc::clone(config))
+    }
+
+    fn handle_save_secret(&self, secret: &str) -> Result<()> {
+        let action = match config::save_secret(&self.config_path, secret) {
+            Ok(()) => {
+                info!(path = %self.config_path.display(), "Saved mihomo-secret to config file");
+                let msg = format!("Saved to `{}`", self.config_path.display());
+                Action::Info(("Save secret", msg).into())
+            }
+            Err(e) => {
+                error!(error = ?e, "Failed to save mihomo-secret");
+                Action::Error(ComponentId::Root, ("Save secret", e).into())
+            }
+        };
+        self.action_tx.send(action)?;
+        Ok(())
+    }
+
+    /// Ask for the secret once the app is running, used when the controller answered 401 on
+    /// startup.
+    pub fn prompt_secret(&self) -> Result<()> {
+        self.action_tx.send(Action::SecretPrompt)?;
+        Ok(())
+    }
+
+    /// Saved off the UI thread, encrypting the sidecar is slow. Saves run one at a time and read
+    /// the settings when they start, so the last one writes the latest settings.
+    fn save_runtime_config(&self) {
+        static SAVING: Mutex<()> = Mutex::new(());
+        let runtime_path = self.runtime_path.clone();
+        let action_tx = self.action_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let _saving = SAVING.lock().unwrap();
+            if let Err(e) = runtime::save(&runtime_path, &RuntimeSettings::snapshot()) {
+                error!(error = ?e, "Failed to save runtime config");
+                let _ = action_tx.send(Action::Error(
+                    ComponentId::Root,
+                    AppMessage::from(("Save runtime config", e)).msg_box_size(60, 30),
+                ));
+            }
+        });
     }
 
     fn handle_self_update(&mut self, tui: &mut Tui, restart: bool) -> Result<()> {
@@ -189,7 +347,7 @@ impl App {
             }
             Err(e) => {
                 error!(error = ?e, "app self update failed");
-                Action::Error(("Update app", e).into())
+                Action::Error(ComponentId::Root, ("Update app", e).into())
             }
         };
 
@@ -222,12 +380,18 @@ impl App {
                     );
                     let msg =
                         format!("Editor `{}` exited with non-zero status: {}", editor, status);
-                    self.action_tx.send(Action::Error(("Spawning external editor", msg).into()))?;
+                    self.action_tx.send(Action::Error(
+                        ComponentId::Root,
+                        ("Spawning external editor", msg).into(),
+                    ))?;
                 }
             }
             Err(e) => {
                 error!("Failed to spawn editor `{}`: {}", editor, e);
-                self.action_tx.send(Action::Error(("Spawning external editor", e).into()))?;
+                self.action_tx.send(Action::Error(
+                    ComponentId::Root,
+                    ("Spawning external editor", e).into(),
+                ))?;
             }
         }
 
@@ -237,6 +401,21 @@ impl App {
         Ok(())
     }
 
+    fn handle_open_url(&self, url: &str) -> Result<()> {
+        let Some(opener) = browser::resolve_opener(self.config.open_url_command.as_deref()) else {
+            warn!(url, "Opening URLs is disabled or no graphical session detected");
+            let msg = format!("No browser available, open it manually:\n{url}");
+            self.action_tx.send(Action::Info(("Open URL", msg).into()))?;
+            return Ok(());
+        };
+        info!("Opening URL `{}` with `{}`", url, opener);
+        if let Err(e) = browser::open_url(&opener, url) {
+            error!(error = ?e, "Failed to open URL");
+            self.action_tx.send(Action::Error(ComponentId::Root, ("Open URL", e).into()))?;
+        }
+        Ok(())
+    }
+
     fn handle_resize(&mut self, tui: &mut Tui, w: u16, h: u16) -> Result<()> {
         debug!("Resizing to {}x{}", w, h);
         tui.resize(Rect::new(0, 0, w, h))?;
@@ -244,7 +423,15 @@ impl App {
         Ok(())
     }
 
+    /// Frames are skipped while the terminal window is not focused, unless disabled by config.
+    fn is_render_throttled(&self) -> bool {
+        !self.focused
+            && self.config.unfocused != UnfocusedMode::None
+            && self.last_render.elapsed() < UNFOCUSED_RENDER_INTERVAL
+    }
+
     fn render(&mut self, tui: &mut Tui) -> Result<()> {
+        self.last_render = Instant::now();
         tui.draw(|frame| {
             if let Err(err) = self.root.draw(frame, frame.area()) {
                 error!(error = ?err, "Failed to draw ROOT component");
@@ -0,0 +1,66 @@
+//! `--check-config`: validate the deployment without launching the TUI.
+
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+
+use crate::api::Api;
+use crate::config;
+use crate::config::runtime;
+
+/// Prints a report of each check to stdout, returns an error if any of them failed.
+pub async fn run(config_path: Option<PathBuf>, ping: bool) -> Result<()> {
+    let config_path = config_path.unwrap_or_else(config::get_config_path);
+    let mut failed = 0;
+
+    // `config::load` creates the default file when missing, a check must not touch the disk
+    if !config_path.is_file() {
+        report("config", &config_path.display().to_string(), Err("file does not exist".into()));
+        bail!("config check failed");
+    }
+    let mut loaded = match config::load(Some(config_path.clone())) {
+        Ok(loaded) => {
+            report("config", &config_path.display().to_string(), Ok("ok".into()));
+            loaded
+        }
+        Err(e) => {
+            report("config", &config_path.display().to_string(), Err(format!("{e:#}")));
+            bail!("config check failed");
+        }
+    };
+
+    let runtime_path = loaded.runtime_path.clone();
+    let runtime_status = if !runtime_path.exists() {
+        Ok("absent, skipped".into())
+    } else {
+        runtime::load_and_apply(&mut loaded.config, &runtime_path)
+            .map(|_| "ok".into())
+            .map_err(|e| format!("{e:#}"))
+    };
+    failed += usize::from(runtime_status.is_err());
+    report("runtime", &runtime_path.display().to_string(), runtime_status);
+
+    if ping {
+        let target = loaded.mihomo_api.to_string();
+        let status = match Api::new(&loaded) {
+            Ok(api) => {
+                api.get_version().await.map(|v| format!("ok ({v})")).map_err(|e| format!("{e:#}"))
+            }
+            Err(e) => Err(format!("{e:#}")),
+        };
+        failed += usize::from(status.is_err());
+        report("controller", &target, status);
+    }
+
+    if failed > 0 {
+        bail!("{failed} check(s) failed");
+    }
+    Ok(())
+}
+
+fn report(name: &str, target: &str, status: Result<String, String>) {
+    match status {
+        Ok(msg) => println!("[ OK ] {name:<10} {target}: {msg}"),
+        Err(msg) => println!("[FAIL] {name:<10} {target}: {msg}"),
+    }
+}
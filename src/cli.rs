@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
-use clap::{CommandFactory, FromArgMatches, Parser, ValueHint};
+use clap::{Command, CommandFactory, FromArgMatches, Parser, Subcommand, ValueHint};
+use clap_complete::Shell;
 
 use crate::config::get_config_path;
 use crate::config::runtime::runtime_path_for;
@@ -15,6 +16,9 @@ use crate::config::runtime::runtime_path_for;
     about
 )]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+
     /// Path to config file, leave empty to use default path
     #[arg(short, long, value_name = "CONFIG_FILE")]
     pub config: Option<PathBuf>,
@@ -22,9 +26,53 @@ pub struct Args {
     /// Self-update before starting
     #[arg(long)]
     pub update: bool,
+
+    /// Validate the config and runtime files, print a report and exit
+    #[arg(long)]
+    pub check_config: bool,
+
+    /// With `--check-config`, also check that the mihomo controller is reachable
+    #[arg(long, requires = "check_config")]
+    pub ping: bool,
+
+    /// Print the default config, with every option documented, and exit
+    #[arg(long, conflicts_with_all = ["update", "check_config"])]
+    pub print_default_config: bool,
+
+    /// Print the JSON schema of the core config used by the config tab and exit
+    #[arg(long, conflicts_with_all = ["update", "check_config", "print_default_config"])]
+    pub print_core_config_schema: bool,
+
+    /// Export the recorded traffic history (see `history` in the config) to FILE and exit, as
+    /// JSON if FILE ends with `.json`, CSV otherwise
+    #[arg(
+        long,
+        value_name = "FILE",
+        value_hint = ValueHint::FilePath,
+        conflicts_with_all = [
+            "update", "check_config", "print_default_config", "print_core_config_schema"
+        ]
+    )]
+    pub export_history: Option<PathBuf>,
+
+    /// Start without websocket streams and background refreshes, only loading on demand, to
+    /// diagnose crashes or hangs and on very constrained devices
+    #[arg(long)]
+    pub safe_mode: bool,
+
+    /// Log filter overriding `log-level` and the log level environment variables, e.g.
+    /// `info,[component{id=Rules}]=trace` to debug a single component
+    #[arg(long, value_name = "FILTER")]
+    pub log_filter: Option<String>,
 }
 
-pub fn parse_args() -> anyhow::Result<Args> {
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    /// Print the completion script for the shell and exit
+    Completions { shell: Shell },
+}
+
+fn command() -> Command {
     // Enhance the help message for the config argument
     let def = get_config_path();
     let runtime = runtime_path_for(&def);
@@ -35,8 +83,27 @@ pub fn parse_args() -> anyhow::Result<Args> {
         runtime.display()
     );
 
-    let cmd = Args::command()
-        .mut_arg("config", |a| a.help(help).value_hint(ValueHint::FilePath).next_line_help(true));
+    Args::command()
+        .mut_arg("config", |a| a.help(help).value_hint(ValueHint::FilePath).next_line_help(true))
+}
+
+pub fn parse_args() -> anyhow::Result<Args> {
+    Ok(Args::from_arg_matches(&command().get_matches())?)
+}
+
+/// Write the completion script of `shell` to stdout.
+pub fn print_completions(shell: Shell) {
+    let mut cmd = command();
+    let name = cmd.get_name().to_owned();
+    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(Args::from_arg_matches(&cmd.get_matches())?)
+    #[test]
+    fn command_is_well_formed() {
+        command().debug_assert();
+    }
 }
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::widgets::{
+    Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap,
+};
+
+use crate::action::Action;
+use crate::api::Api;
+use crate::api::{ApiCall, RequestBody};
+use crate::app_message::AppMessage;
+use crate::components::{Component, ComponentId};
+use crate::store::api_log::ApiLog;
+use crate::utils::byte_size::human_bytes;
+use crate::utils::clipboard;
+use crate::utils::symbols::arrow;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::time::format_time_from_now;
+use crate::widgets::scrollable_navigator::ScrollableNavigator;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+/// Debug popup listing recent [`ApiLog`] requests, the selected one can be copied as a curl
+/// command.
+#[derive(Debug, Default)]
+pub struct ApiCallsComponent {
+    api: Option<Arc<Api>>,
+    calls: Vec<ApiCall>,
+    navigator: ScrollableNavigator,
+    table_state: TableState,
+}
+
+impl ApiCallsComponent {
+    fn selected(&self) -> Option<&ApiCall> {
+        self.calls.get(self.navigator.focused.unwrap_or(0))
+    }
+
+    fn copy_selected(&self) -> Option<Action> {
+        let call = self.selected()?;
+        let command = self.api.as_ref()?.curl_command(call);
+        let action = match clipboard::copy(&command) {
+            Ok(()) => Action::Info(
+                AppMessage::from((
+                    "Copy as curl",
+                    "Copied to the clipboard, export MIHOMO_SECRET before running it.",
+                ))
+                .msg_box_size(40, 20),
+            ),
+            Err(e) => Action::Error(ComponentId::ApiCalls, ("Copy as curl", e).into()),
+        };
+        Some(action)
+    }
+}
+
+impl Component for ApiCallsComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::ApiCalls
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![
+                Fragment::hl(arrow::UP),
+                Fragment::raw(" nav "),
+                Fragment::hl(arrow::DOWN),
+            ]),
+            Shortcut::new(vec![Fragment::raw("copy as curl "), Fragment::hl("y")]),
+            Shortcut::from("refresh", 0).unwrap(),
+            Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn init(&mut self, api: Arc<Api>) -> Result<()> {
+        self.api = Some(api);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.navigator.handle_key_event(false, key).is_consumed() {
+            return Ok(None);
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(Action::Unfocus)),
+            KeyCode::Char('y') | KeyCode::Enter => return Ok(self.copy_selected()),
+            KeyCode::Char('r') => self.calls = ApiLog::snapshot(),
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        // not refreshed on tick, new requests would shift the selection
+        if let Action::Focus(ComponentId::ApiCalls) = action {
+            self.navigator = Default::default();
+            self.calls = ApiLog::snapshot();
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let area = popup_area(area, 80, 70);
+        frame.render_widget(Clear, area); // clears out the background
+        let title = format!("api calls ({})", self.calls.len());
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line(&title, Style::default()));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let [list_area, detail_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Percentage(40)]).areas(inner);
+
+        let viewport_len = list_area.height.saturating_sub(2) as usize;
+        self.navigator.length(self.calls.len(), viewport_len);
+        let records = self
+            .calls
+            .get(self.navigator.scroller.pos()..self.navigator.scroller.end_pos())
+            .unwrap_or(&[]);
+        *self.table_state.selected_mut() =
+            self.navigator.focused.map(|v| v.saturating_sub(self.navigator.scroller.pos()));
+
+        let header = Row::new(["AGO", "METHOD", "PATH", "BODY"])
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .bottom_margin(1);
+        let rows = records.iter().map(|call| {
+            let path = match call.url.query() {
+                Some(query) => format!("{}?{query}", call.url.path()),
+                None => call.url.path().to_owned(),
+            };
+            let body = match &call.body {
+                RequestBody::Empty => String::new(),
+                RequestBody::Text(text) => human_bytes(text.len() as f64, None),
+                RequestBody::Omitted(len) => human_bytes(*len as f64, None),
+            };
+            Row::new([
+                Cell::from(format_time_from_now(call.time)),
+                Cell::from(call.method.as_str().to_owned()),
+                Cell::from(path),
+                Cell::from(body),
+            ])
+        });
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Length(6),
+            Constraint::Fill(1),
+            Constraint::Length(9),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .column_spacing(2)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED).fg(Color::Cyan));
+        frame.render_stateful_widget(table, list_area, &mut self.table_state);
+        self.navigator.render(frame, list_area);
+
+        // the curl command of the selected request, or the newest one
+        let command = self
+            .selected()
+            .zip(self.api.as_ref())
+            .map(|(call, api)| api.curl_command(call))
+            .unwrap_or_default();
+        let detail = Paragraph::new(command)
+            .wrap(Wrap { trim: false })
+            .block(Block::new().borders(Borders::TOP).border_style(Color::DarkGray));
+        frame.render_widget(detail, detail_area);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Style};
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Padding, Paragraph, Wrap};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::info;
+
+use crate::action::Action;
+use crate::components::{Component, ComponentId};
+use crate::config::{Config, MihomoApiEndpoint};
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+/// Confirms reconnecting after the API endpoint or secret changed in the config file.
+#[derive(Default)]
+pub struct ApiReconnectComponent {
+    action_tx: Option<UnboundedSender<Action>>,
+    target: Option<Arc<Config>>,
+}
+
+impl ApiReconnectComponent {
+    fn endpoint_display(endpoint: &MihomoApiEndpoint) -> String {
+        match endpoint {
+            MihomoApiEndpoint::Http(url) => url.to_string(),
+            MihomoApiEndpoint::UnixSocket(path) => format!("unix://{}", path.display()),
+            MihomoApiEndpoint::WindowsNamedPipe(name) => format!("pipe://{name}"),
+        }
+    }
+}
+
+impl Component for ApiReconnectComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::ApiReconnect
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![Fragment::hl("y"), Fragment::raw("es "), Fragment::hl("↵")]),
+            Shortcut::new(vec![Fragment::hl("n"), Fragment::raw("o "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Char('n') | KeyCode::Esc => {
+                info!("API reconnect declined, keep current connection");
+                self.target = None;
+                return Ok(Some(Action::Unfocus));
+            }
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Some(config) = self.target.take() {
+                    self.action_tx.as_ref().unwrap().send(Action::ApiReconnect(config))?;
+                }
+                return Ok(Some(Action::Unfocus));
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::ApiReconnectRequest(config) = action {
+            self.target = Some(config);
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some(config) = self.target.as_deref() else {
+            return Ok(());
+        };
+
+        let area = popup_area(area, 60, 40);
+        frame.render_widget(Clear, area); // clears out the background
+        let border = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line("reconnect", Style::default()))
+            .padding(Padding::symmetric(2, 1));
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let lines = vec![
+            Line::raw("The mihomo API settings in the config file have changed."),
+            Line::raw(""),
+            Line::from(vec![
+                Span::styled(format!("{:<12}", "endpoint"), bold),
+                Span::styled(Self::endpoint_display(&config.mihomo_api), Color::LightCyan),
+            ]),
+            Line::from(vec![
+                Span::styled(format!("{:<12}", "secret"), bold),
+                Span::raw(if config.mihomo_secret.is_some() { "******" } else { "-" }),
+            ]),
+            Line::raw(""),
+            Line::raw("Reconnect now? All views will be reloaded."),
+        ];
+        let content = Paragraph::new(lines).wrap(Wrap { trim: true }).block(border);
+        frame.render_widget(content, area);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,280 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::prelude::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Paragraph};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::info;
+use tui_input::Input;
+
+use crate::action::Action;
+use crate::components::{Component, ComponentId};
+use crate::store::capture_file::{self, CaptureFileRequest};
+use crate::task_manager::BlockingJob;
+use crate::utils::encryption::Encryption;
+use crate::utils::path::{complete_path, expand_home};
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::tui_input::input_request;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+const DEFAULT_CAPTURE_PATH: &str = "mihomo-capture.jsonl";
+
+/// Asks for a path to save the connections capture buffer to, or to open a saved capture from.
+#[derive(Debug, Default)]
+pub struct CaptureFileComponent {
+    request: Option<CaptureFileRequest>,
+    input: Input,
+    /// Path is kept between saves and loads of the session
+    last_path: Option<String>,
+    /// Set once the user was warned the file exists, the next confirm overwrites it
+    confirm_overwrite: bool,
+    error: Option<String>,
+    /// Saving or loading, encryption makes it slow
+    job: BlockingJob<Action>,
+
+    action_tx: Option<UnboundedSender<Action>>,
+}
+
+impl CaptureFileComponent {
+    fn show(&mut self, request: CaptureFileRequest) {
+        self.request = Some(request);
+        self.input = self.last_path.as_deref().unwrap_or(DEFAULT_CAPTURE_PATH).into();
+        self.confirm_overwrite = false;
+        self.error = None;
+    }
+
+    fn hide(&mut self) {
+        self.request = None;
+        self.input.reset();
+        self.error = None;
+        self.job = BlockingJob::default();
+    }
+
+    fn path(&self) -> Result<PathBuf> {
+        let raw = self.input.value().trim();
+        anyhow::ensure!(!raw.is_empty(), "Path cannot be empty");
+        Ok(expand_home(raw))
+    }
+
+    /// Starts saving or loading in the background, returns `false` if the user has to confirm
+    /// overwriting first.
+    fn submit(&mut self) -> Result<bool> {
+        let path = self.path()?;
+        let Some(request) = self.request.clone() else {
+            return Ok(false);
+        };
+        if matches!(request, CaptureFileRequest::Save(_))
+            && path.exists()
+            && !self.confirm_overwrite
+        {
+            self.confirm_overwrite = true;
+            return Ok(false);
+        }
+        self.last_path = Some(self.input.value().trim().to_string());
+        self.job.start(ComponentId::CaptureFile, "capture-file", move || {
+            Ok(match request {
+                CaptureFileRequest::Save(conns) => {
+                    capture_file::save(&path, &conns)?;
+                    info!(path = %path.display(), connections = conns.len(), "Capture saved");
+                    let msg = format!("Saved {} connections to `{}`", conns.len(), path.display());
+                    Action::Info(("Save capture", msg).into())
+                }
+                CaptureFileRequest::Load => {
+                    let snapshot = capture_file::load(&path)?;
+                    info!(path = %path.display(), connections = snapshot.connections.len(), "Capture loaded");
+                    Action::CaptureLoaded(Arc::new(snapshot))
+                }
+            })
+        })?;
+        Ok(true)
+    }
+
+    /// Applies a finished save or load, returns `true` once the popup can be closed.
+    fn poll(&mut self) -> Result<bool> {
+        match self.job.poll() {
+            Some(Ok(action)) => {
+                self.action_tx.as_ref().unwrap().send(action)?;
+                Ok(true)
+            }
+            Some(Err(e)) => {
+                self.error = Some(format!("{e:#}"));
+                Ok(false)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn render_content(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::vertical([Constraint::Length(3), Constraint::Length(3)]).split(area);
+
+        let title = match self.request {
+            Some(CaptureFileRequest::Save(_)) if Encryption::enabled() => {
+                "Save to (JSON lines, encrypted)"
+            }
+            Some(CaptureFileRequest::Save(_)) => "Save to (JSON lines)",
+            _ => "Open (JSON lines)",
+        };
+        let block = Block::bordered()
+            .title(title)
+            .border_type(BorderType::Rounded)
+            .border_style(Color::Cyan);
+        let width = chunks[0].width.saturating_sub(2) as usize;
+        let scroll = self.input.visual_scroll(width);
+        let paragraph = Paragraph::new(self.input.value()).scroll((0, scroll as u16)).block(block);
+        frame.render_widget(paragraph, chunks[0]);
+        let x = self.input.visual_cursor().max(scroll) - scroll + 1;
+        frame.set_cursor_position((chunks[0].x + x as u16, chunks[0].y + 1));
+
+        let message = match (&self.error, self.confirm_overwrite) {
+            (Some(err), _) => Some((Color::Red, err.as_str())),
+            _ if self.job.is_running() => Some((Color::Yellow, "Working...")),
+            (None, true) => Some((Color::Yellow, "File exists, press Enter again to overwrite")),
+            (None, false) => None,
+        };
+        if let Some((color, msg)) = message {
+            let block = Block::bordered().border_type(BorderType::Rounded).border_style(color);
+            let line = Line::from(Span::styled(msg, Style::default().fg(color)));
+            frame.render_widget(Paragraph::new(line).block(block), chunks[1]);
+        }
+    }
+}
+
+impl Component for CaptureFileComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::CaptureFile
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        let confirm = match self.request {
+            Some(CaptureFileRequest::Save(_)) => "save ",
+            _ => "open ",
+        };
+        vec![
+            Shortcut::new(vec![Fragment::raw(confirm), Fragment::hl("↵")]),
+            Shortcut::new(vec![Fragment::raw("complete "), Fragment::hl("Tab")]),
+            Shortcut::new(vec![Fragment::raw("cancel "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Esc => {
+                self.hide();
+                return Ok(Some(Action::Unfocus));
+            }
+            KeyCode::Tab => {
+                if let Some(completed) = complete_path(self.input.value()) {
+                    self.input = completed.into();
+                    self.confirm_overwrite = false;
+                }
+            }
+            KeyCode::Enter if self.job.is_running() => {}
+            KeyCode::Enter => {
+                self.error = None;
+                if let Err(e) = self.submit() {
+                    self.error = Some(format!("{e:#}"));
+                }
+            }
+            _ => {
+                if let Some(req) = input_request(key) {
+                    self.confirm_overwrite = false;
+                    self.error = None;
+                    let _ = self.input.handle(req);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::CaptureFile(request) => {
+                self.show(request);
+                return Ok(Some(Action::Shortcuts(self.shortcuts())));
+            }
+            Action::Tick if self.request.is_some() && self.poll()? => {
+                self.hide();
+                return Ok(Some(Action::Unfocus));
+            }
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some(request) = &self.request else {
+            return Ok(());
+        };
+
+        let area = popup_area(area, 60, 40);
+        frame.render_widget(Clear, area);
+        let area = area.inner(Margin::new(2, 1));
+
+        let title = match request {
+            CaptureFileRequest::Save(_) => "save capture",
+            CaptureFileRequest::Load => "open capture",
+        };
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line(title, Style::default()));
+        let content_area = block.inner(area);
+        frame.render_widget(block, area);
+        self.render_content(frame, content_area);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::utils::test::poll_until;
+
+    async fn finish(component: &mut CaptureFileComponent) {
+        poll_until(|| component.poll().unwrap().then_some(())).await;
+        component.hide();
+    }
+
+    #[tokio::test]
+    async fn save_requires_confirm_and_load_reads_it_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.jsonl");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut component = CaptureFileComponent::default();
+        component.register_action_handler(tx).unwrap();
+        component.show(CaptureFileRequest::Save(Arc::from([])));
+        component.input = path.to_str().unwrap().into();
+        assert!(component.submit().unwrap());
+        finish(&mut component).await;
+        assert!(matches!(rx.try_recv().unwrap(), Action::Info(_)));
+
+        component.show(CaptureFileRequest::Save(Arc::from([])));
+        assert_eq!(component.input.value(), path.to_str().unwrap());
+        assert!(!component.submit().unwrap());
+        assert!(component.submit().unwrap());
+        finish(&mut component).await;
+        assert!(matches!(rx.try_recv().unwrap(), Action::Info(_)));
+
+        component.show(CaptureFileRequest::Load);
+        assert!(component.submit().unwrap());
+        finish(&mut component).await;
+        let Ok(Action::CaptureLoaded(snapshot)) = rx.try_recv() else {
+            panic!("expected a loaded capture");
+        };
+        assert_eq!(snapshot.path, path);
+        assert!(snapshot.connections.is_empty());
+    }
+}
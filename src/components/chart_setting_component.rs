@@ -0,0 +1,208 @@
+use std::borrow::Cow;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Margin, Rect};
+use ratatui::prelude::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Paragraph};
+use strum::{Display, EnumIter, IntoEnumIterator};
+use tokio::sync::mpsc::UnboundedSender;
+use tui_input::Input;
+
+use crate::action::Action;
+use crate::components::{Component, ComponentId};
+use crate::store::chart_setting::ChartSetting;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::tui_input::input_request;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+const LINE_HEIGHT: u16 = 3;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Display, EnumIter)]
+pub enum ChartSettingField {
+    #[default]
+    #[strum(to_string = "Traffic Smoothing (0 = off, < 1)")]
+    Smoothing,
+    #[strum(to_string = "Traffic Peak Markers")]
+    PeakMarkers,
+}
+
+impl ChartSettingField {
+    pub fn next(&self) -> Self {
+        match self {
+            ChartSettingField::Smoothing => ChartSettingField::PeakMarkers,
+            ChartSettingField::PeakMarkers => ChartSettingField::Smoothing,
+        }
+    }
+
+    pub fn value(&self) -> String {
+        let setting = ChartSetting::global().read().unwrap();
+        match self {
+            ChartSettingField::Smoothing => setting.smoothing.to_string(),
+            ChartSettingField::PeakMarkers => setting.peak_markers.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ChartSettingComponent {
+    show: bool,
+    focused: ChartSettingField,
+    input: Input,
+    error: Option<String>,
+
+    action_tx: Option<UnboundedSender<Action>>,
+}
+
+impl ChartSettingComponent {
+    fn show(&mut self) {
+        self.show = true;
+        self.focused = ChartSettingField::default();
+        self.input = self.focused.value().into();
+        self.error = None;
+    }
+
+    fn hide(&mut self) {
+        self.show = false;
+        self.input.reset();
+        self.error = None;
+    }
+
+    fn submit(&self) -> Result<(), String> {
+        let input = self.input.value().trim();
+        let mut setting = ChartSetting::global().write().unwrap();
+
+        match self.focused {
+            ChartSettingField::Smoothing => {
+                let smoothing =
+                    input.parse::<f64>().map_err(|_| "Smoothing must be a number".to_string())?;
+                let next = ChartSetting { smoothing, ..*setting };
+                next.validate().map_err(|e| e.to_string())?;
+                *setting = next;
+                Ok(())
+            }
+            ChartSettingField::PeakMarkers => {
+                setting.peak_markers = input
+                    .parse::<bool>()
+                    .map_err(|_| "Peak markers must be true or false".to_string())?;
+                Ok(())
+            }
+        }
+    }
+
+    fn next(&mut self) {
+        self.error = self.submit().err();
+        if self.error.is_some() {
+            return;
+        }
+        self.focused = self.focused.next();
+        self.input = self.focused.value().into();
+    }
+
+    fn render_settings(&self, frame: &mut Frame, mut area: Rect) {
+        area.height = LINE_HEIGHT;
+
+        for field in ChartSettingField::iter() {
+            let focused = self.focused == field;
+            let (border_color, val) = if focused {
+                (Color::Cyan, Cow::from(self.input.value()))
+            } else {
+                (Color::DarkGray, field.value().into())
+            };
+            let block = Block::bordered()
+                .title(field.to_string())
+                .border_type(BorderType::Rounded)
+                .border_style(border_color);
+            let paragraph = Paragraph::new(Line::raw(val)).block(block);
+            frame.render_widget(paragraph, area);
+            if focused {
+                frame.set_cursor_position((
+                    area.x + self.input.visual_cursor() as u16 + 1,
+                    area.y + 1,
+                ));
+            }
+            area.y += LINE_HEIGHT;
+        }
+        if let Some(err) = &self.error {
+            let block = Block::bordered().border_type(BorderType::Rounded).border_style(Color::Red);
+            let line = Line::from(Span::styled(err, Style::default().fg(Color::Red)));
+            let paragraph = Paragraph::new(line).block(block);
+            frame.render_widget(paragraph, area);
+        }
+    }
+}
+
+impl Component for ChartSettingComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::ChartSetting
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![Fragment::hl("⇧⇤"), Fragment::raw(" nav "), Fragment::hl("⇥")])
+                .compact(vec![Fragment::hl("⇧⇤"), Fragment::raw("/"), Fragment::hl("⇥")]),
+            Shortcut::new(vec![Fragment::raw("confirm "), Fragment::hl("↵")]),
+        ]
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.hide();
+                return Ok(Some(Action::Unfocus));
+            }
+            // two fields only, both directions land on the other one
+            KeyCode::Tab | KeyCode::BackTab => self.next(),
+            KeyCode::Enter => {
+                self.error = self.submit().err();
+                if self.error.is_none() {
+                    self.hide();
+                    self.action_tx.as_ref().unwrap().send(Action::ChartSettingChanged)?;
+                    return Ok(Some(Action::Unfocus));
+                }
+            }
+            _ => {
+                if let Some(req) = input_request(key) {
+                    let _ = self.input.handle(req);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if matches!(action, Action::ChartSetting) {
+            self.show();
+        }
+
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.show {
+            return Ok(());
+        }
+
+        let area = popup_area(area, 60, 50);
+        frame.render_widget(Clear, area);
+        let area = area.inner(Margin::new(2, 1));
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line("chart settings", Style::default()));
+        let content_area = block.inner(area);
+        frame.render_widget(block, area);
+        self.render_settings(frame, content_area);
+
+        Ok(())
+    }
+}
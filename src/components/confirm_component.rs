@@ -0,0 +1,87 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Clear, Padding, Paragraph, Wrap};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::info;
+
+use crate::action::Action;
+use crate::app_message::AppMessage;
+use crate::components::{Component, ComponentId};
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+/// Asks for confirmation of a [`Action::ConfirmRequest`], the wrapped action is sent once
+/// confirmed.
+#[derive(Default)]
+pub struct ConfirmComponent {
+    action_tx: Option<UnboundedSender<Action>>,
+    request: Option<(AppMessage, Box<Action>)>,
+}
+
+impl Component for ConfirmComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::Confirm
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![Fragment::hl("y"), Fragment::raw("es "), Fragment::hl("↵")]),
+            Shortcut::new(vec![Fragment::hl("n"), Fragment::raw("o "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Char('n') | KeyCode::Esc => {
+                if let Some((msg, _)) = self.request.take() {
+                    info!("'{}' declined", msg.title);
+                }
+                return Ok(Some(Action::Unfocus));
+            }
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Some((_, action)) = self.request.take() {
+                    self.action_tx.as_ref().unwrap().send(*action)?;
+                }
+                return Ok(Some(Action::Unfocus));
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::ConfirmRequest(msg, action) = action {
+            self.request = Some((msg, action));
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some((msg, _)) = self.request.as_ref() else {
+            return Ok(());
+        };
+
+        let size = msg.msg_box_size.unwrap_or_default();
+        let area = popup_area(area, size.percent_x, size.percent_y);
+        frame.render_widget(Clear, area); // clears out the background
+        let border = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line(msg.title, Style::default()))
+            .padding(Padding::symmetric(2, 1));
+        let lines = msg.message.lines().map(Line::raw).collect::<Vec<_>>();
+        let content = Paragraph::new(lines).wrap(Wrap { trim: true }).block(border);
+        frame.render_widget(content, area);
+
+        Ok(())
+    }
+}
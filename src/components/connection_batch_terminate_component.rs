@@ -12,6 +12,7 @@ use tracing::{debug, info};
 use crate::action::Action;
 use crate::api::Api;
 use crate::components::{Component, ComponentId};
+use crate::task_manager::TaskManager;
 use crate::utils::text_ui::{popup_area, top_title_line};
 use crate::widgets::shortcut::{Fragment, Shortcut};
 
@@ -71,30 +72,34 @@ impl ConnectionBatchTerminateComponent {
         let ids = self.targets.clone();
         let token = self.token.clone();
 
-        tokio::task::Builder::new().name("connections-batch-terminator").spawn(async move {
-            let mut ok = 0;
-            let mut err = 0;
-
-            for id in ids {
-                tokio::select! {
-                    _ = token.cancelled() => {
-                        info!("Connections batch termination cancelled");
-                        return;
-                    }
-                    result = api.delete_connection(&id) => {
-                        match result {
-                            Ok(_) => ok += 1,
-                            Err(e) => {
-                                err += 1;
-                                debug!(error = ?e, connection_id = %id, "Failed to terminate connection");
+        TaskManager::spawn(
+            ComponentId::ConnectionBatchTerminate,
+            "connections-batch-terminator",
+            async move {
+                let mut ok = 0;
+                let mut err = 0;
+
+                for id in ids {
+                    tokio::select! {
+                        _ = token.cancelled() => {
+                            info!("Connections batch termination cancelled");
+                            return;
+                        }
+                        result = api.delete_connection(&id) => {
+                            match result {
+                                Ok(_) => ok += 1,
+                                Err(e) => {
+                                    err += 1;
+                                    debug!(error = ?e, connection_id = %id, "Failed to terminate connection");
+                                }
                             }
                         }
                     }
                 }
-            }
 
-            *phase.write().unwrap() = Phase::Done { ok, err };
-        })?;
+                *phase.write().unwrap() = Phase::Done { ok, err };
+            },
+        )?;
 
         Ok(())
     }
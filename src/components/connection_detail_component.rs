@@ -1,33 +1,78 @@
 use anyhow::Result;
+use const_format::concatcp;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::Frame;
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::prelude::Style;
-use ratatui::style::Color;
-use ratatui::widgets::{Block, BorderType, Clear, Paragraph};
+use ratatui::style::{Color, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Paragraph, Wrap};
 use serde::Serialize;
 use serde_json::Serializer;
 use serde_json::ser::PrettyFormatter;
+use tui_input::Input;
 
 use crate::action::Action;
+use crate::app_message::AppMessage;
 use crate::components::{Component, ComponentId};
 use crate::models::Connection;
+use crate::store::geoip::{GeoIp, GeoMismatch};
+use crate::store::labels::Labels;
+use crate::store::proxies::Proxies;
+use crate::store::proxy_setting::ProxySetting;
 use crate::utils::symbols::arrow;
 use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::tui_input::input_request;
 use crate::widgets::scrollbar::Scroller;
 use crate::widgets::shortcut::{Fragment, Shortcut};
 
 const INDENT: &[u8; 4] = b"    "; // 4 spaces
+const HOP_SEPARATOR: &str = concatcp!(" ", arrow::RIGHT, " ");
+/// Lines kept above a search match or path jumped to.
+const JUMP_CONTEXT: usize = 2;
 
 #[derive(Debug, Default)]
 pub struct ConnectionDetailComponent {
     show: bool,
     total_lines: usize,
     data: String,
+    /// Inbound followed by the chain from the outermost group to the exit node.
+    hops: Vec<String>,
+    /// `(raw value, label)` of the labeled source IP, host and destination IP.
+    labels: Vec<(String, String)>,
+    geo_mismatch: Option<GeoMismatch>,
+    /// Opened with `b`, see [`Connection::web_url`].
+    web_url: Option<String>,
+
+    /// Input being edited, `/` searches text and `.` jumps to a key path.
+    prompt: Option<(PromptKind, Input)>,
+    /// Confirmed search, matches are cycled with `n`/`N`.
+    query: String,
+    /// Lines containing [`Self::query`], case-insensitive.
+    matches: Vec<usize>,
+    /// Index into [`Self::matches`] of the match jumped to.
+    current_match: usize,
+    /// Result of the last search or path jump, e.g. "path not found".
+    status: Option<String>,
 
     scroller: Scroller,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PromptKind {
+    Search,
+    Path,
+}
+
+impl PromptKind {
+    fn prefix(self) -> &'static str {
+        match self {
+            Self::Search => "/ ",
+            Self::Path => ". ",
+        }
+    }
+}
+
 impl ConnectionDetailComponent {
     fn show(&mut self, data: &Connection) {
         self.show = true;
@@ -35,12 +80,292 @@ impl ConnectionDetailComponent {
         let pretty = Self::pretty(data);
         self.total_lines = pretty.lines().count();
         self.data = pretty;
+        self.hops = Self::hops(data);
+        self.labels = Self::labels(data);
+        self.geo_mismatch = GeoIp::mismatch(data);
+        self.web_url = data.web_url();
         self.scroller.position(0);
+        self.clear_search();
+    }
+
+    fn clear_search(&mut self) {
+        self.prompt = None;
+        self.query.clear();
+        self.matches.clear();
+        self.current_match = 0;
+        self.status = None;
+    }
+
+    fn jump_to(&mut self, line: usize) {
+        self.scroller.position(line.saturating_sub(JUMP_CONTEXT));
+    }
+
+    fn confirm_prompt(&mut self, kind: PromptKind, value: &str) {
+        let value = value.trim();
+        match kind {
+            PromptKind::Search => {
+                self.query = value.to_owned();
+                self.matches = Self::match_lines(&self.data, value);
+                // first match at or below the top of the viewport
+                let top = self.scroller.pos();
+                self.current_match =
+                    self.matches.iter().position(|&line| line >= top).unwrap_or_default();
+                self.status = match self.matches.get(self.current_match) {
+                    Some(&line) => {
+                        self.jump_to(line);
+                        None
+                    }
+                    None if value.is_empty() => None,
+                    None => Some(format!("no match for `{value}`")),
+                };
+            }
+            PromptKind::Path => {
+                self.status = match Self::path_line(&self.data, value) {
+                    Some(line) => {
+                        self.jump_to(line);
+                        None
+                    }
+                    None => Some(format!("path `{value}` not found")),
+                };
+            }
+        }
+    }
+
+    fn cycle_match(&mut self, forward: bool) {
+        let len = self.matches.len();
+        if len == 0 {
+            return;
+        }
+        self.current_match = if forward {
+            (self.current_match + 1) % len
+        } else {
+            (self.current_match + len - 1) % len
+        };
+        self.jump_to(self.matches[self.current_match]);
+    }
+
+    fn handle_prompt_key(&mut self, key: KeyEvent) {
+        let Some((_, input)) = self.prompt.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => self.prompt = None,
+            KeyCode::Enter => {
+                let (kind, input) = self.prompt.take().unwrap();
+                self.confirm_prompt(kind, input.value());
+            }
+            _ => {
+                if let Some(req) = input_request(key) {
+                    let _ = input.handle(req);
+                }
+            }
+        }
+    }
+
+    /// Lines of `text` containing `query`, case-insensitive.
+    fn match_lines(text: &str, query: &str) -> Vec<usize> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let query = query.to_lowercase();
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| line.to_lowercase().contains(&query))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Line of the value at a dotted key path of the pretty-printed JSON, e.g.
+    /// `metadata.processPath` or `chains.0`. Keys compare case-insensitively.
+    fn path_line(pretty: &str, path: &str) -> Option<usize> {
+        let path = path.trim_start_matches('.');
+        if path.is_empty() {
+            return None;
+        }
+        // containers opened so far: (path of the container, is array, next array index)
+        let mut stack: Vec<(String, bool, usize)> = Vec::new();
+        for (idx, line) in pretty.lines().enumerate() {
+            let trimmed = line.trim_start();
+            let depth = (line.len() - trimmed.len()) / INDENT.len();
+            if trimmed.starts_with(['}', ']']) || (depth == 0 && !stack.is_empty()) {
+                continue;
+            }
+            stack.truncate(depth);
+            let segment = match stack.last_mut() {
+                None => String::new(),
+                Some((_, true, next)) => {
+                    *next += 1;
+                    (*next - 1).to_string()
+                }
+                Some(_) => match Self::json_key(trimmed) {
+                    Some(key) => key,
+                    None => continue,
+                },
+            };
+            let line_path = match stack.last() {
+                Some((parent, ..)) if !parent.is_empty() => format!("{parent}.{segment}"),
+                _ => segment,
+            };
+            if !line_path.is_empty() && line_path.eq_ignore_ascii_case(path) {
+                return Some(idx);
+            }
+            if trimmed.ends_with(['{', '[']) {
+                stack.push((line_path, trimmed.ends_with('['), 0));
+            }
+        }
+        None
+    }
+
+    /// Key of an object member line, e.g. `"host": "example.com",` gives `host`.
+    fn json_key(line: &str) -> Option<String> {
+        let rest = line.strip_prefix('"')?;
+        let mut escaped = false;
+        let end = rest.char_indices().find_map(|(idx, c)| match c {
+            _ if escaped => {
+                escaped = false;
+                None
+            }
+            '\\' => {
+                escaped = true;
+                None
+            }
+            '"' => Some(idx),
+            _ => None,
+        })?;
+        rest[end + 1..].starts_with(':').then(|| {
+            serde_json::from_str(&line[..end + 2]).unwrap_or_else(|_| rest[..end].to_owned())
+        })
+    }
+
+    /// Visible lines of the JSON, search matches highlighted.
+    fn content_lines(&self, height: usize) -> Vec<Line<'_>> {
+        let current = self.matches.get(self.current_match).copied();
+        let query = self.query.to_lowercase();
+        self.data
+            .lines()
+            .enumerate()
+            .skip(self.scroller.pos())
+            .take(height)
+            .map(|(idx, line)| {
+                if self.matches.binary_search(&idx).is_err() {
+                    return Line::raw(line);
+                }
+                let style = if Some(idx) == current {
+                    Style::default().black().on_yellow()
+                } else {
+                    Style::default().yellow()
+                };
+                Self::highlight(line, &query, style)
+            })
+            .collect()
+    }
+
+    fn highlight<'a>(line: &'a str, query: &str, style: Style) -> Line<'a> {
+        // lowercasing may change byte lengths, fall back to the whole line then
+        let lower = line.to_lowercase();
+        if lower.len() != line.len() {
+            return Line::styled(line, style);
+        }
+        let mut spans = Vec::new();
+        let mut last = 0;
+        for (start, _) in lower.match_indices(query) {
+            spans.push(Span::raw(&line[last..start]));
+            spans.push(Span::styled(&line[start..start + query.len()], style));
+            last = start + query.len();
+        }
+        spans.push(Span::raw(&line[last..]));
+        Line::from(spans)
+    }
+
+    fn prompt_line(&self) -> Option<Line<'_>> {
+        if let Some((kind, input)) = &self.prompt {
+            return Some(Line::from(vec![
+                Span::raw(kind.prefix()).light_blue(),
+                Span::raw(input.value()),
+            ]));
+        }
+        if let Some(status) = &self.status {
+            return Some(Line::raw(status.as_str()).light_red());
+        }
+        (!self.matches.is_empty()).then(|| {
+            Line::from(vec![
+                Span::raw(PromptKind::Search.prefix()).dark_gray(),
+                Span::raw(self.query.as_str()),
+                Span::raw(format!(" {}/{}", self.current_match + 1, self.matches.len()))
+                    .dark_gray(),
+            ])
+        })
     }
 
     fn hide(&mut self) {
         self.show = false;
         self.data = String::default();
+        self.hops.clear();
+        self.labels.clear();
+        self.geo_mismatch = None;
+        self.clear_search();
+    }
+
+    fn labels(data: &Connection) -> Vec<(String, String)> {
+        let mut labels: Vec<(String, String)> = ["sourceIP", "host", "sniffHost", "destinationIP"]
+            .into_iter()
+            .filter_map(|key| data.metadata_str(key))
+            .filter_map(|value| Labels::get(value).map(|label| (value.to_owned(), label)))
+            .collect();
+        labels.dedup();
+        labels
+    }
+
+    fn labels_line(labels: &[(String, String)]) -> Line<'_> {
+        let mut spans = vec![Span::raw("labels: ").dark_gray()];
+        for (idx, (value, label)) in labels.iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::raw(", ").dark_gray());
+            }
+            spans.push(Span::raw(label.as_str()).bold());
+            spans.push(Span::raw(format!(" ({value})")).dark_gray());
+        }
+        Line::from(spans)
+    }
+
+    fn geo_mismatch_line(mismatch: &GeoMismatch) -> Line<'_> {
+        Line::from(vec![
+            Span::raw("GeoIP mismatch: ").yellow().bold(),
+            Span::raw(format!(
+                "matched GEOIP,{} but the local database places it in {}, ",
+                mismatch.rule, mismatch.local
+            )),
+            Span::raw("u").cyan().bold(),
+            Span::raw(" to update GEO databases"),
+        ])
+    }
+
+    /// `chains` of mihomo starts with the exit node, reverse it to follow the traffic.
+    fn hops(data: &Connection) -> Vec<String> {
+        let inbound = data
+            .metadata_str("inboundName")
+            .or_else(|| data.metadata_str("type"))
+            .unwrap_or("inbound");
+        std::iter::once(inbound.to_owned()).chain(data.chains.iter().rev().cloned()).collect()
+    }
+
+    /// Breadcrumb of hops, proxies are annotated with their latency if the proxies are loaded.
+    fn breadcrumb(hops: &[String]) -> Line<'_> {
+        let threshold = ProxySetting::global().read().unwrap().latency_threshold;
+        let mut spans = Vec::with_capacity(hops.len() * 4);
+        for (idx, hop) in hops.iter().enumerate() {
+            if idx == 0 {
+                spans.push(Span::raw(hop.as_str()).dark_gray());
+                continue;
+            }
+            spans.push(Span::raw(HOP_SEPARATOR).dark_gray());
+            spans.push(Span::raw(hop.as_str()).bold());
+            if let Some(proxy) = Proxies::get_by_name(hop) {
+                spans.push(Span::raw(" "));
+                spans.push(proxy.latency.as_span(threshold));
+            }
+        }
+        Line::from(spans)
     }
 
     fn pretty(data: &Connection) -> String {
@@ -61,7 +386,7 @@ impl Component for ConnectionDetailComponent {
     }
 
     fn shortcuts(&self) -> Vec<Shortcut> {
-        vec![
+        let mut shortcuts = vec![
             Shortcut::new(vec![
                 Fragment::raw("esc "),
                 Fragment::hl("Esc"),
@@ -78,18 +403,58 @@ impl Component for ConnectionDetailComponent {
                 Fragment::raw(" page "),
                 Fragment::hl("PgDn"),
             ]),
-        ]
+            Shortcut::new(vec![Fragment::raw("search "), Fragment::hl("/")]),
+            Shortcut::new(vec![
+                Fragment::hl("n"),
+                Fragment::raw("/"),
+                Fragment::hl("N"),
+                Fragment::raw(" match"),
+            ]),
+            Shortcut::new(vec![Fragment::raw("path "), Fragment::hl(".")]),
+        ];
+        if self.web_url.is_some() {
+            shortcuts.push(Shortcut::from("browser", 0).unwrap());
+        }
+        shortcuts
     }
 
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.prompt.is_some() {
+            self.handle_prompt_key(key);
+            return Ok(None);
+        }
         if self.scroller.handle_key_event(key).is_consumed() {
             return Ok(None);
         }
         match key.code {
+            KeyCode::Char('/') => {
+                self.status = None;
+                self.prompt = Some((PromptKind::Search, Input::new(self.query.clone())));
+            }
+            KeyCode::Char('.') => {
+                self.status = None;
+                self.prompt = Some((PromptKind::Path, Input::default()));
+            }
+            KeyCode::Char('n') => self.cycle_match(true),
+            KeyCode::Char('N') => self.cycle_match(false),
+            KeyCode::Esc if !self.query.is_empty() || self.status.is_some() => self.clear_search(),
             KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => {
                 self.hide();
                 return Ok(Some(Action::Unfocus));
             }
+            KeyCode::Char('b') if self.web_url.is_some() => {
+                return Ok(self.web_url.clone().map(Action::OpenUrl));
+            }
+            KeyCode::Char('u') if self.geo_mismatch.is_some() => {
+                let msg = AppMessage::from((
+                    "Update GEO",
+                    "Update the GEO databases of the core? A stale GeoIP database routes \
+                     connections by outdated countries.",
+                ))
+                .msg_box_size(50, 25);
+                self.hide();
+                return Ok(Some(Action::ConfirmRequest(msg, Box::new(Action::GeoUpdate))));
+            }
             _ => {}
         };
         Ok(None)
@@ -109,21 +474,105 @@ impl Component for ConnectionDetailComponent {
         }
 
         let area = popup_area(area, 80, 75);
-        self.scroller.length(self.total_lines, area.height.saturating_sub(2) as usize);
-
-        // content
         let block = Block::bordered()
             .border_type(BorderType::Rounded)
             .border_style(Color::LightBlue)
             .title(top_title_line("detail", Style::default()));
-        let paragraph =
-            Paragraph::new(self.data.as_str()).scroll((self.scroller.pos() as u16, 0)).block(block);
+        let inner = block.inner(area);
+        let breadcrumb = Self::breadcrumb(&self.hops);
+        let breadcrumb_height =
+            (breadcrumb.width() as u16).div_ceil(inner.width.max(1)).min(inner.height / 2);
+        let breadcrumb = Paragraph::new(breadcrumb).wrap(Wrap { trim: false });
+        let labels_height = if self.labels.is_empty() { 0 } else { 1 };
+        let mismatch_height = if self.geo_mismatch.is_some() { 1 } else { 0 };
+        let prompt_height = if self.prompt_line().is_some() { 1 } else { 0 };
+        let [breadcrumb_area, labels_area, mismatch_area, _, content_area, prompt_area] =
+            Layout::vertical([
+                Constraint::Length(breadcrumb_height),
+                Constraint::Length(labels_height),
+                Constraint::Length(mismatch_height),
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(prompt_height),
+            ])
+            .areas(inner);
+        let height = content_area.height as usize;
+        self.scroller.length(self.total_lines, height);
+        // a jump near the end can't scroll the last line above the bottom
+        let max_pos = self.total_lines.saturating_sub(height);
+        if self.scroller.pos() > max_pos {
+            self.scroller.position(max_pos);
+        }
+
+        // content
+        let paragraph = Paragraph::new(self.content_lines(height));
 
         frame.render_widget(Clear, area); // clears out the background
-        frame.render_widget(paragraph, area);
+        frame.render_widget(block, area);
+        frame.render_widget(breadcrumb, breadcrumb_area);
+        if !self.labels.is_empty() {
+            frame.render_widget(Self::labels_line(&self.labels), labels_area);
+        }
+        if let Some(mismatch) = &self.geo_mismatch {
+            frame.render_widget(Self::geo_mismatch_line(mismatch), mismatch_area);
+        }
+        frame.render_widget(paragraph, content_area);
+        if let Some(line) = self.prompt_line() {
+            frame.render_widget(line, prompt_area);
+            if let Some((kind, input)) = &self.prompt {
+                let x = (kind.prefix().len() + input.visual_cursor()) as u16;
+                frame.set_cursor_position((prompt_area.x + x, prompt_area.y));
+            }
+        }
 
         self.scroller.render(frame, area);
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::test::conn;
+
+    #[test]
+    fn hops_follow_traffic_direction() {
+        let conn = conn("a")
+            .metadata(json!({ "inboundName": "DEFAULT-MIXED", "type": "HTTP" }))
+            .chains(&["HK-01", "Auto", "Proxy"])
+            .rule("Match", "")
+            .build();
+        assert_eq!(
+            ConnectionDetailComponent::hops(&conn),
+            ["DEFAULT-MIXED", "Proxy", "Auto", "HK-01"]
+        );
+    }
+
+    #[test]
+    fn path_and_search_find_lines() {
+        let conn = conn("a")
+            .metadata(json!({ "host": "example.com", "processPath": "/usr/bin/curl" }))
+            .chains(&["HK-01", "Proxy"])
+            .rule("Match", "")
+            .build();
+        let pretty = ConnectionDetailComponent::pretty(&conn);
+        let lines = pretty.lines().collect::<Vec<_>>();
+        let line_of = |path| ConnectionDetailComponent::path_line(&pretty, path).map(|i| lines[i]);
+
+        assert_eq!(
+            line_of("metadata.processPath"),
+            Some(r#"        "processPath": "/usr/bin/curl""#)
+        );
+        assert_eq!(line_of(".METADATA.host"), Some(r#"        "host": "example.com","#));
+        assert_eq!(line_of("chains.1"), Some(r#"        "Proxy""#));
+        assert_eq!(line_of("metadata"), Some(r#"    "metadata": {"#));
+        assert_eq!(line_of("metadata.missing"), None);
+        assert_eq!(line_of("host"), None);
+
+        let matches = ConnectionDetailComponent::match_lines(&pretty, "PROXY");
+        assert_eq!(matches.iter().map(|&i| lines[i].trim()).collect::<Vec<_>>(), [r#""Proxy""#]);
+    }
+}
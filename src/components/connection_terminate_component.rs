@@ -17,6 +17,7 @@ use crate::api::Api;
 use crate::components::{Component, ComponentId};
 use crate::models::Connection;
 use crate::store::connections::CONNECTION_COLS;
+use crate::task_manager::TaskManager;
 use crate::utils::columns::ColDef;
 use crate::utils::text_ui::{popup_area, top_title_line};
 use crate::widgets::shortcut::{Fragment, Shortcut};
@@ -80,22 +81,26 @@ impl ConnectionTerminateComponent {
         let id = self.target.as_deref().unwrap().id.clone();
         let token = self.token.clone();
 
-        tokio::task::Builder::new().name("connection-terminator").spawn(async move {
-            tokio::select! {
-                _ = token.cancelled() => {
-                    info!("Connection termination cancelled");
-                }
-                result = api.delete_connection(&id) => {
-                    match result {
-                        Ok(_) => *phase.write().unwrap() = Phase::DoneOk,
-                        Err(e) => {
-                            error!(error = ?e, "Failed to terminate connection");
-                            *phase.write().unwrap() = Phase::DoneErr(e.to_string());
-                        },
+        TaskManager::spawn(
+            ComponentId::ConnectionTerminate,
+            "connection-terminator",
+            async move {
+                tokio::select! {
+                    _ = token.cancelled() => {
+                        info!("Connection termination cancelled");
+                    }
+                    result = api.delete_connection(&id) => {
+                        match result {
+                            Ok(_) => *phase.write().unwrap() = Phase::DoneOk,
+                            Err(e) => {
+                                error!(error = ?e, "Failed to terminate connection");
+                                *phase.write().unwrap() = Phase::DoneErr(e.to_string());
+                            },
+                        }
                     }
                 }
-            }
-        })?;
+            },
+        )?;
 
         Ok(())
     }
@@ -229,7 +234,7 @@ const fn find_conn_def_by_id(id: &str) -> &ColDef<Connection> {
     let mut i = 0;
 
     while i < CONNECTION_COLS.len() {
-        if CONNECTION_COLS[i].col.id.eq_ignore_ascii_case(id) {
+        if CONNECTION_COLS[i].col.is_builtin(id) {
             return &CONNECTION_COLS[i].col;
         }
 
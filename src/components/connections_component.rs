@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
@@ -20,31 +22,58 @@ use tracing::{debug, info};
 
 use crate::action::Action;
 use crate::api::Api;
+use crate::api::stream::CONNECTIONS_PATH;
+use crate::app_message::AppMessage;
+use crate::components::search::SearchEntry;
 use crate::components::{Component, ComponentId};
+use crate::config::CaptureRetention;
 use crate::models::Connection;
 use crate::models::sort::SortDir;
+use crate::store::capture_file::{CaptureFileRequest, CaptureSnapshot};
 use crate::store::connections::{
-    ALIVE_COLUMN_INDEX, CONNECTION_COLS, Connections, SourceIpAliasTextResolver,
+    ALIVE_COLUMN_INDEX, ConnectionTextResolver, Connections, NEW_WINDOW, Network,
 };
 use crate::store::connections_setting::ConnectionsSetting;
+use crate::store::custom_columns::connection_cols;
+use crate::store::stream_health::StreamHealth;
+use crate::task_manager::TaskManager;
+use crate::utils::byte_size::human_bytes;
 use crate::utils::columns::{TextResolver, filter_placeholder};
 use crate::utils::symbols::{arrow, triangle};
 use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT};
+use crate::widgets::empty_state::EmptyState;
 use crate::widgets::scrollable_navigator::ScrollableNavigator;
 use crate::widgets::shortcut::{Fragment, Shortcut};
+use crate::widgets::table_style;
 
 const ROW_HEIGHT: usize = 1;
 const COLUMN_SPACING: u16 = 2;
 const TABLE_FLEX: Flex = Flex::Start;
 const LAYOUT_SAVE_TICKS: u8 = 4;
 
+/// Style of a row opened `age` ago, fading out over [`NEW_WINDOW`].
+fn new_row_style(age: Duration) -> Style {
+    match age.as_millis() * 3 / NEW_WINDOW.as_millis() {
+        0 => Style::default().fg(Color::Black).bg(Color::LightGreen),
+        1 => Style::default().fg(Color::LightGreen).bold(),
+        _ => Style::default().fg(Color::Green),
+    }
+}
+
 pub struct ConnectionsComponent {
     token: CancellationToken,
     conns_rx: Arc<AsyncMutex<Receiver<Vec<Connection>>>>,
     action_tx: Option<UnboundedSender<Action>>,
 
+    /// Streamed connections, still updated while a saved capture is shown
+    live: Arc<Connections>,
+    /// Shown connections, the live ones or a saved capture
     store: Arc<Connections>,
     navigator: ScrollableNavigator,
+    /// The focused connection as last seen, focus follows its id when the view is recomputed
+    focused: Option<Arc<Connection>>,
+    /// The focused connection left the view because it was closed
+    focused_closed: bool,
     table_state: TableState,
     pending_column_width_deltas: HashMap<usize, i16>,
     layout_save_ticks_remaining: u8,
@@ -54,19 +83,27 @@ pub struct ConnectionsComponent {
 
     capture_mode: Arc<AtomicBool>,
     capture_throbber: ThrobberState,
+
+    /// File of the saved capture shown instead of the live connections
+    snapshot: Option<PathBuf>,
 }
 
 impl ConnectionsComponent {
     pub fn new(
         conns_rx: Arc<AsyncMutex<Receiver<Vec<Connection>>>>,
         store_capacity: NonZeroUsize,
+        retention: CaptureRetention,
     ) -> Self {
+        let live = Arc::new(Connections::new(store_capacity).with_retention(retention));
         Self {
             token: CancellationToken::new(),
             conns_rx,
             action_tx: None,
-            store: Arc::new(Connections::new(store_capacity)),
+            store: Arc::clone(&live),
+            live,
             navigator: Default::default(),
+            focused: None,
+            focused_closed: false,
             table_state: Default::default(),
             pending_column_width_deltas: Default::default(),
             layout_save_ticks_remaining: 0,
@@ -74,24 +111,24 @@ impl ConnectionsComponent {
             live_throbber: Default::default(),
             capture_mode: Default::default(),
             capture_throbber: Default::default(),
+            snapshot: None,
         }
     }
 
     fn load_connections(&mut self) -> Result<()> {
-        let store = Arc::clone(&self.store);
-        let live_mode = Arc::clone(&self.live_mode);
+        let store = Arc::clone(&self.live);
         let capture_mode = Arc::clone(&self.capture_mode);
         let rx = Arc::clone(&self.conns_rx);
 
         let token = self.token.clone();
-        tokio::task::Builder::new().name("connections-loader").spawn(async move {
+        TaskManager::spawn(ComponentId::Connections, "connections-loader", async move {
             loop {
                 tokio::select! {
                     _ = token.cancelled() => break,
                     res = async { rx.lock().await.recv().await } => match res {
                         Some(records) => {
-                            store.push(capture_mode.load(Ordering::Relaxed), records);
-                            if live_mode.load(Ordering::Relaxed) {
+                            let diff = store.push(capture_mode.load(Ordering::Relaxed), records);
+                            if !diff.is_empty() {
                                 store.compute_view();
                             }
                         },
@@ -105,6 +142,14 @@ impl ConnectionsComponent {
     }
 
     fn render_throbber(&mut self, frame: &mut Frame, area: Rect) {
+        if self.snapshot.is_some() {
+            let label = Span::styled(
+                " Snapshot ",
+                Style::default().fg(Color::White).bg(Color::Magenta).bold(),
+            );
+            frame.render_widget(label, Rect::new(area.right().saturating_sub(11), area.y, 10, 1));
+            return;
+        }
         if self.capture_mode.load(Ordering::Relaxed) {
             let symbol = Throbber::default()
                 .label("Capture")
@@ -136,21 +181,62 @@ impl ConnectionsComponent {
         );
     }
 
+    /// Move focus to wherever the focused connection landed after the view was recomputed, or
+    /// mark it closed once it is gone from the snapshot. While paused the scroll offset stays
+    /// put, the highlight follows the connection within it. No row is highlighted while the
+    /// connection is out of the view, the row left at its index is another connection.
+    fn follow_focused(&mut self, viewport: usize) {
+        let (len, found) = self.store.with_view(|records| {
+            let found = self
+                .focused
+                .as_ref()
+                .and_then(|focused| records.iter().enumerate().find(|(_, c)| c.id == focused.id));
+            (records.len(), found.map(|(idx, c)| (idx, Arc::clone(c))))
+        });
+        self.navigator.length(len, viewport);
+        match found {
+            Some((idx, conn)) => {
+                self.focused_closed = conn.inactive.load(Ordering::Relaxed);
+                self.focused = Some(conn);
+                if !self.live_mode.load(Ordering::Relaxed) {
+                    self.navigator.focused = Some(idx);
+                } else if self.navigator.focused != Some(idx) {
+                    self.navigator.focus(idx);
+                }
+            }
+            // filtered out rows are not closed
+            None => {
+                self.navigator.focused = None;
+                self.focused_closed =
+                    self.focused.as_ref().is_some_and(|c| !self.store.is_active(&c.id));
+            }
+        }
+    }
+
     fn render_table(&mut self, frame: &mut Frame, area: Rect) {
+        // viewport = area.height - 2 (border) - 2 (table header)
+        self.follow_focused((area.height - 2 - 2) as usize);
+        // NOTE: end_pos() depends on length()
+        let start = self.navigator.scroller.pos();
+        let end = self.navigator.scroller.end_pos();
         let records = self.store.with_view(|records| {
-            // update scroller, viewport = area.height - 2 (border) - 2 (table header)
-            self.navigator.length(records.len(), (area.height - 2 - 2) as usize);
-            // NOTE: end_pos() depends on length()
-            let start = self.navigator.scroller.pos();
-            let end = self.navigator.scroller.end_pos();
             records.iter().skip(start).take(end - start).cloned().collect::<Vec<_>>()
         });
 
-        // update table selected, which is relative position in current viewport
+        // update table selected, which is relative position in current viewport, a paused view
+        // doesn't scroll to the focused row
         *self.table_state.selected_mut() =
-            self.navigator.focused.map(|v| v.saturating_sub(self.navigator.scroller.pos()));
+            self.navigator.focused.filter(|idx| (start..end).contains(idx)).map(|idx| idx - start);
+
+        let setting = ConnectionsSetting::snapshot();
+        let cols = connection_cols();
+        let sort_keys = setting.query_state.sort_keys();
+        let arrow = |dir| match dir {
+            SortDir::Asc => triangle::UP,
+            SortDir::Desc => triangle::DOWN,
+        };
 
-        let title_line = Line::from(vec![
+        let mut title_line = Line::from(vec![
             Span::raw(TOP_TITLE_LEFT),
             Span::raw("connections ("),
             Span::styled(
@@ -160,50 +246,83 @@ impl ConnectionsComponent {
             Span::raw("/"),
             Span::styled(self.navigator.scroller.content_length().to_string(), Color::Cyan),
             Span::raw(")"),
-            Span::raw(TOP_TITLE_RIGHT),
         ]);
-        let block = Block::bordered().border_type(BorderType::Rounded).title(title_line);
-        let setting = ConnectionsSetting::snapshot();
-        let sort = setting.query_state.sort;
+        if let Some(path) = &self.snapshot {
+            let name = path.file_name().unwrap_or(path.as_os_str()).to_string_lossy();
+            title_line.push_span(Span::styled(format!(" snapshot {name}"), Color::Magenta));
+        }
+        if self.shows_closed() {
+            let closed = self.store.closed_count();
+            if closed > 0 {
+                title_line.push_span(Span::styled(format!(" {closed} closed"), Color::DarkGray));
+            }
+        }
+        if self.focused_closed {
+            title_line.push_span(Span::styled(" focused connection closed", Color::Red));
+        }
+        self.push_network_spans(setting.network, &mut title_line);
+        // spell out the order once secondary keys are in use, e.g. "Chains ↓, DownRate ↓"
+        if sort_keys.len() > 1 {
+            let order = sort_keys
+                .iter()
+                .filter_map(|sort| {
+                    let def = setting.columns.get(sort.col).and_then(|&i| cols.get(i))?;
+                    Some(format!("{} {}", def.col.title, arrow(sort.dir)))
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            title_line.push_span(Span::raw(" by "));
+            title_line.push_span(Span::styled(order, Color::Cyan));
+        }
+        title_line.push_span(Span::raw(TOP_TITLE_RIGHT));
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(title_line)
+            .title_top(StreamHealth::indicator(CONNECTIONS_PATH));
         let header = setting
             .columns
             .iter()
-            .filter_map(|&index| CONNECTION_COLS.get(index).map(|def| (index, def.col.title)))
+            .filter_map(|&index| cols.get(index).map(|def| (index, def.col.title.as_ref())))
             .enumerate()
             .map(|(visible_index, (_index, title))| {
-                if let Some(sort) = sort
-                    && visible_index == sort.col
-                {
-                    let arrow = match sort.dir {
-                        SortDir::Asc => triangle::UP,
-                        SortDir::Desc => triangle::DOWN,
-                    };
-                    Cell::from(format!("{}{}", title, arrow)).bold().cyan()
+                if let Some(sort) = sort_keys.iter().find(|sort| sort.col == visible_index) {
+                    Cell::from(format!("{}{}", title, arrow(sort.dir))).cyan()
                 } else {
-                    Cell::from(title).bold()
+                    Cell::from(title)
                 }
             })
             .collect::<Row>()
+            .style(table_style::header_style())
             .height(1)
             .bottom_margin(1);
-        let selected_row_style = Style::default().add_modifier(Modifier::REVERSED).fg(Color::Cyan);
-        let text_resolver = SourceIpAliasTextResolver { source_ip_alias: &setting.source_ip_alias };
+        let selected_row_style = if self.focused_closed {
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::CROSSED_OUT).fg(Color::Red)
+        } else {
+            Style::default().add_modifier(Modifier::REVERSED).fg(Color::Cyan)
+        };
+        let text_resolver =
+            ConnectionTextResolver { source_ip_alias: &setting.source_ip_alias, cols: &cols };
+        let live_mode = self.live_mode.load(Ordering::Relaxed);
+        let now = Instant::now();
 
-        let rows: Vec<Row> =
-            records
-                .iter()
-                .map(|item| {
-                    Row::new(
-                        setting.columns.iter().filter_map(|&index| CONNECTION_COLS.get(index)).map(
-                            |def| text_resolver.resolve(&def.col, item, (def.col.accessor)(item)),
-                        ),
-                    )
-                    .height(ROW_HEIGHT as u16)
-                })
-                .collect();
+        let rows: Vec<Row> = records
+            .iter()
+            .map(|item| {
+                let row =
+                    Row::new(setting.columns.iter().filter_map(|&index| cols.get(index)).map(
+                        |def| text_resolver.resolve(&def.col, item, (def.col.accessor)(item)),
+                    ))
+                    .height(ROW_HEIGHT as u16);
+                match self.store.new_for(&item.id, now).filter(|_| live_mode) {
+                    Some(age) => row.style(new_row_style(age)),
+                    None => row,
+                }
+            })
+            .collect();
         let mut constraints = self.table_constraints(&setting);
-        self.apply_pending_column_width_deltas(&mut constraints, &setting, block.inner(area));
-        let table = Table::new(rows, constraints)
+        let inner = block.inner(area);
+        self.apply_pending_column_width_deltas(&mut constraints, &setting, inner);
+        let table = Table::new(rows, constraints.clone())
             .block(block)
             .header(header)
             .flex(TABLE_FLEX)
@@ -211,21 +330,84 @@ impl ConnectionsComponent {
             .row_highlight_style(selected_row_style);
 
         frame.render_stateful_widget(table, area, &mut self.table_state);
+        table_style::render_separators(frame, inner, &constraints, TABLE_FLEX, COLUMN_SPACING);
+
+        if records.is_empty() {
+            EmptyState::new(self.store.load_state(), "connections")
+                .filtered(setting.query_state.pattern.is_some())
+                .render(frame, area.inner(Margin::new(1, 1)), &mut self.live_throbber);
+        }
     }
 
     fn live_mode(&mut self, live_mode: bool) {
         self.live_mode.store(live_mode, Ordering::Relaxed);
         if live_mode {
-            self.navigator.focused = None;
+            self.unfocus();
             self.navigator.scroller.position(0);
         }
     }
 
-    fn handle_query_state_changed(&self) {
-        // recompute view only when not in live mode, and has sorting specified
-        if !self.live_mode.load(Ordering::Relaxed) {
-            self.store.compute_view();
+    /// Closed connections are kept in capture mode, and may be part of a saved capture.
+    fn shows_closed(&self) -> bool {
+        self.snapshot.is_some() || self.capture_mode.load(Ordering::Relaxed)
+    }
+
+    /// Show a saved capture read-only until [`Self::leave_snapshot`], the live connections keep
+    /// streaming in the background.
+    fn enter_snapshot(&mut self, snapshot: &CaptureSnapshot) {
+        self.snapshot = Some(snapshot.path.clone());
+        self.store = Arc::new(Connections::of_records(snapshot.connections.clone()));
+        self.live_mode.store(false, Ordering::Relaxed);
+        self.unfocus();
+        self.navigator.scroller.position(0);
+        self.store.compute_view();
+    }
+
+    /// Back to the live connections, including what capture mode kept meanwhile.
+    fn leave_snapshot(&mut self) {
+        self.snapshot = None;
+        self.store = Arc::clone(&self.live);
+        self.store.compute_view();
+        self.live_mode(true);
+    }
+
+    fn unfocus(&mut self) {
+        self.navigator.focused = None;
+        self.focused = None;
+        self.focused_closed = false;
+    }
+
+    /// Per-network counts and rates, the filtered network highlighted, e.g.
+    /// "tcp 120 ↓1.2 MB/s ↑20 KB/s udp 8 ↓3 KB/s ↑1 KB/s".
+    fn push_network_spans(&self, filter: Option<Network>, title_line: &mut Line) {
+        let totals = self.store.network_totals();
+        for network in [Network::Tcp, Network::Udp] {
+            let share = totals.get(network);
+            let style = match filter {
+                Some(n) if n == network => Style::default().fg(Color::Black).bg(Color::LightCyan),
+                Some(_) => Style::default().fg(Color::DarkGray),
+                None => Style::default(),
+            };
+            title_line.push_span(Span::raw(" "));
+            title_line
+                .push_span(Span::styled(format!("{} {}", network.label(), share.conns), style));
+            if share.conns > 0 {
+                title_line.push_span(Span::styled(
+                    format!(
+                        " {}{} {}{}",
+                        arrow::DOWN,
+                        human_bytes(share.down_rate as f64, Some("/s")),
+                        arrow::UP,
+                        human_bytes(share.up_rate as f64, Some("/s")),
+                    ),
+                    Color::DarkGray,
+                ));
+            }
         }
+    }
+
+    /// The view is recomputed on [`Action::ConnectionsSettingChanged`], live or paused.
+    fn handle_query_state_changed(&self) {
         if let Some(tx) = &self.action_tx {
             let _ = tx.send(Action::ConnectionsSettingChanged);
         }
@@ -233,8 +415,9 @@ impl ConnectionsComponent {
 
     fn filter_placeholder() -> Option<String> {
         let setting = ConnectionsSetting::snapshot();
+        let cols = connection_cols();
         filter_placeholder(
-            setting.columns.iter().filter_map(|&idx| CONNECTION_COLS.get(idx)).map(|col| &col.col),
+            setting.columns.iter().filter_map(|&idx| cols.get(idx)).map(|col| &col.col),
         )
     }
 
@@ -252,7 +435,8 @@ impl ConnectionsComponent {
 // Column width adjustment and deferred persistence.
 impl ConnectionsComponent {
     fn table_constraints(&self, setting: &ConnectionsSetting) -> Vec<Constraint> {
-        let hidden_alive = !self.capture_mode.load(Ordering::Relaxed);
+        let hidden_alive = !self.shows_closed();
+        let cols = connection_cols();
         let mut constraints: Vec<_> = setting
             .columns
             .iter()
@@ -261,7 +445,7 @@ impl ConnectionsComponent {
                     return Some(Constraint::Length(0));
                 }
                 // Looking up the definition also filters invalid column indices
-                let default = CONNECTION_COLS.get(index)?.constraint;
+                let default = cols.get(index)?.constraint;
                 let constraint = match setting.column_widths.get(&index) {
                     Some(&width) => Constraint::Length(width),
                     None => default,
@@ -316,7 +500,7 @@ impl ConnectionsComponent {
     ///
     /// * `Option<Vec<(usize, usize, u16)>>` - The applicable column-width updates, or `None` if no
     ///   pending delta can be applied. Each tuple contains, in order:
-    ///   1. The stable column index into [`CONNECTION_COLS`].
+    ///   1. The stable column index into [`connection_cols`].
     ///   2. The visible column index in the current table.
     ///   3. The new column width.
     fn resolve_pending_column_widths(
@@ -492,6 +676,8 @@ impl Component for ConnectionsComponent {
                 Fragment::raw(" sort "),
                 Fragment::hl("r"),
             ]),
+            Shortcut::new(vec![Fragment::hl("p"), Fragment::raw(" then by")])
+                .compact(vec![Fragment::hl("p"), Fragment::raw(" then")]),
             Shortcut::new(vec![Fragment::hl("-/+"), Fragment::raw(" width")])
                 .compact(vec![Fragment::hl("-/+"), Fragment::raw(" w")]),
             Shortcut::new(vec![Fragment::hl("Del"), Fragment::raw(" reset")])
@@ -502,8 +688,23 @@ impl Component for ConnectionsComponent {
                 Fragment::hl("T"),
                 Fragment::raw("erm"),
             ]),
-            Shortcut::from("capture", 0).unwrap(),
-            Shortcut::new(vec![Fragment::raw("detail "), Fragment::hl("↵")]),
+            Shortcut::new(vec![
+                Fragment::hl("c"),
+                Fragment::raw("apture/"),
+                Fragment::hl("C"),
+                Fragment::raw("lear"),
+            ])
+            .compact(vec![Fragment::hl("c"), Fragment::raw("apture")]),
+            Shortcut::new(vec![Fragment::hl("n"), Fragment::raw("et tcp/udp")])
+                .compact(vec![Fragment::hl("n"), Fragment::raw("et")]),
+            Shortcut::new(vec![
+                Fragment::hl("w"),
+                Fragment::raw("rite/"),
+                Fragment::hl("o"),
+                Fragment::raw("pen"),
+            ]),
+            Shortcut::from("browser", 0).unwrap(),
+            Shortcut::new(vec![Fragment::raw("detail "), Fragment::hl("↵")]).pinned(),
             Shortcut::new(vec![Fragment::raw("live "), Fragment::hl("Esc")]),
             Shortcut::from("setting", 0).unwrap(),
         ]
@@ -523,9 +724,13 @@ impl Component for ConnectionsComponent {
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
         if self.navigator.handle_key_event(false, key).is_consumed() {
             self.live_mode(false);
+            // focus follows the connection rather than the row
+            self.focused = self.navigator.focused.and_then(|idx| self.store.get(idx));
+            self.focused_closed = false;
             return Ok(None);
         }
         match key.code {
+            KeyCode::Esc if self.snapshot.is_some() => self.leave_snapshot(),
             KeyCode::Esc => self.live_mode(true),
             KeyCode::Left => {
                 ConnectionsSetting::update(|setting| setting.query_state.sort_prev());
@@ -536,9 +741,7 @@ impl Component for ConnectionsComponent {
                     // When capture mode is off, the runtime Alive column is hidden with zero width.
                     // If sorting starts from None, advance once more so Right lands on the first
                     // visible user column instead of the hidden Alive column.
-                    if setting.query_state.sort.is_none()
-                        && !self.capture_mode.load(Ordering::Relaxed)
-                    {
+                    if setting.query_state.sort.is_none() && !self.shows_closed() {
                         setting.query_state.sort_next()
                     }
                     setting.query_state.sort_next()
@@ -549,6 +752,10 @@ impl Component for ConnectionsComponent {
                 ConnectionsSetting::update(|setting| setting.query_state.sort_rev());
                 self.handle_query_state_changed();
             }
+            KeyCode::Char('p') => {
+                ConnectionsSetting::update(|setting| setting.query_state.toggle_pin());
+                self.handle_query_state_changed();
+            }
             KeyCode::Char('-') if key.modifiers == KeyModifiers::NONE => {
                 self.adjust_column_width(-1);
             }
@@ -561,12 +768,10 @@ impl Component for ConnectionsComponent {
                 self.adjust_column_width(1);
             }
             KeyCode::Delete if key.modifiers == KeyModifiers::NONE => self.reset_column_width(),
-            KeyCode::Char('t') => {
-                let action = self
-                    .navigator
-                    .focused
-                    .and_then(|idx| self.store.get(idx))
-                    .map(Action::ConnectionTerminateRequest);
+            // a saved capture is read-only
+            KeyCode::Char('t' | 'T') if self.snapshot.is_some() => (),
+            KeyCode::Char('t') if !self.focused_closed => {
+                let action = self.focused.clone().map(Action::ConnectionTerminateRequest);
                 return Ok(action);
             }
             KeyCode::Char('T') => {
@@ -577,16 +782,47 @@ impl Component for ConnectionsComponent {
                 }
                 return Ok(Some(Action::ConnectionBatchTerminateRequest(ids)));
             }
-            KeyCode::Char('c') => self
-                .capture_mode
-                .store(!self.capture_mode.load(Ordering::Relaxed), Ordering::Relaxed),
+            KeyCode::Char('c') if self.capture_mode.load(Ordering::Relaxed) => {
+                let closed = self.live.closed_count();
+                if closed == 0 {
+                    self.capture_mode.store(false, Ordering::Relaxed);
+                    return Ok(None);
+                }
+                // the next snapshot drops them
+                let msg = AppMessage::from((
+                    "Stop capture",
+                    format!("Drop the {closed} closed connections kept by capture mode? Save them with `w` first to keep them."),
+                ))
+                .msg_box_size(50, 25);
+                return Ok(Some(Action::ConfirmRequest(msg, Box::new(Action::CaptureStop))));
+            }
+            KeyCode::Char('c') => self.capture_mode.store(true, Ordering::Relaxed),
+            KeyCode::Char('C') if self.snapshot.is_none() => {
+                let cleared = self.store.clear_closed();
+                debug!(cleared, "Cleared closed connections of capture mode");
+                if self.focused_closed {
+                    self.unfocus();
+                }
+                self.store.compute_view();
+            }
             KeyCode::Char('f') => return Ok(Some(Action::Focus(ComponentId::Filter))),
+            KeyCode::Char('n') => {
+                ConnectionsSetting::update(|setting| {
+                    setting.network = Network::cycle(setting.network)
+                });
+                self.handle_query_state_changed();
+            }
+            KeyCode::Char('w') => {
+                let request = CaptureFileRequest::Save(self.store.snapshot().into());
+                return Ok(Some(Action::CaptureFile(request)));
+            }
+            KeyCode::Char('o') => return Ok(Some(Action::CaptureFile(CaptureFileRequest::Load))),
+            KeyCode::Char('b') => {
+                return Ok(self.focused.as_ref().and_then(|c| c.web_url()).map(Action::OpenUrl));
+            }
+            // a closed connection can still be inspected
             KeyCode::Enter => {
-                let action = self
-                    .navigator
-                    .focused
-                    .and_then(|idx| self.store.get(idx))
-                    .map(Action::ConnectionDetail);
+                let action = self.focused.clone().map(Action::ConnectionDetail);
                 return Ok(action);
             }
             KeyCode::Char('s') => {
@@ -598,6 +834,19 @@ impl Component for ConnectionsComponent {
         Ok(None)
     }
 
+    fn search_entries(&self) -> Vec<SearchEntry> {
+        self.store
+            .snapshot()
+            .iter()
+            .filter_map(|conn| {
+                let host = conn.metadata_str("host").filter(|host| !host.is_empty());
+                host.or_else(|| conn.metadata_str("destinationIP"))
+            })
+            .filter(|host| !host.is_empty())
+            .map(|host| SearchEntry::filter(self.id(), "host", host.to_owned()))
+            .collect()
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::Quit => {
@@ -610,28 +859,20 @@ impl Component for ConnectionsComponent {
                 }
                 self.tick_layout_save();
             }
-            Action::FilterChanged(pattern) => {
+            Action::FilterChanged(tab, pattern) if tab == self.id() => {
                 debug!("handle Action::FilterChanged, got pattern={pattern:?}");
                 ConnectionsSetting::update(|setting| setting.query_state.set_pattern(pattern));
+                self.store.compute_view();
             }
             Action::TabSwitch(to) if to == self.id() => {
-                let pattern = ConnectionsSetting::global()
-                    .write()
-                    .unwrap()
-                    .query_state
-                    .pattern
-                    .as_ref()
-                    .map(|pattern| pattern.raw().into());
-                debug!("handle Action::TabSwitch, current filter pattern={pattern:?}");
-                if let Some(tx) = &self.action_tx {
-                    tx.send(Action::FilterPlaceholder(Self::filter_placeholder()))?;
-                }
-                return Ok(Some(Action::FilterSet(pattern)));
+                return Ok(Some(Action::FilterPlaceholder(self.id(), Self::filter_placeholder())));
             }
-            Action::ConnectionsSettingChanged => {
+            Action::CaptureLoaded(snapshot) => self.enter_snapshot(&snapshot),
+            Action::CaptureStop => self.capture_mode.store(false, Ordering::Relaxed),
+            Action::ConnectionsSettingChanged | Action::ConfigReloaded(_) => {
                 self.store.compute_view();
                 if let Some(tx) = &self.action_tx {
-                    tx.send(Action::FilterPlaceholder(Self::filter_placeholder()))?;
+                    tx.send(Action::FilterPlaceholder(self.id(), Self::filter_placeholder()))?;
                 }
             }
             _ => {}
@@ -652,7 +893,9 @@ impl Component for ConnectionsComponent {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::store::connections::CONNECTION_COLS;
     use crate::store::query::QueryState;
+    use crate::utils::test::conns;
 
     fn connection_col_index(id: &str) -> usize {
         CONNECTION_COLS
@@ -663,17 +906,22 @@ mod tests {
 
     fn component() -> ConnectionsComponent {
         let (_tx, rx) = tokio::sync::mpsc::channel(1);
-        ConnectionsComponent::new(Arc::new(AsyncMutex::new(rx)), NonZeroUsize::new(1).unwrap())
+        ConnectionsComponent::new(
+            Arc::new(AsyncMutex::new(rx)),
+            NonZeroUsize::new(1).unwrap(),
+            Default::default(),
+        )
     }
 
     fn setting() -> ConnectionsSetting {
         let columns =
             vec![ALIVE_COLUMN_INDEX, connection_col_index("host"), connection_col_index("rule")];
         ConnectionsSetting {
-            query_state: QueryState { pattern: None, sort: None, max_cols: columns.len() },
+            query_state: QueryState::new(columns.len()),
             columns,
             column_widths: HashMap::new(),
             source_ip_alias: HashMap::new(),
+            network: None,
         }
     }
 
@@ -729,6 +977,126 @@ mod tests {
         assert_eq!(component.layout_save_ticks_remaining, 0);
     }
 
+    #[test]
+    fn focus_follows_connection_id() {
+        let (_tx, rx) = tokio::sync::mpsc::channel(1);
+        let mut component = ConnectionsComponent::new(
+            Arc::new(AsyncMutex::new(rx)),
+            NonZeroUsize::new(8).unwrap(),
+            Default::default(),
+        );
+        let position = |component: &ConnectionsComponent, id: &str| {
+            component.store.with_view(|records| records.iter().position(|c| c.id == id))
+        };
+        component.store.push(false, conns(&["a", "b", "c"]));
+        component.store.compute_view();
+        component.follow_focused(10);
+        component.handle_key_event(KeyEvent::from(KeyCode::Down)).unwrap();
+        component.handle_key_event(KeyEvent::from(KeyCode::Down)).unwrap();
+        let id = component.focused.as_ref().unwrap().id.clone();
+        // navigating pauses live mode
+        assert!(!component.live_mode.load(Ordering::Relaxed));
+
+        // rows shift, the highlight moves with the connection
+        component.store.push(false, conns(&["x", "y", "a", "b", "c"]));
+        component.store.compute_view();
+        component.follow_focused(10);
+        assert_eq!(component.navigator.focused, position(&component, &id));
+        assert!(!component.focused_closed);
+
+        component.store.push(false, conns(&["x", "y"]));
+        component.store.compute_view();
+        component.follow_focused(10);
+        assert!(component.focused_closed);
+        assert_eq!(component.focused.as_ref().unwrap().id, id);
+        // the row left at its index is another connection, it is not painted as closed
+        assert_eq!(component.navigator.focused, None);
+        assert!(component.handle_key_event(KeyEvent::from(KeyCode::Char('t'))).unwrap().is_none());
+
+        component.handle_key_event(KeyEvent::from(KeyCode::Esc)).unwrap();
+        assert!(component.focused.is_none() && component.navigator.focused.is_none());
+        assert!(component.live_mode.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn paused_view_keeps_the_picked_connection() {
+        let (_tx, rx) = tokio::sync::mpsc::channel(1);
+        let mut component = ConnectionsComponent::new(
+            Arc::new(AsyncMutex::new(rx)),
+            NonZeroUsize::new(8).unwrap(),
+            Default::default(),
+        );
+        component.store.push(false, conns(&["a", "b", "c"]));
+        component.store.compute_view();
+        component.follow_focused(10);
+        component.handle_key_event(KeyEvent::from(KeyCode::Down)).unwrap();
+        component.handle_key_event(KeyEvent::from(KeyCode::Down)).unwrap();
+
+        // the selection stays on the picked connection, the scroll offset doesn't move
+        component.store.push(false, conns(&["x", "a", "b", "c"]));
+        component.store.compute_view();
+        component.follow_focused(10);
+        assert_eq!(component.navigator.focused, Some(2));
+        assert_eq!(component.navigator.scroller.pos(), 0);
+        assert_eq!(component.focused.as_ref().unwrap().id, "b");
+
+        component.store.push(false, conns(&["x"]));
+        component.store.compute_view();
+        component.follow_focused(10);
+        assert_eq!(component.store.with_view(|records| records.len()), 1);
+        assert_eq!(component.focused.as_ref().unwrap().id, "b");
+        assert!(component.focused_closed);
+        assert_eq!(component.navigator.focused, None);
+        assert!(component.handle_key_event(KeyEvent::from(KeyCode::Char('t'))).unwrap().is_none());
+    }
+
+    #[test]
+    fn saved_capture_is_read_only_until_esc() {
+        let mut component = component();
+        component.store.push(false, conns(&["x"]));
+        component.store.compute_view();
+        // more than the live buffer holds
+        let connections = conns(&["a", "b", "c"]).into_iter().map(Arc::new).collect();
+        let snapshot = CaptureSnapshot { path: "incident.jsonl".into(), connections };
+        component.update(Action::CaptureLoaded(Arc::new(snapshot))).unwrap();
+        assert!(!component.live_mode.load(Ordering::Relaxed));
+        assert_eq!(component.store.with_view(|records| records.len()), 3);
+        // the live connections are left alone
+        assert_eq!(component.live.snapshot().len(), 1);
+
+        component.follow_focused(10);
+        component.handle_key_event(KeyEvent::from(KeyCode::Down)).unwrap();
+        assert!(component.focused.is_some());
+        assert!(component.handle_key_event(KeyEvent::from(KeyCode::Char('t'))).unwrap().is_none());
+        assert!(component.handle_key_event(KeyEvent::from(KeyCode::Char('T'))).unwrap().is_none());
+
+        component.handle_key_event(KeyEvent::from(KeyCode::Esc)).unwrap();
+        assert!(component.snapshot.is_none());
+        assert!(component.live_mode.load(Ordering::Relaxed));
+        assert!(Arc::ptr_eq(&component.store, &component.live));
+        assert_eq!(component.store.with_view(|records| records.len()), 1);
+    }
+
+    #[test]
+    fn stopping_capture_confirms_dropping_closed_connections() {
+        let mut component = component();
+        let key = |component: &mut ConnectionsComponent| {
+            component.handle_key_event(KeyEvent::from(KeyCode::Char('c'))).unwrap()
+        };
+        assert!(key(&mut component).is_none());
+        assert!(component.capture_mode.load(Ordering::Relaxed));
+        component.store.push(true, conns(&["a"]));
+        component.store.push(true, conns(&[]));
+
+        let action = key(&mut component);
+        assert!(
+            matches!(action, Some(Action::ConfirmRequest(_, action)) if matches!(*action, Action::CaptureStop))
+        );
+        assert!(component.capture_mode.load(Ordering::Relaxed));
+        component.update(Action::CaptureStop).unwrap();
+        assert!(!component.capture_mode.load(Ordering::Relaxed));
+    }
+
     #[test]
     fn pending_width_delta_prefers_latest_fixed_width() {
         let mut component = component();
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use const_format::concatcp;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::Frame;
@@ -6,9 +8,8 @@ use ratatui::prelude::{Color, Line, Modifier, Span, Style};
 use ratatui::widgets::{Block, BorderType, Paragraph, Wrap};
 
 use super::{Direction, SettingPane};
-use crate::store::connections::{
-    ALIVE_COLUMN_INDEX, CONNECTION_COLS, DEFAULT_CONNECTION_COL_INDICES,
-};
+use crate::store::connections::{ALIVE_COLUMN_INDEX, DEFAULT_CONNECTION_COL_INDICES};
+use crate::store::custom_columns::connection_cols;
 use crate::utils::input::KeyOutcome;
 use crate::utils::symbols::arrow;
 use crate::widgets::shortcut::{Fragment, Shortcut};
@@ -18,7 +19,7 @@ const COLUMN_PAGE_STEP: usize = 4;
 #[derive(Debug, Clone)]
 struct ColumnSettingItem {
     original_index: usize,
-    title: &'static str,
+    title: Cow<'static, str>,
     selected: bool,
 }
 
@@ -226,7 +227,8 @@ impl ColumnsSettingPane {
         let last_index = self.items.len().saturating_sub(1);
         for (index, item) in self.items.iter().enumerate() {
             let focused = self.focused == index && active;
-            tokens.push(Span::styled(item.title, self.token_style(item.selected, focused)));
+            tokens
+                .push(Span::styled(item.title.as_ref(), self.token_style(item.selected, focused)));
             if index != last_index {
                 tokens.push(Span::raw(" "));
             }
@@ -248,22 +250,25 @@ impl ColumnsSettingPane {
 fn column_items_from_selected(selected_columns: &[usize]) -> Vec<ColumnSettingItem> {
     let selected_columns =
         if selected_columns.is_empty() { DEFAULT_CONNECTION_COL_INDICES } else { selected_columns };
-    let mut items = Vec::with_capacity(CONNECTION_COLS.len());
+    let cols = connection_cols();
+    let mut items = Vec::with_capacity(cols.len());
 
     for &original_index in selected_columns {
         if original_index == ALIVE_COLUMN_INDEX {
             continue;
         }
-        if let Some(def) = CONNECTION_COLS.get(original_index) {
-            items.push(ColumnSettingItem { original_index, title: def.col.title, selected: true });
+        if let Some(def) = cols.get(original_index) {
+            let title = def.col.title.clone();
+            items.push(ColumnSettingItem { original_index, title, selected: true });
         }
     }
 
-    for (original_index, def) in CONNECTION_COLS.iter().enumerate() {
+    for (original_index, def) in cols.iter().enumerate() {
         if original_index == ALIVE_COLUMN_INDEX || selected_columns.contains(&original_index) {
             continue;
         }
-        items.push(ColumnSettingItem { original_index, title: def.col.title, selected: false });
+        let title = def.col.title.clone();
+        items.push(ColumnSettingItem { original_index, title, selected: false });
     }
 
     items
@@ -272,6 +277,7 @@ fn column_items_from_selected(selected_columns: &[usize]) -> Vec<ColumnSettingIt
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::store::connections::CONNECTION_COLS;
 
     #[test]
     fn columns_load_keeps_selected_order_and_appends_unselected() {
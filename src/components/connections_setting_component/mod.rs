@@ -14,7 +14,6 @@ use tokio::sync::mpsc::UnboundedSender;
 
 use crate::action::Action;
 use crate::components::{Component, ComponentId};
-use crate::models::sort::SortSpec;
 use crate::store::connections::with_alive_column;
 use crate::store::connections_setting::ConnectionsSetting;
 use crate::utils::input::KeyOutcome;
@@ -154,17 +153,12 @@ impl ConnectionsSettingComponent {
             // update source ip alias
             setting.source_ip_alias = source_ip_alias;
 
-            // update column and sort
-            let prev_sort = setting
-                .query_state
-                .sort
-                .and_then(|sort| setting.columns.get(sort.col).map(|&col| (col, sort.dir)));
-            setting.query_state.sort = prev_sort.and_then(|(prev_sort_col, dir)| {
-                // try to restore the previous sort column as an index in the new visible columns
-                columns
-                    .iter()
-                    .position(|&col| col == prev_sort_col)
-                    .map(|col| SortSpec { col, dir })
+            // update column and sort, try to restore the previous sort columns as indices in
+            // the new visible columns
+            let prev_columns = std::mem::take(&mut setting.columns);
+            setting.query_state.remap_cols(|visible| {
+                let prev_col = prev_columns.get(visible)?;
+                columns.iter().position(|col| col == prev_col)
             });
             setting.query_state.set_max_cols(columns.len());
             setting.columns = columns;
@@ -1,14 +1,17 @@
 use std::fs::File;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
 use anyhow::{Context, Result};
 use crossterm::event::{KeyCode, KeyEvent};
+use futures_util::FutureExt;
+use futures_util::future::BoxFuture;
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::style::{Color, Style};
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Paragraph};
+use ratatui::widgets::{Block, BorderType, List, ListItem, ListState, Paragraph};
 use serde::Serialize;
 use serde_json::{Serializer, Value};
 use tempfile::{Builder, NamedTempFile};
@@ -18,29 +21,45 @@ use tracing::{error, info, warn};
 
 use crate::action::Action;
 use crate::api::Api;
+use crate::app_message::AppMessage;
 use crate::components::{Component, ComponentId};
-use crate::config::Config;
+use crate::config::{Config, DEFAULT_CORE_CONFIG_SCHEMA};
 use crate::models::CoreConfig;
+use crate::store::load_state::LoadStateCell;
+use crate::store::timeline::{EventKind, Timeline};
+use crate::store::warmup::Warmup;
+use crate::task_manager::TaskManager;
+use crate::utils::byte_size::human_bytes;
 use crate::utils::editor::resolve_editor;
 use crate::utils::input::KeyOutcome;
-use crate::utils::json5_formatter::{Json5Formatter, collect_paths, extract_comments};
+use crate::utils::json_schema;
+use crate::utils::json5_formatter::{
+    Json5Formatter, Section, collect_paths, extract_comments, outline,
+};
+use crate::utils::redact::Redactor;
 use crate::utils::symbols::arrow;
-use crate::utils::text_ui::{dashed_title_line, top_title_line};
+use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT, dashed_title_line, freshness_span};
 use crate::widgets::button::Button;
+use crate::widgets::empty_state::EmptyState;
 use crate::widgets::scrollbar::Scroller;
 use crate::widgets::shortcut::{Fragment, Shortcut};
 
-/// schema for core config JSON
-const DEFAULT_SCHEMA: &str = include_str!("../../.config/core-config.schema.json");
 /// ui hints displayed above the editor.
 const CORE_CONFIG_EDIT_HINTS: [&str; 2] = [
     r#" 1. Partial updates are supported: include only the fields you want to change (e.g. {"tun": {"enable": true}})."#,
     " 2. Not all fields are configurable: only annotated fields are supported, and all fields under `tun` and `tuic-server`.",
 ];
 const COMMENT_STYLE: Style = Style::new().fg(Color::DarkGray);
+/// Max violations listed above the editor, the rest is summarized.
+const MAX_VIOLATION_LINES: usize = 5;
 
 /// Action button labels and constraints
-const ACTIONS: [&str; 5] = ["Reload", "Restart", "Flush FakeIP", "Flush DNS", "Update GEO"];
+const ACTIONS: [&str; 6] =
+    ["Reload", "Restart", "Flush FakeIP", "Flush DNS", "Update GEO", "Update UI"];
+/// Index of the `Restart` action, confirmed before it interrupts every connection
+pub const RESTART_ACTION: usize = 1;
+/// Index of the `Update GEO` action, only one update runs at a time across tabs
+const GEO_ACTION: usize = 4;
 const ACTION_CONSTRAINTS: [Constraint; ACTIONS.len()] = [Constraint::Min(1); ACTIONS.len()];
 
 #[derive(Debug, Default)]
@@ -53,14 +72,52 @@ pub struct CoreConfigComponent {
     store: Arc<RwLock<String>>,
     editor_state: EditorState,
     modified: Arc<AtomicBool>,
+    validation: Validation,
+    /// Profile the pending edits were imported from, marked applied once they are submitted
+    profile: Option<String>,
 
     line_count: Arc<AtomicUsize>,
+    /// Summary of the `external-ui` fields of the running config
+    external_ui: Arc<RwLock<String>>,
+    /// Top-level sections of the displayed config
+    outline: Arc<RwLock<Vec<Section>>>,
+    /// Selected section while the outline sidebar is open
+    outline_state: Option<ListState>,
     scroller: Scroller,
+    /// Line count the scroll position was last set for, a refresh keeps the position unless the
+    /// content changed a lot
+    scrolled_lines: usize,
 
     loading: Arc<AtomicBool>,
+    progress: Arc<LoadProgress>,
+    load_state: Arc<LoadStateCell>,
     throbber: ThrobberState,
 }
 
+/// Progress of the current core config fetch, huge configs take seconds to fetch and format.
+#[derive(Debug, Default)]
+struct LoadProgress {
+    received: AtomicUsize,
+    formatting: AtomicBool,
+}
+
+impl LoadProgress {
+    fn reset(&self) {
+        self.received.store(0, Ordering::Relaxed);
+        self.formatting.store(false, Ordering::Relaxed);
+    }
+
+    fn label(&self) -> String {
+        if self.formatting.load(Ordering::Relaxed) {
+            return "Formatting".into();
+        }
+        match self.received.load(Ordering::Relaxed) {
+            0 => "Loading".into(),
+            received => format!("Loading {}", human_bytes(received as f64, None)),
+        }
+    }
+}
+
 /// Async task execution context (`'static + Send`).
 /// Contains only shared, thread-safe state; no UI-only fields.
 #[derive(Clone, Debug)]
@@ -68,8 +125,12 @@ struct TaskContext {
     api: Arc<Api>,
     store: Arc<RwLock<String>>,
     line_count: Arc<AtomicUsize>,
+    external_ui: Arc<RwLock<String>>,
+    outline: Arc<RwLock<Vec<Section>>>,
     modified: Arc<AtomicBool>,
     loading: Arc<AtomicBool>,
+    progress: Arc<LoadProgress>,
+    load_state: Arc<LoadStateCell>,
     app_config: Arc<Config>,
 }
 
@@ -81,6 +142,15 @@ enum EditorState {
     SyncFailed,
 }
 
+/// Dry-run result of the edited config, submission is only offered once it passed.
+#[derive(Debug, Default)]
+enum Validation {
+    #[default]
+    Pending,
+    Passed,
+    Failed(Vec<String>),
+}
+
 #[derive(Copy, Clone, Debug, Default)]
 enum ActivePane {
     #[default]
@@ -112,32 +182,63 @@ impl CoreConfigComponent {
             api: Arc::clone(self.api.as_ref().unwrap()),
             store: Arc::clone(&self.store),
             line_count: Arc::clone(&self.line_count),
+            external_ui: Arc::clone(&self.external_ui),
+            outline: Arc::clone(&self.outline),
             modified: Arc::clone(&self.modified),
             loading: Arc::clone(&self.loading),
+            progress: Arc::clone(&self.progress),
+            load_state: Arc::clone(&self.load_state),
             app_config: Arc::clone(self.config.as_ref().unwrap()),
         }
     }
 
     fn load_core_config(&mut self) -> Result<()> {
         info!("Loading core config");
+        self.validation = Validation::Pending;
         let ctx = self.task_context();
+        ctx.loading.store(true, Ordering::Relaxed);
+        // an explicit reload discards the pending edits
+        ctx.modified.store(false, Ordering::Relaxed);
+        self.profile = None;
 
-        tokio::task::Builder::new().name("core-config-loader").spawn(async move {
+        TaskManager::spawn(ComponentId::Config, "core-config-loader", async move {
             Self::refresh_core_config(ctx).await;
+            Warmup::ready(ComponentId::Config);
         })?;
         Ok(())
     }
 
     async fn refresh_core_config(ctx: TaskContext) {
-        match ctx
+        ctx.progress.reset();
+        let result = match ctx
             .api
-            .get_core_config()
+            .get_core_config_with_progress(&ctx.progress.received)
             .await
             .with_context(|| "failed to get core config from mihomo API")
-            .and_then(|config| Self::pretty_print_core_config(&ctx, config))
         {
+            Ok(config) => {
+                *ctx.external_ui.write().unwrap() = external_ui_summary(&config);
+                ctx.progress.formatting.store(true, Ordering::Relaxed);
+                // formatting thousands of rules blocks for a while, keep it off the runtime
+                let format_ctx = ctx.clone();
+                tokio::task::spawn_blocking(move || {
+                    Self::pretty_print_core_config(&format_ctx, config)
+                })
+                .await
+                .with_context(|| "failed to join core config formatter")
+                .and_then(|result| result)
+            }
+            Err(e) => Err(e),
+        };
+        ctx.load_state.set_result(&result);
+        match result {
+            Ok(_) if ctx.modified.load(Ordering::Relaxed) => {
+                // edits synced from the external editor while the config was in flight
+                info!("Core config edited during the refresh, keeping the edits");
+            }
             Ok(config) => {
                 ctx.line_count.store(config.lines().count(), Ordering::Relaxed);
+                *ctx.outline.write().unwrap() = outline(&config);
                 ctx.modified.store(false, Ordering::Relaxed);
 
                 let mut writable = ctx.store.write().unwrap();
@@ -175,7 +276,7 @@ impl CoreConfigComponent {
                 serde_json::from_reader(file)
                     .with_context(|| format!("failed to parse core config schema file: {:?}", path))
             }
-            None => serde_json::from_str(DEFAULT_SCHEMA)
+            None => serde_json::from_str(DEFAULT_CORE_CONFIG_SCHEMA)
                 .with_context(|| "failed to parse builtin core config schema file"),
         }
     }
@@ -207,7 +308,9 @@ impl CoreConfigComponent {
                 content != *readable
             };
             if modified {
+                self.validation = Validation::Pending;
                 self.line_count.store(content.lines().count(), Ordering::Relaxed);
+                *self.outline.write().unwrap() = outline(&content);
                 self.scroller.first();
                 let mut writable = self.store.write().unwrap();
                 *writable = content;
@@ -219,6 +322,45 @@ impl CoreConfigComponent {
         Ok(())
     }
 
+    /// Validates the edited core configuration without applying it.
+    ///
+    /// Mihomo has no dry-run for `PATCH /configs`, so the content is checked locally: it must
+    /// parse as JSON5 and conform to the core config schema.
+    fn validate_core_config(&mut self) -> Vec<String> {
+        let value: Value = match json5::from_str(&self.store.read().unwrap()) {
+            Ok(value) => value,
+            Err(e) => return vec![format!("invalid JSON5: {e}")],
+        };
+        let schema = self
+            .config
+            .as_deref()
+            .map(Self::load_config_schema)
+            .transpose()
+            .unwrap_or_else(|err| {
+                error!(error = ?err, "load core config schema failed, skip schema validation");
+                None
+            })
+            .unwrap_or(Value::Null);
+        json_schema::validate(&schema, &value).iter().map(ToString::to_string).collect()
+    }
+
+    /// Validates on first confirm, submits on the next confirm once validation passed.
+    fn validate_or_submit(&mut self) -> Result<()> {
+        if !self.modified.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        if matches!(self.validation, Validation::Passed) {
+            return self.submit_core_config();
+        }
+
+        let violations = self.validate_core_config();
+        info!(violations = violations.len(), "Core config validated");
+        self.validation =
+            if violations.is_empty() { Validation::Passed } else { Validation::Failed(violations) };
+        self.action_tx.as_ref().unwrap().send(Action::Shortcuts(self.shortcuts()))?;
+        Ok(())
+    }
+
     /// Submits the edited core configuration to the API.
     ///
     /// Skips the submission if a loading process is already in progress to avoid state conflicts.
@@ -243,18 +385,25 @@ impl CoreConfigComponent {
 
         let ctx = self.task_context();
         let action_tx = self.action_tx.as_ref().unwrap().clone();
+        let profile = self.profile.take();
 
+        self.validation = Validation::Pending;
         ctx.loading.store(true, Ordering::Relaxed);
-        tokio::task::Builder::new().name("core-config-submitter").spawn(async move {
+        TaskManager::spawn(ComponentId::Config, "core-config-submitter", async move {
             match ctx.api.update_core_config(content).await {
                 Ok(_) => {
                     info!("Core config successfully submitted");
+                    Timeline::record(EventKind::Config, "Core config submitted");
                     ctx.modified.store(false, Ordering::Relaxed);
+                    if let Some(name) = profile {
+                        let _ = action_tx.send(Action::ProfileApplied(name));
+                    }
                     Self::refresh_core_config(ctx).await;
                 }
                 Err(e) => {
                     error!(error = ?e, "Failed to submit core config to mihomo API");
-                    let _ = action_tx.send(Action::Error(("Submit core config", e).into()));
+                    let _ = action_tx
+                        .send(Action::Error(ComponentId::Config, ("Submit core config", e).into()));
                     ctx.loading.store(false, Ordering::Relaxed);
                 }
             }
@@ -262,7 +411,25 @@ impl CoreConfigComponent {
         Ok(())
     }
 
-    fn handle_action_button(&mut self, idx: usize) -> Result<()> {
+    /// Restart asks for confirmation first, the GEO update is run by the root like the other
+    /// ones, the other actions run right away.
+    fn handle_action_button(&mut self, idx: usize) -> Result<Option<Action>> {
+        if idx == GEO_ACTION {
+            return Ok(Some(Action::GeoUpdate));
+        }
+        if idx == RESTART_ACTION {
+            let msg = AppMessage::from((
+                "Restart",
+                "Restart the mihomo core? Every connection will be dropped.",
+            ))
+            .msg_box_size(50, 25);
+            return Ok(Some(Action::ConfirmRequest(msg, Box::new(Action::CoreActionRun(idx)))));
+        }
+        self.run_core_action(idx)?;
+        Ok(None)
+    }
+
+    fn run_core_action(&mut self, idx: usize) -> Result<()> {
         let action_name = match ACTIONS.get(idx) {
             Some(name) => *name,
             None => return Ok(()),
@@ -272,25 +439,39 @@ impl CoreConfigComponent {
             return Ok(());
         }
 
-        info!("Triggering core action '{}'", action_name);
-        let ctx = self.task_context();
         let action_tx = self.action_tx.as_ref().unwrap().clone();
+        let ctx = self.task_context();
+        let api = Arc::clone(&ctx.api);
+        // picked before the task starts, so every started task resets `loading`
+        let request: BoxFuture<'static, Result<()>> = match idx {
+            0 => async move { api.reload_config().await }.boxed(),
+            1 => async move { api.restart().await }.boxed(),
+            2 => async move { api.flush_fake_ip_cache().await }.boxed(),
+            3 => async move { api.flush_dns_cache().await }.boxed(),
+            5 => async move { api.upgrade_ui().await }.boxed(),
+            _ => return Ok(()),
+        };
+        info!("Triggering core action '{}'", action_name);
 
         ctx.loading.store(true, Ordering::Relaxed);
-        tokio::task::Builder::new().name("core-action-trigger").spawn(async move {
-            let result = match idx {
-                0 => ctx.api.reload_config().await,
-                1 => ctx.api.restart().await,
-                2 => ctx.api.flush_fake_ip_cache().await,
-                3 => ctx.api.flush_dns_cache().await,
-                4 => ctx.api.update_geo().await,
-                _ => return,
-            };
+        TaskManager::spawn(ComponentId::Config, "core-action-trigger", async move {
+            let started = Instant::now();
+            let result = request.await;
+            let elapsed = format!("{:.1}s", started.elapsed().as_secs_f64());
             match result {
-                Ok(_) => info!("Core action '{}' completed successfully", action_name),
+                Ok(_) => {
+                    info!("Core action '{}' completed successfully in {}", action_name, elapsed);
+                    Timeline::record(EventKind::Core, action_name);
+                    let msg = format!("{action_name} succeeded in {elapsed}.");
+                    let _ = action_tx.send(Action::Info(
+                        AppMessage::from((action_name, msg)).msg_box_size(40, 20),
+                    ));
+                }
                 Err(e) => {
                     error!(error = ?e, action = action_name, "Core action failed");
-                    let _ = action_tx.send(Action::Error((action_name, e).into()));
+                    let e = e.context(format!("{action_name} failed after {elapsed}"));
+                    let _ =
+                        action_tx.send(Action::Error(ComponentId::Config, (action_name, e).into()));
                 }
             }
             ctx.loading.store(false, Ordering::Relaxed);
@@ -298,6 +479,63 @@ impl CoreConfigComponent {
         Ok(())
     }
 
+    /// The running config that imports and profiles are diffed against, pending edits would be
+    /// mixed up with it.
+    fn running_config(&self) -> Result<Value> {
+        anyhow::ensure!(
+            !self.modified.load(Ordering::Relaxed),
+            "Submit or discard the pending edits first"
+        );
+        let content = self.store.read().unwrap();
+        json5::from_str(&content).context("Failed to parse running config")
+    }
+
+    /// Put the imported changes into the editor and validate them, they are submitted with ↵ like
+    /// any other edit.
+    fn apply_imported(&mut self, patch: Value, profile: Option<String>) -> Result<()> {
+        let content = serde_json::to_string_pretty(&patch)?;
+        self.line_count.store(content.lines().count(), Ordering::Relaxed);
+        *self.outline.write().unwrap() = outline(&content);
+        self.scroller.first();
+        *self.store.write().unwrap() = content;
+        self.modified.store(true, Ordering::Relaxed);
+        self.validation = Validation::Pending;
+        self.profile = profile;
+
+        self.validate_or_submit()
+    }
+
+    /// Ask to flip `sniffing`. It is the only sniffer field the controller accepts in a config
+    /// patch, the ports and force/skip domains are only read from the core's config file.
+    fn confirm_sniffing(&self) -> Result<Action> {
+        let running = self.running_config()?;
+        let enabled = running.get("sniffing").and_then(Value::as_bool).unwrap_or_default();
+        let msg = format!(
+            "Turn sniffing {} on the running core? Sniffer ports and domains can't be patched \
+             through the controller, edit them in the core's config file.",
+            if enabled { "off" } else { "on" }
+        );
+        let msg = AppMessage::from(("Sniffing", msg)).msg_box_size(50, 25);
+        Ok(Action::ConfirmRequest(msg, Box::new(Action::SniffingSet(!enabled))))
+    }
+
+    /// Submit the confirmed `sniffing` patch.
+    fn set_sniffing(&mut self, enabled: bool) -> Result<()> {
+        self.apply_imported(serde_json::json!({ "sniffing": enabled }), None)?;
+        if matches!(self.validation, Validation::Passed) {
+            self.submit_core_config()?;
+        }
+        Ok(())
+    }
+
+    /// Convert the displayed config (including unsubmitted edits) to YAML.
+    fn export_yaml(&self) -> Result<String> {
+        let content = self.store.read().unwrap();
+        let mut value: Value = json5::from_str(&content).context("Failed to parse config")?;
+        Redactor::json(&mut value);
+        yaml_serde::to_string(&value).context("Failed to convert config to YAML")
+    }
+
     fn handle_global_key_event(&mut self, key: KeyEvent) -> KeyOutcome {
         let is_editor = matches!(self.active_pane, ActivePane::Editor);
 
@@ -314,6 +552,43 @@ impl CoreConfigComponent {
                 let _ = self.action_tx.as_ref().unwrap().send(Action::DnsQuery);
                 (false, KeyOutcome::Consumed)
             }
+            KeyCode::Char('t') => {
+                let _ = self.action_tx.as_ref().unwrap().send(Action::TcpPing);
+                (false, KeyOutcome::Consumed)
+            }
+            KeyCode::Char('i') => {
+                let action = match self.running_config() {
+                    Ok(running) => Action::CoreConfigImport(running),
+                    Err(e) => Action::Error(ComponentId::Config, ("Import core config", e).into()),
+                };
+                let _ = self.action_tx.as_ref().unwrap().send(action);
+                (false, KeyOutcome::Consumed)
+            }
+            KeyCode::Char('p') => {
+                let action = match self.running_config() {
+                    Ok(running) => Action::Profiles(running),
+                    Err(e) => {
+                        Action::Error(ComponentId::Config, ("Core config profiles", e).into())
+                    }
+                };
+                let _ = self.action_tx.as_ref().unwrap().send(action);
+                (false, KeyOutcome::Consumed)
+            }
+            KeyCode::Char('s') => {
+                let action = self
+                    .confirm_sniffing()
+                    .unwrap_or_else(|e| Action::Error(ComponentId::Config, ("Sniffing", e).into()));
+                let _ = self.action_tx.as_ref().unwrap().send(action);
+                (false, KeyOutcome::Consumed)
+            }
+            KeyCode::Char('x') => {
+                let action = match self.export_yaml() {
+                    Ok(yaml) => Action::CoreConfigExport(yaml),
+                    Err(e) => Action::Error(ComponentId::Config, ("Export core config", e).into()),
+                };
+                let _ = self.action_tx.as_ref().unwrap().send(action);
+                (false, KeyOutcome::Consumed)
+            }
             _ => (false, KeyOutcome::Ignored),
         };
 
@@ -324,6 +599,63 @@ impl CoreConfigComponent {
         key_out
     }
 
+    /// Open the outline on the section shown at the top of the editor, or close it.
+    fn toggle_outline(&mut self) {
+        if self.outline_state.take().is_none() {
+            let pos = self.scroller.pos();
+            let outline = self.outline.read().unwrap();
+            let selected = outline.iter().rposition(|s| s.line <= pos);
+            let selected = selected.or((!outline.is_empty()).then_some(0));
+            self.outline_state = Some(ListState::default().with_selected(selected));
+        }
+        let _ = self.action_tx.as_ref().unwrap().send(Action::Shortcuts(self.shortcuts()));
+    }
+
+    /// Select a section by `(selected, len) -> index` and scroll the editor to it.
+    fn select_section(&mut self, f: impl FnOnce(usize, usize) -> usize) {
+        let outline = Arc::clone(&self.outline);
+        let outline = outline.read().unwrap();
+        let Some(state) = self.outline_state.as_mut() else {
+            return;
+        };
+        if outline.is_empty() {
+            return;
+        }
+        let idx = f(state.selected().unwrap_or(0), outline.len()).min(outline.len() - 1);
+        state.select(Some(idx));
+        let max_pos =
+            self.scroller.content_length().saturating_sub(self.scroller.viewport_content_length());
+        self.scroller.position(outline[idx].line.min(max_pos));
+    }
+
+    fn handle_outline_key_event(&mut self, key: KeyEvent) -> KeyOutcome {
+        match key.code {
+            KeyCode::Char('k') | KeyCode::Up => self.select_section(|i, _| i.saturating_sub(1)),
+            KeyCode::Char('j') | KeyCode::Down => self.select_section(|i, _| i + 1),
+            KeyCode::Char('g') => self.select_section(|_, _| 0),
+            KeyCode::Char('G') => self.select_section(|_, len| len),
+            KeyCode::Char('o') | KeyCode::Enter | KeyCode::Esc => self.toggle_outline(),
+            _ => return KeyOutcome::Ignored,
+        }
+        KeyOutcome::Consumed
+    }
+
+    fn render_outline(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(state) = self.outline_state.as_mut() else {
+            return;
+        };
+        let outline = self.outline.read().unwrap();
+        let items = outline.iter().map(|s| ListItem::new(s.key.as_str()));
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(Span::raw(" outline ").into_centered_line());
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, state);
+    }
+
     fn render_edit_hints(&mut self, frame: &mut Frame, area: Rect) {
         let notes = CORE_CONFIG_EDIT_HINTS
             .iter()
@@ -334,10 +666,15 @@ impl CoreConfigComponent {
     }
 
     fn render_cfg_content(&mut self, frame: &mut Frame, area: Rect) {
-        self.scroller.length(
-            self.line_count.load(Ordering::Relaxed),
-            area.height.saturating_sub(2) as usize,
-        );
+        let line_count = self.line_count.load(Ordering::Relaxed);
+        let viewport = area.height.saturating_sub(2) as usize;
+        if line_count != self.scrolled_lines {
+            let pos =
+                retained_position(self.scroller.pos(), self.scrolled_lines, line_count, viewport);
+            self.scroller.position(pos);
+            self.scrolled_lines = line_count;
+        }
+        self.scroller.length(line_count, viewport);
         let title = if self.modified.load(Ordering::Relaxed) {
             Span::styled(" core config * ", Style::default().fg(Color::Yellow))
         } else {
@@ -372,37 +709,92 @@ impl CoreConfigComponent {
             let paragraph =
                 Paragraph::new(lines).scroll((self.scroller.pos() as u16, 0)).block(block);
             frame.render_widget(paragraph, area);
+
+            if content.is_empty() {
+                EmptyState::new(self.load_state.get(), "core config").render(
+                    frame,
+                    area.inner(Margin::new(1, 1)),
+                    &mut self.throbber,
+                );
+            }
         }
         self.scroller.render(frame, area);
         self.render_throbber(frame, area);
     }
 
+    fn validation_lines(&self) -> Vec<Line<'_>> {
+        match &self.validation {
+            Validation::Pending => vec![],
+            Validation::Passed => vec![Line::styled(
+                " ✓ Validation passed, press ↵ again to submit.",
+                Style::default().fg(Color::Green),
+            )],
+            Validation::Failed(violations) => {
+                let style = Style::default().fg(Color::Red);
+                let mut lines = vec![Line::styled(
+                    format!(" ✗ Validation failed with {} error(s):", violations.len()),
+                    style,
+                )];
+                lines.extend(
+                    violations
+                        .iter()
+                        .take(MAX_VIOLATION_LINES)
+                        .map(|v| Line::styled(format!("   {v}"), style)),
+                );
+                if violations.len() > MAX_VIOLATION_LINES {
+                    let more = violations.len() - MAX_VIOLATION_LINES;
+                    lines.push(Line::styled(format!("   ... and {more} more"), style));
+                }
+                lines
+            }
+        }
+    }
+
     fn render_cfg_preview(&mut self, frame: &mut Frame, area: Rect) {
-        let chunks = Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).split(area);
+        let validation = self.validation_lines();
+        let chunks = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(validation.len() as u16),
+            Constraint::Min(1),
+        ])
+        .split(area);
+        frame.render_widget(Paragraph::new(validation), chunks[1]);
         self.render_edit_hints(frame, chunks[0]);
-        self.render_cfg_content(frame, chunks[1]);
+        if self.outline_state.is_none() {
+            self.render_cfg_content(frame, chunks[2]);
+            return;
+        }
+        let key_width = self.outline.read().unwrap().iter().map(|s| s.key.len()).max();
+        let width = (key_width.unwrap_or_default() as u16 + 4).clamp(12, 28);
+        let [outline_area, content_area] =
+            Layout::horizontal([Constraint::Length(width), Constraint::Min(1)]).areas(chunks[2]);
+        self.render_outline(frame, outline_area);
+        self.render_cfg_content(frame, content_area);
     }
 
     fn render_throbber(&mut self, frame: &mut Frame, area: Rect) {
         if !self.loading.load(Ordering::Relaxed) {
             return;
         }
+        let label = self.progress.label();
+        let width = label.len() as u16 + 2;
         let symbol = Throbber::default()
-            .label("Loading")
+            .label(label)
             .style(Style::default().fg(Color::White).bg(Color::Green).bold())
             .throbber_style(Style::default().fg(Color::White).bg(Color::Green).bold())
             .throbber_set(BRAILLE_SIX)
             .use_type(WhichUse::Spin);
         frame.render_stateful_widget(
             symbol,
-            Rect::new(area.right().saturating_sub(10), area.y, 9, 1),
+            Rect::new(area.right().saturating_sub(width + 1), area.y, width, 1),
             &mut self.throbber,
         );
     }
 
     fn render_actions(&mut self, frame: &mut Frame, area: Rect) {
-        let [title_area, buttons_area] =
-            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+        let [title_area, buttons_area, external_ui_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0), Constraint::Length(1)])
+                .areas(area);
         let title = dashed_title_line("actions", area.width - 4).centered();
         frame.render_widget(title, title_area);
 
@@ -412,6 +804,43 @@ impl CoreConfigComponent {
                 matches!(self.active_pane, ActivePane::Action(active_idx) if active_idx == idx);
             frame.render_widget(Button::new(label).active(active), chunks[idx]);
         }
+        let external_ui = self.external_ui.read().unwrap();
+        frame.render_widget(
+            Line::styled(format!(" {external_ui}"), COMMENT_STYLE),
+            external_ui_area,
+        );
+    }
+}
+
+/// Scroll position after the content changed from `old` to `new` lines: kept, within the last
+/// page, unless the length changed by more than half.
+fn retained_position(pos: usize, old: usize, new: usize, viewport: usize) -> usize {
+    if new.abs_diff(old) > old / 2 {
+        return 0;
+    }
+    pos.min(new.saturating_sub(viewport))
+}
+
+/// One line describing the `external-ui` fields of `config`.
+///
+/// Most cores keep the controller settings out of `GET /configs`, they are only shown if reported.
+fn external_ui_summary(config: &CoreConfig) -> String {
+    let field = |key: &str| config.get(key).and_then(Value::as_str).filter(|v| !v.is_empty());
+    match (field("external-ui"), field("external-ui-name"), field("external-ui-url")) {
+        (None, None, None) => {
+            "external UI: not reported by the core, `Update UI` uses its configured `external-ui`"
+                .into()
+        }
+        (dir, name, url) => {
+            let mut summary = format!("external UI: {}", dir.unwrap_or("-"));
+            if let Some(name) = name {
+                summary.push_str(&format!(" ({name})"));
+            }
+            if let Some(url) = url {
+                summary.push_str(&format!(", from {url}"));
+            }
+            summary
+        }
     }
 }
 
@@ -422,6 +851,16 @@ impl Component for CoreConfigComponent {
 
     fn shortcuts(&self) -> Vec<Shortcut> {
         match self.active_pane {
+            ActivePane::Editor if self.outline_state.is_some() => {
+                vec![
+                    Shortcut::new(vec![
+                        Fragment::hl(arrow::UP),
+                        Fragment::raw(" section "),
+                        Fragment::hl(arrow::DOWN),
+                    ]),
+                    Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("o")]),
+                ]
+            }
             ActivePane::Editor => {
                 vec![
                     Shortcut::new(vec![
@@ -457,8 +896,21 @@ impl Component for CoreConfigComponent {
                     ]),
                     Shortcut::from("edit", 0).unwrap(),
                     Shortcut::from("discard", 0).unwrap(),
-                    Shortcut::new(vec![Fragment::raw("submit "), Fragment::hl("↵")]),
+                    Shortcut::from("outline", 0).unwrap(),
+                    Shortcut::new(vec![
+                        Fragment::raw(if matches!(self.validation, Validation::Passed) {
+                            "submit "
+                        } else {
+                            "validate "
+                        }),
+                        Fragment::hl("↵"),
+                    ]),
                     Shortcut::from("dns", 1).unwrap(),
+                    Shortcut::from("tcp ping", 0).unwrap(),
+                    Shortcut::from("import", 0).unwrap(),
+                    Shortcut::from("export", 1).unwrap(),
+                    Shortcut::from("profiles", 0).unwrap(),
+                    Shortcut::from("sniffing", 0).unwrap(),
                 ]
             }
             ActivePane::Action(_) => {
@@ -475,6 +927,11 @@ impl Component for CoreConfigComponent {
                     ]),
                     Shortcut::new(vec![Fragment::raw("execute "), Fragment::hl("↵")]),
                     Shortcut::from("dns", 1).unwrap(),
+                    Shortcut::from("tcp ping", 0).unwrap(),
+                    Shortcut::from("import", 0).unwrap(),
+                    Shortcut::from("export", 1).unwrap(),
+                    Shortcut::from("profiles", 0).unwrap(),
+                    Shortcut::from("sniffing", 0).unwrap(),
                 ]
             }
         }
@@ -506,6 +963,10 @@ impl Component for CoreConfigComponent {
 
         match self.active_pane {
             ActivePane::Editor => {
+                if self.outline_state.is_some() && self.handle_outline_key_event(key).is_consumed()
+                {
+                    return Ok(None);
+                }
                 if self.scroller.handle_key_event(key).is_consumed() {
                     return Ok(None);
                 }
@@ -513,9 +974,13 @@ impl Component for CoreConfigComponent {
                 match key.code {
                     KeyCode::Char('e') => return self.edit_core_config(),
                     KeyCode::Char('d') => self.load_core_config()?,
+                    KeyCode::Char('o') => self.toggle_outline(),
                     KeyCode::Enter => {
-                        return self.submit_core_config().map(|_| None).or_else(|e| {
-                            Ok(Some(Action::Error(("Submit core config", e).into())))
+                        return self.validate_or_submit().map(|_| None).or_else(|e| {
+                            Ok(Some(Action::Error(
+                                ComponentId::Config,
+                                ("Submit core config", e).into(),
+                            )))
                         });
                     }
                     _ => (),
@@ -524,7 +989,7 @@ impl Component for CoreConfigComponent {
 
             ActivePane::Action(idx) => {
                 if key.code == KeyCode::Enter {
-                    self.handle_action_button(idx)?
+                    return self.handle_action_button(idx);
                 }
             }
         }
@@ -533,11 +998,38 @@ impl Component for CoreConfigComponent {
     }
 
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        // keep the editor content, only pick up the new app config for later tasks
+        if let Action::ConfigReloaded(config) = action {
+            self.config = Some(config);
+            return Ok(None);
+        }
+        if let Action::CoreActionRun(idx) = action {
+            self.run_core_action(idx)?;
+            return Ok(None);
+        }
+        if let Action::SniffingSet(enabled) = action {
+            if let Err(e) = self.set_sniffing(enabled) {
+                return Ok(Some(Action::Error(ComponentId::Config, ("Sniffing", e).into())));
+            }
+            return Ok(None);
+        }
+        if let Action::CoreConfigImported(patch, profile) = action {
+            if let Err(e) = self.apply_imported(patch, profile) {
+                return Ok(Some(Action::Error(
+                    ComponentId::Config,
+                    ("Import core config", e).into(),
+                )));
+            }
+            return Ok(None);
+        }
         if let Action::Tick = action {
             if let Err(err) = self.sync_core_config() {
                 self.editor_state = EditorState::SyncFailed;
                 error!(error = ?err, "Failed to sync config from external editor");
-                return Ok(Some(Action::Error(("Sync config from external editor", err).into())));
+                return Ok(Some(Action::Error(
+                    ComponentId::Config,
+                    ("Sync config from external editor", err).into(),
+                )));
             }
             if self.loading.load(Ordering::Relaxed) {
                 self.throbber.calc_next();
@@ -549,16 +1041,94 @@ impl Component for CoreConfigComponent {
 
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
         // render border and title
-        let title_line = top_title_line("config", Style::default());
+        let mut title_line = Line::from(vec![Span::raw(TOP_TITLE_LEFT), Span::raw("config")]);
+        title_line.extend(freshness_span(self.load_state.updated_at()));
+        title_line.push_span(Span::raw(TOP_TITLE_RIGHT));
         let block = Block::bordered().border_type(BorderType::Rounded).title(title_line);
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
         // render content
-        let chunks = Layout::vertical([Constraint::Min(0), Constraint::Length(4)]).split(inner);
+        let chunks = Layout::vertical([Constraint::Min(0), Constraint::Length(5)]).split(inner);
         self.render_cfg_preview(frame, chunks[0]);
         self.render_actions(frame, chunks[1]);
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_progress_label() {
+        let progress = LoadProgress::default();
+        assert_eq!(progress.label(), "Loading");
+        progress.received.store(2048, Ordering::Relaxed);
+        assert!(progress.label().starts_with("Loading 2"));
+        progress.formatting.store(true, Ordering::Relaxed);
+        assert_eq!(progress.label(), "Formatting");
+        progress.reset();
+        assert_eq!(progress.label(), "Loading");
+    }
+
+    #[test]
+    fn external_ui_summary_lists_reported_fields() {
+        let config = serde_json::json!({
+            "external-ui": "ui",
+            "external-ui-name": "zashboard",
+            "external-ui-url": "https://example.com/dist.zip",
+        });
+        assert_eq!(
+            external_ui_summary(&config),
+            "external UI: ui (zashboard), from https://example.com/dist.zip"
+        );
+        assert!(external_ui_summary(&serde_json::json!({"mode": "rule"})).contains("not reported"));
+    }
+
+    #[test]
+    fn scroll_position_survives_small_changes() {
+        assert_eq!(retained_position(120, 1000, 1004, 40), 120);
+        assert_eq!(retained_position(990, 1000, 980, 40), 940);
+        assert_eq!(retained_position(120, 1000, 300, 40), 0);
+        assert_eq!(retained_position(0, 0, 1000, 40), 0);
+    }
+
+    #[test]
+    fn restart_is_confirmed_first() {
+        let mut component = CoreConfigComponent::default();
+        let action = component.handle_action_button(RESTART_ACTION).unwrap();
+        assert!(matches!(
+            action,
+            Some(Action::ConfirmRequest(_, action))
+                if matches!(*action, Action::CoreActionRun(RESTART_ACTION))
+        ));
+        assert!(!component.loading.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn imported_changes_wait_for_submit() {
+        let mut component = CoreConfigComponent::default();
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        component.register_action_handler(tx).unwrap();
+        let patch = serde_json::json!({ "mode": "rule" });
+        component.apply_imported(patch, Some("home".into())).unwrap();
+        assert!(matches!(component.validation, Validation::Passed));
+        assert!(component.modified.load(Ordering::Relaxed));
+        assert!(!component.loading.load(Ordering::Relaxed));
+        assert_eq!(component.profile.as_deref(), Some("home"));
+    }
+
+    #[test]
+    fn sniffing_toggle_is_confirmed_first() {
+        let component = CoreConfigComponent::default();
+        *component.store.write().unwrap() = r#"{ "sniffing": true }"#.into();
+        let action = component.confirm_sniffing().unwrap();
+        assert!(matches!(
+            action,
+            Action::ConfirmRequest(_, action) if matches!(*action, Action::SniffingSet(false))
+        ));
+        assert!(!component.loading.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,246 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::prelude::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Paragraph};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::info;
+use tui_input::Input;
+
+use crate::action::Action;
+use crate::components::{Component, ComponentId};
+use crate::store::timeline::{EventKind, Timeline};
+use crate::task_manager::BlockingJob;
+use crate::utils::encryption::Encryption;
+use crate::utils::path::expand_home;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::tui_input::input_request;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+const DEFAULT_EXPORT_PATH: &str = "mihomo-config.yaml";
+
+/// Asks for a path and writes the core config, converted to YAML, to it.
+#[derive(Debug, Default)]
+pub struct CoreConfigExportComponent {
+    show: bool,
+    yaml: String,
+    input: Input,
+    /// Path is kept between exports of the session
+    last_path: Option<String>,
+    /// Set once the user was warned the file exists, the next confirm overwrites it
+    confirm_overwrite: bool,
+    error: Option<String>,
+    /// Writing the file, encryption makes it slow
+    job: BlockingJob<PathBuf>,
+
+    action_tx: Option<UnboundedSender<Action>>,
+}
+
+impl CoreConfigExportComponent {
+    fn show(&mut self, yaml: String) {
+        self.show = true;
+        self.yaml = yaml;
+        self.input = self.last_path.as_deref().unwrap_or(DEFAULT_EXPORT_PATH).into();
+        self.confirm_overwrite = false;
+        self.error = None;
+    }
+
+    fn hide(&mut self) {
+        self.show = false;
+        self.yaml.clear();
+        self.input.reset();
+        self.error = None;
+        self.job = BlockingJob::default();
+    }
+
+    /// Starts writing the file in the background, returns `false` if the user has to confirm
+    /// overwriting first.
+    fn export(&mut self) -> Result<bool> {
+        let raw = self.input.value().trim();
+        anyhow::ensure!(!raw.is_empty(), "Path cannot be empty");
+        let path = expand_home(raw);
+        if path.exists() && !self.confirm_overwrite {
+            self.confirm_overwrite = true;
+            return Ok(false);
+        }
+        self.last_path = Some(raw.to_string());
+        let yaml = self.yaml.clone();
+        self.job.start(ComponentId::CoreConfigExport, "core-config-export", move || {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Fail to create directory `{}`", parent.display()))?;
+            }
+            Encryption::write(&path, yaml.as_bytes())?;
+            Ok(path)
+        })?;
+        Ok(true)
+    }
+
+    /// Reports a finished export, returns `true` once the popup can be closed.
+    fn poll(&mut self) -> Result<bool> {
+        match self.job.poll() {
+            Some(Ok(path)) => {
+                info!(path = %path.display(), "Core config exported");
+                Timeline::record(
+                    EventKind::Config,
+                    format!("Core config exported to {}", path.display()),
+                );
+                self.action_tx.as_ref().unwrap().send(Action::Info(
+                    ("Export core config", format!("Exported to `{}`", path.display())).into(),
+                ))?;
+                Ok(true)
+            }
+            Some(Err(e)) => {
+                self.error = Some(format!("{e:#}"));
+                Ok(false)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn render_content(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::vertical([Constraint::Length(3), Constraint::Length(3)]).split(area);
+
+        let block = Block::bordered()
+            .title(if Encryption::enabled() {
+                "Export to (YAML, encrypted)"
+            } else {
+                "Export to (YAML)"
+            })
+            .border_type(BorderType::Rounded)
+            .border_style(Color::Cyan);
+        let width = chunks[0].width.saturating_sub(2) as usize;
+        let scroll = self.input.visual_scroll(width);
+        let paragraph = Paragraph::new(self.input.value()).scroll((0, scroll as u16)).block(block);
+        frame.render_widget(paragraph, chunks[0]);
+        let x = self.input.visual_cursor().max(scroll) - scroll + 1;
+        frame.set_cursor_position((chunks[0].x + x as u16, chunks[0].y + 1));
+
+        let message = match (&self.error, self.confirm_overwrite) {
+            (Some(err), _) => Some((Color::Red, err.as_str())),
+            _ if self.job.is_running() => Some((Color::Yellow, "Exporting...")),
+            (None, true) => Some((Color::Yellow, "File exists, press Enter again to overwrite")),
+            (None, false) => None,
+        };
+        if let Some((color, msg)) = message {
+            let block = Block::bordered().border_type(BorderType::Rounded).border_style(color);
+            let line = Line::from(Span::styled(msg, Style::default().fg(color)));
+            frame.render_widget(Paragraph::new(line).block(block), chunks[1]);
+        }
+    }
+}
+
+impl Component for CoreConfigExportComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::CoreConfigExport
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![Fragment::raw("export "), Fragment::hl("↵")]),
+            Shortcut::new(vec![Fragment::raw("cancel "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Esc => {
+                self.hide();
+                return Ok(Some(Action::Unfocus));
+            }
+            KeyCode::Enter if self.job.is_running() => {}
+            KeyCode::Enter => {
+                self.error = None;
+                if let Err(e) = self.export() {
+                    self.error = Some(format!("{e:#}"));
+                }
+            }
+            _ => {
+                if let Some(req) = input_request(key) {
+                    self.confirm_overwrite = false;
+                    self.error = None;
+                    let _ = self.input.handle(req);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::CoreConfigExport(yaml) => self.show(yaml),
+            Action::Tick if self.show && self.poll()? => {
+                self.hide();
+                return Ok(Some(Action::Unfocus));
+            }
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.show {
+            return Ok(());
+        }
+
+        let area = popup_area(area, 60, 40);
+        frame.render_widget(Clear, area);
+        let area = area.inner(Margin::new(2, 1));
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line("export config", Style::default()));
+        let content_area = block.inner(area);
+        frame.render_widget(block, area);
+        self.render_content(frame, content_area);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::utils::test::poll_until;
+
+    async fn finish(component: &mut CoreConfigExportComponent) {
+        poll_until(|| component.poll().unwrap().then_some(())).await;
+        component.hide();
+    }
+
+    #[tokio::test]
+    async fn existing_file_requires_confirm() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/config.yaml");
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut component = CoreConfigExportComponent::default();
+        component.register_action_handler(tx).unwrap();
+        component.show("mode: rule\n".into());
+        component.input = path.to_str().unwrap().into();
+
+        assert!(component.export().unwrap());
+        finish(&mut component).await;
+        assert_eq!(fs::read_to_string(&path).unwrap(), "mode: rule\n");
+
+        component.show("mode: global\n".into());
+        assert_eq!(component.input.value(), path.to_str().unwrap());
+        assert!(!component.export().unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "mode: rule\n");
+        assert!(component.export().unwrap());
+        finish(&mut component).await;
+        assert_eq!(fs::read_to_string(&path).unwrap(), "mode: global\n");
+    }
+}
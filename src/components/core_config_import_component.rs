@@ -0,0 +1,322 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::prelude::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Paragraph};
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::info;
+use tui_input::Input;
+
+use crate::action::Action;
+use crate::components::{Component, ComponentId};
+use crate::store::timeline::{EventKind, Timeline};
+use crate::task_manager::BlockingJob;
+use crate::utils::encryption::Encryption;
+use crate::utils::json_diff::{self, Change};
+use crate::utils::path::{complete_path, expand_home};
+use crate::utils::symbols::arrow;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::tui_input::input_request;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+/// Reads a local YAML config, shows its difference to the running config and hands the changed
+/// fields to the config tab for submission.
+#[derive(Debug, Default)]
+pub struct CoreConfigImportComponent {
+    show: bool,
+    running: Value,
+    input: Input,
+    /// Path is kept between imports of the session
+    last_path: Option<String>,
+    error: Option<String>,
+    /// Set once the file is parsed, Enter then applies the patch
+    preview: Option<Preview>,
+    /// Reading the file, decryption makes it slow
+    job: BlockingJob<Preview>,
+
+    action_tx: Option<UnboundedSender<Action>>,
+}
+
+#[derive(Debug)]
+struct Preview {
+    path: PathBuf,
+    patch: Value,
+    changes: Vec<Change>,
+    scroll: usize,
+}
+
+impl CoreConfigImportComponent {
+    fn show(&mut self, running: Value) {
+        self.show = true;
+        self.running = running;
+        self.input = self.last_path.clone().unwrap_or_default().into();
+        self.error = None;
+        self.preview = None;
+    }
+
+    fn hide(&mut self) {
+        self.show = false;
+        self.running = Value::Null;
+        self.input.reset();
+        self.error = None;
+        self.preview = None;
+        self.job = BlockingJob::default();
+    }
+
+    /// Starts reading the file in the background, see [`Self::poll`].
+    fn load(&mut self) -> Result<()> {
+        let raw = self.input.value().trim();
+        anyhow::ensure!(!raw.is_empty(), "Path cannot be empty");
+        let path = expand_home(raw);
+        self.last_path = Some(raw.to_string());
+        let running = self.running.clone();
+        self.job.start(ComponentId::CoreConfigImport, "core-config-import", move || {
+            preview(path, &running)
+        })?;
+        Ok(())
+    }
+
+    /// Shows the preview of a file read by [`Self::load`] once it is done.
+    fn poll(&mut self) -> Result<()> {
+        match self.job.poll() {
+            Some(Ok(preview)) => {
+                info!(path = %preview.path.display(), changes = preview.changes.len(), "Config file loaded");
+                self.preview = Some(preview);
+                self.action_tx.as_ref().unwrap().send(Action::Shortcuts(self.shortcuts()))?;
+            }
+            Some(Err(e)) => self.error = Some(format!("{e:#}")),
+            None => (),
+        }
+        Ok(())
+    }
+
+    fn handle_path_key(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Esc => {
+                self.hide();
+                return Ok(Some(Action::Unfocus));
+            }
+            KeyCode::Tab => {
+                if let Some(completed) = complete_path(self.input.value()) {
+                    self.input = completed.into();
+                }
+            }
+            KeyCode::Enter if self.job.is_running() => {}
+            KeyCode::Enter => {
+                self.error = None;
+                if let Err(e) = self.load() {
+                    self.error = Some(format!("{e:#}"));
+                }
+            }
+            _ => {
+                if let Some(req) = input_request(key) {
+                    self.error = None;
+                    let _ = self.input.handle(req);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn handle_preview_key(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let Some(preview) = self.preview.as_mut() else {
+            return Ok(None);
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.preview = None;
+                self.action_tx.as_ref().unwrap().send(Action::Shortcuts(self.shortcuts()))?;
+            }
+            KeyCode::Up | KeyCode::Char('k') => preview.scroll = preview.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                preview.scroll = (preview.scroll + 1).min(preview.changes.len().saturating_sub(1))
+            }
+            KeyCode::Enter => {
+                let preview = self.preview.take().unwrap();
+                Timeline::record(
+                    EventKind::Config,
+                    format!("Core config imported from {}", preview.path.display()),
+                );
+                self.hide();
+                let tx = self.action_tx.as_ref().unwrap();
+                tx.send(Action::CoreConfigImported(preview.patch, None))?;
+                return Ok(Some(Action::Unfocus));
+            }
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn render_path(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::vertical([Constraint::Length(3), Constraint::Length(3)]).split(area);
+
+        let block = Block::bordered()
+            .title("Import from (YAML)")
+            .border_type(BorderType::Rounded)
+            .border_style(Color::Cyan);
+        let width = chunks[0].width.saturating_sub(2) as usize;
+        let scroll = self.input.visual_scroll(width);
+        let paragraph = Paragraph::new(self.input.value()).scroll((0, scroll as u16)).block(block);
+        frame.render_widget(paragraph, chunks[0]);
+        let x = self.input.visual_cursor().max(scroll) - scroll + 1;
+        frame.set_cursor_position((chunks[0].x + x as u16, chunks[0].y + 1));
+
+        let message = match &self.error {
+            Some(err) => Some((Color::Red, err.as_str())),
+            None if self.job.is_running() => Some((Color::Yellow, "Reading...")),
+            None => None,
+        };
+        if let Some((color, msg)) = message {
+            let block = Block::bordered().border_type(BorderType::Rounded).border_style(color);
+            let line = Line::from(Span::styled(msg, Style::default().fg(color)));
+            frame.render_widget(Paragraph::new(line).block(block), chunks[1]);
+        }
+    }
+}
+
+/// Changes of the config file at `path` compared to the `running` config.
+fn preview(path: PathBuf, running: &Value) -> Result<Preview> {
+    // exports may be encrypted
+    let content = Encryption::read_to_string(&path)?;
+    let target: Value = yaml_serde::from_str(&content).context("Fail to parse YAML")?;
+    anyhow::ensure!(target.is_object(), "Config must be a YAML mapping");
+
+    let mut changes = vec![];
+    let patch = json_diff::diff(running, &target, &mut changes)
+        .context("No changes compared to the running config")?;
+    Ok(Preview { path, patch, changes, scroll: 0 })
+}
+
+/// Render config changes as `path: old → new` lines, shared with the profile manager.
+pub(super) fn render_changes(
+    title: String,
+    changes: &[Change],
+    scroll: usize,
+    frame: &mut Frame,
+    area: Rect,
+) {
+    let block =
+        Block::bordered().title(title).border_type(BorderType::Rounded).border_style(Color::Cyan);
+    let lines = changes
+        .iter()
+        .skip(scroll)
+        .map(|change| {
+            let old = change.old.as_ref().map_or("(unset)".to_string(), Value::to_string);
+            Line::from(vec![
+                Span::styled(change.path.as_str(), Style::default().fg(Color::Yellow)),
+                Span::raw(": "),
+                Span::styled(old, Style::default().fg(Color::DarkGray)),
+                Span::raw(format!(" {} ", arrow::RIGHT)),
+                Span::styled(change.new.to_string(), Style::default().fg(Color::Green)),
+            ])
+        })
+        .collect::<Vec<_>>();
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+impl Component for CoreConfigImportComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::CoreConfigImport
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        match self.preview {
+            None => vec![
+                Shortcut::new(vec![Fragment::raw("complete "), Fragment::hl("⇥")]),
+                Shortcut::new(vec![Fragment::raw("load "), Fragment::hl("↵")]),
+                Shortcut::new(vec![Fragment::raw("cancel "), Fragment::hl("Esc")]),
+            ],
+            Some(_) => vec![
+                Shortcut::new(vec![
+                    Fragment::hl(arrow::UP),
+                    Fragment::raw(" scroll "),
+                    Fragment::hl(arrow::DOWN),
+                ]),
+                Shortcut::new(vec![Fragment::raw("apply "), Fragment::hl("↵")]),
+                Shortcut::new(vec![Fragment::raw("back "), Fragment::hl("Esc")]),
+            ],
+        }
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.preview.is_some() {
+            self.handle_preview_key(key)
+        } else {
+            self.handle_path_key(key)
+        }
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::CoreConfigImport(running) => self.show(running),
+            Action::Tick if self.show => self.poll()?,
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.show {
+            return Ok(());
+        }
+
+        let area = popup_area(area, 70, 60);
+        frame.render_widget(Clear, area);
+        let area = area.inner(Margin::new(2, 1));
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line("import config", Style::default()));
+        let content_area = block.inner(area);
+        frame.render_widget(block, area);
+        match &self.preview {
+            Some(preview) => {
+                let title =
+                    format!("{} change(s) from {}", preview.changes.len(), preview.path.display());
+                render_changes(title, &preview.changes, preview.scroll, frame, content_area)
+            }
+            None => self.render_path(frame, content_area),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn preview_diffs_against_running_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        fs::write(&path, "mode: global\nport: 7890\ndns:\n  enable: true\n").unwrap();
+
+        let running = json!({ "mode": "rule", "port": 7890, "dns": { "enable": false } });
+        let loaded = preview(path.clone(), &running).unwrap();
+        assert_eq!(loaded.patch, json!({ "mode": "global", "dns": { "enable": true } }));
+        assert_eq!(loaded.changes.len(), 2);
+
+        let running = json!({ "mode": "global", "port": 7890, "dns": { "enable": true } });
+        let err = preview(path.clone(), &running).unwrap_err();
+        assert!(err.to_string().contains("No changes"));
+
+        fs::write(&path, "- a\n- b\n").unwrap();
+        assert!(preview(path, &running).unwrap_err().to_string().contains("YAML mapping"));
+    }
+}
@@ -20,6 +20,7 @@ use crate::action::Action;
 use crate::api::Api;
 use crate::components::{Component, ComponentId, HORIZ_STEP};
 use crate::models::dns::{DnsAnswer, DnsQueryRequest, DnsQueryResponse, DnsRecordType};
+use crate::task_manager::TaskManager;
 use crate::utils::input::KeyOutcome;
 use crate::utils::text_ui::{popup_area, top_title_line};
 use crate::utils::tui_input::input_request;
@@ -169,13 +170,11 @@ impl DnsQueryComponent {
         self.reset_answers();
         self.loading.store(true, Ordering::Relaxed);
 
-        tokio::task::Builder::new()
-            .name("dns-query")
-            .spawn(async move {
-                let result = api.query_dns(&req).await.map_err(|err| err.to_string());
-                let _ = tx.send(result);
-            })
-            .unwrap();
+        TaskManager::spawn(ComponentId::DnsQuery, "dns-query", async move {
+            let result = api.query_dns(&req).await.map_err(|err| err.to_string());
+            let _ = tx.send(result);
+        })
+        .unwrap();
     }
 
     fn poll_result(&mut self) {
@@ -0,0 +1,162 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::widgets::{
+    Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap,
+};
+
+use crate::action::Action;
+use crate::components::{Component, ComponentId};
+use crate::store::error_log::{ErrorEntry, ErrorLog};
+use crate::utils::symbols::arrow;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::time::{format_datetime, format_time_from_now};
+use crate::widgets::scrollable_navigator::ScrollableNavigator;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+/// Popup reviewing the [`ErrorLog`], newest first, with the full message of the selected error.
+#[derive(Debug, Default)]
+pub struct ErrorLogComponent {
+    errors: Vec<ErrorEntry>,
+    navigator: ScrollableNavigator,
+    table_state: TableState,
+    /// Popups stay registered once closed, only errors reported while open count as viewed
+    shown: bool,
+}
+
+impl ErrorLogComponent {
+    fn refresh(&mut self) {
+        self.errors = ErrorLog::snapshot();
+        ErrorLog::mark_read();
+    }
+}
+
+impl Component for ErrorLogComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::ErrorLog
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![
+                Fragment::hl(arrow::UP),
+                Fragment::raw(" nav "),
+                Fragment::hl(arrow::DOWN),
+            ]),
+            Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.navigator.handle_key_event(false, key).is_consumed() {
+            return Ok(None);
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.shown = false;
+                return Ok(Some(Action::Unfocus));
+            }
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Focus(ComponentId::ErrorLog) => {
+                self.shown = true;
+                self.navigator = Default::default();
+                self.refresh();
+            }
+            Action::Unfocus => self.shown = false,
+            // errors reported while the popup is open count as viewed
+            Action::Tick if self.shown && ErrorLog::unread() > 0 => self.refresh(),
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let area = popup_area(area, 80, 70);
+        frame.render_widget(Clear, area); // clears out the background
+        let title = format!("errors ({})", self.errors.len());
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightRed)
+            .title(top_title_line(&title, Style::default()));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let [list_area, detail_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Percentage(40)]).areas(inner);
+
+        let viewport_len = list_area.height.saturating_sub(2) as usize;
+        self.navigator.length(self.errors.len(), viewport_len);
+        let records = self
+            .errors
+            .get(self.navigator.scroller.pos()..self.navigator.scroller.end_pos())
+            .unwrap_or(&[]);
+        *self.table_state.selected_mut() =
+            self.navigator.focused.map(|v| v.saturating_sub(self.navigator.scroller.pos()));
+
+        let header = Row::new(["AGO", "TIME (UTC)", "SOURCE", "ERROR"])
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .bottom_margin(1);
+        let rows = records.iter().map(|entry| {
+            Row::new([
+                Cell::from(format_time_from_now(entry.time)),
+                Cell::from(format_datetime(entry.time).unwrap_or_default().into_string()),
+                Cell::from(entry.source.full_name()),
+                Cell::from(entry.title),
+            ])
+        });
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Length(19),
+            Constraint::Length(16),
+            Constraint::Fill(1),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .column_spacing(2)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED).fg(Color::Cyan));
+        frame.render_stateful_widget(table, list_area, &mut self.table_state);
+        self.navigator.render(frame, list_area);
+
+        // the full message of the selected error, or the newest one
+        let selected = self.errors.get(self.navigator.focused.unwrap_or(0));
+        let detail = Paragraph::new(selected.map(|entry| &*entry.message).unwrap_or_default())
+            .wrap(Wrap { trim: false })
+            .block(Block::new().borders(Borders::TOP).border_style(Color::DarkGray));
+        frame.render_widget(detail, detail_area);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+    use crate::store::error_log;
+
+    #[test]
+    fn errors_reported_after_closing_stay_unread() {
+        let _guard = error_log::test_lock();
+        let mut component = ErrorLogComponent::default();
+        component.update(Action::Focus(ComponentId::ErrorLog)).unwrap();
+        ErrorLog::record(ComponentId::Rules, &("Load rules", "while open").into());
+        component.update(Action::Tick).unwrap();
+        assert_eq!(ErrorLog::unread(), 0);
+
+        let action = component.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(matches!(action.unwrap(), Some(Action::Unfocus)));
+        component.update(Action::Unfocus).unwrap();
+        ErrorLog::record(ComponentId::Rules, &("Load rules", "while closed").into());
+        component.update(Action::Tick).unwrap();
+        assert_eq!(ErrorLog::unread(), 1);
+        ErrorLog::mark_read();
+    }
+}
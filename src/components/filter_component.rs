@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use anyhow::Result;
@@ -15,31 +16,106 @@ use tui_input::Input;
 use crate::action::Action;
 use crate::api::Api;
 use crate::components::{Component, ComponentId};
+use crate::store::filter_history::FilterHistory;
+use crate::utils::symbols::arrow;
 use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT};
 use crate::utils::tui_input::input_request;
 use crate::widgets::shortcut::{Fragment, Shortcut, ShortcutMode, shortcuts_full_width};
 
+/// Search state of one tab, kept while switching tabs.
 #[derive(Debug, Clone, Default)]
-pub struct FilterComponent {
-    is_active: bool,
-    should_send: bool,
+struct FilterState {
     input: Input,
+    should_send: bool,
     placeholder: Option<String>,
+    /// Position of the recalled history entry, 0 is the newest
+    history_back: Option<usize>,
+    /// Input before recalling history, restored when moving past the newest entry
+    draft: String,
+}
+
+impl FilterState {
+    fn pattern(&self) -> Option<String> {
+        Some(self.input.value().trim()).filter(|s| !s.is_empty()).map(str::to_owned)
+    }
+}
+
+/// Filter bar of the searchable tabs, each tab has its own [`FilterState`] and only receives the
+/// patterns addressed to it.
+#[derive(Debug, Clone, Default)]
+pub struct FilterComponent {
+    is_active: bool,
+    states: HashMap<ComponentId, FilterState>,
+    /// Tab the filter currently applies to
+    tab: ComponentId,
     action_tx: Option<UnboundedSender<Action>>,
     shortcuts_full_width: usize,
 }
 
 impl FilterComponent {
+    fn state(&mut self) -> &mut FilterState {
+        self.states.entry(self.tab).or_default()
+    }
+
     fn send(&mut self) -> Result<()> {
-        if self.is_active && self.should_send {
-            let pattern =
-                Some(str::trim(self.input.value())).filter(|s| !s.is_empty()).map(str::to_owned);
-            self.action_tx.as_ref().unwrap().send(Action::FilterChanged(pattern))?;
-            self.should_send = false;
+        let tab = self.tab;
+        let state = self.state();
+        if state.should_send {
+            state.should_send = false;
+            let pattern = state.pattern();
+            self.action_tx.as_ref().unwrap().send(Action::FilterChanged(tab, pattern))?;
+        }
+
+        Ok(())
+    }
+
+    /// Apply the pattern of the tab switched to again, the tab may have been recreated since.
+    fn restore(&mut self, tab: ComponentId) -> Result<()> {
+        self.tab = tab;
+        if let Some(pattern) = self.state().pattern() {
+            self.action_tx.as_ref().unwrap().send(Action::FilterChanged(tab, Some(pattern)))?;
         }
+        Ok(())
+    }
 
+    fn record_history(&mut self) -> Result<()> {
+        let tab = self.tab;
+        let Some(pattern) = self.state().pattern() else {
+            return Ok(());
+        };
+        if FilterHistory::global().write().unwrap().push(tab.full_name(), &pattern) {
+            self.action_tx.as_ref().unwrap().send(Action::FilterHistoryChanged)?;
+        }
         Ok(())
     }
+
+    /// Recall an `older` (Up) or newer (Down) submitted pattern, like shell history.
+    fn recall_history(&mut self, older: bool) {
+        let tab = self.tab;
+        let state = self.state();
+        let back = match (state.history_back, older) {
+            (None, true) => 0,
+            (None, false) => return,
+            (Some(back), true) => back + 1,
+            (Some(0), false) => {
+                state.history_back = None;
+                state.input = state.draft.as_str().into();
+                state.should_send = true;
+                return;
+            }
+            (Some(back), false) => back - 1,
+        };
+        let history = FilterHistory::global().read().unwrap();
+        let Some(pattern) = history.get(tab.full_name(), back) else {
+            return;
+        };
+        if state.history_back.is_none() {
+            state.draft = state.input.value().to_string();
+        }
+        state.history_back = Some(back);
+        state.input = pattern.into();
+        state.should_send = true;
+    }
 }
 
 impl Component for FilterComponent {
@@ -70,6 +146,16 @@ impl Component for FilterComponent {
                 Fragment::hl("Del"),
             ]),
             Shortcut::new(vec![Fragment::hl("C-Y"), Fragment::raw("ank")]),
+            Shortcut::new(vec![
+                Fragment::hl(arrow::UP),
+                Fragment::raw(" history "),
+                Fragment::hl(arrow::DOWN),
+            ])
+            .compact(vec![
+                Fragment::hl(arrow::UP),
+                Fragment::raw("/"),
+                Fragment::hl(arrow::DOWN),
+            ]),
             Shortcut::new(vec![Fragment::hl("Home"), Fragment::raw(" jump "), Fragment::hl("End")])
                 .compact(vec![Fragment::hl("Home"), Fragment::raw("/"), Fragment::hl("End")]),
             Shortcut::new(vec![
@@ -95,14 +181,21 @@ impl Component for FilterComponent {
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
         match key.code {
             KeyCode::Enter | KeyCode::Esc => {
-                self.is_active = false;
+                if key.code == KeyCode::Enter {
+                    self.record_history()?;
+                }
                 self.send()?;
+                self.is_active = false;
                 return Ok(Some(Action::Unfocus));
             }
+            KeyCode::Up => self.recall_history(true),
+            KeyCode::Down => self.recall_history(false),
             _ => {
                 if let Some(req) = input_request(key) {
-                    self.should_send = true;
-                    let _ = self.input.handle(req);
+                    let state = self.state();
+                    state.should_send = true;
+                    state.history_back = None;
+                    let _ = state.input.handle(req);
                 }
             }
         }
@@ -111,15 +204,24 @@ impl Component for FilterComponent {
 
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
-            Action::Focus(ComponentId::Filter) => self.is_active = true,
-            Action::Tick => self.send()?,
-            Action::FilterSet(pattern) => {
-                debug!("handle Action::FilterSet, pattern={pattern:?}");
-                self.input = pattern.unwrap_or_default().into();
+            Action::Focus(ComponentId::Filter) => {
+                self.is_active = true;
+                self.state().history_back = None;
+            }
+            Action::TabSwitch(to) => self.restore(to)?,
+            Action::Tick if self.is_active => self.send()?,
+            // patterns set by other components, e.g. a search jump
+            Action::FilterChanged(tab, pattern) => {
+                let state = self.states.entry(tab).or_default();
+                if state.pattern() != pattern {
+                    debug!(?tab, "handle Action::FilterChanged, pattern={pattern:?}");
+                    state.input = pattern.unwrap_or_default().into();
+                    state.history_back = None;
+                }
             }
-            Action::FilterPlaceholder(placeholder) => {
-                debug!("handle Action::FilterPlaceholder, placeholder={placeholder:?}");
-                self.placeholder = placeholder;
+            Action::FilterPlaceholder(tab, placeholder) => {
+                debug!(?tab, "handle Action::FilterPlaceholder, placeholder={placeholder:?}");
+                self.states.entry(tab).or_default().placeholder = placeholder;
             }
             _ => (),
         }
@@ -131,8 +233,10 @@ impl Component for FilterComponent {
         let style =
             if self.is_active { Style::default().fg(Color::LightBlue) } else { Style::default() };
 
+        let empty = FilterState::default();
+        let state = self.states.get(&self.tab).unwrap_or(&empty);
         let width = area.width.max(3) - 3;
-        let scroll = self.input.visual_scroll(width as usize);
+        let scroll = state.input.visual_scroll(width as usize);
 
         // left align
         let mut left = Line::from(Span::raw(TOP_TITLE_LEFT));
@@ -158,21 +262,60 @@ impl Component for FilterComponent {
             .border_style(style)
             .title(left.left_aligned())
             .title(right.right_aligned());
-        let paragraph = if self.input.value().is_empty() {
+        let paragraph = if state.input.value().is_empty() {
             Paragraph::new(Line::from(Span::styled(
-                self.placeholder.as_deref().unwrap_or_default(),
+                state.placeholder.as_deref().unwrap_or_default(),
                 Style::default().fg(Color::DarkGray),
             )))
         } else {
-            Paragraph::new(self.input.value()).scroll((0, scroll as u16)).style(style)
+            Paragraph::new(state.input.value()).scroll((0, scroll as u16)).style(style)
         };
         let input = paragraph.block(block);
         frame.render_widget(input, area);
         if self.is_active {
-            let x = self.input.visual_cursor().max(scroll) - scroll + 1;
+            let x = state.input.visual_cursor().max(scroll) - scroll + 1;
             frame.set_cursor_position((area.x + x as u16, area.y + 1));
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::*;
+
+    #[test]
+    fn patterns_stay_with_their_tab() {
+        let (tx, mut rx) = unbounded_channel();
+        let mut filter = FilterComponent::default();
+        filter.register_action_handler(tx).unwrap();
+        let mut sent = || {
+            std::iter::from_fn(|| rx.try_recv().ok())
+                .filter_map(|action| match action {
+                    Action::FilterChanged(tab, pattern) => Some((tab, pattern.unwrap_or_default())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        filter.update(Action::TabSwitch(ComponentId::Connections)).unwrap();
+        filter.update(Action::Focus(ComponentId::Filter)).unwrap();
+        filter.handle_key_event(KeyCode::Char('a').into()).unwrap();
+        filter.handle_key_event(KeyCode::Esc.into()).unwrap();
+        assert_eq!(sent(), vec![(ComponentId::Connections, "a".to_string())]);
+
+        // a search jump sets the pattern of another tab
+        filter.update(Action::FilterChanged(ComponentId::Logs, Some("b".into()))).unwrap();
+        filter.update(Action::TabSwitch(ComponentId::Rules)).unwrap();
+        assert_eq!(sent(), vec![]);
+        filter.update(Action::TabSwitch(ComponentId::Logs)).unwrap();
+        filter.update(Action::TabSwitch(ComponentId::Connections)).unwrap();
+        assert_eq!(
+            sent(),
+            vec![(ComponentId::Logs, "b".to_string()), (ComponentId::Connections, "a".to_string())]
+        );
+    }
+}
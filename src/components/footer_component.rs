@@ -1,43 +1,121 @@
 use ratatui::Frame;
 use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
 use ratatui::symbols::line::{BOTTOM_LEFT, BOTTOM_RIGHT};
 use ratatui::text::{Line, Span};
 
 use crate::action::Action;
 use crate::components::{Component, ComponentId};
-use crate::widgets::shortcut::{Shortcut, ShortcutMode, shortcuts_full_width};
+use crate::store::error_log::ErrorLog;
+use crate::widgets::shortcut::{Fragment, Shortcut, ShortcutMode, shortcuts_full_width};
+
+/// Width of the borders around each shortcut
+const PAD_WIDTH: usize = 2;
+/// Width reserved for the page indicator, e.g. `┘1/3 ^f└`
+const PAGER_WIDTH: usize = 10;
 
 pub struct FooterComponent {
     shortcuts: Vec<Shortcut>,
     full_width: usize,
+    /// Page shown when the shortcuts overflow, wraps around the page count
+    page: usize,
 }
 
 fn default_shortcuts() -> Vec<Shortcut> {
-    vec![Shortcut::from("help", 0).unwrap(), Shortcut::from("quit", 0).unwrap()]
+    vec![Shortcut::from("help", 0).unwrap().pinned(), Shortcut::from("quit", 0).unwrap().pinned()]
 }
 
 impl Default for FooterComponent {
     fn default() -> Self {
         let shortcuts = default_shortcuts();
-        let full_width = shortcuts_full_width(&shortcuts, 2);
+        let full_width = shortcuts_full_width(&shortcuts, PAD_WIDTH);
+
+        Self { shortcuts, full_width, page: 0 }
+    }
+}
+
+/// Split shortcuts of the given `(width, pinned)` into pages fitting `width`, each page lists
+/// indices in their original order. Pinned shortcuts are on every page, the others fill pages
+/// in order, so the first page holds the most important ones.
+fn paginate(widths: &[(usize, bool)], width: usize) -> Vec<Vec<usize>> {
+    if widths.iter().map(|(w, _)| w).sum::<usize>() <= width {
+        return vec![(0..widths.len()).collect()];
+    }
+    let pinned_width: usize = widths.iter().filter(|(_, pinned)| *pinned).map(|(w, _)| w).sum();
+    let available = width.saturating_sub(pinned_width + PAGER_WIDTH);
 
-        Self { shortcuts, full_width }
+    let mut pages: Vec<Vec<usize>> = vec![];
+    let mut page = vec![];
+    let mut used = 0;
+    for (idx, (w, _)) in widths.iter().enumerate().filter(|(_, (_, pinned))| !pinned) {
+        // a shortcut wider than the footer still gets a page of its own
+        if !page.is_empty() && used + w > available {
+            pages.push(std::mem::take(&mut page));
+            used = 0;
+        }
+        page.push(idx);
+        used += w;
+    }
+    if !page.is_empty() || pages.is_empty() {
+        pages.push(page);
     }
+    pages
+        .into_iter()
+        .map(|page| {
+            let mut indices: Vec<usize> =
+                (0..widths.len()).filter(|idx| widths[*idx].1).chain(page).collect();
+            indices.sort_unstable();
+            indices
+        })
+        .collect()
 }
 
 impl FooterComponent {
+    /// Count of errors not reviewed yet, e.g. `┘! 3 errors└`.
+    fn error_badge(&self) -> Option<Vec<Span<'static>>> {
+        let unread = ErrorLog::unread();
+        if unread == 0 {
+            return None;
+        }
+        let text = format!("! {unread} error{}", if unread == 1 { "" } else { "s" });
+        Some(vec![
+            Span::raw(BOTTOM_RIGHT),
+            Span::styled(text, Style::default().fg(Color::LightRed).bold()),
+            Span::raw(BOTTOM_LEFT),
+        ])
+    }
+
     fn short_cuts_widget(&self, width: u16) -> Line<'_> {
+        let badge = self.error_badge().unwrap_or_default();
+        let width = width.saturating_sub(badge.iter().map(Span::width).sum::<usize>() as u16);
         let mode = if self.full_width <= width as usize {
             ShortcutMode::Full
         } else {
             ShortcutMode::Compact
         };
-        let mut spans = vec![];
-        for shortcut in &self.shortcuts {
+        let widths = self
+            .shortcuts
+            .iter()
+            .map(|v| (v.width_for(mode) + PAD_WIDTH, v.is_pinned()))
+            .collect::<Vec<_>>();
+        let pages = paginate(&widths, width as usize);
+        let page = self.page % pages.len();
+
+        let mut spans = badge;
+        for shortcut in pages[page].iter().map(|idx| &self.shortcuts[*idx]) {
             spans.push(Span::raw(BOTTOM_RIGHT));
             spans.extend(shortcut.spans_for(mode, None));
             spans.push(Span::raw(BOTTOM_LEFT));
         }
+        if pages.len() > 1 {
+            let pager = Shortcut::new(vec![
+                Fragment::raw(format!("{}/{} ", page + 1, pages.len())),
+                Fragment::hl("^f"),
+            ]);
+            spans.push(Span::raw(BOTTOM_RIGHT));
+            spans.extend(pager.into_spans(None));
+            spans.push(Span::raw(BOTTOM_LEFT));
+        }
 
         Line::from(spans)
     }
@@ -49,11 +127,16 @@ impl Component for FooterComponent {
     }
 
     fn update(&mut self, action: Action) -> anyhow::Result<Option<Action>> {
-        if let Action::Shortcuts(shortcuts) = action {
-            let mut sc = default_shortcuts();
-            sc.extend(shortcuts);
-            self.full_width = shortcuts_full_width(&sc, 2);
-            self.shortcuts = sc;
+        match action {
+            Action::Shortcuts(shortcuts) => {
+                let mut sc = default_shortcuts();
+                sc.extend(shortcuts);
+                self.full_width = shortcuts_full_width(&sc, PAD_WIDTH);
+                self.shortcuts = sc;
+                self.page = 0;
+            }
+            Action::ShortcutsNextPage => self.page = self.page.wrapping_add(1),
+            _ => {}
         }
         Ok(None)
     }
@@ -65,3 +148,22 @@ impl Component for FooterComponent {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflowing_shortcuts_are_paged_around_pinned_ones() {
+        let widths = [(6, true), (10, false), (10, false), (6, true), (20, false), (8, false)];
+        assert_eq!(paginate(&widths, 60), vec![vec![0, 1, 2, 3, 4, 5]]);
+
+        // 40 - 12 pinned - 10 pager leaves 18 per page
+        assert_eq!(
+            paginate(&widths, 40),
+            vec![vec![0, 1, 3], vec![0, 2, 3], vec![0, 3, 4], vec![0, 3, 5]]
+        );
+        assert_eq!(paginate(&widths, 60 - 1), vec![vec![0, 1, 2, 3], vec![0, 3, 4, 5]]);
+        assert_eq!(paginate(&[(6, true), (6, true)], 4), vec![vec![0, 1]]);
+    }
+}
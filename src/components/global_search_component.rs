@@ -0,0 +1,247 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, List, ListItem, ListState, Paragraph};
+use tokio::sync::mpsc::UnboundedSender;
+use tui_input::Input;
+
+use crate::action::Action;
+use crate::components::search::{self, SearchEntry, SearchTarget};
+use crate::components::{Component, ComponentId};
+use crate::utils::symbols::arrow;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::tui_input::input_request;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+#[derive(Debug)]
+enum Row {
+    /// (tab, kind, distinct hits)
+    Group(ComponentId, &'static str, usize),
+    Hit(SearchEntry),
+}
+
+/// "Find anything" popup: fuzzy search over the items of all loaded tabs, selecting a hit switches
+/// to its tab with a filter applied or its detail opened.
+#[derive(Default)]
+pub struct GlobalSearchComponent {
+    action_tx: Option<UnboundedSender<Action>>,
+
+    entries: Arc<[SearchEntry]>,
+    input: Input,
+    rows: Vec<Row>,
+    list_state: ListState,
+}
+
+impl GlobalSearchComponent {
+    fn load(&mut self, entries: Arc<[SearchEntry]>) {
+        self.entries = entries;
+        self.input.reset();
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        self.rows = search::search(&self.entries, self.input.value())
+            .into_iter()
+            .flat_map(|group| {
+                let header = Row::Group(group.tab, group.kind, group.total);
+                let hits = group.hits.into_iter().map(|entry| Row::Hit(entry.clone()));
+                std::iter::once(header).chain(hits)
+            })
+            .collect();
+        self.list_state.select(self.rows.iter().position(|row| matches!(row, Row::Hit(_))));
+    }
+
+    /// Select the next or previous hit, wrapping around and skipping group rows.
+    fn select(&mut self, forward: bool) {
+        let hits: Vec<usize> =
+            (0..self.rows.len()).filter(|idx| matches!(self.rows[*idx], Row::Hit(_))).collect();
+        let Some(current) =
+            self.list_state.selected().and_then(|s| hits.iter().position(|i| *i == s))
+        else {
+            return;
+        };
+        let next = if forward {
+            (current + 1) % hits.len()
+        } else {
+            (current + hits.len() - 1) % hits.len()
+        };
+        self.list_state.select(Some(hits[next]));
+    }
+
+    fn confirm(&mut self) -> Option<Action> {
+        let Some(Row::Hit(entry)) = self.list_state.selected().and_then(|idx| self.rows.get(idx))
+        else {
+            return None;
+        };
+        let target = match &entry.target {
+            SearchTarget::Query => SearchTarget::Filter(self.input.value().trim().to_owned()),
+            target => target.clone(),
+        };
+        if let Some(tx) = &self.action_tx {
+            let _ = tx.send(Action::SearchJump(entry.tab, target));
+        }
+        Some(Action::Unfocus)
+    }
+
+    fn render_rows(&mut self, frame: &mut Frame, area: Rect) {
+        if self.rows.is_empty() {
+            let hint = if self.input.value().trim().is_empty() {
+                format!("Search {} items of the loaded tabs", self.entries.len())
+            } else {
+                "No match".to_owned()
+            };
+            frame.render_widget(Line::from(hint.dark_gray()), area);
+            return;
+        }
+
+        let items = self.rows.iter().map(|row| match row {
+            Row::Group(tab, kind, total) => ListItem::new(Line::from(vec![
+                Span::styled(format!("{} {kind}", tab.full_name()), Style::default().bold()),
+                Span::styled(format!(" ({total})"), Color::DarkGray),
+            ])),
+            Row::Hit(entry) => ListItem::new(Line::from(vec![
+                Span::raw("  "),
+                Span::raw(entry.text.as_str()),
+                Span::styled(
+                    if entry.detail.is_empty() {
+                        String::new()
+                    } else {
+                        format!("  {}", entry.detail)
+                    },
+                    Color::DarkGray,
+                ),
+            ])),
+        });
+        let list = List::new(items)
+            .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+}
+
+impl Component for GlobalSearchComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::GlobalSearch
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![
+                Fragment::hl(arrow::UP),
+                Fragment::raw(" nav "),
+                Fragment::hl(arrow::DOWN),
+            ]),
+            Shortcut::new(vec![Fragment::raw("go to "), Fragment::hl("↵")]),
+            Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => return Ok(Some(Action::Unfocus)),
+            (KeyCode::Enter, _) => return Ok(self.confirm()),
+            (KeyCode::Up, _) | (KeyCode::BackTab, _) => self.select(false),
+            (KeyCode::Char('p'), KeyModifiers::CONTROL) => self.select(false),
+            (KeyCode::Down, _) | (KeyCode::Tab, _) => self.select(true),
+            (KeyCode::Char('n'), KeyModifiers::CONTROL) => self.select(true),
+            _ => {
+                if let Some(req) = input_request(key)
+                    && self.input.handle(req).is_some_and(|s| s.value)
+                {
+                    self.refresh();
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::GlobalSearchEntries(entries) = action {
+            self.load(entries);
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let area = popup_area(area, 70, 70);
+        frame.render_widget(Clear, area); // clears out the background
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line("find anything", Style::default()));
+        let inner = block.inner(area).inner(Margin::new(1, 0));
+        frame.render_widget(block, area);
+
+        let [input_area, list_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(inner);
+
+        let input_width = input_area.width.saturating_sub(2) as usize;
+        let scroll = self.input.visual_scroll(input_width);
+        let input = Paragraph::new(self.input.value())
+            .scroll((0, scroll as u16))
+            .block(Block::bordered().border_type(BorderType::Rounded).border_style(Color::Cyan));
+        frame.render_widget(input, input_area);
+        let x = self.input.visual_cursor().max(scroll) - scroll + 1;
+        frame.set_cursor_position((input_area.x + x as u16, input_area.y + 1));
+
+        self.render_rows(frame, list_area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn selection_skips_groups_and_jumps_with_query_filter() {
+        let mut component = GlobalSearchComponent::default();
+        let (tx, mut rx) = unbounded_channel();
+        component.register_action_handler(tx).unwrap();
+        component.load(Arc::from(vec![
+            SearchEntry::filter(ComponentId::Connections, "host", "www.google.com".into()),
+            SearchEntry::new(
+                ComponentId::Logs,
+                "log",
+                "dial google failed".into(),
+                SearchTarget::Query,
+            ),
+        ]));
+
+        for c in "google".chars() {
+            component.handle_key_event(key(KeyCode::Char(c))).unwrap();
+        }
+        assert_eq!(component.rows.len(), 4);
+        assert_eq!(component.list_state.selected(), Some(1));
+        component.handle_key_event(key(KeyCode::Down)).unwrap();
+        assert_eq!(component.list_state.selected(), Some(3));
+        component.handle_key_event(key(KeyCode::Down)).unwrap();
+        assert_eq!(component.list_state.selected(), Some(1));
+        component.handle_key_event(key(KeyCode::Up)).unwrap();
+
+        let action = component.handle_key_event(key(KeyCode::Enter)).unwrap();
+        assert!(matches!(action, Some(Action::Unfocus)));
+        let Ok(Action::SearchJump(ComponentId::Logs, SearchTarget::Filter(pattern))) =
+            rx.try_recv()
+        else {
+            panic!("expected a jump to the logs tab");
+        };
+        assert_eq!(pattern, "google");
+    }
+}
@@ -14,7 +14,11 @@ use tracing::{error, info, warn};
 use crate::action::Action;
 use crate::api::Api;
 use crate::components::{Component, ComponentId, TABS};
-use crate::config::Config;
+use crate::config::{Config, UnfocusedMode};
+use crate::store::geo_update::{GeoUpdate, format_progress};
+use crate::store::warmup::Warmup;
+use crate::task_manager::TaskManager;
+use crate::utils::safe_mode::SafeMode;
 use crate::utils::symbols::{SUPERSCRIPT, arrow};
 use crate::version_update::SharedVersionUpdateState;
 use crate::widgets::shortcut::{Fragment, Shortcut};
@@ -44,18 +48,20 @@ enum TabNameMode {
 
 pub struct HeaderComponent {
     selected: usize,
+    terminal_focused: bool,
 
     api: Option<Arc<Api>>,
     config: Option<Arc<Config>>,
     version: Arc<Mutex<Option<String>>>,
     update_state: SharedVersionUpdateState,
-    release_checker: Option<JoinHandle<()>>,
+    release_checker: Option<JoinHandle<Option<()>>>,
 }
 
 impl HeaderComponent {
     pub fn new(update_state: SharedVersionUpdateState) -> Self {
         Self {
             selected: Self::component_index(ComponentId::default()),
+            terminal_focused: true,
             api: None,
             config: None,
             version: Default::default(),
@@ -71,7 +77,7 @@ impl HeaderComponent {
     fn load_version(&mut self, api: Arc<Api>) -> anyhow::Result<()> {
         info!("Loading version");
         let version = Arc::clone(&self.version);
-        tokio::task::Builder::new().name("version-loader").spawn(async move {
+        TaskManager::spawn(ComponentId::Header, "version-loader", async move {
             match api.get_version().await {
                 Ok(v) => {
                     *version.lock().unwrap() = Some(v.to_string());
@@ -87,7 +93,7 @@ impl HeaderComponent {
     }
 
     fn start_release_checker(&mut self) -> anyhow::Result<()> {
-        if self.release_checker.is_some() {
+        if self.release_checker.is_some() || SafeMode::enabled() {
             return Ok(());
         }
 
@@ -99,7 +105,7 @@ impl HeaderComponent {
             return Ok(());
         };
         let update_state = self.update_state.clone();
-        let handle = tokio::task::Builder::new().name("release-checker").spawn(async move {
+        let handle = TaskManager::spawn(ComponentId::Header, "release-checker", async move {
             loop {
                 if let Err(e) = update_state.refresh(&api, &mihomo_repo).await {
                     warn!(error = ?e, "Failed to check release updates");
@@ -140,7 +146,33 @@ impl HeaderComponent {
             guard.as_deref().unwrap_or("-").to_string()
         };
         let availability = self.update_state.is_available();
-        let mut spans = Vec::with_capacity(8);
+        let mut spans = Vec::with_capacity(10);
+        // what slowed down while the terminal window is not focused
+        let unfocused = match self.config.as_ref().map(|c| c.unfocused).unwrap_or_default() {
+            _ if self.terminal_focused => None,
+            UnfocusedMode::None => None,
+            UnfocusedMode::Slow => Some(("unfocused ", Color::DarkGray)),
+            UnfocusedMode::Pause => Some(("paused ", Color::Yellow)),
+        };
+        if let Some((text, color)) = unfocused {
+            spans.push(Span::styled(text, Style::default().fg(color)));
+        }
+        if SafeMode::enabled() {
+            spans.push(Span::styled("safe mode ", Style::default().fg(Color::LightRed)));
+        }
+        if let Some((elapsed, estimate)) = GeoUpdate::progress() {
+            spans.push(Span::styled(
+                format!("GEO update {} ", format_progress(elapsed, estimate)),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        // readiness of the tabs loaded on startup
+        if let Some((ready, total)) = Warmup::progress() {
+            spans.push(Span::styled(
+                format!("loading {ready}/{total} "),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
         // mihomo core version
         spans.push(Span::styled(format!("[ {} ", version), Style::default().fg(Color::Blue)));
         if availability.core {
@@ -193,6 +225,8 @@ impl Component for HeaderComponent {
     fn update(&mut self, action: Action) -> anyhow::Result<Option<Action>> {
         match action {
             Action::TabSwitch(to) => self.selected = Self::component_index(to),
+            Action::TerminalFocus(focused) => self.terminal_focused = focused,
+            Action::ConfigReloaded(config) => self.config = Some(config),
             Action::CoreVersionUpdated(version) => {
                 *self.version.lock().unwrap() = Some(version.to_string())
             }
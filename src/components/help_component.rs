@@ -61,13 +61,22 @@ impl HelpComponent {
             HelpRow::entry("h", "Toggle help"),
             HelpRow::entry("q / Ctrl+c", "Quits program"),
             HelpRow::entry("Number", "switch to tab"),
+            HelpRow::entry("Tab", "fuzzy switch tab by name (except Cfg)"),
             HelpRow::entry("k / Up, j / Down", "navigation"),
             HelpRow::entry("g, G", "go to first, last"),
             HelpRow::entry("PageUp, Space / PageDown", "page up, down"),
             HelpRow::entry("Esc", "cancel / back / live toggle"),
             HelpRow::entry("Enter", "confirm / open detail"),
             HelpRow::entry("Ctrl+l", "clear idle tabs"),
+            HelpRow::entry("Ctrl+f", "next page of footer shortcuts on narrow terminals"),
             HelpRow::entry("Ctrl+u", "open updates"),
+            HelpRow::entry("Ctrl+t", "list background tasks and stream ingest rates"),
+            HelpRow::entry("Ctrl+e", "timeline of recent actions and core events"),
+            HelpRow::entry("Ctrl+r", "recent API requests, y copies the selected one as curl"),
+            HelpRow::entry("Ctrl+k", "find anything: hosts, rules, providers, proxies and logs"),
+            HelpRow::entry("!", "review recent errors, clears the footer error badge"),
+            HelpRow::entry("o", "open URL: repository (help), releases (updates)"),
+            HelpRow::entry("Ctrl+o", "open test URL (proxy settings)"),
             // filter / proxy setting input keys
             HelpRow::Empty,
             HelpRow::key_title("input box"),
@@ -76,6 +85,7 @@ impl HelpComponent {
             HelpRow::entry("Back, Ctrl+Back, Del, Ctrl-Del", "delete"),
             HelpRow::entry("Ctrl+y", "yank last deleted word"),
             HelpRow::entry("Home, End", "jump to line start, end"),
+            HelpRow::entry("Up, Down", "recall previous filters of the tab"),
             // filter syntax
             HelpRow::Empty,
             HelpRow::key_title("filter syntax"),
@@ -85,6 +95,10 @@ impl HelpComponent {
             HelpRow::entry("^str$", "match exactly 'str'"),
             HelpRow::entry("'str", "match if the value contains substring 'str'"),
             HelpRow::entry("!<pattern>", "negate the match of <pattern>, examples: !^str, !'str"),
+            HelpRow::entry(
+                "*.domain.com",
+                "match domain.com and its subdomains exactly, in Host column if present",
+            ),
             HelpRow::entry("\"com:443\"", "quote plain patterns containing spaces or colons"),
             HelpRow::entry(
                 "field:pattern",
@@ -95,6 +109,13 @@ impl HelpComponent {
                 "field1:pat1 field2:pat2 pat3",
                 "match named fields and remaining columns using AND",
             ),
+            // `overview` key bindings
+            HelpRow::Empty,
+            HelpRow::key_title("# Overview (View)"),
+            HelpRow::entry("[, ]", "narrow/widen traffic chart (saved to runtime config)"),
+            HelpRow::entry("s", "chart settings: smoothing, peak markers"),
+            HelpRow::entry("e", "dump traffic and memory buffers to a CSV file in the data dir"),
+            HelpRow::entry("H", "toggle recorded traffic history (`history.enabled`)"),
             // `connections` key bindings
             HelpRow::Empty,
             HelpRow::key_title("# Connections (Conn)"),
@@ -102,10 +123,19 @@ impl HelpComponent {
             HelpRow::entry("t", "terminate selected connection"),
             HelpRow::entry("T", "terminate filtered connections"),
             HelpRow::entry("r", "reverse sort direction"),
+            HelpRow::entry("p", "pin/unpin sort column, then sort by the next one"),
             HelpRow::entry("c", "capture mode"),
+            HelpRow::entry("C", "clear the closed connections kept by capture mode"),
+            HelpRow::entry("n", "cycle network filter: all, TCP only, UDP only"),
+            HelpRow::entry("w", "save the capture buffer to a file (JSON lines)"),
+            HelpRow::entry("o", "open a saved capture read-only, Esc returns to live connections"),
+            HelpRow::entry("b", "open the host of a web connection (port 80/443) in the browser"),
             HelpRow::entry("s", "open connection settings"),
             HelpRow::entry("-, +", "decrease/increase sort column width"),
             HelpRow::entry("Delete", "reset sort column width"),
+            HelpRow::entry("u", "connection detail: update GEO databases on a GeoIP mismatch"),
+            HelpRow::entry("/, n, N", "connection detail: search the JSON, next/previous match"),
+            HelpRow::entry(".", "connection detail: jump to a key path, e.g. metadata.processPath"),
             // connections settings
             HelpRow::Empty,
             HelpRow::key_title("## Connections Settings"),
@@ -123,7 +153,11 @@ impl HelpComponent {
             HelpRow::key_title("# Proxies (Pxy)"),
             HelpRow::entry("r", "refresh proxies"),
             HelpRow::entry("s", "open proxy settings"),
+            HelpRow::entry("c", "calibrate latency thresholds from a test of the largest groups"),
             HelpRow::entry("t", "test proxy"),
+            HelpRow::entry("f", "show failing (slow / timeout) nodes of group"),
+            HelpRow::entry("Space", "switch the selector group to its next healthy node"),
+            HelpRow::entry("p", "pick a node of the group by its number"),
             // proxy detail
             HelpRow::Empty,
             HelpRow::key_title("## Proxy Detail"),
@@ -132,11 +166,18 @@ impl HelpComponent {
             HelpRow::entry("[, ]", "navigate nested groups"),
             HelpRow::entry("s", "switch sort by: none, latency, name"),
             HelpRow::entry("S", "toggle sort direction"),
+            HelpRow::entry("f", "toggle showing only failing nodes"),
+            HelpRow::entry("l", "toggle ordering nodes fastest first, focusing the fastest"),
+            HelpRow::entry("i", "look up server and exit IP of the focused or selected node"),
+            HelpRow::entry("/", "search nodes by name or type, Enter keeps it, Esc clears it"),
+            HelpRow::entry("T", "url-test / fallback: drop the manual pick and re-test the group"),
             // proxy providers / proxy provider detail
             HelpRow::Empty,
             HelpRow::key_title("# ProxyProviders (Pxy-Pr)"),
             HelpRow::entry("Enter", "show provider detail"),
             HelpRow::entry("u", "update providers"),
+            HelpRow::entry("T", "health check all providers, then summarize alive nodes"),
+            HelpRow::entry("/", "provider detail: search nodes by name or type"),
             // `logs` key bindings
             HelpRow::Empty,
             HelpRow::key_title("# Logs (Log)"),
@@ -146,7 +187,11 @@ impl HelpComponent {
             HelpRow::key_title("# Rules (Rule)"),
             HelpRow::entry("r", "refresh rules"),
             HelpRow::entry("t", "toggle disabled state (selected or all filtered)"),
+            HelpRow::entry("T", "disable/enable all rules matched by the filter"),
             HelpRow::entry("s", "submit disabled state changes"),
+            HelpRow::entry("b", "rule count breakdown by type"),
+            HelpRow::entry("m", "test which rule matches a domain, IP or port"),
+            HelpRow::entry("p", "open the rule provider of a RULE-SET rule"),
             // `rule providers` key bindings
             HelpRow::Empty,
             HelpRow::key_title("# RuleProviders (R-Pr)"),
@@ -156,10 +201,25 @@ impl HelpComponent {
             HelpRow::Empty,
             HelpRow::key_title("# Config (Cfg)"),
             HelpRow::entry("Shift+Tab, Tab", "move focus between editor and actions"),
-            HelpRow::entry("Enter", "execute focused action / confirm"),
+            HelpRow::entry("Enter", "execute focused action / validate, then submit edited config"),
             HelpRow::entry("e", "open config in external editor ($EDITOR → vim → vi)"),
             HelpRow::entry("d", "discard changes and reload config"),
+            HelpRow::entry("o", "toggle the outline of top-level keys, ↑/↓ scroll to a section"),
             HelpRow::entry("n", "open DNS query dialog"),
+            HelpRow::entry("t", "TCP ping a host:port directly and through a proxy"),
+            HelpRow::entry(
+                "i",
+                "import a local YAML config, review changes, then submit with Enter",
+            ),
+            HelpRow::entry("x", "export config as YAML to a local file"),
+            HelpRow::entry(
+                "p",
+                "manage local config profiles: save running config, apply with diff",
+            ),
+            HelpRow::entry(
+                "s",
+                "toggle sniffing after a confirm (ports and domains: core config file)",
+            ),
             // dns query dialog
             HelpRow::Empty,
             HelpRow::key_title("## DNS Query"),
@@ -167,6 +227,14 @@ impl HelpComponent {
             HelpRow::entry("Enter", "query DNS records"),
             HelpRow::entry("Left, Right", "select DNS record type"),
             HelpRow::entry("k / Up, j / Down", "scroll answers"),
+            // tcp ping dialog
+            HelpRow::Empty,
+            HelpRow::key_title("## TCP Ping"),
+            HelpRow::entry("Shift+Tab, Tab", "switch between target and proxy"),
+            HelpRow::entry(
+                "Enter",
+                "ping the target, then HTTP test it directly and via the proxy",
+            ),
             HelpRow::Empty,
             HelpRow::Empty,
         ]
@@ -216,6 +284,7 @@ impl Component for HelpComponent {
             KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('h') => {
                 return Ok(Some(Action::Unfocus));
             }
+            KeyCode::Char('o') => return Ok(Some(Action::OpenUrl(REPOSITORY_URL.into()))),
             _ => (),
         }
         Ok(None)
@@ -0,0 +1,68 @@
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use time::OffsetDateTime;
+use tokio::sync::watch::Receiver;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::components::ComponentId;
+use crate::config::HistoryConfig;
+use crate::store::connection_stats::ConnectionStats;
+use crate::store::history::{History, HistorySampler};
+use crate::store::stream_hub::StreamHub;
+use crate::task_manager::TaskManager;
+
+/// Sample traffic, memory and the connection count to the history file every
+/// `history.interval` seconds, after loading the samples kept from previous runs.
+pub fn spawn(
+    config: HistoryConfig,
+    mut stats_rx: Receiver<Option<ConnectionStats>>,
+) -> io::Result<JoinHandle<Option<()>>> {
+    TaskManager::spawn(ComponentId::Root, "history-recorder", async move {
+        let path = History::default_path();
+        let retention = config.retention as i64 * 3600;
+        // decrypting and encrypting the history is slow, keep it off the runtime
+        let since = OffsetDateTime::now_utc().unix_timestamp() - retention;
+        let load = blocking(&path, move |path| History::load(path, since));
+        if let Err(e) = load.await {
+            warn!("Fail to load traffic history: {e:#}");
+        }
+
+        let mut sampler = HistorySampler::default();
+        let mut traffic = StreamHub::global().traffic();
+        let mut memory = StreamHub::global().memory();
+        let mut ticker = tokio::time::interval(Duration::from_secs(config.interval.max(1)));
+        // the first tick completes immediately
+        ticker.tick().await;
+        loop {
+            tokio::select! {
+                Some(record) = traffic.recv() => sampler.push_traffic(record.up, record.down),
+                Some(record) = memory.recv() => sampler.push_memory(record.used),
+                Ok(()) = stats_rx.changed() => {
+                    let conns = stats_rx.borrow_and_update().as_ref().map(|s| s.conns_size);
+                    if let Some(conns) = conns {
+                        sampler.push_conns(conns as u64);
+                    }
+                }
+                _ = ticker.tick() => {
+                    let now = OffsetDateTime::now_utc().unix_timestamp();
+                    let Some(sample) = sampler.take(now) else {
+                        continue;
+                    };
+                    let record = blocking(&path, move |path| History::record(path, sample, now - retention));
+                    if let Err(e) = record.await {
+                        warn!("Fail to record traffic history: {e:#}");
+                    }
+                }
+            }
+        }
+    })
+}
+
+async fn blocking(path: &Path, f: impl FnOnce(&Path) -> Result<()> + Send + 'static) -> Result<()> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || f(&path)).await.context("Fail to join history task")?
+}
@@ -0,0 +1,241 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Margin, Rect};
+use ratatui::prelude::{Color, Style};
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Padding, Paragraph, Wrap};
+use throbber_widgets_tui::{BRAILLE_SIX, Throbber, ThrobberState, WhichUse};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::oneshot;
+use tracing::info;
+
+use crate::action::Action;
+use crate::api::Api;
+use crate::components::{Component, ComponentId};
+use crate::config::LatencyThreshold;
+use crate::store::latency_calibration::Calibration;
+use crate::store::proxy_setting::ProxySetting;
+use crate::task_manager::TaskManager;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::widgets::latency::LatencyQuality;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+/// Tests the nodes of several groups against the configured URL and suggests latency thresholds
+/// from the observed distribution, written to the proxy settings once confirmed.
+#[derive(Default)]
+pub struct LatencyCalibrationComponent {
+    api: Option<Arc<Api>>,
+    action_tx: Option<UnboundedSender<Action>>,
+
+    result: Option<Result<Calibration, String>>,
+    result_rx: Option<oneshot::Receiver<Result<Calibration, String>>>,
+    throbber: ThrobberState,
+}
+
+impl LatencyCalibrationComponent {
+    fn run(&mut self) -> Result<()> {
+        if self.result_rx.is_some() {
+            return Ok(());
+        }
+        let api = Arc::clone(self.api.as_ref().unwrap());
+        let groups = Calibration::groups();
+        let (tx, rx) = oneshot::channel();
+        self.result_rx = Some(rx);
+        self.result = None;
+        TaskManager::spawn(ComponentId::LatencyCalibration, "latency-calibration", async move {
+            let result = Calibration::run(api, groups).await.map_err(|e| format!("{e:#}"));
+            let _ = tx.send(result);
+        })?;
+        Ok(())
+    }
+
+    fn poll_result(&mut self) {
+        let Some(rx) = &mut self.result_rx else {
+            return;
+        };
+        self.result = match rx.try_recv() {
+            Ok(result) => Some(result),
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => {
+                Some(Err("Calibration task stopped".into()))
+            }
+        };
+        self.result_rx = None;
+    }
+
+    fn suggestion(&self) -> Option<LatencyThreshold> {
+        self.result.as_ref()?.as_ref().ok()?.suggest()
+    }
+
+    fn apply(&mut self) -> Result<Option<Action>> {
+        let Some(threshold) = self.suggestion() else {
+            return Ok(None);
+        };
+        info!(%threshold, "Latency threshold calibrated");
+        ProxySetting::global().write().unwrap().latency_threshold = threshold;
+        self.action_tx.as_ref().unwrap().send(Action::ProxySettingChanged)?;
+        Ok(Some(Action::Unfocus))
+    }
+
+    fn render_throbber(&mut self, frame: &mut Frame, area: Rect) {
+        if self.result_rx.is_none() {
+            return;
+        }
+        let symbol = Throbber::default()
+            .label("Testing")
+            .style(Style::default().fg(Color::White).bg(Color::Green).bold())
+            .throbber_style(Style::default().fg(Color::White).bg(Color::Green).bold())
+            .throbber_set(BRAILLE_SIX)
+            .use_type(WhichUse::Spin);
+        frame.render_stateful_widget(
+            symbol,
+            Rect::new(area.right().saturating_sub(10), area.y, 9, 1),
+            &mut self.throbber,
+        );
+    }
+
+    fn quality_line(
+        title: &'static str,
+        calibration: &Calibration,
+        threshold: LatencyThreshold,
+    ) -> Line<'static> {
+        let [fast, medium, slow] = calibration.quality_counts(threshold);
+        let count = |quality: LatencyQuality, n: usize| {
+            Span::styled(format!("{n:>6}"), Style::default().fg(quality.color()))
+        };
+        Line::from(vec![
+            Span::raw(format!("{title:<10}{:>10}", threshold.to_string())),
+            count(LatencyQuality::Fast, fast),
+            count(LatencyQuality::Medium, medium),
+            count(LatencyQuality::Slow, slow),
+            count(LatencyQuality::NotConnected, calibration.failed),
+        ])
+    }
+
+    fn report_lines(&self) -> Vec<Line<'static>> {
+        let calibration = match &self.result {
+            None => {
+                return vec![Line::from("Testing the nodes of the largest groups...".dark_gray())];
+            }
+            Some(Err(e)) => return vec![Line::from(Span::styled(e.clone(), Color::Red))],
+            Some(Ok(calibration)) => calibration,
+        };
+        let ms = |v: Option<u64>| v.map_or("-".into(), |v| format!("{v}ms"));
+        let mut lines = vec![
+            Line::from(vec!["groups ".dark_gray(), Span::raw(calibration.groups.join(", "))]),
+            Line::from(vec![
+                "nodes ".dark_gray(),
+                Span::raw(format!(
+                    "{} tested, {} failed",
+                    calibration.delays.len() + calibration.failed,
+                    calibration.failed
+                )),
+            ]),
+            Line::from(vec![
+                "delay ".dark_gray(),
+                Span::raw(format!(
+                    "min {}, median {}, p90 {}, max {}",
+                    ms(calibration.delays.first().copied()),
+                    ms(calibration.percentile(50)),
+                    ms(calibration.percentile(90)),
+                    ms(calibration.delays.last().copied()),
+                )),
+            ]),
+            Line::default(),
+            Line::from(
+                format!(
+                    "{:<10}{:>10}{:>6}{:>6}{:>6}{:>6}",
+                    "", "threshold", "fast", "mid", "slow", "fail"
+                )
+                .bold(),
+            ),
+        ];
+        let current = ProxySetting::global().read().unwrap().latency_threshold;
+        lines.push(Self::quality_line("current", calibration, current));
+        match calibration.suggest() {
+            Some(suggested) => {
+                lines.push(Self::quality_line("suggested", calibration, suggested));
+                lines.push(Line::default());
+                lines.push(Line::from(
+                    "Fast up to the median, slow beyond the 90th percentile. ↵ to apply".yellow(),
+                ));
+            }
+            None => {
+                lines.push(Line::default());
+                lines.push(Line::from("Too few successful tests to suggest thresholds".yellow()));
+            }
+        }
+        lines
+    }
+}
+
+impl Component for LatencyCalibrationComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::LatencyCalibration
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![Fragment::raw("apply "), Fragment::hl("↵")]),
+            Shortcut::from("retest", 0).unwrap(),
+            Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn init(&mut self, api: Arc<Api>) -> Result<()> {
+        self.api = Some(api);
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(Action::Unfocus)),
+            KeyCode::Char('r') => self.run()?,
+            KeyCode::Enter => return self.apply(),
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::LatencyCalibration => self.run()?,
+            Action::Tick => {
+                self.poll_result();
+                if self.result_rx.is_some() {
+                    self.throbber.calc_next();
+                }
+            }
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let area = popup_area(area, 60, 50);
+        frame.render_widget(Clear, area); // clears out the background
+        let area = area.inner(Margin::new(2, 1));
+
+        let border = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line("latency calibration", Style::default()))
+            .padding(Padding::symmetric(2, 1));
+        let content_area = border.inner(area);
+        frame.render_widget(border, area);
+        self.render_throbber(frame, area);
+        let report = Paragraph::new(self.report_lines()).wrap(Wrap { trim: false });
+        frame.render_widget(report, content_area);
+
+        Ok(())
+    }
+}
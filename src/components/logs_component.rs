@@ -20,16 +20,26 @@ use unicode_segmentation::UnicodeSegmentation;
 
 use crate::action::Action;
 use crate::api::Api;
+use crate::api::stream::LOGS_PATH;
+use crate::components::search::{SearchEntry, SearchTarget};
 use crate::components::{Component, ComponentId, HORIZ_STEP};
 use crate::models::LogLevel;
+use crate::store::load_state::LoadState;
 use crate::store::logs::{LOG_COLS, Logs};
+use crate::store::stream_health::StreamHealth;
+use crate::task_manager::TaskManager;
 use crate::utils::columns::filter_placeholder;
 use crate::utils::filter::FilterPattern;
+use crate::utils::safe_mode::SafeMode;
 use crate::utils::symbols::arrow;
 use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT};
+use crate::widgets::empty_state::EmptyState;
 use crate::widgets::scrollable_navigator::ScrollableNavigator;
 use crate::widgets::shortcut::{Fragment, Shortcut};
 
+/// Upper bound of records inserted at once, a log storm arrives in large batches.
+const LOG_BATCH: usize = 1024;
+
 pub struct LogsComponent {
     api: Option<Arc<Api>>,
     token: CancellationToken,
@@ -70,6 +80,10 @@ impl LogsComponent {
     }
 
     fn load_log(&mut self) -> Result<()> {
+        if SafeMode::enabled() {
+            info!("Safe mode, not streaming logs");
+            return Ok(());
+        }
         info!("Loading log, with level: {:?}", self.level);
         let token = self.token.clone();
         let api = Arc::clone(self.api.as_ref().unwrap());
@@ -78,7 +92,7 @@ impl LogsComponent {
         let filter_pattern = Arc::clone(&self.filter_pattern);
         let live_mode = Arc::clone(&self.live_mode);
 
-        tokio::task::Builder::new().name("log-loader").spawn(async move {
+        TaskManager::spawn(ComponentId::Logs, "log-loader", async move {
             let stream = match api.stream_logs(level).await {
                 Ok(stream) => stream,
                 Err(e) => {
@@ -90,13 +104,14 @@ impl LogsComponent {
                 .take_until(token.cancelled())
                 .inspect_err(|e| warn!("Failed to parse log: {e}"))
                 .filter_map(|res| future::ready(res.ok()))
-                .for_each(|record| {
+                .ready_chunks(LOG_BATCH)
+                .for_each(|records| {
                     // Keep log-store updates exclusive with view recomputation.
                     let filter_pattern = filter_pattern.lock().unwrap();
                     if live_mode.load(Ordering::Relaxed) {
-                        store.push_and_update_view(record, filter_pattern.as_ref());
+                        store.push_and_update_view(records, filter_pattern.as_ref());
                     } else {
-                        store.push(record);
+                        store.push(records);
                     }
                     future::ready(())
                 })
@@ -175,16 +190,35 @@ impl LogsComponent {
             Span::raw("/"),
             Span::styled(self.navigator.scroller.content_length().to_string(), Color::Cyan),
             Span::raw(")"),
-            Span::raw(TOP_TITLE_RIGHT),
         ]);
+        let held_back = self.store.held_back();
+        if held_back > 0 && self.live_mode.load(Ordering::Relaxed) {
+            title_line.push_span(Span::styled(format!(" +{held_back} lines"), Color::Yellow));
+        }
+        title_line.push_span(Span::raw(TOP_TITLE_RIGHT));
         title_line.extend(self.level_shortcuts());
-        let block = Block::bordered().border_type(BorderType::Rounded).title(title_line);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(title_line)
+            .title_top(StreamHealth::indicator(LOGS_PATH));
         let selected_style = Style::default().add_modifier(Modifier::REVERSED).fg(Color::Cyan);
         let logs = List::new(items).block(block).highlight_style(selected_style);
         *self.list_state.selected_mut() =
             self.navigator.focused.map(|v| v.saturating_sub(self.navigator.scroller.pos()));
         frame.render_stateful_widget(logs, area, &mut self.list_state);
 
+        if records.is_empty() {
+            // logs may legitimately stay empty, so there is no loading state
+            let state = if StreamHealth::is_stalled(LOGS_PATH) {
+                LoadState::Failed
+            } else {
+                LoadState::Loaded
+            };
+            EmptyState::new(state, "logs")
+                .filtered(self.filter_pattern.lock().unwrap().is_some())
+                .render(frame, area.inner(Margin::new(1, 1)), &mut self.throbber_state);
+        }
+
         let (throbber_label, throbber_color) = if self.live_mode.load(Ordering::Relaxed) {
             ("Live  ", Color::Green)
         } else {
@@ -296,12 +330,27 @@ impl Component for LogsComponent {
         Ok(None)
     }
 
+    fn search_entries(&self) -> Vec<SearchEntry> {
+        self.store.with_all(|logs| {
+            logs.iter()
+                .rev()
+                .map(|log| {
+                    SearchEntry::new(self.id(), "log", log.payload.clone(), SearchTarget::Query)
+                        .detail(log.r#type.to_string())
+                })
+                .collect()
+        })
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::Quit => self.token.cancel(),
             Action::Tick => {
                 if self.live_mode.load(Ordering::Relaxed) {
                     self.throbber_state.calc_next();
+                    // the tail of a log storm
+                    let filter_pattern = self.filter_pattern.lock().unwrap();
+                    self.store.update_view_if_due(filter_pattern.as_ref());
                 }
                 if self.filter_pattern_changed {
                     let filter_pattern = self.filter_pattern.lock().unwrap();
@@ -315,23 +364,16 @@ impl Component for LogsComponent {
                     self.level_changed = false;
                 }
             }
-            Action::FilterChanged(pattern) => {
+            Action::FilterChanged(tab, pattern) if tab == self.id() => {
                 debug!("handle Action::FilterChanged, got pattern={pattern:?}");
                 *self.filter_pattern.lock().unwrap() = pattern.and_then(FilterPattern::new);
                 self.filter_pattern_changed = true;
             }
             Action::TabSwitch(to) if to == self.id() => {
-                let pattern = self
-                    .filter_pattern
-                    .lock()
-                    .unwrap()
-                    .as_ref()
-                    .map(|pattern| pattern.raw().into());
-                debug!("handle Action::TabSwitch, current filter pattern={pattern:?}");
-                if let Some(tx) = &self.action_tx {
-                    tx.send(Action::FilterPlaceholder(filter_placeholder(LOG_COLS.iter())))?;
-                }
-                return Ok(Some(Action::FilterSet(pattern)));
+                return Ok(Some(Action::FilterPlaceholder(
+                    self.id(),
+                    filter_placeholder(LOG_COLS.iter()),
+                )));
             }
             _ => {}
         }
@@ -0,0 +1,146 @@
+use std::collections::VecDeque;
+use std::io;
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+use tracing::info;
+
+use crate::action::Action;
+use crate::app_message::AppMessage;
+use crate::components::ComponentId;
+use crate::components::core_config_component::RESTART_ACTION;
+use crate::config::MemoryTrendConfig;
+use crate::store::stream_hub::StreamHub;
+use crate::store::timeline::{EventKind, Timeline};
+use crate::task_manager::TaskManager;
+use crate::utils::byte_size::human_bytes;
+
+/// Minimum time between two samples, the memory stream reports every second.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+const MIB: f64 = 1024.0 * 1024.0;
+
+/// Memory samples of the last window, tells when the growth rate crosses the threshold.
+#[derive(Debug)]
+pub struct MemoryTrend {
+    config: MemoryTrendConfig,
+    samples: VecDeque<(Instant, u64)>,
+    exceeded: bool,
+}
+
+impl MemoryTrend {
+    pub fn new(config: MemoryTrendConfig) -> Self {
+        Self { config, samples: VecDeque::new(), exceeded: false }
+    }
+
+    /// Record `used` bytes, returns the growth in bytes per hour when it starts exceeding the
+    /// threshold. Nothing is reported until the samples cover the whole window.
+    pub fn update(&mut self, used: u64, now: Instant) -> Option<f64> {
+        if self.samples.back().is_some_and(|(at, _)| now.duration_since(*at) < SAMPLE_INTERVAL) {
+            return None;
+        }
+        let window = Duration::from_secs(self.config.window * 60);
+        self.samples.push_back((now, used));
+        while self.samples.front().is_some_and(|(at, _)| now.duration_since(*at) > window) {
+            self.samples.pop_front();
+        }
+
+        let covered = self
+            .samples
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) + SAMPLE_INTERVAL > window);
+        let growth = if covered { slope(&self.samples) * 3600.0 } else { 0.0 };
+        let exceeded = growth > self.config.growth as f64 * MIB;
+        let started = exceeded && !self.exceeded;
+        self.exceeded = exceeded;
+        started.then_some(growth)
+    }
+}
+
+/// Least squares slope of the samples in bytes per second.
+fn slope(samples: &VecDeque<(Instant, u64)>) -> f64 {
+    let Some((start, _)) = samples.front() else {
+        return 0.0;
+    };
+    let n = samples.len() as f64;
+    let points = samples.iter().map(|(at, used)| (at.duration_since(*start).as_secs_f64(), *used));
+    let (mean_x, mean_y) =
+        points.clone().fold((0.0, 0.0), |(x, y), (px, py)| (x + px / n, y + py as f64 / n));
+    let (cov, var) = points.fold((0.0, 0.0), |(cov, var), (x, y)| {
+        (cov + (x - mean_x) * (y as f64 - mean_y), var + (x - mean_x).powi(2))
+    });
+    if var == 0.0 { 0.0 } else { cov / var }
+}
+
+/// Watch the core memory and offer a core restart when it keeps growing, a common mitigation for
+/// leaking cores on routers.
+pub fn spawn(
+    config: MemoryTrendConfig,
+    action_tx: UnboundedSender<Action>,
+) -> io::Result<JoinHandle<Option<()>>> {
+    TaskManager::spawn(ComponentId::Root, "memory-trend-watcher", async move {
+        let mut trend = MemoryTrend::new(config);
+        let mut memory = StreamHub::global().memory();
+        while let Some(record) = memory.recv().await {
+            if record.used == 0 {
+                continue;
+            }
+            let Some(growth) = trend.update(record.used, Instant::now()) else {
+                continue;
+            };
+            let message = format!(
+                "Core memory grew {}/h over the last {} min, now {}.",
+                human_bytes(growth, None),
+                config.window,
+                human_bytes(record.used as f64, None)
+            );
+            info!(growth, used = record.used, "Sustained core memory growth");
+            Timeline::record(EventKind::Alert, &message);
+            let msg =
+                AppMessage::from(("Memory growth", format!("{message}\n\nRestart the core?")))
+                    .msg_box_size(50, 25);
+            let restart = Box::new(Action::CoreActionRun(RESTART_ACTION));
+            if action_tx.send(Action::ConfirmRequest(msg, restart)).is_err() {
+                return;
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sustained_growth_alerts_once() {
+        let config = MemoryTrendConfig { growth: 64, window: 60 };
+        let mut trend = MemoryTrend::new(config);
+        let start = Instant::now();
+        let mut alerts = vec![];
+        // 120 MiB per hour for two hours
+        for step in 0..720 {
+            let secs = step * 10;
+            let used = 100 * MIB as u64 + secs * 120 * MIB as u64 / 3600;
+            if let Some(growth) = trend.update(used, start + Duration::from_secs(secs)) {
+                alerts.push((secs, growth));
+            }
+        }
+        assert_eq!(alerts.len(), 1);
+        let (secs, growth) = alerts[0];
+        // only once the hour is covered
+        assert!((3590..=3610).contains(&secs), "{secs}");
+        assert!((growth / MIB - 120.0).abs() < 1.0, "{growth}");
+    }
+
+    #[test]
+    fn stable_memory_does_not_alert() {
+        let mut trend = MemoryTrend::new(MemoryTrendConfig { growth: 64, window: 60 });
+        let start = Instant::now();
+        // saw tooth around 200 MiB, e.g. GC cycles
+        let alerted = (0..720).any(|step| {
+            let used = (200 + (step % 6) * 10) * MIB as u64;
+            trend.update(used, start + Duration::from_secs(step * 10)).is_some()
+        });
+        assert!(!alerted);
+    }
+}
@@ -1,28 +1,54 @@
+mod api_calls_component;
+mod api_reconnect_component;
+mod capture_file_component;
+mod chart_setting_component;
+mod confirm_component;
 mod connection_batch_terminate_component;
 mod connection_detail_component;
 mod connection_terminate_component;
 mod connections_component;
 mod connections_setting_component;
 mod core_config_component;
+mod core_config_export_component;
+mod core_config_import_component;
 mod dns_query_component;
+mod error_log_component;
 mod filter_component;
 mod footer_component;
+mod global_search_component;
 mod header_component;
 mod help_component;
+mod history_recorder;
+mod latency_calibration_component;
 mod logs_component;
+mod memory_watcher;
 mod msg_box_component;
+mod node_cache;
 mod overview_component;
+mod plugin_component;
+mod profiles_component;
+mod provider_watcher;
 mod proxies_component;
 mod proxy_detail_component;
 mod proxy_provider_detail_component;
 mod proxy_providers_component;
 mod proxy_setting_component;
+mod refresh_scheduler;
 pub mod root_component;
+mod rule_breakdown_component;
+mod rule_match_component;
 mod rule_providers_component;
 mod rules_component;
+pub mod search;
+mod secret_prompt_component;
+mod tab_switcher_component;
+mod tasks_component;
+mod tcp_ping_component;
+mod timeline_component;
 mod updates_component;
+mod whats_new_component;
 
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 
 use anyhow::Result;
 use crossterm::event::{KeyEvent, MouseEvent};
@@ -31,25 +57,36 @@ use ratatui::layout::Rect;
 use strum::IntoStaticStr;
 use tokio::sync::mpsc::UnboundedSender;
 
+use self::search::SearchEntry;
 use crate::action::Action;
-use crate::api::Api;
+use crate::api::{Api, ApiEvent, ApiObserver};
 use crate::config::Config;
+use crate::plugins;
+use crate::store::api_log::ApiLog;
+use crate::store::auth_failures::AuthFailures;
+use crate::store::stream_health::StreamHealth;
 use crate::tui::Event;
 use crate::widgets::shortcut::Shortcut;
 
 const HORIZ_STEP: usize = 4;
 
-/// Header tabs in display order; index is used for tab navigation and shortcuts
-const TABS: [ComponentId; 8] = [
-    ComponentId::Overview,
-    ComponentId::Connections,
-    ComponentId::Proxies,
-    ComponentId::ProxyProviders,
-    ComponentId::Logs,
-    ComponentId::Rules,
-    ComponentId::RuleProviders,
-    ComponentId::Config,
-];
+/// Header tabs in display order, plugin tabs come last; index is used for tab navigation and
+/// shortcuts
+static TABS: LazyLock<Vec<ComponentId>> = LazyLock::new(|| {
+    [
+        ComponentId::Overview,
+        ComponentId::Connections,
+        ComponentId::Proxies,
+        ComponentId::ProxyProviders,
+        ComponentId::Logs,
+        ComponentId::Rules,
+        ComponentId::RuleProviders,
+        ComponentId::Config,
+    ]
+    .into_iter()
+    .chain(plugins::PLUGIN_TABS.iter().map(|tab| ComponentId::Plugin(tab.name)))
+    .collect()
+});
 
 #[derive(Default, PartialEq, Debug, IntoStaticStr, Clone, Eq, Hash, Copy)]
 pub enum ComponentId {
@@ -65,17 +102,38 @@ pub enum ComponentId {
     ConnectionBatchTerminate,
     Connections,
     ConnectionsSetting,
+    CaptureFile,
     Proxies,
     ProxyDetail,
     ProxySetting,
+    LatencyCalibration,
+    ChartSetting,
     ProxyProviders,
     ProxyProviderDetail,
     Logs,
     Rules,
+    RuleBreakdown,
+    RuleMatch,
     RuleProviders,
     Config,
+    CoreConfigExport,
+    CoreConfigImport,
+    Profiles,
     DnsQuery,
+    TcpPing,
     Filter,
+    TabSwitcher,
+    GlobalSearch,
+    ApiReconnect,
+    Confirm,
+    SecretPrompt,
+    Tasks,
+    Timeline,
+    ErrorLog,
+    ApiCalls,
+    WhatsNew,
+    /// Custom tab registered in [`plugins::PLUGIN_TABS`], identified by its name.
+    Plugin(&'static str),
 }
 
 impl ComponentId {
@@ -99,15 +157,34 @@ impl ComponentId {
             ComponentId::Rules => Some("Rule"),
             ComponentId::RuleProviders => Some("R-Pr"),
             ComponentId::Config => Some("Cfg"),
+            ComponentId::Plugin(name) => plugins::find(name).map(|tab| tab.short_name),
             _ => Some(self.full_name()),
         }
     }
 
     pub fn full_name(self) -> &'static str {
-        self.into()
+        match self {
+            ComponentId::Plugin(name) => name,
+            _ => self.into(),
+        }
     }
 }
 
+/// Observer of the app's [`Api`], feeding the stores shown by the components: the [`ApiLog`],
+/// [`AuthFailures`] and [`StreamHealth`].
+pub fn api_observer() -> ApiObserver {
+    Arc::new(|event| match event {
+        ApiEvent::Request(req) => ApiLog::record(req),
+        ApiEvent::Authorized(true) => AuthFailures::global().record_success(),
+        ApiEvent::Authorized(false) => AuthFailures::global().record_unauthorized(),
+        ApiEvent::Stalled(path) => StreamHealth::set_stalled(path, true),
+        ApiEvent::Message { path, bytes } => {
+            StreamHealth::set_stalled(path, false);
+            StreamHealth::record_message(path, bytes);
+        }
+    })
+}
+
 /// `Component` is a trait that represents a visual and interactive element of the user interface.
 ///
 /// Implementors of this trait can be registered with the main application loop and will be able to
@@ -121,6 +198,11 @@ pub trait Component {
         vec![]
     }
 
+    /// Get the loaded items listed by the global search.
+    fn search_entries(&self) -> Vec<SearchEntry> {
+        vec![]
+    }
+
     /// Initialize the component with a specified area if necessary.
     ///
     /// # Arguments
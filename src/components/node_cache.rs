@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Results of per-node background loads of a node card popup by node name, e.g. the proxy
+/// detail's lookups. Cleared when the popup is closed or shows another provider, results of loads
+/// started before are dropped.
+#[derive(Debug)]
+pub struct NodeCache<T> {
+    inner: Arc<RwLock<Inner<T>>>,
+}
+
+#[derive(Debug)]
+struct Inner<T> {
+    generation: u64,
+    /// `None` while loading
+    entries: HashMap<String, Option<T>>,
+}
+
+/// A load started by [`NodeCache::start`], moved into its task.
+pub struct NodeLoad<T> {
+    inner: Arc<RwLock<Inner<T>>>,
+    generation: u64,
+    node: String,
+}
+
+impl<T> Default for NodeCache<T> {
+    fn default() -> Self {
+        Self { inner: Arc::new(RwLock::new(Inner { generation: 0, entries: HashMap::new() })) }
+    }
+}
+
+impl<T: Clone> NodeCache<T> {
+    /// Mark `node` as loading until the returned load finishes.
+    pub fn start(&self, node: &str) -> NodeLoad<T> {
+        let mut inner = self.inner.write().unwrap();
+        inner.entries.insert(node.to_owned(), None);
+        NodeLoad {
+            inner: Arc::clone(&self.inner),
+            generation: inner.generation,
+            node: node.to_owned(),
+        }
+    }
+
+    /// Result of `node`, `Some(None)` while loading.
+    pub fn get(&self, node: &str) -> Option<Option<T>> {
+        self.inner.read().unwrap().entries.get(node).cloned()
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.inner.write().unwrap();
+        inner.generation += 1;
+        inner.entries.clear();
+    }
+}
+
+impl<T> NodeLoad<T> {
+    /// Keep the result, unless the cache was cleared since the load started.
+    pub fn finish(self, value: T) {
+        let mut inner = self.inner.write().unwrap();
+        if inner.generation == self.generation {
+            inner.entries.insert(self.node, Some(value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_started_before_clearing_are_dropped() {
+        let cache = NodeCache::default();
+        assert_eq!(cache.get("a"), None);
+        let load = cache.start("a");
+        assert_eq!(cache.get("a"), Some(None));
+        load.finish(1);
+        assert_eq!(cache.get("a"), Some(Some(1)));
+
+        let stale = cache.start("b");
+        cache.clear();
+        stale.finish(2);
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b"), None);
+    }
+}
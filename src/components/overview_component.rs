@@ -1,30 +1,51 @@
+use std::fs;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use const_format::concatcp;
-use futures_util::{StreamExt, TryStreamExt, future};
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Style, Stylize};
 use ratatui::symbols::Marker;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{
-    Axis, Block, BorderType, Cell, Chart, Dataset, GraphType, Padding, Row, Table,
+    Axis, Block, BorderType, Cell, Chart, Dataset, GraphType, LegendPosition, Padding, Row, Table,
 };
 use ringbuffer::{AllocRingBuffer, RingBuffer};
+use time::OffsetDateTime;
 use tokio::sync::watch::Receiver;
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info, warn};
+use tracing::info;
 
 use crate::action::Action;
 use crate::api::Api;
+use crate::api::stream::{MEMORY_PATH, TRAFFIC_PATH};
 use crate::components::{Component, ComponentId};
-use crate::config::OverviewBufferConfig;
-use crate::models::{ConnectionStats, Memory, Traffic};
+use crate::config::{Config, HistoryConfig, OverviewBufferConfig, get_project_dir};
+use crate::models::{Memory, Traffic};
 use crate::palette;
+use crate::store::bursts::Burst;
+use crate::store::chart_setting::ChartSetting;
+use crate::store::churn::ChurnRate;
+use crate::store::closed_conns::ClosedStats;
+use crate::store::connection_stats::ConnectionStats;
+use crate::store::history::History;
+use crate::store::ip_family::{FamilyShare, IpFamilyStats};
+use crate::store::layout_setting::LayoutSetting;
+use crate::store::snapshot_drops::DropStats;
+use crate::store::stream_health::StreamHealth;
+use crate::store::stream_hub::StreamHub;
+use crate::task_manager::TaskManager;
 use crate::utils::axis::{axis_bounds, axis_labels};
 use crate::utils::byte_size::{ByteSizeOptExt, human_bytes};
+use crate::utils::csv::CsvWriter;
+use crate::utils::encryption::Encryption;
+use crate::utils::number::format_thousands;
+use crate::utils::path::unused_path;
 use crate::utils::symbols::arrow;
+use crate::widgets::shortcut::{Fragment, Shortcut};
 
 const UP: &str = concatcp!(arrow::UP, " ");
 const DOWN: &str = concatcp!(" ", arrow::DOWN);
@@ -33,12 +54,21 @@ type Series = Vec<(f64, f64)>;
 
 #[derive(Debug)]
 pub struct OverviewComponent {
-    api: Option<Arc<Api>>,
     token: CancellationToken,
+    /// Token of the traffic and memory loaders, `None` while not watching
+    streams: Option<CancellationToken>,
 
     stats_rx: Receiver<Option<ConnectionStats>>,
-    memory: Arc<Mutex<AllocRingBuffer<Memory>>>,
-    traffic: Arc<Mutex<AllocRingBuffer<Traffic>>>,
+    /// Records with the unix timestamp they arrived at
+    memory: Arc<Mutex<AllocRingBuffer<(i64, Memory)>>>,
+    traffic: Arc<Mutex<AllocRingBuffer<(i64, Traffic)>>>,
+    /// Session peak of (up, down) rates, kept beyond the traffic buffer
+    peaks: Arc<Mutex<(u64, u64)>>,
+    /// Connection churn per connections snapshot, sized like the traffic buffer
+    churn: Arc<Mutex<AllocRingBuffer<ChurnRate>>>,
+    history: HistoryConfig,
+    /// Charts show the recorded history instead of the live streams
+    show_history: bool,
 }
 
 impl OverviewComponent {
@@ -48,86 +78,191 @@ impl OverviewComponent {
     ) -> Self {
         let memory = AllocRingBuffer::new(store_capacity.memory.get());
         let traffic = AllocRingBuffer::new(store_capacity.traffic.get());
+        let churn = AllocRingBuffer::new(store_capacity.traffic.get());
         Self {
-            api: Default::default(),
             token: Default::default(),
+            streams: None,
 
             stats_rx,
             memory: Arc::new(Mutex::new(memory)),
             traffic: Arc::new(Mutex::new(traffic)),
+            peaks: Default::default(),
+            churn: Arc::new(Mutex::new(churn)),
+            history: Default::default(),
+            show_history: false,
         }
     }
 
-    fn load_memory(&mut self) -> Result<()> {
-        info!("Loading memory");
-        let token = self.token.clone();
-        let api = Arc::clone(self.api.as_ref().unwrap());
+    /// Subscribe the shared traffic and memory streams, only while the tab is shown.
+    fn watch_streams(&mut self) -> Result<()> {
+        if self.streams.is_some() {
+            return Ok(());
+        }
+        info!("Watching traffic and memory");
+        let token = self.token.child_token();
+        self.streams = Some(token.clone());
+
+        let mut memory = StreamHub::global().memory();
         let store = Arc::clone(&self.memory);
+        let memory_token = token.clone();
+        TaskManager::spawn(ComponentId::Overview, "memory-loader", async move {
+            while let Some(Some(record)) = memory_token.run_until_cancelled(memory.recv()).await {
+                if record.used > 0 {
+                    store.lock().unwrap().enqueue((now(), record));
+                }
+            }
+        })?;
 
-        tokio::task::Builder::new().name("memory-loader").spawn(async move {
-            let stream = match api.stream_memory().await {
-                Ok(stream) => stream,
-                Err(e) => {
-                    error!(error = ?e, "Failed to get memory stream");
-                    return;
+        let mut traffic = StreamHub::global().traffic();
+        let store = Arc::clone(&self.traffic);
+        let peaks = Arc::clone(&self.peaks);
+        TaskManager::spawn(ComponentId::Overview, "traffic-loader", async move {
+            while let Some(Some(record)) = token.run_until_cancelled(traffic.recv()).await {
+                {
+                    let mut peaks = peaks.lock().unwrap();
+                    peaks.0 = peaks.0.max(record.up);
+                    peaks.1 = peaks.1.max(record.down);
                 }
-            };
-            stream
-                .take_until(token.cancelled())
-                .inspect_err(|e| warn!("Failed to parse memory: {e}"))
-                .filter_map(|res| future::ready(res.ok()))
-                .for_each(|record| {
-                    if record.used > 0 {
-                        store.lock().unwrap().enqueue(record);
-                    }
-                    future::ready(())
-                })
-                .await;
+                store.lock().unwrap().enqueue((now(), record));
+            }
         })?;
         Ok(())
     }
 
-    fn load_traffic(&mut self) -> Result<()> {
-        info!("Loading traffic");
-        let token = self.token.clone();
-        let api = Arc::clone(self.api.as_ref().unwrap());
-        let store = Arc::clone(&self.traffic);
+    /// Write the traffic and memory buffers to a new CSV file in the data directory, encrypted like
+    /// other exports.
+    fn dump_csv(&self) -> Result<PathBuf> {
+        let content = {
+            let traffic = self.traffic.lock().unwrap();
+            let memory = self.memory.lock().unwrap();
+            speed_csv(traffic.iter(), memory.iter())
+        };
+        let dir = get_project_dir().data_dir().join("dumps");
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Fail to create directory `{}`", dir.display()))?;
+        let path = unused_path(&dir, &format!("traffic-{}", now()), "csv");
+        Encryption::write(&path, content.as_bytes())?;
+        info!(path = %path.display(), "Traffic history dumped");
+        Ok(path)
+    }
 
-        tokio::task::Builder::new().name("traffic-loader").spawn(async move {
-            let stream = match api.stream_traffic().await {
-                Ok(stream) => stream,
-                Err(e) => {
-                    error!(error = ?e, "Failed to get traffic stream");
-                    return;
+    /// Release the streams while the tab is hidden, the hub stops them if nobody else watches.
+    fn unwatch_streams(&mut self) {
+        if let Some(token) = self.streams.take() {
+            info!("Stopped watching traffic and memory");
+            token.cancel();
+        }
+    }
+
+    fn load_churn(&mut self) -> Result<()> {
+        let token = self.token.clone();
+        let mut stats_rx = self.stats_rx.clone();
+        let store = Arc::clone(&self.churn);
+
+        TaskManager::spawn(ComponentId::Overview, "churn-sampler", async move {
+            while token.run_until_cancelled(stats_rx.changed()).await.is_some_and(|r| r.is_ok()) {
+                let churn = stats_rx.borrow_and_update().as_ref().map(|s| s.churn);
+                if let Some(churn) = churn {
+                    store.lock().unwrap().enqueue(churn);
                 }
-            };
-            stream
-                .take_until(token.cancelled())
-                .inspect_err(|e| warn!("Failed to parse traffic: {e}"))
-                .filter_map(|res| future::ready(res.ok()))
-                .for_each(|record| {
-                    store.lock().unwrap().enqueue(record);
-                    future::ready(())
-                })
-                .await;
+            }
         })?;
         Ok(())
     }
 
+    /// Connections closed since the connections stream started and the bytes they transferred.
+    fn closed_line<'a>(closed: Option<&ClosedStats>) -> Line<'a> {
+        let Some(closed) = closed.filter(|c| c.conns > 0) else {
+            return Line::default();
+        };
+        Line::from(vec![
+            Span::raw(" closed ").dark_gray(),
+            Span::raw(format_thousands(closed.conns)).bold(),
+            Span::raw(" conns ").dark_gray(),
+            Span::styled(UP, Style::default().fg(palette::UP)),
+            Span::raw(human_bytes(closed.upload as f64, None)),
+            Span::raw(" / ").dark_gray(),
+            Span::raw(human_bytes(closed.download as f64, None)),
+            Span::styled(DOWN, Style::default().fg(palette::DOWN)),
+            Span::raw(" "),
+        ])
+    }
+
+    /// Warns while connections snapshots are dropped, their connections are never shown.
+    fn drops_line<'a>(drops: Option<&DropStats>) -> Line<'a> {
+        let Some(drops) = drops.filter(|d| d.recent) else {
+            return Line::default();
+        };
+        Line::from(vec![
+            Span::raw(" ").yellow(),
+            Span::raw(format_thousands(drops.dropped)).yellow().bold(),
+            Span::raw(" snapshots dropped, raise buffer.snapshots ").yellow(),
+        ])
+    }
+
+    /// Source IPs opening connections in bursts, e.g. ` burst 10.0.0.2 (120) +1 `.
+    fn bursts_line<'a>(bursts: Option<&[Burst]>) -> Line<'a> {
+        let Some((first, rest)) = bursts.and_then(<[Burst]>::split_first) else {
+            return Line::default();
+        };
+        let mut spans = vec![
+            Span::raw(" burst ").light_red(),
+            Span::raw(first.source_ip.clone()).light_red().bold(),
+            Span::raw(format!(" ({}) ", format_thousands(first.opened as u64))).light_red(),
+        ];
+        if !rest.is_empty() {
+            spans.push(Span::raw(format!("+{} ", rest.len())).light_red());
+        }
+        Line::from(spans)
+    }
+
+    /// IPv4/IPv6 share of connections and rates, classified by destination IP.
+    fn ip_family_line<'a>(stats: Option<&IpFamilyStats>) -> Line<'a> {
+        let Some(stats) = stats.filter(|s| s.v6_conns_percent().is_some()) else {
+            return Line::from(" IPv4 / IPv6: - ").dark_gray();
+        };
+        let share = |name: &'static str, share: &FamilyShare| {
+            vec![
+                Span::raw(name).bold(),
+                Span::raw(format!(" {} conns ", share.conns)),
+                Span::styled(UP, Style::default().fg(palette::UP)),
+                Span::raw(human_bytes(share.up_rate as f64, Some("/s"))),
+                Span::raw(" / ").dark_gray(),
+                Span::raw(human_bytes(share.down_rate as f64, Some("/s"))),
+                Span::styled(DOWN, Style::default().fg(palette::DOWN)),
+            ]
+        };
+        let mut spans = vec![Span::raw(" ")];
+        spans.extend(share("IPv4", &stats.v4));
+        spans.push(Span::raw("  │  ").dark_gray());
+        spans.extend(share("IPv6", &stats.v6));
+        spans.push(
+            Span::raw(format!(" ({}%) ", stats.v6_conns_percent().unwrap_or_default())).cyan(),
+        );
+        Line::from(spans)
+    }
+
     fn render_header(&mut self, frame: &mut Frame, area: Rect) {
-        let conn_stats = {
+        let (conn_stats, ip_family_line, closed_line, bursts_line, drops_line) = {
             let stats = self.stats_rx.borrow();
             let stats = stats.as_ref();
-            (
+            let conn_stats = (
                 stats.map(|s| s.up_total).fmt(None),
                 stats.map(|s| s.down_total).fmt(None),
                 stats.map(|s| s.conns_size.to_string()).unwrap_or("-".into()),
                 stats.map(|s| s.memory).fmt(None),
+            );
+            (
+                conn_stats,
+                Self::ip_family_line(stats.map(|s| &s.ip_family)),
+                Self::closed_line(stats.map(|s| &s.closed)),
+                Self::bursts_line(stats.map(|s| s.bursts.as_slice())),
+                Self::drops_line(stats.map(|s| &s.drops)),
             )
         };
         let traffic = {
             let guard = self.traffic.lock().unwrap();
-            guard.back().map(|t| (t.up, t.down))
+            guard.back().map(|(_, t)| (t.up, t.down))
         };
 
         let header = Row::new([
@@ -173,7 +308,14 @@ impl OverviewComponent {
         )
         .header(header)
         .column_spacing(2)
-        .block(Block::bordered().border_type(BorderType::Rounded));
+        .block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title_top(bursts_line.left_aligned())
+                .title_top(closed_line.right_aligned())
+                .title_bottom(ip_family_line.centered())
+                .title_bottom(drops_line.right_aligned()),
+        );
         frame.render_widget(table, area);
     }
 
@@ -182,45 +324,100 @@ impl OverviewComponent {
             Block::bordered().border_type(BorderType::Rounded).padding(Padding::new(1, 1, 1, 1));
         frame.render_widget(outer.clone(), area);
 
+        let traffic_width = LayoutSetting::global().read().unwrap().overview_traffic_width;
         let chunks = Layout::horizontal([
-            Constraint::Percentage(49),
+            Constraint::Percentage(traffic_width.saturating_sub(1)),
             Constraint::Percentage(1),
             Constraint::Fill(1),
         ])
         .split(outer.inner(area));
 
-        let traffic = self.split_traffic();
-        self.render_traffic_chart(frame, chunks[0], traffic);
-        let memory: Series = self
-            .memory
-            .lock()
-            .unwrap()
-            .iter()
-            .enumerate()
-            .map(|(i, m)| (i as f64, m.used as f64))
-            .collect();
-        self.render_memory_chart(frame, chunks[2], memory);
+        let (traffic, memory): (Vec<_>, Series) = if self.show_history {
+            History::snapshot()
+                .iter()
+                .enumerate()
+                .map(|(i, s)| ((s.up, s.down), (i as f64, s.memory as f64)))
+                .unzip()
+        } else {
+            let traffic =
+                self.traffic.lock().unwrap().iter().map(|(_, t)| (t.up, t.down)).collect();
+            let memory = self.memory.lock().unwrap();
+            let memory = memory.iter().enumerate().map(|(i, (_, m))| (i as f64, m.used as f64));
+            (traffic, memory.collect())
+        };
+        self.render_traffic_chart(frame, chunks[0], Self::split_traffic(&traffic));
+        let [memory_area, churn_area] =
+            Layout::vertical([Constraint::Percentage(65), Constraint::Percentage(35)])
+                .areas(chunks[2]);
+        self.render_memory_chart(frame, memory_area, memory);
+        self.render_churn_chart(frame, churn_area);
+    }
+
+    fn chart_title(&self, name: &str) -> String {
+        if self.show_history {
+            format!("{name} history, last {}h", self.history.retention)
+        } else {
+            format!("{name} chart")
+        }
     }
 
-    fn split_traffic(&mut self) -> [Series; 2] {
-        let traffic = self.traffic.lock().unwrap();
+    /// Up and down series of (up, down) rates.
+    fn split_traffic(traffic: &[(u64, u64)]) -> [Series; 2] {
         let mut up_points = Vec::with_capacity(traffic.len());
         let mut down_points = Vec::with_capacity(traffic.len());
 
-        for (i, t) in traffic.iter().enumerate() {
-            up_points.push((i as f64, t.up as f64));
-            down_points.push((i as f64, -(t.down as f64)));
+        for (i, (up, down)) in traffic.iter().enumerate() {
+            up_points.push((i as f64, *up as f64));
+            down_points.push((i as f64, -(*down as f64)));
         }
 
+        let setting = ChartSetting::global().read().unwrap();
+        setting.smooth(&mut up_points);
+        setting.smooth(&mut down_points);
         [up_points, down_points]
     }
 
+    /// Horizontal line at the session peak, only drawn while the peak fits the current bounds
+    /// so an old spike doesn't flatten the chart; the value is always shown in the legend.
+    fn peak_dataset<'a>(
+        peak: f64,
+        bound: (f64, f64),
+        len: usize,
+        data: &'a mut Series,
+    ) -> Dataset<'a> {
+        let label = human_bytes(peak.abs(), Some("/s"));
+        if peak != 0.0 && peak >= bound.0 && peak <= bound.1 {
+            *data = vec![(0.0, peak), (len as f64, peak)];
+        }
+        Dataset::default()
+            .name(format!("peak {label}"))
+            .marker(Marker::Braille)
+            .style(Style::default().dark_gray())
+            .graph_type(GraphType::Line)
+            .data(data)
+    }
+
     fn render_traffic_chart(&mut self, frame: &mut Frame, area: Rect, traffic: [Series; 2]) {
         let colors = [palette::UP, palette::DOWN];
+        let setting = *ChartSetting::global().read().unwrap();
+        let peaks = if self.show_history {
+            [
+                traffic[0].iter().map(|(_, y)| *y).fold(0.0, f64::max),
+                traffic[1].iter().map(|(_, y)| *y).fold(0.0, f64::min),
+            ]
+        } else {
+            let peaks = self.peaks.lock().unwrap();
+            [peaks.0 as f64, -(peaks.1 as f64)]
+        };
+        let legend_positions = [LegendPosition::TopRight, LegendPosition::BottomRight];
         let chunks =
             Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).split(area);
         let blocks = [
-            Some(Block::default().title(Line::from("Traffic chart").cyan().bold().centered())),
+            Some(
+                Block::default()
+                    .title(Line::from(self.chart_title("Traffic")).cyan().bold().centered())
+                    .title_top(StreamHealth::indicator(TRAFFIC_PATH)),
+            ),
             None,
         ];
         for index in 0..2 {
@@ -238,8 +435,20 @@ impl OverviewComponent {
                 .style(colors[index])
                 .graph_type(GraphType::Line)
                 .data(&traffic[index]);
+            let mut datasets = vec![dataset];
+            let mut peak_data = vec![];
+            if setting.peak_markers {
+                datasets.push(Self::peak_dataset(
+                    peaks[index],
+                    bound,
+                    traffic[index].len(),
+                    &mut peak_data,
+                ));
+            }
 
-            let mut chart = Chart::new(vec![dataset])
+            let mut chart = Chart::new(datasets)
+                .legend_position(Some(legend_positions[index]))
+                .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)))
                 .x_axis(Axis::default().bounds([0.0, traffic[index].len() as f64]))
                 .y_axis(
                     Axis::default()
@@ -263,7 +472,8 @@ impl OverviewComponent {
             .block(
                 Block::default()
                     .padding(Padding::left(1))
-                    .title(Line::from("Memory chart").cyan().bold().centered()),
+                    .title(Line::from(self.chart_title("Memory")).cyan().bold().centered())
+                    .title_top(StreamHealth::indicator(MEMORY_PATH)),
             )
             .x_axis(Axis::default().bounds([0.0, data.len() as f64]))
             .y_axis(
@@ -274,6 +484,76 @@ impl OverviewComponent {
             );
         frame.render_widget(chart, area);
     }
+
+    /// Connections opened and closed per minute, high churn with stable totals hints at retry
+    /// loops.
+    fn render_churn_chart(&mut self, frame: &mut Frame, area: Rect) {
+        let (opened, closed): (Series, Series) = self
+            .churn
+            .lock()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(i, c)| ((i as f64, c.opened), (i as f64, c.closed)))
+            .unzip();
+        let latest = |series: &Series| series.last().map_or(0.0, |(_, v)| *v).round();
+        let datasets = vec![
+            Dataset::default()
+                .name(format!("opened {}/min", latest(&opened)))
+                .marker(Marker::Braille)
+                .style(palette::UP)
+                .graph_type(GraphType::Line)
+                .data(&opened),
+            Dataset::default()
+                .name(format!("closed {}/min", latest(&closed)))
+                .marker(Marker::Braille)
+                .style(palette::DOWN)
+                .graph_type(GraphType::Line)
+                .data(&closed),
+        ];
+
+        let max = opened.iter().chain(&closed).map(|(_, y)| *y).fold(1.0, f64::max);
+        let labels = [0.0, max / 2.0, max].map(|v| format!("{v:.0}"));
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .padding(Padding::left(1))
+                    .title(Line::from("Connection churn").cyan().bold().centered()),
+            )
+            .legend_position(Some(LegendPosition::TopLeft))
+            .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)))
+            .x_axis(Axis::default().bounds([0.0, opened.len() as f64]))
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().dark_gray())
+                    .bounds([0.0, max])
+                    .labels(labels),
+            );
+        frame.render_widget(chart, area);
+    }
+}
+
+fn now() -> i64 {
+    OffsetDateTime::now_utc().unix_timestamp()
+}
+
+/// One row per traffic record with the memory last reported at that time, empty before the first
+/// memory record.
+fn speed_csv<'a>(
+    traffic: impl Iterator<Item = &'a (i64, Traffic)>,
+    memory: impl Iterator<Item = &'a (i64, Memory)>,
+) -> String {
+    let mut memory = memory.peekable();
+    let mut used = None;
+    let mut csv = CsvWriter::new(&["time", "up", "down", "memory"]);
+    for (time, t) in traffic {
+        while let Some((_, m)) = memory.next_if(|(at, _)| at <= time) {
+            used = Some(m.used);
+        }
+        let used = used.map(|u| u.to_string()).unwrap_or_default();
+        csv.row(&[time, &t.up, &t.down, &used]);
+    }
+    csv.finish()
 }
 
 impl Drop for OverviewComponent {
@@ -288,22 +568,69 @@ impl Component for OverviewComponent {
         ComponentId::Overview
     }
 
-    fn init(&mut self, api: Arc<Api>) -> Result<()> {
-        self.api = Some(api);
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        let mut shortcuts = vec![
+            Shortcut::new(vec![Fragment::hl("["), Fragment::raw(" chart "), Fragment::hl("]")]),
+            Shortcut::from("settings", 0).unwrap(),
+            Shortcut::new(vec![Fragment::hl("e"), Fragment::raw(" csv dump")]),
+        ];
+        if self.history.enabled {
+            let label = if self.show_history { "live" } else { "history" };
+            shortcuts
+                .push(Shortcut::new(vec![Fragment::hl("H"), Fragment::raw(format!(" {label}"))]));
+        }
+        shortcuts
+    }
+
+    fn register_config_handler(&mut self, config: Arc<Config>) -> Result<()> {
+        self.history = config.history;
+        self.show_history &= self.history.enabled;
+        Ok(())
+    }
+
+    fn init(&mut self, _api: Arc<Api>) -> Result<()> {
         self.token = CancellationToken::new();
-        self.load_memory()?;
-        self.load_traffic()?;
+        self.streams = None;
+        self.load_churn()?;
         Ok(())
     }
 
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let grow = match key.code {
+            KeyCode::Char(']') => true,
+            KeyCode::Char('[') => false,
+            KeyCode::Char('s') => return Ok(Some(Action::ChartSetting)),
+            KeyCode::Char('e') => {
+                return Ok(Some(match self.dump_csv() {
+                    Ok(path) => Action::Info(
+                        ("Traffic dump", format!("Written to `{}`", path.display())).into(),
+                    ),
+                    Err(e) => Action::Error(ComponentId::Overview, ("Traffic dump", e).into()),
+                }));
+            }
+            KeyCode::Char('H') if self.history.enabled => {
+                self.show_history = !self.show_history;
+                return Ok(Some(Action::Shortcuts(self.shortcuts())));
+            }
+            _ => return Ok(None),
+        };
+        let changed = LayoutSetting::global().write().unwrap().resize_overview_traffic(grow);
+        Ok(changed.then_some(Action::LayoutChanged))
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
-        if matches!(action, Action::Quit) {
-            self.token.cancel();
+        match action {
+            Action::Quit => self.token.cancel(),
+            Action::TabSwitch(to) if to != self.id() => self.unwatch_streams(),
+            Action::ConfigReloaded(config) => self.register_config_handler(config)?,
+            _ => {}
         }
         Ok(None)
     }
 
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        // only drawn while shown, warmed up or idle tabs don't subscribe
+        self.watch_streams()?;
         let chunks = Layout::vertical([Constraint::Length(4), Constraint::Min(0)]).split(area);
 
         self.render_header(frame, chunks[0]);
@@ -324,4 +651,16 @@ mod tests {
         let inner = b.inner(area);
         assert_eq!(inner, Rect::new(1, 1, 8, 3));
     }
+
+    #[test]
+    fn speed_csv_joins_latest_memory() {
+        use super::*;
+
+        let traffic = [(10, Traffic { up: 1, down: 2 }), (11, Traffic { up: 3, down: 4 })];
+        let memory = [(11, Memory { used: 64 }), (12, Memory { used: 65 })];
+        assert_eq!(
+            speed_csv(traffic.iter(), memory.iter()),
+            "time,up,down,memory\n10,1,2,\n11,3,4,64\n"
+        );
+    }
 }
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::info;
+
+use crate::action::Action;
+use crate::api::Api;
+use crate::components::{Component, ComponentId};
+use crate::config::Config;
+use crate::plugins::{Plugin, PluginContext, PluginTab};
+use crate::widgets::shortcut::Shortcut;
+
+/// Adapts a [`Plugin`] to [`Component`], the plugin is initialized once API, action handler and
+/// config have all been registered.
+pub struct PluginComponent {
+    tab: &'static PluginTab,
+    plugin: Box<dyn Plugin>,
+    api: Option<Arc<Api>>,
+    action_tx: Option<UnboundedSender<Action>>,
+    initialized: bool,
+}
+
+impl PluginComponent {
+    pub fn new(tab: &'static PluginTab) -> Self {
+        Self { tab, plugin: (tab.build)(), api: None, action_tx: None, initialized: false }
+    }
+
+    fn init_plugin(&mut self, config: Option<Arc<Config>>) -> Result<()> {
+        if self.initialized {
+            return Ok(());
+        }
+        let api = self.api.clone().ok_or_else(|| anyhow!("API is not initialized"))?;
+        let action_tx =
+            self.action_tx.clone().ok_or_else(|| anyhow!("action handler is not registered"))?;
+        info!("Initializing plugin `{}`", self.tab.name);
+        self.plugin.init(PluginContext { api, action_tx, config })?;
+        self.initialized = true;
+        Ok(())
+    }
+}
+
+impl Drop for PluginComponent {
+    fn drop(&mut self) {
+        info!("Plugin `{}` dropped", self.tab.name);
+    }
+}
+
+impl Component for PluginComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::Plugin(self.tab.name)
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        self.plugin.shortcuts()
+    }
+
+    fn init(&mut self, api: Arc<Api>) -> Result<()> {
+        self.api = Some(api);
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Arc<Config>) -> Result<()> {
+        self.init_plugin(Some(config))
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        self.plugin.handle_key_event(key)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        // no config registered, e.g. in tests
+        self.init_plugin(None)?;
+        self.plugin.update(&action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        self.plugin.draw(frame, area)
+    }
+}
@@ -0,0 +1,397 @@
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::prelude::{Color, Style};
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Paragraph};
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::info;
+use tui_input::Input;
+
+use crate::action::Action;
+use crate::components::core_config_import_component::render_changes;
+use crate::components::{Component, ComponentId};
+use crate::store::profiles::{Profile, Profiles, validate_name};
+use crate::store::timeline::{EventKind, Timeline};
+use crate::task_manager::BlockingJob;
+use crate::utils::json_diff::{self, Change};
+use crate::utils::symbols::arrow;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::time::{format_datetime, format_time_from_now};
+use crate::utils::tui_input::input_request;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+/// Lists locally stored core config profiles and applies the selected one after confirming the
+/// changes against the running config.
+#[derive(Debug, Default)]
+pub struct ProfilesComponent {
+    show: bool,
+    store: Profiles,
+    running: Value,
+    profiles: Vec<Profile>,
+    selected: usize,
+    mode: Mode,
+    error: Option<String>,
+    /// Existing profile the next save overwrites, set once asked to confirm it
+    overwrite: Option<String>,
+    /// Reading the selected profile, decryption makes it slow
+    loading: BlockingJob<Mode>,
+    /// Writing the running config as the named profile
+    saving: BlockingJob<String>,
+
+    action_tx: Option<UnboundedSender<Action>>,
+}
+
+#[derive(Debug, Default)]
+enum Mode {
+    #[default]
+    List,
+    /// Name input for saving the running config as a profile
+    Naming(Input),
+    Confirm {
+        name: String,
+        patch: Value,
+        changes: Vec<Change>,
+        scroll: usize,
+    },
+}
+
+impl ProfilesComponent {
+    fn show(&mut self, running: Value) {
+        self.show = true;
+        self.running = running;
+        self.mode = Mode::List;
+        self.error = None;
+        self.reload();
+    }
+
+    fn hide(&mut self) {
+        self.show = false;
+        self.running = Value::Null;
+        self.profiles.clear();
+        self.mode = Mode::List;
+        self.error = None;
+        self.overwrite = None;
+        self.loading = BlockingJob::default();
+        self.saving = BlockingJob::default();
+    }
+
+    fn reload(&mut self) {
+        match self.store.list() {
+            Ok(profiles) => self.profiles = profiles,
+            Err(e) => self.error = Some(format!("{e:#}")),
+        }
+        self.selected = self.selected.min(self.profiles.len().saturating_sub(1));
+    }
+
+    fn set_mode(&mut self, mode: Mode) -> Result<()> {
+        self.mode = mode;
+        self.error = None;
+        self.overwrite = None;
+        self.action_tx.as_ref().unwrap().send(Action::Shortcuts(self.shortcuts()))?;
+        Ok(())
+    }
+
+    /// Starts reading the selected profile, its changes are confirmed once read.
+    fn load_selected(&mut self) -> Result<()> {
+        let name = self.profiles.get(self.selected).context("No profile selected")?.name.clone();
+        let (store, running) = (self.store.clone(), self.running.clone());
+        self.loading.start(ComponentId::Profiles, "profile-loader", move || {
+            let target = store.load(&name)?;
+            let mut changes = vec![];
+            let patch = json_diff::diff(&running, &target, &mut changes)
+                .with_context(|| format!("Profile `{name}` matches the running config"))?;
+            Ok(Mode::Confirm { name, patch, changes, scroll: 0 })
+        })?;
+        Ok(())
+    }
+
+    /// Saves the running config as `name`, an existing profile is only overwritten once confirmed
+    /// by saving it again.
+    fn save_running(&mut self, name: String) -> Result<()> {
+        validate_name(&name)?;
+        if self.overwrite.as_ref() != Some(&name) && self.profiles.iter().any(|p| p.name == name) {
+            self.overwrite = Some(name);
+            return Ok(());
+        }
+        self.overwrite = None;
+        let (store, running) = (self.store.clone(), self.running.clone());
+        self.saving.start(ComponentId::Profiles, "profile-saver", move || {
+            store.save(&name, &running)?;
+            Ok(name)
+        })?;
+        Ok(())
+    }
+
+    /// Applies a finished load or save.
+    fn poll(&mut self) -> Result<()> {
+        match self.loading.poll() {
+            Some(Ok(mode)) => self.set_mode(mode)?,
+            Some(Err(e)) => self.error = Some(format!("{e:#}")),
+            None => (),
+        }
+        match self.saving.poll() {
+            Some(Ok(name)) => {
+                info!(name, "Running config saved as profile");
+                self.reload();
+                self.selected =
+                    self.profiles.iter().position(|p| p.name == name).unwrap_or_default();
+                self.set_mode(Mode::List)?;
+            }
+            Some(Err(e)) => self.error = Some(format!("{e:#}")),
+            None => (),
+        }
+        Ok(())
+    }
+
+    fn is_busy(&self) -> bool {
+        self.loading.is_running() || self.saving.is_running()
+    }
+
+    fn handle_list_key(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.hide();
+                return Ok(Some(Action::Unfocus));
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.profiles.len().saturating_sub(1))
+            }
+            KeyCode::Char('n') => self.set_mode(Mode::Naming(Input::default()))?,
+            KeyCode::Enter if self.is_busy() => (),
+            KeyCode::Enter => {
+                self.error = None;
+                if let Err(e) = self.load_selected() {
+                    self.error = Some(format!("{e:#}"));
+                }
+            }
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn handle_naming_key(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let Mode::Naming(input) = &mut self.mode else {
+            return Ok(None);
+        };
+        match key.code {
+            KeyCode::Esc => self.set_mode(Mode::List)?,
+            KeyCode::Enter if self.loading.is_running() || self.saving.is_running() => (),
+            KeyCode::Enter => {
+                let name = input.value().trim().to_string();
+                self.error = None;
+                if let Err(e) = self.save_running(name) {
+                    self.error = Some(format!("{e:#}"));
+                }
+            }
+            _ => {
+                if let Some(req) = input_request(key) {
+                    let _ = input.handle(req);
+                    self.error = None;
+                    self.overwrite = None;
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn handle_confirm_key(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let Mode::Confirm { changes, scroll, .. } = &mut self.mode else {
+            return Ok(None);
+        };
+        match key.code {
+            KeyCode::Esc => self.set_mode(Mode::List)?,
+            KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                *scroll = (*scroll + 1).min(changes.len().saturating_sub(1))
+            }
+            KeyCode::Enter => {
+                let Mode::Confirm { name, patch, .. } = std::mem::take(&mut self.mode) else {
+                    unreachable!()
+                };
+                info!(name, "Applying core config profile");
+                self.hide();
+                let tx = self.action_tx.as_ref().unwrap();
+                tx.send(Action::CoreConfigImported(patch, Some(name)))?;
+                return Ok(Some(Action::Unfocus));
+            }
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    /// Records a profile whose changes were submitted as applied.
+    fn applied(&mut self, name: String) -> Option<Action> {
+        if let Err(e) = self.store.mark_applied(&name) {
+            return Some(Action::Error(ComponentId::Profiles, ("Apply profile", e).into()));
+        }
+        Timeline::record(EventKind::Config, format!("Profile `{name}` applied"));
+        None
+    }
+
+    fn render_list(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered()
+            .title(format!("{} profile(s)", self.profiles.len()))
+            .border_type(BorderType::Rounded)
+            .border_style(Color::Cyan);
+        let lines = if self.profiles.is_empty() {
+            vec![Line::styled(
+                "No profiles yet, press `n` to save the running config as one",
+                Style::default().fg(Color::DarkGray),
+            )]
+        } else {
+            let width = self.profiles.iter().map(|p| p.name.chars().count()).max().unwrap_or(0);
+            self.profiles
+                .iter()
+                .enumerate()
+                .map(|(idx, profile)| {
+                    let applied = match profile.applied_at {
+                        Some(at) => format!(
+                            "applied {} ({} ago)",
+                            format_datetime(at).unwrap_or_default(),
+                            format_time_from_now(at)
+                        ),
+                        None => "never applied".to_string(),
+                    };
+                    let style = if idx == self.selected {
+                        Style::default().add_modifier(Modifier::REVERSED)
+                    } else {
+                        Style::default()
+                    };
+                    Line::from(vec![
+                        Span::styled(format!(" {:width$} ", profile.name), style),
+                        Span::styled(format!("  {applied}"), Style::default().fg(Color::DarkGray)),
+                    ])
+                })
+                .collect()
+        };
+        // keep the selection visible
+        let visible = area.height.saturating_sub(2) as usize;
+        let scroll = (self.selected + 1).saturating_sub(visible);
+        frame.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)).block(block), area);
+    }
+
+    fn render_naming(input: &Input, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered()
+            .title("Save running config as")
+            .border_type(BorderType::Rounded)
+            .border_style(Color::Cyan);
+        let width = area.width.saturating_sub(2) as usize;
+        let scroll = input.visual_scroll(width);
+        frame.render_widget(
+            Paragraph::new(input.value()).scroll((0, scroll as u16)).block(block),
+            area,
+        );
+        let x = input.visual_cursor().max(scroll) - scroll + 1;
+        frame.set_cursor_position((area.x + x as u16, area.y + 1));
+    }
+}
+
+impl Component for ProfilesComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::Profiles
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        let scroll = Shortcut::new(vec![
+            Fragment::hl(arrow::UP),
+            Fragment::raw(" nav "),
+            Fragment::hl(arrow::DOWN),
+        ]);
+        match self.mode {
+            Mode::List => vec![
+                scroll,
+                Shortcut::new(vec![Fragment::raw("apply "), Fragment::hl("↵")]),
+                Shortcut::from("new", 0).unwrap(),
+                Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("Esc")]),
+            ],
+            Mode::Naming(_) => vec![
+                Shortcut::new(vec![Fragment::raw("save "), Fragment::hl("↵")]),
+                Shortcut::new(vec![Fragment::raw("back "), Fragment::hl("Esc")]),
+            ],
+            Mode::Confirm { .. } => vec![
+                scroll,
+                Shortcut::new(vec![Fragment::raw("confirm "), Fragment::hl("↵")]),
+                Shortcut::new(vec![Fragment::raw("back "), Fragment::hl("Esc")]),
+            ],
+        }
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match self.mode {
+            Mode::List => self.handle_list_key(key),
+            Mode::Naming(_) => self.handle_naming_key(key),
+            Mode::Confirm { .. } => self.handle_confirm_key(key),
+        }
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Profiles(running) => self.show(running),
+            Action::ProfileApplied(name) => return Ok(self.applied(name)),
+            Action::Tick if self.show => self.poll()?,
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.show {
+            return Ok(());
+        }
+
+        let area = popup_area(area, 70, 60);
+        frame.render_widget(Clear, area);
+        let area = area.inner(Margin::new(2, 1));
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line("profiles", Style::default()));
+        let content_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let message = match (&self.error, &self.overwrite) {
+            (Some(err), _) => Some((Color::Red, err.clone())),
+            _ if self.loading.is_running() => Some((Color::Yellow, "Reading profile...".into())),
+            _ if self.saving.is_running() => Some((Color::Yellow, "Saving profile...".into())),
+            (None, Some(name)) => Some((
+                Color::Yellow,
+                format!("Profile `{name}` exists, press ↵ again to overwrite it"),
+            )),
+            (None, None) => None,
+        };
+        let error_height = if message.is_some() { 3 } else { 0 };
+        let [main_area, error_area] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(error_height)])
+                .areas(content_area);
+        match &self.mode {
+            Mode::List => self.render_list(frame, main_area),
+            Mode::Naming(input) => {
+                let [input_area, _] =
+                    Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(main_area);
+                Self::render_naming(input, frame, input_area)
+            }
+            Mode::Confirm { name, changes, scroll, .. } => {
+                let title = format!("Apply `{name}`: {} change(s)", changes.len());
+                render_changes(title, changes, *scroll, frame, main_area)
+            }
+        }
+        if let Some((color, msg)) = message {
+            let block = Block::bordered().border_type(BorderType::Rounded).border_style(color);
+            let line = Line::from(Span::styled(msg, Style::default().fg(color)));
+            frame.render_widget(Paragraph::new(line).block(block), error_area);
+        }
+
+        Ok(())
+    }
+}
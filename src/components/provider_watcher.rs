@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use indexmap::IndexMap;
+use time::OffsetDateTime;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::action::Action;
+use crate::api::Api;
+use crate::components::ComponentId;
+use crate::models::proxy_provider::ProxyProvider;
+use crate::store::provider_alive::ProviderAlive;
+use crate::store::timeline::{EventKind, Timeline};
+use crate::task_manager::TaskManager;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// Tabs showing provider nodes, refreshed when a provider file changed
+const TARGETS: [ComponentId; 2] = [ComponentId::ProxyProviders, ComponentId::Proxies];
+
+/// Last seen `updatedAt` of file-vehicle providers.
+#[derive(Debug, Default)]
+pub struct FileProviderStamps {
+    stamps: Option<HashMap<String, Option<OffsetDateTime>>>,
+}
+
+impl FileProviderStamps {
+    /// Record the stamps of `providers`, returns names of file providers that changed since the
+    /// previous call. The first call only records.
+    pub fn update(&mut self, providers: &IndexMap<String, ProxyProvider>) -> Vec<String> {
+        let current: HashMap<_, _> = providers
+            .values()
+            .filter(|p| p.vehicle_type.eq_ignore_ascii_case("file"))
+            .map(|p| (p.name.clone(), p.updated_at))
+            .collect();
+        let changed = match &self.stamps {
+            Some(previous) => current
+                .iter()
+                .filter(|(name, stamp)| previous.get(*name).is_some_and(|prev| prev != *stamp))
+                .map(|(name, _)| name.clone())
+                .collect(),
+            None => vec![],
+        };
+        self.stamps = Some(current);
+        changed
+    }
+}
+
+/// Poll proxy providers, sampling their alive nodes, and refresh the provider and proxy tabs when a
+/// file provider was updated outside of the app, e.g. by a cron job rewriting the file.
+pub fn spawn(
+    api: Arc<Api>,
+    action_tx: UnboundedSender<Action>,
+) -> io::Result<JoinHandle<Option<()>>> {
+    TaskManager::spawn(ComponentId::Root, "provider-file-watcher", async move {
+        let mut stamps = FileProviderStamps::default();
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let providers = match api.get_providers().await {
+                Ok(providers) => providers,
+                Err(e) => {
+                    warn!(error = ?e, "Failed to poll proxy providers");
+                    continue;
+                }
+            };
+            ProviderAlive::record(&providers, OffsetDateTime::now_utc());
+            let mut changed = stamps.update(&providers);
+            if changed.is_empty() {
+                continue;
+            }
+            changed.sort();
+            info!(providers = ?changed, "Provider files changed, refreshing");
+            Timeline::record(
+                EventKind::Provider,
+                format!("Provider file changed: {}", changed.join(", ")),
+            );
+            for id in TARGETS {
+                if action_tx.send(Action::AutoRefresh(id)).is_err() {
+                    return;
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn providers(file_updated: &str, http_updated: &str) -> IndexMap<String, ProxyProvider> {
+        let provider = |name: &str, vehicle: &str, updated: &str| {
+            let p: ProxyProvider = serde_json::from_value(json!({
+                "name": name,
+                "vehicleType": vehicle,
+                "proxies": [],
+                "updatedAt": updated,
+            }))
+            .unwrap();
+            (name.to_string(), p)
+        };
+        IndexMap::from([
+            provider("local", "File", file_updated),
+            provider("remote", "HTTP", http_updated),
+        ])
+    }
+
+    #[test]
+    fn only_changed_file_providers_are_reported() {
+        let mut stamps = FileProviderStamps::default();
+        let t1 = "2025-01-01T00:00:00Z";
+        let t2 = "2025-01-01T00:05:00Z";
+
+        assert!(stamps.update(&providers(t1, t1)).is_empty());
+        assert!(stamps.update(&providers(t1, t1)).is_empty());
+        // updates of remote providers are refreshed by the scheduler already
+        assert!(stamps.update(&providers(t1, t2)).is_empty());
+        assert_eq!(stamps.update(&providers(t2, t2)), vec!["local"]);
+        assert!(stamps.update(&providers(t2, t2)).is_empty());
+        // newly added providers are not a change
+        let mut added = providers(t2, t2);
+        added.shift_remove("local");
+        stamps.update(&added);
+        assert!(stamps.update(&providers(t2, t2)).is_empty());
+    }
+}
@@ -6,26 +6,35 @@ use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Layout, Margin, Rect};
 use ratatui::prelude::Style;
-use ratatui::style::Color;
+use ratatui::style::{Color, Stylize};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
 use throbber_widgets_tui::{BLACK_CIRCLE, BRAILLE_SIX, Throbber, ThrobberState, WhichUse};
 use tokio::sync::mpsc::UnboundedSender;
 use tracing::{error, info, warn};
 
 use crate::action::Action;
 use crate::api::Api;
+use crate::components::search::{SearchEntry, SearchTarget};
 use crate::components::{Component, ComponentId};
 use crate::config::{Config, LatencyThreshold};
-use crate::store::proxies::{Proxies, ProxyView};
+use crate::models::proxy::GroupKind;
+use crate::store::proxies::{Proxies, ProxyView, TestProgress};
 use crate::store::proxy_setting::ProxySetting;
+use crate::store::warmup::Warmup;
+use crate::task_manager::TaskManager;
 use crate::utils::symbols::arrow;
-use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT};
+use crate::utils::text_ui::{
+    TOP_TITLE_LEFT, TOP_TITLE_RIGHT, freshness_span, popup_area, top_title_line,
+};
+use crate::widgets::empty_state::EmptyState;
 use crate::widgets::scrollable_navigator::ScrollableNavigator;
 use crate::widgets::shortcut::{Fragment, Shortcut};
 
 const CARD_HEIGHT: u16 = 4;
 const CARDS_PER_ROW: usize = 2;
+/// Digits of a quick pick number, more than any group has nodes.
+const PICK_DIGITS: usize = 4;
 
 #[derive(Debug)]
 pub struct ProxiesComponent {
@@ -37,7 +46,40 @@ pub struct ProxiesComponent {
     throbber: ThrobberState,
 
     pending_test: Arc<AtomicU16>,
+    test_progress: Arc<TestProgress>,
     pending_test_throbber: ThrobberState,
+
+    /// Picking a node of the focused group by its number, keys go to the pick while set
+    pick: Option<QuickPick>,
+}
+
+#[derive(Debug)]
+struct QuickPick {
+    group: String,
+    children: Vec<String>,
+    selected: Option<String>,
+    /// Number typed so far, 1-based
+    typed: String,
+}
+
+impl QuickPick {
+    /// Node of the typed number.
+    fn target(&self) -> Option<&String> {
+        let number = self.typed.parse::<usize>().ok()?;
+        self.children.get(number.checked_sub(1)?)
+    }
+}
+
+/// Next node of `children` after `selected` that is not `failing`, wrapping around.
+fn next_healthy(children: &[String], selected: Option<&str>, failing: &[String]) -> Option<String> {
+    let start = selected.and_then(|s| children.iter().position(|c| c == s)).map_or(0, |i| i + 1);
+    children
+        .iter()
+        .cycle()
+        .skip(start)
+        .take(children.len())
+        .find(|c| Some(c.as_str()) != selected && !failing.contains(c))
+        .cloned()
 }
 
 impl Default for ProxiesComponent {
@@ -49,7 +91,9 @@ impl Default for ProxiesComponent {
             loading: Default::default(),
             throbber: Default::default(),
             pending_test: Default::default(),
+            test_progress: Default::default(),
             pending_test_throbber: Default::default(),
+            pick: None,
         }
     }
 }
@@ -61,11 +105,12 @@ impl ProxiesComponent {
         let api = Arc::clone(self.api.as_ref().unwrap());
         let loading = Arc::clone(&self.loading);
 
-        tokio::task::Builder::new().name("proxies-loader").spawn(async move {
+        TaskManager::spawn(ComponentId::Proxies, "proxies-loader", async move {
             if let Err(e) = Proxies::load(api).await {
                 error!(error = ?e, "Failed to load proxies");
             }
             loading.store(false, Ordering::Relaxed);
+            Warmup::ready(ComponentId::Proxies);
         })?;
 
         Ok(())
@@ -75,10 +120,11 @@ impl ProxiesComponent {
         info!("Testing proxy group {}", name);
         let api = Arc::clone(self.api.as_ref().unwrap());
         let pending_test = Arc::clone(&self.pending_test);
+        let test_progress = Arc::clone(&self.test_progress);
         pending_test.fetch_add(1, Ordering::Relaxed);
 
-        tokio::task::Builder::new().name("proxy-group-tester").spawn(async move {
-            if let Err(e) = Proxies::test_group_and_reload(api, &name).await {
+        TaskManager::spawn(ComponentId::Proxies, "proxy-group-tester", async move {
+            if let Err(e) = Proxies::test_group_and_reload(api, &name, &test_progress).await {
                 error!(error = ?e, "Failed to test and load proxy: {}", name);
             }
             let _ = pending_test.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| {
@@ -89,17 +135,146 @@ impl ProxiesComponent {
         Ok(())
     }
 
+    /// Switch the focused selector group to its next healthy node, without opening the detail
+    /// popup. Other groups pick their node themselves.
+    fn select_next_healthy(&self) -> Option<Action> {
+        let view = self.navigator.focused.and_then(Proxies::get)?;
+        if view.proxy.group_kind() != GroupKind::Selector {
+            let msg =
+                format!("{} picks its node itself, only selector groups switch", view.proxy.name);
+            return Some(Action::Info(("Proxies", msg).into()));
+        }
+        let children = view.proxy.children.clone().unwrap_or_default();
+        let failing = Proxies::filter_failing(&children);
+        let action = match next_healthy(&children, view.proxy.selected.as_deref(), &failing) {
+            Some(node) => Action::ProxyUpdateRequest(view.proxy.name.clone(), node),
+            None => {
+                let msg = format!("No other healthy node in {}, test it first", view.proxy.name);
+                Action::Info(("Proxies", msg).into())
+            }
+        };
+        Some(action)
+    }
+
+    fn start_pick(&mut self) -> Result<Option<Action>> {
+        let Some(view) = self.navigator.focused.and_then(Proxies::get) else {
+            return Ok(None);
+        };
+        self.pick = Some(QuickPick {
+            group: view.proxy.name.clone(),
+            children: view.proxy.children.clone().unwrap_or_default(),
+            selected: view.proxy.selected.clone(),
+            typed: String::new(),
+        });
+        self.action_tx.as_ref().unwrap().send(Action::Shortcuts(self.shortcuts()))?;
+        Ok(Some(Action::Focus(self.id())))
+    }
+
+    fn handle_pick_key(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let Some(pick) = self.pick.as_mut() else {
+            return Ok(None);
+        };
+        let request = match key.code {
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                if pick.typed.len() < PICK_DIGITS {
+                    pick.typed.push(c);
+                }
+                return Ok(None);
+            }
+            KeyCode::Backspace => {
+                pick.typed.pop();
+                return Ok(None);
+            }
+            KeyCode::Enter => match pick.target() {
+                Some(node) => Some(Action::ProxyUpdateRequest(pick.group.clone(), node.clone())),
+                None => return Ok(None),
+            },
+            KeyCode::Esc => None,
+            _ => return Ok(None),
+        };
+        self.pick = None;
+        let action_tx = self.action_tx.as_ref().unwrap();
+        action_tx.send(Action::Shortcuts(self.shortcuts()))?;
+        if let Some(request) = request {
+            action_tx.send(request)?;
+        }
+        Ok(Some(Action::Unfocus))
+    }
+
+    fn render_pick(&self, frame: &mut Frame, area: Rect) {
+        let Some(pick) = &self.pick else {
+            return;
+        };
+        let area = popup_area(area, 50, 60);
+        frame.render_widget(Clear, area);
+        let typed = if pick.typed.is_empty() { "_" } else { pick.typed.as_str() };
+        let title = format!("pick {}", pick.group);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line(&title, Style::default()))
+            .title_bottom(
+                Line::from(vec![
+                    Span::raw(TOP_TITLE_LEFT),
+                    Span::raw("# "),
+                    Span::styled(typed, Color::LightCyan),
+                    Span::raw(TOP_TITLE_RIGHT),
+                ])
+                .centered(),
+            );
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let threshold = ProxySetting::global().read().unwrap().latency_threshold;
+        let width = pick.children.len().to_string().len();
+        let target = pick.target();
+        let lines: Vec<Line> = Proxies::with_by_names(&pick.children, |proxies| {
+            let latency = |name: &str| proxies.iter().find(|p| p.name == name).map(|p| p.latency);
+            pick.children
+                .iter()
+                .enumerate()
+                .map(|(i, name)| (format!("{:>width$}", i + 1), name))
+                .filter(|(number, _)| number.trim_start().starts_with(&pick.typed))
+                .map(|(number, name)| {
+                    let number = if Some(name) == target {
+                        Span::styled(number, Color::LightCyan).reversed()
+                    } else {
+                        Span::styled(number, Color::LightCyan)
+                    };
+                    let name_style = if pick.selected.as_ref() == Some(name) {
+                        Style::default().fg(Color::Green)
+                    } else {
+                        Style::default()
+                    };
+                    let mut line = Line::from(vec![
+                        number,
+                        Span::raw(" "),
+                        Span::styled(name.as_str(), name_style),
+                        Span::raw(" "),
+                    ]);
+                    if let Some(latency) = latency(name) {
+                        line.push_span(latency.as_span(threshold));
+                    }
+                    line
+                })
+                .collect()
+        });
+        frame.render_widget(Paragraph::new(lines), inner.inner(Margin::new(1, 0)));
+    }
+
     fn render_throbber(&mut self, frame: &mut Frame, area: Rect) {
         if self.pending_test.load(Ordering::Relaxed) > 0 {
+            let label = self.test_progress.label();
+            let width = label.len() as u16 + 2;
             let symbol = Throbber::default()
-                .label("Testing")
+                .label(label)
                 .style(Style::default().fg(Color::White).bg(Color::Green).bold())
                 .throbber_style(Style::default().fg(Color::White).bg(Color::Green).bold())
                 .throbber_set(BLACK_CIRCLE)
                 .use_type(WhichUse::Spin);
             frame.render_stateful_widget(
                 symbol,
-                Rect::new(area.right().saturating_sub(20), area.y, 9, 1),
+                Rect::new(area.right().saturating_sub(11 + width), area.y, width, 1),
                 &mut self.pending_test_throbber,
             );
         }
@@ -166,16 +341,22 @@ impl ProxiesComponent {
 
     fn render_proxies(&mut self, frame: &mut Frame, outer: Rect) {
         let proxies_len = Proxies::with_view(|p| p.len());
-        let title_line = Line::from(vec![
+        let updated_at = Proxies::global().read().unwrap().updated_at();
+        let mut title_line = Line::from(vec![
             Span::raw(TOP_TITLE_LEFT),
             Span::raw("proxies ("),
             Span::styled(format!("{}", proxies_len), Color::LightCyan),
             Span::raw(")"),
-            Span::raw(TOP_TITLE_RIGHT),
         ]);
+        title_line.extend(freshness_span(updated_at));
+        title_line.push_span(Span::raw(TOP_TITLE_RIGHT));
         let block = Block::bordered().border_type(BorderType::Rounded).title(title_line);
         let area = block.inner(outer);
         frame.render_widget(block, outer);
+        if proxies_len == 0 {
+            let load_state = Proxies::global().read().unwrap().load_state();
+            EmptyState::new(load_state, "proxy groups").render(frame, area, &mut self.throbber);
+        }
 
         let col_chunks =
             Layout::horizontal((0..CARDS_PER_ROW).map(|_| Constraint::Fill(1))).split(area);
@@ -212,6 +393,13 @@ impl Component for ProxiesComponent {
     }
 
     fn shortcuts(&self) -> Vec<Shortcut> {
+        if self.pick.is_some() {
+            return vec![
+                Shortcut::new(vec![Fragment::hl("0-9"), Fragment::raw(" number")]),
+                Shortcut::new(vec![Fragment::raw("select "), Fragment::hl("↵")]),
+                Shortcut::new(vec![Fragment::raw("cancel "), Fragment::hl("Esc")]),
+            ];
+        }
         vec![
             Shortcut::new(vec![
                 Fragment::hl(arrow::LEFT),
@@ -233,7 +421,11 @@ impl Component for ProxiesComponent {
             Shortcut::new(vec![Fragment::raw("detail "), Fragment::hl("↵")]),
             Shortcut::from("refresh", 0).unwrap(),
             Shortcut::from("setting", 0).unwrap(),
+            Shortcut::from("calibrate", 0).unwrap(),
             Shortcut::from("test", 0).unwrap(),
+            Shortcut::from("failing", 0).unwrap(),
+            Shortcut::new(vec![Fragment::raw("next healthy "), Fragment::hl("Space")]),
+            Shortcut::from("pick", 0).unwrap(),
         ]
     }
 
@@ -256,6 +448,13 @@ impl Component for ProxiesComponent {
     }
 
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.pick.is_some() {
+            return self.handle_pick_key(key);
+        }
+        // Space pages down only when no group is focused
+        if key.code == KeyCode::Char(' ') && self.navigator.focused.is_some() {
+            return Ok(self.select_next_healthy());
+        }
         if self.navigator.handle_key_event(true, key).is_consumed() {
             return Ok(None);
         }
@@ -263,6 +462,8 @@ impl Component for ProxiesComponent {
             KeyCode::Esc => self.navigator.focused = None,
             KeyCode::Char('r') => self.load_proxies()?,
             KeyCode::Char('s') => return Ok(Some(Action::ProxySetting)),
+            KeyCode::Char('c') => return Ok(Some(Action::LatencyCalibration)),
+            KeyCode::Char('p') => return self.start_pick(),
             KeyCode::Enter => {
                 let action = self
                     .navigator
@@ -271,6 +472,16 @@ impl Component for ProxiesComponent {
                     .map(|v| Action::ProxyDetail(v.proxy.name.clone()));
                 return Ok(action);
             }
+            KeyCode::Char('f') => {
+                let Some(view) = self.navigator.focused.and_then(Proxies::get) else {
+                    return Ok(None);
+                };
+                if view.quality_stats.failing() == 0 {
+                    let msg = format!("No failing nodes in {}", view.proxy.name);
+                    return Ok(Some(Action::Info(("Proxies", msg).into())));
+                }
+                return Ok(Some(Action::ProxyDetailFailing(view.proxy.name.clone())));
+            }
             KeyCode::Char('t') => {
                 if let Some(name) =
                     self.navigator.focused.and_then(Proxies::get).map(|v| v.proxy.name.clone())
@@ -284,9 +495,31 @@ impl Component for ProxiesComponent {
         Ok(None)
     }
 
+    fn search_entries(&self) -> Vec<SearchEntry> {
+        let located = Proxies::global().read().unwrap().locate_all();
+        located
+            .into_iter()
+            .map(|(name, group)| {
+                let detail = match &group {
+                    Some(group) if *group == name => "group".to_owned(),
+                    Some(group) => format!("in {group}"),
+                    None => String::new(),
+                };
+                let target = group.map_or(SearchTarget::Tab, |g| {
+                    SearchTarget::Open(Box::new(Action::ProxyDetail(g)))
+                });
+                SearchEntry::new(self.id(), "proxy", name, target).detail(detail)
+            })
+            .collect()
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::ProxySettingChanged => self.load_proxies()?,
+            Action::AutoRefresh(id) if id == self.id() && !self.loading.load(Ordering::Relaxed) => {
+                self.load_proxies()?
+            }
+            Action::ConfigReloaded(config) => self.register_config_handler(config)?,
             Action::Tick => {
                 if self.loading.load(Ordering::Relaxed) {
                     self.throbber.calc_next();
@@ -305,7 +538,43 @@ impl Component for ProxiesComponent {
         self.render_proxies(frame, area);
         self.render_throbber(frame, area);
         self.navigator.render(frame, area.inner(Margin::new(0, 1)));
+        self.render_pick(frame, area);
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_healthy_wraps_and_skips_failing() {
+        let children: Vec<String> = ["a", "b", "c", "d"].map(String::from).to_vec();
+        let failing = vec!["c".to_owned()];
+        assert_eq!(next_healthy(&children, Some("b"), &failing).as_deref(), Some("d"));
+        assert_eq!(next_healthy(&children, Some("d"), &failing).as_deref(), Some("a"));
+        assert_eq!(next_healthy(&children, None, &failing).as_deref(), Some("a"));
+        let failing: Vec<String> = ["a", "c", "d"].map(String::from).to_vec();
+        assert_eq!(next_healthy(&children, Some("b"), &failing), None);
+    }
+
+    #[test]
+    fn pick_targets_the_typed_number() {
+        let mut pick = QuickPick {
+            group: "g".into(),
+            children: (1..=12).map(|i| format!("n{i}")).collect(),
+            selected: None,
+            typed: String::new(),
+        };
+        assert_eq!(pick.target(), None);
+        pick.typed = "1".into();
+        assert_eq!(pick.target().map(String::as_str), Some("n1"));
+        pick.typed = "12".into();
+        assert_eq!(pick.target().map(String::as_str), Some("n12"));
+        for typed in ["0", "13"] {
+            pick.typed = typed.into();
+            assert_eq!(pick.target(), None);
+        }
+    }
+}
@@ -5,27 +5,35 @@ use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Layout, Margin, Rect};
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Style, Stylize};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Clear, Paragraph};
 use throbber_widgets_tui::{BLACK_CIRCLE, BRAILLE_SIX, Throbber, ThrobberState, WhichUse};
 use tokio::sync::mpsc::UnboundedSender;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info};
 
 use crate::action::Action;
 use crate::api::Api;
+use crate::components::node_cache::NodeCache;
 use crate::components::{Component, ComponentId};
-use crate::config::LatencyThreshold;
-use crate::models::proxy::Proxy;
-use crate::store::proxies::Proxies;
+use crate::config::{Config, ExitIpConfig, LatencyThreshold};
+use crate::models::proxy::{self, GroupKind, Proxy};
+use crate::store::node_lookup::{self, Exit, NodeLookup};
+use crate::store::proxies::{Proxies, TestProgress};
 use crate::store::proxy_setting::ProxySetting;
+use crate::task_manager::TaskManager;
 use crate::utils::symbols::arrow;
 use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT, popup_area, space_between};
+use crate::utils::time::HOUR_MINUTE_FMT;
+use crate::widgets::card_search::CardSearch;
+use crate::widgets::latency::{Latency, LatencyQuality};
 use crate::widgets::scrollable_navigator::ScrollableNavigator;
 use crate::widgets::shortcut::{Fragment, Shortcut};
 
 const CARD_HEIGHT: u16 = 3;
 const CARD_WIDTH: u16 = 25;
+/// Width of a delay history cell, fits `HH:MM` and a 4 digit delay.
+const HISTORY_CELL_WIDTH: usize = 6;
 
 #[derive(Debug, Default)]
 pub struct ProxyDetailComponent {
@@ -39,6 +47,11 @@ pub struct ProxyDetailComponent {
     /// - first: top-level proxy group
     /// - last:  currently viewed proxy group
     layers: Vec<Layer>,
+    /// Only show nodes in the failing quality buckets (slow or timed out).
+    failing_only: bool,
+    /// Order nodes by their latency, fastest first, instead of the proxies sort.
+    fastest_first: bool,
+    search: CardSearch,
 
     navigator: ScrollableNavigator,
 
@@ -46,7 +59,14 @@ pub struct ProxyDetailComponent {
     throbber: ThrobberState,
 
     pending_test: Arc<AtomicU16>,
+    /// A whole group test finished, offer to sort its nodes by the fresh results.
+    group_tested: Arc<AtomicBool>,
+    test_progress: Arc<TestProgress>,
     pending_test_throbber: ThrobberState,
+
+    exit_ip: ExitIpConfig,
+    /// Server and exit IP lookups by node name
+    lookups: NodeCache<NodeLookup>,
 }
 
 #[derive(Debug)]
@@ -67,6 +87,8 @@ impl ProxyDetailComponent {
         self.proxy_name = Some(proxy_name.clone());
         self.loading.store(false, Ordering::Relaxed);
         self.pending_test.store(0, Ordering::Relaxed);
+        self.group_tested.store(false, Ordering::Relaxed);
+        self.search.clear();
         self.sync_layer(proxy_name);
 
         self.show = true;
@@ -75,7 +97,51 @@ impl ProxyDetailComponent {
     pub fn hide(&mut self) {
         self.show = false;
         self.proxy_name = None;
+        self.failing_only = false;
+        self.fastest_first = false;
+        self.search.clear();
         self.layers.clear();
+        self.lookups.clear();
+    }
+
+    /// Children of `group` currently listed, respecting the failing-only filter and the search.
+    fn children(&self, group: &Proxy) -> Vec<String> {
+        let names = group.children.as_deref().unwrap_or_default();
+        let mut names =
+            if self.failing_only { Proxies::filter_failing(names) } else { names.to_vec() };
+        if let Some(mut matcher) = self.search.matcher() {
+            names.retain(|name| {
+                Proxies::get_by_name(name).is_some_and(|p| matcher.matches(&p.name, &p.r#type))
+            });
+        }
+        if self.fastest_first {
+            names = Proxies::with_by_names(&names, |proxies| {
+                let mut proxies = proxies.to_vec();
+                proxies.sort_by(|a, b| a.latency.fastest_first(&b.latency));
+                proxies.into_iter().map(|p| p.name.clone()).collect()
+            });
+        }
+        names
+    }
+
+    /// Toggle ordering by latency; when turned on, focus the fastest node.
+    fn toggle_fastest_first(&mut self) {
+        self.fastest_first = !self.fastest_first;
+        self.group_tested.store(false, Ordering::Relaxed);
+        self.reset_navigator();
+        if self.fastest_first {
+            self.navigator.focus(0);
+        }
+    }
+
+    fn toggle_failing_only(&mut self) {
+        self.failing_only = !self.failing_only;
+        self.reset_navigator();
+    }
+
+    fn reset_navigator(&mut self) {
+        self.navigator.focused = None;
+        self.navigator.scroller.position(0);
     }
 
     fn close(&mut self) {
@@ -112,10 +178,11 @@ impl ProxyDetailComponent {
         let loading = Arc::clone(&self.loading);
         let action_tx = self.action_tx.as_ref().unwrap().clone();
 
-        tokio::task::Builder::new().name("proxies-loader").spawn(async move {
+        TaskManager::spawn(ComponentId::ProxyDetail, "proxies-loader", async move {
             if let Err(e) = Proxies::load(api).await {
                 error!(error = ?e, "Failed to load proxies");
-                let _ = action_tx.send(Action::Error(("Load proxy", e).into()));
+                let _ = action_tx
+                    .send(Action::Error(ComponentId::ProxyDetail, ("Load proxy", e).into()));
             }
             loading.store(false, Ordering::Relaxed);
         })?;
@@ -123,44 +190,26 @@ impl ProxyDetailComponent {
         Ok(())
     }
 
-    fn update_proxy(&mut self, selector_name: String, name: String) -> Result<()> {
-        info!("Updating proxy {}: {}", selector_name, name);
-        let api = Arc::clone(self.api.as_ref().unwrap());
-        let loading = Arc::clone(&self.loading);
-        let action_tx = self.action_tx.as_ref().unwrap().clone();
-
-        tokio::task::Builder::new().name("proxy-updater").spawn(async move {
-            match Proxies::update_and_reload(api.clone(), &selector_name, &name).await {
-                Ok(()) => Self::spawn_connection_terminator(api, selector_name),
-                Err(e) => {
-                    warn!(error = ?e, "Failed to update selected proxy for {}: {}", selector_name, name);
-                    let _ = action_tx.send(Action::Error(("Update selected proxy", e).into()));
-                }
-            }
-
-            loading.store(false, Ordering::Relaxed);
-        })?;
-
-        Ok(())
-    }
-
     fn test_proxy(&self, name: String, is_group: bool, reset_pending: bool) -> Result<()> {
         info!(name = %name, is_group, reset_pending, "Testing proxy");
         let api = Arc::clone(self.api.as_ref().unwrap());
         let pending_test = Arc::clone(&self.pending_test);
+        let group_tested = Arc::clone(&self.group_tested);
+        let test_progress = Arc::clone(&self.test_progress);
         pending_test.fetch_add(1, Ordering::Relaxed);
 
-        tokio::task::Builder::new().name("proxy-tester").spawn(async move {
+        TaskManager::spawn(ComponentId::ProxyDetail, "proxy-tester", async move {
             let result = if is_group {
-                Proxies::test_group_and_reload(api, &name).await
+                Proxies::test_group_and_reload(api, &name, &test_progress).await
             } else {
                 Proxies::test_and_reload(api, &name).await
             };
-            if let Err(e) = result {
+            if let Err(e) = &result {
                 error!(error = ?e, name = %name, is_group, "Failed to test and load proxy");
             }
             if reset_pending {
                 pending_test.store(0, Ordering::Relaxed);
+                group_tested.store(is_group && result.is_ok(), Ordering::Relaxed);
             } else {
                 let _ = pending_test.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| {
                     if x == 0 { None } else { Some(x - 1) }
@@ -171,43 +220,64 @@ impl ProxyDetailComponent {
         Ok(())
     }
 
-    fn spawn_connection_terminator(api: Arc<Api>, selector_name: String) {
-        if !ProxySetting::global().read().unwrap().auto_terminate_connections {
-            return;
-        }
-        debug!("Auto-terminating connections for selector {} after proxy update", selector_name);
-        if let Err(e) = tokio::task::Builder::new().name("conn-terminator").spawn(async move {
-            let Ok(wrapper) = api.get_connections().await else {
-                debug!("Failed to get connections for termination");
-                return;
-            };
-            // `into_iter + collect` to release large connection payloads early.
-            let conns = wrapper
-                .connections
-                .into_iter()
-                .flat_map(|c| c.into_iter())
-                .filter(|c| c.chains.contains(&selector_name))
-                .map(|c| c.id)
-                .collect::<Vec<_>>();
-            debug!(selector_name = %selector_name, num_conns = conns.len(), "Terminating connections");
-            for conn_id in conns {
-                if let Err(e) = api.delete_connection(&conn_id).await {
-                    debug!(error = ?e, "Failed to terminate connection: {}", conn_id);
-                }
+    /// Test the group with the manual pick dropped, so url-test and fallback select by delay again.
+    fn retest_group(&self, name: String) -> Result<()> {
+        info!(name = %name, "Re-testing proxy group");
+        let api = Arc::clone(self.api.as_ref().unwrap());
+        let pending_test = Arc::clone(&self.pending_test);
+        let action_tx = self.action_tx.as_ref().unwrap().clone();
+        pending_test.fetch_add(1, Ordering::Relaxed);
+
+        TaskManager::spawn(ComponentId::ProxyDetail, "group-retester", async move {
+            if let Err(e) = Proxies::retest_group_and_reload(api, &name).await {
+                error!(error = ?e, name = %name, "Failed to re-test proxy group");
+                let _ = action_tx.send(Action::Error(
+                    ComponentId::ProxyDetail,
+                    ("Re-test proxy group", e).into(),
+                ));
             }
-        }) {
-            warn!(error = ?e, "Failed to spawn connection terminator task");
+            pending_test.store(0, Ordering::Relaxed);
+        })?;
+
+        Ok(())
+    }
+
+    /// The focused node, or the one selected in `group`.
+    fn target_node(&self, group: &Proxy) -> Option<String> {
+        match self.navigator.focused {
+            Some(idx) => self.children(group).get(idx).cloned(),
+            None => group.selected.clone(),
         }
     }
 
+    fn lookup_node(&self, node: String) -> Result<()> {
+        info!(node = %node, "Looking up node server and exit IP");
+        let api = Arc::clone(self.api.as_ref().unwrap());
+        let config = self.exit_ip.clone();
+        let load = self.lookups.start(&node);
+
+        TaskManager::spawn(ComponentId::ProxyDetail, "node-lookup", async move {
+            let lookup = node_lookup::lookup(&api, &config, &node).await;
+            load.finish(lookup);
+        })?;
+
+        Ok(())
+    }
+
+    /// The viewed group selects by delay, so dropping its manual pick and re-testing matters.
+    fn retestable(&self) -> bool {
+        self.proxy_name
+            .as_deref()
+            .and_then(Proxies::get_by_name)
+            .is_some_and(|p| matches!(p.group_kind(), GroupKind::UrlTest | GroupKind::Fallback))
+    }
+
     fn focus_current(&mut self, proxy: &Proxy) {
         let Some(current_sel) = proxy.selected.as_deref() else {
             return;
         };
         info!("Focus current proxy: {}", current_sel);
-        if let Some(idx) =
-            proxy.children.as_ref().and_then(|v| v.iter().position(|name| name == current_sel))
-        {
+        if let Some(idx) = self.children(proxy).iter().position(|name| name == current_sel) {
             self.navigator.focus(idx);
         }
     }
@@ -220,21 +290,33 @@ impl ProxyDetailComponent {
             Span::raw(" ("),
             Span::styled(format!("{}", children_len), Color::LightCyan),
             Span::raw(")"),
+            Span::styled(if self.failing_only { " failing" } else { "" }, Color::LightRed),
+            Span::styled(if self.fastest_first { " fastest first" } else { "" }, Color::LightGreen),
+            Span::styled(
+                if self.group_tested.load(Ordering::Relaxed) && !self.fastest_first {
+                    " tested, `l` sorts by latency"
+                } else {
+                    ""
+                },
+                Color::Yellow,
+            ),
             Span::raw(TOP_TITLE_RIGHT),
         ])
     }
 
     fn render_throbber(&mut self, frame: &mut Frame, area: Rect) {
         if self.pending_test.load(Ordering::Relaxed) > 0 {
+            let label = self.test_progress.label();
+            let width = label.len() as u16 + 2;
             let symbol = Throbber::default()
-                .label("Testing")
+                .label(label)
                 .style(Style::default().fg(Color::White).bg(Color::Green).bold())
                 .throbber_style(Style::default().fg(Color::White).bg(Color::Green).bold())
                 .throbber_set(BLACK_CIRCLE)
                 .use_type(WhichUse::Spin);
             frame.render_stateful_widget(
                 symbol,
-                Rect::new(area.right().saturating_sub(20), area.y, 9, 1),
+                Rect::new(area.right().saturating_sub(11 + width), area.y, width, 1),
                 &mut self.pending_test_throbber,
             );
         }
@@ -253,6 +335,124 @@ impl ProxyDetailComponent {
         }
     }
 
+    /// Group type specific state: the url-test pick, the fallback health order or the load-balance
+    /// strategy.
+    fn group_line(group: &Proxy) -> Option<Line<'static>> {
+        let mut spans = Vec::new();
+        match group.group_kind() {
+            GroupKind::UrlTest => {
+                spans.push(Span::raw("url-test").bold());
+                match group.fixed() {
+                    Some(fixed) => spans
+                        .push(format!("  fixed to {fixed}, `T` drops it and re-tests").yellow()),
+                    None => {
+                        let now = group.selected.as_deref().unwrap_or("-");
+                        spans.push(Span::raw(format!("  fastest {now}, `T` re-tests")).dark_gray())
+                    }
+                }
+            }
+            GroupKind::Fallback => {
+                spans.push(Span::raw("fallback").bold());
+                spans.push(Span::raw("  ").dark_gray());
+                let children = group.children.as_deref().unwrap_or_default();
+                // the core uses the first alive node in config order
+                Proxies::with_by_names(children, |proxies| {
+                    for (idx, node) in proxies.iter().enumerate() {
+                        if idx > 0 {
+                            spans.push(Span::raw(format!(" {} ", arrow::RIGHT)).dark_gray());
+                        }
+                        let span = match node.latency.0 {
+                            Some(delay) if delay > 0 => Span::raw(node.name.clone()).green(),
+                            Some(_) => Span::raw(node.name.clone()).light_red(),
+                            None => Span::raw(node.name.clone()).dark_gray(),
+                        };
+                        let active = group.selected.as_deref() == Some(node.name.as_str());
+                        spans.push(if active { span.bold().underlined() } else { span });
+                    }
+                });
+                if let Some(fixed) = group.fixed() {
+                    spans.push(format!("  fixed to {fixed}, `T` drops it").yellow());
+                }
+            }
+            GroupKind::LoadBalance => {
+                spans.push(Span::raw("load-balance").bold());
+                match group.strategy.as_deref() {
+                    Some(strategy) => {
+                        spans.push(Span::raw(format!("  strategy {strategy}")));
+                        if let Some(key) = proxy::hashing_key(strategy) {
+                            spans.push(Span::raw(format!(", hashed by {key}")).dark_gray());
+                        }
+                    }
+                    None => spans.push(Span::raw("  strategy not exposed by the core").dark_gray()),
+                }
+            }
+            GroupKind::Selector | GroupKind::Other => return None,
+        }
+        Some(Line::from(spans))
+    }
+
+    fn lookup_line(&self, node: &str) -> Line<'static> {
+        let mut spans = vec![Span::raw(node.to_owned()).bold(), Span::raw("  ")];
+        match self.lookups.get(node) {
+            None => spans.push(Span::raw("`i` looks up its server and exit IP").dark_gray()),
+            Some(None) => spans.push(Span::raw("looking up…").dark_gray()),
+            Some(Some(lookup)) => {
+                spans.push(Span::raw("server ").dark_gray());
+                spans.push(match lookup.servers.split_first() {
+                    Some((server, [])) => Span::raw(server.clone()),
+                    Some((server, rest)) => Span::raw(format!("{server} (+{})", rest.len())),
+                    None => Span::raw("no live connections").dark_gray(),
+                });
+                spans.push(Span::raw("  exit ").dark_gray());
+                match lookup.exit {
+                    Some(Exit::Ip(ip)) => spans.push(ip.to_string().green()),
+                    Some(Exit::NotThrough(Some(via))) => spans.push(
+                        format!(
+                            "unknown, the core routed it via {via}, select this node to test it"
+                        )
+                        .yellow(),
+                    ),
+                    Some(Exit::NotThrough(None)) => {
+                        spans.push("unknown, its route wasn't found".yellow())
+                    }
+                    Some(Exit::Failed(e)) => spans.push(e.light_red()),
+                    None => (),
+                }
+            }
+        }
+        Line::from(spans)
+    }
+
+    /// Delay history of `node` as a strip of cells colored by latency quality, the time of each
+    /// test below, oldest first. Only the newest entries fitting `width` are kept.
+    fn history_lines(
+        node: &Proxy,
+        threshold: LatencyThreshold,
+        width: u16,
+    ) -> Option<[Line<'static>; 2]> {
+        const LABEL: &str = "history ";
+        let fits = (width as usize).saturating_sub(LABEL.len()) / HISTORY_CELL_WIDTH;
+        let history = &node.history[node.history.len().saturating_sub(fits)..];
+        if history.is_empty() {
+            return None;
+        }
+        let mut delays = Line::from(Span::raw(LABEL).dark_gray());
+        let mut times = Line::from(Span::raw(" ".repeat(LABEL.len())));
+        let cell = HISTORY_CELL_WIDTH - 1;
+        for entry in history {
+            let quality = LatencyQuality::from(Latency(Some(entry.delay)), threshold);
+            let delay = if entry.delay > 0 { entry.delay.to_string() } else { "✕".into() };
+            let style = Style::default().fg(Color::Black).bg(quality.color());
+            delays.push_span(Span::styled(format!("{delay:^cell$}"), style));
+            delays.push_span(Span::raw(" "));
+            let time = entry.time.and_then(|t| t.format(&HOUR_MINUTE_FMT).ok());
+            times.push_span(
+                Span::raw(format!("{:^cell$} ", time.as_deref().unwrap_or("-"))).dark_gray(),
+            );
+        }
+        Some([delays, times])
+    }
+
     fn render_card(
         threshold: LatencyThreshold,
         group: &Proxy,
@@ -284,8 +484,13 @@ impl ProxyDetailComponent {
         frame.render_widget(para, area);
     }
 
-    fn render_cards(&mut self, group: &Proxy, frame: &mut Frame, area: Rect) {
-        let children_names = group.children.as_deref().unwrap_or_default();
+    fn render_cards(
+        &mut self,
+        group: &Proxy,
+        children_names: &[String],
+        frame: &mut Frame,
+        area: Rect,
+    ) {
         let cols = (area.width / CARD_WIDTH).max(1) as usize;
         let col_chunks =
             Layout::horizontal((0..cols).map(|_| Constraint::Min(CARD_WIDTH))).split(area);
@@ -311,7 +516,7 @@ impl Component for ProxyDetailComponent {
     }
 
     fn shortcuts(&self) -> Vec<Shortcut> {
-        vec![
+        let mut shortcuts = vec![
             Shortcut::new(vec![
                 Fragment::hl(arrow::LEFT),
                 Fragment::raw("/"),
@@ -356,10 +561,18 @@ impl Component for ProxyDetailComponent {
                 .compact(vec![Fragment::hl("[/]"), Fragment::raw(" layer")]),
             Shortcut::from("cur", 0).unwrap(),
             Shortcut::new(vec![Fragment::raw("sel "), Fragment::hl("↵")]),
+            Shortcut::new(vec![Fragment::raw("search "), Fragment::hl("/")]),
             Shortcut::new(vec![Fragment::raw("back "), Fragment::hl("Esc")]),
             Shortcut::from("test", 0).unwrap(),
             Shortcut::from("refresh", 0).unwrap(),
-        ]
+            Shortcut::from("failing", 0).unwrap(),
+            Shortcut::new(vec![Fragment::raw("fastest "), Fragment::hl("l")]),
+            Shortcut::from("info", 0).unwrap(),
+        ];
+        if self.retestable() {
+            shortcuts.push(Shortcut::new(vec![Fragment::raw("re-test "), Fragment::hl("T")]));
+        }
+        shortcuts
     }
 
     fn init(&mut self, api: Arc<Api>) -> Result<()> {
@@ -372,10 +585,22 @@ impl Component for ProxyDetailComponent {
         Ok(())
     }
 
+    fn register_config_handler(&mut self, config: Arc<Config>) -> Result<()> {
+        self.exit_ip = config.exit_ip.clone();
+        Ok(())
+    }
+
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
         let Some(proxy) = self.proxy_name.as_ref().and_then(|n| Proxies::get_by_name(n)) else {
             return Ok(None);
         };
+        let mut search_changed = false;
+        if self.search.handle_key_event(key, &mut search_changed).is_consumed() {
+            if search_changed {
+                self.reset_navigator();
+            }
+            return Ok(None);
+        }
         if self.navigator.handle_key_event(true, key).is_consumed() {
             return Ok(None);
         }
@@ -396,21 +621,37 @@ impl Component for ProxyDetailComponent {
                 self.backup_navigator();
                 self.load_proxies()?;
             }
+            KeyCode::Char('f') => self.toggle_failing_only(),
+            KeyCode::Char('l') => self.toggle_fastest_first(),
+            KeyCode::Char('i') => {
+                if let Some(node) = self.target_node(&proxy) {
+                    self.lookup_node(node)?;
+                }
+            }
             KeyCode::Enter => {
                 // update selected proxy
                 if let Some(idx) = self.navigator.focused
-                    && let Some(name) = proxy.children.as_ref().and_then(|v| v.get(idx))
+                    && let Some(name) = self.children(&proxy).get(idx)
                 {
                     let selector_name = proxy.name.clone();
                     self.backup_navigator();
-                    self.update_proxy(selector_name, name.clone())?;
+                    return Ok(Some(Action::ProxyUpdateRequest(selector_name, name.clone())));
+                }
+            }
+            KeyCode::Char('t') if self.failing_only && self.navigator.focused.is_none() => {
+                // test every listed failing node, the whole group may be large
+                for name in self.children(&proxy) {
+                    let is_group = Proxies::get_by_name(&name)
+                        .is_some_and(|p| p.children.as_ref().is_some_and(|c| !c.is_empty()));
+                    self.test_proxy(name, is_group, false)?;
                 }
             }
             KeyCode::Char('t') => {
+                let children = self.children(&proxy);
                 let (name, is_group, reset_pending) = self
                     .navigator
                     .focused
-                    .and_then(|idx| proxy.children.as_ref().and_then(|v| v.get(idx)))
+                    .and_then(|idx| children.get(idx))
                     .map(|name| {
                         let is_group = Proxies::get_by_name(name)
                             .map(|p| p.children.as_ref().is_some_and(|c| !c.is_empty()))
@@ -420,6 +661,7 @@ impl Component for ProxyDetailComponent {
                     .unwrap_or_else(|| (proxy.name.clone(), proxy.children.is_some(), true));
                 self.test_proxy(name, is_group, reset_pending)?;
             }
+            KeyCode::Char('T') if self.retestable() => self.retest_group(proxy.name.clone())?,
             KeyCode::Char('s') => Proxies::switch_sort_field(self.api.clone().unwrap()),
             KeyCode::Char('S') => Proxies::toggle_sort_direction(self.api.clone().unwrap()),
             KeyCode::Char('[')
@@ -432,13 +674,9 @@ impl Component for ProxyDetailComponent {
                 self.show(parent_name);
             }
             KeyCode::Char(']') if !self.loading.load(Ordering::Relaxed) => {
-                // Use `navigator.focused` first; otherwise fall back to the stored selection.
-                let proxy_name = match self.navigator.focused {
-                    Some(idx) => proxy.children.as_ref().and_then(|v| v.get(idx)),
-                    None => proxy.selected.as_ref(),
-                };
-                if let Some(proxy) = proxy_name
-                    .map(String::as_str)
+                if let Some(proxy) = self
+                    .target_node(&proxy)
+                    .as_deref()
                     .and_then(Proxies::get_by_name)
                     .filter(|p| p.children.as_ref().is_some_and(|c| !c.is_empty()))
                 {
@@ -457,7 +695,14 @@ impl Component for ProxyDetailComponent {
 
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
-            Action::ProxyDetail(name) => self.show(name),
+            Action::ProxyDetail(name) => {
+                self.failing_only = false;
+                self.show(name);
+            }
+            Action::ProxyDetailFailing(name) => {
+                self.failing_only = true;
+                self.show(name);
+            }
             Action::Tick => {
                 if self.loading.load(Ordering::Relaxed) {
                     self.throbber.calc_next();
@@ -486,6 +731,7 @@ impl Component for ProxyDetailComponent {
             Some(p) => p,
         };
 
+        let children = self.children(&proxy);
         let area = popup_area(area, 80, 80);
         frame.render_widget(Clear, area); // clears out the background
         // outer margin
@@ -494,14 +740,82 @@ impl Component for ProxyDetailComponent {
         let block = Block::bordered()
             .border_type(BorderType::Rounded)
             .border_style(Color::LightBlue)
-            .title(self.title_line(proxy.children.as_ref().map(Vec::len).unwrap_or_default()));
-        let content_area = block.inner(area);
+            .title(self.title_line(children.len()));
+        let mut content_area = block.inner(area);
         frame.render_widget(block, area);
         self.render_throbber(frame, area);
 
-        self.render_cards(&proxy, frame, content_area);
+        if let Some(line) = Self::group_line(&proxy) {
+            let [group_area, cards_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content_area);
+            frame.render_widget(Paragraph::new(line), group_area.inner(Margin::new(1, 0)));
+            content_area = cards_area;
+        }
+        if let Some(node) = self.target_node(&proxy) {
+            let [cards_area, lookup_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(content_area);
+            let line = self.lookup_line(&node);
+            frame.render_widget(Paragraph::new(line), lookup_area.inner(Margin::new(1, 0)));
+            content_area = cards_area;
+
+            let threshold = ProxySetting::global().read().unwrap().latency_threshold;
+            let history = Proxies::get_by_name(&node).and_then(|node| {
+                Self::history_lines(&node, threshold, content_area.width.saturating_sub(2))
+            });
+            if let Some(lines) = history {
+                let [cards_area, history_area] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Length(2)])
+                        .areas(content_area);
+                frame.render_widget(
+                    Paragraph::new(lines.to_vec()),
+                    history_area.inner(Margin::new(1, 0)),
+                );
+                content_area = cards_area;
+            }
+        }
+        if self.search.is_visible() {
+            let [cards_area, search_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(content_area);
+            let total = proxy.children.as_ref().map_or(0, Vec::len);
+            self.search.render(children.len(), total, frame, search_area.inner(Margin::new(1, 0)));
+            content_area = cards_area;
+        }
+        self.render_cards(&proxy, &children, frame, content_area);
         self.navigator.render(frame, area.inner(Margin::new(0, 1)));
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn history_keeps_the_newest_cells_that_fit() {
+        let node: Proxy = serde_json::from_value(json!({
+            "name": "hk-01",
+            "type": "Trojan",
+            "history": [
+                { "time": "2024-01-01T08:00:00Z", "delay": 120 },
+                { "time": "2024-01-01T08:05:00.123456789+08:00", "delay": 0 },
+                { "delay": 980 },
+            ],
+        }))
+        .unwrap();
+        let threshold = LatencyThreshold { medium: 200, high: 800 };
+        let text = |line: &Line| line.spans.iter().map(|s| s.content.as_ref()).collect::<String>();
+
+        let [delays, times] = ProxyDetailComponent::history_lines(&node, threshold, 100).unwrap();
+        assert_eq!(text(&delays), "history  120    ✕    980  ");
+        assert_eq!(text(&times), "        08:00 08:05   -   ");
+        assert_eq!(delays.spans[5].style.bg, Some(LatencyQuality::Slow.color()));
+
+        // only the newest two fit
+        let [delays, _] = ProxyDetailComponent::history_lines(&node, threshold, 20).unwrap();
+        assert_eq!(text(&delays), "history   ✕    980  ");
+        assert!(ProxyDetailComponent::history_lines(&node, threshold, 10).is_none());
+    }
+}
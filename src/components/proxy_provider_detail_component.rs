@@ -4,26 +4,34 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Layout, Margin, Rect};
-use ratatui::prelude::{Color, Line, Span, Style};
-use ratatui::widgets::{Block, BorderType, Clear, Paragraph};
+use ratatui::prelude::{Color, Line, Span, Style, Stylize};
+use ratatui::symbols::Marker;
+use ratatui::widgets::{Axis, Block, BorderType, Chart, Clear, Dataset, GraphType, Paragraph};
 use throbber_widgets_tui::{BLACK_CIRCLE, BRAILLE_SIX, Throbber, ThrobberState, WhichUse};
 use tokio::sync::mpsc::UnboundedSender;
 use tracing::{error, info};
 
 use crate::action::Action;
 use crate::api::Api;
+use crate::app_message::AppMessage;
+use crate::components::node_cache::NodeCache;
 use crate::components::{Component, ComponentId};
 use crate::config::LatencyThreshold;
-use crate::models::proxy::Proxy;
+use crate::models::proxy::{NodeDetail, Proxy};
+use crate::store::provider_alive::{AliveSample, ProviderAlive};
 use crate::store::proxy_providers::{ProviderView, ProxyProviders};
 use crate::store::proxy_setting::ProxySetting;
+use crate::task_manager::TaskManager;
 use crate::utils::symbols::arrow;
 use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT, popup_area, space_between};
+use crate::utils::time::format_time_from_now;
+use crate::widgets::card_search::CardSearch;
 use crate::widgets::scrollable_navigator::ScrollableNavigator;
 use crate::widgets::shortcut::{Fragment, Shortcut};
 
 const CARD_HEIGHT: u16 = 3;
 const CARD_WIDTH: u16 = 25;
+const ALIVE_CHART_HEIGHT: u16 = 6;
 
 #[derive(Debug, Default)]
 pub struct ProxyProviderDetailComponent {
@@ -41,12 +49,22 @@ pub struct ProxyProviderDetailComponent {
     provider_name: Option<String>,
     provider_index: Option<usize>,
     navigator: ScrollableNavigator,
+    search: CardSearch,
+
+    /// Per-node details by node name
+    details: NodeCache<Result<NodeDetail, String>>,
 }
 
 impl ProxyProviderDetailComponent {
     pub fn show(&mut self, provider_name: String) {
         self.show = true;
         self.provider_name = Some(provider_name);
+        self.search.clear();
+        self.details.clear();
+        self.reset_navigator();
+    }
+
+    fn reset_navigator(&mut self) {
         self.navigator.focused = None;
         self.navigator.scroller.position(0);
     }
@@ -55,6 +73,7 @@ impl ProxyProviderDetailComponent {
         self.show = false;
         self.provider_name = None;
         self.provider_index = None;
+        self.search.clear();
     }
 
     fn close(&mut self) {
@@ -68,12 +87,16 @@ impl ProxyProviderDetailComponent {
         let loading = Arc::clone(&self.loading);
         loading.store(true, Ordering::Relaxed);
 
-        tokio::task::Builder::new().name("proxy-providers-loader").spawn(async move {
-            if let Err(e) = ProxyProviders::load(api).await {
-                error!(error = ?e, "Failed to get proxy providers")
-            }
-            loading.store(false, Ordering::Relaxed);
-        })?;
+        TaskManager::spawn(
+            ComponentId::ProxyProviderDetail,
+            "proxy-providers-loader",
+            async move {
+                if let Err(e) = ProxyProviders::load(api).await {
+                    error!(error = ?e, "Failed to get proxy providers")
+                }
+                loading.store(false, Ordering::Relaxed);
+            },
+        )?;
 
         Ok(())
     }
@@ -84,12 +107,16 @@ impl ProxyProviderDetailComponent {
         let health_checking = Arc::clone(&self.health_checking);
         health_checking.store(true, Ordering::Relaxed);
 
-        tokio::task::Builder::new().name("proxy-provider-health-check").spawn(async move {
-            if let Err(e) = ProxyProviders::health_check_and_reload(api, &name).await {
-                error!(error = ?e, "Failed to health check and reload provider");
-            }
-            health_checking.store(false, Ordering::Relaxed);
-        })?;
+        TaskManager::spawn(
+            ComponentId::ProxyProviderDetail,
+            "proxy-provider-health-check",
+            async move {
+                if let Err(e) = ProxyProviders::health_check_and_reload(api, &name).await {
+                    error!(error = ?e, "Failed to health check and reload provider");
+                }
+                health_checking.store(false, Ordering::Relaxed);
+            },
+        )?;
 
         Ok(())
     }
@@ -101,17 +128,98 @@ impl ProxyProviderDetailComponent {
         let loading = Arc::clone(&self.loading);
         loading.store(true, Ordering::Relaxed);
 
-        tokio::task::Builder::new().name("proxy-provider-update").spawn(async move {
-            if let Err(e) = ProxyProviders::update_and_reload(api, &name).await {
-                error!(error = ?e, "Failed to update provider");
-                let _ = action_tx.send(Action::Error(("Update proxy provider", e).into()));
-            }
-            loading.store(false, Ordering::Relaxed);
-        })?;
+        TaskManager::spawn(
+            ComponentId::ProxyProviderDetail,
+            "proxy-provider-update",
+            async move {
+                let action = match ProxyProviders::update_and_reload(api, &name).await {
+                    Ok(diff) => Action::Info(
+                        AppMessage::from((
+                            "Update proxy provider",
+                            format!("{name}: {}", diff.summary()),
+                        ))
+                        .msg_box_size(60, 60),
+                    ),
+                    Err(e) => {
+                        error!(error = ?e, "Failed to update provider");
+                        Action::Error(
+                            ComponentId::ProxyProviderDetail,
+                            ("Update proxy provider", e).into(),
+                        )
+                    }
+                };
+                let _ = action_tx.send(action);
+                loading.store(false, Ordering::Relaxed);
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn load_node_detail(&self, provider: String, node: String) -> anyhow::Result<()> {
+        info!(provider = %provider, node = %node, "Loading provider node detail");
+        let api = Arc::clone(self.api.as_ref().unwrap());
+        let load = self.details.start(&node);
+
+        TaskManager::spawn(
+            ComponentId::ProxyProviderDetail,
+            "proxy-provider-node-detail",
+            async move {
+                let detail = api.get_provider_proxy(&provider, &node).await.map_err(|e| {
+                    error!(error = ?e, "Failed to get provider node detail");
+                    format!("{e:#}")
+                });
+                load.finish(detail);
+            },
+        )?;
 
         Ok(())
     }
 
+    fn detail_line(&self, node: &str) -> Line<'static> {
+        let mut spans = vec![Span::raw(node.to_owned()).bold(), Span::raw("  ")];
+        match self.details.get(node) {
+            None => spans.push(Span::raw("`↵` loads its detail").dark_gray()),
+            Some(None) => spans.push(Span::raw("loading…").dark_gray()),
+            Some(Some(Err(e))) => spans.push(Span::raw(e).light_red()),
+            Some(Some(Ok(detail))) => {
+                spans.push(if detail.alive { "alive".green() } else { "dead".light_red() });
+                let (alive, total) = detail.alive_count();
+                spans.push(format!("  checks {alive}/{total}").into());
+                if let Some(at) = detail.last_failure() {
+                    spans.push("  last failure ".dark_gray());
+                    spans.push(format_time_from_now(at).light_red());
+                }
+                let features = detail.features();
+                if !features.is_empty() {
+                    spans.push(format!("  {}", features.join(" ")).into());
+                }
+                if let Some(dialer) = detail.dialer_proxy.as_deref().filter(|v| !v.is_empty()) {
+                    spans.push("  via ".dark_gray());
+                    spans.push(dialer.to_owned().into());
+                }
+                if let Some(iface) = detail.interface.as_deref().filter(|v| !v.is_empty()) {
+                    spans.push("  iface ".dark_gray());
+                    spans.push(iface.to_owned().into());
+                }
+                if let Some(id) = detail.id.as_deref().filter(|v| !v.is_empty()) {
+                    spans.push("  id ".dark_gray());
+                    spans.push(id.to_owned().dark_gray());
+                }
+            }
+        }
+        Line::from(spans)
+    }
+
+    /// Nodes of `provider` matching the search, in display order.
+    fn visible_proxies<'a>(&self, provider: &'a ProviderView) -> Vec<&'a Proxy> {
+        let proxies = provider.provider.proxies.iter();
+        match self.search.matcher() {
+            Some(mut matcher) => proxies.filter(|p| matcher.matches(&p.name, &p.r#type)).collect(),
+            None => proxies.collect(),
+        }
+    }
+
     fn title_line(provider_view: &'_ ProviderView) -> Line<'_> {
         let provider = &provider_view.provider;
         Line::from(vec![
@@ -154,6 +262,38 @@ impl ProxyProviderDetailComponent {
         }
     }
 
+    /// Alive node ratio over the sampled history, needs two samples to draw a line.
+    fn render_alive_chart(history: &[AliveSample], frame: &mut Frame, area: Rect) {
+        let (Some(first), Some(last)) = (history.first(), history.last()) else {
+            return;
+        };
+        let min = history.iter().map(AliveSample::percent).fold(f64::MAX, f64::min);
+        let data: Vec<(f64, f64)> =
+            history.iter().enumerate().map(|(idx, s)| (idx as f64, s.percent())).collect();
+        let dataset = Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Color::Green)
+            .data(&data);
+        let title = Line::from(vec![
+            Span::raw("Alive nodes, last "),
+            Span::raw(format_time_from_now(first.at)),
+            Span::raw(": now "),
+            Span::styled(format!("{:.0}%", last.percent()), Color::LightCyan),
+            Span::raw(format!(" ({}/{}), min {min:.0}%", last.alive, last.total)),
+        ]);
+        let chart = Chart::new(vec![dataset])
+            .block(Block::default().title(title.centered()))
+            .x_axis(Axis::default().bounds([0.0, (data.len() - 1).max(1) as f64]))
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().dark_gray())
+                    .bounds([0.0, 100.0])
+                    .labels(["0%", "50%", "100%"]),
+            );
+        frame.render_widget(chart, area);
+    }
+
     fn render_card(
         threshold: LatencyThreshold,
         proxy: &Proxy,
@@ -180,22 +320,27 @@ impl ProxyProviderDetailComponent {
         frame.render_widget(para, area);
     }
 
-    fn render_cards(&mut self, provider: &ProviderView, frame: &mut Frame, area: Rect) {
-        let provider = &provider.provider;
+    fn render_cards(&mut self, proxies: &[&Proxy], frame: &mut Frame, area: Rect) {
         let cols = (area.width / CARD_WIDTH).max(1) as usize;
         let col_chunks =
             Layout::horizontal((0..cols).map(|_| Constraint::Min(CARD_WIDTH))).split(area);
         self.navigator
             .step(cols)
-            .length(provider.proxies.len(), ((area.height / CARD_HEIGHT) as usize) * cols);
-        let visible =
-            &provider.proxies[self.navigator.scroller.pos()..self.navigator.scroller.end_pos()];
+            .length(proxies.len(), ((area.height / CARD_HEIGHT) as usize) * cols);
+        let visible = &proxies[self.navigator.scroller.pos()..self.navigator.scroller.end_pos()];
         let threshold = ProxySetting::global().read().unwrap().latency_threshold;
         self.navigator.iter_layout(visible, CARD_HEIGHT, col_chunks).for_each(
             |(proxy, focused, rect)| Self::render_card(threshold, proxy, focused, frame, rect),
         );
     }
 
+    /// Name of the focused node card.
+    fn focused_node(&mut self) -> Option<String> {
+        let idx = self.navigator.focused?;
+        let provider = self.get_provider()?;
+        self.visible_proxies(&provider).get(idx).map(|p| p.name.clone())
+    }
+
     fn get_provider(&mut self) -> Option<Arc<ProviderView>> {
         let provider_name = self.provider_name.as_deref()?;
         if let Some(provider) = self
@@ -241,6 +386,8 @@ impl Component for ProxyProviderDetailComponent {
                 Fragment::hl("S"),
                 Fragment::raw("ort"),
             ]),
+            Shortcut::new(vec![Fragment::raw("detail "), Fragment::hl("↵")]),
+            Shortcut::new(vec![Fragment::raw("search "), Fragment::hl("/")]),
             Shortcut::new(vec![Fragment::raw("back "), Fragment::hl("Esc")]),
             Shortcut::from("test", 0).unwrap(),
             Shortcut::from("update", 0).unwrap(),
@@ -262,6 +409,13 @@ impl Component for ProxyProviderDetailComponent {
         let Some(provider_name) = self.provider_name.clone() else {
             return Ok(None);
         };
+        let mut search_changed = false;
+        if self.search.handle_key_event(key, &mut search_changed).is_consumed() {
+            if search_changed {
+                self.reset_navigator();
+            }
+            return Ok(None);
+        }
         if self.navigator.handle_key_event(true, key).is_consumed() {
             return Ok(None);
         }
@@ -271,6 +425,11 @@ impl Component for ProxyProviderDetailComponent {
                 return Ok(Some(Action::Unfocus));
             }
             KeyCode::Char('r') => self.load_providers()?,
+            KeyCode::Enter => {
+                if let Some(node) = self.focused_node() {
+                    self.load_node_detail(provider_name, node)?;
+                }
+            }
             KeyCode::Char('t') => self.provider_health_check(provider_name)?,
             KeyCode::Char('u') => self.update_provider(provider_name)?,
             KeyCode::Char('s') => ProxyProviders::switch_sort_field(self.api.clone().unwrap()),
@@ -321,11 +480,38 @@ impl Component for ProxyProviderDetailComponent {
             .border_type(BorderType::Rounded)
             .border_style(Color::LightBlue)
             .title(Self::title_line(&provider));
-        let content_area = block.inner(area);
+        let mut content_area = block.inner(area);
         frame.render_widget(block, area);
         self.render_throbber(frame, area);
 
-        self.render_cards(&provider, frame, content_area);
+        let proxies = self.visible_proxies(&provider);
+        if let Some(node) = self.navigator.focused.and_then(|idx| proxies.get(idx)) {
+            let [cards_area, detail_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(content_area);
+            let line = self.detail_line(&node.name);
+            frame.render_widget(Paragraph::new(line), detail_area.inner(Margin::new(1, 0)));
+            content_area = cards_area;
+        }
+        if self.search.is_visible() {
+            let [cards_area, search_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(content_area);
+            self.search.render(
+                proxies.len(),
+                provider.provider.proxies.len(),
+                frame,
+                search_area.inner(Margin::new(1, 0)),
+            );
+            content_area = cards_area;
+        }
+        let history = ProviderAlive::history(&provider.provider.name);
+        if history.len() > 1 && content_area.height > ALIVE_CHART_HEIGHT + CARD_HEIGHT {
+            let [chart_area, cards_area] =
+                Layout::vertical([Constraint::Length(ALIVE_CHART_HEIGHT), Constraint::Min(0)])
+                    .areas(content_area);
+            Self::render_alive_chart(&history, frame, chart_area.inner(Margin::new(1, 0)));
+            content_area = cards_area;
+        }
+        self.render_cards(&proxies, frame, content_area);
         self.navigator.render(frame, area.inner(Margin::new(0, 1)));
 
         Ok(())
@@ -15,13 +15,18 @@ use tracing::{error, info, warn};
 
 use crate::action::Action;
 use crate::api::Api;
+use crate::app_message::AppMessage;
+use crate::components::search::{SearchEntry, SearchTarget};
 use crate::components::{Component, ComponentId};
 use crate::config::Config;
-use crate::store::proxy_providers::{ProviderView, ProxyProviders};
+use crate::store::proxy_providers::{ProviderView, ProxyProviders, health_check_summary};
+use crate::store::warmup::Warmup;
+use crate::task_manager::TaskManager;
 use crate::utils::byte_size::human_bytes;
 use crate::utils::symbols::arrow;
-use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT, space_between_many};
+use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT, freshness_span, space_between_many};
 use crate::utils::time::format_timestamp;
+use crate::widgets::empty_state::EmptyState;
 use crate::widgets::scrollable_navigator::ScrollableNavigator;
 use crate::widgets::shortcut::{Fragment, Shortcut};
 
@@ -48,11 +53,12 @@ impl ProxyProvidersComponent {
         let loading = Arc::clone(&self.loading);
         loading.store(true, Ordering::Relaxed);
 
-        tokio::task::Builder::new().name("proxy-providers-loader").spawn(async move {
+        TaskManager::spawn(ComponentId::ProxyProviders, "proxy-providers-loader", async move {
             if let Err(e) = ProxyProviders::load(api).await {
                 error!(error = ?e, "Failed to get proxy providers")
             }
             loading.store(false, Ordering::Relaxed);
+            Warmup::ready(ComponentId::ProxyProviders);
         })?;
 
         Ok(())
@@ -64,14 +70,53 @@ impl ProxyProvidersComponent {
         let pending_test = Arc::clone(&self.pending_test);
         pending_test.fetch_add(1, Ordering::Relaxed);
 
-        tokio::task::Builder::new().name("proxy-provider-health-check").spawn(async move {
-            if let Err(e) = ProxyProviders::health_check_and_reload(api, &name).await {
-                error!(error = ?e, "Failed to health check and reload provider");
-            }
-            let _ = pending_test.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| {
-                if x == 0 { None } else { Some(x - 1) }
-            });
-        })?;
+        TaskManager::spawn(
+            ComponentId::ProxyProviders,
+            "proxy-provider-health-check",
+            async move {
+                if let Err(e) = ProxyProviders::health_check_and_reload(api, &name).await {
+                    error!(error = ?e, "Failed to health check and reload provider");
+                }
+                let _ = pending_test.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| {
+                    if x == 0 { None } else { Some(x - 1) }
+                });
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Health check all providers at once and report alive nodes before and after.
+    fn health_check_all(&self) -> Result<()> {
+        info!("Health check for all providers");
+        let api = Arc::clone(self.api.as_ref().unwrap());
+        let action_tx = self.action_tx.as_ref().unwrap().clone();
+        let pending_test = Arc::clone(&self.pending_test);
+        pending_test.fetch_add(1, Ordering::Relaxed);
+
+        TaskManager::spawn(
+            ComponentId::ProxyProviders,
+            "proxy-providers-health-check",
+            async move {
+                let action = match ProxyProviders::health_check_all(api).await {
+                    Ok(results) => Action::Info(
+                        AppMessage::from(("Provider health check", health_check_summary(&results)))
+                            .msg_box_size(70, 60),
+                    ),
+                    Err(e) => {
+                        error!(error = ?e, "Failed to health check all providers");
+                        Action::Error(
+                            ComponentId::ProxyProviders,
+                            ("Provider health check", e).into(),
+                        )
+                    }
+                };
+                let _ = action_tx.send(action);
+                let _ = pending_test.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| {
+                    if x == 0 { None } else { Some(x - 1) }
+                });
+            },
+        )?;
 
         Ok(())
     }
@@ -83,11 +128,21 @@ impl ProxyProvidersComponent {
         let loading = Arc::clone(&self.loading);
         loading.store(true, Ordering::Relaxed);
 
-        tokio::task::Builder::new().name("proxy-provider-update").spawn(async move {
-            if let Err(e) = ProxyProviders::update_and_reload(api, &name).await {
-                error!(error = ?e, "Failed to update provider");
-                let _ = action_tx.send(Action::Error(("Update proxy provider", e).into()));
-            }
+        TaskManager::spawn(ComponentId::ProxyProviders, "proxy-provider-update", async move {
+            let action = match ProxyProviders::update_and_reload(api, &name).await {
+                Ok(diff) => Action::Info(
+                    AppMessage::from((
+                        "Update proxy provider",
+                        format!("{name}: {}", diff.summary()),
+                    ))
+                    .msg_box_size(60, 60),
+                ),
+                Err(e) => {
+                    error!(error = ?e, "Failed to update provider");
+                    Action::Error(ComponentId::ProxyProviders, ("Update proxy provider", e).into())
+                }
+            };
+            let _ = action_tx.send(action);
             loading.store(false, Ordering::Relaxed);
         })?;
 
@@ -215,21 +270,25 @@ impl ProxyProvidersComponent {
     }
 
     fn render_providers(&mut self, frame: &mut Frame, outer: Rect) {
-        let providers = {
+        let (providers, load_state, updated_at) = {
             let guard = ProxyProviders::global().read().unwrap();
-            guard.view()
+            (guard.view(), guard.load_state(), guard.updated_at())
         };
 
-        let title_line = Line::from(vec![
+        let mut title_line = Line::from(vec![
             Span::raw(TOP_TITLE_LEFT),
             Span::raw("proxy providers ("),
             Span::styled(format!("{}", providers.len()), Color::LightCyan),
             Span::raw(")"),
-            Span::raw(TOP_TITLE_RIGHT),
         ]);
+        title_line.extend(freshness_span(updated_at));
+        title_line.push_span(Span::raw(TOP_TITLE_RIGHT));
         let block = Block::bordered().border_type(BorderType::Rounded).title(title_line);
         let area = block.inner(outer);
         frame.render_widget(block, outer);
+        if providers.is_empty() {
+            EmptyState::new(load_state, "proxy providers").render(frame, area, &mut self.throbber);
+        }
 
         let col_chunks =
             Layout::horizontal((0..CARDS_PER_ROW).map(|_| Constraint::Fill(1))).split(area);
@@ -282,6 +341,7 @@ impl Component for ProxyProvidersComponent {
             Shortcut::new(vec![Fragment::raw("detail "), Fragment::hl("↵")]),
             Shortcut::from("setting", 0).unwrap(),
             Shortcut::from("test", 0).unwrap(),
+            Shortcut::new(vec![Fragment::raw("test all "), Fragment::hl("T")]),
             Shortcut::from("update", 0).unwrap(),
             Shortcut::from("refresh", 0).unwrap(),
         ]
@@ -331,6 +391,7 @@ impl Component for ProxyProvidersComponent {
                     self.provider_health_check(p.provider.name.clone())?;
                 }
             }
+            KeyCode::Char('T') => self.health_check_all()?,
             KeyCode::Char('u') => {
                 if let Some(idx) = self.navigator.focused
                     && let Some(p) = ProxyProviders::get(idx)
@@ -344,9 +405,30 @@ impl Component for ProxyProvidersComponent {
         Ok(None)
     }
 
+    fn search_entries(&self) -> Vec<SearchEntry> {
+        let providers = ProxyProviders::global().read().unwrap().view();
+        providers
+            .iter()
+            .map(|view| {
+                let name = view.provider.name.clone();
+                let target =
+                    SearchTarget::Open(Box::new(Action::ProxyProviderDetail(name.clone())));
+                SearchEntry::new(self.id(), "provider", name, target).detail(format!(
+                    "{}/{} alive",
+                    view.alive(),
+                    view.provider.proxies.len()
+                ))
+            })
+            .collect()
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::ProxySettingChanged => self.load_providers()?,
+            Action::ConfigReloaded(config) => self.register_config_handler(config)?,
+            Action::AutoRefresh(id) if id == self.id() && !self.loading.load(Ordering::Relaxed) => {
+                self.load_providers()?
+            }
             Action::Tick => {
                 if self.loading.load(Ordering::Relaxed) {
                     self.throbber.calc_next();
@@ -3,7 +3,7 @@ use std::cmp::PartialEq;
 use std::num::NonZeroUsize;
 
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::Frame;
 use ratatui::layout::{Margin, Rect};
 use ratatui::prelude::{Color, Style};
@@ -30,28 +30,36 @@ pub enum ProxySettingField {
     TestUrl,
     #[strum(to_string = "Test Timeout (ms)")]
     TestTimeout,
+    #[strum(to_string = "Expected Status (e.g. 204 or 200-299/302, empty = any)")]
+    ExpectedStatus,
     #[strum(to_string = "Threshold (medium,high)")]
     Threshold,
     #[strum(to_string = "Auto Terminate Connections")]
     AutoTerminateConnections,
+    #[strum(to_string = "Group Test Batch (size,delay ms; size 0 = off)")]
+    TestBatch,
 }
 
 impl ProxySettingField {
     pub fn next(&self) -> Self {
         match self {
             ProxySettingField::TestUrl => ProxySettingField::TestTimeout,
-            ProxySettingField::TestTimeout => ProxySettingField::Threshold,
+            ProxySettingField::TestTimeout => ProxySettingField::ExpectedStatus,
+            ProxySettingField::ExpectedStatus => ProxySettingField::Threshold,
             ProxySettingField::Threshold => ProxySettingField::AutoTerminateConnections,
-            ProxySettingField::AutoTerminateConnections => ProxySettingField::TestUrl,
+            ProxySettingField::AutoTerminateConnections => ProxySettingField::TestBatch,
+            ProxySettingField::TestBatch => ProxySettingField::TestUrl,
         }
     }
 
     pub fn prev(&self) -> Self {
         match self {
-            ProxySettingField::TestUrl => ProxySettingField::AutoTerminateConnections,
+            ProxySettingField::TestUrl => ProxySettingField::TestBatch,
             ProxySettingField::TestTimeout => ProxySettingField::TestUrl,
-            ProxySettingField::Threshold => ProxySettingField::TestTimeout,
+            ProxySettingField::ExpectedStatus => ProxySettingField::TestTimeout,
+            ProxySettingField::Threshold => ProxySettingField::ExpectedStatus,
             ProxySettingField::AutoTerminateConnections => ProxySettingField::Threshold,
+            ProxySettingField::TestBatch => ProxySettingField::AutoTerminateConnections,
         }
     }
 
@@ -62,10 +70,14 @@ impl ProxySettingField {
         match self {
             ProxySettingField::TestUrl => setting.test_url.clone(),
             ProxySettingField::TestTimeout => setting.test_timeout.to_string(),
+            ProxySettingField::ExpectedStatus => setting.test_expected_status.clone(),
             ProxySettingField::Threshold => setting.latency_threshold.to_string(),
             ProxySettingField::AutoTerminateConnections => {
                 setting.auto_terminate_connections.to_string()
             }
+            ProxySettingField::TestBatch => {
+                format!("{},{}", setting.test_batch_size, setting.test_batch_delay)
+            }
         }
     }
 }
@@ -117,6 +129,12 @@ impl ProxySettingComponent {
                 Err(_) => Err("Timeout must be a valid number".into()),
             },
 
+            ProxySettingField::ExpectedStatus => {
+                ProxySetting::validate_test_expected_status(input).map_err(|e| e.to_string())?;
+                setting.test_expected_status = input.into();
+                Ok(())
+            }
+
             ProxySettingField::Threshold => {
                 let threshold = input.parse::<LatencyThreshold>().map_err(|e| e.to_string())?;
                 ProxySetting::validate_latency_threshold(threshold).map_err(|e| e.to_string())?;
@@ -131,6 +149,17 @@ impl ProxySettingComponent {
                     .map_err(|_| "Auto terminate connections must be true or false".to_string())?;
                 Ok(())
             }
+
+            ProxySettingField::TestBatch => {
+                let invalid = || "Batch must be `size,delay` numbers, e.g. 20,500".to_string();
+                let (size, delay) = input.split_once(',').ok_or_else(invalid)?;
+                let size = size.trim().parse::<usize>().map_err(|_| invalid())?;
+                let delay = delay.trim().parse::<u64>().map_err(|_| invalid())?;
+                ProxySetting::validate_test_batch_delay(delay).map_err(|e| e.to_string())?;
+                setting.test_batch_size = size;
+                setting.test_batch_delay = delay;
+                Ok(())
+            }
         }
     }
 
@@ -196,6 +225,7 @@ impl Component for ProxySettingComponent {
             Shortcut::new(vec![Fragment::hl("⇧⇤"), Fragment::raw(" nav "), Fragment::hl("⇥")])
                 .compact(vec![Fragment::hl("⇧⇤"), Fragment::raw("/"), Fragment::hl("⇥")]),
             Shortcut::new(vec![Fragment::raw("confirm "), Fragment::hl("↵")]),
+            Shortcut::new(vec![Fragment::raw("open url "), Fragment::hl("C-o")]),
             Shortcut::new(vec![
                 Fragment::hl("←/C-←"),
                 Fragment::raw(" move "),
@@ -226,6 +256,15 @@ impl Component for ProxySettingComponent {
             }
             KeyCode::Tab => self.next(),
             KeyCode::BackTab => self.prev(),
+            KeyCode::Char('o')
+                if key.modifiers == KeyModifiers::CONTROL
+                    && self.focused == ProxySettingField::TestUrl =>
+            {
+                let url = self.input.value().trim();
+                if !url.is_empty() {
+                    return Ok(Some(Action::OpenUrl(url.to_owned())));
+                }
+            }
             KeyCode::Enter => {
                 self.error = self.submit().err();
                 if self.error.is_none() {
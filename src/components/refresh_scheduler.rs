@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use crate::components::ComponentId;
+
+/// Tabs that are loaded on demand and refreshed in the background by the scheduler.
+const TARGETS: [ComponentId; 3] =
+    [ComponentId::ProxyProviders, ComponentId::Rules, ComponentId::RuleProviders];
+/// 15 seconds at 4 ticks per second
+const VISIBLE_TICKS: u16 = 15 * 4;
+/// 60 seconds at 4 ticks per second, must stay below the idle tab lifetime
+const HIDDEN_TICKS: u16 = 60 * 4;
+
+/// Central scheduler for background refresh of on-demand tabs.
+///
+/// The visible tab is refreshed more often than hidden ones, and at most one tab is due per
+/// tick, so refreshes are staggered instead of hitting the API at the same time.
+#[derive(Debug, Default)]
+pub struct RefreshScheduler {
+    elapsed: HashMap<ComponentId, u16>,
+}
+
+impl RefreshScheduler {
+    /// Advance counters of alive targets, returns the tab that should be refreshed now.
+    pub fn tick(
+        &mut self,
+        current_tab: ComponentId,
+        is_alive: impl Fn(ComponentId) -> bool,
+    ) -> Option<ComponentId> {
+        let mut due = None;
+        for id in TARGETS {
+            if !is_alive(id) {
+                self.elapsed.remove(&id);
+                continue;
+            }
+            let ticks = self.elapsed.entry(id).or_default();
+            *ticks = ticks.saturating_add(1);
+            let interval = if id == current_tab { VISIBLE_TICKS } else { HIDDEN_TICKS };
+            if due.is_none() && *ticks >= interval {
+                due = Some(id);
+            }
+        }
+        if let Some(id) = due {
+            self.reset(id);
+        }
+        due
+    }
+
+    /// Restart the interval of `id`, e.g. when the tab has just been (re)loaded.
+    pub fn reset(&mut self, id: ComponentId) {
+        if let Some(ticks) = self.elapsed.get_mut(&id) {
+            *ticks = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visible_tab_refreshes_faster_and_one_per_tick() {
+        let mut scheduler = RefreshScheduler::default();
+        let current = ComponentId::Rules;
+        let alive = |id| id != ComponentId::ProxyProviders;
+
+        let mut due = vec![];
+        for tick in 1..=HIDDEN_TICKS + 1 {
+            if let Some(id) = scheduler.tick(current, alive) {
+                due.push((tick, id));
+            }
+        }
+
+        let visible: Vec<_> = due.iter().filter(|(_, id)| *id == current).collect();
+        assert_eq!(visible.len(), 4);
+        assert_eq!(visible[0].0, VISIBLE_TICKS);
+        // hidden tab is due on the same tick as the visible one, postponed by one tick
+        assert!(due.contains(&(HIDDEN_TICKS + 1, ComponentId::RuleProviders)));
+        assert!(!due.iter().any(|(_, id)| *id == ComponentId::ProxyProviders));
+    }
+
+    #[test]
+    fn reset_restarts_interval() {
+        let mut scheduler = RefreshScheduler::default();
+        let current = ComponentId::RuleProviders;
+        for _ in 1..VISIBLE_TICKS {
+            assert_eq!(scheduler.tick(current, |id| id == current), None);
+        }
+        scheduler.reset(current);
+        assert_eq!(scheduler.tick(current, |id| id == current), None);
+    }
+}
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use futures_util::{StreamExt, TryStreamExt, future};
+use futures_util::{StreamExt, TryStreamExt, future, stream};
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Color, Stylize};
@@ -12,43 +13,97 @@ use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::{Mutex as AsyncMutex, mpsc, watch};
+use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, trace_span, warn};
 
 use crate::action::Action;
+use crate::alerts::AlertEngine;
 use crate::api::Api;
+use crate::app_message::AppMessage;
+use crate::components::api_calls_component::ApiCallsComponent;
+use crate::components::api_reconnect_component::ApiReconnectComponent;
+use crate::components::capture_file_component::CaptureFileComponent;
+use crate::components::chart_setting_component::ChartSettingComponent;
+use crate::components::confirm_component::ConfirmComponent;
 use crate::components::connection_batch_terminate_component::ConnectionBatchTerminateComponent;
 use crate::components::connection_detail_component::ConnectionDetailComponent;
 use crate::components::connection_terminate_component::ConnectionTerminateComponent;
 use crate::components::connections_component::ConnectionsComponent;
 use crate::components::connections_setting_component::ConnectionsSettingComponent;
 use crate::components::core_config_component::CoreConfigComponent;
+use crate::components::core_config_export_component::CoreConfigExportComponent;
+use crate::components::core_config_import_component::CoreConfigImportComponent;
 use crate::components::dns_query_component::DnsQueryComponent;
+use crate::components::error_log_component::ErrorLogComponent;
 use crate::components::filter_component::FilterComponent;
 use crate::components::footer_component::FooterComponent;
+use crate::components::global_search_component::GlobalSearchComponent;
 use crate::components::header_component::HeaderComponent;
 use crate::components::help_component::HelpComponent;
+use crate::components::latency_calibration_component::LatencyCalibrationComponent;
 use crate::components::logs_component::LogsComponent;
 use crate::components::msg_box_component::MsgBoxComponent;
 use crate::components::overview_component::OverviewComponent;
+use crate::components::plugin_component::PluginComponent;
+use crate::components::profiles_component::ProfilesComponent;
 use crate::components::proxies_component::ProxiesComponent;
 use crate::components::proxy_detail_component::ProxyDetailComponent;
 use crate::components::proxy_provider_detail_component::ProxyProviderDetailComponent;
 use crate::components::proxy_providers_component::ProxyProvidersComponent;
 use crate::components::proxy_setting_component::ProxySettingComponent;
+use crate::components::refresh_scheduler::RefreshScheduler;
+use crate::components::rule_breakdown_component::RuleBreakdownComponent;
+use crate::components::rule_match_component::RuleMatchComponent;
 use crate::components::rule_providers_component::RuleProvidersComponent;
 use crate::components::rules_component::RulesComponent;
+use crate::components::search::{SearchEntry, SearchTarget};
+use crate::components::secret_prompt_component::SecretPromptComponent;
+use crate::components::tab_switcher_component::TabSwitcherComponent;
+use crate::components::tasks_component::TasksComponent;
+use crate::components::tcp_ping_component::TcpPingComponent;
+use crate::components::timeline_component::TimelineComponent;
 use crate::components::updates_component::UpdatesComponent;
+use crate::components::whats_new_component::{self, WhatsNewComponent};
 use crate::components::{Component, ComponentId, TABS};
-use crate::config::Config;
-use crate::models::{Connection, ConnectionStats};
+use crate::components::{history_recorder, memory_watcher, provider_watcher};
+use crate::config::{Config, UnfocusedMode};
+use crate::models::Connection;
+use crate::plugins;
+use crate::store::auth_failures::AuthFailures;
+use crate::store::bursts::BurstDetector;
+use crate::store::churn::ChurnTracker;
+use crate::store::closed_conns::ClosedConnTracker;
+use crate::store::connection_stats::ConnectionStats;
+use crate::store::error_log::ErrorLog;
+use crate::store::geo_update::GeoUpdate;
+use crate::store::geoip::GeoIp;
+use crate::store::ip_family::IpFamilyStats;
+use crate::store::proxies::Proxies;
+use crate::store::proxy_setting::ProxySetting;
+use crate::store::snapshot_delta::SnapshotDiffer;
+use crate::store::snapshot_drops::SnapshotDrops;
+use crate::store::stream_hub::StreamHub;
+use crate::store::timeline::{EventKind, Timeline};
+use crate::store::warmup::Warmup;
+use crate::task_manager::TaskManager;
+use crate::utils::safe_mode::SafeMode;
 use crate::utils::text_ui::top_title_line;
 use crate::version_update::SharedVersionUpdateState;
+use crate::widgets::shortcut::{Fragment, Shortcut};
 
 /// Minimum terminal area `(width, height)` to render the UI properly.
 const MIN_AREA: (u16, u16) = (80, 18);
 /// 120 seconds at 4 ticks per second
 const IDLE_TICKS: u16 = 120 * 4;
+/// Tabs backed by REST loads, loaded in parallel on startup.
+const WARMUP_TABS: [ComponentId; 5] = [
+    ComponentId::Proxies,
+    ComponentId::ProxyProviders,
+    ComponentId::Rules,
+    ComponentId::RuleProviders,
+    ComponentId::Config,
+];
 
 pub struct RootComponent {
     api: Option<Arc<Api>>,
@@ -58,13 +113,24 @@ pub struct RootComponent {
 
     current_tab: ComponentId,
     idle_tabs: HashMap<ComponentId, u16>,
+    refresh_scheduler: RefreshScheduler,
+    provider_watcher: Option<JoinHandle<Option<()>>>,
+    memory_watcher: Option<JoinHandle<Option<()>>>,
+    history_recorder: Option<JoinHandle<Option<()>>>,
+    /// Repeated 401s took over the screen, loaders stay stopped until the API is reconnected
+    auth_failed: bool,
+    terminal_focused: bool,
+    /// Streams and background refreshes stopped while the terminal is unfocused
+    paused: bool,
     components: HashMap<ComponentId, Box<dyn Component>>,
 
-    /// UI priority (input & render): `msg_box` > `focused` > `popup` > `normal`.
+    /// UI priority (input & render): `msg_box` > `focused` > `popups` > `normal`.
     /// Message box lifecycle is owned and eagerly cleared by RootComponent
     msg_box: Option<MsgBoxComponent>,
     focused: Option<ComponentId>,
-    popup: Option<ComponentId>,
+    /// Opened popups, the last one on top. Popups opened from a popup stack on it, closing one
+    /// focuses the popup below.
+    popups: Vec<ComponentId>,
 
     conn_token: Option<CancellationToken>,
     stats_tx: watch::Sender<Option<ConnectionStats>>,
@@ -88,9 +154,16 @@ impl RootComponent {
             api: Default::default(),
             config: Default::default(),
             current_tab: Default::default(),
-            popup: Default::default(),
+            popups: Default::default(),
             focused: Default::default(),
             idle_tabs: Default::default(),
+            refresh_scheduler: Default::default(),
+            provider_watcher: Default::default(),
+            memory_watcher: Default::default(),
+            history_recorder: Default::default(),
+            auth_failed: Default::default(),
+            terminal_focused: true,
+            paused: Default::default(),
             msg_box: Default::default(),
             components,
             action_tx: Default::default(),
@@ -104,6 +177,13 @@ impl RootComponent {
         }
     }
 
+    /// Draw `id` within its tracing span, so its logs can be filtered with
+    /// `[component{id=<name>}]=<level>`.
+    fn draw_component(&mut self, id: ComponentId, frame: &mut Frame, area: Rect) -> Result<()> {
+        let _span = trace_span!("component", id = id.full_name(), op = "draw").entered();
+        self.get_or_init(id).draw(frame, area)
+    }
+
     fn get_or_init(&mut self, id: ComponentId) -> &mut Box<dyn Component> {
         self.components.entry(id).or_insert_with(|| {
             let mut c: Box<dyn Component> = match id {
@@ -113,18 +193,20 @@ impl RootComponent {
                     Box::new(OverviewComponent::new(self.stats_rx.clone(), store_capacity))
                 }
                 ComponentId::Connections => {
-                    let store_capacity = self
-                        .config
-                        .as_ref()
-                        .map(|c| c.buffer.clone())
-                        .unwrap_or_default()
-                        .connections;
-                    Box::new(ConnectionsComponent::new(Arc::clone(&self.conns_rx), store_capacity))
+                    let buffer = self.config.as_ref().map(|c| c.buffer.clone()).unwrap_or_default();
+                    Box::new(ConnectionsComponent::new(
+                        Arc::clone(&self.conns_rx),
+                        buffer.connections,
+                        buffer.capture,
+                    ))
                 }
                 ComponentId::ConnectionsSetting => Box::new(ConnectionsSettingComponent::default()),
+                ComponentId::CaptureFile => Box::new(CaptureFileComponent::default()),
                 ComponentId::Proxies => Box::new(ProxiesComponent::default()),
                 ComponentId::ProxyDetail => Box::new(ProxyDetailComponent::default()),
                 ComponentId::ProxySetting => Box::new(ProxySettingComponent::default()),
+                ComponentId::LatencyCalibration => Box::new(LatencyCalibrationComponent::default()),
+                ComponentId::ChartSetting => Box::new(ChartSettingComponent::default()),
                 ComponentId::ProxyProviders => Box::new(ProxyProvidersComponent::default()),
                 ComponentId::ProxyProviderDetail => {
                     Box::new(ProxyProviderDetailComponent::default())
@@ -135,6 +217,8 @@ impl RootComponent {
                     Box::new(LogsComponent::new(store_capacity))
                 }
                 ComponentId::Rules => Box::new(RulesComponent::default()),
+                ComponentId::RuleBreakdown => Box::new(RuleBreakdownComponent::default()),
+                ComponentId::RuleMatch => Box::new(RuleMatchComponent::default()),
                 ComponentId::RuleProviders => Box::new(RuleProvidersComponent::default()),
                 ComponentId::Config => Box::new(CoreConfigComponent::default()),
                 ComponentId::Updates => Box::new(UpdatesComponent::new(self.update_state.clone())),
@@ -148,6 +232,24 @@ impl RootComponent {
                 }
                 ComponentId::Filter => Box::new(FilterComponent::default()),
                 ComponentId::DnsQuery => Box::new(DnsQueryComponent::default()),
+                ComponentId::TcpPing => Box::new(TcpPingComponent::default()),
+                ComponentId::CoreConfigExport => Box::new(CoreConfigExportComponent::default()),
+                ComponentId::CoreConfigImport => Box::new(CoreConfigImportComponent::default()),
+                ComponentId::Profiles => Box::new(ProfilesComponent::default()),
+                ComponentId::ApiReconnect => Box::new(ApiReconnectComponent::default()),
+                ComponentId::Confirm => Box::new(ConfirmComponent::default()),
+                ComponentId::SecretPrompt => Box::new(SecretPromptComponent::default()),
+                ComponentId::Tasks => Box::new(TasksComponent::default()),
+                ComponentId::Timeline => Box::new(TimelineComponent::default()),
+                ComponentId::ErrorLog => Box::new(ErrorLogComponent::default()),
+                ComponentId::ApiCalls => Box::new(ApiCallsComponent::default()),
+                ComponentId::WhatsNew => Box::new(WhatsNewComponent::default()),
+                ComponentId::TabSwitcher => Box::new(TabSwitcherComponent::default()),
+                ComponentId::GlobalSearch => Box::new(GlobalSearchComponent::default()),
+                ComponentId::Plugin(name) => match plugins::find(name) {
+                    Some(tab) => Box::new(PluginComponent::new(tab)),
+                    None => panic!("unknown plugin `{name}`"),
+                },
                 _ => panic!("unsupported component `{:?}`", id),
             };
             debug!("Initializing component `{:?}`", id);
@@ -162,7 +264,9 @@ impl RootComponent {
 
     fn open_popup(&mut self, id: ComponentId) -> Result<()> {
         info!("Opening popup {:?}", id);
-        self.popup = Some(id);
+        // reopening a popup of the stack brings it to the top
+        self.popups.retain(|popup| *popup != id);
+        self.popups.push(id);
 
         // get and init component, send shortcuts of current tab to footer
         let shortcuts = self.get_or_init(id).shortcuts();
@@ -175,6 +279,136 @@ impl RootComponent {
         Ok(())
     }
 
+    /// (Re)start the watchers driven by config settings, on init and when the config file changed.
+    fn spawn_config_watchers(&mut self) -> Result<()> {
+        if let Some(handle) = self.memory_watcher.take() {
+            handle.abort();
+        }
+        let action_tx = self.action_tx.as_ref().unwrap().clone();
+        let memory_trend = self.config.as_ref().map(|c| c.memory_trend).unwrap_or_default();
+        if memory_trend.growth > 0 {
+            self.memory_watcher = Some(memory_watcher::spawn(memory_trend, action_tx)?);
+        }
+        if let Some(handle) = self.history_recorder.take() {
+            handle.abort();
+        }
+        let history = self.config.as_ref().map(|c| c.history).unwrap_or_default();
+        if history.enabled {
+            let stats_rx = self.stats_rx.clone();
+            self.history_recorder = Some(history_recorder::spawn(history, stats_rx)?);
+        }
+        Ok(())
+    }
+
+    /// Switch to a new API client: all components except the footer are dropped and lazily
+    /// recreated with the new client, so no background task keeps using the old endpoint.
+    pub fn reset_api(&mut self, api: Arc<Api>, config: Arc<Config>) -> Result<()> {
+        info!("Resetting components with new API client");
+        self.drop_components();
+        self.auth_failed = false;
+        AuthFailures::global().reset();
+        self.components
+            .insert(ComponentId::Header, Box::new(HeaderComponent::new(self.update_state.clone())));
+        self.register_config_handler(config)?;
+        self.init(api)?;
+
+        self.action_tx.as_ref().unwrap().send(Action::TabSwitch(self.current_tab))?;
+        Ok(())
+    }
+
+    /// Drop all components except the footer and stop their background tasks.
+    fn drop_components(&mut self) {
+        self.stop_conn();
+        self.popups.clear();
+        self.focused = None;
+        self.idle_tabs.clear();
+        let ids = self.components.keys().copied().collect::<Vec<_>>();
+        for id in ids.into_iter().filter(|id| *id != ComponentId::Footer) {
+            self.components.remove(&id);
+            TaskManager::cancel_owner(id);
+        }
+    }
+
+    /// Switch to the authentication failed screen after repeated 401s, instead of every loader
+    /// failing on each refresh. Retrying or entering a new secret reconnects the API, which
+    /// recreates the components.
+    fn enter_auth_failed(&mut self) -> Result<()> {
+        warn!("Repeated authentication failures, pausing background loaders");
+        Timeline::record(EventKind::Core, "Authentication failed, loaders paused");
+        let prompting = self.popups.contains(&ComponentId::SecretPrompt);
+        self.auth_failed = true;
+        self.drop_components();
+        if let Some(handle) = self.provider_watcher.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.memory_watcher.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.history_recorder.take() {
+            handle.abort();
+        }
+        self.msg_box = None;
+        let action_tx = self.action_tx.as_ref().unwrap();
+        action_tx.send(Action::Shortcuts(Self::auth_failed_shortcuts()))?;
+        // keep the secret prompt opened on startup
+        if prompting {
+            action_tx.send(Action::SecretPrompt)?;
+        }
+        Ok(())
+    }
+
+    fn auth_failed_shortcuts() -> Vec<Shortcut> {
+        vec![
+            Shortcut::from("retry", 0).unwrap(),
+            Shortcut::new(vec![Fragment::raw("edit secret "), Fragment::hl("e")]),
+            Shortcut::from("quit", 0).unwrap(),
+        ]
+    }
+
+    fn handle_auth_failed_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Char('r') => {
+                info!("Retrying after authentication failure");
+                self.config.as_ref().map(|c| Action::ApiReconnect(Arc::clone(c)))
+            }
+            KeyCode::Char('e') => Some(Action::SecretPrompt),
+            KeyCode::Char('q') => Some(Action::Quit),
+            _ => None,
+        }
+    }
+
+    fn draw_auth_failed(&self, frame: &mut Frame, area: Rect) {
+        let endpoint = self.config.as_ref().map(|c| c.mihomo_api.to_string()).unwrap_or_default();
+        let lines = vec![
+            Line::raw(""),
+            Line::from("Authentication failed").bold().red().centered(),
+            Line::raw(""),
+            Line::from(vec![
+                Span::raw("The controller "),
+                Span::raw(endpoint).cyan(),
+                Span::raw(" keeps rejecting the secret."),
+            ])
+            .centered(),
+            Line::from("Background loaders are paused until the connection works again.")
+                .dark_gray()
+                .centered(),
+            Line::raw(""),
+            Line::from(vec![
+                Span::raw("r").bold().cyan(),
+                Span::raw(" retry   "),
+                Span::raw("e").bold().cyan(),
+                Span::raw(" edit secret   "),
+                Span::raw("q").bold().cyan(),
+                Span::raw(" quit"),
+            ])
+            .centered(),
+        ];
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(top_title_line("error", Color::Red));
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
     /// Returns `true` if the connections stream is currently active.
     fn is_conn_active(&self) -> bool {
         self.conn_token.as_ref().is_some_and(|t| !t.is_cancelled())
@@ -201,7 +435,7 @@ impl RootComponent {
 
     /// Start loading connections if needed
     fn maybe_load_conn(&mut self) -> Result<()> {
-        if !self.is_conn_tab() || self.is_conn_active() {
+        if !self.is_conn_tab() || self.is_conn_active() || self.paused {
             return Ok(());
         }
 
@@ -212,13 +446,32 @@ impl RootComponent {
         let stats_tx = self.stats_tx.clone();
         let conns_tx = self.conns_tx.clone();
         let conns_rx = Arc::clone(&self.conns_rx);
+        let action_tx = self.action_tx.as_ref().unwrap().clone();
+        // conditions are validated when loading config
+        let mut alerts = self
+            .config
+            .as_ref()
+            .and_then(|c| AlertEngine::new(&c.alerts, &c.hooks).ok())
+            .filter(|engine| !engine.is_empty());
+        let burst_config = self.config.as_ref().map(|c| c.burst).unwrap_or_default();
 
-        tokio::task::Builder::new().name("connections_wrapper-loader").spawn(async move {
-            let stream = match api.stream_connections().await {
-                Ok(stream) => stream,
-                Err(e) => {
-                    error!(error = ?e, "Failed to get connections stream.");
-                    return;
+        TaskManager::spawn(ComponentId::Root, "connections_wrapper-loader", async move {
+            let mut differ = SnapshotDiffer::default();
+            let mut closed = ClosedConnTracker::default();
+            let mut churn = ChurnTracker::default();
+            let mut bursts = BurstDetector::new(burst_config);
+            let mut drops = SnapshotDrops::default();
+            let mut dropping = false;
+            let stream = if SafeMode::enabled() {
+                // a single snapshot each time a connections tab is opened
+                stream::once(api.get_connections()).boxed()
+            } else {
+                match api.stream_connections().await {
+                    Ok(stream) => stream.boxed(),
+                    Err(e) => {
+                        error!(error = ?e, "Failed to get connections stream.");
+                        return;
+                    }
                 }
             };
             stream
@@ -226,7 +479,64 @@ impl RootComponent {
                 .inspect_err(|e| warn!(error = ?e, "Failed to parse connections."))
                 .filter_map(|res| future::ready(res.ok()))
                 .for_each(|record| {
-                    let _ = stats_tx.send(Some((&record).into()));
+                    let mut stats = ConnectionStats::from(&record);
+                    let conns = record.connections.as_deref().unwrap_or_default();
+                    // computed once, shared by every tracker
+                    let delta = differ.diff(conns);
+                    stats.ip_family = IpFamilyStats::of(&delta);
+                    stats.closed = closed.update(&delta);
+                    stats.churn = churn.update(&delta, Instant::now());
+                    let burst_report = bursts.update(&delta, Instant::now());
+                    stats.bursts = burst_report.active;
+                    stats.drops = drops.update();
+                    if stats.drops.recent != dropping {
+                        dropping = stats.drops.recent;
+                        if dropping {
+                            warn!(drops = ?stats.drops, "Connections snapshots dropped");
+                            Timeline::record(EventKind::Alert, "Connections snapshots dropped");
+                        }
+                    }
+                    let _ = stats_tx.send(Some(stats));
+                    if !burst_report.started.is_empty() {
+                        let messages = burst_report
+                            .started
+                            .iter()
+                            .map(|b| {
+                                format!(
+                                    "{} opened {} connections in {}s",
+                                    b.source_ip, b.opened, burst_config.window
+                                )
+                            })
+                            .collect::<Vec<_>>();
+                        info!(?messages, "Connection bursts detected");
+                        messages.iter().for_each(|m| Timeline::record(EventKind::Alert, m));
+                        if burst_config.alert {
+                            let _ = action_tx.send(Action::Info(
+                                ("Connection burst", messages.join("\n")).into(),
+                            ));
+                        }
+                    }
+                    let fired = alerts.as_mut().map(|a| a.check(&delta)).unwrap_or_default();
+                    if !fired.alerts.is_empty() {
+                        let messages = fired.alerts;
+                        info!(?messages, "Connection alerts fired");
+                        messages.iter().for_each(|m| Timeline::record(EventKind::Alert, m));
+                        let _ = action_tx.send(Action::Info(("Alert", messages.join("\n")).into()));
+                    }
+                    for hook in fired.hooks {
+                        info!(name = hook.name, host = hook.host, "Running connection hook");
+                        Timeline::record(
+                            EventKind::Alert,
+                            format!("Hook [{}] ran for {}", hook.name, hook.host),
+                        );
+                        if let Err(e) = hook.spawn() {
+                            error!(error = ?e, "Failed to run connection hook");
+                            let _ = action_tx.send(Action::Error(
+                                ComponentId::Root,
+                                ("Connection hook", e).into(),
+                            ));
+                        }
+                    }
                     if let Err(TrySendError::Full(v)) =
                         conns_tx.try_send(record.connections.unwrap_or_default())
                     {
@@ -234,6 +544,11 @@ impl RootComponent {
                         if let Ok(mut guard) = conns_rx.try_lock() {
                             let _ = guard.try_recv();
                         }
+                        // the root and this task hold the receiver, other holders are the
+                        // Connections tab, without it nothing reads the snapshots anyway
+                        if Arc::strong_count(&conns_rx) > 2 {
+                            drops.record_dropped();
+                        }
                         let _ = conns_tx.try_send(v);
                     }
                     future::ready(())
@@ -243,6 +558,68 @@ impl RootComponent {
         Ok(())
     }
 
+    /// Update the core's GEO databases, every [`Action::GeoUpdate`] runs here so only one update
+    /// runs at a time and the local GeoIP database is reloaded after it.
+    fn update_geo(&self) -> Result<()> {
+        let action_tx = self.action_tx.as_ref().unwrap().clone();
+        let Some(guard) = GeoUpdate::start() else {
+            action_tx.send(Action::Info(GeoUpdate::running_message()))?;
+            return Ok(());
+        };
+        info!("Updating GEO databases");
+        let api = Arc::clone(self.api.as_ref().unwrap());
+        TaskManager::spawn(ComponentId::Root, "geo-updater", async move {
+            let result = api.update_geo().await;
+            let elapsed = guard.finish(result.is_ok()).as_secs_f64();
+            match result {
+                Ok(()) => {
+                    Timeline::record(EventKind::Core, format!("Update GEO in {elapsed:.1}s"));
+                    // the local database may be the file the core just replaced
+                    GeoIp::reload();
+                    let msg = format!("GEO databases updated in {elapsed:.1}s.");
+                    let _ = action_tx.send(Action::Info(
+                        AppMessage::from(("Update GEO", msg)).msg_box_size(40, 20),
+                    ));
+                }
+                Err(e) => {
+                    error!(error = ?e, "Failed to update GEO databases");
+                    let e = e.context(format!("Update GEO failed after {elapsed:.1}s"));
+                    let _ =
+                        action_tx.send(Action::Error(ComponentId::Root, ("Update GEO", e).into()));
+                }
+            }
+        })?;
+        Ok(())
+    }
+
+    fn update_proxy(&self, group: String, node: String) -> Result<()> {
+        info!("Updating proxy {}: {}", group, node);
+        let api = Arc::clone(self.api.as_ref().unwrap());
+        let action_tx = self.action_tx.as_ref().unwrap().clone();
+        TaskManager::spawn(ComponentId::Root, "proxy-updater", async move {
+            match Proxies::update_and_reload(api.clone(), &group, &node).await {
+                Ok(()) => {
+                    Timeline::record(EventKind::Proxy, format!("{group} → {node}"));
+                    if ProxySetting::global().read().unwrap().auto_terminate_connections {
+                        debug!(
+                            "Auto-terminating connections for selector {} after proxy update",
+                            group
+                        );
+                        Proxies::terminate_connections_of(&api, &group).await;
+                    }
+                }
+                Err(e) => {
+                    warn!(error = ?e, "Failed to update selected proxy for {}: {}", group, node);
+                    let _ = action_tx.send(Action::Error(
+                        ComponentId::Root,
+                        ("Update selected proxy", e).into(),
+                    ));
+                }
+            }
+        })?;
+        Ok(())
+    }
+
     fn area_msg_line<'a>(width: u16, height: u16) -> Line<'a> {
         Line::default().spans(vec![
             "Width = ".bold(),
@@ -254,11 +631,27 @@ impl RootComponent {
 
     fn renew_idle(&mut self, to: ComponentId) {
         self.idle_tabs.remove(&to);
+        self.refresh_scheduler.reset(to);
         if self.current_tab != to {
             self.idle_tabs.insert(self.current_tab, IDLE_TICKS);
         }
     }
 
+    /// Eagerly create the [`WARMUP_TABS`] so their loads run in parallel and switching to them is
+    /// instant. They start out idle, so they are dropped again if never visited.
+    fn warmup(&mut self) {
+        let ids = WARMUP_TABS
+            .into_iter()
+            .filter(|id| *id != self.current_tab && !self.components.contains_key(id))
+            .collect::<Vec<_>>();
+        info!("Warming up tabs {:?}", ids);
+        Warmup::start(&ids);
+        for id in ids {
+            self.get_or_init(id);
+            self.idle_tabs.insert(id, IDLE_TICKS);
+        }
+    }
+
     fn destroy_component(&mut self, id: ComponentId) {
         // double check
         if id == self.current_tab {
@@ -266,11 +659,46 @@ impl RootComponent {
         }
         if self.components.remove(&id).is_some() {
             self.idle_tabs.remove(&id);
+            // stop consumers started by the component, so they don't leak across tab switches
+            TaskManager::cancel_owner(id);
             info!("Destroyed idle component {:?}", id);
         }
     }
 
-    fn on_tick(&mut self) {
+    /// Pause or resume the streams and background refreshes after the terminal focus or the
+    /// `unfocused` config changed.
+    fn sync_paused(&mut self) -> Result<()> {
+        if SafeMode::enabled() {
+            // nothing streams or refreshes in the background
+            return Ok(());
+        }
+        let mode = self.config.as_ref().map(|c| c.unfocused).unwrap_or_default();
+        let paused = !self.terminal_focused && mode == UnfocusedMode::Pause;
+        if paused == self.paused {
+            return Ok(());
+        }
+        self.paused = paused;
+        if paused {
+            info!("Terminal unfocused, pausing streams");
+            self.stop_conn();
+            StreamHub::global().pause();
+        } else {
+            info!("Terminal focused, resuming streams");
+            StreamHub::global().resume();
+            if !self.auth_failed {
+                self.maybe_load_conn()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn on_tick(&mut self) -> Result<()> {
+        if self.auth_failed {
+            return Ok(());
+        }
+        if AuthFailures::global().is_failing() {
+            return self.enter_auth_failed();
+        }
         // decrement idle counters
         let mut to_remove = vec![];
         for (&id, ticks) in self.idle_tabs.iter_mut() {
@@ -286,6 +714,19 @@ impl RootComponent {
         if self.should_stop_conn() {
             self.stop_conn();
         }
+        if self.paused || SafeMode::enabled() {
+            return Ok(());
+        }
+        // background refresh, idle tabs don't receive propagated actions so deliver it directly
+        let due =
+            self.refresh_scheduler.tick(self.current_tab, |id| self.components.contains_key(&id));
+        if let Some(id) = due
+            && let Some(component) = self.components.get_mut(&id)
+            && let Some(action) = component.update(Action::AutoRefresh(id))?
+        {
+            self.action_tx.as_ref().unwrap().send(action)?;
+        }
+        Ok(())
     }
 
     fn handle_global_shortcut(&mut self, key: KeyEvent) -> Option<Action> {
@@ -299,14 +740,49 @@ impl RootComponent {
                     }
                     return Some(Action::Tick);
                 }
+                KeyCode::Char('f') if key.modifiers == KeyModifiers::CONTROL => {
+                    return Some(Action::ShortcutsNextPage);
+                }
                 KeyCode::Char('u')
                     if key.modifiers == KeyModifiers::CONTROL
-                        && self.popup.is_none()
+                        && self.popups.is_empty()
                         && self.focused.is_none()
                         && self.msg_box.is_none() =>
                 {
                     return Some(Action::AppUpdateRequest);
                 }
+                KeyCode::Char('t')
+                    if key.modifiers == KeyModifiers::CONTROL
+                        && self.popups.is_empty()
+                        && self.focused.is_none()
+                        && self.msg_box.is_none() =>
+                {
+                    return Some(Action::Tasks);
+                }
+                KeyCode::Char('e')
+                    if key.modifiers == KeyModifiers::CONTROL
+                        && self.popups.is_empty()
+                        && self.focused.is_none()
+                        && self.msg_box.is_none() =>
+                {
+                    return Some(Action::Timeline);
+                }
+                KeyCode::Char('k')
+                    if key.modifiers == KeyModifiers::CONTROL
+                        && self.popups.is_empty()
+                        && self.focused.is_none()
+                        && self.msg_box.is_none() =>
+                {
+                    return Some(Action::GlobalSearch);
+                }
+                KeyCode::Char('r')
+                    if key.modifiers == KeyModifiers::CONTROL
+                        && self.popups.is_empty()
+                        && self.focused.is_none()
+                        && self.msg_box.is_none() =>
+                {
+                    return Some(Action::ApiCalls);
+                }
                 _ => {}
             }
         }
@@ -332,8 +808,21 @@ impl Component for RootComponent {
         for component in self.components.values_mut() {
             component.init(Arc::clone(&api))?;
         }
+        StreamHub::global().set_api(Arc::clone(&api));
         self.maybe_load_conn()?;
-        Ok(())
+        self.warmup();
+        if SafeMode::enabled() {
+            // traffic and memory never start, and no watcher polls the core
+            StreamHub::global().pause();
+            return Ok(());
+        }
+        // restarted with the new API client on reconnect
+        if let Some(handle) = self.provider_watcher.take() {
+            handle.abort();
+        }
+        let action_tx = self.action_tx.as_ref().unwrap().clone();
+        self.provider_watcher = Some(provider_watcher::spawn(api, action_tx)?);
+        self.spawn_config_watchers()
     }
 
     fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
@@ -343,6 +832,12 @@ impl Component for RootComponent {
 
     fn register_config_handler(&mut self, config: Arc<Config>) -> Result<()> {
         self.config = Some(Arc::clone(&config));
+        // the Connections tab takes the receiver when created, size it before that
+        if !self.components.contains_key(&ComponentId::Connections) && !self.is_conn_active() {
+            let (conns_tx, conns_rx) = mpsc::channel(config.buffer.snapshots.get());
+            self.conns_tx = conns_tx;
+            self.conns_rx = Arc::new(AsyncMutex::new(conns_rx));
+        }
         for component in self.components.values_mut() {
             component.register_config_handler(Arc::clone(&config))?;
         }
@@ -369,9 +864,18 @@ impl Component for RootComponent {
             return self.get_or_init(focused).handle_key_event(key);
         }
 
+        if self.auth_failed {
+            return Ok(self.handle_auth_failed_key(key));
+        }
+
         match key.code {
             KeyCode::Char('q') => return Ok(Some(Action::Quit)),
             KeyCode::Char('h') => return Ok(Some(Action::Help)),
+            KeyCode::Char('!') => return Ok(Some(Action::ErrorLog)),
+            // `Config` uses Tab to move focus between its panes
+            KeyCode::Tab if self.current_tab != ComponentId::Config => {
+                return Ok(Some(Action::TabSwitcher));
+            }
             KeyCode::Char(c) if c.is_ascii_digit() => {
                 let index = (c as u8 - b'0') as usize;
                 if let Some(component_id) = TABS.get(index.saturating_sub(1)) {
@@ -389,8 +893,9 @@ impl Component for RootComponent {
         let action_tx = self.action_tx.as_ref().unwrap().clone();
         match action {
             Action::Quit => self.stop_conn(),
-            Action::Tick => self.on_tick(),
-            Action::Error(err) => {
+            Action::Tick => self.on_tick()?,
+            Action::Error(source, err) => {
+                ErrorLog::record(source, &err);
                 self.msg_box =
                     Some(MsgBoxComponent::error(err.title, err.message, err.msg_box_size));
                 return Ok(None);
@@ -400,7 +905,16 @@ impl Component for RootComponent {
                     Some(MsgBoxComponent::info(info.title, info.message, info.msg_box_size));
                 return Ok(None);
             }
+            // switching tabs would recreate the paused loaders
+            Action::TabSwitch(to) if self.auth_failed => self.current_tab = to,
             Action::TabSwitch(to) => {
+                // the tab being left turns idle and misses the propagation below, tell it directly
+                if to != self.current_tab
+                    && let Some(component) = self.components.get_mut(&self.current_tab)
+                    && let Some(action) = component.update(Action::TabSwitch(to))?
+                {
+                    action_tx.send(action)?;
+                }
                 self.renew_idle(to);
                 self.current_tab = to;
                 self.maybe_load_conn()?;
@@ -413,11 +927,50 @@ impl Component for RootComponent {
             }
             Action::AppUpdateRequest => self.open_popup(ComponentId::Updates)?,
             Action::Help => self.open_popup(ComponentId::Help)?,
+            Action::TabSwitcher => self.open_popup(ComponentId::TabSwitcher)?,
+            Action::GlobalSearch => {
+                let entries: Vec<SearchEntry> = TABS
+                    .iter()
+                    .filter_map(|id| self.components.get(id))
+                    .flat_map(|component| component.search_entries())
+                    .collect();
+                self.open_popup(ComponentId::GlobalSearch)?;
+                action_tx.send(Action::GlobalSearchEntries(entries.into()))?;
+            }
+            Action::SearchJump(tab, target) => {
+                // switch inline, so the filter the tab restores on switching is queued before
+                // the one of the hit
+                self.update(Action::TabSwitch(tab))?;
+                match target {
+                    SearchTarget::Filter(pattern) => {
+                        action_tx.send(Action::FilterChanged(tab, Some(pattern)))?
+                    }
+                    SearchTarget::Open(action) => action_tx.send(*action)?,
+                    SearchTarget::Query | SearchTarget::Tab => {}
+                }
+                return Ok(None);
+            }
+            Action::Tasks => self.open_popup(ComponentId::Tasks)?,
+            Action::Timeline => self.open_popup(ComponentId::Timeline)?,
+            Action::ErrorLog => self.open_popup(ComponentId::ErrorLog)?,
+            Action::ApiCalls => self.open_popup(ComponentId::ApiCalls)?,
+            // nothing to show, e.g. after a downgrade
+            Action::WhatsNew(ref last) if !whats_new_component::has_notes_since(last) => {
+                return Ok(None);
+            }
+            Action::WhatsNew(_) => self.open_popup(ComponentId::WhatsNew)?,
             Action::ConnectionDetail(_) => self.open_popup(ComponentId::ConnectionDetail)?,
             Action::ConnectionsSetting(_) => self.open_popup(ComponentId::ConnectionsSetting)?,
-            Action::ProxyDetail(_) => self.open_popup(ComponentId::ProxyDetail)?,
+            Action::CaptureFile(_) => self.open_popup(ComponentId::CaptureFile)?,
+            Action::ProxyDetail(_) | Action::ProxyDetailFailing(_) => {
+                self.open_popup(ComponentId::ProxyDetail)?
+            }
             Action::ProxySetting => self.open_popup(ComponentId::ProxySetting)?,
+            Action::LatencyCalibration => self.open_popup(ComponentId::LatencyCalibration)?,
+            Action::ChartSetting => self.open_popup(ComponentId::ChartSetting)?,
             Action::ProxyProviderDetail(_) => self.open_popup(ComponentId::ProxyProviderDetail)?,
+            Action::RuleBreakdown(..) => self.open_popup(ComponentId::RuleBreakdown)?,
+            Action::RuleMatch(_) => self.open_popup(ComponentId::RuleMatch)?,
             Action::ConnectionTerminateRequest(_) => {
                 self.open_popup(ComponentId::ConnectionTerminate)?
             }
@@ -425,14 +978,63 @@ impl Component for RootComponent {
                 self.open_popup(ComponentId::ConnectionBatchTerminate)?
             }
             Action::DnsQuery => self.open_popup(ComponentId::DnsQuery)?,
+            Action::TcpPing => self.open_popup(ComponentId::TcpPing)?,
+            Action::CoreConfigExport(_) => self.open_popup(ComponentId::CoreConfigExport)?,
+            Action::CoreConfigImport(_) => self.open_popup(ComponentId::CoreConfigImport)?,
+            Action::Profiles(_) => self.open_popup(ComponentId::Profiles)?,
+            Action::ApiReconnectRequest(_) => self.open_popup(ComponentId::ApiReconnect)?,
+            Action::SecretPrompt => self.open_popup(ComponentId::SecretPrompt)?,
+            Action::ConfirmRequest(..) => self.open_popup(ComponentId::Confirm)?,
+            Action::GeoUpdate => self.update_geo()?,
+            Action::ProxyUpdateRequest(ref group, ref node) => {
+                self.update_proxy(group.clone(), node.clone())?
+            }
+            Action::ConfigReloaded(ref config) => {
+                let previous = self.config.replace(Arc::clone(config));
+                let watched = |c: &Config| (c.memory_trend, c.history);
+                let changed = previous.is_none_or(|p| watched(&p) != watched(config));
+                if changed && self.api.is_some() && !self.auth_failed && !SafeMode::enabled() {
+                    self.spawn_config_watchers()?;
+                }
+                self.sync_paused()?;
+            }
+            Action::TerminalFocus(focused) => {
+                self.terminal_focused = focused;
+                self.sync_paused()?;
+            }
+            // also sent from other tabs, e.g. the memory watcher's restart, deliver it to the
+            // Config tab even when it's idle or not opened yet
+            Action::CoreActionRun(_) => {
+                if let Some(action) = self.get_or_init(ComponentId::Config).update(action)? {
+                    action_tx.send(action)?;
+                }
+                return Ok(None);
+            }
+            // idle tabs don't receive propagated actions, deliver it directly like `on_tick`
+            Action::AutoRefresh(id) if self.idle_tabs.contains_key(&id) => {
+                self.refresh_scheduler.reset(id);
+                if let Some(component) = self.components.get_mut(&id)
+                    && let Some(action) = component.update(Action::AutoRefresh(id))?
+                {
+                    action_tx.send(action)?;
+                }
+                return Ok(None);
+            }
             Action::Focus(focused) => self.focused = Some(focused),
             Action::Unfocus => {
-                self.focused = None;
-                // close popup when unfocused
-                if self.popup.is_some() {
-                    self.popup = None;
-                    // send shortcuts of current tab to footer
-                    let shortcuts = self.get_or_init(self.current_tab).shortcuts();
+                // close the unfocused popup, it may not be on top if it opened another popup
+                // right before, e.g. a confirmed action
+                let closed = self.focused.take();
+                let count = self.popups.len();
+                self.popups.retain(|popup| Some(*popup) != closed);
+                if self.popups.len() != count {
+                    // focus the popup below, or the current tab, without reopening it
+                    self.focused = self.popups.last().copied();
+                    let shortcuts = match self.focused {
+                        Some(popup) => self.get_or_init(popup).shortcuts(),
+                        None if self.auth_failed => Self::auth_failed_shortcuts(),
+                        None => self.get_or_init(self.current_tab).shortcuts(),
+                    };
                     action_tx.send(Action::Shortcuts(shortcuts))?;
                 }
             }
@@ -444,6 +1046,8 @@ impl Component for RootComponent {
                 continue;
             }
 
+            let _span =
+                trace_span!("component", id = component_id.full_name(), op = "update").entered();
             if let Some(action) = component.update(action.clone())? {
                 action_tx.send(action)?;
             }
@@ -468,29 +1072,42 @@ impl Component for RootComponent {
             frame.render_widget(paragraph, area);
             return Ok(());
         }
+        if self.auth_failed {
+            let main_area = Rect { height: area.height - 1, ..area };
+            self.draw_auth_failed(frame, main_area);
+            for popup in self.popups.clone() {
+                self.draw_component(popup, frame, main_area)?;
+            }
+            self.msg_box.as_ref().map(|c| c.draw(frame, area)).transpose()?;
+            let footer_area = Rect::new(area.x + 1, area.y + area.height - 1, area.width - 2, 1);
+            self.draw_component(ComponentId::Footer, frame, footer_area)?;
+            return Ok(());
+        }
         let chunks = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).split(area);
 
         // draw header
-        self.get_or_init(ComponentId::Header).draw(frame, chunks[0])?;
+        self.draw_component(ComponentId::Header, frame, chunks[0])?;
 
         // draw main area
         if self.current_tab.supports_filter() {
             let inner_chunks =
                 Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).split(chunks[1]);
-            self.get_or_init(ComponentId::Filter).draw(frame, inner_chunks[0])?;
-            self.get_or_init(self.current_tab).draw(frame, inner_chunks[1])?;
+            self.draw_component(ComponentId::Filter, frame, inner_chunks[0])?;
+            self.draw_component(self.current_tab, frame, inner_chunks[1])?;
         } else {
-            self.get_or_init(self.current_tab).draw(frame, chunks[1])?;
+            self.draw_component(self.current_tab, frame, chunks[1])?;
         }
 
-        // draw popup if any
-        self.popup.map(|c| self.get_or_init(c).draw(frame, chunks[1])).transpose()?;
+        // draw popups bottom up
+        for popup in self.popups.clone() {
+            self.draw_component(popup, frame, chunks[1])?;
+        }
         self.msg_box.as_ref().map(|c| c.draw(frame, area)).transpose()?;
 
         // draw footer
         // get last row of main area for footer, with margin left/right = 1
         let footer_area = Rect::new(area.x + 1, area.y + area.height - 1, area.width - 2, 1);
-        self.get_or_init(ComponentId::Footer).draw(frame, footer_area)?;
+        self.draw_component(ComponentId::Footer, frame, footer_area)?;
         Ok(())
     }
 }
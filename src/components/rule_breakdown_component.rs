@@ -0,0 +1,117 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::text::Span;
+use ratatui::widgets::{Block, BorderType, Cell, Clear, Row, Table, TableState};
+
+use crate::action::Action;
+use crate::components::{Component, ComponentId};
+use crate::utils::number::format_thousands;
+use crate::utils::symbols::arrow;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::widgets::scrollable_navigator::ScrollableNavigator;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+/// Width of the share bar at 100%.
+const BAR_WIDTH: usize = 20;
+
+/// Popup listing how many rules of each type the running config has, most used first.
+#[derive(Debug, Default)]
+pub struct RuleBreakdownComponent {
+    total: usize,
+    counts: Vec<(String, usize)>,
+    navigator: ScrollableNavigator,
+    table_state: TableState,
+}
+
+impl Component for RuleBreakdownComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::RuleBreakdown
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![
+                Fragment::hl(arrow::UP),
+                Fragment::raw(" nav "),
+                Fragment::hl(arrow::DOWN),
+            ]),
+            Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.navigator.handle_key_event(false, key).is_consumed() {
+            return Ok(None);
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(Action::Unfocus)),
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::RuleBreakdown(total, counts) = action {
+            self.navigator = Default::default();
+            self.total = total;
+            self.counts = counts;
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let area = popup_area(area, 60, 70);
+        frame.render_widget(Clear, area); // clears out the background
+        let title = format!(
+            "rule types ({} types, {} rules)",
+            self.counts.len(),
+            format_thousands(self.total as u64)
+        );
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line(&title, Style::default()));
+
+        let viewport_len = area.height.saturating_sub(4) as usize;
+        self.navigator.length(self.counts.len(), viewport_len);
+        let records = self
+            .counts
+            .get(self.navigator.scroller.pos()..self.navigator.scroller.end_pos())
+            .unwrap_or(&[]);
+        *self.table_state.selected_mut() =
+            self.navigator.focused.map(|v| v.saturating_sub(self.navigator.scroller.pos()));
+
+        let header = Row::new(["TYPE", "COUNT", "SHARE", ""])
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .bottom_margin(1);
+        let total = self.total.max(1) as f64;
+        let rows = records.iter().map(|(r#type, count)| {
+            let share = *count as f64 / total;
+            let bar = "█".repeat(((share * BAR_WIDTH as f64).round() as usize).max(1));
+            Row::new([
+                Cell::from(r#type.as_str()),
+                Cell::from(format_thousands(*count as u64)),
+                Cell::from(format!("{:.1}%", share * 100.0)),
+                Cell::from(Span::styled(bar, Color::Cyan)),
+            ])
+        });
+        let widths = [
+            Constraint::Fill(1),
+            Constraint::Length(9),
+            Constraint::Length(6),
+            Constraint::Length(BAR_WIDTH as u16),
+        ];
+        let table = Table::new(rows, widths)
+            .block(block)
+            .header(header)
+            .column_spacing(2)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED).fg(Color::Cyan));
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+        self.navigator.render(frame, area);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Paragraph, Wrap};
+use tokio::sync::mpsc::UnboundedSender;
+use tui_input::Input;
+
+use crate::action::Action;
+use crate::components::{Component, ComponentId};
+use crate::models::Rule;
+use crate::store::geoip::GeoIp;
+use crate::store::proxies::Proxies;
+use crate::utils::rule_match::{MatchOutcome, MatchTarget, first_match};
+use crate::utils::symbols::arrow;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::tui_input::input_request;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+/// Undecided rules listed under the result, the rest are counted.
+const UNDECIDED_LISTED: usize = 5;
+
+/// Tests which rule, and so which proxy, the loaded rules pick for a domain, an IP or a port.
+#[derive(Debug, Default)]
+pub struct RuleMatchComponent {
+    rules: Arc<[Arc<Rule>]>,
+    input: Input,
+    outcome: Option<std::result::Result<MatchOutcome, String>>,
+
+    action_tx: Option<UnboundedSender<Action>>,
+}
+
+impl RuleMatchComponent {
+    fn test(&mut self) -> Option<Arc<Rule>> {
+        let outcome = MatchTarget::parse(self.input.value())
+            .map(|target| first_match(&self.rules, &target, GeoIp::country));
+        let matched = outcome.as_ref().ok().and_then(|o| o.matched);
+        self.outcome = Some(outcome);
+        matched.map(|idx| Arc::clone(&self.rules[idx]))
+    }
+
+    /// Proxy of the rule followed by the nodes currently selected down its groups.
+    fn proxy_chain(rule: &Rule) -> String {
+        let chain = Proxies::global().read().unwrap().selected_chain(&rule.proxy);
+        chain.join(&format!(" {} ", arrow::RIGHT))
+    }
+
+    fn rule_line(&self, idx: usize, style: Style) -> Line<'_> {
+        let rule = &self.rules[idx];
+        Line::from(vec![
+            Span::raw(format!("#{} ", rule.index.unwrap_or(idx))).dim(),
+            Span::styled(rule.r#type.as_str(), style),
+            Span::raw(" "),
+            Span::raw(rule.payload.as_str()),
+            Span::raw(format!(" {} ", arrow::RIGHT)),
+            Span::styled(rule.proxy.as_str(), Style::default().fg(Color::LightCyan)),
+        ])
+    }
+
+    fn result_lines(&self) -> Vec<Line<'_>> {
+        let outcome = match &self.outcome {
+            None => return vec![Line::from("Domain, IP or port, e.g. example.com:443").dim()],
+            Some(Err(e)) => return vec![Line::from(e.as_str()).red()],
+            Some(Ok(outcome)) => outcome,
+        };
+        let mut lines = match outcome.matched {
+            Some(idx) => vec![
+                Line::from("Matched").green().bold(),
+                self.rule_line(idx, Style::default().add_modifier(Modifier::BOLD)),
+                Line::from(vec![
+                    Span::raw("Via ").dim(),
+                    Span::styled(
+                        Self::proxy_chain(&self.rules[idx]),
+                        Style::default().fg(Color::LightCyan),
+                    ),
+                ]),
+            ],
+            None => vec![Line::from("No rule matches").yellow()],
+        };
+        if !outcome.undecided.is_empty() {
+            lines.push(Line::default());
+            lines.push(
+                Line::from(format!(
+                    "{} rules{} depend on more than the destination, or a resolved IP:",
+                    outcome.undecided.len(),
+                    if outcome.matched.is_some() { " before it" } else { "" }
+                ))
+                .yellow(),
+            );
+            lines.extend(
+                outcome
+                    .undecided
+                    .iter()
+                    .take(UNDECIDED_LISTED)
+                    .map(|&idx| self.rule_line(idx, Style::default().add_modifier(Modifier::DIM))),
+            );
+            let rest = outcome.undecided.len().saturating_sub(UNDECIDED_LISTED);
+            if rest > 0 {
+                lines.push(Line::from(format!("… {rest} more")).dim());
+            }
+        }
+        lines
+    }
+}
+
+impl Component for RuleMatchComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::RuleMatch
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![Fragment::raw("test "), Fragment::hl("↵")]),
+            Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Esc => return Ok(Some(Action::Unfocus)),
+            KeyCode::Enter => {
+                let matched = self.test();
+                self.action_tx.as_ref().unwrap().send(Action::RuleMatched(matched))?;
+            }
+            _ => {
+                if let Some(req) = input_request(key) {
+                    let _ = self.input.handle(req);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::RuleMatch(rules) = action {
+            self.rules = rules;
+            // keep the last input to tweak it, but test it again against the new rules
+            self.outcome = None;
+            return Ok(Some(Action::Shortcuts(self.shortcuts())));
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let area = popup_area(area, 60, 50);
+        frame.render_widget(Clear, area);
+        let area = area.inner(Margin::new(2, 1));
+        let title = format!("rule match ({} rules)", self.rules.len());
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line(&title, Style::default()));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [input_area, result_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(inner);
+        let input_block = Block::bordered()
+            .title("Destination")
+            .border_type(BorderType::Rounded)
+            .border_style(Color::Cyan);
+        let width = input_area.width.saturating_sub(2) as usize;
+        let scroll = self.input.visual_scroll(width);
+        let paragraph =
+            Paragraph::new(self.input.value()).scroll((0, scroll as u16)).block(input_block);
+        frame.render_widget(paragraph, input_area);
+        let x = self.input.visual_cursor().max(scroll) - scroll + 1;
+        frame.set_cursor_position((input_area.x + x as u16, input_area.y + 1));
+
+        let result = Paragraph::new(self.result_lines()).wrap(Wrap { trim: false });
+        frame.render_widget(result, result_area.inner(Margin::new(1, 0)));
+        Ok(())
+    }
+}
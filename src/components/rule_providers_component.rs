@@ -5,8 +5,8 @@ use std::sync::{Arc, Mutex, RwLock};
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::Frame;
-use ratatui::layout::{Margin, Rect};
-use ratatui::prelude::{Color, Line, Modifier, Span, Style, Stylize};
+use ratatui::layout::{Flex, Margin, Rect};
+use ratatui::prelude::{Color, Line, Modifier, Span, Style};
 use ratatui::widgets::{Block, BorderType, Cell, Row, Table, TableState};
 use throbber_widgets_tui::{BRAILLE_SIX, Throbber, ThrobberState, WhichUse};
 use tokio::sync::mpsc::UnboundedSender;
@@ -14,14 +14,20 @@ use tracing::{debug, error, info};
 
 use crate::action::Action;
 use crate::api::Api;
+use crate::components::search::SearchEntry;
 use crate::components::{Component, ComponentId};
 use crate::store::rule_providers::{RULE_PROVIDER_COLS, RuleProviders};
+use crate::store::timeline::{EventKind, Timeline};
+use crate::store::warmup::Warmup;
+use crate::task_manager::TaskManager;
 use crate::utils::columns::filter_placeholder;
 use crate::utils::filter::FilterPattern;
 use crate::utils::symbols::arrow;
-use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT};
+use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT, freshness_span};
+use crate::widgets::empty_state::EmptyState;
 use crate::widgets::scrollable_navigator::ScrollableNavigator;
 use crate::widgets::shortcut::{Fragment, Shortcut};
+use crate::widgets::table_style;
 
 #[derive(Default)]
 pub struct RuleProvidersComponent {
@@ -49,9 +55,10 @@ impl RuleProvidersComponent {
         let loading = Arc::clone(&self.loading);
         loading.store(true, Ordering::Relaxed);
 
-        tokio::task::Builder::new().name("log-loader").spawn(async move {
+        TaskManager::spawn(ComponentId::RuleProviders, "log-loader", async move {
             Self::refresh_rule_providers(&api, &store, &filter_pattern).await;
             loading.store(false, Ordering::Relaxed);
+            Warmup::ready(ComponentId::RuleProviders);
         })?;
 
         Ok(())
@@ -78,8 +85,12 @@ impl RuleProvidersComponent {
         tokio::spawn(async move {
             // update
             for name in names.iter() {
-                if let Err(e) = api.update_rule_provider(name).await {
-                    error!(error = ?e, provider = name, "update rule provider failed");
+                match api.update_rule_provider(name).await {
+                    Ok(()) => Timeline::record(
+                        EventKind::Provider,
+                        format!("Rule provider {name} updated"),
+                    ),
+                    Err(e) => error!(error = ?e, provider = name, "update rule provider failed"),
                 }
                 {
                     let mut guard = pending_update.write().unwrap();
@@ -102,7 +113,9 @@ impl RuleProvidersComponent {
         store: &RuleProviders,
         filter_pattern: &Mutex<Option<FilterPattern>>,
     ) {
-        match api.get_rule_providers().await {
+        let result = api.get_rule_providers().await;
+        store.load_state().set_result(&result);
+        match result {
             Ok(providers) => {
                 store.push(providers);
                 let filter_pattern = filter_pattern.lock().unwrap();
@@ -168,7 +181,7 @@ impl RuleProvidersComponent {
         *self.table_state.selected_mut() =
             self.navigator.focused.map(|v| v.saturating_sub(self.navigator.scroller.pos()));
 
-        let title_line = Line::from(vec![
+        let mut title_line = Line::from(vec![
             Span::raw(TOP_TITLE_LEFT),
             Span::raw("rule providers ("),
             Span::styled(
@@ -178,14 +191,16 @@ impl RuleProvidersComponent {
             Span::raw("/"),
             Span::styled(self.navigator.scroller.content_length().to_string(), Color::Cyan),
             Span::raw(")"),
-            Span::raw(TOP_TITLE_RIGHT),
         ]);
+        title_line.extend(freshness_span(self.store.load_state().updated_at()));
+        title_line.push_span(Span::raw(TOP_TITLE_RIGHT));
         let block = Block::bordered().border_type(BorderType::Rounded).title(title_line);
         let header = RULE_PROVIDER_COLS
             .iter()
-            .map(|def| def.col.title)
-            .map(|title| Cell::from(title).bold())
+            .map(|def| def.col.title.as_ref())
+            .map(Cell::from)
             .collect::<Row>()
+            .style(table_style::header_style())
             .height(1)
             .bottom_margin(1);
         let selected_row_style = Style::default().add_modifier(Modifier::REVERSED).fg(Color::Cyan);
@@ -196,13 +211,23 @@ impl RuleProvidersComponent {
                 Row::new(RULE_PROVIDER_COLS.iter().map(|def| (def.col.accessor)(item))).height(1u16)
             })
             .collect();
-        let table = Table::new(rows, RULE_PROVIDER_COLS.iter().map(|def| def.constraint))
+        let inner = block.inner(area);
+        let constraints = RULE_PROVIDER_COLS.iter().map(|def| def.constraint).collect::<Vec<_>>();
+        let table = Table::new(rows, constraints.clone())
             .block(block)
             .header(header)
             .column_spacing(2)
             .row_highlight_style(selected_row_style);
 
         frame.render_stateful_widget(table, area, &mut self.table_state);
+        table_style::render_separators(frame, inner, &constraints, Flex::Start, 2);
+
+        if records.is_empty() {
+            let filtered = self.filter_pattern.lock().unwrap().is_some();
+            EmptyState::new(self.store.load_state().get(), "rule providers")
+                .filtered(filtered)
+                .render(frame, area.inner(Margin::new(1, 1)), &mut self.throbber);
+        }
     }
 }
 
@@ -265,6 +290,18 @@ impl Component for RuleProvidersComponent {
         Ok(None)
     }
 
+    fn search_entries(&self) -> Vec<SearchEntry> {
+        self.store.with_all(|providers| {
+            providers
+                .iter()
+                .map(|p| {
+                    SearchEntry::filter(self.id(), "provider", p.name.clone())
+                        .detail(format!("{}, {} rules", p.behavior, p.rule_count))
+                })
+                .collect()
+        })
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::Tick => {
@@ -278,25 +315,19 @@ impl Component for RuleProvidersComponent {
                     self.throbber.calc_next();
                 }
             }
-            Action::FilterChanged(pattern) => {
+            Action::AutoRefresh(id) if id == self.id() && !self.is_busy() => {
+                self.load_rule_providers()?
+            }
+            Action::FilterChanged(tab, pattern) if tab == self.id() => {
                 debug!("handle Action::FilterChanged, got pattern={pattern:?}");
                 *self.filter_pattern.lock().unwrap() = pattern.and_then(FilterPattern::new);
                 self.filter_pattern_changed = true;
             }
             Action::TabSwitch(to) if to == self.id() => {
-                let pattern = self
-                    .filter_pattern
-                    .lock()
-                    .unwrap()
-                    .as_ref()
-                    .map(|pattern| pattern.raw().into());
-                debug!("handle Action::TabSwitch, current filter pattern={pattern:?}");
-                if let Some(tx) = &self.action_tx {
-                    tx.send(Action::FilterPlaceholder(filter_placeholder(
-                        RULE_PROVIDER_COLS.iter().map(|col| &col.col),
-                    )))?;
-                }
-                return Ok(Some(Action::FilterSet(pattern)));
+                return Ok(Some(Action::FilterPlaceholder(
+                    self.id(),
+                    filter_placeholder(RULE_PROVIDER_COLS.iter().map(|col| &col.col)),
+                )));
             }
             _ => {}
         }
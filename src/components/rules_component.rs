@@ -5,7 +5,7 @@ use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
 use indexmap::IndexMap;
 use ratatui::Frame;
-use ratatui::layout::{Margin, Rect};
+use ratatui::layout::{Flex, Margin, Rect};
 use ratatui::prelude::{Color, Line, Modifier, Span, Style};
 use ratatui::style::Stylize;
 use ratatui::widgets::{Block, BorderType, Cell, Row, Table, TableState};
@@ -15,15 +15,25 @@ use tracing::{debug, error, info, warn};
 
 use crate::action::Action;
 use crate::api::Api;
+use crate::components::search::{SearchEntry, SearchTarget};
 use crate::components::{Component, ComponentId};
 use crate::models::Rule;
 use crate::store::rules::{RULE_COLS, Rules};
+use crate::store::timeline::{EventKind, Timeline};
+use crate::store::warmup::Warmup;
+use crate::task_manager::TaskManager;
 use crate::utils::columns::filter_placeholder;
 use crate::utils::filter::FilterPattern;
+use crate::utils::number::format_thousands;
 use crate::utils::symbols::arrow;
-use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT};
+use crate::utils::text_ui::{TOP_TITLE_LEFT, TOP_TITLE_RIGHT, freshness_span};
+use crate::widgets::empty_state::EmptyState;
 use crate::widgets::scrollable_navigator::ScrollableNavigator;
 use crate::widgets::shortcut::{Fragment, Shortcut};
+use crate::widgets::table_style;
+
+/// Rule types listed in the panel title, the rest are in the breakdown popup.
+const SUMMARY_TYPES: usize = 3;
 
 #[derive(Default)]
 pub struct RulesComponent {
@@ -37,6 +47,10 @@ pub struct RulesComponent {
 
     loading: Arc<AtomicBool>,
     throbber: ThrobberState,
+    /// Pending bulk change awaiting confirmation: (matched count, disable)
+    bulk_confirm: Option<(usize, bool)>,
+    /// Rule matched by the last test of the rule matching popup, highlighted in the table
+    matched: Option<Arc<Rule>>,
 
     action_tx: Option<UnboundedSender<Action>>,
 }
@@ -50,9 +64,10 @@ impl RulesComponent {
         let loading = Arc::clone(&self.loading);
         loading.store(true, Ordering::Relaxed);
 
-        tokio::task::Builder::new().name("rule-loader").spawn(async move {
+        TaskManager::spawn(ComponentId::Rules, "rule-loader", async move {
             Self::refresh_rules(&api, &store, &filter_pattern).await;
             loading.store(false, Ordering::Relaxed);
+            Warmup::ready(ComponentId::Rules);
         })?;
 
         Ok(())
@@ -63,7 +78,9 @@ impl RulesComponent {
         store: &Arc<Rules>,
         filter_pattern: &Arc<Mutex<Option<FilterPattern>>>,
     ) {
-        match api.get_rules().await {
+        let result = api.get_rules().await;
+        store.load_state().set_result(&result);
+        match result {
             Ok(rules) => {
                 store.push(rules);
                 // initial view
@@ -100,6 +117,49 @@ impl RulesComponent {
         }
     }
 
+    /// Rule provider referenced by the focused `RULE-SET` rule, see [`Rule::rule_set`].
+    fn focused_rule_set(&self) -> Option<String> {
+        let idx = self.navigator.focused?;
+        self.store.with_view(|records| records.get(idx)?.rule_set().map(ToOwned::to_owned))
+    }
+
+    /// Asks to disable all matched rules, or enable them if all are already disabled.
+    fn request_bulk_toggle(&mut self) -> Result<Option<Action>> {
+        if self.filter_pattern.lock().unwrap().is_none() {
+            return Ok(Some(Action::Info(
+                ("Toggle matched rules", "Set a filter pattern first").into(),
+            )));
+        }
+        if !self.store.supports_disable() {
+            warn!(
+                meta_version_required = ">= v1.19.19",
+                upstream_pr = 2502,
+                "Rule disabling is not supported by the current rule model"
+            );
+            return Ok(None);
+        }
+        let (count, any_enabled) = self.store.with_view(|records| {
+            (records.len(), records.iter().any(|r| !r.disable_state.load(Ordering::Relaxed)))
+        });
+        if count == 0 {
+            return Ok(Some(Action::Info(
+                ("Toggle matched rules", "No rules match the filter").into(),
+            )));
+        }
+        self.bulk_confirm = Some((count, any_enabled));
+        Ok(None)
+    }
+
+    fn apply_bulk_toggle(&mut self, disable: bool) -> Result<()> {
+        debug!("Setting disabled={disable} for all matched rules");
+        self.store.with_view(|records| {
+            for record in records {
+                record.disable_state.store(disable, Ordering::Relaxed);
+            }
+        });
+        self.submit_disabled_changes()
+    }
+
     fn collect_disabled_changes(&self) -> IndexMap<usize, bool> {
         let mut state: IndexMap<usize, bool> = IndexMap::new();
         self.store.with_view(|records| {
@@ -146,16 +206,23 @@ impl RulesComponent {
         loading.store(true, Ordering::Relaxed);
         let action_tx = self.action_tx.as_ref().unwrap().clone();
 
-        tokio::task::Builder::new().name("rule-disabled-change-submitter").spawn(async move {
+        TaskManager::spawn(ComponentId::Rules, "rule-disabled-change-submitter", async move {
+            let count = changes.len();
             match api.update_rules_disabled_state(changes).await {
                 Ok(_) => {
                     info!("Successfully submit disabled rule changes");
+                    Timeline::record(
+                        EventKind::Rule,
+                        format!("Disabled state of {count} rule(s) changed"),
+                    );
                     Self::refresh_rules(&api, &store, &filter_pattern).await;
                 }
                 Err(e) => {
                     error!(error = ?e, "Failed to submit disabled rule changes");
-                    let _ =
-                        action_tx.send(Action::Error(("Submit disabled rule changes", e).into()));
+                    let _ = action_tx.send(Action::Error(
+                        ComponentId::Rules,
+                        ("Submit disabled rule changes", e).into(),
+                    ));
                 }
             }
             loading.store(false, Ordering::Relaxed);
@@ -182,6 +249,28 @@ impl RulesComponent {
         );
     }
 
+    /// Total rule count and the most used rule types, e.g. ` of 1,234: DOMAIN-SUFFIX 1,000, GEOIP
+    /// 200 +3 types`.
+    fn type_summary(&self) -> Vec<Span<'static>> {
+        let (total, counts) = self.store.type_counts();
+        if total == 0 {
+            return vec![];
+        }
+        let mut spans = vec![
+            Span::raw(" of "),
+            Span::styled(format_thousands(total as u64), Color::Cyan),
+            Span::raw(":"),
+        ];
+        for (r#type, count) in counts.iter().take(SUMMARY_TYPES) {
+            spans.push(Span::raw(format!(" {type}")).dim());
+            spans.push(Span::raw(format!(" {}", format_thousands(*count as u64))));
+        }
+        if let Some(rest) = counts.len().checked_sub(SUMMARY_TYPES).filter(|&n| n > 0) {
+            spans.push(Span::raw(format!(" +{rest} types")).dim());
+        }
+        spans
+    }
+
     fn render_rules(&mut self, frame: &mut Frame, area: Rect) {
         let records = self.store.with_view(|records| {
             let len = records.len();
@@ -198,7 +287,7 @@ impl RulesComponent {
         *self.table_state.selected_mut() =
             self.navigator.focused.map(|v| v.saturating_sub(self.navigator.scroller.pos()));
 
-        let title_line = Line::from(vec![
+        let mut title_line = Line::from(vec![
             Span::raw(TOP_TITLE_LEFT),
             Span::raw("rules ("),
             Span::styled(
@@ -208,29 +297,67 @@ impl RulesComponent {
             Span::raw("/"),
             Span::styled(self.navigator.scroller.content_length().to_string(), Color::Cyan),
             Span::raw(")"),
-            Span::raw(TOP_TITLE_RIGHT),
         ]);
-        let block = Block::bordered().border_type(BorderType::Rounded).title(title_line);
+        title_line.extend(self.type_summary());
+        title_line.extend(freshness_span(self.store.load_state().updated_at()));
+        title_line.push_span(Span::raw(TOP_TITLE_RIGHT));
+        let mut block = Block::bordered().border_type(BorderType::Rounded).title(title_line);
+        if let Some((count, disable)) = self.bulk_confirm {
+            block = block.title_bottom(
+                Line::from(vec![
+                    Span::raw(TOP_TITLE_LEFT),
+                    Span::raw(if disable { "disable " } else { "enable " }),
+                    Span::styled(count.to_string(), Style::default().fg(Color::Yellow).bold()),
+                    Span::raw(" matched rules? "),
+                    Span::styled("y", Color::LightCyan),
+                    Span::raw("/"),
+                    Span::styled("n", Color::LightCyan),
+                    Span::raw(TOP_TITLE_RIGHT),
+                ])
+                .centered(),
+            );
+        }
         let header = RULE_COLS
             .iter()
-            .map(|def| def.col.title)
-            .map(|title| Cell::from(title).bold())
+            .map(|def| def.col.title.as_ref())
+            .map(Cell::from)
             .collect::<Row>()
+            .style(table_style::header_style())
             .height(1)
             .bottom_margin(1);
         let selected_row_style = Style::default().add_modifier(Modifier::REVERSED).fg(Color::Cyan);
 
+        let matched_style = Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD);
         let rows: Vec<Row> = records
             .iter()
-            .map(|item| Row::new(RULE_COLS.iter().map(|def| (def.col.accessor)(item))).height(1u16))
+            .map(|item| {
+                let row = Row::new(RULE_COLS.iter().map(|def| (def.col.accessor)(item)));
+                match &self.matched {
+                    Some(matched) if Arc::ptr_eq(matched, item) => row.style(matched_style),
+                    _ => row,
+                }
+                .height(1u16)
+            })
             .collect();
-        let table = Table::new(rows, RULE_COLS.iter().map(|def| def.constraint))
+        let inner = block.inner(area);
+        let constraints = RULE_COLS.iter().map(|def| def.constraint).collect::<Vec<_>>();
+        let table = Table::new(rows, constraints.clone())
             .block(block)
             .header(header)
             .column_spacing(2)
             .row_highlight_style(selected_row_style);
 
         frame.render_stateful_widget(table, area, &mut self.table_state);
+        table_style::render_separators(frame, inner, &constraints, Flex::Start, 2);
+
+        if records.is_empty() {
+            let filtered = self.filter_pattern.lock().unwrap().is_some();
+            EmptyState::new(self.store.load_state().get(), "rules").filtered(filtered).render(
+                frame,
+                area.inner(Margin::new(1, 1)),
+                &mut self.throbber,
+            );
+        }
     }
 }
 
@@ -246,7 +373,7 @@ impl Component for RulesComponent {
     }
 
     fn shortcuts(&self) -> Vec<Shortcut> {
-        vec![
+        let mut shortcuts = vec![
             Shortcut::new(vec![
                 Fragment::hl(arrow::UP),
                 Fragment::raw("/"),
@@ -262,8 +389,18 @@ impl Component for RulesComponent {
             ]),
             Shortcut::from("refresh", 0).unwrap(),
             Shortcut::from("toggle", 0).unwrap(),
+            Shortcut::new(vec![Fragment::hl("T"), Fragment::raw(" toggle matched")]),
             Shortcut::from("submit", 0).unwrap(),
-        ]
+            Shortcut::from("breakdown", 0).unwrap(),
+            Shortcut::from("match", 0).unwrap(),
+        ];
+        if let Some(name) = self.focused_rule_set() {
+            shortcuts.push(Shortcut::new(vec![
+                Fragment::raw(format!("{} {name} ", arrow::RIGHT)),
+                Fragment::hl("p"),
+            ]));
+        }
+        shortcuts
     }
 
     fn init(&mut self, api: Arc<Api>) -> Result<()> {
@@ -279,21 +416,62 @@ impl Component for RulesComponent {
     }
 
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if let Some((_, disable)) = self.bulk_confirm.take() {
+            if matches!(key.code, KeyCode::Char('y') | KeyCode::Enter) {
+                self.apply_bulk_toggle(disable)?;
+            }
+            return Ok(None);
+        }
         if self.navigator.handle_key_event(false, key).is_consumed() {
             return Ok(None);
         }
         match key.code {
-            KeyCode::Esc => self.navigator.focused = None,
+            KeyCode::Esc => {
+                self.navigator.focused = None;
+                self.matched = None;
+            }
             KeyCode::Char('f') => return Ok(Some(Action::Focus(ComponentId::Filter))),
             KeyCode::Char('r') => self.load_rules()?,
             KeyCode::Char('t') => self.toggle_disabled(),
+            KeyCode::Char('T') => return self.request_bulk_toggle(),
             KeyCode::Char('s') => self.submit_disabled_changes()?,
+            KeyCode::Char('b') => {
+                let (total, counts) = self.store.type_counts();
+                return Ok(Some(Action::RuleBreakdown(total, counts)));
+            }
+            KeyCode::Char('m') => {
+                let rules = self.store.with_all(|rules| rules.iter().cloned().collect());
+                return Ok(Some(Action::RuleMatch(rules)));
+            }
+            KeyCode::Char('p') => {
+                let jump = self.focused_rule_set().map(|name| {
+                    Action::SearchJump(ComponentId::RuleProviders, SearchTarget::filter(&name))
+                });
+                return Ok(jump);
+            }
             _ => (),
         };
 
         Ok(None)
     }
 
+    fn search_entries(&self) -> Vec<SearchEntry> {
+        self.store.with_all(|rules| {
+            rules
+                .iter()
+                .filter(|rule| !rule.payload.is_empty())
+                .map(|rule| {
+                    SearchEntry::filter(self.id(), "rule", rule.payload.clone()).detail(format!(
+                        "{} {} {}",
+                        rule.r#type,
+                        arrow::RIGHT,
+                        rule.proxy
+                    ))
+                })
+                .collect()
+        })
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::Tick => {
@@ -307,25 +485,39 @@ impl Component for RulesComponent {
                     self.throbber.calc_next();
                 }
             }
-            Action::FilterChanged(pattern) => {
+            Action::AutoRefresh(id) if id == self.id() => {
+                if self.loading.load(Ordering::Relaxed) {
+                    return Ok(None);
+                }
+                // don't wipe toggled but not yet submitted rules
+                if !self.collect_disabled_changes().is_empty() {
+                    debug!("Pending disabled rule changes, auto refresh is skipped");
+                    return Ok(None);
+                }
+                self.load_rules()?;
+            }
+            Action::FilterChanged(tab, pattern) if tab == self.id() => {
                 debug!("handle Action::FilterChanged, got pattern={pattern:?}");
                 *self.filter_pattern.lock().unwrap() = pattern.and_then(FilterPattern::new);
                 self.filter_pattern_changed = true;
             }
-            Action::TabSwitch(to) if to == self.id() => {
-                let pattern = self
-                    .filter_pattern
-                    .lock()
-                    .unwrap()
-                    .as_ref()
-                    .map(|pattern| pattern.raw().into());
-                debug!("handle Action::TabSwitch, current filter pattern={pattern:?}");
-                if let Some(tx) = &self.action_tx {
-                    tx.send(Action::FilterPlaceholder(filter_placeholder(
-                        RULE_COLS.iter().map(|col| &col.col),
-                    )))?;
+            Action::RuleMatched(matched) => {
+                // no longer in the view if the rules were reloaded since the snapshot
+                self.matched = matched;
+                let pos = self.matched.as_ref().and_then(|matched| {
+                    self.store.with_view(|records| {
+                        records.iter().position(|record| Arc::ptr_eq(record, matched))
+                    })
+                });
+                if let Some(pos) = pos {
+                    self.navigator.focus(pos);
                 }
-                return Ok(Some(Action::FilterSet(pattern)));
+            }
+            Action::TabSwitch(to) if to == self.id() => {
+                return Ok(Some(Action::FilterPlaceholder(
+                    self.id(),
+                    filter_placeholder(RULE_COLS.iter().map(|col| &col.col)),
+                )));
             }
             _ => {}
         }
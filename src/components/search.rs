@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config as MatcherConfig, Matcher, Utf32Str};
+
+use crate::action::Action;
+use crate::components::ComponentId;
+
+/// Hits listed per group, the rest is only counted.
+pub const GROUP_LIMIT: usize = 20;
+
+/// What selecting a search hit does once its tab is shown.
+#[derive(Debug, Clone)]
+pub enum SearchTarget {
+    /// Apply the given filter pattern to the tab
+    Filter(String),
+    /// Apply the search query as the tab filter
+    Query,
+    /// Send the action, e.g. to open a detail popup
+    Open(Box<Action>),
+    /// Only switch to the tab
+    Tab,
+}
+
+impl SearchTarget {
+    /// Filter the tab by `text` quoted, so it is matched as is.
+    pub fn filter(text: &str) -> Self {
+        Self::Filter(quote(text))
+    }
+}
+
+/// An item of a loaded store, listed by the global search.
+#[derive(Debug, Clone)]
+pub struct SearchEntry {
+    pub tab: ComponentId,
+    /// Kind of item, e.g. `host`, groups hits with the tab
+    pub kind: &'static str,
+    /// Text matched against the query
+    pub text: String,
+    /// Shown after the text, not matched
+    pub detail: String,
+    pub target: SearchTarget,
+}
+
+impl SearchEntry {
+    pub fn new(tab: ComponentId, kind: &'static str, text: String, target: SearchTarget) -> Self {
+        Self { tab, kind, text, detail: String::new(), target }
+    }
+
+    /// Entry filtering its tab by `text` itself.
+    pub fn filter(tab: ComponentId, kind: &'static str, text: String) -> Self {
+        let target = SearchTarget::filter(&text);
+        Self::new(tab, kind, text, target)
+    }
+
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = detail.into();
+        self
+    }
+}
+
+/// Hits of one kind of item.
+#[derive(Debug, Clone)]
+pub struct SearchGroup<'a> {
+    pub tab: ComponentId,
+    pub kind: &'static str,
+    /// Best match first, at most [`GROUP_LIMIT`]
+    pub hits: Vec<&'a SearchEntry>,
+    /// Distinct matching texts
+    pub total: usize,
+}
+
+/// Fuzzy matches `query` against `entries`, grouped in the order the groups first appear. Entries
+/// with a text already matched in their group are skipped.
+pub fn search<'a>(entries: &'a [SearchEntry], query: &str) -> Vec<SearchGroup<'a>> {
+    let query = query.trim();
+    if query.is_empty() {
+        return vec![];
+    }
+    let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
+    let mut matcher = Matcher::new(MatcherConfig::DEFAULT);
+    let mut buf = Vec::new();
+
+    let mut groups: Vec<(SearchGroup, Vec<(u32, &SearchEntry)>)> = vec![];
+    let mut seen = HashSet::new();
+    for entry in entries {
+        let Some(score) = pattern.score(Utf32Str::new(&entry.text, &mut buf), &mut matcher) else {
+            continue;
+        };
+        if !seen.insert((entry.tab, entry.kind, entry.text.as_str())) {
+            continue;
+        }
+        let idx = match groups.iter().position(|(g, _)| g.tab == entry.tab && g.kind == entry.kind)
+        {
+            Some(idx) => idx,
+            None => {
+                let group =
+                    SearchGroup { tab: entry.tab, kind: entry.kind, hits: vec![], total: 0 };
+                groups.push((group, vec![]));
+                groups.len() - 1
+            }
+        };
+        groups[idx].1.push((score, entry));
+    }
+
+    groups
+        .into_iter()
+        .map(|(mut group, mut scored)| {
+            // stable sort keeps store order for equal scores
+            scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+            group.total = scored.len();
+            group.hits = scored.into_iter().take(GROUP_LIMIT).map(|(_, entry)| entry).collect();
+            group
+        })
+        .collect()
+}
+
+/// Quoted filter term matching `text` as a substring, see [`crate::utils::filter`].
+fn quote(text: &str) -> String {
+    format!("\"'{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::filter::FilterPattern;
+
+    fn entries() -> Vec<SearchEntry> {
+        vec![
+            SearchEntry::filter(ComponentId::Connections, "host", "www.google.com".into()),
+            SearchEntry::filter(ComponentId::Connections, "host", "example.com".into()),
+            SearchEntry::filter(ComponentId::Connections, "host", "www.google.com".into()),
+            SearchEntry::new(
+                ComponentId::Logs,
+                "log",
+                "dial google.com failed".into(),
+                SearchTarget::Query,
+            ),
+            SearchEntry::filter(ComponentId::Rules, "rule", "google".into())
+                .detail("DOMAIN-KEYWORD"),
+        ]
+    }
+
+    #[test]
+    fn hits_are_grouped_deduplicated_and_ranked() {
+        let entries = entries();
+        let groups = search(&entries, "google");
+        let summary: Vec<_> = groups
+            .iter()
+            .map(|g| (g.kind, g.total, g.hits.iter().map(|e| e.text.as_str()).collect::<Vec<_>>()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("host", 1, vec!["www.google.com"]),
+                ("log", 1, vec!["dial google.com failed"]),
+                ("rule", 1, vec!["google"]),
+            ]
+        );
+        assert!(search(&entries, "  ").is_empty());
+    }
+
+    #[test]
+    fn filter_targets_are_valid_patterns() {
+        let SearchTarget::Filter(raw) =
+            SearchEntry::filter(ComponentId::Rules, "rule", r#"a "b" \c"#.into()).target
+        else {
+            panic!("expected a filter target");
+        };
+        assert_eq!(raw, r#""'a \"b\" \\c""#);
+        assert!(FilterPattern::new(raw).is_some());
+    }
+}
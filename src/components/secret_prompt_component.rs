@@ -0,0 +1,239 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::prelude::{Color, Style};
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Paragraph, Wrap};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::{info, warn};
+use tui_input::Input;
+
+use crate::action::Action;
+use crate::api::Api;
+use crate::components::{Component, ComponentId, api_observer};
+use crate::config::Config;
+use crate::store::timeline::{EventKind, Timeline};
+use crate::task_manager::TaskManager;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::tui_input::input_request;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+type Verification = Arc<Mutex<Option<Result<(), String>>>>;
+
+/// Asks for the controller secret after the API answered 401, the entered secret is verified
+/// against `GET /version` before reconnecting with it.
+#[derive(Default)]
+pub struct SecretPromptComponent {
+    show: bool,
+    input: Input,
+    /// Also write the secret into the config file once it is accepted
+    save: bool,
+    /// Config with the entered secret, set while it is being verified
+    pending: Option<Arc<Config>>,
+    verification: Verification,
+    error: Option<String>,
+
+    config: Option<Arc<Config>>,
+    action_tx: Option<UnboundedSender<Action>>,
+}
+
+impl SecretPromptComponent {
+    fn show(&mut self) {
+        self.show = true;
+        self.input.reset();
+        self.pending = None;
+        self.error = None;
+    }
+
+    fn hide(&mut self) {
+        self.show = false;
+        self.input.reset();
+        self.pending = None;
+    }
+
+    fn verify(&mut self) -> Result<()> {
+        let secret = self.input.value();
+        anyhow::ensure!(!secret.is_empty(), "Secret cannot be empty");
+        let Some(config) = self.config.as_deref() else {
+            return Ok(());
+        };
+
+        let mut candidate = config.clone();
+        candidate.mihomo_secret = Some(secret.to_string());
+        let api = Api::new(&candidate)?.with_observer(api_observer());
+        self.pending = Some(Arc::new(candidate));
+        self.error = None;
+
+        let verification = Arc::clone(&self.verification);
+        TaskManager::spawn(ComponentId::SecretPrompt, "secret-verifier", async move {
+            let result = match api.get_version().await {
+                Ok(_) => Ok(()),
+                Err(e) if Api::is_unauthorized(&e) => {
+                    Err("Secret rejected by the controller".to_string())
+                }
+                Err(e) => Err(format!("{e:#}")),
+            };
+            *verification.lock().unwrap() = Some(result);
+        })?;
+        Ok(())
+    }
+
+    /// Applies a finished verification, returns `true` once the prompt can be closed.
+    fn poll_verification(&mut self) -> Result<bool> {
+        let Some(result) = self.verification.lock().unwrap().take() else {
+            return Ok(false);
+        };
+        let Some(config) = self.pending.take() else {
+            return Ok(false);
+        };
+        if let Err(e) = result {
+            warn!(error = e, "Entered secret was not accepted");
+            self.error = Some(e);
+            return Ok(false);
+        }
+
+        info!(save = self.save, "Entered secret accepted, reconnecting");
+        Timeline::record(EventKind::Core, "Connected with the entered secret");
+        let action_tx = self.action_tx.as_ref().unwrap();
+        if self.save
+            && let Some(secret) = config.mihomo_secret.clone()
+        {
+            action_tx.send(Action::SaveSecret(secret))?;
+        }
+        action_tx.send(Action::ApiReconnect(config))?;
+        Ok(true)
+    }
+
+    fn render_content(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+        let endpoint = self.config.as_ref().map(|c| c.mihomo_api.to_string()).unwrap_or_default();
+        let intro = Line::from(vec![
+            Span::raw("The controller "),
+            Span::styled(endpoint, Color::LightCyan),
+            Span::raw(" requires a secret."),
+        ]);
+        frame.render_widget(Paragraph::new(intro).wrap(Wrap { trim: true }), chunks[0]);
+
+        let block = Block::bordered()
+            .title("Secret")
+            .border_type(BorderType::Rounded)
+            .border_style(Color::Cyan);
+        let width = chunks[1].width.saturating_sub(2) as usize;
+        let cursor = self.input.cursor();
+        let scroll = (cursor + 1).saturating_sub(width);
+        let masked = "*".repeat(self.input.value().chars().count());
+        let paragraph = Paragraph::new(masked).scroll((0, scroll as u16)).block(block);
+        frame.render_widget(paragraph, chunks[1]);
+        let x = cursor - scroll + 1;
+        frame.set_cursor_position((chunks[1].x + x as u16, chunks[1].y + 1));
+
+        let checkbox = if self.save { "[x]" } else { "[ ]" };
+        let save = Line::from(vec![
+            Span::styled(checkbox, Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" save to config file (plain text)"),
+        ]);
+        frame.render_widget(Paragraph::new(save), chunks[2]);
+
+        let status = match (&self.error, self.pending.is_some()) {
+            (_, true) => Some(Span::styled("Verifying...", Color::Yellow)),
+            (Some(err), false) => Some(Span::styled(err.as_str(), Color::Red)),
+            (None, false) => None,
+        };
+        if let Some(status) = status {
+            frame.render_widget(Paragraph::new(Line::from(status)), chunks[3]);
+        }
+    }
+}
+
+impl Component for SecretPromptComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::SecretPrompt
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![Fragment::raw("connect "), Fragment::hl("↵")]),
+            Shortcut::new(vec![Fragment::raw("toggle save "), Fragment::hl("Tab")]),
+            Shortcut::new(vec![Fragment::raw("cancel "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Arc<Config>) -> Result<()> {
+        self.config = Some(config);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Esc => {
+                info!("Secret prompt cancelled");
+                self.hide();
+                return Ok(Some(Action::Unfocus));
+            }
+            KeyCode::Tab => self.save = !self.save,
+            KeyCode::Enter if self.pending.is_none() => {
+                if let Err(e) = self.verify() {
+                    self.error = Some(format!("{e:#}"));
+                }
+            }
+            _ if self.pending.is_none() => {
+                if let Some(req) = input_request(key) {
+                    self.error = None;
+                    let _ = self.input.handle(req);
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::SecretPrompt => self.show(),
+            Action::ConfigReloaded(config) => self.config = Some(config),
+            Action::Tick if self.show && self.poll_verification()? => {
+                self.hide();
+                return Ok(Some(Action::Unfocus));
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.show {
+            return Ok(());
+        }
+
+        let area = popup_area(area, 60, 40);
+        frame.render_widget(Clear, area);
+        let area = area.inner(Margin::new(2, 1));
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line("authentication", Style::default()));
+        let content_area = block.inner(area);
+        frame.render_widget(block, area);
+        self.render_content(frame, content_area.inner(Margin::new(1, 0)));
+
+        Ok(())
+    }
+}
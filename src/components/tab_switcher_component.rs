@@ -0,0 +1,247 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config as MatcherConfig, Matcher, Utf32Str};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Flex, Layout, Margin, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, List, ListItem, ListState, Paragraph};
+use tokio::sync::mpsc::UnboundedSender;
+use tui_input::Input;
+
+use crate::action::Action;
+use crate::components::{Component, ComponentId, TABS};
+use crate::utils::symbols::arrow;
+use crate::utils::text_ui::top_title_line;
+use crate::utils::tui_input::input_request;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+const POPUP_WIDTH: u16 = 40;
+
+#[derive(Default)]
+pub struct TabSwitcherComponent {
+    action_tx: Option<UnboundedSender<Action>>,
+
+    show: bool,
+    input: Input,
+    matched: Vec<ComponentId>,
+    list_state: ListState,
+}
+
+impl TabSwitcherComponent {
+    fn show(&mut self) {
+        self.show = true;
+        self.input.reset();
+        self.refresh_matches();
+    }
+
+    fn hide(&mut self) {
+        self.show = false;
+        self.input.reset();
+        self.matched.clear();
+    }
+
+    fn refresh_matches(&mut self) {
+        self.matched = match_tabs(self.input.value());
+        self.list_state.select((!self.matched.is_empty()).then_some(0));
+    }
+
+    fn select_next(&mut self) {
+        if self.matched.is_empty() {
+            return;
+        }
+        let next = self.list_state.selected().map_or(0, |i| (i + 1) % self.matched.len());
+        self.list_state.select(Some(next));
+    }
+
+    fn select_prev(&mut self) {
+        if self.matched.is_empty() {
+            return;
+        }
+        let len = self.matched.len();
+        let prev = self.list_state.selected().map_or(0, |i| (i + len - 1) % len);
+        self.list_state.select(Some(prev));
+    }
+
+    fn confirm(&mut self) -> Option<Action> {
+        let selected = self.list_state.selected().and_then(|i| self.matched.get(i)).copied();
+        self.hide();
+        if let Some(tab) = selected
+            && let Some(tx) = &self.action_tx
+        {
+            let _ = tx.send(Action::TabSwitch(tab));
+        }
+        Some(Action::Unfocus)
+    }
+}
+
+/// Returns tabs matching `query` (fuzzy, over both full and short names), best match first.
+///
+/// An empty query keeps all tabs in display order.
+fn match_tabs(query: &str) -> Vec<ComponentId> {
+    let query = query.trim();
+    if query.is_empty() {
+        return TABS.to_vec();
+    }
+
+    let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
+    let mut matcher = Matcher::new(MatcherConfig::DEFAULT);
+    let mut buf = Vec::new();
+    let mut scored = TABS
+        .iter()
+        .filter_map(|&tab| {
+            let haystack = format!("{} {}", tab.full_name(), tab.short_name().unwrap_or_default());
+            pattern.score(Utf32Str::new(&haystack, &mut buf), &mut matcher).map(|s| (tab, s))
+        })
+        .collect::<Vec<_>>();
+    // stable sort keeps display order for equal scores
+    scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    scored.into_iter().map(|(tab, _)| tab).collect()
+}
+
+impl Component for TabSwitcherComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::TabSwitcher
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![
+                Fragment::hl(arrow::UP),
+                Fragment::raw(" nav "),
+                Fragment::hl(arrow::DOWN),
+            ]),
+            Shortcut::new(vec![Fragment::raw("switch "), Fragment::hl("↵")]),
+            Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => {
+                self.hide();
+                return Ok(Some(Action::Unfocus));
+            }
+            (KeyCode::Enter, _) => return Ok(self.confirm()),
+            (KeyCode::Up, _) | (KeyCode::BackTab, _) => self.select_prev(),
+            (KeyCode::Char('p'), KeyModifiers::CONTROL) => self.select_prev(),
+            (KeyCode::Down, _) | (KeyCode::Tab, _) => self.select_next(),
+            (KeyCode::Char('n'), KeyModifiers::CONTROL) => self.select_next(),
+            _ => {
+                if let Some(req) = input_request(key)
+                    && self.input.handle(req).is_some_and(|s| s.value)
+                {
+                    self.refresh_matches();
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::Focus(ComponentId::TabSwitcher) = action {
+            self.show();
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.show {
+            return Ok(());
+        }
+
+        let [area] =
+            Layout::horizontal([Constraint::Length(POPUP_WIDTH)]).flex(Flex::Center).areas(area);
+        let [area] = Layout::vertical([Constraint::Length(TABS.len() as u16 + 5)])
+            .flex(Flex::Center)
+            .areas(area);
+        frame.render_widget(Clear, area);
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line("switch tab", Style::default()));
+        let inner = block.inner(area).inner(Margin::new(1, 0));
+        frame.render_widget(block, area);
+
+        let [input_area, list_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(inner);
+
+        // render input box
+        let input_width = input_area.width.saturating_sub(2) as usize;
+        let scroll = self.input.visual_scroll(input_width);
+        let input = Paragraph::new(self.input.value())
+            .scroll((0, scroll as u16))
+            .block(Block::bordered().border_type(BorderType::Rounded).border_style(Color::Cyan));
+        frame.render_widget(input, input_area);
+        let x = self.input.visual_cursor().max(scroll) - scroll + 1;
+        frame.set_cursor_position((input_area.x + x as u16, input_area.y + 1));
+
+        // render matched tabs
+        let items = self.matched.iter().map(|tab| {
+            let index = TABS.iter().position(|t| t == tab).map_or(0, |i| i + 1);
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{index} "), Color::DarkGray),
+                Span::raw(tab.full_name()),
+                Span::styled(
+                    format!(" ({})", tab.short_name().unwrap_or_default()),
+                    Color::DarkGray,
+                ),
+            ]))
+        });
+        let list = List::new(items)
+            .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, list_area, &mut self.list_state);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn empty_query_keeps_display_order() {
+        assert_eq!(match_tabs(""), TABS.to_vec());
+        assert_eq!(match_tabs("  "), TABS.to_vec());
+    }
+
+    #[test]
+    fn fuzzy_query_matches_full_and_short_names() {
+        assert_eq!(match_tabs("conn").first(), Some(&ComponentId::Connections));
+        assert_eq!(match_tabs("rprov").first(), Some(&ComponentId::RuleProviders));
+        assert_eq!(match_tabs("cfg"), vec![ComponentId::Config]);
+        assert!(match_tabs("zzz").is_empty());
+    }
+
+    #[test]
+    fn enter_switches_to_selected_tab() {
+        let mut component = TabSwitcherComponent::default();
+        let (tx, mut rx) = unbounded_channel();
+        component.register_action_handler(tx).unwrap();
+        component.update(Action::Focus(ComponentId::TabSwitcher)).unwrap();
+
+        for c in "logs".chars() {
+            component.handle_key_event(key(KeyCode::Char(c))).unwrap();
+        }
+        let action = component.handle_key_event(key(KeyCode::Enter)).unwrap();
+
+        assert!(matches!(action, Some(Action::Unfocus)));
+        assert!(matches!(rx.try_recv(), Ok(Action::TabSwitch(ComponentId::Logs))));
+        assert!(!component.show);
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::widgets::{Block, BorderType, Clear, Row, Table, TableState};
+
+use crate::action::Action;
+use crate::components::{Component, ComponentId};
+use crate::store::stream_health::{IngestRate, StreamHealth};
+use crate::task_manager::{TaskInfo, TaskManager};
+use crate::utils::byte_size::human_bytes;
+use crate::utils::symbols::arrow;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::widgets::scrollable_navigator::ScrollableNavigator;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+/// Debug popup listing background tasks registered in the [`TaskManager`] and the ingest rates
+/// of the websocket streams.
+#[derive(Debug, Default)]
+pub struct TasksComponent {
+    tasks: Vec<TaskInfo>,
+    streams: Vec<IngestRate>,
+    navigator: ScrollableNavigator,
+    table_state: TableState,
+}
+
+impl TasksComponent {
+    fn refresh(&mut self) {
+        self.tasks = TaskManager::snapshot();
+        self.streams = StreamHealth::ingest_rates();
+    }
+
+    fn cancel_focused(&mut self) {
+        if let Some(task) = self.navigator.focused.and_then(|i| self.tasks.get(i)) {
+            TaskManager::cancel(task.id);
+        }
+    }
+}
+
+fn format_elapsed(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3_599 => format!("{}m{}s", secs / 60, secs % 60),
+        _ => format!("{}h{}m", secs / 3_600, secs % 3_600 / 60),
+    }
+}
+
+impl Component for TasksComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::Tasks
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![
+                Fragment::hl(arrow::UP),
+                Fragment::raw(" nav "),
+                Fragment::hl(arrow::DOWN),
+            ]),
+            Shortcut::new(vec![Fragment::raw("cancel "), Fragment::hl("x")]),
+            Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.navigator.handle_key_event(false, key).is_consumed() {
+            return Ok(None);
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(Action::Unfocus)),
+            KeyCode::Char('x') | KeyCode::Delete => self.cancel_focused(),
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Focus(ComponentId::Tasks) => {
+                self.navigator = Default::default();
+                self.refresh();
+            }
+            Action::Tick => self.refresh(),
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let area = popup_area(area, 70, 70);
+        frame.render_widget(Clear, area); // clears out the background
+        let [area, streams_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(self.streams.len() as u16 + 4),
+        ])
+        .areas(area);
+        self.render_streams(frame, streams_area);
+        let title = format!("tasks ({})", self.tasks.len());
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line(&title, Style::default()));
+
+        let viewport_len = area.height.saturating_sub(4) as usize;
+        self.navigator.length(self.tasks.len(), viewport_len);
+        let records = self
+            .tasks
+            .get(self.navigator.scroller.pos()..self.navigator.scroller.end_pos())
+            .unwrap_or(&[]);
+        *self.table_state.selected_mut() =
+            self.navigator.focused.map(|v| v.saturating_sub(self.navigator.scroller.pos()));
+
+        let header = Row::new(["ID", "OWNER", "TASK", "ELAPSED"])
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .bottom_margin(1);
+        let rows = records.iter().map(|task| {
+            Row::new([
+                task.id.to_string(),
+                task.owner.full_name().to_owned(),
+                task.name.to_owned(),
+                format_elapsed(task.elapsed.as_secs()),
+            ])
+        });
+        let widths = [
+            Constraint::Length(6),
+            Constraint::Length(24),
+            Constraint::Fill(1),
+            Constraint::Length(8),
+        ];
+        let table = Table::new(rows, widths)
+            .block(block)
+            .header(header)
+            .column_spacing(2)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED).fg(Color::Cyan));
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+        self.navigator.render(frame, area);
+
+        Ok(())
+    }
+}
+
+impl TasksComponent {
+    fn render_streams(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line("streams", Style::default()));
+        let header = Row::new(["STREAM", "MSG/S", "RATE", "MSGS", "RECEIVED"])
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .bottom_margin(1);
+        let rows = self.streams.iter().map(|stream| {
+            Row::new([
+                stream.path.clone(),
+                format!("{:.1}", stream.msgs_per_sec),
+                human_bytes(stream.bytes_per_sec, Some("/s")),
+                stream.total_msgs.to_string(),
+                human_bytes(stream.total_bytes as f64, None),
+            ])
+        });
+        let widths = [
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(10),
+        ];
+        let table = Table::new(rows, widths).block(block).header(header).column_spacing(2);
+        frame.render_widget(table, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_elapsed_is_compact() {
+        assert_eq!(format_elapsed(5), "5s");
+        assert_eq!(format_elapsed(125), "2m5s");
+        assert_eq!(format_elapsed(3_725), "1h2m");
+    }
+}
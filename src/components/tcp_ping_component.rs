@@ -0,0 +1,399 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::prelude::{Color, Style};
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Clear, Padding, Paragraph, Wrap};
+use throbber_widgets_tui::{BRAILLE_SIX, Throbber, ThrobberState, WhichUse};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::oneshot;
+use tui_input::Input;
+
+use crate::action::Action;
+use crate::api::Api;
+use crate::components::{Component, ComponentId};
+use crate::store::proxy_setting::ProxySetting;
+use crate::task_manager::TaskManager;
+use crate::utils::tcp_ping::{self, PingStats};
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::tui_input::input_request;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+const FORM_HEIGHT: u16 = 3;
+/// Direct connection attempts per run.
+const ATTEMPTS: usize = 4;
+const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(3);
+const DEFAULT_VIA: &str = "GLOBAL";
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+enum FocusedField {
+    #[default]
+    Target,
+    Via,
+}
+
+impl FocusedField {
+    fn toggle(self) -> Self {
+        match self {
+            Self::Target => Self::Via,
+            Self::Via => Self::Target,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PingReport {
+    direct: Vec<std::result::Result<Duration, String>>,
+    /// HTTP delay tests of the target, if a proxy was given
+    http: Option<HttpReport>,
+}
+
+/// The same HTTP request to the target sent directly and through a proxy, the delay test of the
+/// core measures HTTP rather than TCP connect.
+#[derive(Debug)]
+struct HttpReport {
+    url: String,
+    direct: std::result::Result<Duration, String>,
+    proxy: String,
+    proxied: std::result::Result<u16, String>,
+}
+
+/// Network tools popup: TCP-ping a `host:port` directly from this machine, and compare an HTTP
+/// request to it sent directly with the delay test of a proxy, to tell local network issues from
+/// proxy issues.
+pub struct TcpPingComponent {
+    api: Option<Arc<Api>>,
+    action_tx: Option<UnboundedSender<Action>>,
+
+    focused: FocusedField,
+    target: Input,
+    via: Input,
+
+    error: Option<String>,
+    report: Option<PingReport>,
+    result_rx: Option<oneshot::Receiver<PingReport>>,
+
+    loading: Arc<AtomicBool>,
+    throbber: ThrobberState,
+}
+
+impl Default for TcpPingComponent {
+    fn default() -> Self {
+        Self {
+            api: None,
+            action_tx: None,
+            focused: FocusedField::default(),
+            target: Input::default(),
+            via: Input::new(DEFAULT_VIA.into()),
+            error: None,
+            report: None,
+            result_rx: None,
+            loading: Default::default(),
+            throbber: Default::default(),
+        }
+    }
+}
+
+impl TcpPingComponent {
+    fn run(&mut self) -> Result<()> {
+        if self.loading.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let (host, port) = match tcp_ping::parse_target(self.target.value()) {
+            Ok(target) => target,
+            Err(e) => {
+                self.error = Some(e.to_string());
+                return Ok(());
+            }
+        };
+        let Some(api) = self.api.as_ref().map(Arc::clone) else {
+            self.error = Some("API is not initialized".into());
+            return Ok(());
+        };
+        let via = Some(self.via.value().trim().to_owned()).filter(|v| !v.is_empty());
+        let setting = ProxySetting::global().read().unwrap().clone();
+
+        let (tx, rx) = oneshot::channel();
+        self.result_rx = Some(rx);
+        self.error = None;
+        self.report = None;
+        self.loading.store(true, Ordering::Relaxed);
+
+        TaskManager::spawn(ComponentId::TcpPing, "tcp-ping", async move {
+            let mut direct = Vec::with_capacity(ATTEMPTS);
+            for _ in 0..ATTEMPTS {
+                let result = tcp_ping::ping(&host, port, ATTEMPT_TIMEOUT).await;
+                direct.push(result.map_err(|e| format!("{e:#}")));
+            }
+            let http = match via {
+                Some(proxy) => {
+                    let scheme = if port == 443 { "https" } else { "http" };
+                    let url = format!("{scheme}://{}:{port}", bracket_v6(&host));
+                    let timeout = setting.test_timeout.get();
+                    let direct = tcp_ping::http_delay(&url, Duration::from_millis(timeout as u64))
+                        .await
+                        .map_err(|e| format!("{e:#}"));
+                    let proxied = api
+                        .test_proxy(proxy.as_str(), url.as_str(), timeout, "")
+                        .await
+                        .map_err(|e| format!("{e:#}"));
+                    Some(HttpReport { url, direct, proxy, proxied })
+                }
+                None => None,
+            };
+            let _ = tx.send(PingReport { direct, http });
+        })?;
+        Ok(())
+    }
+
+    fn poll_result(&mut self) {
+        let Some(rx) = &mut self.result_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(report) => self.report = Some(report),
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => {
+                self.error = Some("TCP ping task stopped".into());
+            }
+        }
+        self.result_rx = None;
+        self.loading.store(false, Ordering::Relaxed);
+    }
+
+    fn focused_input(&mut self) -> &mut Input {
+        match self.focused {
+            FocusedField::Target => &mut self.target,
+            FocusedField::Via => &mut self.via,
+        }
+    }
+
+    fn render_throbber(&mut self, frame: &mut Frame, area: Rect) {
+        if !self.loading.load(Ordering::Relaxed) {
+            return;
+        }
+        let symbol = Throbber::default()
+            .label("Pinging")
+            .style(Style::default().fg(Color::White).bg(Color::Green).bold())
+            .throbber_style(Style::default().fg(Color::White).bg(Color::Green).bold())
+            .throbber_set(BRAILLE_SIX)
+            .use_type(WhichUse::Spin);
+        frame.render_stateful_widget(
+            symbol,
+            Rect::new(area.right().saturating_sub(10), area.y, 9, 1),
+            &mut self.throbber,
+        );
+    }
+
+    fn render_input(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        field: FocusedField,
+        title: &str,
+        input: &Input,
+    ) {
+        let focused = self.focused == field;
+        let style = if focused { Style::default().fg(Color::Cyan) } else { Style::default() };
+        let width = area.width.saturating_sub(2) as usize;
+        let scroll = input.visual_scroll(width);
+        let widget = Paragraph::new(input.value()).scroll((0, scroll as u16)).block(
+            Block::bordered().border_type(BorderType::Rounded).border_style(style).title(title),
+        );
+        frame.render_widget(widget, area);
+        if focused {
+            let x = input.visual_cursor().max(scroll) - scroll + 1;
+            frame.set_cursor_position((area.x + x as u16, area.y + 1));
+        }
+    }
+
+    fn report_lines(&self) -> Vec<Line<'_>> {
+        if let Some(error) = &self.error {
+            return vec![Line::from(Span::styled(error.as_str(), Color::Red))];
+        }
+        let Some(report) = &self.report else {
+            return vec![Line::from(
+                "Enter a target and press ↵, leave `Via` empty to skip the HTTP tests".dark_gray(),
+            )];
+        };
+
+        let mut lines = vec![Line::from("direct (TCP connect from this machine)".bold())];
+        for (idx, result) in report.direct.iter().enumerate() {
+            let value = match result {
+                Ok(elapsed) => Span::styled(format_ms(*elapsed), Color::Green),
+                Err(e) => Span::styled(e.as_str(), Color::Red),
+            };
+            lines.push(Line::from(vec![Span::raw(format!("  #{} ", idx + 1)), value]));
+        }
+        let stats = PingStats::from_results(&report.direct);
+        lines.push(Line::from(match stats {
+            Some(s) => format!(
+                "  min/avg/max {}/{}/{}, loss {:.0}%",
+                format_ms(s.min),
+                format_ms(s.avg),
+                format_ms(s.max),
+                s.loss
+            ),
+            None => "  unreachable".into(),
+        }));
+
+        let Some(http) = &report.http else {
+            return lines;
+        };
+        lines.push(Line::default());
+        lines.push(Line::from(format!("HTTP HEAD {} (delay test)", http.url).bold()));
+        let delay_line = |label: String, result: std::result::Result<String, &str>| {
+            Line::from(vec![
+                Span::raw(format!("  {label} ")),
+                match result {
+                    Ok(delay) => Span::styled(delay, Color::Green),
+                    Err(e) => Span::styled(e.to_owned(), Color::Red),
+                },
+            ])
+        };
+        lines.push(delay_line(
+            "direct".into(),
+            http.direct.as_ref().map(|d| format_ms(*d)).map_err(String::as_str),
+        ));
+        lines.push(delay_line(
+            format!("via {}", http.proxy),
+            http.proxied.as_ref().map(|d| format!("{d}ms")).map_err(String::as_str),
+        ));
+
+        lines.push(Line::default());
+        let hint = match (http.direct.is_ok(), http.proxied.is_ok()) {
+            (true, false) => "target answers directly but not through the proxy: proxy issue",
+            (false, true) => "target answers through the proxy only: local network issue",
+            (false, false) => "target answers neither way: not HTTP, target or network issue",
+            (true, true) => "target answers both ways",
+        };
+        lines.push(Line::from(hint.yellow()));
+        lines
+    }
+}
+
+fn format_ms(d: Duration) -> String {
+    format!("{}ms", d.as_millis())
+}
+
+fn bracket_v6(host: &str) -> String {
+    if host.contains(':') { format!("[{host}]") } else { host.to_owned() }
+}
+
+impl Component for TcpPingComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::TcpPing
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![Fragment::hl("⇧⇤"), Fragment::raw(" focus "), Fragment::hl("⇥")]),
+            Shortcut::new(vec![Fragment::raw("ping "), Fragment::hl("↵")]),
+            Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn init(&mut self, api: Arc<Api>) -> Result<()> {
+        self.api = Some(api);
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Esc => return Ok(Some(Action::Unfocus)),
+            KeyCode::Tab | KeyCode::BackTab => self.focused = self.focused.toggle(),
+            KeyCode::Enter => self.run()?,
+            _ => {
+                if let Some(req) = input_request(key) {
+                    let _ = self.focused_input().handle(req);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::Tick = action {
+            self.poll_result();
+            if self.loading.load(Ordering::Relaxed) {
+                self.throbber.calc_next();
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let area = popup_area(area, 70, 70);
+        frame.render_widget(Clear, area); // clears out the background
+        let area = area.inner(Margin::new(2, 1));
+
+        let border = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line("tcp ping", Style::default()))
+            .padding(Padding::symmetric(2, 1));
+        let content_area = border.inner(area);
+        frame.render_widget(border, area);
+        self.render_throbber(frame, area);
+
+        let [form_area, report_area] =
+            Layout::vertical([Constraint::Length(FORM_HEIGHT), Constraint::Min(1)])
+                .spacing(1)
+                .areas(content_area);
+        let [target_area, via_area] =
+            Layout::horizontal([Constraint::Fill(2), Constraint::Fill(1)])
+                .spacing(2)
+                .areas(form_area);
+        self.render_input(
+            frame,
+            target_area,
+            FocusedField::Target,
+            " Target host:port ",
+            &self.target,
+        );
+        self.render_input(frame, via_area, FocusedField::Via, " Via proxy ", &self.via);
+
+        let report = Paragraph::new(self.report_lines()).wrap(Wrap { trim: false });
+        frame.render_widget(report, report_area);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn invalid_target_is_reported_without_running() {
+        let mut component = TcpPingComponent::default();
+        for c in "example.com".chars() {
+            component.handle_key_event(key(KeyCode::Char(c))).unwrap();
+        }
+        component.handle_key_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(component.error.as_deref(), Some("Expected `host:port`"));
+        assert!(component.result_rx.is_none());
+
+        component.handle_key_event(key(KeyCode::Tab)).unwrap();
+        component.handle_key_event(key(KeyCode::Backspace)).unwrap();
+        assert_eq!(component.via.value(), "GLOBA");
+        assert_eq!(component.target.value(), "example.com");
+    }
+}
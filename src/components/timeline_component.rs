@@ -0,0 +1,111 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::text::Span;
+use ratatui::widgets::{Block, BorderType, Cell, Clear, Row, Table, TableState};
+
+use crate::action::Action;
+use crate::components::{Component, ComponentId};
+use crate::store::timeline::{Timeline, TimelineEvent};
+use crate::utils::symbols::arrow;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::utils::time::{format_datetime, format_time_from_now};
+use crate::widgets::scrollable_navigator::ScrollableNavigator;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+/// Popup listing the [`Timeline`] of user actions and core events, newest first.
+#[derive(Debug, Default)]
+pub struct TimelineComponent {
+    events: Vec<TimelineEvent>,
+    navigator: ScrollableNavigator,
+    table_state: TableState,
+}
+
+impl Component for TimelineComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::Timeline
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![
+                Fragment::hl(arrow::UP),
+                Fragment::raw(" nav "),
+                Fragment::hl(arrow::DOWN),
+            ]),
+            Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.navigator.handle_key_event(false, key).is_consumed() {
+            return Ok(None);
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(Action::Unfocus)),
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Focus(ComponentId::Timeline) => {
+                self.navigator = Default::default();
+                self.events = Timeline::snapshot();
+            }
+            Action::Tick => self.events = Timeline::snapshot(),
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let area = popup_area(area, 80, 70);
+        frame.render_widget(Clear, area); // clears out the background
+        let title = format!("timeline ({})", self.events.len());
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line(&title, Style::default()));
+
+        let viewport_len = area.height.saturating_sub(4) as usize;
+        self.navigator.length(self.events.len(), viewport_len);
+        let records = self
+            .events
+            .get(self.navigator.scroller.pos()..self.navigator.scroller.end_pos())
+            .unwrap_or(&[]);
+        *self.table_state.selected_mut() =
+            self.navigator.focused.map(|v| v.saturating_sub(self.navigator.scroller.pos()));
+
+        let header = Row::new(["AGO", "TIME (UTC)", "KIND", "EVENT"])
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .bottom_margin(1);
+        let rows = records.iter().map(|event| {
+            let kind: &'static str = event.kind.into();
+            Row::new([
+                Cell::from(format_time_from_now(event.time)),
+                Cell::from(format_datetime(event.time).unwrap_or_default().into_string()),
+                Cell::from(Span::styled(kind, event.kind.color())),
+                Cell::from(event.message.as_str()),
+            ])
+        });
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Length(19),
+            Constraint::Length(8),
+            Constraint::Fill(1),
+        ];
+        let table = Table::new(rows, widths)
+            .block(block)
+            .header(header)
+            .column_spacing(2)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED).fg(Color::Cyan));
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+        self.navigator.render(frame, area);
+
+        Ok(())
+    }
+}
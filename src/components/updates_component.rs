@@ -16,6 +16,8 @@ use crate::action::Action;
 use crate::api::Api;
 use crate::app_message::AppMessage;
 use crate::config::Config;
+use crate::store::timeline::{EventKind, Timeline};
+use crate::task_manager::TaskManager;
 use crate::utils::symbols::arrow;
 use crate::utils::text_ui::{popup_area, top_title_line};
 use crate::version_update::{SharedVersionUpdateState, VersionStatus, VersionUpdateState};
@@ -83,7 +85,7 @@ impl UpdatesComponent {
         };
         debug!("refresh versions");
         let update_state = self.update_state.clone();
-        tokio::task::Builder::new().name("app-version-refresher").spawn(async move {
+        TaskManager::spawn(ComponentId::Updates, "app-version-refresher", async move {
             if let Err(e) = update_state.refresh(&api, &mihomo_repo).await {
                 warn!(error = ?e, "Failed to refresh update status");
             }
@@ -109,10 +111,11 @@ impl UpdatesComponent {
             state.core = VersionStatus::Refreshing;
         }
 
-        tokio::task::Builder::new().name("mihomo-core-upgrader").spawn(async move {
+        TaskManager::spawn(ComponentId::Updates, "mihomo-core-upgrader", async move {
             match api.upgrade_core().await {
                 Ok(()) => {
                     info!("Mihomo core upgrade requested successfully");
+                    Timeline::record(EventKind::Core, "Core upgrade requested");
                     let _ = action_tx.send(Action::Info(
                         AppMessage::from((
                             "Update mihomo core",
@@ -143,6 +146,7 @@ impl UpdatesComponent {
                         warn!(previous_version, "Timed out waiting for mihomo core upgrade");
                         update_state.lock().core = previous;
                         let _ = action_tx.send(Action::Error(
+                            ComponentId::Updates,
                             (
                                 "Update mihomo core",
                                 "Timed out waiting for mihomo core to restart with a new version",
@@ -154,7 +158,10 @@ impl UpdatesComponent {
                 Err(e) => {
                     warn!(error = ?e, "Failed to upgrade mihomo core");
                     update_state.lock().core = previous;
-                    let _ = action_tx.send(Action::Error(("Update mihomo core", e).into()));
+                    let _ = action_tx.send(Action::Error(
+                        ComponentId::Updates,
+                        ("Update mihomo core", e).into(),
+                    ));
                 }
             }
         })?;
@@ -162,6 +169,16 @@ impl UpdatesComponent {
         Ok(())
     }
 
+    fn release_page_url(&self) -> Option<String> {
+        match self.selected {
+            UpdateTarget::App => Some(format!("{}/releases", env!("CARGO_PKG_REPOSITORY"))),
+            UpdateTarget::Core => self
+                .config
+                .as_ref()
+                .map(|c| format!("https://github.com/{}/releases", c.mihomo_repo)),
+        }
+    }
+
     fn trigger_selected(&mut self) -> Result<Option<Action>> {
         let selected_status = {
             let guard = self.update_state.lock();
@@ -220,6 +237,7 @@ impl Component for UpdatesComponent {
             Shortcut::new(vec![Fragment::raw("toggle "), Fragment::hl("Space")]),
             Shortcut::new(vec![Fragment::raw("update "), Fragment::hl("↵")]),
             Shortcut::from("refresh", 0).unwrap(),
+            Shortcut::from("open releases", 0).unwrap(),
         ]
     }
 
@@ -244,6 +262,7 @@ impl Component for UpdatesComponent {
             KeyCode::Tab | KeyCode::BackTab => self.selected = self.selected.next(),
             KeyCode::Char(' ') => self.toggle_auto_restart(),
             KeyCode::Char('r') => self.refresh_versions()?,
+            KeyCode::Char('o') => return Ok(self.release_page_url().map(Action::OpenUrl)),
             KeyCode::Enter => return self.trigger_selected(),
             _ => (),
         }
@@ -255,6 +274,7 @@ impl Component for UpdatesComponent {
         match action {
             Action::AppUpdateRequest => self.show(),
             Action::RefreshVersion => self.refresh_versions()?,
+            Action::ConfigReloaded(config) => self.config = Some(config),
             _ => (),
         }
 
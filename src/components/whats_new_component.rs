@@ -0,0 +1,144 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Margin, Rect};
+use ratatui::prelude::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Clear, Padding, Paragraph, Wrap};
+
+use crate::action::Action;
+use crate::components::{Component, ComponentId};
+use crate::utils::symbols::arrow;
+use crate::utils::text_ui::{popup_area, top_title_line};
+use crate::widgets::scrollbar::Scroller;
+use crate::widgets::shortcut::{Fragment, Shortcut};
+
+/// Release notes embedded at build time.
+const CHANGELOG: &str = include_str!("../../CHANGELOG.md");
+
+/// Popup shown once after an upgrade, with the release notes of the versions since the last run.
+#[derive(Debug, Default)]
+pub struct WhatsNewComponent {
+    notes: String,
+    scroller: Scroller,
+}
+
+/// Sections of `changelog` above the one of version `last`, i.e. the newer releases. Empty if
+/// `last` is the newest release or not listed, e.g. after a downgrade.
+fn notes_since(changelog: &str, last: &str) -> String {
+    let mut notes = Vec::new();
+    for line in changelog.lines() {
+        if let Some(heading) = line.strip_prefix("## ")
+            && heading.split_whitespace().next() == Some(last)
+        {
+            return notes.join("\n").trim().to_owned();
+        }
+        notes.push(line);
+    }
+    String::new()
+}
+
+/// Whether the embedded changelog has notes of releases newer than `last`.
+pub fn has_notes_since(last: &str) -> bool {
+    !notes_since(CHANGELOG, last).is_empty()
+}
+
+/// Rows taken by `lines` wrapped to `width` columns.
+fn wrapped_height(lines: &[Line], width: usize) -> usize {
+    lines.iter().map(|line| line.width().div_ceil(width.max(1)).max(1)).sum()
+}
+
+impl Component for WhatsNewComponent {
+    fn id(&self) -> ComponentId {
+        ComponentId::WhatsNew
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![
+            Shortcut::new(vec![
+                Fragment::hl(arrow::UP),
+                Fragment::raw(" scroll "),
+                Fragment::hl(arrow::DOWN),
+            ]),
+            Shortcut::new(vec![Fragment::raw("close "), Fragment::hl("Esc")]),
+        ]
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.scroller.handle_key_event(key).is_consumed() {
+            return Ok(None);
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => Ok(Some(Action::Unfocus)),
+            _ => Ok(None),
+        }
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Action::WhatsNew(last) = action {
+            self.notes = notes_since(CHANGELOG, &last);
+            self.scroller.first();
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let area = popup_area(area, 70, 70);
+        frame.render_widget(Clear, area); // clears out the background
+        let title = concat!("what's new in ", env!("CARGO_PKG_VERSION"));
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::LightBlue)
+            .title(top_title_line(title, Style::default()))
+            .padding(Padding::horizontal(1));
+
+        let lines: Vec<_> = self
+            .notes
+            .lines()
+            .map(|line| match line {
+                _ if line.starts_with("## ") => Line::from(line).bold().light_cyan(),
+                _ if line.starts_with("### ") => Line::from(line).bold(),
+                _ => Line::from(line),
+            })
+            .collect();
+        let inner = block.inner(area);
+        self.scroller.length(wrapped_height(&lines, inner.width as usize), inner.height as usize);
+        let paragraph = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroller.pos() as u16, 0))
+            .block(block);
+        frame.render_widget(paragraph, area);
+        self.scroller.render(frame, area.inner(Margin::new(0, 1)));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTES: &str = "\
+## 0.5.0 - 2026-09-01
+
+### Features
+
+- New
+
+## 0.4.5 - 2026-07-19
+
+- Old
+";
+
+    #[test]
+    fn notes_of_newer_releases_only() {
+        assert_eq!(notes_since(NOTES, "0.4.5"), "## 0.5.0 - 2026-09-01\n\n### Features\n\n- New");
+        assert_eq!(notes_since(NOTES, "0.5.0"), "");
+        assert_eq!(notes_since(NOTES, "0.3.0"), "");
+    }
+
+    #[test]
+    fn embedded_changelog_lists_releases() {
+        assert!(CHANGELOG.lines().any(|line| line.starts_with("## ")));
+    }
+}
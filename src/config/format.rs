@@ -0,0 +1,67 @@
+use std::path::Path;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// File names looked up in the config directory, the first existing one is used.
+pub const CONFIG_FILE_NAMES: &[&str] = &["config.yaml", "config.yml", "config.toml", "config.json"];
+
+/// Format of the app config file and its runtime sidecar, decided by the file extension. Every
+/// format maps to the same [`Config`](super::Config), keys are kebab-case in all of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// `.yaml`, `.yml` or any other extension
+    #[default]
+    Yaml,
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    pub fn from_path(path: &Path) -> Self {
+        let ext = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+        match ext.to_ascii_lowercase().as_str() {
+            "toml" => Self::Toml,
+            "json" => Self::Json,
+            _ => Self::Yaml,
+        }
+    }
+
+    pub fn parse<T: DeserializeOwned>(self, raw: &str) -> anyhow::Result<T> {
+        match self {
+            Self::Yaml => Ok(yaml_serde::from_str(raw)?),
+            Self::Toml => Ok(toml::from_str(raw)?),
+            Self::Json => Ok(serde_json::from_str(raw)?),
+        }
+    }
+
+    pub fn serialize<T: Serialize>(self, value: &T) -> anyhow::Result<String> {
+        match self {
+            Self::Yaml => Ok(yaml_serde::to_string(value)?),
+            Self::Toml => Ok(toml::to_string(value)?),
+            Self::Json => {
+                let mut raw = serde_json::to_string_pretty(value)?;
+                raw.push('\n');
+                Ok(raw)
+            }
+        }
+    }
+
+    /// `value` as a string scalar of a YAML or TOML document, JSON documents are serialized
+    /// whole by [`Self::serialize`] instead.
+    pub fn string_scalar(self, value: &str) -> anyhow::Result<String> {
+        match self {
+            Self::Yaml => Ok(yaml_serde::to_string(value)?.trim_end().to_owned()),
+            Self::Toml => Ok(toml::Value::String(value.to_owned()).to_string()),
+            Self::Json => Ok(serde_json::to_string(value)?),
+        }
+    }
+
+    /// Text between a top level key and its value, e.g. `:` in `key: value`.
+    pub fn key_separator(self) -> &'static str {
+        match self {
+            Self::Yaml | Self::Json => ":",
+            Self::Toml => " =",
+        }
+    }
+}
@@ -1,21 +1,29 @@
 mod deserialize;
+mod format;
 pub mod runtime;
 mod schema;
 #[cfg(test)]
 mod tests;
 pub mod validate;
+pub mod watcher;
 
 use std::ops::Deref;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 use std::{env, fs};
 
 use anyhow::{Context, anyhow};
 use directories::ProjectDirs;
+pub use format::ConfigFormat;
 pub use schema::*;
 use tracing::info;
 
-static DEFAULT_CONFIG: &str = include_str!("../../.config/config.yaml");
+use crate::utils::encryption::Encryption;
+
+/// Default app config with every option documented, written on first start.
+pub static DEFAULT_CONFIG: &str = include_str!("../../.config/config.yaml");
+/// Schema of the core config JSON, used when `mihomo-config-schema` is not set.
+pub static DEFAULT_CORE_CONFIG_SCHEMA: &str = include_str!("../../.config/core-config.schema.json");
 pub static PROJECT_NAME: LazyLock<&'static str> = LazyLock::new(|| {
     let s = env!("CARGO_CRATE_NAME").replace('-', "_").to_ascii_uppercase();
     Box::leak(s.into_boxed_str())
@@ -38,6 +46,8 @@ impl Deref for LoadedConfig {
 
 impl LoadedConfig {
     pub fn try_apply_runtime(&mut self) {
+        // the sidecar is encrypted like the other state files
+        Encryption::configure(&self.config.encryption);
         runtime::try_load_and_apply(&mut self.config, &self.runtime_path);
     }
 }
@@ -71,19 +81,80 @@ pub fn load(path: Option<PathBuf>) -> anyhow::Result<LoadedConfig> {
     Ok(LoadedConfig { config, config_path, runtime_path })
 }
 
+/// Re-read the config file and overlay the runtime sidecar, same as on startup.
+pub fn reload(config_path: &Path, runtime_path: &Path) -> anyhow::Result<Config> {
+    let mut config = read_from_file(config_path)?;
+    if let Some(parent) = config_path.parent() {
+        config.mihomo_api.resolve_relative_to(parent);
+    }
+    Encryption::configure(&config.encryption);
+    runtime::try_load_and_apply(&mut config, runtime_path);
+    Ok(config)
+}
+
 pub(crate) fn default_config() -> anyhow::Result<Config> {
     let default_config: Config = yaml_serde::from_str(DEFAULT_CONFIG)?;
     default_config.validate()?;
     Ok(default_config)
 }
 
-fn read_from_file(path: &PathBuf) -> anyhow::Result<Config> {
+/// Write `mihomo-secret` into the config file, in place for YAML and TOML to keep comments and
+/// layout.
+pub fn save_secret(path: &Path, secret: &str) -> anyhow::Result<()> {
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("Fail to read file `{}`", path.display()))?;
+    let format = ConfigFormat::from_path(path);
+    let content = match format {
+        ConfigFormat::Json => {
+            let mut doc: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&raw)
+                .with_context(|| format!("Fail to deserialize file `{}`", path.display()))?;
+            doc.insert("mihomo-secret".to_owned(), secret.into());
+            format.serialize(&doc)?
+        }
+        _ => {
+            let value = format.string_scalar(secret).context("Fail to serialize secret")?;
+            set_secret_line(&raw, format, &value)
+        }
+    };
+    fs::write(path, content).with_context(|| format!("Fail to write file `{}`", path.display()))
+}
+
+/// Replaces the top level `mihomo-secret` line, a commented out one is used as the placeholder,
+/// otherwise the line is inserted after `mihomo-api`.
+fn set_secret_line(raw: &str, format: ConfigFormat, value: &str) -> String {
+    let separator = format.key_separator();
+    let line = format!("mihomo-secret{separator} {value}");
+    let mut lines: Vec<&str> = raw.lines().collect();
+    let position = |key: &str| {
+        lines.iter().position(|l| {
+            l.strip_prefix(key).is_some_and(|rest| rest.trim_start().starts_with(separator.trim()))
+        })
+    };
+    match position("mihomo-secret").or_else(|| position("#mihomo-secret")) {
+        Some(idx) => lines[idx] = &line,
+        None => {
+            let idx = match position("mihomo-api") {
+                Some(idx) => idx + 1,
+                // keys after the first table header of a TOML file belong to that table
+                None if format == ConfigFormat::Toml => 0,
+                None => lines.len(),
+            };
+            lines.insert(idx, &line);
+        }
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+fn read_from_file(path: &Path) -> anyhow::Result<Config> {
     if !path.is_file() {
         return Err(anyhow!("Config file `{}` does not exist", path.display()));
     }
-    let result =
-        fs::File::open(path).with_context(|| format!("Fail to open file `{}`", path.display()))?;
-    let cfg: Config = yaml_serde::from_reader(result)
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("Fail to read file `{}`", path.display()))?;
+    let cfg: Config = ConfigFormat::from_path(path)
+        .parse(&raw)
         .with_context(|| format!("Fail to deserialize file `{}`", path.display()))?;
     cfg.validate().with_context(|| format!("Invalid config file `{}`", path.display()))?;
     Ok(cfg)
@@ -98,7 +169,16 @@ pub fn get_config_path() -> PathBuf {
             .unwrap();
     }
 
-    dir.join("config.yaml")
+    find_config_file(&dir)
+}
+
+/// The first existing config file of `dir` in any format, `config.yaml` if there is none.
+fn find_config_file(dir: &Path) -> PathBuf {
+    format::CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| dir.join(format::CONFIG_FILE_NAMES[0]))
 }
 
 #[cfg(test)]
@@ -1,12 +1,17 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
-use tracing::error;
+use tracing::{error, warn};
 
-use crate::config::{Config, ConnectionsUiConfig, ProxySetting, UiConfig};
+use crate::config::{
+    ChartSetting, Config, ConfigFormat, ConnectionsUiConfig, LayoutSetting, ProxySetting, UiConfig,
+};
 use crate::store::connections_setting::ConnectionsSetting;
+use crate::store::filter_history::FilterHistory;
+use crate::utils::encryption::Encryption;
 
 const SCHEMA_VERSION: u16 = 1;
 
@@ -17,18 +22,46 @@ pub struct RuntimeConfig {
     schema_version: u16,
     ui: Option<UiConfig>,
     proxy_setting: Option<ProxySetting>,
+    /// App version that last ran, to show the release notes once after an upgrade
+    last_version: Option<String>,
+}
+
+/// Settings changed at runtime, persisted to the sidecar by [`save`].
+#[derive(Clone)]
+pub struct RuntimeSettings {
+    pub connections: Arc<ConnectionsSetting>,
+    pub layout: LayoutSetting,
+    pub chart: ChartSetting,
+    pub filter_history: FilterHistory,
+    pub proxy_setting: ProxySetting,
+}
+
+impl RuntimeSettings {
+    /// The current settings of the stores.
+    pub fn snapshot() -> Self {
+        Self {
+            connections: ConnectionsSetting::snapshot(),
+            layout: *LayoutSetting::global().read().unwrap(),
+            chart: *ChartSetting::global().read().unwrap(),
+            filter_history: FilterHistory::global().read().unwrap().clone(),
+            proxy_setting: ProxySetting::global().read().unwrap().clone(),
+        }
+    }
 }
 
 impl RuntimeConfig {
-    fn new(connections: &ConnectionsSetting, proxy_setting: &ProxySetting) -> Result<Self> {
+    fn new(settings: &RuntimeSettings) -> Result<Self> {
         Ok(Self {
             schema_version: SCHEMA_VERSION,
             ui: Some(UiConfig {
-                connections: Some(ConnectionsUiConfig::try_from(connections)?),
-                proxy_detail: None,
-                proxy_provider_detail: None,
+                connections: Some(ConnectionsUiConfig::try_from(settings.connections.as_ref())?),
+                layout: Some(settings.layout),
+                chart: Some(settings.chart),
+                filter_history: settings.filter_history.to_config(),
+                ..Default::default()
             }),
-            proxy_setting: Some(proxy_setting.clone()),
+            proxy_setting: Some(settings.proxy_setting.clone()),
+            last_version: Some(env!("CARGO_PKG_VERSION").to_owned()),
         })
     }
 }
@@ -64,7 +97,7 @@ pub fn try_load_and_apply(config: &mut Config, runtime_path: &Path) {
     }
 }
 
-fn load_and_apply(config: &mut Config, runtime_path: &Path) -> Result<()> {
+pub fn load_and_apply(config: &mut Config, runtime_path: &Path) -> Result<()> {
     let Some(runtime) = load(runtime_path)? else {
         return Ok(());
     };
@@ -81,11 +114,12 @@ pub fn load(runtime_path: &Path) -> Result<Option<RuntimeConfig>> {
         return Ok(None);
     }
 
-    let raw = fs::read_to_string(runtime_path)
+    let raw = Encryption::read_to_string(runtime_path)
         .with_context(|| format!("Fail to read runtime config `{}`", runtime_path.display()))?;
-    let runtime: RuntimeConfig = yaml_serde::from_str(&raw).with_context(|| {
-        format!("Fail to deserialize runtime config `{}`", runtime_path.display())
-    })?;
+    let runtime: RuntimeConfig =
+        ConfigFormat::from_path(runtime_path).parse(&raw).with_context(|| {
+            format!("Fail to deserialize runtime config `{}`", runtime_path.display())
+        })?;
     Ok(Some(runtime))
 }
 
@@ -98,15 +132,30 @@ fn apply(config: &mut Config, runtime: RuntimeConfig) -> Result<()> {
         );
     }
 
-    if let Some(runtime_connections) = runtime.ui.and_then(|ui| ui.connections)
+    let (runtime_connections, runtime_layout, runtime_chart, runtime_filter_history) = runtime
+        .ui
+        .map(|ui| (ui.connections, ui.layout, ui.chart, ui.filter_history))
+        .unwrap_or_default();
+    if let Some(runtime_connections) = runtime_connections
         && !is_empty_connections(&runtime_connections)
     {
-        let ui = config.ui.get_or_insert(UiConfig {
-            connections: None,
-            proxy_detail: None,
-            proxy_provider_detail: None,
-        });
-        ui.connections = Some(runtime_connections);
+        // e.g. a custom column was removed from the config file since
+        let custom = config.ui.as_ref().map(|ui| ui.custom_columns.as_slice()).unwrap_or_default();
+        match runtime_connections.validate(custom) {
+            Ok(()) => ui_mut(config).connections = Some(runtime_connections),
+            Err(e) => warn!(error = ?e, "Ignoring runtime connections setting"),
+        }
+    }
+    if let Some(runtime_layout) = runtime_layout {
+        ui_mut(config).layout = Some(runtime_layout);
+    }
+    if let Some(runtime_chart) = runtime_chart {
+        ui_mut(config).chart = Some(runtime_chart);
+    }
+    // only the entries are runtime state, whether to persist them is up to the config file
+    if let Some(runtime_filter_history) = runtime_filter_history {
+        ui_mut(config).filter_history.get_or_insert_default().entries =
+            runtime_filter_history.entries;
     }
 
     if let Some(runtime_proxy) = runtime.proxy_setting {
@@ -116,19 +165,42 @@ fn apply(config: &mut Config, runtime: RuntimeConfig) -> Result<()> {
     Ok(())
 }
 
-pub fn save(
-    runtime_path: &Path,
-    connections: &ConnectionsSetting,
-    proxy_setting: &ProxySetting,
-) -> Result<()> {
+fn ui_mut(config: &mut Config) -> &mut UiConfig {
+    config.ui.get_or_insert_with(Default::default)
+}
+
+/// Record the running app version, returns the one that last ran. `None` on a fresh install or
+/// with a sidecar written before versions were recorded.
+pub fn swap_last_version(runtime_path: &Path) -> Result<Option<String>> {
+    let mut runtime = load(runtime_path)?.unwrap_or(RuntimeConfig {
+        schema_version: SCHEMA_VERSION,
+        ui: None,
+        proxy_setting: None,
+        last_version: None,
+    });
+    let current = env!("CARGO_PKG_VERSION");
+    let last = runtime.last_version.replace(current.to_owned());
+    if last.as_deref() != Some(current) {
+        write(runtime_path, &runtime)?;
+    }
+    Ok(last)
+}
+
+pub fn save(runtime_path: &Path, settings: &RuntimeSettings) -> Result<()> {
+    let runtime = RuntimeConfig::new(settings)?;
+    write(runtime_path, &runtime)
+}
+
+fn write(runtime_path: &Path, runtime: &RuntimeConfig) -> Result<()> {
     if let Some(parent) = runtime_path.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("Fail to create directory `{}`", parent.display()))?;
     }
 
-    let runtime = RuntimeConfig::new(connections, proxy_setting)?;
-    let raw = yaml_serde::to_string(&runtime).context("Fail to serialize runtime config")?;
-    fs::write(runtime_path, raw)
+    let raw = ConfigFormat::from_path(runtime_path)
+        .serialize(runtime)
+        .context("Fail to serialize runtime config")?;
+    Encryption::write(runtime_path, raw.as_bytes())
         .with_context(|| format!("Fail to write runtime config `{}`", runtime_path.display()))?;
     Ok(())
 }
@@ -169,14 +241,27 @@ mod tests {
             columns: DEFAULT_CONNECTION_COL_INDICES.to_vec(),
             column_widths: HashMap::from([(1, 24)]),
             source_ip_alias: HashMap::from([("192.168.1.10".into(), "phone".into())]),
+            network: None,
         };
         let proxy = ProxySetting {
             test_url: "https://example.com/generate_204".into(),
             test_timeout: NonZeroUsize::new(3000).unwrap(),
+            test_expected_status: "204".into(),
             latency_threshold: LatencyThreshold { medium: 200, high: 800 },
             auto_terminate_connections: true,
+            test_batch_size: 20,
+            test_batch_delay: 1000,
+        };
+        let layout = LayoutSetting { overview_traffic_width: 65 };
+        let chart = ChartSetting { smoothing: 0.5, peak_markers: false };
+        let settings = RuntimeSettings {
+            connections: Arc::new(setting),
+            layout,
+            chart,
+            filter_history: FilterHistory::default(),
+            proxy_setting: proxy,
         };
-        let runtime = RuntimeConfig::new(&setting, &proxy).unwrap();
+        let runtime = RuntimeConfig::new(&settings).unwrap();
         let raw = yaml_serde::to_string(&runtime).unwrap();
 
         assert!(raw.contains("$schema-version: 1"));
@@ -189,6 +274,11 @@ mod tests {
         assert!(raw.contains("Host: 24"));
         assert!(raw.contains("test-url: https://example.com/generate_204"));
         assert!(raw.contains("latency-threshold: 200,800"));
+        assert!(raw.contains("test-expected-status: '204'"));
+        assert!(raw.contains("test-batch-size: 20"));
+        assert!(raw.contains("overview-traffic-width: 65"));
+        assert!(raw.contains("smoothing: 0.5"));
+        assert!(raw.contains("peak-markers: false"));
     }
 
     #[test]
@@ -199,10 +289,17 @@ mod tests {
             columns: DEFAULT_CONNECTION_COL_INDICES.to_vec(),
             column_widths: HashMap::new(),
             source_ip_alias: HashMap::new(),
+            network: None,
+        };
+        let settings = RuntimeSettings {
+            connections: Arc::new(setting),
+            layout: LayoutSetting::default(),
+            chart: ChartSetting::default(),
+            filter_history: FilterHistory::default(),
+            proxy_setting: ProxySetting::default(),
         };
-        let proxy = ProxySetting::default();
 
-        save(&runtime_path, &setting, &proxy).unwrap();
+        save(&runtime_path, &settings).unwrap();
         let raw = fs::read_to_string(&runtime_path).unwrap();
         fs::remove_file(&runtime_path).unwrap();
 
@@ -210,6 +307,39 @@ mod tests {
         assert!(raw.contains("proxy-setting:"));
     }
 
+    #[test]
+    fn sidecar_follows_the_config_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let setting = ConnectionsSetting {
+            query_state: QueryState::new(DEFAULT_CONNECTION_COL_INDICES.len()),
+            columns: DEFAULT_CONNECTION_COL_INDICES.to_vec(),
+            column_widths: HashMap::from([(1, 24)]),
+            source_ip_alias: HashMap::from([("192.168.1.10".into(), "phone".into())]),
+            network: None,
+        };
+        let layout = LayoutSetting { overview_traffic_width: 65 };
+        let settings = RuntimeSettings {
+            connections: Arc::new(setting),
+            layout,
+            chart: ChartSetting::default(),
+            filter_history: FilterHistory::default(),
+            proxy_setting: ProxySetting::default(),
+        };
+        for ext in ["toml", "json"] {
+            let runtime_path = runtime_path_for(&dir.path().join(format!("config.{ext}")));
+            save(&runtime_path, &settings).unwrap();
+            let raw = fs::read_to_string(&runtime_path).unwrap();
+            assert!(raw.contains("overview-traffic-width"), "{ext}: {raw}");
+
+            let mut config = crate::config::default_config().unwrap();
+            load_and_apply(&mut config, &runtime_path).unwrap();
+            let ui = config.ui.unwrap();
+            assert_eq!(ui.layout, Some(layout), "{ext}");
+            let alias = ui.connections.unwrap().source_ip_alias;
+            assert_eq!(alias.get("192.168.1.10").map(String::as_str), Some("phone"), "{ext}");
+        }
+    }
+
     #[test]
     fn widths_only_connections_are_not_empty() {
         let connections = ConnectionsUiConfig {
@@ -225,10 +355,25 @@ mod tests {
     #[test]
     fn apply_rejects_unknown_schema_version() {
         let mut config = crate::config::default_config().unwrap();
-        let err =
-            apply(&mut config, RuntimeConfig { schema_version: 2, ui: None, proxy_setting: None })
-                .unwrap_err();
+        let runtime =
+            RuntimeConfig { schema_version: 2, ui: None, proxy_setting: None, last_version: None };
+        let err = apply(&mut config, runtime).unwrap_err();
 
         assert!(err.to_string().contains("Unsupported runtime config schema version"));
     }
+
+    #[test]
+    fn last_version_is_swapped_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.runtime.yaml");
+        assert_eq!(swap_last_version(&path).unwrap(), None);
+        let current = env!("CARGO_PKG_VERSION");
+        assert_eq!(swap_last_version(&path).unwrap().as_deref(), Some(current));
+
+        let mut runtime = load(&path).unwrap().unwrap();
+        runtime.last_version = Some("0.0.1".into());
+        write(&path, &runtime).unwrap();
+        assert_eq!(swap_last_version(&path).unwrap().as_deref(), Some("0.0.1"));
+        assert_eq!(load(&path).unwrap().unwrap().last_version.as_deref(), Some(current));
+    }
 }
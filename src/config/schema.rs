@@ -6,6 +6,9 @@ use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::models::sort::{ProxySortField, SortDir};
+use crate::utils::collation::Collation;
+use crate::utils::number::ThousandsSeparator;
+use crate::widgets::table_style::TableStyle;
 
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -16,6 +19,10 @@ pub struct Config {
     #[serde(default = "default_mihomo_repo")]
     pub mihomo_repo: String,
 
+    /// Command used to open URLs in the system browser, empty string disables it.
+    /// Defaults to `xdg-open`/`open`/`explorer`, disabled when no graphical session is detected.
+    pub open_url_command: Option<String>,
+
     pub log_file: Option<String>,
 
     /// Log filtering directives compatible with `tracing_subscriber::EnvFilter`.
@@ -33,6 +40,174 @@ pub struct Config {
 
     #[serde(default)]
     pub buffer: BufferConfig,
+
+    /// Alert conditions evaluated against live connections.
+    #[serde(default)]
+    pub alerts: Vec<AlertConfig>,
+
+    /// Commands run when a live connection matches a condition.
+    #[serde(default)]
+    pub hooks: Vec<HookConfig>,
+
+    /// Flags source IPs opening connections in bursts.
+    #[serde(default)]
+    pub burst: BurstConfig,
+
+    /// Alerts on sustained memory growth of the core.
+    #[serde(default)]
+    pub memory_trend: MemoryTrendConfig,
+
+    /// Traffic, memory and connection count history persisted across restarts.
+    #[serde(default)]
+    pub history: HistoryConfig,
+
+    /// Exit IP lookups of proxy nodes.
+    #[serde(default)]
+    pub exit_ip: ExitIpConfig,
+
+    /// Local GeoIP database checked against the country of `GEOIP` rules.
+    #[serde(default)]
+    pub geoip: GeoIpConfig,
+
+    /// Friendly labels keyed by host or IP, displayed alongside the raw values.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+
+    /// Redaction applied to exports and the log file.
+    #[serde(default)]
+    pub redact: RedactConfig,
+
+    /// Encryption at rest of captures, core config exports, profiles, the runtime state file and
+    /// the traffic history.
+    #[serde(default)]
+    pub encryption: EncryptionConfig,
+
+    /// What slows down while the terminal window is not focused.
+    #[serde(default)]
+    pub unfocused: UnfocusedMode,
+}
+
+/// Behavior while the terminal window is not focused, for terminals reporting focus changes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnfocusedMode {
+    /// Keep everything running
+    None,
+    /// Redraw once per second
+    #[default]
+    Slow,
+    /// Also stop the connections, traffic and memory streams and background refreshes
+    Pause,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct RedactConfig {
+    /// Replace secret-like fields (`secret`, `password`, `uuid`, `Authorization`...)
+    pub strip_secrets: bool,
+    /// Mask host names and IPs, e.g. `www.example.com` -> `w**.e******.com`
+    pub mask_hosts: bool,
+    /// Replace source IPs with a pseudonym that is stable within a session
+    pub hash_source_ips: bool,
+    /// Extra field names to strip, case-insensitive
+    pub strip_keys: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct EncryptionConfig {
+    /// Environment variable holding the passphrase, encryption is off when unset
+    pub passphrase_env: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct BurstConfig {
+    /// Connections opened by one source IP within `window` to count as a burst, 0 disables.
+    pub connections: usize,
+    /// Window in seconds.
+    pub window: u64,
+    /// Show a message box when a burst starts, bursts are always flagged on Overview.
+    pub alert: bool,
+}
+
+impl Default for BurstConfig {
+    fn default() -> Self {
+        Self { connections: 100, window: 10, alert: false }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct MemoryTrendConfig {
+    /// Memory growth in MiB per hour, sustained over `window`, to alert on, 0 (default) disables.
+    pub growth: u64,
+    /// Window in minutes the growth is measured over.
+    pub window: u64,
+}
+
+impl Default for MemoryTrendConfig {
+    fn default() -> Self {
+        Self { growth: 0, window: 60 }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct HistoryConfig {
+    /// Record samples to the data directory.
+    pub enabled: bool,
+    /// Seconds between two samples.
+    pub interval: u64,
+    /// Hours of samples kept.
+    pub retention: u64,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self { enabled: false, interval: 60, retention: 24 }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct ExitIpConfig {
+    /// Service answering with the caller's IP in plain text.
+    pub url: String,
+    /// Proxy the lookup is sent through, defaults to the core's `mixed-port` (or `port`).
+    pub proxy: Option<String>,
+}
+
+impl Default for ExitIpConfig {
+    fn default() -> Self {
+        Self { url: "https://api.ipify.org".into(), proxy: None }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct GeoIpConfig {
+    /// MaxMind DB of countries, usually the core's own `Country.mmdb`. Empty string disables it.
+    pub mmdb: String,
+}
+
+impl Default for GeoIpConfig {
+    fn default() -> Self {
+        Self { mmdb: "~/.config/mihomo/Country.mmdb".into() }
+    }
+}
+
+impl Default for RedactConfig {
+    fn default() -> Self {
+        Self { strip_secrets: true, mask_hosts: false, hash_source_ips: false, strip_keys: vec![] }
+    }
+}
+
+impl Config {
+    /// Returns `true` if both configs connect to the same mihomo API with the same secret.
+    pub fn same_api(&self, other: &Config) -> bool {
+        self.mihomo_api == other.mihomo_api && self.mihomo_secret == other.mihomo_secret
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -52,7 +227,7 @@ impl MihomoApiEndpoint {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct UiConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -61,6 +236,21 @@ pub struct UiConfig {
     pub proxy_detail: Option<ProxyDetailUiConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy_provider_detail: Option<ProxyDetailUiConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thousands_separator: Option<ThousandsSeparator>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collation: Option<Collation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table: Option<TableStyle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout: Option<LayoutSetting>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chart: Option<ChartSetting>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_history: Option<FilterHistoryConfig>,
+    /// Extra Connections columns read from the connection metadata
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_columns: Vec<CustomColumnConfig>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -76,6 +266,29 @@ pub struct ConnectionsUiConfig {
     pub source_ip_alias: BTreeMap<String, String>,
 }
 
+/// A user-defined Connections column, e.g. `{ title: Dscp, pointer: /dscp }`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CustomColumnConfig {
+    pub title: String,
+    /// JSON pointer into the connection metadata
+    pub pointer: String,
+    #[serde(default)]
+    pub format: CustomColumnFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CustomColumnFormat {
+    /// Strings as is, other values as JSON
+    #[default]
+    Text,
+    /// Byte counts, e.g. `1.2 MB`
+    Bytes,
+    /// Numbers with the configured thousands separator
+    Number,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct ConnectionsSortConfig {
@@ -83,6 +296,19 @@ pub struct ConnectionsSortConfig {
 
     #[serde(default)]
     pub dir: SortDir,
+
+    /// Secondary sort keys, applied in order when the previous keys are equal
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub then_by: Vec<ConnectionsSortKey>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConnectionsSortKey {
+    pub field: String,
+
+    #[serde(default)]
+    pub dir: SortDir,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -101,13 +327,47 @@ pub struct ProxySortConfig {
     pub dir: SortDir,
 }
 
+/// Proportions of split panes, adjustable at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct LayoutSetting {
+    /// Width of the traffic chart on Overview in percent, the memory chart takes the rest.
+    pub overview_traffic_width: u16,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct FilterHistoryConfig {
+    /// Save submitted filter patterns to the runtime config, otherwise kept for the session only.
+    pub persist: bool,
+    /// Patterns of each tab, oldest first; written to the runtime config.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub entries: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct ChartSetting {
+    /// Exponential smoothing of the Overview traffic chart in `[0, 1)`, 0 disables it.
+    pub smoothing: f64,
+    /// Mark the session peak up/down rates on the Overview traffic chart.
+    pub peak_markers: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case", default)]
 pub struct ProxySetting {
     pub test_url: String,
     pub test_timeout: NonZeroUsize,
+    /// Status codes accepted by delay tests, mihomo's `expected` syntax e.g. `204` or
+    /// `200-299/302`, empty accepts any status.
+    pub test_expected_status: String,
     pub latency_threshold: LatencyThreshold,
     pub auto_terminate_connections: bool,
+    /// Test group nodes this many at a time instead of one group request, 0 disables batching.
+    pub test_batch_size: usize,
+    /// Pause between test batches in milliseconds.
+    pub test_batch_delay: u64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -122,12 +382,43 @@ impl LatencyThreshold {
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AlertConfig {
+    pub name: String,
+    /// Condition expression, see [`crate::utils::expr`] for the syntax.
+    pub when: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HookConfig {
+    pub name: String,
+    /// Condition expression, same as [`AlertConfig::when`].
+    pub when: String,
+    /// Command line run on a match, the connection is passed in environment variables.
+    pub run: String,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "kebab-case", default)]
 pub struct BufferConfig {
     pub overview: OverviewBufferConfig,
     pub connections: NonZeroUsize,
     pub logs: NonZeroUsize,
+    pub capture: CaptureRetention,
+    /// Connections snapshots queued for the Connections tab, the oldest is dropped when full.
+    pub snapshots: NonZeroUsize,
+}
+
+/// Closed connections kept by capture mode, they share the connections buffer with live ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct CaptureRetention {
+    /// Seconds a closed connection is kept, 0 keeps it until the buffer is full.
+    pub max_age: u64,
+    /// Closed connections kept at most, the oldest closed are dropped first. 0 is unlimited.
+    pub max_closed: usize,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -137,6 +428,18 @@ pub struct OverviewBufferConfig {
     pub traffic: NonZeroUsize,
 }
 
+impl Default for LayoutSetting {
+    fn default() -> Self {
+        Self { overview_traffic_width: 50 }
+    }
+}
+
+impl Default for ChartSetting {
+    fn default() -> Self {
+        Self { smoothing: 0.0, peak_markers: true }
+    }
+}
+
 impl Default for LatencyThreshold {
     fn default() -> Self {
         Self { medium: 500, high: 1000 }
@@ -148,8 +451,11 @@ impl Default for ProxySetting {
         Self {
             test_url: "https://www.gstatic.com/generate_204".into(),
             test_timeout: NonZeroUsize::new(5000).unwrap(),
+            test_expected_status: String::new(),
             latency_threshold: LatencyThreshold::default(),
             auto_terminate_connections: false,
+            test_batch_size: 0,
+            test_batch_delay: 500,
         }
     }
 }
@@ -160,10 +466,18 @@ impl Default for BufferConfig {
             overview: Default::default(),
             connections: NonZeroUsize::new(500).unwrap(),
             logs: NonZeroUsize::new(500).unwrap(),
+            capture: Default::default(),
+            snapshots: NonZeroUsize::new(2).unwrap(),
         }
     }
 }
 
+impl Default for CaptureRetention {
+    fn default() -> Self {
+        Self { max_age: 600, max_closed: 0 }
+    }
+}
+
 impl Default for OverviewBufferConfig {
     fn default() -> Self {
         OverviewBufferConfig {
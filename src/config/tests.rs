@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::num::NonZeroUsize;
 
 use super::*;
@@ -15,6 +16,12 @@ fn connection_col_index(title: &str) -> usize {
         .unwrap_or_else(|| panic!("connection column {title:?} should exist"))
 }
 
+#[test]
+fn test_default_core_config_schema_is_json() {
+    let schema: serde_json::Value = serde_json::from_str(DEFAULT_CORE_CONFIG_SCHEMA).unwrap();
+    assert!(schema.get("properties").is_some_and(serde_json::Value::is_object));
+}
+
 #[test]
 fn test_config_default() {
     let default_config: Config = yaml_serde::from_str(DEFAULT_CONFIG).unwrap();
@@ -44,6 +51,8 @@ fn test_config_default() {
     assert_eq!(config.buffer.logs, default_config.buffer.logs);
     assert_eq!(config.buffer.overview.memory, default_config.buffer.overview.memory);
     assert_eq!(config.buffer.overview.traffic, default_config.buffer.overview.traffic);
+    assert_eq!(config.buffer.capture, default_config.buffer.capture);
+    assert_eq!(config.buffer.snapshots, default_config.buffer.snapshots);
 }
 
 #[test]
@@ -577,11 +586,13 @@ fn test_config_runtime_connections_sort_alive_is_ignored() {
         query_state: QueryState {
             pattern: None,
             sort: Some(SortSpec { col: 0, dir: SortDir::Asc }),
+            pinned: vec![],
             max_cols: 2,
         },
         columns: vec![ALIVE_COLUMN_INDEX, connection_col_index("Host")],
         column_widths: Default::default(),
         source_ip_alias: Default::default(),
+        network: None,
     };
 
     let ui: ConnectionsUiConfig = (&setting).try_into().unwrap();
@@ -590,6 +601,35 @@ fn test_config_runtime_connections_sort_alive_is_ignored() {
     assert!(ui.sort.is_none());
 }
 
+#[test]
+fn test_config_connections_sort_then_by() {
+    let connections: ConnectionsUiConfig = yaml_serde::from_str(
+        r#"
+columns: ["Host", "Chains", "DownRate"]
+sort:
+  field: chains
+  dir: asc
+  then-by:
+    - field: DownRate
+"#,
+    )
+    .unwrap();
+    let setting = ConnectionsSetting::try_from(&connections).unwrap();
+    assert_eq!(setting.query_state.pinned, vec![SortSpec { col: 2, dir: SortDir::Asc }]);
+    assert_eq!(setting.query_state.sort, Some(SortSpec { col: 3, dir: SortDir::Desc }));
+
+    let ui: ConnectionsUiConfig = (&setting).try_into().unwrap();
+    let sort = ui.sort.unwrap();
+    assert_eq!((sort.field.as_str(), sort.dir), ("Chains", SortDir::Asc));
+    assert_eq!(sort.then_by.len(), 1);
+    assert_eq!((sort.then_by[0].field.as_str(), sort.then_by[0].dir), ("DownRate", SortDir::Desc));
+
+    let repeated: ConnectionsUiConfig =
+        yaml_serde::from_str("sort: { field: Host, then-by: [{ field: host }] }").unwrap();
+    let err = repeated.validate(&[]).unwrap_err();
+    assert!(err.to_string().contains("repeats the sort field"), "{err}");
+}
+
 #[test]
 fn test_config_ui_proxy_detail_sort_only() {
     let cfg_path = TempFile::new(temp_config_path());
@@ -748,6 +788,71 @@ ui:
     }
 }
 
+#[test]
+fn test_config_ui_custom_columns() {
+    let cfg_path = TempFile::new(temp_config_path());
+    let custom_config = r#"
+mihomo-api: "http://localhost"
+ui:
+  custom-columns:
+    - { title: Dscp, pointer: /dscp, format: number }
+    - { title: Uid, pointer: /uid }
+  connections:
+    columns: ["Host", "uid", "Dscp"]
+    sort: { field: dscp, then-by: [{ field: Host }] }
+    column-widths: { Uid: 6 }
+"#;
+    fs::write(&cfg_path.0, custom_config).unwrap();
+
+    let config = load(Some(cfg_path.0.clone())).unwrap();
+    let ui = config.ui.as_ref().unwrap();
+    assert_eq!(ui.custom_columns[0].format, CustomColumnFormat::Number);
+    assert_eq!(ui.custom_columns[1].format, CustomColumnFormat::Text);
+    let connections = ui.connections.as_ref().unwrap();
+    let (dscp, uid) = (CONNECTION_COLS.len(), CONNECTION_COLS.len() + 1);
+    let columns = ConnectionsUiConfig::parse_connections_columns(
+        connections.columns.as_ref().unwrap(),
+        &ui.custom_columns,
+    )
+    .unwrap();
+    assert_eq!(columns, vec![connection_col_index("Host"), uid, dscp]);
+    let sort = ConnectionsUiConfig::parse_connections_sort(
+        connections.sort.as_ref().unwrap(),
+        &ui.custom_columns,
+    )
+    .unwrap();
+    assert_eq!(sort[0].col, dscp);
+    let widths = ConnectionsUiConfig::parse_connections_column_widths(
+        &connections.column_widths,
+        &ui.custom_columns,
+    )
+    .unwrap();
+    assert_eq!(widths, HashMap::from([(uid, 6)]));
+    // unknown without the custom columns
+    assert!(connections.validate(&[]).is_err());
+
+    let cases = [
+        ("{ title: host, pointer: /host }", "duplicate `ui.custom-columns` title"),
+        ("{ title: Uid, pointer: /uid }, { title: uid, pointer: /x }", "duplicate"),
+        ("{ title: Process Uid, pointer: /uid }", "only contain ASCII letters"),
+        ("{ title: Uid, pointer: uid }", "must be a JSON pointer"),
+        ("{ title: Uid, pointer: /uid, format: hex }", "unknown variant"),
+    ];
+    for (columns, expected_error) in cases {
+        let custom_config = format!(
+            r#"
+mihomo-api: "http://localhost"
+ui:
+  custom-columns: [{columns}]
+"#
+        );
+        fs::write(&cfg_path.0, custom_config).unwrap();
+
+        let err_msg = format!("{:#}", load(Some(cfg_path.0.clone())).unwrap_err());
+        assert!(err_msg.contains(expected_error), "unexpected error: {}", err_msg);
+    }
+}
+
 #[test]
 fn test_config_buffer_defaults_when_missing() {
     let cfg_path = TempFile::new(temp_config_path());
@@ -763,6 +868,8 @@ mihomo-api: "http://localhost"
     assert_eq!(config.buffer.logs.get(), 500);
     assert_eq!(config.buffer.overview.memory.get(), 100);
     assert_eq!(config.buffer.overview.traffic.get(), 100);
+    assert_eq!(config.buffer.capture, CaptureRetention { max_age: 600, max_closed: 0 });
+    assert_eq!(config.buffer.snapshots.get(), 2);
 
     drop(cfg_path);
 }
@@ -809,3 +916,112 @@ impl Drop for TempFile {
         self.remove();
     }
 }
+
+#[test]
+fn test_set_secret_line() {
+    assert_eq!(
+        set_secret_line(
+            "mihomo-api: http://a\n\n# secret\n#mihomo-secret:\nx: 1\n",
+            ConfigFormat::Yaml,
+            "abc"
+        ),
+        "mihomo-api: http://a\n\n# secret\nmihomo-secret: abc\nx: 1\n"
+    );
+    assert_eq!(
+        set_secret_line("mihomo-secret: old\n#mihomo-secret:\n", ConfigFormat::Yaml, "new"),
+        "mihomo-secret: new\n#mihomo-secret:\n"
+    );
+    assert_eq!(
+        set_secret_line("x: 1\nmihomo-api: http://a", ConfigFormat::Yaml, "'1'"),
+        "x: 1\nmihomo-api: http://a\nmihomo-secret: '1'\n"
+    );
+
+    let path = temp_config_path();
+    fs::write(&path, DEFAULT_CONFIG).unwrap();
+    save_secret(&path, "p@ss: word").unwrap();
+    let config = read_from_file(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(config.mihomo_secret.as_deref(), Some("p@ss: word"));
+}
+
+#[test]
+fn test_config_formats_are_equivalent() {
+    let yaml = r#"
+mihomo-api: "http://localhost:9090"
+mihomo-secret: "secret"
+log-level: info
+ui:
+  connections:
+    columns: ["Host", "Rule"]
+    sort: { field: Host, dir: desc }
+proxy-setting:
+  test-url: https://example.com/generate_204
+  latency-threshold: "200,800"
+buffer:
+  logs: 500
+"#;
+    let toml = r#"
+mihomo-api = "http://localhost:9090"
+mihomo-secret = "secret"
+log-level = "info"
+
+[ui.connections]
+columns = ["Host", "Rule"]
+sort = { field = "Host", dir = "desc" }
+
+[proxy-setting]
+test-url = "https://example.com/generate_204"
+latency-threshold = "200,800"
+
+[buffer]
+logs = 500
+"#;
+    let json = r#"{
+  "mihomo-api": "http://localhost:9090",
+  "mihomo-secret": "secret",
+  "log-level": "info",
+  "ui": { "connections": { "columns": ["Host", "Rule"], "sort": { "field": "Host", "dir": "desc" } } },
+  "proxy-setting": { "test-url": "https://example.com/generate_204", "latency-threshold": "200,800" },
+  "buffer": { "logs": 500 }
+}"#;
+    let dir = tempfile::tempdir().unwrap();
+    let configs =
+        [("config.yaml", yaml), ("config.toml", toml), ("config.json", json)].map(|(name, raw)| {
+            let path = dir.path().join(name);
+            fs::write(&path, raw).unwrap();
+            format!("{:?}", load(Some(path)).unwrap().config)
+        });
+    assert_eq!(configs[0], configs[1]);
+    assert_eq!(configs[0], configs[2]);
+
+    // YAML is preferred when several formats exist
+    assert_eq!(find_config_file(dir.path()), dir.path().join("config.yaml"));
+    fs::remove_file(dir.path().join("config.yaml")).unwrap();
+    assert_eq!(find_config_file(dir.path()), dir.path().join("config.toml"));
+
+    let broken = dir.path().join("broken.toml");
+    fs::write(&broken, "mihomo-api: http://localhost").unwrap();
+    assert!(load(Some(broken)).is_err());
+}
+
+#[test]
+fn test_save_secret_in_toml_and_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml = dir.path().join("config.toml");
+    fs::write(&toml, "# api\nmihomo-api = \"http://a\"\n\n[buffer]\nlogs = 10\n").unwrap();
+    save_secret(&toml, "p\"ss").unwrap();
+    assert_eq!(
+        fs::read_to_string(&toml).unwrap(),
+        "# api\nmihomo-api = \"http://a\"\nmihomo-secret = 'p\"ss'\n\n[buffer]\nlogs = 10\n"
+    );
+    assert_eq!(read_from_file(&toml).unwrap().mihomo_secret.as_deref(), Some("p\"ss"));
+    assert_eq!(
+        set_secret_line("[buffer]\nlogs = 10\n", ConfigFormat::Toml, "\"x\""),
+        "mihomo-secret = \"x\"\n[buffer]\nlogs = 10\n"
+    );
+
+    let json = dir.path().join("config.json");
+    fs::write(&json, r#"{"mihomo-api": "http://a", "mihomo-secret": "old"}"#).unwrap();
+    save_secret(&json, "new").unwrap();
+    assert_eq!(read_from_file(&json).unwrap().mihomo_secret.as_deref(), Some("new"));
+}
@@ -4,8 +4,10 @@ use std::num::{NonZeroU16, NonZeroUsize};
 use anyhow::{Result, anyhow, bail};
 use url::Url;
 
+use crate::alerts::AlertEngine;
 use crate::config::{
-    Config, ConnectionsSortConfig, ConnectionsUiConfig, LatencyThreshold, ProxySetting,
+    ChartSetting, Config, ConnectionsSortConfig, ConnectionsUiConfig, CustomColumnConfig,
+    LatencyThreshold, LayoutSetting, ProxySetting,
 };
 use crate::models::sort::SortSpec;
 use crate::store::connections::{ALIVE_COLUMN_INDEX, CONNECTION_COLS};
@@ -24,40 +26,94 @@ impl Config {
             _ => {}
         }
         self.proxy_setting.validate()?;
+        AlertEngine::new(&self.alerts, &self.hooks)?;
+        let custom_columns =
+            self.ui.as_ref().map(|ui| ui.custom_columns.as_slice()).unwrap_or_default();
+        validate_custom_columns(custom_columns)?;
         if let Some(connections) = self.ui.as_ref().and_then(|ui| ui.connections.as_ref()) {
-            connections.validate()?;
+            connections.validate(custom_columns)?;
+        }
+        if let Some(layout) = self.ui.as_ref().and_then(|ui| ui.layout.as_ref()) {
+            layout.validate()?;
+        }
+        if let Some(chart) = self.ui.as_ref().and_then(|ui| ui.chart.as_ref()) {
+            chart.validate()?;
         }
         Ok(())
     }
 }
 
+/// Titles must be usable as `Title:expr` filter fields and not shadow built-in columns.
+fn validate_custom_columns(custom: &[CustomColumnConfig]) -> Result<()> {
+    for (i, column) in custom.iter().enumerate() {
+        let title = &column.title;
+        if title.is_empty() || !title.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            bail!(
+                "`ui.custom-columns` titles must be non-empty and only contain ASCII letters, \
+                 digits or `_`, got {title:?}"
+            );
+        }
+        let builtin = CONNECTION_COLS.iter().any(|def| def.col.title.eq_ignore_ascii_case(title));
+        if builtin || custom[..i].iter().any(|c| c.title.eq_ignore_ascii_case(title)) {
+            bail!("duplicate `ui.custom-columns` title: {title:?}");
+        }
+        if !column.pointer.is_empty() && !column.pointer.starts_with('/') {
+            bail!(
+                "`ui.custom-columns` pointer of {title:?} must be a JSON pointer starting with \
+                 `/`, got {:?}",
+                column.pointer
+            );
+        }
+    }
+    Ok(())
+}
+
 impl ConnectionsUiConfig {
-    pub fn validate(&self) -> Result<()> {
+    pub fn validate(&self, custom: &[CustomColumnConfig]) -> Result<()> {
         if let Some(columns) = &self.columns {
-            Self::parse_connections_columns(columns)?;
+            Self::parse_connections_columns(columns, custom)?;
         }
         if let Some(sort) = &self.sort {
-            Self::parse_connections_sort(sort)?;
+            Self::parse_connections_sort(sort, custom)?;
         }
-        Self::parse_connections_column_widths(&self.column_widths)?;
+        Self::parse_connections_column_widths(&self.column_widths, custom)?;
         Ok(())
     }
 
-    pub fn parse_connections_sort(raw: &ConnectionsSortConfig) -> Result<SortSpec> {
-        let sortable_cols = Self::sortable_connection_cols();
-        let Some(col) = Self::find_index_ignore_case(&sortable_cols, &raw.field) else {
-            bail!(
-                "`ui.connections.sort.field` must be one of [{}], got {:?}",
-                Self::join_connection_col_titles(&sortable_cols),
-                raw.field
-            );
+    /// Returns the primary key followed by `then-by` keys, columns are indices into
+    /// [`CONNECTION_COLS`] followed by `custom`.
+    pub fn parse_connections_sort(
+        raw: &ConnectionsSortConfig,
+        custom: &[CustomColumnConfig],
+    ) -> Result<Vec<SortSpec>> {
+        let sortable_cols = Self::sortable_connection_cols(custom);
+        let parse = |key: &str, field: &str, dir| {
+            let Some(col) = Self::find_index_ignore_case(&sortable_cols, field) else {
+                bail!(
+                    "`{key}` must be one of [{}], got {:?}",
+                    Self::join_connection_col_titles(&sortable_cols),
+                    field
+                );
+            };
+            Ok(SortSpec { col, dir })
         };
 
-        Ok(SortSpec { col, dir: raw.dir })
+        let mut keys = vec![parse("ui.connections.sort.field", &raw.field, raw.dir)?];
+        for then in &raw.then_by {
+            let key = parse("ui.connections.sort.then-by.field", &then.field, then.dir)?;
+            if keys.iter().any(|k| k.col == key.col) {
+                bail!("`ui.connections.sort.then-by` repeats the sort field {:?}", then.field);
+            }
+            keys.push(key);
+        }
+        Ok(keys)
     }
 
-    pub fn parse_connections_columns(raw: &[String]) -> Result<Vec<usize>> {
-        let configurable_cols = Self::configurable_connection_cols();
+    pub fn parse_connections_columns(
+        raw: &[String],
+        custom: &[CustomColumnConfig],
+    ) -> Result<Vec<usize>> {
+        let configurable_cols = Self::configurable_connection_cols(custom);
         if raw.is_empty() {
             bail!(
                 "`ui.connections.columns` cannot be empty, must be one of [{}]",
@@ -86,8 +142,9 @@ impl ConnectionsUiConfig {
 
     pub fn parse_connections_column_widths(
         raw: &BTreeMap<String, NonZeroU16>,
+        custom: &[CustomColumnConfig],
     ) -> Result<HashMap<usize, u16>> {
-        let configurable_cols = Self::configurable_connection_cols();
+        let configurable_cols = Self::configurable_connection_cols(custom);
         let mut widths = HashMap::with_capacity(raw.len());
         for (field, width) in raw {
             let Some(col) = Self::find_index_ignore_case(&configurable_cols, field) else {
@@ -106,39 +163,76 @@ impl ConnectionsUiConfig {
         Ok(widths)
     }
 
-    fn find_index_ignore_case(items: &[(usize, &'static str)], name: &str) -> Option<usize> {
+    fn find_index_ignore_case(items: &[(usize, &str)], name: &str) -> Option<usize> {
         items.iter().find(|(_, title)| title.eq_ignore_ascii_case(name)).map(|(idx, _)| *idx)
     }
 
-    fn sortable_connection_cols() -> Vec<(usize, &'static str)> {
+    /// Custom columns are always sortable.
+    fn sortable_connection_cols(custom: &[CustomColumnConfig]) -> Vec<(usize, &str)> {
         CONNECTION_COLS
             .iter()
             .enumerate()
             .filter(|(idx, _)| *idx != ALIVE_COLUMN_INDEX)
             .filter(|(_, def)| def.col.sortable)
-            .map(|(idx, def)| (idx, def.col.title))
+            .map(|(idx, def)| (idx, def.col.title.as_ref()))
+            .chain(Self::custom_connection_cols(custom))
             .collect::<Vec<_>>()
     }
 
-    fn configurable_connection_cols() -> Vec<(usize, &'static str)> {
+    fn configurable_connection_cols(custom: &[CustomColumnConfig]) -> Vec<(usize, &str)> {
         CONNECTION_COLS
             .iter()
             .enumerate()
             .filter(|(idx, _)| *idx != ALIVE_COLUMN_INDEX)
-            .map(|(idx, def)| (idx, def.col.title))
+            .map(|(idx, def)| (idx, def.col.title.as_ref()))
+            .chain(Self::custom_connection_cols(custom))
             .collect::<Vec<_>>()
     }
 
-    fn join_connection_col_titles(cols: &[(usize, &'static str)]) -> String {
+    fn custom_connection_cols(
+        custom: &[CustomColumnConfig],
+    ) -> impl Iterator<Item = (usize, &str)> {
+        custom
+            .iter()
+            .enumerate()
+            .map(|(i, column)| (CONNECTION_COLS.len() + i, column.title.as_str()))
+    }
+
+    fn join_connection_col_titles(cols: &[(usize, &str)]) -> String {
         cols.iter().map(|(_, title)| *title).collect::<Vec<_>>().join(", ")
     }
 }
 
+impl ChartSetting {
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..1.0).contains(&self.smoothing) {
+            bail!("`ui.chart.smoothing` must be in [0, 1), got {}", self.smoothing);
+        }
+        Ok(())
+    }
+}
+
+impl LayoutSetting {
+    pub fn validate(&self) -> Result<()> {
+        if !Self::PERCENT_RANGE.contains(&self.overview_traffic_width) {
+            bail!(
+                "`ui.layout.overview-traffic-width` must be between {} and {}, got {}",
+                Self::PERCENT_RANGE.start(),
+                Self::PERCENT_RANGE.end(),
+                self.overview_traffic_width
+            );
+        }
+        Ok(())
+    }
+}
+
 impl ProxySetting {
     pub fn validate(&self) -> Result<()> {
         Self::validate_test_url(&self.test_url)?;
         Self::validate_test_timeout(self.test_timeout)?;
+        Self::validate_test_expected_status(&self.test_expected_status)?;
         Self::validate_latency_threshold(self.latency_threshold)?;
+        Self::validate_test_batch_delay(self.test_batch_delay)?;
         Ok(())
     }
 
@@ -162,6 +256,40 @@ impl ProxySetting {
         }
     }
 
+    /// `/`-separated status codes or inclusive ranges, e.g. `200-299/302`, empty accepts any.
+    pub fn validate_test_expected_status(value: &str) -> Result<()> {
+        if value.is_empty() {
+            return Ok(());
+        }
+        let code = |s: &str| {
+            s.trim()
+                .parse::<u16>()
+                .ok()
+                .filter(|c| (100..=599).contains(c))
+                .ok_or_else(|| anyhow!("Invalid status code `{}`, must be 100-599", s.trim()))
+        };
+        for part in value.split('/') {
+            match part.split_once('-') {
+                Some((start, end)) if code(start)? > code(end)? => {
+                    bail!("Invalid status range `{part}`, start is greater than end")
+                }
+                Some(_) => {}
+                None => {
+                    code(part)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn validate_test_batch_delay(value: u64) -> Result<()> {
+        if value <= 60000 {
+            Ok(())
+        } else {
+            bail!("Batch delay must be between 0 and 60000 milliseconds");
+        }
+    }
+
     pub fn validate_latency_threshold(value: LatencyThreshold) -> Result<()> {
         if value.medium == 0 || value.high == 0 {
             bail!("Threshold values must be valid positive numbers");
@@ -186,6 +314,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_layout_percent_range() {
+        assert!(LayoutSetting::default().validate().is_ok());
+        let err = LayoutSetting { overview_traffic_width: 95 }.validate().unwrap_err();
+        assert!(err.to_string().contains("must be between 20 and 80, got 95"));
+    }
+
+    #[test]
+    fn test_chart_smoothing_range() {
+        assert!(ChartSetting::default().validate().is_ok());
+        let err = ChartSetting { smoothing: 1.0, peak_markers: true }.validate().unwrap_err();
+        assert!(err.to_string().contains("must be in [0, 1), got 1"));
+    }
+
     #[test]
     fn test_latency_threshold_invalid_order() {
         let err =
@@ -200,4 +342,14 @@ mod tests {
         assert!(ProxySetting::validate_test_timeout(NonZeroUsize::new(60000).unwrap()).is_ok());
         assert!(ProxySetting::validate_test_timeout(NonZeroUsize::new(60001).unwrap()).is_err());
     }
+
+    #[test]
+    fn test_proxy_expected_status() {
+        for valid in ["", "204", "200-299", "200/302/400-503"] {
+            assert!(ProxySetting::validate_test_expected_status(valid).is_ok(), "{valid}");
+        }
+        for invalid in ["20", "abc", "299-200", "200//204", "200-", "600"] {
+            assert!(ProxySetting::validate_test_expected_status(invalid).is_err(), "{invalid}");
+        }
+    }
 }
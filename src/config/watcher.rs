@@ -0,0 +1,132 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use notify::event::{EventKind, ModifyKind};
+use notify::{Event, RecursiveMode, Watcher};
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+use crate::action::Action;
+use crate::components::ComponentId;
+use crate::config;
+
+/// Editors usually emit several events per save (truncate, write, rename...),
+/// wait for the file to settle before reloading.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watch the app config file and send [`Action::ConfigFileChanged`] with the re-parsed config
+/// whenever it changes on disk.
+///
+/// The parent directory is watched instead of the file itself, so that atomic saves
+/// (write to temp file + rename) are still detected.
+pub fn spawn(
+    config_path: PathBuf,
+    runtime_path: PathBuf,
+    action_tx: UnboundedSender<Action>,
+    token: CancellationToken,
+) -> Result<()> {
+    let dir = config_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+        .to_path_buf();
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        let _ = event_tx.send(res);
+    })
+    .context("Fail to create config file watcher")?;
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Fail to watch directory `{}`", dir.display()))?;
+    info!("Watching config file `{}` for changes", config_path.display());
+
+    tokio::task::Builder::new().name("config-watcher").spawn(async move {
+        // keep watcher alive as long as the task
+        let _watcher = watcher;
+        loop {
+            let event = tokio::select! {
+                _ = token.cancelled() => break,
+                event = event_rx.recv() => event,
+            };
+            match event {
+                None => break,
+                Some(Ok(event)) if is_relevant(&event, &config_path) => {}
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => {
+                    warn!(error = ?e, "Config file watcher error");
+                    continue;
+                }
+            }
+
+            // drain the burst of events
+            loop {
+                match tokio::time::timeout(DEBOUNCE, event_rx.recv()).await {
+                    Ok(Some(_)) => continue,
+                    Ok(None) => return,
+                    Err(_) => break,
+                }
+            }
+
+            debug!("Config file changed, reloading `{}`", config_path.display());
+            let action = match config::reload(&config_path, &runtime_path) {
+                Ok(config) => Action::ConfigFileChanged(Arc::new(config)),
+                Err(e) => {
+                    error!(error = ?e, "Failed to reload config file");
+                    Action::Error(ComponentId::Root, ("Reload config", e).into())
+                }
+            };
+            if action_tx.send(action).is_err() {
+                break;
+            }
+        }
+        info!("Config file watcher stopped");
+    })?;
+
+    Ok(())
+}
+
+fn is_relevant(event: &Event, config_path: &Path) -> bool {
+    let kind_matches = matches!(
+        event.kind,
+        EventKind::Create(_)
+            | EventKind::Modify(ModifyKind::Any | ModifyKind::Data(_) | ModifyKind::Name(_))
+    );
+    kind_matches
+        && event
+            .paths
+            .iter()
+            .any(|p| p.file_name().is_some_and(|n| Some(n) == config_path.file_name()))
+}
+
+#[cfg(test)]
+mod tests {
+    use notify::event::{AccessKind, CreateKind, DataChange};
+
+    use super::*;
+
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn relevant_events_match_config_file_name() {
+        let config_path = Path::new("/tmp/mihomo-tui/config.yaml");
+
+        let modify = EventKind::Modify(ModifyKind::Data(DataChange::Content));
+        assert!(is_relevant(&event(modify, "/tmp/mihomo-tui/config.yaml"), config_path));
+        assert!(is_relevant(
+            &event(EventKind::Create(CreateKind::File), "/tmp/mihomo-tui/config.yaml"),
+            config_path
+        ));
+
+        // runtime sidecar is written by the app itself
+        assert!(!is_relevant(&event(modify, "/tmp/mihomo-tui/config.runtime.yaml"), config_path));
+        assert!(!is_relevant(
+            &event(EventKind::Access(AccessKind::Any), "/tmp/mihomo-tui/config.yaml"),
+            config_path
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 use std::fs::OpenOptions;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use tracing::Level;
@@ -8,8 +9,9 @@ use tracing_subscriber::prelude::*;
 use tracing_subscriber::{EnvFilter, fmt};
 
 use crate::config::{Config, PROJECT_NAME};
+use crate::utils::redact::RedactingMakeWriter;
 
-pub fn init(config: &Config) -> Result<()> {
+pub fn init(config: &Config, log_filter: Option<&str>) -> Result<()> {
     let log_file = match &config.log_file {
         Some(path) => PathBuf::from(path),
         None => return Ok(()),
@@ -21,18 +23,25 @@ pub fn init(config: &Config) -> Result<()> {
         .with_context(|| format!("Fail to open file `{}`", log_file.display()))?;
 
     // Resolve log filtering rules with the following priority:
-    // 1. <PROJECT_NAME>_LOG_LEVEL (project-specific override)
-    // 2. RUST_LOG (standard tracing environment variable)
-    // 3. config.log_level (fallback, defaults to "info")
-    let log_level = config.log_level.as_deref().unwrap_or(Level::INFO.as_str());
-    let env_filter = EnvFilter::try_from_env(format!("{}_LOG_LEVEL", *PROJECT_NAME))
-        .or_else(|_| EnvFilter::try_from_default_env())
-        .or_else(|_| EnvFilter::try_new(log_level))?;
+    // 1. --log-filter (command line override)
+    // 2. <PROJECT_NAME>_LOG_LEVEL (project-specific override)
+    // 3. RUST_LOG (standard tracing environment variable)
+    // 4. config.log_level (fallback, defaults to "info")
+    let env_filter = match log_filter {
+        Some(filter) => EnvFilter::try_new(filter)
+            .with_context(|| format!("Invalid `--log-filter` value `{filter}`"))?,
+        None => {
+            let log_level = config.log_level.as_deref().unwrap_or(Level::INFO.as_str());
+            EnvFilter::try_from_env(format!("{}_LOG_LEVEL", *PROJECT_NAME))
+                .or_else(|_| EnvFilter::try_from_default_env())
+                .or_else(|_| EnvFilter::try_new(log_level))?
+        }
+    };
 
     let file_subscriber = fmt::layer()
         .with_file(false)
         .with_line_number(false)
-        .with_writer(log_file)
+        .with_writer(RedactingMakeWriter(Arc::new(log_file)))
         .with_target(true)
         .with_ansi(false)
         .with_filter(env_filter);
@@ -1,13 +1,15 @@
-use std::{env, thread};
+use std::{env, process, thread};
 
 use anyhow::{Context, anyhow};
 
 use crate::version_update::RestartOutcome;
 
 mod action;
+mod alerts;
 mod api;
 mod app;
 mod app_message;
+mod check_config;
 mod cli;
 mod components;
 mod config;
@@ -15,7 +17,9 @@ mod logging;
 mod models;
 mod palette;
 mod panic;
+mod plugins;
 mod store;
+mod task_manager;
 mod tui;
 mod utils;
 mod version_update;
@@ -26,6 +30,27 @@ async fn main() -> anyhow::Result<()> {
     panic::init()?;
 
     let args = cli::parse_args()?;
+    if args.print_default_config {
+        print!("{}", config::DEFAULT_CONFIG);
+        return Ok(());
+    }
+    if args.print_core_config_schema {
+        print!("{}", config::DEFAULT_CORE_CONFIG_SCHEMA);
+        return Ok(());
+    }
+    if let Some(cli::Commands::Completions { shell }) = args.command {
+        cli::print_completions(shell);
+        return Ok(());
+    }
+    if let Some(out) = args.export_history {
+        // the history and the export are encrypted if configured
+        let loaded_config = config::load(args.config.clone())?;
+        utils::encryption::Encryption::configure(&loaded_config.encryption);
+        let samples =
+            store::history::History::export(&store::history::History::default_path(), &out)?;
+        println!("{samples} samples exported to {}.", out.display());
+        return Ok(());
+    }
     if args.update {
         let exe_path = env::current_exe().context("get current exe path")?;
         match thread::spawn(version_update::update_app)
@@ -55,22 +80,52 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    if args.check_config {
+        if let Err(e) = check_config::run(args.config, args.ping).await {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        return Ok(());
+    }
+
     let mut loaded_config = config::load(args.config)?;
-    logging::init(&loaded_config)?;
+    // before logging, so the log file is redacted from the first line
+    utils::redact::Redactor::configure(&loaded_config.redact);
+    logging::init(&loaded_config, args.log_filter.as_deref())?;
     loaded_config.try_apply_runtime();
+    if args.safe_mode {
+        utils::safe_mode::SafeMode::enable();
+        tracing::info!("Safe mode, streams and background refreshes are disabled");
+    }
     tracing::info!(
         config_path = %loaded_config.config_path.display(),
         runtime_path = %loaded_config.runtime_path.display(),
         "Loaded app configuration"
     );
 
-    let api = api::Api::new(&loaded_config)?;
-    if let Err(e) = api.get_version().await {
-        tracing::error!("Failed to get version from API: {:?}", e);
-        anyhow::bail!("`mihomo-api` unavailable, exiting: {:?}", e);
-    }
+    let api = api::Api::new(&loaded_config)?.with_observer(components::api_observer());
+    let unauthorized = match api.get_version().await {
+        Ok(_) => false,
+        // the secret is asked for in the app instead of failing
+        Err(e) if api::Api::is_unauthorized(&e) => {
+            tracing::warn!("Unauthorized to get version from API: {:?}", e);
+            true
+        }
+        Err(e) => {
+            tracing::error!("Failed to get version from API: {:?}", e);
+            anyhow::bail!("`mihomo-api` unavailable, exiting: {:?}", e);
+        }
+    };
 
-    let mut app = app::App::new(loaded_config.config, loaded_config.runtime_path, api)?;
+    let mut app = app::App::new(
+        loaded_config.config,
+        loaded_config.config_path,
+        loaded_config.runtime_path,
+        api,
+    )?;
+    if unauthorized {
+        app.prompt_secret()?;
+    }
     app.run().await?;
 
     Ok(())
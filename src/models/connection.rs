@@ -5,27 +5,6 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use time::OffsetDateTime;
 
-use crate::utils::byte_size::ByteSize;
-
-#[derive(Debug)]
-pub struct ConnectionStats {
-    pub conns_size: usize,
-    pub memory: ByteSize,
-    pub down_total: ByteSize,
-    pub up_total: ByteSize,
-}
-
-impl From<&ConnectionsWrapper> for ConnectionStats {
-    fn from(value: &ConnectionsWrapper) -> Self {
-        ConnectionStats {
-            conns_size: value.connections.as_ref().map(Vec::len).unwrap_or_default(),
-            memory: value.memory.into(),
-            down_total: value.download_total.into(),
-            up_total: value.upload_total.into(),
-        }
-    }
-}
-
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConnectionsWrapper {
@@ -62,4 +41,77 @@ impl Connection {
     pub fn metadata_str(&self, key: &str) -> Option<&str> {
         self.metadata.get(key)?.as_str().map(str::trim).filter(|s| !s.is_empty())
     }
+
+    /// Badges of lesser-known metadata: a sniffed host, a DSCP mark and a special proxy, e.g.
+    /// `sniffed dscp46 mitm`.
+    pub fn marks(&self) -> Vec<String> {
+        let mut marks = Vec::new();
+        if self.metadata_str("sniffHost").is_some() {
+            marks.push("sniffed".to_owned());
+        }
+        if let Some(dscp) = self.metadata.get("dscp").and_then(Value::as_u64).filter(|d| *d > 0) {
+            marks.push(format!("dscp{dscp}"));
+        }
+        match self.metadata_str("specialProxy") {
+            Some(special) if special.eq_ignore_ascii_case("mitm") => marks.push("mitm".to_owned()),
+            Some(special) => marks.push(format!("special:{special}")),
+            None => (),
+        }
+        marks
+    }
+
+    /// Site of a web connection, `https://<host>`, or `http://<host>` on port 80. Connections to
+    /// other ports or without a (sniffed) host have none.
+    pub fn web_url(&self) -> Option<String> {
+        let host = self.metadata_str("host").or_else(|| self.metadata_str("sniffHost"))?;
+        // a string in current cores, a number in old ones
+        let port = match self.metadata.get("destinationPort")? {
+            Value::String(port) => port.parse().ok(),
+            port => port.as_u64(),
+        };
+        match port? {
+            80 => Some(format!("http://{host}")),
+            443 => Some(format!("https://{host}")),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::test::conn;
+
+    fn connection(metadata: Value) -> Connection {
+        conn("a").metadata(metadata).build()
+    }
+
+    #[test]
+    fn marks_of_special_metadata() {
+        let marks = |metadata| connection(metadata).marks();
+        assert_eq!(
+            marks(json!({ "sniffHost": "example.com", "dscp": 46, "specialProxy": "MITM" })),
+            ["sniffed", "dscp46", "mitm"]
+        );
+        assert_eq!(marks(json!({ "specialProxy": "relay" })), ["special:relay"]);
+        assert!(marks(json!({ "sniffHost": "", "dscp": 0, "specialProxy": "" })).is_empty());
+    }
+
+    #[test]
+    fn web_url_of_http_and_https_connections() {
+        let url = |metadata| connection(metadata).web_url();
+        assert_eq!(
+            url(json!({ "host": "example.com", "destinationPort": "443" })).as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            url(json!({ "host": "", "sniffHost": "example.com", "destinationPort": 80 }))
+                .as_deref(),
+            Some("http://example.com")
+        );
+        assert_eq!(url(json!({ "host": "example.com", "destinationPort": "53" })), None);
+        assert_eq!(url(json!({ "destinationIP": "1.1.1.1", "destinationPort": "443" })), None);
+    }
 }
@@ -11,7 +11,7 @@ pub mod sort;
 mod traffic;
 mod version;
 
-pub use connection::{Connection, ConnectionStats, ConnectionsWrapper};
+pub use connection::{Connection, ConnectionsWrapper};
 pub use core_config::CoreConfig;
 pub use log::{Log, LogLevel};
 pub use memory::Memory;
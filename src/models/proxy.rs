@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use time::OffsetDateTime;
 
 use crate::widgets::latency::Latency;
 
@@ -16,6 +17,10 @@ pub struct Proxy {
     /// current selected node
     #[serde(rename(deserialize = "now"))]
     pub selected: Option<String>,
+    /// manual pick of url-test and fallback groups, empty when the core selects by delay
+    pub fixed: Option<String>,
+    /// balancing strategy of load-balance groups, only exposed by some cores
+    pub strategy: Option<String>,
 
     // pub test_url: Option<String>,
     /// delay history
@@ -26,10 +31,177 @@ pub struct Proxy {
     pub latency: Latency,
 }
 
+/// Group types whose behavior the detail popup surfaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupKind {
+    Selector,
+    UrlTest,
+    Fallback,
+    LoadBalance,
+    Other,
+}
+
+impl Proxy {
+    pub fn group_kind(&self) -> GroupKind {
+        match self.r#type.as_str() {
+            "Selector" => GroupKind::Selector,
+            "URLTest" => GroupKind::UrlTest,
+            "Fallback" => GroupKind::Fallback,
+            "LoadBalance" => GroupKind::LoadBalance,
+            _ => GroupKind::Other,
+        }
+    }
+
+    /// The manual pick of an url-test or fallback group.
+    pub fn fixed(&self) -> Option<&str> {
+        self.fixed.as_deref().filter(|f| !f.is_empty())
+    }
+
+    /// Whether the last delay test succeeded, the alive count of a provider.
+    pub fn is_alive(&self) -> bool {
+        self.history.last().is_some_and(|h| h.delay > 0)
+    }
+}
+
+/// What a load-balance `strategy` hashes to stick connections to a node, `None` when it doesn't.
+pub fn hashing_key(strategy: &str) -> Option<&'static str> {
+    match strategy {
+        "consistent-hashing" => Some("destination domain or IP"),
+        "sticky-sessions" => Some("source IP and destination"),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct DelayHistory {
-    // /// time in RFC3339Nano format, e.g. "2006-01-02T15:04:05.999999999Z07:00"
-    // pub time: String,
+    /// time of the test, RFC3339Nano in the payload
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub time: Option<OffsetDateTime>,
     /// delay in milliseconds, less than or equal to 0 means timeout
     pub delay: i64,
 }
+
+/// A single provider node from `GET /providers/proxies/{provider}/{proxy}`, carrying the fields the
+/// bulk providers payload doesn't keep.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NodeDetail {
+    /// stable id the core assigns to the node
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub alive: bool,
+    #[serde(default)]
+    pub udp: bool,
+    #[serde(default)]
+    pub xudp: bool,
+    #[serde(default)]
+    pub tfo: bool,
+    #[serde(default)]
+    pub mptcp: bool,
+    #[serde(default)]
+    pub smux: bool,
+    #[serde(default)]
+    pub interface: Option<String>,
+    #[serde(default, rename = "dialer-proxy")]
+    pub dialer_proxy: Option<String>,
+    #[serde(default)]
+    pub history: Vec<NodeDelayHistory>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NodeDelayHistory {
+    #[serde(with = "time::serde::rfc3339")]
+    pub time: OffsetDateTime,
+    /// delay in milliseconds, less than or equal to 0 means timeout
+    pub delay: i64,
+}
+
+impl NodeDetail {
+    /// Alive checks of the kept history, `(alive, total)`.
+    pub fn alive_count(&self) -> (usize, usize) {
+        (self.history.iter().filter(|h| h.delay > 0).count(), self.history.len())
+    }
+
+    /// The newest failed check, the core doesn't expose why it failed.
+    pub fn last_failure(&self) -> Option<OffsetDateTime> {
+        self.history.iter().filter(|h| h.delay <= 0).map(|h| h.time).max()
+    }
+
+    /// Enabled transport features, e.g. `udp`, `tfo`.
+    pub fn features(&self) -> Vec<&'static str> {
+        [
+            (self.udp, "udp"),
+            (self.xudp, "xudp"),
+            (self.tfo, "tfo"),
+            (self.mptcp, "mptcp"),
+            (self.smux, "smux"),
+        ]
+        .into_iter()
+        .filter_map(|(on, name)| on.then_some(name))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn group_kind_and_fixed_pick() {
+        let group = |value| serde_json::from_value::<Proxy>(value).unwrap();
+        let url_test = group(json!({
+            "name": "auto",
+            "type": "URLTest",
+            "all": ["a", "b"],
+            "now": "b",
+            "fixed": "b",
+            "history": [],
+        }));
+        assert_eq!(url_test.group_kind(), GroupKind::UrlTest);
+        assert_eq!(url_test.fixed(), Some("b"));
+        let fallback =
+            group(json!({ "name": "fb", "type": "Fallback", "fixed": "", "history": [] }));
+        assert_eq!(fallback.group_kind(), GroupKind::Fallback);
+        assert_eq!(fallback.fixed(), None);
+        let selector = group(json!({ "name": "pick", "type": "Selector", "history": [] }));
+        assert_eq!(selector.group_kind(), GroupKind::Selector);
+        let balance = group(json!({ "name": "lb", "type": "LoadBalance", "history": [] }));
+        assert_eq!(balance.group_kind(), GroupKind::LoadBalance);
+        assert!(balance.strategy.is_none());
+
+        assert!(!balance.is_alive());
+        let node = |history| group(json!({ "name": "n", "type": "Vmess", "history": history }));
+        assert!(node(json!([{ "delay": 0 }, { "delay": 80 }])).is_alive());
+        assert!(!node(json!([{ "delay": 80 }, { "delay": 0 }])).is_alive());
+
+        assert_eq!(hashing_key("round-robin"), None);
+        assert!(hashing_key("sticky-sessions").is_some());
+    }
+
+    #[test]
+    fn node_detail_from_provider_endpoint() {
+        let detail: NodeDetail = serde_json::from_value(json!({
+            "name": "hk-01",
+            "type": "Trojan",
+            "id": "5f0e",
+            "alive": false,
+            "udp": true,
+            "tfo": true,
+            "dialer-proxy": "relay",
+            "interface": "",
+            "history": [
+                { "time": "2024-01-01T00:00:00Z", "delay": 120 },
+                { "time": "2024-01-01T00:05:00Z", "delay": 0 },
+                { "time": "2024-01-01T00:10:00Z", "delay": 98 },
+            ],
+            "extra": {},
+        }))
+        .unwrap();
+        assert_eq!(detail.alive_count(), (2, 3));
+        assert_eq!(detail.features(), ["udp", "tfo"]);
+        assert_eq!(detail.dialer_proxy.as_deref(), Some("relay"));
+        assert_eq!(detail.last_failure(), Some(time::macros::datetime!(2024-01-01 00:05:00 UTC)));
+    }
+}
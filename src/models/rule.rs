@@ -3,6 +3,8 @@ use std::sync::atomic::AtomicBool;
 use serde::Deserialize;
 use time::OffsetDateTime;
 
+use crate::utils::rule_parser::{self, RuleNode};
+
 #[derive(Debug, Deserialize)]
 pub struct Rule {
     pub r#type: String,
@@ -49,4 +51,48 @@ impl Rule {
     pub fn supports_disable(&self) -> bool {
         self.index.is_some() && self.extra.is_some()
     }
+
+    /// Name of the rule provider a `RULE-SET` rule references, the first one nested in a logic
+    /// rule otherwise.
+    pub fn rule_set(&self) -> Option<&str> {
+        fn find<'a>(node: &RuleNode<'a>) -> Option<&'a str> {
+            match node {
+                RuleNode::Leaf { r#type, payload } => (r#type.eq_ignore_ascii_case("RuleSet")
+                    || r#type.eq_ignore_ascii_case("RULE-SET"))
+                .then_some(*payload),
+                RuleNode::Logic { children, .. } => children.iter().find_map(find),
+            }
+        }
+        let node = rule_parser::parse(&self.r#type, &self.payload).ok()?;
+        find(&node).filter(|name| !name.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(r#type: &str, payload: &str) -> Rule {
+        Rule {
+            r#type: r#type.into(),
+            payload: payload.into(),
+            proxy: "DIRECT".into(),
+            index: None,
+            extra: None,
+            size: -1,
+            disable_state: AtomicBool::new(false),
+        }
+    }
+
+    #[test]
+    fn rule_set_of_plain_and_logic_rules() {
+        assert_eq!(rule("RuleSet", "ads").rule_set(), Some("ads"));
+        assert_eq!(
+            rule("AND", "((NETWORK,UDP),(RULE-SET,streaming))").rule_set(),
+            Some("streaming")
+        );
+        assert_eq!(rule("OR", "((DOMAIN,a.com),(NOT,((RuleSet,cn))))").rule_set(), Some("cn"));
+        assert_eq!(rule("Domain", "ads").rule_set(), None);
+        assert_eq!(rule("AND", "((RULE-SET").rule_set(), None);
+    }
 }
@@ -0,0 +1,76 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Paragraph};
+
+use super::{Plugin, PluginContext, PluginTab};
+use crate::action::Action;
+use crate::components::ComponentId;
+use crate::task_manager::TaskManager;
+use crate::widgets::shortcut::Shortcut;
+
+pub const TAB: PluginTab =
+    PluginTab { name: "Example", short_name: "Exm", build: || Box::new(ExamplePlugin::default()) };
+
+/// Shows the core version and counts ticks, press `r` to reload the version.
+#[derive(Debug, Default)]
+struct ExamplePlugin {
+    ctx: Option<PluginContext>,
+    version: Arc<Mutex<Option<String>>>,
+    ticks: u64,
+}
+
+impl ExamplePlugin {
+    fn load_version(&self) -> Result<()> {
+        let api = Arc::clone(&self.ctx.as_ref().unwrap().api);
+        let version = Arc::clone(&self.version);
+        TaskManager::spawn(ComponentId::Plugin(TAB.name), "version-loader", async move {
+            let loaded = match api.get_version().await {
+                Ok(v) => v.version,
+                Err(e) => format!("error: {e}"),
+            };
+            *version.lock().unwrap() = Some(loaded);
+        })?;
+        Ok(())
+    }
+}
+
+impl Plugin for ExamplePlugin {
+    fn init(&mut self, ctx: PluginContext) -> Result<()> {
+        self.ctx = Some(ctx);
+        self.load_version()
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![Shortcut::from("reload", 0).unwrap()]
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if key.code == KeyCode::Char('r') {
+            self.load_version()?;
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: &Action) -> Result<Option<Action>> {
+        if matches!(action, Action::Tick) {
+            self.ticks += 1;
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let version = self.version.lock().unwrap().clone().unwrap_or_else(|| "-".into());
+        let lines = vec![
+            Line::from(format!("core version: {version}")),
+            Line::from(format!("ticks: {}", self.ticks)),
+        ];
+        let block = Block::bordered().border_type(BorderType::Rounded).title(TAB.name);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+//! Compile-time plugins adding custom tabs.
+//!
+//! A plugin implements [`Plugin`] and is listed in [`PLUGIN_TABS`] behind its own cargo feature,
+//! its tab is appended after the built-in tabs and is otherwise handled like any other tab
+//! (header, tab switcher, number keys, idle destruction). See `example.rs` (feature
+//! `plugin-example`) for a minimal one.
+#[cfg(feature = "plugin-example")]
+mod example;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::action::Action;
+use crate::api::Api;
+use crate::config::Config;
+use crate::widgets::shortcut::Shortcut;
+
+/// Handles available to a plugin.
+#[allow(dead_code)] // only read by plugins
+#[derive(Debug, Clone)]
+pub struct PluginContext {
+    pub api: Arc<Api>,
+    pub action_tx: UnboundedSender<Action>,
+    pub config: Option<Arc<Config>>,
+}
+
+/// A custom tab. Background work should be spawned with [`crate::task_manager::TaskManager`]
+/// under the tab's [`crate::components::ComponentId::Plugin`] id so it is cancelled with the tab.
+pub trait Plugin {
+    /// Called once when the tab is first opened.
+    fn init(&mut self, ctx: PluginContext) -> Result<()>;
+
+    /// Shortcuts shown in the footer while the tab is active.
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        vec![]
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let _ = key; // to appease clippy
+        Ok(None)
+    }
+
+    /// Receives every action broadcast to components, including [`Action::Tick`].
+    fn update(&mut self, action: &Action) -> Result<Option<Action>> {
+        let _ = action; // to appease clippy
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()>;
+}
+
+/// Registration of a plugin tab.
+pub struct PluginTab {
+    /// Unique name, shown in the header and the tab switcher.
+    pub name: &'static str,
+    /// Name used when the header is too narrow for full names.
+    pub short_name: &'static str,
+    pub build: fn() -> Box<dyn Plugin>,
+}
+
+/// Compiled-in plugin tabs in display order.
+pub static PLUGIN_TABS: &[PluginTab] = &[
+    #[cfg(feature = "plugin-example")]
+    example::TAB,
+];
+
+pub fn find(name: &str) -> Option<&'static PluginTab> {
+    PLUGIN_TABS.iter().find(|tab| tab.name == name)
+}
@@ -0,0 +1,25 @@
+use std::sync::{LazyLock, Mutex};
+
+use reqwest::Request;
+use ringbuffer::{AllocRingBuffer, RingBuffer};
+
+use crate::api::ApiCall;
+
+const CAPACITY: usize = 100;
+
+static CALLS: LazyLock<Mutex<AllocRingBuffer<ApiCall>>> =
+    LazyLock::new(|| Mutex::new(AllocRingBuffer::new(CAPACITY)));
+
+/// The last [`CAPACITY`] requests sent to the mihomo API, so they can be replayed with curl.
+pub struct ApiLog;
+
+impl ApiLog {
+    pub fn record(req: &Request) {
+        CALLS.lock().unwrap().enqueue(ApiCall::new(req));
+    }
+
+    /// Requests, newest first.
+    pub fn snapshot() -> Vec<ApiCall> {
+        CALLS.lock().unwrap().iter().rev().cloned().collect()
+    }
+}
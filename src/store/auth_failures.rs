@@ -0,0 +1,64 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Consecutive 401s before the UI switches to the authentication failed screen, so a single
+/// rejected request (e.g. a secret being verified) doesn't take over the screen.
+const THRESHOLD: usize = 3;
+
+static GLOBAL: AuthFailures = AuthFailures::new();
+
+/// Requests rejected with `401 Unauthorized` in a row, recorded by REST calls and websocket
+/// handshakes alike.
+#[derive(Debug)]
+pub struct AuthFailures {
+    consecutive: AtomicUsize,
+}
+
+impl AuthFailures {
+    const fn new() -> Self {
+        Self { consecutive: AtomicUsize::new(0) }
+    }
+
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    pub fn record_unauthorized(&self) {
+        self.consecutive.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Any accepted request proves the secret works.
+    pub fn record_success(&self) {
+        // skip the write on the hot path
+        if self.consecutive.load(Ordering::Relaxed) > 0 {
+            self.consecutive.store(0, Ordering::Relaxed);
+        }
+    }
+
+    pub fn reset(&self) {
+        self.consecutive.store(0, Ordering::Relaxed);
+    }
+
+    pub fn is_failing(&self) -> bool {
+        self.consecutive.load(Ordering::Relaxed) >= THRESHOLD
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failing_after_consecutive_rejections() {
+        let failures = AuthFailures::new();
+        failures.record_unauthorized();
+        failures.record_unauthorized();
+        failures.record_success();
+        failures.record_unauthorized();
+        failures.record_unauthorized();
+        assert!(!failures.is_failing());
+        failures.record_unauthorized();
+        assert!(failures.is_failing());
+        failures.reset();
+        assert!(!failures.is_failing());
+    }
+}
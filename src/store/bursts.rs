@@ -0,0 +1,122 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::config::BurstConfig;
+use crate::store::snapshot_delta::SnapshotDelta;
+
+/// A source IP that opened more connections than allowed within the window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Burst {
+    pub source_ip: String,
+    /// Connections opened within the window
+    pub opened: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BurstReport {
+    /// Sources bursting now, most connections first
+    pub active: Vec<Burst>,
+    /// Sources of `active` that were not bursting in the previous snapshot
+    pub started: Vec<Burst>,
+}
+
+/// Detects source IPs opening connections in bursts from consecutive connection snapshots.
+#[derive(Debug, Default)]
+pub struct BurstDetector {
+    config: BurstConfig,
+    /// Open times within the window by source IP
+    opened: HashMap<String, VecDeque<Instant>>,
+    bursting: HashSet<String>,
+}
+
+impl BurstDetector {
+    pub fn new(config: BurstConfig) -> Self {
+        Self { config, ..Default::default() }
+    }
+
+    pub fn update(&mut self, delta: &SnapshotDelta, now: Instant) -> BurstReport {
+        if self.config.connections == 0 {
+            return BurstReport::default();
+        }
+        for conn in delta.added() {
+            let Some(source_ip) = conn.metadata_str("sourceIP").filter(|ip| !ip.is_empty()) else {
+                continue;
+            };
+            self.opened.entry(source_ip.to_owned()).or_default().push_back(now);
+        }
+
+        let window = Duration::from_secs(self.config.window);
+        self.opened.retain(|_, times| {
+            while times.front().is_some_and(|at| now.duration_since(*at) >= window) {
+                times.pop_front();
+            }
+            !times.is_empty()
+        });
+
+        let mut active: Vec<Burst> = self
+            .opened
+            .iter()
+            .filter(|(_, times)| times.len() > self.config.connections)
+            .map(|(ip, times)| Burst { source_ip: ip.clone(), opened: times.len() })
+            .collect();
+        active.sort_unstable_by(|a, b| {
+            b.opened.cmp(&a.opened).then_with(|| a.source_ip.cmp(&b.source_ip))
+        });
+        let started =
+            active.iter().filter(|b| !self.bursting.contains(&b.source_ip)).cloned().collect();
+        self.bursting = active.iter().map(|b| b.source_ip.clone()).collect();
+        BurstReport { active, started }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::Connection;
+    use crate::store::snapshot_delta::SnapshotDiffer;
+    use crate::utils::test::conn;
+
+    fn conns(conns: &[(&str, &str)]) -> Vec<Connection> {
+        let source = |(id, ip): &(&str, &str)| conn(id).metadata(json!({ "sourceIP": ip })).build();
+        conns.iter().map(source).collect()
+    }
+
+    #[test]
+    fn bursts_are_flagged_once_and_expire() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let config = BurstConfig { connections: 2, window: 10, alert: false };
+        let mut detector = BurstDetector::new(config);
+        let mut differ = SnapshotDiffer::default();
+        let mut update =
+            |ids: &[(&str, &str)], now| detector.update(&differ.diff(&conns(ids)), now);
+        let burst = |opened| Burst { source_ip: "10.0.0.2".into(), opened };
+
+        // already open connections don't count
+        let report = update(&[("a", "10.0.0.2"), ("b", "10.0.0.2")], at(0));
+        assert_eq!(report, BurstReport::default());
+
+        let report = update(&[("c", "10.0.0.2"), ("d", "10.0.0.2"), ("e", "10.0.0.1")], at(1));
+        assert_eq!(report, BurstReport::default());
+        let report = update(&[("f", "10.0.0.2")], at(5));
+        assert_eq!(report, BurstReport { active: vec![burst(3)], started: vec![burst(3)] });
+        // still bursting, but not news
+        let report = update(&[("f", "10.0.0.2"), ("g", "10.0.0.2")], at(6));
+        assert_eq!(report, BurstReport { active: vec![burst(4)], started: vec![] });
+        // `c` and `d` left the window
+        let report = update(&[("g", "10.0.0.2")], at(11));
+        assert_eq!(report, BurstReport::default());
+    }
+
+    #[test]
+    fn zero_threshold_disables_detection() {
+        let config = BurstConfig { connections: 0, ..Default::default() };
+        let mut detector = BurstDetector::new(config);
+        let mut differ = SnapshotDiffer::default();
+        detector.update(&differ.diff(&conns(&[])), Instant::now());
+        let report = detector.update(&differ.diff(&conns(&[("a", "10.0.0.2")])), Instant::now());
+        assert_eq!(report, BurstReport::default());
+    }
+}
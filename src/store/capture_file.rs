@@ -0,0 +1,123 @@
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::models::Connection;
+use crate::utils::encryption::Encryption;
+use crate::utils::redact::Redactor;
+
+/// Field added to each saved connection, whether it was already closed when saved.
+const CLOSED_KEY: &str = "closed";
+
+/// What the capture file popup was opened for.
+#[derive(Debug, Clone)]
+pub enum CaptureFileRequest {
+    /// Save these connections, active and retained closed ones.
+    Save(Arc<[Arc<Connection>]>),
+    Load,
+}
+
+/// Connections read back from a capture file, shown read-only.
+#[derive(Debug)]
+pub struct CaptureSnapshot {
+    pub path: PathBuf,
+    pub connections: Vec<Arc<Connection>>,
+}
+
+/// Write `conns` to `path` as JSON lines, redacted and encrypted like other exports.
+pub fn save(path: &Path, conns: &[Arc<Connection>]) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Fail to create directory `{}`", parent.display()))?;
+    }
+    let mut content = Vec::new();
+    for conn in conns {
+        let mut value = serde_json::to_value(conn.as_ref())?;
+        Redactor::json(&mut value);
+        if let Value::Object(map) = &mut value {
+            map.insert(CLOSED_KEY.into(), conn.inactive.load(Ordering::Relaxed).into());
+        }
+        serde_json::to_writer(&mut content, &value)?;
+        content.push(b'\n');
+    }
+    Encryption::write(path, &content)
+}
+
+/// Read a capture written by [`save`], closed connections are marked inactive again.
+pub fn load(path: &Path) -> Result<CaptureSnapshot> {
+    let content = Encryption::read(path)?;
+    let mut connections = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.with_context(|| format!("Fail to read file `{}`", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let conn =
+            parse_line(&line).with_context(|| format!("Invalid connection on line {}", idx + 1))?;
+        connections.push(Arc::new(conn));
+    }
+    Ok(CaptureSnapshot { path: path.to_path_buf(), connections })
+}
+
+fn parse_line(line: &str) -> Result<Connection> {
+    let mut value: Value = serde_json::from_str(line)?;
+    let closed = value
+        .as_object_mut()
+        .and_then(|map| map.remove(CLOSED_KEY))
+        .and_then(|v| v.as_bool())
+        .unwrap_or_default();
+    let conn: Connection = serde_json::from_value(value)?;
+    conn.inactive.store(closed, Ordering::Relaxed);
+    Ok(conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::test;
+
+    fn conn(id: &str, closed: bool) -> Arc<Connection> {
+        let conn = test::conn(id)
+            .metadata(json!({ "host": "example.com", "sourceIP": "192.168.1.2" }))
+            .bytes(1, 2)
+            .start("2024-01-01T00:00:00Z")
+            .chains(&["DIRECT"])
+            .rule("Match", "")
+            .build();
+        conn.inactive.store(closed, Ordering::Relaxed);
+        Arc::new(conn)
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("captures/incident.jsonl");
+        save(&path, &[conn("a", false), conn("b", true)]).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+
+        let snapshot = load(&path).unwrap();
+        assert_eq!(snapshot.path, path);
+        let loaded: Vec<_> = snapshot
+            .connections
+            .iter()
+            .map(|c| (c.id.as_str(), c.inactive.load(Ordering::Relaxed), c.metadata_str("host")))
+            .collect();
+        assert_eq!(loaded, [("a", false, Some("example.com")), ("b", true, Some("example.com"))]);
+    }
+
+    #[test]
+    fn load_reports_the_invalid_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.jsonl");
+        fs::write(&path, "\n{\"id\": 1}\n").unwrap();
+        let err = load(&path).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid connection on line 2");
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::{OnceLock, RwLock};
+
+pub use crate::config::ChartSetting;
+
+pub static GLOBAL_CHART_SETTING: OnceLock<RwLock<ChartSetting>> = OnceLock::new();
+
+impl ChartSetting {
+    pub fn global() -> &'static RwLock<Self> {
+        GLOBAL_CHART_SETTING.get_or_init(Default::default)
+    }
+
+    /// Exponential moving average of `points` in place, `smoothing` is the weight of the
+    /// previous (smoothed) value.
+    pub fn smooth(&self, points: &mut [(f64, f64)]) {
+        if self.smoothing <= 0.0 {
+            return;
+        }
+        let mut prev = None;
+        for (_, y) in points.iter_mut() {
+            if let Some(prev) = prev {
+                *y = self.smoothing * prev + (1.0 - self.smoothing) * *y;
+            }
+            prev = Some(*y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smooth_points() {
+        let mut points = vec![(0.0, 0.0), (1.0, 100.0), (2.0, 0.0)];
+        ChartSetting { smoothing: 0.0, peak_markers: false }.smooth(&mut points);
+        assert_eq!(points, vec![(0.0, 0.0), (1.0, 100.0), (2.0, 0.0)]);
+
+        ChartSetting { smoothing: 0.5, peak_markers: false }.smooth(&mut points);
+        assert_eq!(points, vec![(0.0, 0.0), (1.0, 50.0), (2.0, 25.0)]);
+    }
+}
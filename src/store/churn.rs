@@ -0,0 +1,70 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::store::snapshot_delta::SnapshotDelta;
+
+/// Rates are estimated over the last minute, extrapolated until a full minute was observed.
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Connections opened and closed per minute.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ChurnRate {
+    pub opened: f64,
+    pub closed: f64,
+}
+
+/// Estimates [`ChurnRate`] from consecutive connection snapshots.
+///
+/// High churn with a stable connection count usually means something is retrying in a loop.
+#[derive(Debug, Default)]
+pub struct ChurnTracker {
+    started: Option<Instant>,
+    /// (snapshot time, opened, closed) within [`WINDOW`]
+    deltas: VecDeque<(Instant, usize, usize)>,
+}
+
+impl ChurnTracker {
+    pub fn update(&mut self, delta: &SnapshotDelta, now: Instant) -> ChurnRate {
+        if !delta.baseline {
+            self.deltas.push_back((now, delta.added().count(), delta.removed.len()));
+        }
+        let started = *self.started.get_or_insert(now);
+
+        while self.deltas.front().is_some_and(|(at, ..)| now.duration_since(*at) >= WINDOW) {
+            self.deltas.pop_front();
+        }
+        let observed = now.duration_since(started).min(WINDOW);
+        if observed.is_zero() {
+            return ChurnRate::default();
+        }
+        let scale = WINDOW.as_secs_f64() / observed.as_secs_f64();
+        let (opened, closed) =
+            self.deltas.iter().fold((0, 0), |(o, c), (_, opened, closed)| (o + opened, c + closed));
+        ChurnRate { opened: opened as f64 * scale, closed: closed as f64 * scale }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::snapshot_delta::SnapshotDiffer;
+    use crate::utils::test::conns;
+
+    #[test]
+    fn churn_is_extrapolated_then_windowed() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut tracker = ChurnTracker::default();
+        let mut differ = SnapshotDiffer::default();
+        let mut update = |ids: &[&str], now| tracker.update(&differ.diff(&conns(ids)), now);
+
+        // the first snapshot is a baseline, not a minute of openings
+        assert_eq!(update(&["a", "b"], at(0)), ChurnRate::default());
+        // same count, but `b` was replaced: 1 opened and 1 closed in 10s
+        assert_eq!(update(&["a", "c"], at(10)), ChurnRate { opened: 6.0, closed: 6.0 });
+        assert_eq!(update(&["a", "c", "d"], at(30)), ChurnRate { opened: 4.0, closed: 2.0 });
+        // the change at 10s left the window
+        assert_eq!(update(&["a", "c", "d"], at(70)), ChurnRate { opened: 1.0, closed: 0.0 });
+        assert_eq!(update(&["a", "c", "d"], at(90)), ChurnRate::default());
+    }
+}
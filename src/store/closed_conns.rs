@@ -0,0 +1,55 @@
+use crate::store::snapshot_delta::SnapshotDelta;
+
+/// Connections that disappeared between snapshots, with the bytes they transferred.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ClosedStats {
+    pub conns: u64,
+    pub upload: u64,
+    pub download: u64,
+}
+
+/// Accumulates [`ClosedStats`] from consecutive connection snapshots.
+///
+/// Counting starts with the connections stream, the bytes of a closed connection are the last
+/// values seen before it disappeared.
+#[derive(Debug, Default)]
+pub struct ClosedConnTracker {
+    stats: ClosedStats,
+}
+
+impl ClosedConnTracker {
+    pub fn update(&mut self, delta: &SnapshotDelta) -> ClosedStats {
+        for (upload, download) in &delta.removed {
+            self.stats.conns += 1;
+            self.stats.upload += upload;
+            self.stats.download += download;
+        }
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Connection;
+    use crate::store::snapshot_delta::SnapshotDiffer;
+    use crate::utils::test;
+
+    fn conn(id: &str, upload: u64, download: u64) -> Connection {
+        test::conn(id).bytes(upload, download).build()
+    }
+
+    #[test]
+    fn tracker_accumulates_disappeared_connections() {
+        let mut tracker = ClosedConnTracker::default();
+        let mut differ = SnapshotDiffer::default();
+        let mut update = |conns: &[Connection]| tracker.update(&differ.diff(conns));
+        assert_eq!(update(&[conn("a", 1, 10), conn("b", 2, 20)]), ClosedStats::default());
+        assert_eq!(
+            update(&[conn("b", 3, 30), conn("c", 0, 0)]),
+            ClosedStats { conns: 1, upload: 1, download: 10 }
+        );
+        assert_eq!(update(&[]), ClosedStats { conns: 3, upload: 4, download: 40 });
+        assert_eq!(update(&[]), ClosedStats { conns: 3, upload: 4, download: 40 });
+    }
+}
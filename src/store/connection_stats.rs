@@ -0,0 +1,38 @@
+use crate::models::ConnectionsWrapper;
+use crate::store::bursts::Burst;
+use crate::store::churn::ChurnRate;
+use crate::store::closed_conns::ClosedStats;
+use crate::store::ip_family::IpFamilyStats;
+use crate::store::snapshot_drops::DropStats;
+use crate::utils::byte_size::ByteSize;
+
+/// Stats of a connections snapshot, with the ones of its trackers filled in by the loader.
+#[derive(Debug)]
+pub struct ConnectionStats {
+    pub conns_size: usize,
+    pub memory: ByteSize,
+    pub down_total: ByteSize,
+    pub up_total: ByteSize,
+    pub ip_family: IpFamilyStats,
+    pub closed: ClosedStats,
+    pub churn: ChurnRate,
+    /// Source IPs opening connections in bursts
+    pub bursts: Vec<Burst>,
+    pub drops: DropStats,
+}
+
+impl From<&ConnectionsWrapper> for ConnectionStats {
+    fn from(value: &ConnectionsWrapper) -> Self {
+        ConnectionStats {
+            conns_size: value.connections.as_ref().map(Vec::len).unwrap_or_default(),
+            memory: value.memory.into(),
+            down_total: value.download_total.into(),
+            up_total: value.upload_total.into(),
+            ip_family: Default::default(),
+            closed: Default::default(),
+            churn: Default::default(),
+            bursts: Default::default(),
+            drops: Default::default(),
+        }
+    }
+}
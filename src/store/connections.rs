@@ -4,6 +4,7 @@ use std::convert::Into;
 use std::num::NonZeroUsize;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use const_format::concatcp;
 use indexmap::IndexMap;
@@ -12,20 +13,128 @@ use ratatui::layout::Constraint;
 use ringbuffer::{AllocRingBuffer, RingBuffer};
 use serde_json::Value;
 
+use crate::api::stream::CONNECTIONS_PATH;
+use crate::config::CaptureRetention;
 use crate::models::Connection;
 use crate::store::connections_setting::ConnectionsSetting;
+use crate::store::custom_columns::{ConnectionCols, connection_cols};
+use crate::store::labels::Labels;
+use crate::store::load_state::{LoadState, LoadStateCell};
+use crate::store::stream_health::StreamHealth;
 use crate::utils::byte_size::human_bytes;
 use crate::utils::columns::{ColDef, SortKey, TableColDef, TextResolver};
-use crate::utils::filter::{FilterPattern, RowFilter};
+use crate::utils::filter::{AppliedFilter, FilterPattern, RowFilter};
 use crate::utils::symbols::dot;
 use crate::utils::time::format_time_from_now;
 
+/// How long connections count as new after they first appeared.
+pub const NEW_WINDOW: Duration = Duration::from_secs(2);
+
+/// Changes of a snapshot compared with the previous one, matched by connection id. Only tells
+/// whether the view needs a recompute, which rebuilds it whole.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+/// Transport protocol of a connection, from its `network` metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Tcp,
+    Udp,
+}
+
+impl Network {
+    pub fn of(conn: &Connection) -> Option<Self> {
+        match conn.metadata_str("network")? {
+            n if n.eq_ignore_ascii_case("tcp") => Some(Self::Tcp),
+            n if n.eq_ignore_ascii_case("udp") => Some(Self::Udp),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Tcp => "tcp",
+            Self::Udp => "udp",
+        }
+    }
+
+    /// Next network filter: all, TCP only, UDP only.
+    pub fn cycle(filter: Option<Self>) -> Option<Self> {
+        match filter {
+            None => Some(Self::Tcp),
+            Some(Self::Tcp) => Some(Self::Udp),
+            Some(Self::Udp) => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkShare {
+    pub conns: usize,
+    pub up_rate: u64,
+    pub down_rate: u64,
+}
+
+/// Active connections and their rates per [`Network`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkTotals {
+    pub tcp: NetworkShare,
+    pub udp: NetworkShare,
+}
+
+impl NetworkTotals {
+    fn of<'a>(conns: impl Iterator<Item = &'a Arc<Connection>>) -> Self {
+        let mut totals = Self::default();
+        for conn in conns.filter(|c| !c.inactive.load(Ordering::Relaxed)) {
+            let share = match Network::of(conn) {
+                Some(Network::Tcp) => &mut totals.tcp,
+                Some(Network::Udp) => &mut totals.udp,
+                None => continue,
+            };
+            share.conns += 1;
+            share.up_rate += conn.upload_rate;
+            share.down_rate += conn.download_rate;
+        }
+        totals
+    }
+
+    pub fn get(&self, network: Network) -> NetworkShare {
+        match network {
+            Network::Tcp => self.tcp,
+            Network::Udp => self.udp,
+        }
+    }
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.removed == 0 && self.changed == 0
+    }
+}
+
 pub struct Connections {
     matcher: Mutex<Matcher>,
 
     buffer: RwLock<AllocRingBuffer<Arc<Connection>>>,
     view: RwLock<AllocRingBuffer<Arc<Connection>>>,
-    last_bytes: Mutex<HashMap<Arc<str>, (u64, u64)>>, // id -> (upload, download)
+    applied: Mutex<AppliedFilter>,
+    /// Setting and columns the view was last computed with, the view can only be narrowed if
+    /// everything but the pattern is the same
+    view_context: Mutex<Option<(Arc<ConnectionsSetting>, Arc<ConnectionCols>)>>,
+    /// Active connections of the previous snapshot by id
+    last: Mutex<HashMap<Arc<str>, Arc<Connection>>>,
+    /// When connections retained by capture mode were seen closed
+    closed_at: Mutex<HashMap<Arc<str>, Instant>>,
+    /// When connections opened within [`NEW_WINDOW`] were first seen
+    first_seen: Mutex<HashMap<Arc<str>, Instant>>,
+    /// Totals of the buffer as of the last [`Self::compute_view`]
+    network_totals: Mutex<NetworkTotals>,
+    retention: CaptureRetention,
+    load_state: LoadStateCell,
 }
 
 impl Connections {
@@ -34,62 +143,231 @@ impl Connections {
             matcher: Default::default(),
             buffer: RwLock::new(AllocRingBuffer::new(capacity.get())),
             view: RwLock::new(AllocRingBuffer::new(capacity.get())),
-            last_bytes: Default::default(),
+            applied: Default::default(),
+            view_context: Default::default(),
+            last: Default::default(),
+            closed_at: Default::default(),
+            first_seen: Default::default(),
+            network_totals: Default::default(),
+            retention: Default::default(),
+            load_state: Default::default(),
+        }
+    }
+
+    /// Store of `records` only, e.g. a capture loaded from disk, sized to hold all of them and
+    /// never pushed to. Closed records are expected to be marked inactive already.
+    pub fn of_records(records: Vec<Arc<Connection>>) -> Self {
+        let store = Self::new(NonZeroUsize::new(records.len()).unwrap_or(NonZeroUsize::MIN));
+        let now = Instant::now();
+        {
+            let mut buffer = store.buffer.write().unwrap();
+            let mut last = store.last.lock().unwrap();
+            let mut closed_at = store.closed_at.lock().unwrap();
+            for conn in records {
+                let key: Arc<str> = Arc::from(conn.id.as_str());
+                if conn.inactive.load(Ordering::Relaxed) {
+                    closed_at.insert(key, now);
+                } else {
+                    last.insert(key, Arc::clone(&conn));
+                }
+                buffer.enqueue(conn);
+            }
         }
+        store.load_state.set(LoadState::Loaded);
+        store
+    }
+
+    pub fn with_retention(mut self, retention: CaptureRetention) -> Self {
+        self.retention = retention;
+        self
     }
 
-    pub fn push(&self, capture_mode: bool, records: Vec<Connection>) {
+    /// Loading until the first snapshot arrived, failure is reported by the stream health.
+    pub fn load_state(&self) -> LoadState {
+        match self.load_state.get() {
+            LoadState::Loading if StreamHealth::is_stalled(CONNECTIONS_PATH) => LoadState::Failed,
+            state => state,
+        }
+    }
+
+    /// Replace the buffer with a snapshot, connections idle and otherwise unchanged since the
+    /// previous snapshot keep their `Arc`. The returned diff is empty when nothing changed, the
+    /// view can be kept as is, otherwise [`Self::compute_view`] is due.
+    pub fn push(&self, capture_mode: bool, records: Vec<Connection>) -> SnapshotDiff {
+        self.push_at(capture_mode, records, Instant::now())
+    }
+
+    fn push_at(&self, capture_mode: bool, records: Vec<Connection>, now: Instant) -> SnapshotDiff {
+        // everything of the first snapshot is new, but not just opened
+        let initial = self.load_state.get() != LoadState::Loaded;
+        self.load_state.set(LoadState::Loaded);
+        let mut first_seen = self.first_seen.lock().unwrap();
+        first_seen.retain(|_, at| now.duration_since(*at) < NEW_WINDOW);
         let mut guard = self.buffer.write().unwrap();
+        self.applied.lock().unwrap().invalidate();
         let mut history: IndexMap<Arc<str>, Arc<Connection>> = if capture_mode {
             guard.iter().cloned().map(|p| (p.id.as_str().into(), p)).collect()
         } else {
             Default::default()
         };
         guard.clear();
+        let mut diff = SnapshotDiff::default();
         {
-            let mut map = HashMap::with_capacity(records.len());
-            let mut map_guard = self.last_bytes.lock().unwrap();
+            let mut current = HashMap::with_capacity(records.len());
+            let mut previous = self.last.lock().unwrap();
             records.into_iter().for_each(|mut item| {
-                let key = Arc::from(item.id.as_str());
+                let key: Arc<str> = Arc::from(item.id.as_str());
                 history.shift_remove(&key);
-                map.insert(Arc::clone(&key), (item.upload, item.download));
-                if let Some((up, down)) = map_guard.get(&key) {
-                    item.upload_rate = item.upload.saturating_sub(*up);
-                    item.download_rate = item.download.saturating_sub(*down);
-                }
-                guard.enqueue(Arc::new(item));
+                let conn = match previous.remove(&key) {
+                    // idle and still idle, nothing to show differently
+                    Some(prev) if is_unchanged_idle(&prev, &item) => prev,
+                    Some(prev) => {
+                        diff.changed += 1;
+                        item.upload_rate = item.upload.saturating_sub(prev.upload);
+                        item.download_rate = item.download.saturating_sub(prev.download);
+                        Arc::new(item)
+                    }
+                    None => {
+                        diff.added += 1;
+                        if !initial {
+                            first_seen.insert(Arc::clone(&key), now);
+                        }
+                        Arc::new(item)
+                    }
+                };
+                current.insert(key, Arc::clone(&conn));
+                guard.enqueue(conn);
             });
-            *map_guard = map;
+            diff.removed = previous.len();
+            *previous = current;
         }
+        self.retain_closed(&mut history, now);
         history.into_values().for_each(|v| {
             v.inactive.store(true, Ordering::Relaxed);
             _ = guard.enqueue(v);
         });
+        diff
+    }
+
+    /// Drop closed connections of `history` exceeding the [`CaptureRetention`], the oldest
+    /// closed go first.
+    fn retain_closed(&self, history: &mut IndexMap<Arc<str>, Arc<Connection>>, now: Instant) {
+        let mut closed_at = self.closed_at.lock().unwrap();
+        for key in history.keys() {
+            closed_at.entry(Arc::clone(key)).or_insert(now);
+        }
+        closed_at.retain(|key, _| history.contains_key(key));
+
+        if self.retention.max_age > 0 {
+            let max_age = Duration::from_secs(self.retention.max_age);
+            closed_at.retain(|_, at| now.duration_since(*at) < max_age);
+        }
+        let max_closed = self.retention.max_closed;
+        if max_closed > 0 && closed_at.len() > max_closed {
+            let mut by_age =
+                closed_at.iter().map(|(k, at)| (*at, Arc::clone(k))).collect::<Vec<_>>();
+            by_age.sort_unstable_by_key(|(at, _)| *at);
+            let excess = by_age.len() - max_closed;
+            by_age.into_iter().take(excess).for_each(|(_, key)| _ = closed_at.remove(&key));
+        }
+        history.retain(|key, _| closed_at.contains_key(key));
+    }
+
+    /// Buffered connections, the active ones followed by those retained by capture mode.
+    pub fn snapshot(&self) -> Vec<Arc<Connection>> {
+        self.buffer.read().unwrap().iter().cloned().collect()
+    }
+
+    /// Time since the connection was first seen, if it opened within the last [`NEW_WINDOW`].
+    pub fn new_for(&self, id: &str, now: Instant) -> Option<Duration> {
+        let first_seen = self.first_seen.lock().unwrap();
+        first_seen.get(id).map(|at| now.duration_since(*at)).filter(|age| *age < NEW_WINDOW)
+    }
+
+    /// Drop the closed connections retained by capture mode, capture goes on with the active
+    /// ones. Returns the number of dropped connections.
+    pub fn clear_closed(&self) -> usize {
+        let mut buffer = self.buffer.write().unwrap();
+        let active: Vec<_> =
+            buffer.iter().filter(|c| !c.inactive.load(Ordering::Relaxed)).cloned().collect();
+        let cleared = buffer.len() - active.len();
+        buffer.clear();
+        self.applied.lock().unwrap().invalidate();
+        active.into_iter().for_each(|conn| _ = buffer.enqueue(conn));
+        self.closed_at.lock().unwrap().clear();
+        cleared
+    }
+
+    /// Closed connections currently retained by capture mode.
+    pub fn closed_count(&self) -> usize {
+        self.closed_at.lock().unwrap().len()
+    }
+
+    /// Active connections per network, regardless of filters.
+    pub fn network_totals(&self) -> NetworkTotals {
+        *self.network_totals.lock().unwrap()
     }
 
     pub fn compute_view(&self) {
         let setting = ConnectionsSetting::snapshot();
         let query_state = &setting.query_state;
         let buffer = self.buffer.read().unwrap();
+        *self.network_totals.lock().unwrap() = NetworkTotals::of(buffer.iter());
 
         let mut matcher = self.matcher.lock().unwrap();
-        let text_resolver = SourceIpAliasTextResolver { source_ip_alias: &setting.source_ip_alias };
+        let cols = connection_cols();
+        let text_resolver =
+            ConnectionTextResolver { source_ip_alias: &setting.source_ip_alias, cols: &cols };
+        let network = setting.network;
+        let mut applied = self.applied.lock().unwrap();
+        let context = (Arc::clone(&setting), Arc::clone(&cols));
+        let same_context =
+            self.view_context.lock().unwrap().replace(context).is_some_and(|(prev, prev_cols)| {
+                Arc::ptr_eq(&prev_cols, &cols)
+                    && prev.network == network
+                    && prev.columns == setting.columns
+                    && prev.source_ip_alias == setting.source_ip_alias
+            });
+        if !same_context {
+            applied.invalidate();
+        }
+        // typing more of the pattern only drops rows, no need to go through all of them again
+        let narrowed: Option<Vec<_>> = applied
+            .apply(query_state.pattern.as_ref())
+            .then(|| self.view.read().unwrap().iter().cloned().collect());
+        drop(applied);
+        let rows: Box<dyn Iterator<Item = &Arc<Connection>>> = match &narrowed {
+            Some(view) => Box::new(view.iter()),
+            None => Box::new(buffer.iter()),
+        };
         let filtered = RowFilter::new(
-            buffer.iter(),
+            rows.filter(|c| network.is_none_or(|n| Network::of(c) == Some(n))),
             &mut matcher,
             query_state.pattern.as_ref().map(FilterPattern::expr),
-            setting.columns.iter().filter_map(|&idx| CONNECTION_COLS.get(idx)),
+            setting.columns.iter().filter_map(|&idx| cols.get(idx)),
         )
         .with_text_resolver(&text_resolver);
 
-        if let Some(sort) = query_state.sort
-            && let Some(col_def) =
-                setting.columns.get(sort.col).and_then(|&col| CONNECTION_COLS.get(col))
-            && col_def.col.sortable
-        {
+        let sort_keys = query_state
+            .sort_keys()
+            .into_iter()
+            .filter_map(|sort| {
+                let col_def = setting.columns.get(sort.col).and_then(|&col| cols.get(col));
+                col_def.filter(|def| def.col.sortable).map(|def| (def, sort.dir))
+            })
+            .collect::<Vec<_>>();
+        if !sort_keys.is_empty() {
             let mut v: Vec<Arc<Connection>> = filtered.collect();
+            // ties are broken by id, so equal rows don't swap places between snapshots
             v.sort_by(|a, b| {
-                col_def.col.ordering_with_text_resolver(a, b, sort.dir, &text_resolver)
+                sort_keys
+                    .iter()
+                    .fold(std::cmp::Ordering::Equal, |ord, (col_def, dir)| {
+                        ord.then_with(|| {
+                            col_def.col.ordering_with_text_resolver(a, b, *dir, &text_resolver)
+                        })
+                    })
+                    .then_with(|| a.id.cmp(&b.id))
             });
             let mut guard = self.view.write().unwrap();
             guard.clear();
@@ -114,6 +392,11 @@ impl Connections {
         self.view.read().unwrap().get(index).cloned()
     }
 
+    /// Whether `id` was in the latest snapshot, i.e. the connection is still open.
+    pub fn is_active(&self, id: &str) -> bool {
+        self.last.lock().unwrap().contains_key(id)
+    }
+
     pub fn source_ips(&self) -> Vec<String> {
         let mut source_ips = self
             .buffer
@@ -130,25 +413,56 @@ impl Connections {
     }
 }
 
-pub(crate) struct SourceIpAliasTextResolver<'a> {
+/// Whether `next` shows the same as the idle `prev`: no bytes transferred since and the same
+/// route and metadata, e.g. a host sniffed late still makes it a change.
+fn is_unchanged_idle(prev: &Connection, next: &Connection) -> bool {
+    prev.upload_rate == 0
+        && prev.download_rate == 0
+        && prev.upload == next.upload
+        && prev.download == next.download
+        && prev.chains == next.chains
+        && prev.rule == next.rule
+        && prev.rule_payload == next.rule_payload
+        && prev.metadata == next.metadata
+}
+
+/// Display text of connection cells: source IPs are replaced by their alias, hosts and IPs are
+/// annotated with their [`Labels`], custom columns are read from the metadata. Filtering and
+/// sorting see the same text.
+pub(crate) struct ConnectionTextResolver<'a> {
     pub(crate) source_ip_alias: &'a HashMap<String, String>,
+    pub(crate) cols: &'a ConnectionCols,
 }
 
-impl TextResolver<Connection> for SourceIpAliasTextResolver<'_> {
+impl TextResolver<Connection> for ConnectionTextResolver<'_> {
     fn resolve<'row>(
         &self,
         col: &ColDef<Connection>,
-        _connection: &'row Connection,
+        connection: &'row Connection,
         text: Cow<'row, str>,
     ) -> Cow<'row, str> {
-        if col.id != "source_ip" {
-            return text;
-        }
+        let key = match col.id.as_ref() {
+            "source_ip" => {
+                if let Some(alias) = self.source_ip_alias.get(text.as_ref()) {
+                    return Cow::Owned(alias.clone());
+                }
+                text.to_string()
+            }
+            "host" => match connection
+                .metadata_str("host")
+                .or_else(|| connection.metadata_str("destinationIP"))
+            {
+                Some(key) => key.to_string(),
+                None => return text,
+            },
+            "sniff_host" | "dest" => text.to_string(),
+            _ => return self.cols.text(col, connection).unwrap_or(text),
+        };
+        Labels::annotate(&key, text)
+    }
 
-        self.source_ip_alias
-            .get(text.as_ref())
-            .map(|alias| Cow::Owned(alias.clone()))
-            .unwrap_or(text)
+    fn sort_key(&self, col: &ColDef<Connection>, connection: &Connection) -> Option<SortKey> {
+        self.cols.sort_key(col, connection)
     }
 }
 
@@ -168,13 +482,13 @@ pub fn with_alive_column(columns: impl IntoIterator<Item = usize>) -> Vec<usize>
 /// Column definitions for the connections table.
 ///
 /// User config stores column IDs, which are parsed into runtime indices in this
-/// slice. `ALIVE_COLUMN_INDEX` is runtime-only and must stay excluded from user
-/// column settings.
+/// slice, columns of `ui.custom-columns` follow at runtime, see [`connection_cols`].
+/// `ALIVE_COLUMN_INDEX` is runtime-only and must stay excluded from user column settings.
 pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     TableColDef {
         col: ColDef {
-            id: "alive",
-            title: "Alive",
+            id: Cow::Borrowed("alive"),
+            title: Cow::Borrowed("Alive"),
             filterable: false,
             sortable: true,
             accessor: |c: &Connection| {
@@ -191,8 +505,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "host",
-            title: "Host",
+            id: Cow::Borrowed("host"),
+            title: Cow::Borrowed("Host"),
             filterable: true,
             sortable: true,
             accessor: |c: &Connection| {
@@ -224,8 +538,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "rule",
-            title: "Rule",
+            id: Cow::Borrowed("rule"),
+            title: Cow::Borrowed("Rule"),
             filterable: true,
             sortable: true,
             accessor: |c: &Connection| Cow::Borrowed(c.rule.as_str()),
@@ -235,8 +549,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "chains",
-            title: "Chains",
+            id: Cow::Borrowed("chains"),
+            title: Cow::Borrowed("Chains"),
             filterable: true,
             sortable: true,
             accessor: |c: &Connection| {
@@ -250,8 +564,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "down_rate",
-            title: "DownRate",
+            id: Cow::Borrowed("down_rate"),
+            title: Cow::Borrowed("DownRate"),
             filterable: false,
             sortable: true,
             accessor: |c: &Connection| Cow::Owned(human_bytes(c.download_rate as f64, Some("/s"))),
@@ -261,8 +575,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "up_rate",
-            title: "UpRate",
+            id: Cow::Borrowed("up_rate"),
+            title: Cow::Borrowed("UpRate"),
             filterable: false,
             sortable: true,
             accessor: |c: &Connection| Cow::Owned(human_bytes(c.upload_rate as f64, Some("/s"))),
@@ -272,8 +586,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "down_total",
-            title: "DownTotal",
+            id: Cow::Borrowed("down_total"),
+            title: Cow::Borrowed("DownTotal"),
             filterable: false,
             sortable: true,
             accessor: |c: &Connection| Cow::Owned(human_bytes(c.download as f64, None)),
@@ -283,8 +597,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "up_total",
-            title: "UpTotal",
+            id: Cow::Borrowed("up_total"),
+            title: Cow::Borrowed("UpTotal"),
             filterable: false,
             sortable: true,
             accessor: |c: &Connection| Cow::Owned(human_bytes(c.upload as f64, None)),
@@ -294,8 +608,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "source_ip",
-            title: "SourceIP",
+            id: Cow::Borrowed("source_ip"),
+            title: Cow::Borrowed("SourceIP"),
             filterable: true,
             sortable: true,
             accessor: |c: &Connection| Cow::Borrowed(c.metadata_str("sourceIP").unwrap_or("-")),
@@ -305,8 +619,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "type",
-            title: "Type",
+            id: Cow::Borrowed("type"),
+            title: Cow::Borrowed("Type"),
             filterable: true,
             sortable: true,
             accessor: |c: &Connection| {
@@ -320,8 +634,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "process",
-            title: "Process",
+            id: Cow::Borrowed("process"),
+            title: Cow::Borrowed("Process"),
             filterable: true,
             sortable: true,
             accessor: |c: &Connection| {
@@ -337,8 +651,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "sniff_host",
-            title: "SniffHost",
+            id: Cow::Borrowed("sniff_host"),
+            title: Cow::Borrowed("SniffHost"),
             filterable: true,
             sortable: true,
             accessor: |c: &Connection| {
@@ -350,8 +664,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "connect_time",
-            title: "ConnectTime",
+            id: Cow::Borrowed("connect_time"),
+            title: Cow::Borrowed("ConnectTime"),
             filterable: false,
             sortable: true,
             accessor: |c: &Connection| {
@@ -368,8 +682,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "source_port",
-            title: "SourcePort",
+            id: Cow::Borrowed("source_port"),
+            title: Cow::Borrowed("SourcePort"),
             filterable: true,
             sortable: true,
             accessor: |c: &Connection| match &c.metadata["sourcePort"] {
@@ -387,8 +701,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "dest",
-            title: "Dest",
+            id: Cow::Borrowed("dest"),
+            title: Cow::Borrowed("Dest"),
             filterable: true,
             sortable: true,
             accessor: |c: &Connection| {
@@ -405,8 +719,8 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "inbound",
-            title: "Inbound",
+            id: Cow::Borrowed("inbound"),
+            title: Cow::Borrowed("Inbound"),
             filterable: true,
             sortable: true,
             accessor: |c: &Connection| {
@@ -422,6 +736,31 @@ pub static CONNECTION_COLS: &[TableColDef<Connection>] = &[
         },
         constraint: Constraint::Max(20),
     },
+    TableColDef {
+        col: ColDef {
+            id: Cow::Borrowed("network"),
+            title: Cow::Borrowed("Network"),
+            filterable: true,
+            sortable: true,
+            accessor: |c: &Connection| Cow::Borrowed(c.metadata_str("network").unwrap_or("-")),
+            sort_key: None,
+        },
+        constraint: Constraint::Max(8),
+    },
+    TableColDef {
+        col: ColDef {
+            id: Cow::Borrowed("marks"),
+            title: Cow::Borrowed("Marks"),
+            filterable: true,
+            sortable: true,
+            accessor: |c: &Connection| match c.marks() {
+                marks if marks.is_empty() => "-".into(),
+                marks => Cow::Owned(marks.join(" ")),
+            },
+            sort_key: None,
+        },
+        constraint: Constraint::Max(20),
+    },
 ];
 
 fn process_name(process_path: &str) -> &str {
@@ -432,7 +771,7 @@ const fn find_connection_index_by_id(id: &str) -> usize {
     let mut i = 0;
 
     while i < CONNECTION_COLS.len() {
-        if CONNECTION_COLS[i].col.id.eq_ignore_ascii_case(id) {
+        if CONNECTION_COLS[i].col.is_builtin(id) {
             return i;
         }
 
@@ -463,7 +802,6 @@ mod tests {
     use std::cmp::Ordering as CmpOrdering;
     use std::collections::HashMap;
     use std::num::NonZeroUsize;
-    use std::sync::atomic::AtomicBool;
     use std::sync::{Arc, Mutex as StdMutex, OnceLock};
 
     use ringbuffer::{AllocRingBuffer, RingBuffer};
@@ -473,6 +811,7 @@ mod tests {
     use super::*;
     use crate::models::sort::{SortDir, SortSpec};
     use crate::store::query::QueryState;
+    use crate::utils::test::conn;
 
     fn settings_test_lock() -> std::sync::MutexGuard<'static, ()> {
         static LOCK: OnceLock<StdMutex<()>> = OnceLock::new();
@@ -482,19 +821,7 @@ mod tests {
     fn connection(id: &str, source_ip: Option<&str>) -> Connection {
         let metadata =
             source_ip.map_or_else(|| json!({}), |source_ip| json!({ "sourceIP": source_ip }));
-        Connection {
-            id: id.into(),
-            metadata,
-            upload: 0,
-            download: 0,
-            start: None,
-            chains: Vec::new(),
-            rule: String::new(),
-            rule_payload: String::new(),
-            inactive: Arc::new(AtomicBool::new(false)),
-            upload_rate: 0,
-            download_rate: 0,
-        }
+        conn(id).metadata(metadata).build()
     }
 
     fn connection_col_index(id: &str) -> usize {
@@ -508,6 +835,103 @@ mod tests {
         &CONNECTION_COLS[connection_col_index(id)].col
     }
 
+    #[test]
+    fn push_reuses_unchanged_connections() {
+        let store = Connections::new(NonZeroUsize::new(10).unwrap());
+        let with_bytes = |id: &str, download: u64| Connection { download, ..connection(id, None) };
+
+        let position = |id: &str| store.with_view(|v| v.iter().position(|c| c.id == id));
+
+        let diff = store.push(false, vec![with_bytes("a", 0), with_bytes("b", 0)]);
+        assert_eq!(diff, SnapshotDiff { added: 2, removed: 0, changed: 0 });
+        store.compute_view();
+        let a = store.get(position("a").unwrap()).unwrap();
+
+        // `a` stays idle, `b` transferred bytes
+        let diff = store.push(false, vec![with_bytes("a", 0), with_bytes("b", 10)]);
+        assert_eq!(diff, SnapshotDiff { added: 0, removed: 0, changed: 1 });
+        store.compute_view();
+        assert!(Arc::ptr_eq(&a, &store.get(position("a").unwrap()).unwrap()));
+        assert_eq!(store.get(position("b").unwrap()).unwrap().download_rate, 10);
+
+        // the rate of `b` drops to zero, which is a change as well
+        let diff = store.push(false, vec![with_bytes("b", 10), with_bytes("c", 0)]);
+        assert_eq!(diff, SnapshotDiff { added: 1, removed: 1, changed: 1 });
+        assert!(store.push(false, vec![with_bytes("b", 10), with_bytes("c", 0)]).is_empty());
+        store.compute_view();
+        assert_eq!(position("a"), None);
+
+        // idle but routed or sniffed differently is not stale
+        let c = store.get(position("c").unwrap()).unwrap();
+        let mut rerouted = with_bytes("c", 0);
+        rerouted.chains = vec!["other".into()];
+        let diff = store.push(false, vec![with_bytes("b", 10), rerouted]);
+        assert_eq!(diff, SnapshotDiff { added: 0, removed: 0, changed: 1 });
+        store.compute_view();
+        let c_next = store.get(position("c").unwrap()).unwrap();
+        assert!(!Arc::ptr_eq(&c, &c_next));
+        assert_eq!(c_next.chains, ["other"]);
+    }
+
+    #[test]
+    fn capture_retention_drops_old_closed_connections() {
+        let store = Connections::new(NonZeroUsize::new(10).unwrap())
+            .with_retention(CaptureRetention { max_age: 60, max_closed: 2 });
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let ids = || store.buffer.read().unwrap().iter().map(|c| c.id.clone()).collect::<Vec<_>>();
+        let conns = |ids: &[&str]| ids.iter().map(|id| connection(id, None)).collect::<Vec<_>>();
+
+        store.push_at(true, conns(&["a", "b", "c", "d"]), at(0));
+        store.push_at(true, conns(&["c", "d"]), at(10));
+        assert_eq!(store.closed_count(), 2);
+        // `c` and `d` close later, the older closed `a` and `b` go first
+        store.push_at(true, conns(&["e"]), at(20));
+        assert_eq!(store.closed_count(), 2);
+        assert_eq!(ids(), vec!["e", "c", "d"]);
+
+        store.push_at(true, conns(&["e"]), at(79));
+        assert_eq!(store.closed_count(), 2);
+        store.push_at(true, conns(&["e"]), at(80));
+        assert_eq!(store.closed_count(), 0);
+        assert_eq!(ids(), vec!["e"]);
+
+        // leaving capture mode drops the closed ones right away
+        store.push_at(true, conns(&["f"]), at(90));
+        store.push_at(false, conns(&["f"]), at(91));
+        assert_eq!(store.closed_count(), 0);
+
+        // clearing keeps capturing the active ones
+        store.push_at(true, conns(&["g", "h"]), at(100));
+        store.push_at(true, conns(&["h"]), at(101));
+        assert_eq!(store.clear_closed(), 2);
+        assert_eq!((store.closed_count(), ids()), (0, vec!["h".to_string()]));
+        store.push_at(true, conns(&["h", "i"]), at(102));
+        assert_eq!(ids(), vec!["h", "i"]);
+    }
+
+    #[test]
+    fn new_connections_are_tracked_briefly() {
+        let store = Connections::new(NonZeroUsize::new(10).unwrap());
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let conns = |ids: &[&str]| ids.iter().map(|id| connection(id, None)).collect::<Vec<_>>();
+
+        // the initial snapshot is not news
+        store.push_at(false, conns(&["a"]), at(0));
+        assert_eq!(store.new_for("a", at(0)), None);
+
+        store.push_at(false, conns(&["a", "b"]), at(1_000));
+        assert_eq!(store.new_for("a", at(1_500)), None);
+        assert_eq!(store.new_for("b", at(1_500)), Some(Duration::from_millis(500)));
+        // still listed, but no longer new
+        store.push_at(false, conns(&["a", "b"]), at(2_000));
+        assert_eq!(store.new_for("b", at(3_000)), None);
+        assert!(store.first_seen.lock().unwrap().contains_key("b"));
+        store.push_at(false, conns(&["a", "b"]), at(3_000));
+        assert!(store.first_seen.lock().unwrap().is_empty());
+    }
+
     #[test]
     fn test_ring_buffer() {
         let mut buffer = AllocRingBuffer::new(2);
@@ -625,6 +1049,81 @@ mod tests {
         });
     }
 
+    #[test]
+    fn network_filter_and_totals() {
+        let _guard = settings_test_lock();
+        let store = Connections::new(NonZeroUsize::new(10).unwrap());
+        let with_network = |id: &str, network: &str, download: u64| Connection {
+            metadata: json!({ "network": network }),
+            download,
+            ..connection(id, None)
+        };
+        store.push(false, vec![with_network("1", "tcp", 0), with_network("2", "udp", 0)]);
+        store.push(false, vec![with_network("1", "tcp", 100), with_network("2", "udp", 30)]);
+
+        let columns = DEFAULT_CONNECTION_COL_INDICES.to_vec();
+        ConnectionsSetting::update(|setting| {
+            setting.columns = columns.clone();
+            setting.query_state = QueryState::new(columns.len());
+            setting.source_ip_alias.clear();
+            setting.network = Some(Network::Udp);
+        });
+        store.compute_view();
+        let ids = store.with_view(|v| v.iter().map(|c| c.id.clone()).collect::<Vec<_>>());
+        assert_eq!(ids, ["2"]);
+        let totals = store.network_totals();
+        assert_eq!(totals.tcp, NetworkShare { conns: 1, up_rate: 0, down_rate: 100 });
+        assert_eq!(totals.udp, NetworkShare { conns: 1, up_rate: 0, down_rate: 30 });
+
+        ConnectionsSetting::update(|setting| setting.network = Network::cycle(setting.network));
+        assert_eq!(ConnectionsSetting::snapshot().network, None);
+        store.compute_view();
+        assert_eq!(store.with_view(|v| v.len()), 2);
+    }
+
+    #[test]
+    fn compute_view_narrows_until_the_snapshot_or_setting_changes() {
+        let _guard = settings_test_lock();
+        let store = Connections::new(NonZeroUsize::new(10).unwrap());
+        let with_host = |id: &str, host: &str| conn(id).metadata(json!({ "host": host })).build();
+        store.push(false, vec![with_host("1", "foo.com"), with_host("2", "food.com")]);
+        let ids = || store.with_view(|v| v.iter().map(|c| c.id.clone()).collect::<Vec<_>>());
+        let type_pattern = |pattern: &str| {
+            ConnectionsSetting::update(|setting| {
+                setting.query_state.set_pattern(Some(pattern.to_owned()));
+            });
+            store.compute_view();
+        };
+
+        let columns = DEFAULT_CONNECTION_COL_INDICES.to_vec();
+        ConnectionsSetting::update(|setting| {
+            setting.columns = columns.clone();
+            setting.query_state = QueryState::new(columns.len());
+            setting.source_ip_alias.clear();
+            setting.network = None;
+        });
+        type_pattern("foo");
+        type_pattern("food");
+        assert_eq!(ids(), ["2"]);
+
+        // a new snapshot is filtered in full
+        store.push(false, vec![with_host("1", "foo.com"), with_host("3", "foodie.com")]);
+        type_pattern("foodi");
+        assert_eq!(ids(), ["3"]);
+
+        // and so are other columns, hiding the host and showing it again finds `3` again
+        let set_columns = |columns: Vec<usize>| {
+            ConnectionsSetting::update(|setting| setting.columns = columns);
+            store.compute_view();
+        };
+        set_columns(with_alive_column([connection_col_index("rule")]));
+        assert!(ids().is_empty());
+        set_columns(columns.clone());
+        assert_eq!(ids(), ["3"]);
+
+        ConnectionsSetting::update(|setting| setting.query_state = QueryState::new(columns.len()));
+    }
+
     #[test]
     fn source_ip_alias_filters_and_sorts_view() {
         let _guard = settings_test_lock();
@@ -692,6 +1191,54 @@ mod tests {
         });
     }
 
+    #[test]
+    fn sort_ties_are_broken_by_id() {
+        let _guard = settings_test_lock();
+        let store = Connections::new(NonZeroUsize::new(10).unwrap());
+        let columns = with_alive_column([connection_col_index("source_ip")]);
+        ConnectionsSetting::update(|setting| {
+            setting.columns = columns.clone();
+            setting.query_state = QueryState::new(columns.len());
+            setting.query_state.sort = Some(SortSpec { col: 1, dir: SortDir::Desc });
+            setting.source_ip_alias.clear();
+        });
+        let ids = |store: &Connections| {
+            store.with_view(|records| records.iter().map(|c| c.id.clone()).collect::<Vec<_>>())
+        };
+
+        let conns = |ids: &[&str]| ids.iter().map(|id| connection(id, Some("10.0.0.1"))).collect();
+        store.push(false, conns(&["b", "c", "a"]));
+        store.compute_view();
+        assert_eq!(ids(&store), ["a", "b", "c"]);
+        // arrival order changes, equal rows stay where they were
+        store.push(false, conns(&["c", "a", "b"]));
+        store.compute_view();
+        assert_eq!(ids(&store), ["a", "b", "c"]);
+
+        ConnectionsSetting::update(|setting| {
+            let columns = DEFAULT_CONNECTION_COL_INDICES.to_vec();
+            setting.columns = columns.clone();
+            setting.query_state = QueryState::new(columns.len());
+        });
+    }
+
+    #[test]
+    fn store_of_records_holds_all_of_them() {
+        let closed = connection("closed", None);
+        closed.inactive.store(true, Ordering::Relaxed);
+        let mut records = vec![Arc::new(closed)];
+        // more than the default capacity of the live store
+        records.extend((0..600).map(|idx| Arc::new(connection(&idx.to_string(), None))));
+        let store = Connections::of_records(records);
+        assert_eq!(store.snapshot().len(), 601);
+        assert!(store.is_active("0"));
+        assert!(!store.is_active("closed"));
+        assert_eq!(store.closed_count(), 1);
+        assert_eq!(store.load_state(), LoadState::Loaded);
+
+        assert!(Connections::of_records(vec![]).snapshot().is_empty());
+    }
+
     #[test]
     fn connect_time_sorts_by_elapsed_duration() {
         let mut newer = connection("newer", None);
@@ -4,11 +4,12 @@ use std::sync::{Arc, OnceLock, RwLock};
 
 use anyhow::{Result, anyhow};
 
-use crate::config::{ConnectionsSortConfig, ConnectionsUiConfig};
+use crate::config::{ConnectionsSortConfig, ConnectionsSortKey, ConnectionsUiConfig};
 use crate::models::sort::SortSpec;
 use crate::store::connections::{
-    ALIVE_COLUMN_INDEX, CONNECTION_COLS, DEFAULT_CONNECTION_COL_INDICES, with_alive_column,
+    ALIVE_COLUMN_INDEX, DEFAULT_CONNECTION_COL_INDICES, Network, with_alive_column,
 };
+use crate::store::custom_columns::connection_cols;
 use crate::store::query::QueryState;
 
 pub static GLOBAL_CONNECTION_SETTING: OnceLock<RwLock<Arc<ConnectionsSetting>>> = OnceLock::new();
@@ -18,21 +19,24 @@ pub struct ConnectionsSetting {
     /// Runtime filtering and sorting state.
     ///
     /// `query_state.sort.col` is a position in [`Self::columns`],
-    /// not an index into [`CONNECTION_COLS`].
+    /// not an index into [`connection_cols`].
     pub query_state: QueryState,
 
     /// Ordered connection columns displayed at runtime.
     ///
-    /// Each value is a stable index into [`CONNECTION_COLS`], while its position in this vector
+    /// Each value is a stable index into [`connection_cols`], while its position in this vector
     /// is the column's visible index. The runtime-only Alive column is included here even though
     /// it is not stored in user configuration.
     pub columns: Vec<usize>,
 
-    /// User-defined column widths keyed by stable indices into [`CONNECTION_COLS`].
+    /// User-defined column widths keyed by stable indices into [`connection_cols`].
     pub column_widths: HashMap<usize, u16>,
 
     /// Display aliases keyed by source IP address.
     pub source_ip_alias: HashMap<String, String>,
+
+    /// Only show connections of this network, runtime only.
+    pub network: Option<Network>,
 }
 
 impl ConnectionsSetting {
@@ -44,6 +48,7 @@ impl ConnectionsSetting {
                 columns,
                 column_widths: Default::default(),
                 source_ip_alias: Default::default(),
+                network: None,
             };
 
             RwLock::new(Arc::new(setting))
@@ -66,28 +71,35 @@ impl TryFrom<&ConnectionsUiConfig> for ConnectionsSetting {
     type Error = anyhow::Error;
 
     fn try_from(value: &ConnectionsUiConfig) -> Result<Self> {
+        let cols = connection_cols();
+        let custom = cols.custom();
         let columns = with_alive_column(
             value
                 .columns
                 .as_deref()
-                .map(ConnectionsUiConfig::parse_connections_columns)
+                .map(|columns| ConnectionsUiConfig::parse_connections_columns(columns, custom))
                 .transpose()?
                 .unwrap_or_else(|| DEFAULT_CONNECTION_COL_INDICES.to_vec()),
         );
-        let sort = value
+        // keys of hidden columns are skipped, the last key is put under the column cursor
+        let mut pinned = value
             .sort
             .as_ref()
-            .map(ConnectionsUiConfig::parse_connections_sort)
+            .map(|sort| ConnectionsUiConfig::parse_connections_sort(sort, custom))
             .transpose()?
-            .and_then(|sort| {
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|sort| {
                 columns
                     .iter()
                     .position(|&col| col == sort.col)
                     .map(|col| SortSpec { col, dir: sort.dir })
-            });
-        let query_state = QueryState { pattern: None, sort, max_cols: columns.len() };
+            })
+            .collect::<Vec<_>>();
+        let sort = pinned.pop();
+        let query_state = QueryState { pattern: None, sort, pinned, max_cols: columns.len() };
         let column_widths =
-            ConnectionsUiConfig::parse_connections_column_widths(&value.column_widths)?;
+            ConnectionsUiConfig::parse_connections_column_widths(&value.column_widths, custom)?;
         Ok(Self {
             columns,
             query_state,
@@ -97,6 +109,7 @@ impl TryFrom<&ConnectionsUiConfig> for ConnectionsSetting {
                 .iter()
                 .map(|(source_ip, alias)| (source_ip.clone(), alias.clone()))
                 .collect(),
+            network: None,
         })
     }
 }
@@ -105,15 +118,15 @@ impl TryFrom<&ConnectionsSetting> for ConnectionsUiConfig {
     type Error = anyhow::Error;
 
     fn try_from(value: &ConnectionsSetting) -> Result<Self> {
+        let cols = connection_cols();
         let columns = value
             .columns
             .iter()
             .copied()
             .filter(|&idx| idx != ALIVE_COLUMN_INDEX)
             .map(|idx| {
-                CONNECTION_COLS
-                    .get(idx)
-                    .map(|def| def.col.title.to_owned())
+                cols.get(idx)
+                    .map(|def| def.col.title.to_string())
                     .ok_or_else(|| anyhow!("connection column index {idx} does not exist"))
             })
             .collect::<Result<Vec<_>>>()?;
@@ -122,10 +135,10 @@ impl TryFrom<&ConnectionsSetting> for ConnectionsUiConfig {
             .column_widths
             .iter()
             .map(|(&idx, &width)| {
-                let title = CONNECTION_COLS
+                let title = cols
                     .get(idx)
                     .filter(|_| idx != ALIVE_COLUMN_INDEX)
-                    .map(|def| def.col.title.to_owned())
+                    .map(|def| def.col.title.to_string())
                     .ok_or_else(|| anyhow!("connection column index {idx} does not exist"))?;
                 let width = NonZeroU16::new(width)
                     .ok_or_else(|| anyhow!("connection column width must be greater than zero"))?;
@@ -133,26 +146,28 @@ impl TryFrom<&ConnectionsSetting> for ConnectionsUiConfig {
             })
             .collect::<Result<BTreeMap<_, _>>>()?;
 
-        let sort = match value.query_state.sort {
-            None => None,
-            Some(sort) => {
-                let runtime_col =
-                    value.columns.get(sort.col).cloned().ok_or_else(|| {
-                        anyhow!("connection sort column {} does not exist", sort.col)
-                    })?;
-                if runtime_col == ALIVE_COLUMN_INDEX {
-                    None
-                } else {
-                    let field = CONNECTION_COLS
-                        .get(runtime_col)
-                        .map(|def| def.col.title.to_owned())
-                        .ok_or_else(|| {
-                            anyhow!("connection column index {runtime_col} does not exist")
-                        })?;
-                    Some(ConnectionsSortConfig { field, dir: sort.dir })
-                }
+        let mut keys = vec![];
+        for sort in value.query_state.sort_keys() {
+            let runtime_col = value
+                .columns
+                .get(sort.col)
+                .cloned()
+                .ok_or_else(|| anyhow!("connection sort column {} does not exist", sort.col))?;
+            if runtime_col == ALIVE_COLUMN_INDEX {
+                continue;
             }
-        };
+            let field = cols
+                .get(runtime_col)
+                .map(|def| def.col.title.to_string())
+                .ok_or_else(|| anyhow!("connection column index {runtime_col} does not exist"))?;
+            keys.push(ConnectionsSortKey { field, dir: sort.dir });
+        }
+        let mut keys = keys.into_iter();
+        let sort = keys.next().map(|primary| ConnectionsSortConfig {
+            field: primary.field,
+            dir: primary.dir,
+            then_by: keys.collect(),
+        });
 
         Ok(ConnectionsUiConfig {
             columns: Some(columns),
@@ -0,0 +1,160 @@
+use std::borrow::Cow;
+use std::ops::Deref;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use ratatui::layout::Constraint;
+use serde_json::Value;
+
+use crate::config::{CustomColumnConfig, CustomColumnFormat};
+use crate::models::Connection;
+use crate::store::connections::CONNECTION_COLS;
+use crate::utils::byte_size::human_bytes;
+use crate::utils::columns::{ColDef, SortKey, TableColDef};
+use crate::utils::number::format_thousands;
+
+/// Ids of custom columns are this prefix followed by the position in `ui.custom-columns`.
+const ID_PREFIX: &str = "custom:";
+
+/// [`CONNECTION_COLS`] followed by the columns of `ui.custom-columns`, the stable index of custom
+/// column `i` is `CONNECTION_COLS.len() + i`.
+pub struct ConnectionCols {
+    cols: Vec<TableColDef<Connection>>,
+    custom: Vec<CustomColumnConfig>,
+}
+
+static REGISTRY: LazyLock<RwLock<Arc<ConnectionCols>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(ConnectionCols::new(&[]))));
+
+/// Snapshot of the registered columns, taken once per frame or view computation.
+pub fn connection_cols() -> Arc<ConnectionCols> {
+    Arc::clone(&REGISTRY.read().unwrap())
+}
+
+/// Replace the custom columns, on startup and when the config file changed.
+pub fn register(custom: &[CustomColumnConfig]) {
+    let mut registry = REGISTRY.write().unwrap();
+    if registry.custom != custom {
+        *registry = Arc::new(ConnectionCols::new(custom));
+    }
+}
+
+impl Deref for ConnectionCols {
+    type Target = [TableColDef<Connection>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.cols
+    }
+}
+
+impl ConnectionCols {
+    fn new(custom: &[CustomColumnConfig]) -> Self {
+        let cols =
+            CONNECTION_COLS.iter().cloned().chain(custom.iter().enumerate().map(|(i, column)| {
+                TableColDef {
+                    col: ColDef {
+                        id: Cow::Owned(format!("{ID_PREFIX}{i}")),
+                        title: Cow::Owned(column.title.clone()),
+                        filterable: true,
+                        sortable: true,
+                        // resolved by `ConnectionTextResolver`, see [`ConnectionCols::text`]
+                        accessor: |_| Cow::Borrowed(""),
+                        sort_key: None,
+                    },
+                    constraint: Constraint::Max(16),
+                }
+            }));
+        Self { cols: cols.collect(), custom: custom.to_vec() }
+    }
+
+    /// The registered `ui.custom-columns`.
+    pub fn custom(&self) -> &[CustomColumnConfig] {
+        &self.custom
+    }
+
+    fn lookup(&self, col: &ColDef<Connection>) -> Option<&CustomColumnConfig> {
+        let index = col.id.strip_prefix(ID_PREFIX)?.parse::<usize>().ok()?;
+        self.custom.get(index)
+    }
+
+    /// Cell text of a custom column, `None` for built-in columns.
+    pub fn text<'a>(
+        &self,
+        col: &ColDef<Connection>,
+        connection: &'a Connection,
+    ) -> Option<Cow<'a, str>> {
+        let column = self.lookup(col)?;
+        Some(format_value(column, connection.metadata.pointer(&column.pointer)))
+    }
+
+    /// Numeric custom columns sort by value, values that are not unsigned integers come first.
+    pub fn sort_key(&self, col: &ColDef<Connection>, connection: &Connection) -> Option<SortKey> {
+        let column = self.lookup(col).filter(|column| column.format != CustomColumnFormat::Text)?;
+        let value = connection.metadata.pointer(&column.pointer).and_then(Value::as_u64);
+        Some(value.map_or(SortKey::Bool(false), SortKey::U64))
+    }
+}
+
+fn format_value<'a>(column: &CustomColumnConfig, value: Option<&'a Value>) -> Cow<'a, str> {
+    match (column.format, value) {
+        (_, None | Some(Value::Null)) => Cow::Borrowed("-"),
+        (_, Some(Value::String(s))) if s.is_empty() => Cow::Borrowed("-"),
+        (CustomColumnFormat::Bytes, Some(Value::Number(n))) if n.is_u64() => {
+            Cow::Owned(human_bytes(n.as_u64().unwrap_or_default() as f64, None))
+        }
+        (CustomColumnFormat::Number, Some(Value::Number(n))) if n.is_u64() => {
+            Cow::Owned(format_thousands(n.as_u64().unwrap_or_default()))
+        }
+        (_, Some(Value::String(s))) => Cow::Borrowed(s),
+        (_, Some(value)) => Cow::Owned(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::test::conn;
+
+    fn column(pointer: &str, format: CustomColumnFormat) -> CustomColumnConfig {
+        CustomColumnConfig { title: "Custom".into(), pointer: pointer.into(), format }
+    }
+
+    #[test]
+    fn custom_columns_follow_the_built_in_ones() {
+        let cols = ConnectionCols::new(&[column("/dscp", CustomColumnFormat::Number)]);
+        assert_eq!(cols.len(), CONNECTION_COLS.len() + 1);
+        let custom = &cols[CONNECTION_COLS.len()].col;
+        assert_eq!(custom.title, "Custom");
+
+        let connection = conn("a").metadata(json!({ "dscp": 46 })).build();
+        assert_eq!(cols.text(custom, &connection).as_deref(), Some("46"));
+        assert!(matches!(cols.sort_key(custom, &connection), Some(SortKey::U64(46))));
+        assert_eq!(cols.text(&cols[0].col, &connection), None);
+    }
+
+    #[test]
+    fn values_are_formatted_by_column_format() {
+        let metadata = json!({
+            "dscp": 46,
+            "sniffHost": "",
+            "uid": null,
+            "size": 2048,
+            "tags": ["a", "b"],
+            "nested": { "name": "x" },
+        });
+        let text = |pointer: &str, format| {
+            format_value(&column(pointer, format), metadata.pointer(pointer)).into_owned()
+        };
+
+        assert_eq!(text("/nested/name", CustomColumnFormat::Text), "x");
+        assert_eq!(text("/dscp", CustomColumnFormat::Text), "46");
+        assert_eq!(text("/tags", CustomColumnFormat::Text), r#"["a","b"]"#);
+        assert_eq!(text("/tags/1", CustomColumnFormat::Text), "b");
+        assert_eq!(text("/size", CustomColumnFormat::Bytes), human_bytes(2048.0, None));
+        assert_eq!(text("/nested/name", CustomColumnFormat::Bytes), "x");
+        for missing in ["/sniffHost", "/uid", "/missing"] {
+            assert_eq!(text(missing, CustomColumnFormat::Number), "-");
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+use ringbuffer::{AllocRingBuffer, RingBuffer};
+use time::OffsetDateTime;
+
+use crate::app_message::AppMessage;
+use crate::components::ComponentId;
+
+const CAPACITY: usize = 50;
+
+static ERRORS: LazyLock<Mutex<AllocRingBuffer<ErrorEntry>>> =
+    LazyLock::new(|| Mutex::new(AllocRingBuffer::new(CAPACITY)));
+static UNREAD: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone)]
+pub struct ErrorEntry {
+    pub time: OffsetDateTime,
+    /// Component that reported the error
+    pub source: ComponentId,
+    pub title: &'static str,
+    pub message: Box<str>,
+}
+
+/// The last [`CAPACITY`] errors shown to the user, so they can be reviewed after the message box
+/// was closed.
+pub struct ErrorLog;
+
+impl ErrorLog {
+    pub fn record(source: ComponentId, err: &AppMessage) {
+        let entry = ErrorEntry {
+            time: OffsetDateTime::now_utc(),
+            source,
+            title: err.title,
+            message: err.message.clone(),
+        };
+        ERRORS.lock().unwrap().enqueue(entry);
+        let _ = UNREAD
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| Some((n + 1).min(CAPACITY)));
+    }
+
+    /// Errors, newest first.
+    pub fn snapshot() -> Vec<ErrorEntry> {
+        ERRORS.lock().unwrap().iter().rev().cloned().collect()
+    }
+
+    /// Errors recorded since the log was last viewed.
+    pub fn unread() -> usize {
+        UNREAD.load(Ordering::Relaxed)
+    }
+
+    pub fn mark_read() {
+        UNREAD.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Serializes the tests recording to and reading the global log.
+#[cfg(test)]
+pub fn test_lock() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_log_is_bounded_and_tracks_unread() {
+        let _guard = test_lock();
+        ErrorLog::mark_read();
+        for i in 0..CAPACITY + 5 {
+            ErrorLog::record(ComponentId::Rules, &("Load rules", format!("error {i}")).into());
+        }
+        let errors = ErrorLog::snapshot();
+        assert_eq!(errors.len(), CAPACITY);
+        assert_eq!(&*errors[0].message, format!("error {}", CAPACITY + 4));
+        assert_eq!(errors[0].source, ComponentId::Rules);
+        assert_eq!(ErrorLog::unread(), CAPACITY);
+        ErrorLog::mark_read();
+        assert_eq!(ErrorLog::unread(), 0);
+    }
+}
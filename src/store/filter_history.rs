@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use std::sync::{OnceLock, RwLock};
+
+use crate::config::FilterHistoryConfig;
+
+pub static GLOBAL_FILTER_HISTORY: OnceLock<RwLock<FilterHistory>> = OnceLock::new();
+
+/// Submitted filter patterns of each tab (keyed by tab name), oldest first.
+#[derive(Debug, Default, Clone)]
+pub struct FilterHistory {
+    persist: bool,
+    entries: BTreeMap<String, Vec<String>>,
+}
+
+impl FilterHistory {
+    pub const CAPACITY: usize = 50;
+
+    pub fn global() -> &'static RwLock<Self> {
+        GLOBAL_FILTER_HISTORY.get_or_init(Default::default)
+    }
+
+    /// Apply the (re)loaded config, history of the current session is kept.
+    pub fn configure(&mut self, config: &FilterHistoryConfig) {
+        self.persist = config.persist;
+        if self.persist && self.entries.is_empty() {
+            self.entries = config.entries.clone();
+        }
+    }
+
+    /// Snapshot to save into the runtime config, `None` unless persisting is enabled.
+    pub fn to_config(&self) -> Option<FilterHistoryConfig> {
+        self.persist.then(|| FilterHistoryConfig { persist: true, entries: self.entries.clone() })
+    }
+
+    /// Record a submitted pattern, a repeated one moves to the newest position.
+    /// Returns whether persisted history changed.
+    pub fn push(&mut self, tab: &str, pattern: &str) -> bool {
+        let entries = self.entries.entry(tab.to_string()).or_default();
+        if entries.last().is_some_and(|last| last == pattern) {
+            return false;
+        }
+        entries.retain(|p| p != pattern);
+        entries.push(pattern.to_string());
+        if entries.len() > Self::CAPACITY {
+            entries.drain(..entries.len() - Self::CAPACITY);
+        }
+        self.persist
+    }
+
+    /// The `back`-th newest pattern of `tab`, 0 is the newest.
+    pub fn get(&self, tab: &str, back: usize) -> Option<&str> {
+        let entries = self.entries.get(tab)?;
+        entries.len().checked_sub(back + 1).map(|idx| entries[idx].as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_dedups_and_caps_history() {
+        let mut history = FilterHistory::default();
+        assert!(!history.push("Connections", "a"));
+        history.push("Connections", "b");
+        history.push("Connections", "a");
+        history.push("Logs", "c");
+        assert_eq!(history.get("Connections", 0), Some("a"));
+        assert_eq!(history.get("Connections", 1), Some("b"));
+        assert_eq!(history.get("Connections", 2), None);
+        assert_eq!(history.get("Logs", 0), Some("c"));
+
+        for i in 0..FilterHistory::CAPACITY + 5 {
+            history.push("Rules", &i.to_string());
+        }
+        assert_eq!(history.get("Rules", 0), Some("54"));
+        assert_eq!(history.get("Rules", FilterHistory::CAPACITY - 1), Some("5"));
+        assert_eq!(history.get("Rules", FilterHistory::CAPACITY), None);
+    }
+
+    #[test]
+    fn configure_keeps_session_history() {
+        let mut history = FilterHistory::default();
+        history.push("Connections", "a");
+        let config = FilterHistoryConfig {
+            persist: true,
+            entries: BTreeMap::from([("Connections".into(), vec!["x".into()])]),
+        };
+        history.configure(&config);
+        assert_eq!(history.get("Connections", 0), Some("a"));
+        assert!(history.push("Connections", "b"));
+        assert_eq!(history.to_config().unwrap().entries["Connections"], vec!["a", "b"]);
+    }
+}
@@ -0,0 +1,122 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::app_message::AppMessage;
+
+#[derive(Debug)]
+struct State {
+    started: Option<Instant>,
+    /// Duration of the last successful update, the estimate of the next one
+    last: Option<Duration>,
+}
+
+/// The running GEO database update, shared by every place triggering one so only one runs at a
+/// time. The core reports no progress, the elapsed time is shown against the previous duration.
+#[derive(Debug)]
+pub struct GeoUpdate {
+    state: Mutex<State>,
+}
+
+impl GeoUpdate {
+    const fn new() -> Self {
+        Self { state: Mutex::new(State { started: None, last: None }) }
+    }
+
+    fn global() -> &'static Self {
+        static INSTANCE: GeoUpdate = GeoUpdate::new();
+        &INSTANCE
+    }
+
+    /// Mark an update started, `None` while another one is running.
+    pub fn start() -> Option<GeoUpdateGuard> {
+        Self::global().try_start()
+    }
+
+    /// Message shown when another update is asked for while one is running.
+    pub fn running_message() -> AppMessage {
+        let progress =
+            Self::progress().map(|(elapsed, estimate)| format_progress(elapsed, estimate));
+        let msg = format!(
+            "A GEO update is already running ({}), wait for it to finish.",
+            progress.unwrap_or_default()
+        );
+        AppMessage::from(("Update GEO", msg)).msg_box_size(40, 20)
+    }
+
+    /// Elapsed time of the running update and the duration of the previous one.
+    pub fn progress() -> Option<(Duration, Option<Duration>)> {
+        Self::global().running()
+    }
+
+    fn try_start(&'static self) -> Option<GeoUpdateGuard> {
+        let mut state = self.state.lock().unwrap();
+        if state.started.is_some() {
+            return None;
+        }
+        let started = Instant::now();
+        state.started = Some(started);
+        Some(GeoUpdateGuard { update: self, started })
+    }
+
+    fn running(&self) -> Option<(Duration, Option<Duration>)> {
+        let state = self.state.lock().unwrap();
+        state.started.map(|started| (started.elapsed(), state.last))
+    }
+}
+
+/// Held by the task running the update, dropping it without [`Self::finish`], e.g. a cancelled
+/// task, still ends the update.
+pub struct GeoUpdateGuard {
+    update: &'static GeoUpdate,
+    started: Instant,
+}
+
+impl GeoUpdateGuard {
+    /// End the update, returns its duration.
+    pub fn finish(self, succeeded: bool) -> Duration {
+        let elapsed = self.started.elapsed();
+        if succeeded {
+            self.update.state.lock().unwrap().last = Some(elapsed);
+        }
+        elapsed
+    }
+}
+
+impl Drop for GeoUpdateGuard {
+    fn drop(&mut self) {
+        self.update.state.lock().unwrap().started = None;
+    }
+}
+
+/// `12s`, or `12s/~40s` with the estimate of the previous update.
+pub fn format_progress(elapsed: Duration, estimate: Option<Duration>) -> String {
+    match estimate {
+        Some(estimate) => format!("{}s/~{}s", elapsed.as_secs(), estimate.as_secs().max(1)),
+        None => format!("{}s", elapsed.as_secs()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_update_at_a_time() {
+        static UPDATE: GeoUpdate = GeoUpdate::new();
+        let guard = UPDATE.try_start().unwrap();
+        assert!(UPDATE.try_start().is_none());
+        assert!(matches!(UPDATE.running(), Some((_, None))));
+        guard.finish(true);
+        assert_eq!(UPDATE.running(), None);
+
+        // a failed update keeps the previous estimate
+        let guard = UPDATE.try_start().unwrap();
+        assert!(matches!(UPDATE.running(), Some((_, Some(_)))));
+        drop(guard);
+        assert!(UPDATE.try_start().is_some());
+
+        let secs = Duration::from_secs;
+        assert_eq!(format_progress(secs(12), Some(Duration::from_millis(300))), "12s/~1s");
+        assert_eq!(format_progress(secs(12), None), "12s");
+    }
+}
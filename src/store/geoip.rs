@@ -0,0 +1,128 @@
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use tracing::{info, warn};
+
+use crate::config::GeoIpConfig;
+use crate::models::Connection;
+use crate::utils::mmdb::Mmdb;
+use crate::utils::path::expand_home;
+
+static DB: LazyLock<Mutex<Database>> = LazyLock::new(Default::default);
+
+#[derive(Default)]
+struct Database {
+    path: Option<PathBuf>,
+    /// Bumped on every (re)load so a slower earlier one can't overwrite a later one
+    generation: u64,
+    /// Set once loaded, `None` while loading or if that failed
+    opened: Option<Arc<Mmdb>>,
+}
+
+/// A connection matched by a `GEOIP` rule whose destination the local database places elsewhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeoMismatch {
+    /// Country of the rule payload, as decided by the core's database
+    pub rule: String,
+    /// Country of the destination IP in the local database
+    pub local: String,
+}
+
+/// Local copy of the core's GeoIP database, used to spot a stale database on either side.
+pub struct GeoIp;
+
+impl GeoIp {
+    pub fn configure(config: &GeoIpConfig) {
+        let path = Some(config.mmdb.trim()).filter(|p| !p.is_empty()).map(expand_home);
+        DB.lock().unwrap().path = path;
+        Self::reload();
+    }
+
+    /// Load the database again in the background, e.g. after the core updated it. Lookups find
+    /// nothing until it is loaded.
+    pub fn reload() {
+        let mut db = DB.lock().unwrap();
+        db.generation += 1;
+        db.opened = None;
+        let Some(path) = db.path.clone() else {
+            return;
+        };
+        let generation = db.generation;
+        drop(db);
+        tokio::task::spawn_blocking(move || {
+            let opened = match Mmdb::open(&path) {
+                Ok(db) => {
+                    info!(path = %path.display(), "GeoIP database opened");
+                    Some(Arc::new(db))
+                }
+                Err(e) => {
+                    warn!(error = ?e, "GeoIP database unavailable, mismatches are not checked");
+                    None
+                }
+            };
+            let mut db = DB.lock().unwrap();
+            if db.generation == generation {
+                db.opened = opened;
+            }
+        });
+    }
+
+    fn db() -> Option<Arc<Mmdb>> {
+        DB.lock().unwrap().opened.clone()
+    }
+
+    pub fn mismatch(conn: &Connection) -> Option<GeoMismatch> {
+        let db = Self::db()?;
+        mismatch(conn, |ip| db.country(ip).ok().flatten())
+    }
+
+    /// Country code of `ip` in the local database, `None` if unknown or not configured.
+    pub fn country(ip: IpAddr) -> Option<String> {
+        Self::db()?.country(ip).ok().flatten()
+    }
+}
+
+fn mismatch(conn: &Connection, lookup: impl Fn(IpAddr) -> Option<String>) -> Option<GeoMismatch> {
+    if !conn.rule.eq_ignore_ascii_case("GEOIP") {
+        return None;
+    }
+    // only country codes, not `LAN` or `private`
+    let rule = conn.rule_payload.trim();
+    if rule.len() != 2 || !rule.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+    let ip = conn.metadata_str("destinationIP")?.parse().ok()?;
+    let local = lookup(ip)?;
+    (!local.eq_ignore_ascii_case(rule))
+        .then(|| GeoMismatch { rule: rule.to_ascii_uppercase(), local })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::test;
+
+    fn conn(rule: &str, payload: &str, dest: &str) -> Connection {
+        test::conn("a").metadata(json!({ "destinationIP": dest })).rule(rule, payload).build()
+    }
+
+    #[test]
+    fn only_geoip_country_rules_are_checked() {
+        let lookup = |ip: IpAddr| (ip == IpAddr::from([1, 1, 1, 1])).then(|| "US".to_string());
+        let mismatch_of = |c: Connection| mismatch(&c, lookup);
+
+        assert_eq!(
+            mismatch_of(conn("GeoIP", "cn", "1.1.1.1")),
+            Some(GeoMismatch { rule: "CN".into(), local: "US".into() })
+        );
+        assert_eq!(mismatch_of(conn("GEOIP", "US", "1.1.1.1")), None);
+        assert_eq!(mismatch_of(conn("GEOIP", "LAN", "1.1.1.1")), None);
+        assert_eq!(mismatch_of(conn("DOMAIN", "CN", "1.1.1.1")), None);
+        // not in the local database
+        assert_eq!(mismatch_of(conn("GEOIP", "CN", "8.8.8.8")), None);
+        assert_eq!(mismatch_of(conn("GEOIP", "CN", "")), None);
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::config::get_project_dir;
+use crate::utils::csv::CsvWriter;
+use crate::utils::encryption::Encryption;
+
+/// Bytes of an encoded [`HistorySample`]: five little-endian 64 bit integers.
+const RECORD_LEN: usize = 40;
+
+/// Samples of the current retention window, shared by the recorder and the Overview history view.
+static SAMPLES: LazyLock<Mutex<VecDeque<HistorySample>>> = LazyLock::new(Default::default);
+
+/// One sample of the traffic history: rates averaged over the sample interval, memory and
+/// connection count as last seen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct HistorySample {
+    /// Unix timestamp in seconds
+    pub time: i64,
+    pub up: u64,
+    pub down: u64,
+    pub memory: u64,
+    pub conns: u64,
+}
+
+impl HistorySample {
+    fn encode(&self) -> [u8; RECORD_LEN] {
+        let mut record = [0; RECORD_LEN];
+        let fields = [self.time as u64, self.up, self.down, self.memory, self.conns];
+        for (chunk, field) in record.chunks_exact_mut(8).zip(fields) {
+            chunk.copy_from_slice(&field.to_le_bytes());
+        }
+        record
+    }
+
+    fn decode(record: &[u8]) -> Self {
+        let field = |i: usize| {
+            u64::from_le_bytes(record[i * 8..(i + 1) * 8].try_into().unwrap_or_default())
+        };
+        Self {
+            time: field(0) as i64,
+            up: field(1),
+            down: field(2),
+            memory: field(3),
+            conns: field(4),
+        }
+    }
+}
+
+/// Accumulates the stream records between two samples.
+#[derive(Debug, Default)]
+pub struct HistorySampler {
+    up: u64,
+    down: u64,
+    traffic_records: u64,
+    memory: u64,
+    conns: u64,
+}
+
+impl HistorySampler {
+    pub fn push_traffic(&mut self, up: u64, down: u64) {
+        self.up += up;
+        self.down += down;
+        self.traffic_records += 1;
+    }
+
+    pub fn push_memory(&mut self, used: u64) {
+        self.memory = used;
+    }
+
+    pub fn push_conns(&mut self, conns: u64) {
+        self.conns = conns;
+    }
+
+    /// The sample of the records pushed since the previous one, `None` if no traffic arrived,
+    /// e.g. while the streams are paused.
+    pub fn take(&mut self, time: i64) -> Option<HistorySample> {
+        if self.traffic_records == 0 {
+            return None;
+        }
+        let records = self.traffic_records;
+        let sample = HistorySample {
+            time,
+            up: self.up / records,
+            down: self.down / records,
+            memory: self.memory,
+            conns: self.conns,
+        };
+        (self.up, self.down, self.traffic_records) = (0, 0, 0);
+        Some(sample)
+    }
+}
+
+/// Traffic, memory and connection count history, persisted to a file of fixed size records so
+/// the Overview can show the last hours across restarts.
+pub struct History;
+
+impl History {
+    pub fn default_path() -> PathBuf {
+        get_project_dir().data_dir().join("history.bin")
+    }
+
+    /// Load the samples of `path` taken since `since` for [`Self::snapshot`], an absent file has
+    /// none.
+    pub fn load(path: &Path, since: i64) -> Result<()> {
+        let samples = read(path)?.into_iter().filter(|s| s.time >= since).collect();
+        *SAMPLES.lock().unwrap() = samples;
+        Ok(())
+    }
+
+    /// Append `sample` to `path`, dropping samples older than `since`. The file is compacted once
+    /// it holds twice the retained samples, so appends stay cheap. Encrypted files can't be
+    /// appended to, they are rewritten every time.
+    pub fn record(path: &Path, sample: HistorySample, since: i64) -> Result<()> {
+        let retained = {
+            let mut samples = SAMPLES.lock().unwrap();
+            samples.push_back(sample);
+            while samples.front().is_some_and(|s| s.time < since) {
+                samples.pop_front();
+            }
+            samples.len()
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Fail to create directory `{}`", parent.display()))?;
+        }
+        let stored = fs::metadata(path).map_or(0, |m| m.len() as usize / RECORD_LEN);
+        if stored + 1 > retained.max(1) * 2
+            || Encryption::enabled()
+            || Encryption::is_encrypted(path)
+        {
+            let content: Vec<u8> =
+                SAMPLES.lock().unwrap().iter().flat_map(HistorySample::encode).collect();
+            return Encryption::write(path, &content)
+                .with_context(|| format!("Fail to write history `{}`", path.display()));
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(&sample.encode()))
+            .with_context(|| format!("Fail to append to history `{}`", path.display()))
+    }
+
+    /// Samples of the retention window, oldest first.
+    pub fn snapshot() -> Vec<HistorySample> {
+        SAMPLES.lock().unwrap().iter().copied().collect()
+    }
+
+    /// Dump every sample of `path` to `out`, as a JSON array if `out` ends with `.json`, CSV
+    /// otherwise, encrypted like other exports. Returns the number of samples written.
+    pub fn export(path: &Path, out: &Path) -> Result<usize> {
+        let samples = read(path)?;
+        let content = if out.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            serde_json::to_string_pretty(&samples)?
+        } else {
+            to_csv(&samples)
+        };
+        Encryption::write(out, content.as_bytes())?;
+        Ok(samples.len())
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<HistorySample>> {
+    if fs::metadata(path).is_err_and(|e| e.kind() == ErrorKind::NotFound) {
+        return Ok(vec![]);
+    }
+    let content = Encryption::read(path)
+        .with_context(|| format!("Fail to read history `{}`", path.display()))?;
+    // a partial trailing record is left by an interrupted append
+    Ok(content.chunks_exact(RECORD_LEN).map(HistorySample::decode).collect())
+}
+
+fn to_csv(samples: &[HistorySample]) -> String {
+    let mut csv = CsvWriter::new(&["time", "up", "down", "memory", "conns"]);
+    for s in samples {
+        csv.row(&[&s.time, &s.up, &s.down, &s.memory, &s.conns]);
+    }
+    csv.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(time: i64) -> HistorySample {
+        HistorySample { time, up: 1 << 40, down: 2048, memory: 64 << 20, conns: 12 }
+    }
+
+    #[test]
+    fn sampler_averages_traffic_between_samples() {
+        let mut sampler = HistorySampler::default();
+        assert_eq!(sampler.take(0), None);
+        sampler.push_traffic(100, 1000);
+        sampler.push_traffic(300, 3000);
+        sampler.push_memory(64);
+        sampler.push_conns(5);
+        assert_eq!(
+            sampler.take(60),
+            Some(HistorySample { time: 60, up: 200, down: 2000, memory: 64, conns: 5 })
+        );
+        assert_eq!(sampler.take(120), None);
+    }
+
+    #[test]
+    fn records_round_trip_and_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.bin");
+        let samples = [sample(1), sample(2)];
+        let content: Vec<u8> = samples.iter().flat_map(HistorySample::encode).collect();
+        // with a partial record of an interrupted append
+        fs::write(&path, [content.as_slice(), &[1, 2, 3]].concat()).unwrap();
+        assert_eq!(read(&path).unwrap(), samples);
+
+        let csv = dir.path().join("history.csv");
+        assert_eq!(History::export(&path, &csv).unwrap(), 2);
+        let csv = fs::read_to_string(csv).unwrap();
+        assert_eq!(csv.lines().nth(1), Some("1,1099511627776,2048,67108864,12"));
+        let json = dir.path().join("history.json");
+        History::export(&path, &json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&fs::read(json).unwrap()).unwrap();
+        assert_eq!(json[1]["time"], 2);
+        assert!(read(&dir.path().join("missing.bin")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+use std::net::IpAddr;
+
+use crate::models::Connection;
+use crate::store::snapshot_delta::SnapshotDelta;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpFamily {
+    V4,
+    V6,
+}
+
+impl IpFamily {
+    /// Classify a connection by its destination IP, IPv4-mapped IPv6 addresses count as IPv4.
+    ///
+    /// Returns `None` if the destination IP is unknown, e.g. the domain is resolved remotely.
+    pub fn of(conn: &Connection) -> Option<Self> {
+        let ip: IpAddr = conn.metadata_str("destinationIP")?.parse().ok()?;
+        match ip.to_canonical() {
+            IpAddr::V4(_) => Some(Self::V4),
+            IpAddr::V6(_) => Some(Self::V6),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FamilyShare {
+    pub conns: usize,
+    /// Bytes per second
+    pub up_rate: u64,
+    pub down_rate: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IpFamilyStats {
+    pub v4: FamilyShare,
+    pub v6: FamilyShare,
+}
+
+impl IpFamilyStats {
+    /// IPv6 share of connections in percent, `None` if there is no classified connection.
+    pub fn v6_conns_percent(&self) -> Option<u8> {
+        let total = self.v4.conns + self.v6.conns;
+        (total > 0).then(|| (self.v6.conns * 100 / total) as u8)
+    }
+}
+
+impl IpFamilyStats {
+    /// Per IP family counts and rates in bytes per second of a connections snapshot.
+    pub fn of(delta: &SnapshotDelta) -> Self {
+        let mut stats = Self::default();
+        for (conn, (up, down)) in delta.rates() {
+            if let Some(family) = IpFamily::of(conn) {
+                let share = match family {
+                    IpFamily::V4 => &mut stats.v4,
+                    IpFamily::V6 => &mut stats.v6,
+                };
+                share.conns += 1;
+                share.up_rate += up;
+                share.down_rate += down;
+            }
+        }
+        for share in [&mut stats.v4, &mut stats.v6] {
+            share.up_rate = delta.per_sec(share.up_rate);
+            share.down_rate = delta.per_sec(share.down_rate);
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::store::snapshot_delta::SnapshotDiffer;
+    use crate::utils::test;
+
+    fn conn(id: &str, dest: &str, upload: u64, download: u64) -> Connection {
+        test::conn(id).metadata(json!({ "destinationIP": dest })).bytes(upload, download).build()
+    }
+
+    #[test]
+    fn classify_destination_ip() {
+        assert_eq!(IpFamily::of(&conn("1", "1.1.1.1", 0, 0)), Some(IpFamily::V4));
+        assert_eq!(IpFamily::of(&conn("1", "2606:4700::1111", 0, 0)), Some(IpFamily::V6));
+        assert_eq!(IpFamily::of(&conn("1", "::ffff:1.1.1.1", 0, 0)), Some(IpFamily::V4));
+        assert_eq!(IpFamily::of(&conn("1", "", 0, 0)), None);
+    }
+
+    #[test]
+    fn rates_between_snapshots() {
+        let mut differ = SnapshotDiffer::default();
+        let first = [conn("a", "1.1.1.1", 10, 100), conn("b", "::1", 5, 50)];
+        let first = IpFamilyStats::of(&differ.diff(&first));
+        assert_eq!(first.v4, FamilyShare { conns: 1, up_rate: 0, down_rate: 0 });
+        assert_eq!(first.v6_conns_percent(), Some(50));
+
+        let second = [conn("a", "1.1.1.1", 30, 400), conn("b", "::1", 6, 60)];
+        let mut delta = differ.diff(&second);
+        delta.elapsed = Some(Duration::from_secs(2));
+        let second = IpFamilyStats::of(&delta);
+        assert_eq!(second.v4, FamilyShare { conns: 1, up_rate: 10, down_rate: 150 });
+        assert_eq!(second.v6, FamilyShare { conns: 1, up_rate: 1, down_rate: 5 });
+    }
+}
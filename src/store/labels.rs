@@ -0,0 +1,67 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{LazyLock, RwLock};
+
+static LABELS: LazyLock<RwLock<LabelMap>> = LazyLock::new(Default::default);
+
+/// User defined labels keyed by lowercase host or IP.
+#[derive(Debug, Default)]
+struct LabelMap(HashMap<String, String>);
+
+impl LabelMap {
+    fn new(labels: &BTreeMap<String, String>) -> Self {
+        Self(labels.iter().map(|(key, label)| (key.to_lowercase(), label.clone())).collect())
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        if self.0.is_empty() {
+            return None;
+        }
+        self.0.get(&key.to_lowercase()).cloned()
+    }
+
+    fn annotate<'a>(&self, key: &str, text: Cow<'a, str>) -> Cow<'a, str> {
+        match self.get(key) {
+            Some(label) => Cow::Owned(format!("{text} ({label})")),
+            None => text,
+        }
+    }
+}
+
+/// Friendly labels of hosts and IPs (e.g. `NAS`), shown alongside the raw values.
+pub struct Labels;
+
+impl Labels {
+    pub fn configure(labels: &BTreeMap<String, String>) {
+        *LABELS.write().unwrap() = LabelMap::new(labels);
+    }
+
+    pub fn get(key: &str) -> Option<String> {
+        LABELS.read().unwrap().get(key)
+    }
+
+    /// `text (label)` if `key` has a label, otherwise `text` unchanged.
+    pub fn annotate<'a>(key: &str, text: Cow<'a, str>) -> Cow<'a, str> {
+        LABELS.read().unwrap().annotate(key, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_is_case_insensitive() {
+        let labels = LabelMap::new(&BTreeMap::from([
+            ("NAS.lan".to_string(), "NAS".to_string()),
+            ("192.168.1.20".to_string(), "Work laptop".to_string()),
+        ]));
+        assert_eq!(labels.get("nas.LAN").as_deref(), Some("NAS"));
+        assert_eq!(labels.annotate("nas.lan", "nas.lan:443".into()), "nas.lan:443 (NAS)");
+        assert_eq!(
+            labels.annotate("192.168.1.20", "192.168.1.20".into()),
+            "192.168.1.20 (Work laptop)"
+        );
+        assert_eq!(labels.annotate("example.com", "example.com:443".into()), "example.com:443");
+    }
+}
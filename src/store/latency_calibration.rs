@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+use futures_util::future::join_all;
+use tracing::{info, warn};
+
+use crate::api::Api;
+use crate::config::LatencyThreshold;
+use crate::store::proxies::Proxies;
+use crate::store::proxy_setting::ProxySetting;
+
+/// Groups tested at most, the ones with the most nodes are picked.
+pub const MAX_GROUPS: usize = 4;
+/// Successful tests needed before a threshold is suggested.
+const MIN_SAMPLES: usize = 5;
+/// Suggested thresholds are the next multiple of this many milliseconds.
+const ROUND_TO: u64 = 50;
+
+/// Latency distribution of the nodes of several groups, tested against the configured URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calibration {
+    pub groups: Vec<String>,
+    /// Successful delays in milliseconds, ascending
+    pub delays: Vec<u64>,
+    pub failed: usize,
+}
+
+impl Calibration {
+    /// Groups with the most nodes, the root `GLOBAL` group only if nothing else exists.
+    pub fn groups() -> Vec<String> {
+        let mut groups: Vec<(String, usize)> = Proxies::with_view(|views| {
+            views
+                .iter()
+                .filter_map(|v| Some((v.proxy.name.clone(), v.proxy.children.as_ref()?.len())))
+                .filter(|(_, len)| *len > 0)
+                .collect()
+        });
+        if groups.len() > 1 {
+            groups.retain(|(name, _)| name != "GLOBAL");
+        }
+        groups.sort_by_key(|(_, len)| std::cmp::Reverse(*len));
+        groups.into_iter().take(MAX_GROUPS).map(|(name, _)| name).collect()
+    }
+
+    /// Test every node of `groups` at once, nodes shared by groups are counted once.
+    pub async fn run(api: Arc<Api>, groups: Vec<String>) -> Result<Self> {
+        if groups.is_empty() {
+            bail!("No proxy groups to test, load the proxies first");
+        }
+        let (url, timeout, expected) = {
+            let setting = ProxySetting::global().read().unwrap();
+            (
+                setting.test_url.clone(),
+                setting.test_timeout.get(),
+                setting.test_expected_status.clone(),
+            )
+        };
+        info!(?groups, url, "Calibrating latency thresholds");
+        let results = join_all(
+            groups.iter().map(|group| api.test_proxy_group(group, &url, timeout, &expected)),
+        )
+        .await;
+        let mut nodes: HashMap<String, u16> = HashMap::new();
+        for (group, result) in groups.iter().zip(results) {
+            match result {
+                Ok(delays) => nodes.extend(delays),
+                Err(e) => warn!(error = ?e, "Failed to test proxy group: {}", group),
+            }
+        }
+        // the group test only reports nodes that answered, the rest of the members failed
+        let members = Proxies::with_by_names(&groups, |proxies| {
+            let mut members: Vec<String> =
+                proxies.iter().flat_map(|p| p.children.iter().flatten().cloned()).collect();
+            members.sort_unstable();
+            members.dedup();
+            members
+        });
+        for member in members {
+            nodes.entry(member).or_insert(0);
+        }
+        Ok(Self::from_delays(groups, nodes.into_values()))
+    }
+
+    fn from_delays(groups: Vec<String>, delays: impl IntoIterator<Item = u16>) -> Self {
+        let (mut delays, failed): (Vec<u64>, usize) =
+            delays.into_iter().fold((vec![], 0), |(mut ok, failed), delay| match delay {
+                0 => (ok, failed + 1),
+                delay => {
+                    ok.push(delay as u64);
+                    (ok, failed)
+                }
+            });
+        delays.sort_unstable();
+        Self { groups, delays, failed }
+    }
+
+    /// Delay below which `pct` percent of the successful tests fall.
+    pub fn percentile(&self, pct: usize) -> Option<u64> {
+        let last = self.delays.len().checked_sub(1)?;
+        self.delays.get((last * pct).div_ceil(100)).copied()
+    }
+
+    /// Nodes up to the median are fast, the slowest tenth is slow.
+    pub fn suggest(&self) -> Option<LatencyThreshold> {
+        if self.delays.len() < MIN_SAMPLES {
+            return None;
+        }
+        // thresholds are exclusive, the percentile itself must fall below
+        let round = |ms: u64| (ms / ROUND_TO + 1) * ROUND_TO;
+        let medium = round(self.percentile(50)?);
+        let high = round(self.percentile(90)?).max(medium + ROUND_TO);
+        Some(LatencyThreshold { medium, high })
+    }
+
+    /// Fast, medium and slow nodes under `threshold`, failed tests are not included.
+    pub fn quality_counts(&self, threshold: LatencyThreshold) -> [usize; 3] {
+        let fast = self.delays.partition_point(|d| *d < threshold.medium);
+        let medium = self.delays.partition_point(|d| *d < threshold.high) - fast;
+        [fast, medium, self.delays.len() - fast - medium]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggestion_follows_the_distribution() {
+        let delays = [120, 0, 80, 95, 110, 0, 130, 150, 180, 240, 900];
+        let calibration = Calibration::from_delays(vec!["Auto".into()], delays);
+        assert_eq!(calibration.failed, 2);
+        assert_eq!(calibration.delays.first(), Some(&80));
+        assert_eq!(calibration.percentile(50), Some(130));
+        assert_eq!(calibration.percentile(90), Some(900));
+        let suggested = calibration.suggest().unwrap();
+        assert_eq!(suggested, LatencyThreshold { medium: 150, high: 950 });
+        assert_eq!(calibration.quality_counts(suggested), [5, 4, 0]);
+        let current = LatencyThreshold { medium: 100, high: 200 };
+        assert_eq!(calibration.quality_counts(current), [2, 5, 2]);
+    }
+
+    #[test]
+    fn suggestion_keeps_thresholds_apart_and_needs_samples() {
+        let calibration = Calibration::from_delays(vec![], [100; 6]);
+        assert_eq!(calibration.suggest(), Some(LatencyThreshold { medium: 150, high: 200 }));
+        let calibration = Calibration::from_delays(vec![], [100, 0, 0, 0, 0, 0]);
+        assert_eq!(calibration.suggest(), None);
+    }
+}
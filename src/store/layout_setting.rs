@@ -0,0 +1,43 @@
+use std::ops::RangeInclusive;
+use std::sync::{OnceLock, RwLock};
+
+pub use crate::config::LayoutSetting;
+
+pub static GLOBAL_LAYOUT_SETTING: OnceLock<RwLock<LayoutSetting>> = OnceLock::new();
+
+impl LayoutSetting {
+    pub const PERCENT_RANGE: RangeInclusive<u16> = 20..=80;
+    pub const PERCENT_STEP: u16 = 5;
+
+    pub fn global() -> &'static RwLock<Self> {
+        GLOBAL_LAYOUT_SETTING.get_or_init(Default::default)
+    }
+
+    /// Widen (`grow`) or narrow the Overview traffic chart by one step, returns whether it changed.
+    pub fn resize_overview_traffic(&mut self, grow: bool) -> bool {
+        let old = self.overview_traffic_width;
+        let new = if grow {
+            old.saturating_add(Self::PERCENT_STEP)
+        } else {
+            old.saturating_sub(Self::PERCENT_STEP)
+        };
+        self.overview_traffic_width =
+            new.clamp(*Self::PERCENT_RANGE.start(), *Self::PERCENT_RANGE.end());
+        self.overview_traffic_width != old
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resize_overview_traffic_is_clamped() {
+        let mut layout = LayoutSetting { overview_traffic_width: 75 };
+        assert!(layout.resize_overview_traffic(true));
+        assert_eq!(layout.overview_traffic_width, 80);
+        assert!(!layout.resize_overview_traffic(true));
+        assert!(layout.resize_overview_traffic(false));
+        assert_eq!(layout.overview_traffic_width, 75);
+    }
+}
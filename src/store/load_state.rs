@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicI64, AtomicU8, Ordering};
+
+use time::OffsetDateTime;
+
+/// Progress of the initial load of a store, used to tell an empty view apart from one that is
+/// still loading or failed to load.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum LoadState {
+    #[default]
+    Loading,
+    Loaded,
+    /// The core could not be reached, only meaningful while nothing was loaded yet
+    Failed,
+}
+
+/// [`LoadState`] shared between a store and its background loader, along with the time of the
+/// last successful load.
+#[derive(Debug, Default)]
+pub struct LoadStateCell {
+    state: AtomicU8,
+    /// Unix timestamp in seconds, 0 if nothing was loaded yet
+    updated_at: AtomicI64,
+}
+
+impl LoadStateCell {
+    pub fn get(&self) -> LoadState {
+        match self.state.load(Ordering::Relaxed) {
+            0 => LoadState::Loading,
+            1 => LoadState::Loaded,
+            _ => LoadState::Failed,
+        }
+    }
+
+    pub fn set(&self, state: LoadState) {
+        self.state.store(state as u8, Ordering::Relaxed);
+    }
+
+    /// Record the outcome of a load, a failed reload keeps showing the loaded records.
+    pub fn set_result<T, E>(&self, result: &Result<T, E>) {
+        match result {
+            Ok(_) => {
+                self.set(LoadState::Loaded);
+                self.updated_at
+                    .store(OffsetDateTime::now_utc().unix_timestamp(), Ordering::Relaxed);
+            }
+            Err(_) if self.get() == LoadState::Loading => self.set(LoadState::Failed),
+            Err(_) => (),
+        }
+    }
+
+    /// Time of the last successful load.
+    pub fn updated_at(&self) -> Option<OffsetDateTime> {
+        match self.updated_at.load(Ordering::Relaxed) {
+            0 => None,
+            ts => OffsetDateTime::from_unix_timestamp(ts).ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_reload_keeps_loaded_state() {
+        let cell = LoadStateCell::default();
+        assert_eq!(cell.get(), LoadState::Loading);
+        cell.set_result::<(), ()>(&Err(()));
+        assert_eq!(cell.get(), LoadState::Failed);
+        assert_eq!(cell.updated_at(), None);
+        cell.set_result::<(), ()>(&Ok(()));
+        assert_eq!(cell.get(), LoadState::Loaded);
+        let updated_at = cell.updated_at();
+        assert!(updated_at.is_some());
+        cell.set_result::<(), ()>(&Err(()));
+        assert_eq!(cell.get(), LoadState::Loaded);
+        // a failed reload leaves the data as old as it was
+        assert_eq!(cell.updated_at(), updated_at);
+    }
+}
@@ -1,20 +1,32 @@
 use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::num::NonZeroUsize;
 use std::string::ToString;
 use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use nucleo_matcher::Matcher;
 use ringbuffer::{AllocRingBuffer, RingBuffer};
 
 use crate::models::Log;
 use crate::utils::columns::ColDef;
-use crate::utils::filter::{FilterPattern, RowFilter};
+use crate::utils::filter::{AppliedFilter, FilterPattern, RowFilter};
+
+/// Records held back from the view above this count mean a log storm, see
+/// [`Logs::push_and_update_view`].
+const STORM_LINES: usize = 200;
+/// During a log storm the view is updated at most this often.
+const STORM_VIEW_INTERVAL: Duration = Duration::from_millis(500);
 
 pub struct Logs {
     matcher: Mutex<Matcher>,
 
     buffer: RwLock<AllocRingBuffer<Arc<Log>>>,
     view: RwLock<AllocRingBuffer<Arc<Log>>>,
+    applied: Mutex<AppliedFilter>,
+    /// Records of the buffer newer than the view, not filtered yet
+    held: Mutex<VecDeque<Arc<Log>>>,
+    view_updated: Mutex<Instant>,
 }
 
 impl Logs {
@@ -23,49 +35,111 @@ impl Logs {
             matcher: Default::default(),
             buffer: RwLock::new(AllocRingBuffer::new(capacity.get())),
             view: RwLock::new(AllocRingBuffer::new(capacity.get())),
+            applied: Default::default(),
+            held: Default::default(),
+            view_updated: Mutex::new(Instant::now()),
         }
     }
 
-    pub fn push(&self, record: Log) {
+    pub fn push(&self, records: impl IntoIterator<Item = Log>) {
         let mut guard = self.buffer.write().unwrap();
-        guard.enqueue(Arc::new(record));
+        records.into_iter().for_each(|record| _ = guard.enqueue(Arc::new(record)));
+        // the view misses the records until it is recomputed
+        self.applied.lock().unwrap().invalidate();
     }
 
-    pub fn push_and_update_view(&self, record: Log, pattern: Option<&FilterPattern>) {
-        let record = Arc::new(record);
-        let removed = {
-            let mut guard = self.buffer.write().unwrap();
-            guard.enqueue(Arc::clone(&record))
-        };
+    /// Appends `records` in one go, the view follows right away unless the core is flooding logs:
+    /// then the records are held back and filtered at most every [`STORM_VIEW_INTERVAL`], see
+    /// [`Self::held_back`] and [`Self::update_view_if_due`].
+    pub fn push_and_update_view(
+        &self,
+        records: impl IntoIterator<Item = Log>,
+        pattern: Option<&FilterPattern>,
+    ) {
+        let mut held = self.held.lock().unwrap();
+        {
+            let mut buffer = self.buffer.write().unwrap();
+            let mut view = self.view.write().unwrap();
+            for record in records {
+                let record = Arc::new(record);
+                let Some(removed) = buffer.enqueue(Arc::clone(&record)) else {
+                    held.push_back(record);
+                    continue;
+                };
+                // Keep the filtered view in sync when the ring buffer evicts its oldest record.
+                if view.front().is_some_and(|log| Arc::ptr_eq(log, &removed)) {
+                    view.dequeue();
+                } else if held.front().is_some_and(|log| Arc::ptr_eq(log, &removed)) {
+                    held.pop_front();
+                }
+                held.push_back(record);
+            }
+        }
 
+        let mut view_updated = self.view_updated.lock().unwrap();
+        if held.len() < STORM_LINES || view_updated.elapsed() >= STORM_VIEW_INTERVAL {
+            self.extend_view(held.drain(..), pattern);
+            *view_updated = Instant::now();
+        }
+    }
+
+    /// Moves records held back during a log storm to the view once the interval is over, also
+    /// when the storm just ended.
+    pub fn update_view_if_due(&self, pattern: Option<&FilterPattern>) {
+        let mut held = self.held.lock().unwrap();
+        let mut view_updated = self.view_updated.lock().unwrap();
+        if !held.is_empty() && view_updated.elapsed() >= STORM_VIEW_INTERVAL {
+            self.extend_view(held.drain(..), pattern);
+            *view_updated = Instant::now();
+        }
+    }
+
+    /// Records received but not shown yet because of a log storm.
+    pub fn held_back(&self) -> usize {
+        self.held.lock().unwrap().len()
+    }
+
+    fn extend_view(
+        &self,
+        records: impl Iterator<Item = Arc<Log>>,
+        pattern: Option<&FilterPattern>,
+    ) {
+        let records = records.collect::<Vec<_>>();
         let matches = {
             let mut matcher = self.matcher.lock().unwrap();
             RowFilter::new(
-                std::iter::once(&record),
+                records.iter(),
                 &mut matcher,
                 pattern.map(FilterPattern::expr),
                 LOG_COLS.iter(),
             )
-            .next()
-            .is_some()
+            .collect::<Vec<_>>()
         };
-
-        let mut guard = self.view.write().unwrap();
-        // Keep the filtered view in sync when the ring buffer evicts its oldest record.
-        if let Some(removed) = removed
-            && guard.front().is_some_and(|log| Arc::ptr_eq(log, &removed))
-        {
-            guard.dequeue();
-        }
-        if matches {
-            guard.enqueue(record);
-        }
+        self.applied.lock().unwrap().extend(pattern);
+        self.view.write().unwrap().extend(matches);
     }
 
     pub fn compute_view(&self, pattern: Option<&FilterPattern>) {
+        let mut held = self.held.lock().unwrap();
         let buffer = self.buffer.read().unwrap();
 
         let mut matcher = self.matcher.lock().unwrap();
+        // typing more of the pattern only drops records, no need to go through all of them again
+        if self.applied.lock().unwrap().apply(pattern) {
+            let mut guard = self.view.write().unwrap();
+            let narrowed: Vec<_> = RowFilter::new(
+                guard.iter(),
+                &mut matcher,
+                pattern.map(FilterPattern::expr),
+                LOG_COLS.iter(),
+            )
+            .collect();
+            guard.clear();
+            guard.extend(narrowed);
+            return;
+        }
+        // rebuilt from the whole buffer, nothing is held back anymore
+        held.clear();
         let filtered = RowFilter::new(
             buffer.iter(),
             &mut matcher,
@@ -84,20 +158,29 @@ impl Logs {
         let guard = self.view.read().unwrap();
         f(&guard)
     }
+
+    /// Like [`Self::with_view`] over all loaded records, the filter ignored.
+    pub fn with_all<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&AllocRingBuffer<Arc<Log>>) -> R,
+    {
+        let guard = self.buffer.read().unwrap();
+        f(&guard)
+    }
 }
 
 pub static LOG_COLS: &[ColDef<Log>] = &[
     ColDef {
-        id: "level",
-        title: "Level",
+        id: Cow::Borrowed("level"),
+        title: Cow::Borrowed("Level"),
         filterable: true,
         sortable: false,
         accessor: |c: &Log| Cow::Owned(c.r#type.to_string()),
         sort_key: None,
     },
     ColDef {
-        id: "content",
-        title: "Content",
+        id: Cow::Borrowed("content"),
+        title: Cow::Borrowed("Content"),
         filterable: true,
         sortable: false,
         accessor: |c: &Log| Cow::Borrowed(c.payload.as_str()),
@@ -123,9 +206,9 @@ mod tests {
         let store = Logs::new(NonZeroUsize::new(4).unwrap());
         let pattern = FilterPattern::new("foo".to_owned());
 
-        store.push_and_update_view(log("foo one"), pattern.as_ref());
-        store.push_and_update_view(log("bar two"), pattern.as_ref());
-        store.push_and_update_view(log("foo three"), pattern.as_ref());
+        store.push_and_update_view([log("foo one")], pattern.as_ref());
+        store.push_and_update_view([log("bar two")], pattern.as_ref());
+        store.push_and_update_view([log("foo three")], pattern.as_ref());
 
         assert_eq!(payloads(&store), ["foo one", "foo three"]);
     }
@@ -135,10 +218,58 @@ mod tests {
         let store = Logs::new(NonZeroUsize::new(2).unwrap());
         let pattern = FilterPattern::new("foo".to_owned());
 
-        store.push_and_update_view(log("foo one"), pattern.as_ref());
-        store.push_and_update_view(log("bar two"), pattern.as_ref());
-        store.push_and_update_view(log("foo three"), pattern.as_ref());
+        store.push_and_update_view([log("foo one")], pattern.as_ref());
+        store.push_and_update_view([log("bar two")], pattern.as_ref());
+        store.push_and_update_view([log("foo three")], pattern.as_ref());
 
         assert_eq!(payloads(&store), ["foo three"]);
     }
+
+    #[test]
+    fn log_storm_is_held_back_until_interval() {
+        let store = Logs::new(NonZeroUsize::new(STORM_LINES * 2).unwrap());
+        let pattern = FilterPattern::new("even".to_owned());
+        let storm = |n: usize| {
+            (0..n).map(|i| log(if i % 2 == 0 { "even" } else { "odd" })).collect::<Vec<_>>()
+        };
+
+        store.push_and_update_view(storm(STORM_LINES * 3), pattern.as_ref());
+        // older records were evicted while held back
+        assert_eq!(store.held_back(), STORM_LINES * 2);
+        assert!(payloads(&store).is_empty());
+        store.update_view_if_due(pattern.as_ref());
+        assert!(payloads(&store).is_empty());
+
+        *store.view_updated.lock().unwrap() -= STORM_VIEW_INTERVAL;
+        store.update_view_if_due(pattern.as_ref());
+        assert_eq!(store.held_back(), 0);
+        assert_eq!(payloads(&store).len(), STORM_LINES);
+
+        // quiet again, records show up right away
+        store.push_and_update_view([log("even quiet")], pattern.as_ref());
+        assert_eq!(store.held_back(), 0);
+        assert_eq!(payloads(&store).len(), STORM_LINES);
+        assert_eq!(payloads(&store).last().unwrap(), "even quiet");
+    }
+
+    #[test]
+    fn compute_view_narrows_and_picks_up_unfiltered_records() {
+        let store = Logs::new(NonZeroUsize::new(8).unwrap());
+        for payload in ["foo one", "bar two", "foo three", "food four"] {
+            store.push([log(payload)]);
+        }
+        store.compute_view(FilterPattern::new("fo".to_owned()).as_ref());
+        store.compute_view(FilterPattern::new("foo".to_owned()).as_ref());
+        assert_eq!(payloads(&store), ["foo one", "foo three", "food four"]);
+
+        // pushed while paused, the view doesn't have it yet
+        store.push([log("foo five")]);
+        store.compute_view(FilterPattern::new("foo fi".to_owned()).as_ref());
+        assert_eq!(payloads(&store), ["foo five"]);
+
+        // pushed with another pattern than the view was computed with
+        store.push_and_update_view([log("foo fives")], FilterPattern::new("six".into()).as_ref());
+        store.compute_view(FilterPattern::new("foo fiv".to_owned()).as_ref());
+        assert_eq!(payloads(&store), ["foo five", "foo fives"]);
+    }
 }
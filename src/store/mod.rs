@@ -1,9 +1,37 @@
+pub mod api_log;
+pub mod auth_failures;
+pub mod bursts;
+pub mod capture_file;
+pub mod chart_setting;
+pub mod churn;
+pub mod closed_conns;
+pub mod connection_stats;
 pub mod connections;
 pub mod connections_setting;
+pub mod custom_columns;
+pub mod error_log;
+pub mod filter_history;
+pub mod geo_update;
+pub mod geoip;
+pub mod history;
+pub mod ip_family;
+pub mod labels;
+pub mod latency_calibration;
+pub mod layout_setting;
+pub mod load_state;
 pub mod logs;
+pub mod node_lookup;
+pub mod profiles;
+pub mod provider_alive;
 pub mod proxies;
 pub mod proxy_providers;
 pub mod proxy_setting;
 pub mod query;
 pub mod rule_providers;
 pub mod rules;
+pub mod snapshot_delta;
+pub mod snapshot_drops;
+pub mod stream_health;
+pub mod stream_hub;
+pub mod timeline;
+pub mod warmup;
@@ -0,0 +1,152 @@
+use std::net::IpAddr;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use tracing::debug;
+use url::Url;
+
+use crate::api::{Api, ExitIpApi};
+use crate::config::ExitIpConfig;
+use crate::models::Connection;
+
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where traffic of a proxy node enters and leaves the proxy network.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeLookup {
+    /// Distinct server addresses the core dialed for the node, from its live connections
+    pub servers: Vec<String>,
+    /// Exit IP lookup result
+    pub exit: Option<Exit>,
+}
+
+/// Exit of a node, measured by a lookup sent through the core's proxy port. The core routes it by
+/// its rules rather than through the node, so the IP is only the node's if it went through it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Exit {
+    /// Exit IP of the node, the core routed the lookup through it
+    Ip(IpAddr),
+    /// The core routed the lookup through another node, `None` if its connection wasn't found
+    NotThrough(Option<String>),
+    /// The lookup failed
+    Failed(String),
+}
+
+impl Exit {
+    fn of(ip: IpAddr, via: Option<&str>, node: &str) -> Self {
+        match via {
+            Some(via) if via == node => Self::Ip(ip),
+            via => Self::NotThrough(via.map(str::to_owned)),
+        }
+    }
+}
+
+/// Look up the servers of `node` from live connections, and its exit IP if the core routes a
+/// lookup sent through its proxy port via `node`.
+pub async fn lookup(api: &Api, config: &ExitIpConfig, node: &str) -> NodeLookup {
+    let proxy = match &config.proxy {
+        Some(proxy) => Ok(Some(proxy.clone())),
+        None => api.local_proxy_url().await,
+    };
+    let exit = match proxy {
+        Ok(Some(proxy)) => lookup_exit(api, &proxy, &config.url).await,
+        Ok(None) => Err(anyhow!("the core has no proxy port, set `exit-ip.proxy`")),
+        Err(e) => Err(e),
+    };
+    let (exit, mut servers) = match exit {
+        Ok((ip, via, servers)) => (Exit::of(ip, via.as_deref(), node), servers),
+        Err(e) => (Exit::Failed(format!("{e:#}")), None),
+    };
+    if servers.is_none() {
+        servers = api.get_connections().await.ok().and_then(|w| w.connections);
+    }
+    let servers = servers_of(servers.as_deref().unwrap_or_default(), node);
+    NodeLookup { servers, exit: Some(exit) }
+}
+
+/// The exit IP, the node the lookup went through and the connections while the lookup tunnel is
+/// still open.
+async fn lookup_exit(
+    api: &Api,
+    proxy: &str,
+    url: &str,
+) -> Result<(IpAddr, Option<String>, Option<Vec<Connection>>)> {
+    let client = ExitIpApi::new(proxy, LOOKUP_TIMEOUT)?;
+    let ip = client.lookup(url).await?;
+    let conns = api.get_connections().await.ok().and_then(|w| w.connections);
+    drop(client);
+
+    let host = Url::parse(url).ok().and_then(|url| url.host_str().map(str::to_owned));
+    let via = host
+        .zip(conns.as_deref())
+        .and_then(|(host, conns)| route_of(conns, &host))
+        .map(str::to_owned);
+    debug!(%ip, ?via, "Looked up exit IP");
+    Ok((ip, via, conns))
+}
+
+/// Node of the newest connection to `host`, the first of its chains.
+fn route_of<'a>(conns: &'a [Connection], host: &str) -> Option<&'a str> {
+    conns
+        .iter()
+        .filter(|c| c.metadata_str("host").is_some_and(|h| h.eq_ignore_ascii_case(host)))
+        .max_by_key(|c| c.start)
+        .and_then(|c| c.chains.first())
+        .map(String::as_str)
+}
+
+/// Distinct remote addresses of connections going out through `node`, most used first.
+fn servers_of(conns: &[Connection], node: &str) -> Vec<String> {
+    let mut servers: Vec<(String, usize)> = Vec::new();
+    let remotes = conns
+        .iter()
+        .filter(|c| c.chains.first().is_some_and(|n| n == node))
+        .filter_map(|c| c.metadata_str("remoteDestination").filter(|v| !v.is_empty()));
+    for remote in remotes {
+        match servers.iter_mut().find(|(server, _)| server == remote) {
+            Some((_, count)) => *count += 1,
+            None => servers.push((remote.to_owned(), 1)),
+        }
+    }
+    servers.sort_by(|(_, a), (_, b)| b.cmp(a));
+    servers.into_iter().map(|(server, _)| server).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::test;
+
+    fn conn(host: &str, remote: &str, chains: &[&str], start: &str) -> Connection {
+        test::conn(&format!("{host}-{start}"))
+            .metadata(json!({ "host": host, "remoteDestination": remote }))
+            .start(start)
+            .chains(chains)
+            .build()
+    }
+
+    #[test]
+    fn servers_and_route_come_from_chains() {
+        let conns = [
+            conn("a.com", "198.51.100.1:443", &["hk-01", "Proxy"], "2024-01-01T00:00:00Z"),
+            conn("b.com", "198.51.100.2:443", &["hk-01", "Proxy"], "2024-01-01T00:00:01Z"),
+            conn("c.com", "198.51.100.2:443", &["hk-01", "Proxy"], "2024-01-01T00:00:02Z"),
+            conn("api.ipify.org", "198.51.100.3:443", &["jp-01", "Proxy"], "2024-01-01T00:00:03Z"),
+            conn("api.ipify.org", "", &["DIRECT"], "2024-01-01T00:00:00Z"),
+        ];
+        assert_eq!(servers_of(&conns, "hk-01"), ["198.51.100.2:443", "198.51.100.1:443"]);
+        assert_eq!(servers_of(&conns, "Proxy"), Vec::<String>::new());
+        assert_eq!(route_of(&conns, "API.ipify.org"), Some("jp-01"));
+        assert_eq!(route_of(&conns, "d.com"), None);
+    }
+
+    #[test]
+    fn exit_ip_is_only_the_node_routed_through() {
+        let ip: IpAddr = "203.0.113.1".parse().unwrap();
+        assert_eq!(Exit::of(ip, Some("hk-01"), "hk-01"), Exit::Ip(ip));
+        assert_eq!(Exit::of(ip, Some("jp-01"), "hk-01"), Exit::NotThrough(Some("jp-01".into())));
+        assert_eq!(Exit::of(ip, None, "hk-01"), Exit::NotThrough(None));
+    }
+}
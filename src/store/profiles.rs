@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, ensure};
+use serde_json::Value;
+use time::OffsetDateTime;
+
+use crate::config::get_project_dir;
+use crate::utils::encryption::Encryption;
+
+const PROFILE_EXT: &str = "yaml";
+/// Last applied unix timestamps keyed by profile name, kept next to the profiles.
+const APPLIED_FILE: &str = "applied.json";
+
+/// A named core config document stored locally.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    pub applied_at: Option<OffsetDateTime>,
+}
+
+/// Core config profiles, one YAML file per profile under the data dir.
+#[derive(Debug, Clone)]
+pub struct Profiles {
+    dir: PathBuf,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self::new(get_project_dir().data_dir().join("profiles"))
+    }
+}
+
+impl Profiles {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Profiles sorted by name, an absent directory has no profiles.
+    pub fn list(&self) -> Result<Vec<Profile>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Fail to read directory `{}`", self.dir.display()));
+            }
+        };
+        let applied = self.applied();
+        let mut profiles = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != PROFILE_EXT {
+                    return None;
+                }
+                let name = path.file_stem()?.to_str()?.to_string();
+                let applied_at =
+                    applied.get(&name).and_then(|ts| OffsetDateTime::from_unix_timestamp(*ts).ok());
+                Some(Profile { name, applied_at })
+            })
+            .collect::<Vec<_>>();
+        profiles.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        Ok(profiles)
+    }
+
+    pub fn load(&self, name: &str) -> Result<Value> {
+        let path = self.path(name)?;
+        let content = Encryption::read_to_string(&path)
+            .with_context(|| format!("Fail to read profile `{}`", path.display()))?;
+        let value: Value = yaml_serde::from_str(&content)
+            .with_context(|| format!("Fail to parse profile `{name}`"))?;
+        ensure!(value.is_object(), "Profile `{name}` must be a YAML mapping");
+        Ok(value)
+    }
+
+    pub fn save(&self, name: &str, config: &Value) -> Result<()> {
+        let path = self.path(name)?;
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Fail to create directory `{}`", self.dir.display()))?;
+        let content = yaml_serde::to_string(config).context("Fail to convert config to YAML")?;
+        Encryption::write(&path, content.as_bytes())
+            .with_context(|| format!("Fail to write profile `{}`", path.display()))
+    }
+
+    pub fn mark_applied(&self, name: &str) -> Result<()> {
+        let mut applied = self.applied();
+        applied.insert(name.to_string(), OffsetDateTime::now_utc().unix_timestamp());
+        let path = self.dir.join(APPLIED_FILE);
+        fs::write(&path, serde_json::to_string_pretty(&applied)?)
+            .with_context(|| format!("Fail to write file `{}`", path.display()))
+    }
+
+    fn applied(&self) -> BTreeMap<String, i64> {
+        fs::read_to_string(self.dir.join(APPLIED_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf> {
+        validate_name(name)?;
+        Ok(self.dir.join(format!("{name}.{PROFILE_EXT}")))
+    }
+}
+
+/// Names become file names, so only a safe subset of characters is allowed.
+pub fn validate_name(name: &str) -> Result<()> {
+    ensure!(!name.is_empty(), "Profile name cannot be empty");
+    ensure!(
+        !name.starts_with('.')
+            && name.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ' ')),
+        "Profile name may only contain letters, digits, spaces, `-`, `_` and `.`"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn save_list_and_apply_profiles() {
+        let dir = tempfile::tempdir().unwrap();
+        let profiles = Profiles::new(dir.path().join("profiles"));
+        assert!(profiles.list().unwrap().is_empty());
+
+        profiles.save("work", &json!({ "mode": "rule" })).unwrap();
+        profiles.save("home", &json!({ "mode": "global", "tun": { "enable": true } })).unwrap();
+        assert_eq!(profiles.load("home").unwrap()["tun"]["enable"], json!(true));
+
+        profiles.mark_applied("work").unwrap();
+        let list = profiles.list().unwrap();
+        assert_eq!(list.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(), ["home", "work"]);
+        assert!(list[0].applied_at.is_none());
+        assert!(list[1].applied_at.is_some());
+    }
+
+    #[test]
+    fn reject_unsafe_names() {
+        assert!(validate_name("home office").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("../etc").is_err());
+        assert!(validate_name(".hidden").is_err());
+        assert!(validate_name("a/b").is_err());
+    }
+}
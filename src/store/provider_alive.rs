@@ -0,0 +1,99 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex};
+
+use indexmap::IndexMap;
+use time::OffsetDateTime;
+
+use crate::models::proxy_provider::ProxyProvider;
+
+/// Samples kept per provider, 6 hours at the provider watcher's 30s poll interval.
+const CAPACITY: usize = 720;
+
+static HISTORY: LazyLock<Mutex<HashMap<String, VecDeque<AliveSample>>>> =
+    LazyLock::new(Default::default);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AliveSample {
+    pub at: OffsetDateTime,
+    /// Nodes whose last health check succeeded
+    pub alive: usize,
+    pub total: usize,
+}
+
+impl AliveSample {
+    /// Alive nodes in percent, 0 for an empty provider.
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 { 0.0 } else { self.alive as f64 * 100.0 / self.total as f64 }
+    }
+}
+
+/// History of alive nodes per proxy provider, sampled from periodic provider polls so the detail
+/// popup can show reliability over hours rather than the latest health check only.
+pub struct ProviderAlive;
+
+impl ProviderAlive {
+    /// Sample `providers`, history of providers no longer present is dropped.
+    pub fn record(providers: &IndexMap<String, ProxyProvider>, at: OffsetDateTime) {
+        let mut history = HISTORY.lock().unwrap();
+        history.retain(|name, _| providers.contains_key(name));
+        for provider in providers.values() {
+            let alive = provider.proxies.iter().filter(|p| p.is_alive()).count();
+            let sample = AliveSample { at, alive, total: provider.proxies.len() };
+            let samples = history.entry(provider.name.clone()).or_default();
+            if samples.len() == CAPACITY {
+                samples.pop_front();
+            }
+            samples.push_back(sample);
+        }
+    }
+
+    /// Samples of the provider, oldest first.
+    pub fn history(name: &str) -> Vec<AliveSample> {
+        HISTORY.lock().unwrap().get(name).map(|s| s.iter().copied().collect()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn providers(name: &str, delays: &[i64]) -> IndexMap<String, ProxyProvider> {
+        let proxies: Vec<_> = delays
+            .iter()
+            .enumerate()
+            .map(|(idx, delay)| {
+                let history = json!([{ "delay": delay }]);
+                json!({ "name": format!("node{idx}"), "type": "Vmess", "history": history })
+            })
+            .collect();
+        let provider: ProxyProvider = serde_json::from_value(json!({
+            "name": name,
+            "vehicleType": "HTTP",
+            "proxies": proxies,
+        }))
+        .unwrap();
+        IndexMap::from([(name.to_string(), provider)])
+    }
+
+    #[test]
+    fn samples_are_bounded_and_dropped_with_their_provider() {
+        let at = OffsetDateTime::UNIX_EPOCH;
+        ProviderAlive::record(&providers("alive-test", &[120, 0, 80, 0]), at);
+        let history = ProviderAlive::history("alive-test");
+        assert_eq!(history, [AliveSample { at, alive: 2, total: 4 }]);
+        assert_eq!(history[0].percent(), 50.0);
+
+        for _ in 0..CAPACITY {
+            ProviderAlive::record(&providers("alive-test", &[120]), at);
+        }
+        let history = ProviderAlive::history("alive-test");
+        assert_eq!(history.len(), CAPACITY);
+        assert_eq!(history[0].total, 1);
+
+        ProviderAlive::record(&providers("alive-other", &[]), at);
+        assert!(ProviderAlive::history("alive-test").is_empty());
+        assert_eq!(ProviderAlive::history("alive-other")[0].percent(), 0.0);
+    }
+}
@@ -1,9 +1,14 @@
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{self, AtomicUsize};
 use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
 
 use anyhow::Result;
+use futures_util::future::join_all;
 use indexmap::IndexMap;
+use time::OffsetDateTime;
+use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
 use crate::api::Api;
@@ -11,7 +16,9 @@ use crate::config::{LatencyThreshold, ProxySortConfig};
 use crate::models::proxy::Proxy;
 use crate::models::proxy_provider::ProxyProvider;
 use crate::models::sort::{ProxySortField, SortDir};
+use crate::store::load_state::LoadState;
 use crate::store::proxy_setting::ProxySetting;
+use crate::utils::collation;
 use crate::widgets::latency::{LatencyQuality, QualityStats};
 
 pub static GLOBAL_PROXIES: OnceLock<RwLock<Proxies>> = OnceLock::new();
@@ -26,11 +33,57 @@ pub struct ProxyView {
     pub quality_stats: QualityStats,
 }
 
+/// Progress of batched group tests, shown in the testing throbber.
+#[derive(Debug, Default)]
+pub struct TestProgress {
+    done: AtomicUsize,
+    total: AtomicUsize,
+}
+
+impl TestProgress {
+    pub fn label(&self) -> String {
+        match self.total.load(atomic::Ordering::Relaxed) {
+            0 => "Testing".into(),
+            total => format!("Testing {}/{total}", self.done.load(atomic::Ordering::Relaxed)),
+        }
+    }
+
+    /// Counts a batched test of `total` nodes until the returned guard is dropped, the test task
+    /// may be cancelled halfway.
+    fn start(&self, total: usize) -> TestBatch<'_> {
+        self.total.fetch_add(total, atomic::Ordering::Relaxed);
+        TestBatch { progress: self, total, done: 0 }
+    }
+}
+
+/// A batched test counted in [`TestProgress`], removed from it on drop.
+struct TestBatch<'a> {
+    progress: &'a TestProgress,
+    total: usize,
+    done: usize,
+}
+
+impl TestBatch<'_> {
+    fn tested(&mut self, nodes: usize) {
+        self.done += nodes;
+        self.progress.done.fetch_add(nodes, atomic::Ordering::Relaxed);
+    }
+}
+
+impl Drop for TestBatch<'_> {
+    fn drop(&mut self) {
+        self.progress.done.fetch_sub(self.done, atomic::Ordering::Relaxed);
+        self.progress.total.fetch_sub(self.total, atomic::Ordering::Relaxed);
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Proxies {
     sort: Option<ProxySortConfig>,
     proxies: HashMap<String, Arc<Proxy>>,
     visible: Vec<Arc<ProxyView>>,
+    load_state: LoadState,
+    updated_at: Option<OffsetDateTime>,
 }
 
 /// Global store for proxies, providing thread-safe access and update methods.
@@ -75,6 +128,25 @@ impl Proxies {
         }
     }
 
+    /// Names of `names` whose latency falls into a failing quality bucket, order is kept.
+    pub fn filter_failing(names: &[String]) -> Vec<String> {
+        let threshold = ProxySetting::global().read().unwrap().latency_threshold;
+        match Self::global().read() {
+            Ok(p) => names
+                .iter()
+                .filter(|name| {
+                    let latency = p.proxies.get(*name).map(|v| v.latency).unwrap_or_default();
+                    LatencyQuality::from(latency, threshold).is_failing()
+                })
+                .cloned()
+                .collect(),
+            Err(e) => {
+                error!(error = ?e, "Failed to acquire read lock");
+                vec![]
+            }
+        }
+    }
+
     pub fn with_view<R, F>(f: F) -> R
     where
         F: FnOnce(&[Arc<ProxyView>]) -> R,
@@ -95,7 +167,13 @@ impl Proxies {
         // `GET /providers/proxies`. Merge them in so group children referencing
         // provider nodes can be resolved by name.
         let (proxies, providers) = tokio::join!(api.get_proxies(), api.get_providers());
-        let mut proxies = proxies?;
+        let mut proxies = proxies.inspect_err(|_| {
+            if let Ok(mut p) = Self::global().write()
+                && p.load_state == LoadState::Loading
+            {
+                p.load_state = LoadState::Failed;
+            }
+        })?;
         match providers {
             Ok(providers) => Self::merge_provider_proxies(&mut proxies, providers),
             Err(e) => warn!(error = ?e, "Failed to load providers, provider nodes may be missing"),
@@ -120,13 +198,39 @@ impl Proxies {
         }
     }
 
+    /// Close the connections going through `selector`, so they reconnect through its new pick.
+    pub async fn terminate_connections_of(api: &Api, selector: &str) {
+        let Ok(wrapper) = api.get_connections().await else {
+            debug!("Failed to get connections for termination");
+            return;
+        };
+        // `into_iter + collect` to release large connection payloads early.
+        let conns = wrapper
+            .connections
+            .into_iter()
+            .flat_map(|c| c.into_iter())
+            .filter(|c| c.chains.iter().any(|chain| chain == selector))
+            .map(|c| c.id)
+            .collect::<Vec<_>>();
+        debug!(selector_name = %selector, num_conns = conns.len(), "Terminating connections");
+        for conn_id in conns {
+            if let Err(e) = api.delete_connection(&conn_id).await {
+                debug!(error = ?e, "Failed to terminate connection: {}", conn_id);
+            }
+        }
+    }
+
     pub async fn test_and_reload(api: Arc<Api>, name: &str) -> Result<()> {
-        let (test_url, test_timeout) = {
+        let (test_url, test_timeout, expected) = {
             let setting = ProxySetting::global().read().unwrap();
-            (setting.test_url.clone(), setting.test_timeout.get())
+            (
+                setting.test_url.clone(),
+                setting.test_timeout.get(),
+                setting.test_expected_status.clone(),
+            )
         };
 
-        let result = api.test_proxy(name, &test_url, test_timeout).await;
+        let result = api.test_proxy(name, &test_url, test_timeout, &expected).await;
         // Even if testing fails, we still want to
         // reload the proxies to get the latest latency info.
         if let Err(e) = result {
@@ -135,13 +239,50 @@ impl Proxies {
         Self::load(api).await
     }
 
-    pub async fn test_group_and_reload(api: Arc<Api>, name: &str) -> Result<()> {
-        let (test_url, test_timeout) = {
+    pub async fn test_group_and_reload(
+        api: Arc<Api>,
+        name: &str,
+        progress: &TestProgress,
+    ) -> Result<()> {
+        let (test_url, test_timeout, expected, batch_size, batch_delay) = {
             let setting = ProxySetting::global().read().unwrap();
-            (setting.test_url.clone(), setting.test_timeout.get())
+            (
+                setting.test_url.clone(),
+                setting.test_timeout.get(),
+                setting.test_expected_status.clone(),
+                setting.test_batch_size,
+                Duration::from_millis(setting.test_batch_delay),
+            )
         };
 
-        let result = api.test_proxy_group(name, &test_url, test_timeout).await;
+        let children = Self::get_by_name(name)
+            .and_then(|p| p.children.clone())
+            .filter(|children| batch_size > 0 && children.len() > batch_size);
+        if let Some(children) = children {
+            info!(name, nodes = children.len(), batch_size, "Testing proxy group in batches");
+            let mut batch_progress = progress.start(children.len());
+            for (idx, batch) in children.chunks(batch_size).enumerate() {
+                if idx > 0 {
+                    sleep(batch_delay).await;
+                }
+                let results = join_all(
+                    batch
+                        .iter()
+                        .map(|node| api.test_proxy(node, &test_url, test_timeout, &expected)),
+                )
+                .await;
+                for (node, result) in batch.iter().zip(results) {
+                    if let Err(e) = result {
+                        debug!(error = ?e, "Failed to test proxy: {}", node);
+                    }
+                }
+                batch_progress.tested(batch.len());
+            }
+            drop(batch_progress);
+            return Self::load(api).await;
+        }
+
+        let result = api.test_proxy_group(name, &test_url, test_timeout, &expected).await;
         // Even if testing fails, we still want to
         // reload the proxies to get the latest latency info.
         if let Err(e) = result {
@@ -150,6 +291,28 @@ impl Proxies {
         Self::load(api).await
     }
 
+    /// Drop the manual pick of an url-test or fallback group, then test the whole group so the
+    /// core selects by the fresh results.
+    pub async fn retest_group_and_reload(api: Arc<Api>, name: &str) -> Result<()> {
+        let (test_url, test_timeout, expected) = {
+            let setting = ProxySetting::global().read().unwrap();
+            (
+                setting.test_url.clone(),
+                setting.test_timeout.get(),
+                setting.test_expected_status.clone(),
+            )
+        };
+
+        if Self::get_by_name(name).is_some_and(|p| p.fixed().is_some()) {
+            api.unfix_proxy(name).await?;
+        }
+        let result = api.test_proxy_group(name, &test_url, test_timeout, &expected).await;
+        if let Err(e) = result {
+            warn!(error = ?e, "Failed to re-test proxy group: {}", name);
+        }
+        Self::load(api).await
+    }
+
     pub fn init_sort_config(sort: Option<ProxySortConfig>) {
         let mut p = Self::global().write().expect("proxies store poisoned");
         if p.sort.is_none() {
@@ -210,6 +373,63 @@ impl Proxies {
         self.proxies.shrink_to_fit();
         self.visible.clear();
         self.visible.shrink_to_fit();
+        self.load_state = LoadState::Loading;
+        self.updated_at = None;
+    }
+
+    pub fn load_state(&self) -> LoadState {
+        self.load_state
+    }
+
+    /// Time of the last successful load.
+    pub fn updated_at(&self) -> Option<OffsetDateTime> {
+        self.updated_at
+    }
+
+    /// Every loaded proxy with the visible group to open it in: groups in display order open
+    /// themselves, then nodes by name open the first group listing them, `GLOBAL` last.
+    pub fn locate_all(&self) -> Vec<(String, Option<String>)> {
+        let mut groups: Vec<&Proxy> = self.visible.iter().map(|v| v.proxy.as_ref()).collect();
+        groups.sort_by_key(|g| g.name == ROOT_PROXY_GROUP);
+        let mut holder: HashMap<&str, &str> = HashMap::new();
+        for group in &groups {
+            for child in group.children.iter().flatten() {
+                holder.entry(child.as_str()).or_insert(group.name.as_str());
+            }
+        }
+
+        let group_names: HashSet<&str> = groups.iter().map(|g| g.name.as_str()).collect();
+        let mut nodes: Vec<&str> = self
+            .proxies
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !group_names.contains(name))
+            .collect();
+        nodes.sort_unstable_by(|a, b| collation::compare(a, b));
+        self.visible
+            .iter()
+            .map(|v| (v.proxy.name.clone(), Some(v.proxy.name.clone())))
+            .chain(
+                nodes
+                    .into_iter()
+                    .map(|name| (name.to_owned(), holder.get(name).map(|group| group.to_string()))),
+            )
+            .collect()
+    }
+
+    /// `name` followed by the proxies selected down its groups, e.g. `[Proxy, HK, HK 01]`. Stops
+    /// at a node, a proxy not loaded or a loop.
+    pub fn selected_chain(&self, name: &str) -> Vec<String> {
+        let mut chain = vec![name.to_owned()];
+        while let Some(next) = chain
+            .last()
+            .and_then(|last| self.proxies.get(last))
+            .and_then(|proxy| proxy.selected.as_deref())
+            .filter(|next| !next.is_empty() && !chain.iter().any(|name| name == next))
+        {
+            chain.push(next.to_owned());
+        }
+        chain
     }
 
     pub fn push(&mut self, mut proxies: IndexMap<String, Proxy>) {
@@ -232,6 +452,8 @@ impl Proxies {
         visible.sort_by_key(|v| sort_index.get(&v.proxy.name).copied().unwrap_or(usize::MAX));
 
         self.visible = visible;
+        self.load_state = LoadState::Loaded;
+        self.updated_at = Some(OffsetDateTime::now_utc());
     }
 
     fn build_proxy_view(&self, proxy: &Arc<Proxy>, threshold: LatencyThreshold) -> Arc<ProxyView> {
@@ -280,8 +502,8 @@ impl Proxies {
             };
 
             children.sort_by(|a, b| match dir {
-                SortDir::Asc => a.cmp(b),
-                SortDir::Desc => b.cmp(a),
+                SortDir::Asc => collation::compare(a, b),
+                SortDir::Desc => collation::compare(b, a),
             });
         }
     }
@@ -391,7 +613,9 @@ mod tests {
             hidden: None,
             children: children.map(|v| v.into_iter().map(str::to_string).collect()),
             selected: None,
-            history: vec![DelayHistory { delay: latency.unwrap_or_default() }],
+            fixed: None,
+            strategy: None,
+            history: vec![DelayHistory { time: None, delay: latency.unwrap_or_default() }],
             latency: latency.into(),
         }
     }
@@ -400,6 +624,29 @@ mod tests {
         ProxySortConfig { field, dir }
     }
 
+    #[test]
+    fn test_progress_label() {
+        let progress = TestProgress::default();
+        assert_eq!(progress.label(), "Testing");
+        progress.total.store(120, atomic::Ordering::Relaxed);
+        progress.done.store(40, atomic::Ordering::Relaxed);
+        assert_eq!(progress.label(), "Testing 40/120");
+    }
+
+    #[test]
+    fn cancelled_test_batch_leaves_the_progress() {
+        let progress = TestProgress::default();
+        let mut first = progress.start(20);
+        let second = progress.start(10);
+        first.tested(5);
+        assert_eq!(progress.label(), "Testing 5/30");
+        // a cancelled task drops its batch halfway
+        drop(first);
+        assert_eq!(progress.label(), "Testing 0/10");
+        drop(second);
+        assert_eq!(progress.label(), "Testing");
+    }
+
     #[test]
     fn test_merge_provider_proxies_adds_nodes_and_keeps_existing() {
         let mut proxies = IndexMap::from([
@@ -545,4 +792,48 @@ mod tests {
             vec!["a".to_string(), "b".to_string()]
         );
     }
+
+    #[test]
+    fn test_locate_all_prefers_groups_before_global() {
+        let mut store = Proxies::default();
+        store.push(IndexMap::from([
+            ("GLOBAL".to_string(), proxy("GLOBAL", Some(vec!["auto", "a", "b"]), None)),
+            ("auto".to_string(), proxy("auto", Some(vec!["b"]), None)),
+            ("b".to_string(), proxy("b", None, Some(10))),
+            ("a".to_string(), proxy("a", None, Some(10))),
+        ]));
+
+        let located = store.locate_all();
+        let located: Vec<_> = located.iter().map(|(n, g)| (n.as_str(), g.as_deref())).collect();
+        assert_eq!(
+            located,
+            [
+                ("auto", Some("auto")),
+                ("GLOBAL", Some("GLOBAL")),
+                ("a", Some("GLOBAL")),
+                ("b", Some("auto")),
+            ]
+        );
+    }
+
+    #[test]
+    fn selected_chain_follows_groups_down_to_a_node() {
+        let selecting = |name, children: Vec<&str>, selected: &str| {
+            let mut group = proxy(name, Some(children), None);
+            group.selected = Some(selected.to_owned());
+            (name.to_owned(), group)
+        };
+        let mut store = Proxies::default();
+        store.push(IndexMap::from([
+            selecting("Proxy", vec!["HK", "a"], "HK"),
+            selecting("HK", vec!["b", "loop"], "b"),
+            selecting("loop", vec!["Proxy"], "Proxy"),
+            ("a".to_string(), proxy("a", None, Some(10))),
+            ("b".to_string(), proxy("b", None, Some(10))),
+        ]));
+
+        assert_eq!(store.selected_chain("Proxy"), ["Proxy", "HK", "b"]);
+        assert_eq!(store.selected_chain("DIRECT"), ["DIRECT"]);
+        assert_eq!(store.selected_chain("loop"), ["loop", "Proxy", "HK", "b"]);
+    }
 }
@@ -1,20 +1,34 @@
 use std::cmp::Ordering;
+use std::fmt::Write;
 use std::sync::{Arc, OnceLock, RwLock};
 
 use anyhow::Result;
+use futures_util::future;
 use indexmap::IndexMap;
+use time::OffsetDateTime;
 use tracing::{error, info};
 
 use crate::api::Api;
 use crate::config::{LatencyThreshold, ProxySortConfig};
+use crate::models::proxy::Proxy;
 use crate::models::proxy_provider::ProxyProvider;
 use crate::models::sort::{ProxySortField, SortDir};
+use crate::store::load_state::LoadState;
 use crate::store::proxy_setting::ProxySetting;
+use crate::store::timeline::{EventKind, Timeline};
+use crate::utils::collation;
 use crate::utils::time::format_datetime;
 use crate::widgets::latency::{LatencyQuality, QualityStats};
 
 pub static GLOBAL_PROXY_PROVIDERS: OnceLock<RwLock<ProxyProviders>> = OnceLock::new();
 
+/// Name similarity from which a removed and an added node of the same type count as renamed.
+const RENAME_SIMILARITY: f64 = 0.6;
+/// Names of at most this many characters, ignoring whitespace, need [`SHORT_RENAME_SIMILARITY`]:
+/// a single changed character already scores high, e.g. `HK 01` and `HK 02` are other nodes.
+const SHORT_NAME_LEN: usize = 6;
+const SHORT_RENAME_SIMILARITY: f64 = 0.8;
+
 #[derive(Debug)]
 pub struct ProviderView {
     pub provider: Arc<ProxyProvider>,
@@ -22,10 +36,126 @@ pub struct ProviderView {
     pub usage_percent: Option<f64>,
 }
 
+impl ProviderView {
+    /// Nodes whose last test succeeded.
+    pub fn alive(&self) -> usize {
+        self.provider.proxies.iter().filter(|p| p.is_alive()).count()
+    }
+}
+
+/// Alive nodes of a provider around a bulk health check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthCheckResult {
+    pub name: String,
+    pub alive_before: usize,
+    /// `None` if the provider is gone after the check
+    pub alive_after: Option<usize>,
+    pub total: usize,
+    pub error: Option<String>,
+}
+
+/// Nodes of a provider that changed between two loads, e.g. around an update.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NodeDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// (old, new) names of nodes of the same type with similar names
+    pub renamed: Vec<(String, String)>,
+}
+
+impl NodeDiff {
+    pub fn between(before: &[Proxy], after: &[Proxy]) -> Self {
+        let missing_in = |nodes: &[Proxy], node: &Proxy| !nodes.iter().any(|n| n.name == node.name);
+        let mut removed: Vec<&Proxy> = before.iter().filter(|n| missing_in(after, n)).collect();
+        let mut added: Vec<&Proxy> = after.iter().filter(|n| missing_in(before, n)).collect();
+
+        let mut renamed = vec![];
+        removed.retain(|old| {
+            let best = added
+                .iter()
+                .enumerate()
+                .filter(|(_, new)| new.r#type == old.r#type)
+                .map(|(i, new)| (i, similarity(&old.name, &new.name)))
+                .filter(|(i, score)| *score >= rename_similarity(&old.name, &added[*i].name))
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            let Some((i, _)) = best else {
+                return true;
+            };
+            renamed.push((old.name.clone(), added.remove(i).name.clone()));
+            false
+        });
+        let names = |nodes: Vec<&Proxy>| nodes.into_iter().map(|n| n.name.clone()).collect();
+        Self { added: names(added), removed: names(removed), renamed }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.renamed.is_empty()
+    }
+
+    /// Counts of the changed nodes, e.g. `2 added, 0 removed, 1 renamed`.
+    pub fn counts(&self) -> String {
+        if self.is_empty() {
+            return "nodes unchanged".into();
+        }
+        format!(
+            "{} added, {} removed, {} renamed",
+            self.added.len(),
+            self.removed.len(),
+            self.renamed.len()
+        )
+    }
+
+    /// Counts followed by one line per changed node, for the message box.
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "Nodes unchanged".into();
+        }
+        let mut out = self.counts();
+        out.push('\n');
+        self.added.iter().for_each(|name| _ = write!(out, "\n+ {name}"));
+        self.removed.iter().for_each(|name| _ = write!(out, "\n- {name}"));
+        self.renamed.iter().for_each(|(old, new)| _ = write!(out, "\n~ {old} → {new}"));
+        out
+    }
+}
+
+/// Similarity from which `old` renamed to `new`, higher for short names.
+fn rename_similarity(old: &str, new: &str) -> f64 {
+    let len = |s: &str| s.chars().filter(|c| !c.is_whitespace()).count();
+    if len(old).min(len(new)) <= SHORT_NAME_LEN {
+        SHORT_RENAME_SIMILARITY
+    } else {
+        RENAME_SIMILARITY
+    }
+}
+
+/// Dice coefficient of the character bigrams, ignoring case and whitespace.
+fn similarity(a: &str, b: &str) -> f64 {
+    let bigrams = |s: &str| {
+        let chars: Vec<char> = s.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect::<Vec<_>>()
+    };
+    let (a, mut b) = (bigrams(a), bigrams(b));
+    let total = a.len() + b.len();
+    if total == 0 {
+        return 0.0;
+    }
+    let mut common = 0;
+    for pair in a {
+        if let Some(i) = b.iter().position(|p| *p == pair) {
+            b.swap_remove(i);
+            common += 1;
+        }
+    }
+    2.0 * common as f64 / total as f64
+}
+
 #[derive(Debug, Default)]
 pub struct ProxyProviders {
     sort: Option<ProxySortConfig>,
     providers: Vec<Arc<ProviderView>>,
+    load_state: LoadState,
+    updated_at: Option<OffsetDateTime>,
 }
 
 /// Global store for providers, providing thread-safe access and update methods.
@@ -66,7 +196,14 @@ impl ProxyProviders {
                 Ok(mut p) => p.push(providers),
                 Err(e) => error!(error = ?e, "Failed to acquire write lock"),
             },
-            Err(e) => return Err(e),
+            Err(e) => {
+                if let Ok(mut p) = Self::global().write()
+                    && p.load_state == LoadState::Loading
+                {
+                    p.load_state = LoadState::Failed;
+                }
+                return Err(e);
+            }
         }
 
         Ok(())
@@ -83,10 +220,54 @@ impl ProxyProviders {
         }
     }
 
-    /// Update provider and reload providers.
-    pub async fn update_and_reload(api: Arc<Api>, name: &str) -> Result<()> {
+    /// Health check every provider concurrently, then reload them once.
+    pub async fn health_check_all(api: Arc<Api>) -> Result<Vec<HealthCheckResult>> {
+        let before = Self::global().read().unwrap().view();
+        let checks = before.iter().map(|v| api.health_check_provider(v.provider.name.as_str()));
+        let errors = future::join_all(checks).await;
+        Self::load(Arc::clone(&api)).await?;
+
+        let results: Vec<HealthCheckResult> = before
+            .iter()
+            .zip(errors)
+            .map(|(view, result)| {
+                let after = Self::get_by_name(&view.provider.name).map(|(_, v)| v);
+                HealthCheckResult {
+                    name: view.provider.name.clone(),
+                    alive_before: view.alive(),
+                    alive_after: after.as_ref().map(|v| v.alive()),
+                    total: after.map_or(view.provider.proxies.len(), |v| v.provider.proxies.len()),
+                    error: result.err().map(|e| format!("{e:#}")),
+                }
+            })
+            .collect();
+        let alive = |f: fn(&HealthCheckResult) -> usize| results.iter().map(f).sum::<usize>();
+        Timeline::record(
+            EventKind::Provider,
+            format!(
+                "Health checked {} proxy providers, alive nodes {} → {}",
+                results.len(),
+                alive(|r| r.alive_before),
+                alive(|r| r.alive_after.unwrap_or_default())
+            ),
+        );
+        Ok(results)
+    }
+
+    /// Update provider and reload providers, returns the nodes changed by the update.
+    pub async fn update_and_reload(api: Arc<Api>, name: &str) -> Result<NodeDiff> {
+        let nodes = |name| Self::get_by_name(name).map(|(_, v)| v.provider.proxies.clone());
+        let before = nodes(name).unwrap_or_default();
         match api.update_provider(name).await {
-            Ok(_) => Self::load(api).await,
+            Ok(_) => {
+                Self::load(api).await?;
+                let diff = NodeDiff::between(&before, &nodes(name).unwrap_or_default());
+                Timeline::record(
+                    EventKind::Provider,
+                    format!("Proxy provider {name} updated, {}", diff.counts()),
+                );
+                Ok(diff)
+            }
             Err(e) => {
                 error!(error = ?e, "Failed to update proxy providers");
                 Err(e)
@@ -161,8 +342,8 @@ impl ProxyProviders {
     fn sort_by_name(providers: &mut IndexMap<String, ProxyProvider>, dir: SortDir) {
         for provider in providers.values_mut() {
             provider.proxies.sort_by(|a, b| match dir {
-                SortDir::Asc => a.name.cmp(&b.name),
-                SortDir::Desc => b.name.cmp(&a.name),
+                SortDir::Asc => collation::compare(&a.name, &b.name),
+                SortDir::Desc => collation::compare(&b.name, &a.name),
             });
         }
     }
@@ -190,6 +371,17 @@ impl ProxyProviders {
     pub fn clear(&mut self) {
         self.providers.clear();
         self.providers.shrink_to_fit();
+        self.load_state = LoadState::Loading;
+        self.updated_at = None;
+    }
+
+    pub fn load_state(&self) -> LoadState {
+        self.load_state
+    }
+
+    /// Time of the last successful load.
+    pub fn updated_at(&self) -> Option<OffsetDateTime> {
+        self.updated_at
     }
 
     pub fn push(&mut self, mut providers: IndexMap<String, ProxyProvider>) {
@@ -202,6 +394,8 @@ impl ProxyProviders {
             .filter(|v| v.name != "default" && v.vehicle_type != "Compatible")
             .map(|v| self.build_view(v, threshold))
             .collect();
+        self.load_state = LoadState::Loaded;
+        self.updated_at = Some(OffsetDateTime::now_utc());
     }
 
     fn build_view(
@@ -236,3 +430,115 @@ impl ProxyProviders {
         self.providers.clone()
     }
 }
+
+/// Plain-text table of bulk health check results, for the summary message box.
+pub fn health_check_summary(results: &[HealthCheckResult]) -> String {
+    let width = results.iter().map(|r| r.name.chars().count()).max().unwrap_or(0).max(8);
+    let mut out = format!("{:<width$}  {:>5}  {:>15}\n", "Provider", "Nodes", "Alive");
+    for r in results {
+        let after = r.alive_after.map_or("-".into(), |v| v.to_string());
+        let alive = format!("{} → {}", r.alive_before, after);
+        let _ = write!(out, "{:<width$}  {:>5}  {:>15}", r.name, r.total, alive);
+        match (&r.error, r.alive_after) {
+            (Some(e), _) => _ = write!(out, "  failed: {e}"),
+            (None, Some(after)) if after != r.alive_before => {
+                _ = write!(out, "  ({:+})", after as i64 - r.alive_before as i64)
+            }
+            _ => (),
+        }
+        out.push('\n');
+    }
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    let before: usize = results.iter().map(|r| r.alive_before).sum();
+    let after: usize = results.iter().filter_map(|r| r.alive_after).sum();
+    let _ = write!(
+        out,
+        "\n{} providers checked, {failed} failed, alive nodes {before} → {after}",
+        results.len()
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(name: &str, before: usize, after: usize, error: Option<&str>) -> HealthCheckResult {
+        HealthCheckResult {
+            name: name.into(),
+            alive_before: before,
+            alive_after: Some(after),
+            total: 10,
+            error: error.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn summary_lists_changes_and_failures() {
+        let summary = health_check_summary(&[
+            result("hk-subscription", 6, 9, None),
+            result("jp", 4, 4, None),
+            result("us", 2, 2, Some("timeout")),
+        ]);
+        assert_eq!(
+            summary,
+            [
+                "Provider         Nodes            Alive",
+                "hk-subscription     10            6 → 9  (+3)",
+                "jp                  10            4 → 4",
+                "us                  10            2 → 2  failed: timeout",
+                "",
+                "3 providers checked, 1 failed, alive nodes 12 → 15",
+            ]
+            .join("\n")
+        );
+    }
+
+    fn node(name: &str, r#type: &str) -> Proxy {
+        Proxy {
+            name: name.into(),
+            r#type: r#type.into(),
+            hidden: None,
+            children: None,
+            selected: None,
+            fixed: None,
+            strategy: None,
+            history: vec![],
+            latency: None.into(),
+        }
+    }
+
+    #[test]
+    fn node_diff_matches_renamed_nodes() {
+        let before = [
+            node("HK 01 | 1.0x", "Trojan"),
+            node("JP 01", "Vmess"),
+            node("SG 01", "Trojan"),
+            node("US 01", "Trojan"),
+        ];
+        let after = [
+            node("HK 01 | 1.5x", "Trojan"),
+            // similar name, another type
+            node("JP 01 ", "Trojan"),
+            node("US 01", "Trojan"),
+            node("TW 01", "Trojan"),
+        ];
+        let diff = NodeDiff::between(&before, &after);
+        assert_eq!(diff.renamed, vec![("HK 01 | 1.0x".to_string(), "HK 01 | 1.5x".to_string())]);
+        assert_eq!(diff.removed, vec!["JP 01", "SG 01"]);
+        assert_eq!(diff.added, vec!["JP 01 ", "TW 01"]);
+        assert!(NodeDiff::between(&after, &after).is_empty());
+        assert!(diff.summary().starts_with("2 added, 2 removed, 1 renamed\n\n+ JP 01 \n+ TW 01"));
+        assert_eq!(NodeDiff::default().counts(), "nodes unchanged");
+    }
+
+    #[test]
+    fn short_names_differing_in_one_character_are_not_renames() {
+        let before = [node("HK 01", "Trojan"), node("Tokyo", "Trojan")];
+        let after = [node("HK 02", "Trojan"), node("Tokyo 1", "Trojan")];
+        let diff = NodeDiff::between(&before, &after);
+        assert_eq!(diff.renamed, vec![("Tokyo".to_string(), "Tokyo 1".to_string())]);
+        assert_eq!(diff.removed, vec!["HK 01"]);
+        assert_eq!(diff.added, vec!["HK 02"]);
+    }
+}
@@ -4,14 +4,17 @@ use crate::utils::filter::FilterPattern;
 #[derive(Debug, Clone, Default)]
 pub struct QueryState {
     pub pattern: Option<FilterPattern>,
+    /// Sort key under the column cursor, applied after [`Self::pinned`]
     pub sort: Option<SortSpec>,
+    /// Leading sort keys kept while the cursor moves on, e.g. sort by chains then by download rate
+    pub pinned: Vec<SortSpec>,
     /// Maximum number of sortable columns, for column navigation
     pub max_cols: usize,
 }
 
 impl QueryState {
     pub fn new(max_cols: usize) -> Self {
-        Self { pattern: None, sort: None, max_cols }
+        Self { pattern: None, sort: None, pinned: vec![], max_cols }
     }
 
     pub fn set_pattern(&mut self, pattern: Option<String>) {
@@ -23,11 +26,51 @@ impl QueryState {
         if self.sort.is_some_and(|sort| sort.col >= max_cols) {
             self.sort = None;
         }
+        self.pinned.retain(|sort| sort.col < max_cols);
+    }
+
+    /// Effective sort keys in priority order, the cursor key comes last unless it is pinned.
+    pub fn sort_keys(&self) -> Vec<SortSpec> {
+        let mut keys = self.pinned.clone();
+        if let Some(sort) = self.sort
+            && !self.is_pinned(sort.col)
+        {
+            keys.push(sort);
+        }
+        keys
+    }
+
+    pub fn is_pinned(&self, col: usize) -> bool {
+        self.pinned.iter().any(|sort| sort.col == col)
+    }
+
+    /// Pin the cursor key as a leading sort key, or unpin it if it is pinned already.
+    pub fn toggle_pin(&mut self) {
+        let Some(sort) = self.sort else {
+            return;
+        };
+        if self.is_pinned(sort.col) {
+            self.pinned.retain(|pinned| pinned.col != sort.col);
+        } else {
+            self.pinned.push(sort);
+        }
+    }
+
+    /// Map sort columns to new positions after the columns changed, unmapped keys are dropped.
+    pub fn remap_cols(&mut self, f: impl Fn(usize) -> Option<usize>) {
+        let remap = |sort: SortSpec| f(sort.col).map(|col| SortSpec { col, dir: sort.dir });
+        self.sort = self.sort.and_then(remap);
+        self.pinned = self.pinned.iter().copied().filter_map(remap).collect();
     }
 
     pub fn sort_rev(&mut self) {
         if let Some(ob) = self.sort.as_mut() {
             ob.dir = ob.dir.toggle();
+            let sort = *ob;
+            // reversing a pinned column reverses the pinned key
+            if let Some(pinned) = self.pinned.iter_mut().find(|pinned| pinned.col == sort.col) {
+                pinned.dir = sort.dir;
+            }
         }
     }
 
@@ -84,6 +127,33 @@ mod tests {
         assert_eq!(state.sort.map(|v| v.col), Some(2));
     }
 
+    #[test]
+    fn test_pinned_sort_keys() {
+        let mut state = QueryState::new(4);
+        state.sort = Some(SortSpec { col: 1, dir: SortDir::Asc });
+        state.toggle_pin();
+        state.sort_next();
+        state.sort_next();
+        assert_eq!(
+            state.sort_keys(),
+            vec![SortSpec { col: 1, dir: SortDir::Asc }, SortSpec { col: 3, dir: SortDir::Asc }]
+        );
+
+        // cursor on a pinned column doesn't duplicate it, reversing updates the pinned key
+        state.sort = Some(SortSpec { col: 1, dir: SortDir::Asc });
+        state.sort_rev();
+        assert_eq!(state.sort_keys(), vec![SortSpec { col: 1, dir: SortDir::Desc }]);
+
+        state.remap_cols(|col| (col != 1).then_some(col));
+        assert_eq!(state.sort, None);
+        assert!(state.pinned.is_empty());
+
+        state.sort = Some(SortSpec { col: 2, dir: SortDir::Desc });
+        state.toggle_pin();
+        state.set_max_cols(2);
+        assert!(state.sort_keys().is_empty());
+    }
+
     #[test]
     fn test_set_max_cols_keeps_valid_sort() {
         let mut state = QueryState::new(3);
@@ -1,5 +1,4 @@
 use std::borrow::Cow;
-use std::string::ToString;
 use std::sync::{Arc, Mutex, RwLock};
 
 use indexmap::IndexMap;
@@ -7,8 +6,10 @@ use nucleo_matcher::Matcher;
 use ratatui::layout::Constraint;
 
 use crate::models::RuleProvider;
-use crate::utils::columns::{ColDef, TableColDef};
-use crate::utils::filter::{FilterPattern, RowFilter};
+use crate::store::load_state::LoadStateCell;
+use crate::utils::columns::{ColDef, SortKey, TableColDef};
+use crate::utils::filter::{AppliedFilter, FilterPattern, RowFilter};
+use crate::utils::number::format_thousands;
 use crate::utils::time::format_datetime;
 
 #[derive(Default)]
@@ -17,11 +18,15 @@ pub struct RuleProviders {
 
     buffer: RwLock<Vec<Arc<RuleProvider>>>,
     view: RwLock<Vec<Arc<RuleProvider>>>,
+    applied: Mutex<AppliedFilter>,
+    load_state: LoadStateCell,
 }
 
 impl RuleProviders {
     pub fn push(&self, records: IndexMap<String, RuleProvider>) {
-        *self.buffer.write().unwrap() = records
+        let mut buffer = self.buffer.write().unwrap();
+        self.applied.lock().unwrap().invalidate();
+        *buffer = records
             .into_values()
             .map(|mut v| {
                 v.updated_at_str = v.updated_at.and_then(format_datetime);
@@ -35,6 +40,19 @@ impl RuleProviders {
         let buffer = self.buffer.read().unwrap();
 
         let mut matcher = self.matcher.lock().unwrap();
+        // typing more of the pattern only drops rows, no need to go through all of them again
+        if self.applied.lock().unwrap().apply(pattern) {
+            let mut guard = self.view.write().unwrap();
+            let narrowed = RowFilter::new(
+                guard.iter(),
+                &mut matcher,
+                pattern.map(FilterPattern::expr),
+                RULE_PROVIDER_COLS.iter(),
+            )
+            .collect();
+            *guard = narrowed;
+            return;
+        }
         let filtered = RowFilter::new(
             buffer.iter(),
             &mut matcher,
@@ -46,6 +64,10 @@ impl RuleProviders {
         filtered.for_each(|v| guard.push(v));
     }
 
+    pub fn load_state(&self) -> &LoadStateCell {
+        &self.load_state
+    }
+
     pub fn with_view<F, R>(&self, f: F) -> R
     where
         F: FnOnce(&Vec<Arc<RuleProvider>>) -> R,
@@ -53,13 +75,22 @@ impl RuleProviders {
         let guard = self.view.read().unwrap();
         f(&guard)
     }
+
+    /// Like [`Self::with_view`] over all loaded records, the filter ignored.
+    pub fn with_all<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&Vec<Arc<RuleProvider>>) -> R,
+    {
+        let guard = self.buffer.read().unwrap();
+        f(&guard)
+    }
 }
 
 pub static RULE_PROVIDER_COLS: &[TableColDef<RuleProvider>] = &[
     TableColDef {
         col: ColDef {
-            id: "name",
-            title: "Name",
+            id: Cow::Borrowed("name"),
+            title: Cow::Borrowed("Name"),
             filterable: true,
             sortable: true,
             accessor: |c: &RuleProvider| Cow::Borrowed(c.name.as_str()),
@@ -69,8 +100,8 @@ pub static RULE_PROVIDER_COLS: &[TableColDef<RuleProvider>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "vehicleType",
-            title: "VehicleType",
+            id: Cow::Borrowed("vehicleType"),
+            title: Cow::Borrowed("VehicleType"),
             filterable: true,
             sortable: true,
             accessor: |c: &RuleProvider| Cow::Borrowed(c.vehicle_type.as_str()),
@@ -80,8 +111,8 @@ pub static RULE_PROVIDER_COLS: &[TableColDef<RuleProvider>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "behavior",
-            title: "Behavior",
+            id: Cow::Borrowed("behavior"),
+            title: Cow::Borrowed("Behavior"),
             filterable: true,
             sortable: true,
             accessor: |c: &RuleProvider| Cow::Borrowed(c.behavior.as_str()),
@@ -91,19 +122,30 @@ pub static RULE_PROVIDER_COLS: &[TableColDef<RuleProvider>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "rule_count",
-            title: "RuleCount",
-            filterable: false,
+            id: Cow::Borrowed("format"),
+            title: Cow::Borrowed("Format"),
+            filterable: true,
             sortable: true,
-            accessor: |c: &RuleProvider| Cow::Owned(c.rule_count.to_string()),
+            accessor: |c: &RuleProvider| Cow::Borrowed(c.format.as_str()),
             sort_key: None,
         },
+        constraint: Constraint::Min(10),
+    },
+    TableColDef {
+        col: ColDef {
+            id: Cow::Borrowed("rule_count"),
+            title: Cow::Borrowed("RuleCount"),
+            filterable: false,
+            sortable: true,
+            accessor: |c: &RuleProvider| Cow::Owned(format_thousands(u64::from(c.rule_count))),
+            sort_key: Some(|c: &RuleProvider| SortKey::U64(u64::from(c.rule_count))),
+        },
         constraint: Constraint::Min(15),
     },
     TableColDef {
         col: ColDef {
-            id: "updated_at",
-            title: "UpdatedAt",
+            id: Cow::Borrowed("updated_at"),
+            title: Cow::Borrowed("UpdatedAt"),
             filterable: false,
             sortable: true,
             accessor: |c: &RuleProvider| Cow::Borrowed(c.updated_at_str.as_deref().unwrap_or("-")),
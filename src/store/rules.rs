@@ -1,4 +1,6 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Write;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex, RwLock};
 
@@ -6,8 +8,11 @@ use nucleo_matcher::Matcher;
 use ratatui::layout::Constraint;
 
 use crate::models::Rule;
+use crate::store::load_state::LoadStateCell;
 use crate::utils::columns::{ColDef, TableColDef};
-use crate::utils::filter::{FilterPattern, RowFilter};
+use crate::utils::filter::{AppliedFilter, FilterPattern, RowFilter};
+use crate::utils::number::format_thousands;
+use crate::utils::rule_parser::{self, LogicOp, RuleNode};
 use crate::utils::time::format_datetime;
 
 #[derive(Default)]
@@ -16,11 +21,18 @@ pub struct Rules {
 
     buffer: RwLock<Vec<Arc<Rule>>>,
     view: RwLock<Vec<Arc<Rule>>>,
+    applied: Mutex<AppliedFilter>,
+    /// (rule type, count) of all loaded rules, most used first
+    type_counts: RwLock<Vec<(String, usize)>>,
+    load_state: LoadStateCell,
 }
 
 impl Rules {
     pub fn push(&self, records: Vec<Rule>) {
-        *self.buffer.write().unwrap() = records
+        *self.type_counts.write().unwrap() = count_by_type(&records);
+        let mut buffer = self.buffer.write().unwrap();
+        self.applied.lock().unwrap().invalidate();
+        *buffer = records
             .into_iter()
             .map(|mut r| {
                 if let Some(extra) = r.extra.as_mut() {
@@ -38,6 +50,19 @@ impl Rules {
         let buffer = self.buffer.read().unwrap();
 
         let mut matcher = self.matcher.lock().unwrap();
+        // typing more of the pattern only drops rows, no need to go through all of them again
+        if self.applied.lock().unwrap().apply(pattern) {
+            let mut guard = self.view.write().unwrap();
+            let narrowed = RowFilter::new(
+                guard.iter(),
+                &mut matcher,
+                pattern.map(FilterPattern::expr),
+                RULE_COLS.iter(),
+            )
+            .collect();
+            *guard = narrowed;
+            return;
+        }
         let filtered = RowFilter::new(
             buffer.iter(),
             &mut matcher,
@@ -57,21 +82,50 @@ impl Rules {
         f(&guard)
     }
 
+    /// Like [`Self::with_view`] over all loaded records, the filter ignored.
+    pub fn with_all<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&Vec<Arc<Rule>>) -> R,
+    {
+        let guard = self.buffer.read().unwrap();
+        f(&guard)
+    }
+
+    /// Total rule count and (rule type, count) pairs, most used first.
+    pub fn type_counts(&self) -> (usize, Vec<(String, usize)>) {
+        let total = self.buffer.read().unwrap().len();
+        (total, self.type_counts.read().unwrap().clone())
+    }
+
+    pub fn load_state(&self) -> &LoadStateCell {
+        &self.load_state
+    }
+
     pub fn supports_disable(&self) -> bool {
         let records = self.buffer.read().unwrap();
         records.first().map(|v| v.supports_disable()).unwrap_or(false)
     }
 }
 
+fn count_by_type(records: &[Rule]) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for rule in records {
+        *counts.entry(rule.r#type.as_str()).or_default() += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().map(|(t, n)| (t.to_owned(), n)).collect();
+    counts.sort_unstable_by(|(ta, na), (tb, nb)| nb.cmp(na).then_with(|| ta.cmp(tb)));
+    counts
+}
+
 pub static RULE_COLS: &[TableColDef<Rule>] = &[
     TableColDef {
         col: ColDef {
-            id: "index",
-            title: "Index",
+            id: Cow::Borrowed("index"),
+            title: Cow::Borrowed("Index"),
             filterable: false,
             sortable: false,
             accessor: |rule: &Rule| {
-                Cow::Owned(rule.index.map(|v| v.to_string()).unwrap_or("-".into()))
+                Cow::Owned(rule.index.map(|v| format_thousands(v as u64)).unwrap_or("-".into()))
             },
             sort_key: None,
         },
@@ -79,8 +133,8 @@ pub static RULE_COLS: &[TableColDef<Rule>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "rule",
-            title: "Rule",
+            id: Cow::Borrowed("rule"),
+            title: Cow::Borrowed("Rule"),
             filterable: true,
             sortable: false,
             accessor: |rule: &Rule| {
@@ -88,7 +142,19 @@ pub static RULE_COLS: &[TableColDef<Rule>] = &[
                     rule.r#type.len() + rule.payload.len() + rule.proxy.len() + 2,
                 );
                 content.push_str(&rule.r#type);
-                if !rule.payload.is_empty() {
+                // logic rules read better as an expression, e.g. `AND(DOMAIN,a.com && NETWORK,UDP)`
+                if let Ok(node @ RuleNode::Logic { .. }) =
+                    rule_parser::parse(&rule.r#type, &rule.payload)
+                {
+                    match node {
+                        RuleNode::Logic { op: LogicOp::Not, children } => {
+                            let _ = write!(content, "({})", children[0]);
+                        }
+                        node => {
+                            let _ = write!(content, "({node})");
+                        }
+                    }
+                } else if !rule.payload.is_empty() {
                     content.push(',');
                     content.push_str(&rule.payload);
                 }
@@ -102,12 +168,16 @@ pub static RULE_COLS: &[TableColDef<Rule>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "size",
-            title: "Size",
+            id: Cow::Borrowed("size"),
+            title: Cow::Borrowed("Size"),
             filterable: false,
             sortable: false,
             accessor: |rule: &Rule| {
-                if rule.size <= -1 { Cow::Borrowed("-") } else { Cow::Owned(rule.size.to_string()) }
+                if rule.size <= -1 {
+                    Cow::Borrowed("-")
+                } else {
+                    Cow::Owned(format_thousands(rule.size as u64))
+                }
             },
             sort_key: None,
         },
@@ -115,8 +185,8 @@ pub static RULE_COLS: &[TableColDef<Rule>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "disabled",
-            title: "Disabled",
+            id: Cow::Borrowed("disabled"),
+            title: Cow::Borrowed("Disabled"),
             filterable: false,
             sortable: false,
             accessor: |rule: &Rule| match rule.extra {
@@ -139,13 +209,16 @@ pub static RULE_COLS: &[TableColDef<Rule>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "hits",
-            title: "Hits",
+            id: Cow::Borrowed("hits"),
+            title: Cow::Borrowed("Hits"),
             filterable: false,
             sortable: false,
             accessor: |rule: &Rule| {
                 Cow::Owned(
-                    rule.extra.as_ref().map(|v| v.hit_count.to_string()).unwrap_or("-".into()),
+                    rule.extra
+                        .as_ref()
+                        .map(|v| format_thousands(v.hit_count))
+                        .unwrap_or("-".into()),
                 )
             },
             sort_key: None,
@@ -154,8 +227,8 @@ pub static RULE_COLS: &[TableColDef<Rule>] = &[
     },
     TableColDef {
         col: ColDef {
-            id: "hit_at",
-            title: "HitAt",
+            id: Cow::Borrowed("hit_at"),
+            title: Cow::Borrowed("HitAt"),
             filterable: false,
             sortable: false,
             accessor: |rule: &Rule| {
@@ -168,3 +241,90 @@ pub static RULE_COLS: &[TableColDef<Rule>] = &[
         constraint: Constraint::Percentage(20),
     },
 ];
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn rule(r#type: &str) -> Rule {
+        rule_with(r#type, "")
+    }
+
+    fn rule_with(r#type: &str, payload: &str) -> Rule {
+        serde_json::from_value(
+            json!({ "type": r#type, "payload": payload, "proxy": "DIRECT", "size": -1 }),
+        )
+        .unwrap()
+    }
+
+    fn domain_rules(count: usize) -> Vec<Rule> {
+        (0..count)
+            .map(|i| rule_with("DOMAIN-SUFFIX", &format!("host{i}.example{}.com", i % 97)))
+            .collect()
+    }
+
+    fn view_payloads(store: &Rules) -> Vec<String> {
+        store.with_view(|rules| rules.iter().map(|rule| rule.payload.clone()).collect())
+    }
+
+    /// Computes the view for each prefix of `typed`, as if typed one key per tick.
+    fn type_pattern(store: &Rules, typed: &str, incremental: bool) {
+        for end in 1..=typed.len() {
+            if !incremental {
+                store.applied.lock().unwrap().invalidate();
+            }
+            store.compute_view(FilterPattern::new(typed[..end].to_owned()).as_ref());
+        }
+    }
+
+    #[test]
+    fn type_counts_most_used_first() {
+        let store = Rules::default();
+        store.push(
+            ["GEOIP", "DOMAIN-SUFFIX", "MATCH", "DOMAIN-SUFFIX", "GEOIP", "DOMAIN-SUFFIX"]
+                .into_iter()
+                .map(rule)
+                .collect(),
+        );
+        assert_eq!(
+            store.type_counts(),
+            (6, vec![("DOMAIN-SUFFIX".into(), 3), ("GEOIP".into(), 2), ("MATCH".into(), 1)])
+        );
+    }
+
+    #[test]
+    fn narrowed_view_matches_full_recompute() {
+        let store = Rules::default();
+        store.push(domain_rules(500));
+        type_pattern(&store, "host1.example4", true);
+        let narrowed = view_payloads(&store);
+        assert!(!narrowed.is_empty());
+
+        store.applied.lock().unwrap().invalidate();
+        store.compute_view(FilterPattern::new("host1.example4".into()).as_ref());
+        assert_eq!(narrowed, view_payloads(&store));
+
+        // new rules are filtered in full
+        store.push(vec![rule_with("DOMAIN", "host1.example4.org")]);
+        store.compute_view(FilterPattern::new("host1.example4.".into()).as_ref());
+        assert_eq!(view_payloads(&store), ["host1.example4.org"]);
+    }
+
+    /// `cargo test --release bench_typing_filter -- --ignored --nocapture`
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_typing_filter() {
+        let store = Rules::default();
+        store.push(domain_rules(50_000));
+        let typed = "host12.example3";
+        for incremental in [false, true] {
+            let started = std::time::Instant::now();
+            type_pattern(&store, typed, incremental);
+            let per_key = started.elapsed() / typed.len() as u32;
+            let rows = store.with_view(Vec::len);
+            println!("incremental={incremental}: {per_key:?} per key, {rows} rows left");
+        }
+    }
+}
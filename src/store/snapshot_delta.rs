@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::models::Connection;
+
+/// `(upload, download)` bytes of a connection.
+pub type Bytes = (u64, u64);
+
+/// What changed between two consecutive connections snapshots, computed once per snapshot by a
+/// [`SnapshotDiffer`] and shared by the trackers of the connections stream.
+#[derive(Debug)]
+pub struct SnapshotDelta<'a> {
+    /// Connections of the snapshot with their bytes in the previous one, `None` if opened since
+    pub conns: Vec<(&'a Connection, Option<Bytes>)>,
+    /// Last seen bytes of the connections of the previous snapshot that are gone
+    pub removed: Vec<Bytes>,
+    /// The first snapshot of a stream, its connections may have opened long ago
+    pub baseline: bool,
+    /// Time since the previous snapshot, `None` for the baseline
+    pub elapsed: Option<Duration>,
+}
+
+impl<'a> SnapshotDelta<'a> {
+    /// Connections opened since the previous snapshot, none for the baseline.
+    pub fn added(&self) -> impl Iterator<Item = &'a Connection> + '_ {
+        self.conns.iter().filter(|(_, prev)| !self.baseline && prev.is_none()).map(|(c, _)| *c)
+    }
+
+    /// Connections with the bytes they transferred since the previous snapshot, `(0, 0)` for the
+    /// ones opened since as their rate is unknown.
+    pub fn rates(&self) -> impl Iterator<Item = (&'a Connection, Bytes)> + '_ {
+        self.conns.iter().map(|&(conn, prev)| {
+            let rate = prev.map_or((0, 0), |(up, down)| {
+                (conn.upload.saturating_sub(up), conn.download.saturating_sub(down))
+            });
+            (conn, rate)
+        })
+    }
+
+    /// Bytes per second of `bytes` transferred since the previous snapshot, 0 for the baseline.
+    pub fn per_sec(&self, bytes: u64) -> u64 {
+        match self.elapsed.map(|e| e.as_secs_f64()).filter(|secs| *secs > 0.0) {
+            Some(secs) => (bytes as f64 / secs).round() as u64,
+            None => 0,
+        }
+    }
+}
+
+/// Keeps the bytes by connection id of the previous snapshot to compute [`SnapshotDelta`]s.
+#[derive(Debug, Default)]
+pub struct SnapshotDiffer {
+    last: Option<HashMap<String, Bytes>>,
+    last_at: Option<Instant>,
+}
+
+impl SnapshotDiffer {
+    pub fn diff<'a>(&mut self, conns: &'a [Connection]) -> SnapshotDelta<'a> {
+        let baseline = self.last.is_none();
+        let now = Instant::now();
+        let elapsed = self.last_at.replace(now).map(|at| now.duration_since(at));
+        let mut last = self.last.take().unwrap_or_default();
+        let mut next = HashMap::with_capacity(conns.len());
+        let conns = conns
+            .iter()
+            .map(|conn| {
+                next.insert(conn.id.clone(), (conn.upload, conn.download));
+                (conn, last.remove(&conn.id))
+            })
+            .collect();
+        self.last = Some(next);
+        SnapshotDelta { conns, removed: last.into_values().collect(), baseline, elapsed }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test;
+
+    fn conn(id: &str, upload: u64, download: u64) -> Connection {
+        test::conn(id).bytes(upload, download).build()
+    }
+
+    #[test]
+    fn added_removed_and_kept_connections() {
+        let mut differ = SnapshotDiffer::default();
+        let first = [conn("a", 1, 10), conn("b", 2, 20)];
+        let delta = differ.diff(&first);
+        assert!(delta.baseline);
+        assert_eq!(delta.added().count(), 0);
+        assert!(delta.rates().all(|(_, rate)| rate == (0, 0)));
+
+        let second = [conn("b", 5, 50), conn("c", 1, 1)];
+        let delta = differ.diff(&second);
+        assert!(!delta.baseline);
+        assert_eq!(delta.added().map(|c| c.id.as_str()).collect::<Vec<_>>(), ["c"]);
+        assert_eq!(delta.removed, [(1, 10)]);
+        let rates = delta.rates().map(|(c, rate)| (c.id.as_str(), rate)).collect::<Vec<_>>();
+        assert_eq!(rates, [("b", (3, 30)), ("c", (0, 0))]);
+    }
+
+    #[test]
+    fn bytes_per_second_between_snapshots() {
+        let mut differ = SnapshotDiffer::default();
+        let mut delta = differ.diff(&[]);
+        assert_eq!(delta.elapsed, None);
+        assert_eq!(delta.per_sec(100), 0);
+        delta.elapsed = Some(Duration::from_millis(500));
+        assert_eq!(delta.per_sec(100), 200);
+        assert!(differ.diff(&[]).elapsed.is_some());
+    }
+}
@@ -0,0 +1,52 @@
+/// Snapshots after a dropped one during which the drop is still reported.
+const RECENT_SNAPSHOTS: usize = 5;
+
+/// Connections snapshots dropped because the Connections tab fell behind the stream.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DropStats {
+    /// Snapshots dropped since the stream started
+    pub dropped: u64,
+    /// A snapshot was dropped within the last [`RECENT_SNAPSHOTS`]
+    pub recent: bool,
+}
+
+/// Counts the connections snapshots the queue of the Connections tab overflowed with, see
+/// [`Self::record_dropped`]. Their connections and the bytes they transferred are never shown.
+#[derive(Debug, Default)]
+pub struct SnapshotDrops {
+    since_dropped: Option<usize>,
+    stats: DropStats,
+}
+
+impl SnapshotDrops {
+    /// A snapshot was received.
+    pub fn update(&mut self) -> DropStats {
+        self.since_dropped = self.since_dropped.map(|n| n + 1);
+        self.stats.recent = self.since_dropped.is_some_and(|n| n <= RECENT_SNAPSHOTS);
+        self.stats
+    }
+
+    /// A snapshot was dropped before the Connections tab received it.
+    pub fn record_dropped(&mut self) {
+        self.stats.dropped += 1;
+        self.since_dropped = Some(0);
+        self.stats.recent = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropped_snapshots_are_reported_for_a_while() {
+        let mut drops = SnapshotDrops::default();
+        assert_eq!(drops.update(), DropStats::default());
+
+        drops.record_dropped();
+        for _ in 0..RECENT_SNAPSHOTS {
+            assert_eq!(drops.update(), DropStats { dropped: 1, recent: true });
+        }
+        assert_eq!(drops.update(), DropStats { dropped: 1, recent: false });
+    }
+}
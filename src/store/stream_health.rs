@@ -0,0 +1,184 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+/// Websocket streams (by API path) that are disconnected or stalled and not yet receiving data.
+static STALLED: LazyLock<RwLock<HashSet<String>>> = LazyLock::new(Default::default);
+/// Messages received per stream (by API path), the app's own ingest.
+static INGEST: LazyLock<Mutex<HashMap<String, Ingest>>> = LazyLock::new(Default::default);
+
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Ingest rate of a stream, shown in the tasks popup to tell whether UI lag comes from a flood of
+/// messages from the core.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngestRate {
+    pub path: String,
+    pub msgs_per_sec: f64,
+    pub bytes_per_sec: f64,
+    pub total_msgs: u64,
+    pub total_bytes: u64,
+}
+
+/// Counters of a stream, rates are computed over fixed windows of [`RATE_WINDOW`].
+#[derive(Debug, Clone, Copy)]
+struct Ingest {
+    window_start: Instant,
+    window_msgs: u64,
+    window_bytes: u64,
+    rate: (f64, f64),
+    total_msgs: u64,
+    total_bytes: u64,
+}
+
+impl Ingest {
+    fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            window_msgs: 0,
+            window_bytes: 0,
+            rate: (0.0, 0.0),
+            total_msgs: 0,
+            total_bytes: 0,
+        }
+    }
+
+    fn record(&mut self, now: Instant, bytes: usize) {
+        let elapsed = now.duration_since(self.window_start);
+        if elapsed >= RATE_WINDOW {
+            self.rate = self.window_rate(elapsed);
+            self.window_start = now;
+            self.window_msgs = 0;
+            self.window_bytes = 0;
+        }
+        self.window_msgs += 1;
+        self.window_bytes += bytes as u64;
+        self.total_msgs += 1;
+        self.total_bytes += bytes as u64;
+    }
+
+    fn window_rate(&self, elapsed: Duration) -> (f64, f64) {
+        let secs = elapsed.as_secs_f64();
+        (self.window_msgs as f64 / secs, self.window_bytes as f64 / secs)
+    }
+
+    /// Rate of the last full window, decays once the stream goes quiet.
+    fn rate(&self, now: Instant) -> (f64, f64) {
+        let elapsed = now.duration_since(self.window_start);
+        if elapsed >= RATE_WINDOW * 2 { self.window_rate(elapsed) } else { self.rate }
+    }
+}
+
+/// Health of the websocket streams, written by the reconnecting stream and read by consumers to
+/// show a "stream stalled" indicator.
+pub struct StreamHealth;
+
+impl StreamHealth {
+    pub fn set_stalled(path: &str, stalled: bool) {
+        // cheap check first, it is called on every message
+        if Self::is_stalled(path) == stalled {
+            return;
+        }
+        let mut guard = STALLED.write().unwrap();
+        if stalled {
+            guard.insert(path.to_string());
+        } else {
+            guard.remove(path);
+        }
+    }
+
+    pub fn is_stalled(path: &str) -> bool {
+        STALLED.read().unwrap().contains(path)
+    }
+
+    /// Count a message of `bytes` received on the stream of `path`.
+    pub fn record_message(path: &str, bytes: usize) {
+        let now = Instant::now();
+        let mut guard = INGEST.lock().unwrap();
+        match guard.get_mut(path) {
+            Some(ingest) => ingest.record(now, bytes),
+            None => {
+                let mut ingest = Ingest::new(now);
+                ingest.record(now, bytes);
+                guard.insert(path.to_string(), ingest);
+            }
+        }
+    }
+
+    /// Ingest rates of all streams that received a message, ordered by path.
+    pub fn ingest_rates() -> Vec<IngestRate> {
+        let now = Instant::now();
+        let mut rates = INGEST
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(path, ingest)| {
+                let (msgs_per_sec, bytes_per_sec) = ingest.rate(now);
+                IngestRate {
+                    path: path.clone(),
+                    msgs_per_sec,
+                    bytes_per_sec,
+                    total_msgs: ingest.total_msgs,
+                    total_bytes: ingest.total_bytes,
+                }
+            })
+            .collect::<Vec<_>>();
+        rates.sort_by(|a, b| a.path.cmp(&b.path));
+        rates
+    }
+
+    /// Right aligned block title shown while the stream of `path` is down or stalled.
+    pub fn indicator<'a>(path: &str) -> Line<'a> {
+        if !Self::is_stalled(path) {
+            return Line::default();
+        }
+        Line::from(vec![
+            Span::styled(
+                " stream stalled ",
+                Style::default().fg(Color::White).bg(Color::Red).bold(),
+            ),
+            Span::raw(" "),
+        ])
+        .right_aligned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stalled_state_per_path() {
+        StreamHealth::set_stalled("/test-health", true);
+        assert!(StreamHealth::is_stalled("/test-health"));
+        assert!(!StreamHealth::is_stalled("/test-health-other"));
+        StreamHealth::set_stalled("/test-health", false);
+        assert!(!StreamHealth::is_stalled("/test-health"));
+    }
+
+    #[test]
+    fn ingest_rate_per_window() {
+        let start = Instant::now();
+        let mut ingest = Ingest::new(start);
+        for i in 0..10 {
+            ingest.record(start + Duration::from_millis(i * 100), 100);
+        }
+        // first window is still open
+        assert_eq!(ingest.rate(start + Duration::from_millis(950)), (0.0, 0.0));
+
+        ingest.record(start + RATE_WINDOW, 100);
+        assert_eq!(ingest.rate(start + RATE_WINDOW), (10.0, 1000.0));
+        assert_eq!((ingest.total_msgs, ingest.total_bytes), (11, 1100));
+
+        // quiet stream decays
+        let (msgs, bytes) = ingest.rate(start + RATE_WINDOW * 5);
+        assert_eq!((msgs, bytes), (0.25, 25.0));
+
+        StreamHealth::record_message("/test-ingest", 42);
+        let rate = StreamHealth::ingest_rates().into_iter().find(|r| r.path == "/test-ingest");
+        assert_eq!(rate.map(|r| (r.total_msgs, r.total_bytes)), Some((1, 42)));
+    }
+}
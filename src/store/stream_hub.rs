@@ -0,0 +1,345 @@
+use std::io;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+
+use futures_util::{StreamExt, TryStreamExt, future};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::api::Api;
+use crate::api::stream::{MEMORY_PATH, TRAFFIC_PATH};
+use crate::components::ComponentId;
+use crate::models::{Memory, Traffic};
+use crate::task_manager::TaskManager;
+
+/// Records buffered per subscriber, both streams push one record per second.
+const CAPACITY: usize = 16;
+
+static GLOBAL: LazyLock<StreamHub> = LazyLock::new(StreamHub::default);
+
+/// Shares the `/traffic` and `/memory` streams among components: each stream is subscribed once
+/// on the first [`Subscription`], broadcast to every subscriber, and stopped when the last one
+/// is dropped, so nothing streams while no component is watching.
+#[derive(Default)]
+pub struct StreamHub {
+    api: RwLock<Option<Arc<Api>>>,
+    traffic: Topic<Traffic>,
+    memory: Topic<Memory>,
+}
+
+impl StreamHub {
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    /// Use `api` for streams started from now on, running streams are restarted with it.
+    pub fn set_api(&'static self, api: Arc<Api>) {
+        *self.api.write().unwrap() = Some(api);
+        self.traffic.restart(|token| self.start_traffic(token));
+        self.memory.restart(|token| self.start_memory(token));
+    }
+
+    pub fn traffic(&'static self) -> Subscription<Traffic> {
+        self.traffic.subscribe(|token| self.start_traffic(token))
+    }
+
+    pub fn memory(&'static self) -> Subscription<Memory> {
+        self.memory.subscribe(|token| self.start_memory(token))
+    }
+
+    /// Stop both streams while keeping the subscriptions, e.g. while the terminal is unfocused.
+    pub fn pause(&'static self) {
+        info!("Pausing shared streams");
+        self.traffic.pause();
+        self.memory.pause();
+    }
+
+    /// Restart the streams stopped by [`Self::pause`] that still have subscribers.
+    pub fn resume(&'static self) {
+        info!("Resuming shared streams");
+        self.traffic.resume(|token| self.start_traffic(token));
+        self.memory.resume(|token| self.start_memory(token));
+    }
+
+    fn api(&self) -> io::Result<Arc<Api>> {
+        self.api.read().unwrap().clone().ok_or_else(|| io::Error::other("API is not initialized"))
+    }
+
+    fn start_traffic(&'static self, token: CancellationToken) -> io::Result<()> {
+        info!("Subscribing traffic stream");
+        let api = self.api()?;
+        let tx = self.traffic.tx.clone();
+        let guard = StreamGuard { topic: &self.traffic, token: token.clone() };
+        TaskManager::spawn(ComponentId::Root, "traffic-hub", async move {
+            let _guard = guard;
+            let stream = match api.stream_traffic().await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    error!(error = ?e, "Failed to get traffic stream");
+                    return;
+                }
+            };
+            stream
+                .take_until(token.cancelled())
+                .inspect_err(|e| warn!("Failed to parse traffic: {e}"))
+                .filter_map(|res| future::ready(res.ok()))
+                .for_each(|record| {
+                    let _ = tx.send(record);
+                    future::ready(())
+                })
+                .await;
+            info!(path = TRAFFIC_PATH, "Stream stopped");
+        })?;
+        Ok(())
+    }
+
+    fn start_memory(&'static self, token: CancellationToken) -> io::Result<()> {
+        info!("Subscribing memory stream");
+        let api = self.api()?;
+        let tx = self.memory.tx.clone();
+        let guard = StreamGuard { topic: &self.memory, token: token.clone() };
+        TaskManager::spawn(ComponentId::Root, "memory-hub", async move {
+            let _guard = guard;
+            let stream = match api.stream_memory().await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    error!(error = ?e, "Failed to get memory stream");
+                    return;
+                }
+            };
+            stream
+                .take_until(token.cancelled())
+                .inspect_err(|e| warn!("Failed to parse memory: {e}"))
+                .filter_map(|res| future::ready(res.ok()))
+                .for_each(|record| {
+                    let _ = tx.send(record);
+                    future::ready(())
+                })
+                .await;
+            info!(path = MEMORY_PATH, "Stream stopped");
+        })?;
+        Ok(())
+    }
+}
+
+/// One shared stream, running while it has subscribers.
+struct Topic<T> {
+    tx: broadcast::Sender<T>,
+    state: Mutex<TopicState>,
+}
+
+#[derive(Default)]
+struct TopicState {
+    subscribers: usize,
+    /// Token of the running stream
+    token: Option<CancellationToken>,
+    paused: bool,
+}
+
+impl<T: Clone> Default for Topic<T> {
+    fn default() -> Self {
+        Self { tx: broadcast::channel(CAPACITY).0, state: Default::default() }
+    }
+}
+
+impl<T: Clone> Topic<T> {
+    fn subscribe(
+        &'static self,
+        start: impl FnOnce(CancellationToken) -> io::Result<()>,
+    ) -> Subscription<T> {
+        let mut state = self.state.lock().unwrap();
+        state.subscribers += 1;
+        if state.token.is_none() && !state.paused {
+            let token = CancellationToken::new();
+            match start(token.clone()) {
+                Ok(()) => state.token = Some(token),
+                Err(e) => error!(error = ?e, "Failed to start shared stream"),
+            }
+        }
+        Subscription { rx: self.tx.subscribe(), topic: self }
+    }
+
+    fn restart(&'static self, start: impl FnOnce(CancellationToken) -> io::Result<()>) {
+        let mut state = self.state.lock().unwrap();
+        let Some(token) = state.token.take() else {
+            return;
+        };
+        token.cancel();
+        let token = CancellationToken::new();
+        match start(token.clone()) {
+            Ok(()) => state.token = Some(token),
+            Err(e) => error!(error = ?e, "Failed to restart shared stream"),
+        }
+    }
+
+    /// Stop the stream but keep the subscriptions, until [`Self::resume`].
+    fn pause(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.paused = true;
+        if let Some(token) = state.token.take() {
+            token.cancel();
+        }
+    }
+
+    fn resume(&'static self, start: impl FnOnce(CancellationToken) -> io::Result<()>) {
+        let mut state = self.state.lock().unwrap();
+        state.paused = false;
+        if state.subscribers == 0 || state.token.is_some() {
+            return;
+        }
+        let token = CancellationToken::new();
+        match start(token.clone()) {
+            Ok(()) => state.token = Some(token),
+            Err(e) => error!(error = ?e, "Failed to resume shared stream"),
+        }
+    }
+
+    /// The stream of `token` exited, forget it unless it was already replaced, so the next
+    /// subscription starts a new one.
+    fn stopped(&self, token: &CancellationToken) {
+        let mut state = self.state.lock().unwrap();
+        // pause, restart and the last unsubscribe cancel the token they take
+        if !token.is_cancelled() {
+            state.token = None;
+        }
+    }
+
+    fn unsubscribe(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.subscribers = state.subscribers.saturating_sub(1);
+        if state.subscribers == 0
+            && let Some(token) = state.token.take()
+        {
+            token.cancel();
+        }
+    }
+}
+
+/// Held by a stream task, tells its topic when the stream exits on its own, e.g. on an error.
+struct StreamGuard<T: Clone + 'static> {
+    topic: &'static Topic<T>,
+    token: CancellationToken,
+}
+
+impl<T: Clone> Drop for StreamGuard<T> {
+    fn drop(&mut self) {
+        self.topic.stopped(&self.token);
+    }
+}
+
+/// Records of a shared stream, the stream stops once every subscription is dropped.
+pub struct Subscription<T: Clone + 'static> {
+    rx: broadcast::Receiver<T>,
+    topic: &'static Topic<T>,
+}
+
+impl<T: Clone> Subscription<T> {
+    /// Next record, records missed by a slow subscriber are skipped. `None` once the hub is
+    /// gone.
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            match self.rx.recv().await {
+                Ok(record) => return Some(record),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+impl<T: Clone> Drop for Subscription<T> {
+    fn drop(&mut self) {
+        self.topic.unsubscribe();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn stream_runs_while_subscribed() {
+        let topic: &'static Topic<u32> = Box::leak(Box::default());
+        let started = Arc::new(AtomicUsize::new(0));
+        let token = Arc::new(Mutex::new(None));
+        let start = |t: CancellationToken| {
+            started.fetch_add(1, Ordering::Relaxed);
+            *token.lock().unwrap() = Some(t);
+            Ok(())
+        };
+
+        let mut first = topic.subscribe(start);
+        let mut second = topic.subscribe(start);
+        assert_eq!(started.load(Ordering::Relaxed), 1);
+        topic.tx.send(7).unwrap();
+        assert_eq!(first.recv().await, Some(7));
+        assert_eq!(second.recv().await, Some(7));
+
+        drop(first);
+        let running = token.lock().unwrap().clone().unwrap();
+        assert!(!running.is_cancelled());
+        drop(second);
+        assert!(running.is_cancelled());
+
+        // subscribing again starts a new stream
+        let _third = topic.subscribe(start);
+        assert_eq!(started.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn exited_stream_restarts_on_next_subscription() {
+        let topic: &'static Topic<u32> = Box::leak(Box::default());
+        let started = Arc::new(AtomicUsize::new(0));
+        let token = Arc::new(Mutex::new(None));
+        let start = |t: CancellationToken| {
+            started.fetch_add(1, Ordering::Relaxed);
+            *token.lock().unwrap() = Some(t);
+            Ok(())
+        };
+
+        let _first = topic.subscribe(start);
+        let running = token.lock().unwrap().clone().unwrap();
+        // the stream failed
+        drop(StreamGuard { topic, token: running });
+        let _second = topic.subscribe(start);
+        assert_eq!(started.load(Ordering::Relaxed), 2);
+
+        // a stream replaced by a restart doesn't forget the new one
+        let replaced = token.lock().unwrap().clone().unwrap();
+        topic.restart(start);
+        drop(StreamGuard { topic, token: replaced });
+        let _third = topic.subscribe(start);
+        assert_eq!(started.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn paused_stream_resumes_for_remaining_subscribers() {
+        let topic: &'static Topic<u32> = Box::leak(Box::default());
+        let started = Arc::new(AtomicUsize::new(0));
+        let token = Arc::new(Mutex::new(None));
+        let start = |t: CancellationToken| {
+            started.fetch_add(1, Ordering::Relaxed);
+            *token.lock().unwrap() = Some(t);
+            Ok(())
+        };
+
+        let first = topic.subscribe(start);
+        let running = token.lock().unwrap().clone().unwrap();
+        topic.pause();
+        assert!(running.is_cancelled());
+        // subscribing while paused doesn't start it
+        let second = topic.subscribe(start);
+        assert_eq!(started.load(Ordering::Relaxed), 1);
+
+        topic.resume(start);
+        assert_eq!(started.load(Ordering::Relaxed), 2);
+        drop((first, second));
+        // nothing to resume without subscribers
+        topic.pause();
+        topic.resume(start);
+        assert_eq!(started.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,88 @@
+use std::sync::{LazyLock, Mutex};
+
+use ratatui::style::Color;
+use ringbuffer::{AllocRingBuffer, RingBuffer};
+use strum::IntoStaticStr;
+use time::OffsetDateTime;
+
+const CAPACITY: usize = 200;
+
+static TIMELINE: LazyLock<Mutex<Events>> = LazyLock::new(|| Mutex::new(Events::new(CAPACITY)));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoStaticStr)]
+pub enum EventKind {
+    Proxy,
+    Config,
+    Provider,
+    Rule,
+    Core,
+    Alert,
+}
+
+impl EventKind {
+    pub fn color(self) -> Color {
+        match self {
+            EventKind::Proxy => Color::Cyan,
+            EventKind::Config => Color::LightBlue,
+            EventKind::Provider => Color::Green,
+            EventKind::Rule => Color::LightMagenta,
+            EventKind::Core => Color::Yellow,
+            EventKind::Alert => Color::LightRed,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TimelineEvent {
+    pub time: OffsetDateTime,
+    pub kind: EventKind,
+    pub message: String,
+}
+
+/// Events kept by the [`Timeline`], the oldest are dropped first once `capacity` is reached.
+struct Events(AllocRingBuffer<TimelineEvent>);
+
+impl Events {
+    fn new(capacity: usize) -> Self {
+        Self(AllocRingBuffer::new(capacity))
+    }
+
+    fn push(&mut self, kind: EventKind, message: String) {
+        self.0.enqueue(TimelineEvent { time: OffsetDateTime::now_utc(), kind, message });
+    }
+
+    fn newest_first(&self) -> Vec<TimelineEvent> {
+        self.0.iter().rev().cloned().collect()
+    }
+}
+
+/// Bounded record of notable user actions and core events, the oldest are dropped first.
+pub struct Timeline;
+
+impl Timeline {
+    pub fn record(kind: EventKind, message: impl Into<String>) {
+        TIMELINE.lock().unwrap().push(kind, message.into());
+    }
+
+    /// Events, newest first.
+    pub fn snapshot() -> Vec<TimelineEvent> {
+        TIMELINE.lock().unwrap().newest_first()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timeline_is_bounded_and_newest_first() {
+        let mut events = Events::new(CAPACITY);
+        for i in 0..CAPACITY + 5 {
+            events.push(EventKind::Proxy, format!("event {i}"));
+        }
+        let events = events.newest_first();
+        assert_eq!(events.len(), CAPACITY);
+        assert_eq!(events[0].message, format!("event {}", CAPACITY + 4));
+        assert_eq!(events[CAPACITY - 1].message, "event 5");
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::HashSet;
+use std::sync::{LazyLock, RwLock};
+
+use crate::components::ComponentId;
+
+/// Tabs eagerly loaded on startup whose first load has not finished yet, and the warmup size.
+static PENDING: LazyLock<RwLock<(HashSet<ComponentId>, usize)>> = LazyLock::new(Default::default);
+
+/// Readiness of the tabs loaded in parallel on startup, shown as progress in the header.
+pub struct Warmup;
+
+impl Warmup {
+    pub fn start(ids: &[ComponentId]) {
+        *PENDING.write().unwrap() = (ids.iter().copied().collect(), ids.len());
+    }
+
+    /// Mark the first load of `id` finished, whether it succeeded or not.
+    pub fn ready(id: ComponentId) {
+        // cheap check first, it is called on every reload
+        if !PENDING.read().unwrap().0.contains(&id) {
+            return;
+        }
+        PENDING.write().unwrap().0.remove(&id);
+    }
+
+    /// `(ready, total)` while the warmup is in progress.
+    pub fn progress() -> Option<(usize, usize)> {
+        let guard = PENDING.read().unwrap();
+        let (pending, total) = (guard.0.len(), guard.1);
+        (pending > 0).then_some((total - pending, total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_until_all_ready() {
+        Warmup::start(&[ComponentId::Rules, ComponentId::Proxies]);
+        assert_eq!(Warmup::progress(), Some((0, 2)));
+        Warmup::ready(ComponentId::Rules);
+        Warmup::ready(ComponentId::Rules);
+        Warmup::ready(ComponentId::Logs);
+        assert_eq!(Warmup::progress(), Some((1, 2)));
+        Warmup::ready(ComponentId::Proxies);
+        assert_eq!(Warmup::progress(), None);
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, debug, debug_span, info};
+
+use crate::components::ComponentId;
+
+/// Registry of background tasks spawned by components.
+///
+/// Every task is registered with an id, its owner component, a purpose (the task name) and a
+/// cancellation token. Tasks unregister themselves when they finish or are cancelled, so the
+/// registry only ever lists running tasks.
+#[derive(Debug, Default)]
+pub struct TaskManager {
+    next_id: AtomicU64,
+    tasks: Mutex<BTreeMap<u64, TaskEntry>>,
+}
+
+#[derive(Debug)]
+struct TaskEntry {
+    owner: ComponentId,
+    name: &'static str,
+    started_at: Instant,
+    token: CancellationToken,
+}
+
+/// Snapshot of a running task, used for display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskInfo {
+    pub id: u64,
+    pub owner: ComponentId,
+    pub name: &'static str,
+    pub elapsed: Duration,
+}
+
+/// Removes the task from the registry when the task future is dropped (finished, cancelled or
+/// never spawned).
+struct TaskGuard {
+    manager: &'static TaskManager,
+    id: u64,
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.manager.tasks.lock().unwrap().remove(&self.id);
+    }
+}
+
+impl TaskManager {
+    pub const fn new() -> Self {
+        Self { next_id: AtomicU64::new(0), tasks: Mutex::new(BTreeMap::new()) }
+    }
+
+    fn global() -> &'static TaskManager {
+        static INSTANCE: TaskManager = TaskManager::new();
+        &INSTANCE
+    }
+
+    /// Spawn a named task owned by `owner`.
+    ///
+    /// The task is aborted at its next await point once cancelled, in which case the join handle
+    /// resolves to `None`.
+    pub fn spawn<F>(
+        owner: ComponentId,
+        name: &'static str,
+        future: F,
+    ) -> io::Result<JoinHandle<Option<F::Output>>>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        Self::global().spawn_task(owner, name, future)
+    }
+
+    /// Cancel all running tasks started by `owner`, returns the number of cancelled tasks.
+    pub fn cancel_owner(owner: ComponentId) -> usize {
+        Self::global().cancel_tasks_of(owner)
+    }
+
+    /// Cancel a single task by id, returns `false` if the task is not running.
+    pub fn cancel(id: u64) -> bool {
+        Self::global().cancel_task(id)
+    }
+
+    /// Running tasks ordered by spawn order.
+    pub fn snapshot() -> Vec<TaskInfo> {
+        Self::global().running()
+    }
+
+    fn spawn_task<F>(
+        &'static self,
+        owner: ComponentId,
+        name: &'static str,
+        future: F,
+    ) -> io::Result<JoinHandle<Option<F::Output>>>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let token = CancellationToken::new();
+        let entry = TaskEntry { owner, name, started_at: Instant::now(), token: token.clone() };
+        self.tasks.lock().unwrap().insert(id, entry);
+        let guard = TaskGuard { manager: self, id };
+
+        // same span name as component update/draw, so one filter covers all logs of a component
+        let span = debug_span!("component", id = owner.full_name(), task = name);
+        tokio::task::Builder::new().name(name).spawn(
+            async move {
+                let _guard = guard;
+                tokio::select! {
+                    _ = token.cancelled() => {
+                        debug!(id, name, ?owner, "Task cancelled");
+                        None
+                    }
+                    output = future => Some(output),
+                }
+            }
+            .instrument(span),
+        )
+    }
+
+    fn cancel_tasks_of(&self, owner: ComponentId) -> usize {
+        let tasks = self.tasks.lock().unwrap();
+        let mut cancelled = 0;
+        for entry in tasks.values().filter(|e| e.owner == owner && !e.token.is_cancelled()) {
+            entry.token.cancel();
+            cancelled += 1;
+        }
+        if cancelled > 0 {
+            info!(?owner, cancelled, "Cancelled tasks of component");
+        }
+        cancelled
+    }
+
+    fn cancel_task(&self, id: u64) -> bool {
+        let tasks = self.tasks.lock().unwrap();
+        let Some(entry) = tasks.get(&id) else {
+            return false;
+        };
+        info!(id, name = entry.name, owner = ?entry.owner, "Cancelling task");
+        entry.token.cancel();
+        true
+    }
+
+    fn running(&self) -> Vec<TaskInfo> {
+        let now = Instant::now();
+        self.tasks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(&id, e)| TaskInfo {
+                id,
+                owner: e.owner,
+                name: e.name,
+                elapsed: now.saturating_duration_since(e.started_at),
+            })
+            .collect()
+    }
+}
+
+/// A blocking job, e.g. file IO encrypted with scrypt, run on the blocking thread pool so it
+/// doesn't stall the UI. The component that started it polls its output, usually on tick.
+#[derive(Debug)]
+pub struct BlockingJob<T> {
+    output: Arc<Mutex<Option<anyhow::Result<T>>>>,
+    running: bool,
+}
+
+impl<T> Default for BlockingJob<T> {
+    fn default() -> Self {
+        Self { output: Default::default(), running: false }
+    }
+}
+
+impl<T: Send + 'static> BlockingJob<T> {
+    /// Run `job` in a task named `name` owned by `owner`, the output of a job still running is
+    /// discarded.
+    pub fn start<F>(&mut self, owner: ComponentId, name: &'static str, job: F) -> io::Result<()>
+    where
+        F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+    {
+        let output = Arc::new(Mutex::new(None));
+        self.output = Arc::clone(&output);
+        self.running = true;
+        TaskManager::spawn(owner, name, async move {
+            let result = tokio::task::spawn_blocking(job)
+                .await
+                .context("Fail to join blocking job")
+                .and_then(|result| result);
+            *output.lock().unwrap() = Some(result);
+        })?;
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Output of the finished job, returned once.
+    pub fn poll(&mut self) -> Option<anyhow::Result<T>> {
+        let output = self.output.lock().unwrap().take()?;
+        self.running = false;
+        Some(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test::poll_until;
+
+    #[tokio::test]
+    async fn finished_task_unregisters_itself() {
+        static TASKS: TaskManager = TaskManager::new();
+        let handle = TASKS.spawn_task(ComponentId::DnsQuery, "test-finish", async { 42 }).unwrap();
+
+        assert_eq!(handle.await.unwrap(), Some(42));
+        assert!(TASKS.running().is_empty());
+    }
+
+    #[tokio::test]
+    async fn cancel_owner_stops_only_its_tasks() {
+        static TASKS: TaskManager = TaskManager::new();
+        let pending = TASKS
+            .spawn_task(ComponentId::Rules, "test-pending", std::future::pending::<()>())
+            .unwrap();
+        let other = TASKS
+            .spawn_task(ComponentId::RuleProviders, "test-other", std::future::pending::<()>())
+            .unwrap();
+        assert_eq!(TASKS.running().len(), 2);
+
+        assert_eq!(TASKS.cancel_tasks_of(ComponentId::Rules), 1);
+        assert_eq!(pending.await.unwrap(), None);
+        let running = TASKS.running();
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].owner, ComponentId::RuleProviders);
+
+        assert!(TASKS.cancel_task(running[0].id));
+        assert_eq!(other.await.unwrap(), None);
+        assert!(!TASKS.cancel_task(running[0].id));
+    }
+
+    #[tokio::test]
+    async fn blocking_job_output_is_polled_once() {
+        let mut job = BlockingJob::default();
+        job.start(ComponentId::Tasks, "test-blocking", || Ok(42)).unwrap();
+        assert!(job.is_running());
+        assert_eq!(poll_until(|| job.poll()).await.unwrap(), 42);
+        assert!(!job.is_running());
+        assert!(job.poll().is_none());
+    }
+}
@@ -7,8 +7,9 @@ use std::time::Duration;
 use anyhow::Result;
 use crossterm::cursor;
 use crossterm::event::{
-    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
-    Event as CrosstermEvent, EventStream, KeyEvent, KeyEventKind, MouseEvent,
+    DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange, EnableMouseCapture, Event as CrosstermEvent, EventStream, KeyEvent,
+    KeyEventKind, MouseEvent,
 };
 use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
 use futures_util::{FutureExt, StreamExt};
@@ -157,7 +158,7 @@ impl Tui {
 
     pub fn enter(&mut self) -> Result<()> {
         crossterm::terminal::enable_raw_mode()?;
-        crossterm::execute!(stdout(), EnterAlternateScreen, cursor::Hide)?;
+        crossterm::execute!(stdout(), EnterAlternateScreen, cursor::Hide, EnableFocusChange)?;
         if self.mouse {
             crossterm::execute!(stdout(), EnableMouseCapture)?;
         }
@@ -178,7 +179,7 @@ impl Tui {
             if self.mouse {
                 crossterm::execute!(stdout(), DisableMouseCapture)?;
             }
-            crossterm::execute!(stdout(), LeaveAlternateScreen, cursor::Show)?;
+            crossterm::execute!(stdout(), DisableFocusChange, LeaveAlternateScreen, cursor::Show)?;
             crossterm::terminal::disable_raw_mode()?;
         }
         Ok(())
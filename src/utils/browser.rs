@@ -0,0 +1,63 @@
+use std::env;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, anyhow};
+
+/// Resolve the command used to open URLs.
+///
+/// Returns `None` if opening URLs is disabled: `configured` is set to an empty string, or it is
+/// not set and no graphical session is detected.
+pub fn resolve_opener(configured: Option<&str>) -> Option<String> {
+    match configured.map(str::trim) {
+        Some("") => None,
+        Some(command) => Some(command.to_owned()),
+        None if is_headless() => None,
+        None => Some(default_opener().to_owned()),
+    }
+}
+
+/// Launch `opener` with `url` as the last argument, without waiting for it to exit.
+pub fn open_url(opener: &str, url: &str) -> Result<()> {
+    let mut parts = opener.split_whitespace();
+    let program = parts.next().ok_or_else(|| anyhow!("Empty URL open command"))?;
+    let mut child = Command::new(program)
+        .args(parts)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Fail to spawn `{opener}`"))?;
+    // reap the child in background, openers usually exit right after handing the url over
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+fn default_opener() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "explorer"
+    } else {
+        "xdg-open"
+    }
+}
+
+fn is_headless() -> bool {
+    if cfg!(any(target_os = "macos", windows)) {
+        return env::var_os("SSH_CONNECTION").is_some();
+    }
+    let has_display = |name| env::var_os(name).is_some_and(|v| !v.is_empty());
+    !has_display("DISPLAY") && !has_display("WAYLAND_DISPLAY")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_opener_takes_precedence() {
+        assert_eq!(resolve_opener(Some(" firefox ")), Some("firefox".into()));
+        assert_eq!(resolve_opener(Some("")), None);
+    }
+}
@@ -0,0 +1,12 @@
+use std::io::stdout;
+
+use anyhow::{Context, Result};
+use crossterm::clipboard::CopyToClipboard;
+
+/// Copy `text` to the clipboard through the terminal (OSC 52), so it also works over SSH.
+///
+/// Terminals without OSC 52 support silently ignore it.
+pub fn copy(text: &str) -> Result<()> {
+    crossterm::execute!(stdout(), CopyToClipboard::to_clipboard_from(text))
+        .context("Fail to write to the terminal clipboard")
+}
@@ -0,0 +1,99 @@
+use std::cmp::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+
+use serde::{Deserialize, Serialize};
+
+/// How text columns and names are ordered when sorting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Collation {
+    /// Plain byte order, e.g. `Node10` < `node10` < `node2`
+    #[default]
+    Bytewise,
+    /// Case-insensitive, digit runs compared by value, e.g. `node2` < `Node10`
+    Natural,
+}
+
+static NATURAL: AtomicBool = AtomicBool::new(false);
+
+impl Collation {
+    /// Set the collation used by [`compare`].
+    pub fn apply(self) {
+        NATURAL.store(self == Self::Natural, AtomicOrdering::Relaxed);
+    }
+}
+
+/// Compare text with the configured [`Collation`].
+pub fn compare(a: &str, b: &str) -> Ordering {
+    if NATURAL.load(AtomicOrdering::Relaxed) { natural_cmp(a, b) } else { a.cmp(b) }
+}
+
+/// Case-insensitive comparison with digit runs compared by value, ties are broken bytewise so
+/// the order is total.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut ai, mut bi) = (a.char_indices().peekable(), b.char_indices().peekable());
+    while let (Some(&(ia, ca)), Some(&(ib, cb))) = (ai.peek(), bi.peek()) {
+        if ca.is_ascii_digit() && cb.is_ascii_digit() {
+            let ra = digit_run(&a[ia..]);
+            let rb = digit_run(&b[ib..]);
+            let (na, nb) = (ra.trim_start_matches('0'), rb.trim_start_matches('0'));
+            let ord = na.len().cmp(&nb.len()).then_with(|| na.cmp(nb));
+            if ord != Ordering::Equal {
+                return ord;
+            }
+            // digit runs are ASCII, one char per byte
+            ai.nth(ra.len() - 1);
+            bi.nth(rb.len() - 1);
+            continue;
+        }
+        let ord = ca.to_lowercase().cmp(cb.to_lowercase());
+        if ord != Ordering::Equal {
+            return ord;
+        }
+        ai.next();
+        bi.next();
+    }
+    ai.peek().is_some().cmp(&bi.peek().is_some()).then_with(|| a.cmp(b))
+}
+
+fn digit_run(s: &str) -> &str {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    &s[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut names: Vec<&str>, cmp: fn(&str, &str) -> Ordering) -> Vec<&str> {
+        names.sort_by(|a, b| cmp(a, b));
+        names
+    }
+
+    #[test]
+    fn natural_order_compared_with_bytewise() {
+        let names = vec!["node10", "Node2", "node1", "node01", "alpha", "Beta", "node2"];
+        assert_eq!(
+            sorted(names.clone(), natural_cmp),
+            vec!["alpha", "Beta", "node01", "node1", "Node2", "node2", "node10"]
+        );
+        assert_eq!(
+            sorted(names, |a, b| a.cmp(b)),
+            vec!["Beta", "Node2", "alpha", "node01", "node1", "node10", "node2"]
+        );
+    }
+
+    #[test]
+    fn natural_cmp_edge_cases() {
+        assert_eq!(natural_cmp("", ""), Ordering::Equal);
+        assert_eq!(natural_cmp("", "a"), Ordering::Less);
+        assert_eq!(natural_cmp("a1b", "a1"), Ordering::Greater);
+        assert_eq!(natural_cmp("v1.10", "v1.9"), Ordering::Greater);
+        // longer than u64
+        assert_eq!(
+            natural_cmp("99999999999999999999999", "100000000000000000000000"),
+            Ordering::Less
+        );
+        assert_eq!(natural_cmp("香港 2", "香港 10"), Ordering::Less);
+    }
+}
@@ -4,10 +4,16 @@ use std::cmp::Ordering;
 use ratatui::layout::Constraint;
 
 use crate::models::sort::SortDir;
+use crate::utils::collation;
 
 pub trait TextResolver<T> {
     fn resolve<'row>(&self, col: &ColDef<T>, item: &'row T, text: Cow<'row, str>)
     -> Cow<'row, str>;
+
+    /// Typed sort key of a column without one, e.g. a number the text is resolved from.
+    fn sort_key(&self, _col: &ColDef<T>, _item: &T) -> Option<SortKey> {
+        None
+    }
 }
 
 pub fn filter_placeholder<'a, T, I>(cols: I) -> Option<String>
@@ -15,8 +21,11 @@ where
     T: 'a,
     I: IntoIterator<Item = &'a ColDef<T>>,
 {
-    let fields =
-        cols.into_iter().filter(|col| col.filterable).map(|col| col.title).collect::<Vec<_>>();
+    let fields = cols
+        .into_iter()
+        .filter(|col| col.filterable)
+        .map(|col| col.title.as_ref())
+        .collect::<Vec<_>>();
     if fields.is_empty() {
         None
     } else {
@@ -24,15 +33,18 @@ where
     }
 }
 
+/// Column of a table, `id` and `title` are borrowed for built-in columns and owned for the ones
+/// defined in the config.
 pub struct ColDef<T> {
     #[allow(dead_code)]
-    pub id: &'static str,
-    pub title: &'static str,
+    pub id: Cow<'static, str>,
+    pub title: Cow<'static, str>,
     pub filterable: bool,
     pub sortable: bool,
     /// value accessor, used in cell rendering and filtering
     pub accessor: for<'a> fn(&'a T) -> Cow<'a, str>,
-    /// sort key, optional. If None, use the string from accessor for sorting
+    /// sort key, optional. If None, the string from accessor is sorted with the configured
+    /// [`collation`]
     pub sort_key: Option<fn(&T) -> SortKey>,
 }
 
@@ -41,6 +53,25 @@ pub struct TableColDef<T> {
     pub constraint: Constraint,
 }
 
+impl<T> Clone for ColDef<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            filterable: self.filterable,
+            sortable: self.sortable,
+            accessor: self.accessor,
+            sort_key: self.sort_key,
+        }
+    }
+}
+
+impl<T> Clone for TableColDef<T> {
+    fn clone(&self) -> Self {
+        Self { col: self.col.clone(), constraint: self.constraint }
+    }
+}
+
 impl<T> AsRef<ColDef<T>> for ColDef<T> {
     fn as_ref(&self) -> &ColDef<T> {
         self
@@ -54,6 +85,14 @@ impl<T> AsRef<ColDef<T>> for TableColDef<T> {
 }
 
 impl<T> ColDef<T> {
+    /// Whether this is the built-in column `id`, ignoring case; usable in const contexts.
+    pub const fn is_builtin(&self, id: &str) -> bool {
+        match &self.id {
+            Cow::Borrowed(own) => own.eq_ignore_ascii_case(id),
+            Cow::Owned(_) => false,
+        }
+    }
+
     /// Compare two items based on this column definition
     #[allow(dead_code)]
     #[inline]
@@ -63,7 +102,7 @@ impl<T> ColDef<T> {
         } else {
             let sa = (self.accessor)(a);
             let sb = (self.accessor)(b);
-            sa.as_ref().cmp(sb.as_ref()) // use as_ref to avoid allocating
+            collation::compare(&sa, &sb)
         }
     }
 
@@ -73,10 +112,14 @@ impl<T> ColDef<T> {
     pub fn cmp_with_text_resolver(&self, a: &T, b: &T, resolver: &dyn TextResolver<T>) -> Ordering {
         if let Some(f) = self.sort_key {
             f(a).cmp(&f(b))
+        } else if let (Some(ka), Some(kb)) =
+            (resolver.sort_key(self, a), resolver.sort_key(self, b))
+        {
+            ka.cmp(&kb)
         } else {
             let sa = resolver.resolve(self, a, (self.accessor)(a));
             let sb = resolver.resolve(self, b, (self.accessor)(b));
-            sa.as_ref().cmp(sb.as_ref())
+            collation::compare(&sa, &sb)
         }
     }
 
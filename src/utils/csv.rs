@@ -0,0 +1,46 @@
+use std::fmt::{Display, Write};
+
+/// CSV text of the numeric exports, e.g. the traffic history. Fields are written as is, nothing is
+/// quoted.
+pub struct CsvWriter {
+    out: String,
+}
+
+impl CsvWriter {
+    pub fn new(header: &[&str]) -> Self {
+        let mut writer = Self { out: String::new() };
+        writer.push(header.iter().map(|h| h as &dyn Display));
+        writer
+    }
+
+    pub fn row(&mut self, fields: &[&dyn Display]) {
+        self.push(fields.iter().copied());
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+
+    fn push<'a>(&mut self, fields: impl Iterator<Item = &'a dyn Display>) {
+        for (idx, field) in fields.enumerate() {
+            if idx > 0 {
+                self.out.push(',');
+            }
+            _ = write!(self.out, "{field}");
+        }
+        self.out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_follow_the_header() {
+        let mut csv = CsvWriter::new(&["time", "up", "memory"]);
+        csv.row(&[&10, &1u64, &""]);
+        csv.row(&[&11, &3u64, &64u64]);
+        assert_eq!(csv.finish(), "time,up,memory\n10,1,\n11,3,64\n");
+    }
+}
@@ -0,0 +1,96 @@
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::sync::{LazyLock, RwLock};
+
+use age::secrecy::SecretString;
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::config::EncryptionConfig;
+
+/// Every age file starts with this line.
+const AGE_HEADER: &[u8] = b"age-encryption.org/v1";
+
+static CONFIG: LazyLock<RwLock<EncryptionConfig>> = LazyLock::new(Default::default);
+
+/// Encryption at rest of exported files with the configured [`EncryptionConfig`], in the age
+/// format so they can also be decrypted with `age -d`.
+pub struct Encryption;
+
+impl Encryption {
+    pub fn configure(config: &EncryptionConfig) {
+        *CONFIG.write().unwrap() = config.clone();
+    }
+
+    pub fn enabled() -> bool {
+        CONFIG.read().unwrap().passphrase_env.is_some()
+    }
+
+    /// Whether the file at `path` was written encrypted, `false` if it can't be read.
+    pub fn is_encrypted(path: &Path) -> bool {
+        let mut header = [0; AGE_HEADER.len()];
+        fs::File::open(path).and_then(|mut file| file.read_exact(&mut header)).is_ok()
+            && header == AGE_HEADER
+    }
+
+    /// Write `content` to `path`, encrypted when enabled.
+    pub fn write(path: &Path, content: &[u8]) -> Result<()> {
+        let content = match Self::passphrase()? {
+            Some(passphrase) => seal(passphrase, content)?,
+            None => content.to_vec(),
+        };
+        fs::write(path, content).with_context(|| format!("Fail to write file `{}`", path.display()))
+    }
+
+    /// Read `path` written by [`Self::write`], plain files are read as is whether enabled or not.
+    pub fn read(path: &Path) -> Result<Vec<u8>> {
+        let content =
+            fs::read(path).with_context(|| format!("Fail to read file `{}`", path.display()))?;
+        if !content.starts_with(AGE_HEADER) {
+            return Ok(content);
+        }
+        let Some(passphrase) = Self::passphrase()? else {
+            bail!("`{}` is encrypted, configure `encryption.passphrase-env`", path.display());
+        };
+        open(passphrase, &content).with_context(|| format!("Fail to decrypt `{}`", path.display()))
+    }
+
+    pub fn read_to_string(path: &Path) -> Result<String> {
+        String::from_utf8(Self::read(path)?)
+            .with_context(|| format!("`{}` is not valid UTF-8", path.display()))
+    }
+
+    /// An unset variable is an error rather than a silent fallback to plain text.
+    fn passphrase() -> Result<Option<SecretString>> {
+        let Some(name) = CONFIG.read().unwrap().passphrase_env.clone() else {
+            return Ok(None);
+        };
+        match env::var(&name) {
+            Ok(passphrase) if !passphrase.is_empty() => Ok(Some(passphrase.into())),
+            _ => Err(anyhow!("Encryption passphrase variable `{name}` is not set")),
+        }
+    }
+}
+
+fn seal(passphrase: SecretString, content: &[u8]) -> Result<Vec<u8>> {
+    age::encrypt(&age::scrypt::Recipient::new(passphrase), content).context("Fail to encrypt")
+}
+
+fn open(passphrase: SecretString, content: &[u8]) -> Result<Vec<u8>> {
+    Ok(age::decrypt(&age::scrypt::Identity::new(passphrase), content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_and_open_round_trip() {
+        let passphrase = || SecretString::from("correct horse".to_owned());
+        let sealed = seal(passphrase(), b"secret: abc").unwrap();
+        assert!(sealed.starts_with(AGE_HEADER));
+        assert_eq!(open(passphrase(), &sealed).unwrap(), b"secret: abc");
+        assert!(open(SecretString::from("wrong".to_owned()), &sealed).is_err());
+    }
+}
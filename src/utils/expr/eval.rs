@@ -0,0 +1,111 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+use super::parser::{Ast, CmpOp, Literal, Operand};
+use super::{FieldResolver, Value};
+
+pub fn eval(ast: &Ast, fields: &impl FieldResolver) -> bool {
+    match ast {
+        Ast::Or(lhs, rhs) => eval(lhs, fields) || eval(rhs, fields),
+        Ast::And(lhs, rhs) => eval(lhs, fields) && eval(rhs, fields),
+        Ast::Not(inner) => !eval(inner, fields),
+        Ast::Truthy(operand) => match value(operand, fields) {
+            Some(Value::Bool(b)) => b,
+            Some(Value::Num(n)) => n != 0.0,
+            Some(Value::Str(s)) => !s.is_empty(),
+            None => false,
+        },
+        Ast::Cmp(lhs, op, rhs) => match (value(lhs, fields), value(rhs, fields)) {
+            (Some(lhs), Some(rhs)) => compare(&lhs, *op, &rhs),
+            _ => false,
+        },
+    }
+}
+
+fn value<'a>(operand: &'a Operand, fields: &'a impl FieldResolver) -> Option<Value<'a>> {
+    match operand {
+        Operand::Field(name) => fields.resolve(name),
+        Operand::Literal(Literal::Num(n)) => Some(Value::Num(*n)),
+        Operand::Literal(Literal::Str(s)) => Some(Value::Str(Cow::Borrowed(s))),
+        Operand::Literal(Literal::Bool(b)) => Some(Value::Bool(*b)),
+    }
+}
+
+fn compare(lhs: &Value, op: CmpOp, rhs: &Value) -> bool {
+    match op {
+        CmpOp::Contains | CmpOp::StartsWith | CmpOp::EndsWith => {
+            let (Value::Str(lhs), Value::Str(rhs)) = (lhs, rhs) else {
+                return false;
+            };
+            let (lhs, rhs) = (lhs.to_lowercase(), rhs.to_lowercase());
+            match op {
+                CmpOp::Contains => lhs.contains(&rhs),
+                CmpOp::StartsWith => lhs.starts_with(&rhs),
+                _ => lhs.ends_with(&rhs),
+            }
+        }
+        CmpOp::Eq => lhs == rhs,
+        CmpOp::Ne => lhs != rhs,
+        CmpOp::Gt | CmpOp::Ge | CmpOp::Lt | CmpOp::Le => {
+            let ordering = match (lhs, rhs) {
+                (Value::Num(a), Value::Num(b)) => a.partial_cmp(b),
+                (Value::Str(a), Value::Str(b)) => Some(a.cmp(b)),
+                _ => None,
+            };
+            matches!(
+                (ordering, op),
+                (Some(Ordering::Greater), CmpOp::Gt | CmpOp::Ge)
+                    | (Some(Ordering::Less), CmpOp::Lt | CmpOp::Le)
+                    | (Some(Ordering::Equal), CmpOp::Ge | CmpOp::Le)
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::super::Expr;
+    use super::*;
+
+    struct Fields(HashMap<&'static str, Value<'static>>);
+
+    impl FieldResolver for Fields {
+        fn resolve(&self, name: &str) -> Option<Value<'_>> {
+            self.0.get(name).cloned()
+        }
+    }
+
+    fn fields() -> Fields {
+        Fields(HashMap::from([
+            ("conn.host", Value::Str("www.YouTube.com".into())),
+            ("conn.download_rate", Value::Num(20.0 * 1024.0 * 1024.0)),
+            ("conn.network", Value::Str("tcp".into())),
+            ("conn.alive", Value::Bool(true)),
+        ]))
+    }
+
+    fn eval_str(expr: &str) -> bool {
+        Expr::parse(expr).unwrap().eval(&fields())
+    }
+
+    #[test]
+    fn eval_expressions() {
+        assert!(eval_str(r#"conn.download_rate > 10MB && conn.host contains "youtube""#));
+        assert!(!eval_str(r#"conn.download_rate > 30MB && conn.host contains "youtube""#));
+        assert!(eval_str(r#"conn.network == "udp" || conn.host endswith ".com""#));
+        assert!(eval_str(r#"!(conn.network == "udp") and conn.alive"#));
+        assert!(eval_str("conn.download_rate >= 20MB && conn.download_rate <= 20MB"));
+        assert!(eval_str(r#"conn.alive == true && conn.host startswith "WWW""#));
+    }
+
+    #[test]
+    fn missing_fields_and_type_mismatch_are_false() {
+        assert!(!eval_str("conn.unknown > 1"));
+        assert!(!eval_str("conn.unknown"));
+        assert!(eval_str("!conn.unknown"));
+        assert!(!eval_str(r#"conn.download_rate contains "1""#));
+        assert!(!eval_str(r#"conn.host > 1"#));
+    }
+}
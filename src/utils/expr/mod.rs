@@ -0,0 +1,45 @@
+//! Tiny boolean expression language used by alert conditions and hook triggers.
+//!
+//! ```text
+//! conn.download_rate > 10MB && conn.host contains "youtube"
+//! !(conn.network == "udp") || conn.chains startswith "Proxy"
+//! ```
+//!
+//! Operators: `||`/`or`, `&&`/`and`, `!`/`not`, `==`, `!=`, `>`, `>=`, `<`, `<=`, `contains`,
+//! `startswith`, `endswith`. String matching with `contains`/`startswith`/`endswith` is
+//! case-insensitive. Numbers accept byte size units (`B`, `KB`, `MB`, ...), 1024-based.
+mod eval;
+mod parser;
+
+use std::borrow::Cow;
+
+pub use parser::ParseError;
+
+/// A runtime value of a field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+    Num(f64),
+    Str(Cow<'a, str>),
+    Bool(bool),
+}
+
+/// Exposes named fields (e.g. `conn.host`) to expressions.
+pub trait FieldResolver {
+    /// Returns `None` for unknown or absent fields, comparisons with them are always false.
+    fn resolve(&self, name: &str) -> Option<Value<'_>>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    ast: parser::Ast,
+}
+
+impl Expr {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self { ast: parser::parse(input)? })
+    }
+
+    pub fn eval(&self, fields: &impl FieldResolver) -> bool {
+        eval::eval(&self.ast, fields)
+    }
+}
@@ -0,0 +1,357 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::utils::byte_size::UNITS;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Num(f64),
+    Str(String),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Contains,
+    StartsWith,
+    EndsWith,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Field(String),
+    Literal(Literal),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ast {
+    Or(Box<Ast>, Box<Ast>),
+    And(Box<Ast>, Box<Ast>),
+    Not(Box<Ast>),
+    Cmp(Operand, CmpOp, Operand),
+    /// A bare operand, true if the value is truthy
+    Truthy(Operand),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedChar { pos: usize, ch: char },
+    UnterminatedQuote { pos: usize },
+    InvalidNumber { pos: usize, text: String },
+    UnexpectedToken { pos: usize, found: String },
+    UnexpectedEnd,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedChar { pos, ch } => write!(f, "unexpected char {ch:?} at {pos}"),
+            Self::UnterminatedQuote { pos } => write!(f, "unterminated quote at {pos}"),
+            Self::InvalidNumber { pos, text } => write!(f, "invalid number {text:?} at {pos}"),
+            Self::UnexpectedToken { pos, found } => write!(f, "unexpected {found:?} at {pos}"),
+            Self::UnexpectedEnd => write!(f, "unexpected end of expression"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Num(f64),
+    Str(String),
+    Op(CmpOp),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ident(s) => write!(f, "{s}"),
+            Self::Num(n) => write!(f, "{n}"),
+            Self::Str(s) => write!(f, "\"{s}\""),
+            Self::Op(op) => write!(f, "{op:?}"),
+            Self::And => write!(f, "&&"),
+            Self::Or => write!(f, "||"),
+            Self::Not => write!(f, "!"),
+            Self::LParen => write!(f, "("),
+            Self::RParen => write!(f, ")"),
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Ast, ParseError> {
+    let tokens = Lexer { chars: input.char_indices().peekable() }.tokenize()?;
+    let mut parser = Parser { tokens, idx: 0 };
+    let ast = parser.or()?;
+    match parser.tokens.get(parser.idx) {
+        Some((pos, token)) => {
+            Err(ParseError::UnexpectedToken { pos: *pos, found: token.to_string() })
+        }
+        None => Ok(ast),
+    }
+}
+
+struct Lexer<'a> {
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl Lexer<'_> {
+    fn tokenize(mut self) -> Result<Vec<(usize, Token)>, ParseError> {
+        let mut tokens = vec![];
+        while let Some(&(pos, ch)) = self.chars.peek() {
+            let token = match ch {
+                ch if ch.is_whitespace() => {
+                    self.chars.next();
+                    continue;
+                }
+                '(' => self.single(Token::LParen),
+                ')' => self.single(Token::RParen),
+                '"' => self.string(pos)?,
+                '&' => self.double('&', Token::And)?,
+                '|' => self.double('|', Token::Or)?,
+                '=' => self.double('=', Token::Op(CmpOp::Eq))?,
+                '!' => self.with_eq(Token::Not, Token::Op(CmpOp::Ne)),
+                '>' => self.with_eq(Token::Op(CmpOp::Gt), Token::Op(CmpOp::Ge)),
+                '<' => self.with_eq(Token::Op(CmpOp::Lt), Token::Op(CmpOp::Le)),
+                ch if ch.is_ascii_digit() => self.number(pos)?,
+                ch if ch.is_alphabetic() || ch == '_' => self.ident(),
+                ch => return Err(ParseError::UnexpectedChar { pos, ch }),
+            };
+            tokens.push((pos, token));
+        }
+        Ok(tokens)
+    }
+
+    fn single(&mut self, token: Token) -> Token {
+        self.chars.next();
+        token
+    }
+
+    fn double(&mut self, expected: char, token: Token) -> Result<Token, ParseError> {
+        self.chars.next();
+        match self.chars.next() {
+            Some((_, ch)) if ch == expected => Ok(token),
+            Some((pos, ch)) => Err(ParseError::UnexpectedChar { pos, ch }),
+            None => Err(ParseError::UnexpectedEnd),
+        }
+    }
+
+    fn with_eq(&mut self, plain: Token, with_eq: Token) -> Token {
+        self.chars.next();
+        if self.chars.next_if(|&(_, ch)| ch == '=').is_some() { with_eq } else { plain }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> String {
+        let mut text = String::new();
+        while let Some((_, ch)) = self.chars.next_if(|&(_, ch)| f(ch)) {
+            text.push(ch);
+        }
+        text
+    }
+
+    fn string(&mut self, quote_pos: usize) -> Result<Token, ParseError> {
+        self.chars.next();
+        let mut text = String::new();
+        while let Some((_, ch)) = self.chars.next() {
+            match ch {
+                '"' => return Ok(Token::Str(text)),
+                '\\' => match self.chars.next() {
+                    Some((_, ch)) => text.push(ch),
+                    None => break,
+                },
+                ch => text.push(ch),
+            }
+        }
+        Err(ParseError::UnterminatedQuote { pos: quote_pos })
+    }
+
+    /// Numbers accept an optional byte size unit, e.g. `10MB` (1024-based, same as the UI).
+    fn number(&mut self, pos: usize) -> Result<Token, ParseError> {
+        let digits = self.take_while(|ch| ch.is_ascii_digit() || ch == '.');
+        let unit = self.take_while(|ch| ch.is_ascii_alphabetic());
+        let invalid = || ParseError::InvalidNumber { pos, text: format!("{digits}{unit}") };
+
+        let value: f64 = digits.parse().map_err(|_| invalid())?;
+        if unit.is_empty() {
+            return Ok(Token::Num(value));
+        }
+        let exp = UNITS.iter().position(|u| u.eq_ignore_ascii_case(&unit)).ok_or_else(invalid)?;
+        Ok(Token::Num(value * 1024f64.powi(exp as i32)))
+    }
+
+    fn ident(&mut self) -> Token {
+        let text = self.take_while(|ch| ch.is_alphanumeric() || ch == '_' || ch == '.');
+        match text.as_str() {
+            "and" => Token::And,
+            "or" => Token::Or,
+            "not" => Token::Not,
+            "contains" => Token::Op(CmpOp::Contains),
+            "startswith" => Token::Op(CmpOp::StartsWith),
+            "endswith" => Token::Op(CmpOp::EndsWith),
+            _ => Token::Ident(text),
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    idx: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.idx).map(|(_, t)| t)
+    }
+
+    fn next(&mut self) -> Result<(usize, Token), ParseError> {
+        let token = self.tokens.get(self.idx).cloned().ok_or(ParseError::UnexpectedEnd)?;
+        self.idx += 1;
+        Ok(token)
+    }
+
+    fn or(&mut self) -> Result<Ast, ParseError> {
+        let mut lhs = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.idx += 1;
+            lhs = Ast::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Ast, ParseError> {
+        let mut lhs = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.idx += 1;
+            lhs = Ast::And(Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Ast, ParseError> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.idx += 1;
+                Ok(Ast::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::LParen) => {
+                self.idx += 1;
+                let ast = self.or()?;
+                match self.next()? {
+                    (_, Token::RParen) => Ok(ast),
+                    (pos, token) => {
+                        Err(ParseError::UnexpectedToken { pos, found: token.to_string() })
+                    }
+                }
+            }
+            _ => self.comparison(),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Ast, ParseError> {
+        let lhs = self.operand()?;
+        let Some(&Token::Op(op)) = self.peek() else {
+            return Ok(Ast::Truthy(lhs));
+        };
+        self.idx += 1;
+        Ok(Ast::Cmp(lhs, op, self.operand()?))
+    }
+
+    fn operand(&mut self) -> Result<Operand, ParseError> {
+        let operand = match self.next()? {
+            (_, Token::Num(n)) => Operand::Literal(Literal::Num(n)),
+            (_, Token::Str(s)) => Operand::Literal(Literal::Str(s)),
+            (_, Token::Ident(s)) if s == "true" => Operand::Literal(Literal::Bool(true)),
+            (_, Token::Ident(s)) if s == "false" => Operand::Literal(Literal::Bool(false)),
+            (_, Token::Ident(s)) => Operand::Field(s),
+            (pos, token) => {
+                return Err(ParseError::UnexpectedToken { pos, found: token.to_string() });
+            }
+        };
+        Ok(operand)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str) -> Operand {
+        Operand::Field(name.into())
+    }
+
+    fn num(n: f64) -> Operand {
+        Operand::Literal(Literal::Num(n))
+    }
+
+    #[test]
+    fn parse_precedence_and_units() {
+        let ast = parse(r#"conn.download_rate > 10MB && conn.host contains "youtube""#).unwrap();
+        assert_eq!(
+            ast,
+            Ast::And(
+                Box::new(Ast::Cmp(
+                    field("conn.download_rate"),
+                    CmpOp::Gt,
+                    num(10.0 * 1024.0 * 1024.0)
+                )),
+                Box::new(Ast::Cmp(
+                    field("conn.host"),
+                    CmpOp::Contains,
+                    Operand::Literal(Literal::Str("youtube".into()))
+                )),
+            )
+        );
+
+        // `&&` binds tighter than `||`
+        let ast = parse("a || b && !c").unwrap();
+        assert_eq!(
+            ast,
+            Ast::Or(
+                Box::new(Ast::Truthy(field("a"))),
+                Box::new(Ast::And(
+                    Box::new(Ast::Truthy(field("b"))),
+                    Box::new(Ast::Not(Box::new(Ast::Truthy(field("c"))))),
+                )),
+            )
+        );
+
+        let ast = parse("(a or b) and x != 1.5kb").unwrap();
+        assert_eq!(
+            ast,
+            Ast::And(
+                Box::new(Ast::Or(
+                    Box::new(Ast::Truthy(field("a"))),
+                    Box::new(Ast::Truthy(field("b")))
+                )),
+                Box::new(Ast::Cmp(field("x"), CmpOp::Ne, num(1536.0))),
+            )
+        );
+    }
+
+    #[test]
+    fn parse_errors_have_position() {
+        assert_eq!(parse(r#"a == "b"#), Err(ParseError::UnterminatedQuote { pos: 5 }));
+        assert_eq!(parse("a & b"), Err(ParseError::UnexpectedChar { pos: 3, ch: ' ' }));
+        assert_eq!(
+            parse("a > 10XB"),
+            Err(ParseError::InvalidNumber { pos: 4, text: "10XB".into() })
+        );
+        assert_eq!(parse("a >"), Err(ParseError::UnexpectedEnd));
+        assert_eq!(parse("(a"), Err(ParseError::UnexpectedEnd));
+        assert_eq!(parse("a b"), Err(ParseError::UnexpectedToken { pos: 2, found: "b".into() }));
+        assert_eq!(parse("a ~ b"), Err(ParseError::UnexpectedChar { pos: 2, ch: '~' }));
+    }
+}
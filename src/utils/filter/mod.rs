@@ -2,5 +2,5 @@ pub mod parser;
 mod pattern;
 mod row;
 
-pub use pattern::{FilterExpr, FilterPattern};
+pub use pattern::{AppliedFilter, FilterExpr, FilterPattern, TermMatcher};
 pub use row::RowFilter;
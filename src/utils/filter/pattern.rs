@@ -1,4 +1,4 @@
-use nucleo_matcher::pattern::{Atom as NucleoAtom, CaseMatching, Normalization};
+use nucleo_matcher::pattern::{Atom as NucleoAtom, AtomKind, CaseMatching, Normalization};
 use tracing::debug;
 
 use super::parser;
@@ -27,12 +27,68 @@ impl FilterPattern {
     pub fn expr(&self) -> &FilterExpr {
         &self.expr
     }
+
+    /// Whether every row matching `self` also matches `previous`, e.g. `goo` -> `goog` or
+    /// `host:goo` -> `host:goo rule:direct`, so the rows filtered with `previous` can be filtered
+    /// again instead of all rows. Conservative: negated terms never narrow.
+    pub fn narrows(&self, previous: &FilterPattern) -> bool {
+        let (prev_terms, prev_fields) = previous.expr.terms();
+        let (terms, fields) = self.expr.terms();
+        // unfielded terms skip the columns named by fields, fewer columns only narrow them
+        prev_fields.iter().all(|prev| fields.iter().any(|f| f.eq_ignore_ascii_case(prev)))
+            && terms.len() >= prev_terms.len()
+            && terms.iter().all(|(_, matcher)| !matcher.negative())
+            && prev_terms.iter().zip(&terms).all(|((prev_field, prev), (field, matcher))| {
+                let same_field = match (prev_field, field) {
+                    (Some(prev), Some(field)) => prev.eq_ignore_ascii_case(field),
+                    (None, None) => true,
+                    _ => false,
+                };
+                same_field && matcher.narrows(prev)
+            })
+    }
+}
+
+/// The pattern a filtered view was last computed with, telling whether the next pattern can filter
+/// that view instead of all rows.
+#[derive(Debug, Default)]
+pub struct AppliedFilter(Option<Option<FilterPattern>>);
+
+impl AppliedFilter {
+    /// The view was rebuilt from rows it doesn't know about, e.g. the rows were replaced.
+    pub fn invalidate(&mut self) {
+        self.0 = None;
+    }
+
+    /// Rows matching `pattern` are appended to the view, which only keeps it narrowable if that is
+    /// the applied pattern.
+    pub fn extend(&mut self, pattern: Option<&FilterPattern>) {
+        let raw = pattern.map(FilterPattern::raw);
+        if self.0.as_ref().is_some_and(|applied| applied.as_ref().map(FilterPattern::raw) != raw) {
+            self.0 = None;
+        }
+    }
+
+    /// Records `pattern` as applied, returning whether the view filtered with the previous one
+    /// can be filtered with it instead of all rows.
+    pub fn apply(&mut self, pattern: Option<&FilterPattern>) -> bool {
+        let narrows = matches!(
+            (&self.0, pattern),
+            (Some(Some(previous)), Some(pattern)) if pattern.narrows(previous)
+        );
+        self.0 = Some(pattern.cloned());
+        narrows
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum FilterExpr {
-    Legacy(NucleoAtom),
-    Field { terms: Vec<FilterTerm>, fields: Vec<String> },
+    /// Whole input as a single fuzzy atom, always [`TermMatcher::Fuzzy`]
+    Legacy(TermMatcher),
+    Field {
+        terms: Vec<FilterTerm>,
+        fields: Vec<String>,
+    },
 }
 
 impl FilterExpr {
@@ -48,7 +104,11 @@ impl FilterExpr {
 
         debug!(?raw_terms, "parsed filter expression terms");
         let fields = raw_terms.iter().filter_map(|term| term.field.clone()).collect::<Vec<_>>();
-        if fields.is_empty() && !raw_terms.iter().any(|term| term.quoted) {
+        if fields.is_empty()
+            && !raw_terms
+                .iter()
+                .any(|term| term.quoted || DomainSuffix::parse(&term.pattern).is_some())
+        {
             return Self::legacy(raw);
         }
 
@@ -57,20 +117,105 @@ impl FilterExpr {
     }
 
     fn legacy(raw: &str) -> Option<Self> {
-        parse_atom(raw).map(Self::Legacy)
+        parse_atom(raw).map(|atom| Self::Legacy(TermMatcher::Fuzzy(atom)))
+    }
+
+    /// (field, matcher) of each term and the fields named by the expression.
+    fn terms(&self) -> (Vec<(Option<&str>, &TermMatcher)>, &[String]) {
+        match self {
+            FilterExpr::Legacy(matcher) => (vec![(None, matcher)], &[]),
+            FilterExpr::Field { terms, fields } => {
+                (terms.iter().map(|term| (term.field.as_deref(), &term.matcher)).collect(), fields)
+            }
+        }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct FilterTerm {
     pub field: Option<String>,
-    pub atom: NucleoAtom,
+    pub matcher: TermMatcher,
 }
 
 impl FilterTerm {
     fn parse(term: parser::Term) -> Option<Self> {
-        let atom = parse_atom(&term.pattern)?;
-        Some(FilterTerm { field: term.field, atom })
+        let matcher = match DomainSuffix::parse(&term.pattern) {
+            Some(domain) => TermMatcher::Domain(domain),
+            None => TermMatcher::Fuzzy(parse_atom(&term.pattern)?),
+        };
+        Some(FilterTerm { field: term.field, matcher })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum TermMatcher {
+    Fuzzy(NucleoAtom),
+    Domain(DomainSuffix),
+}
+
+impl TermMatcher {
+    pub fn negative(&self) -> bool {
+        match self {
+            TermMatcher::Fuzzy(atom) => atom.negative,
+            TermMatcher::Domain(domain) => domain.negative,
+        }
+    }
+
+    /// Whether a text matching `self` always matches `previous`, both being positive.
+    fn narrows(&self, previous: &TermMatcher) -> bool {
+        match (previous, self) {
+            (TermMatcher::Fuzzy(previous), TermMatcher::Fuzzy(atom)) => {
+                let extends =
+                    atom.needle_text().to_string().starts_with(&previous.needle_text().to_string());
+                // any match contains the needle in order, which is what a fuzzy match needs
+                extends
+                    && match previous.kind {
+                        AtomKind::Fuzzy => true,
+                        AtomKind::Substring => atom.kind == AtomKind::Substring,
+                        AtomKind::Prefix => matches!(atom.kind, AtomKind::Prefix | AtomKind::Exact),
+                        _ => false,
+                    }
+            }
+            (TermMatcher::Domain(previous), TermMatcher::Domain(domain)) => previous == domain,
+            _ => false,
+        }
+    }
+}
+
+/// Host wildcard `*.example.com`, matches `example.com` and all of its subdomains exactly
+/// instead of fuzzily. A `!` prefix negates it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainSuffix {
+    /// Lowercase domain without the leading `*.`
+    pub domain: String,
+    pub negative: bool,
+}
+
+impl DomainSuffix {
+    fn parse(pattern: &str) -> Option<Self> {
+        let (negative, pattern) = match pattern.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        let domain = pattern.strip_prefix("*.")?;
+        if domain.is_empty() || domain.contains(|c: char| c.is_whitespace() || c == '*') {
+            return None;
+        }
+        Some(Self { domain: domain.to_ascii_lowercase(), negative })
+    }
+
+    /// Whether `host` (optionally with `:port`) is the domain or one of its subdomains.
+    pub fn matches(&self, host: &str) -> bool {
+        let host = match host.rsplit_once(':') {
+            Some((name, port)) if port.bytes().all(|b| b.is_ascii_digit()) => name,
+            _ => host,
+        };
+        let host = host.trim_end_matches('.');
+        let Some(prefix) = host.len().checked_sub(self.domain.len()) else {
+            return false;
+        };
+        host.get(prefix..).is_some_and(|tail| tail.eq_ignore_ascii_case(&self.domain))
+            && (prefix == 0 || host.as_bytes()[prefix - 1] == b'.')
     }
 }
 
@@ -101,6 +246,8 @@ mod tests {
             ("Host:", Some("Host:"), ExpectedExpr::Field),
             (r#"Host:"google"#, Some(r#"Host:"google"#), ExpectedExpr::Legacy),
             ("   ", None, ExpectedExpr::None),
+            ("*.googleapis.com", Some("*.googleapis.com"), ExpectedExpr::Field),
+            ("*.", Some("*."), ExpectedExpr::Legacy),
         ];
 
         for (raw, expected_raw, expected_expr) in cases {
@@ -141,9 +288,82 @@ mod tests {
             for (term, (expected_needle, expected_negative)) in
                 terms.iter().zip(expected_terms.iter())
             {
-                assert_eq!(term.atom.needle_text().to_string(), *expected_needle, "input: {raw:?}");
-                assert_eq!(term.atom.negative, *expected_negative, "input: {raw:?}");
+                let TermMatcher::Fuzzy(atom) = &term.matcher else {
+                    panic!("quoted pattern should be fuzzy: {raw:?}");
+                };
+                assert_eq!(atom.needle_text().to_string(), *expected_needle, "input: {raw:?}");
+                assert_eq!(atom.negative, *expected_negative, "input: {raw:?}");
             }
         }
     }
+
+    #[test]
+    fn narrowing_patterns() {
+        let cases = [
+            ("goo", "goog", true),
+            ("goo", "goo", true),
+            ("goo", "go", false),
+            ("goo", "gxoo", false),
+            ("goo", "^goo", true),
+            ("goo", "'goog", true),
+            ("'goo", "'goog", true),
+            ("^goo", "^goog$", true),
+            ("goo$", "goo$x", false),
+            ("goo", "!goo", false),
+            ("goo", "goo host:a", true),
+            ("host:goo", "host:goog", true),
+            ("host:goo", "host:goo rule:direct", true),
+            ("host:goo", "rule:goo", false),
+            ("Host:", "host:g", true),
+            ("host:goo", "host:goo !direct", false),
+            ("*.google.com", "*.google.com tcp", true),
+            ("*.google.co", "*.google.com", false),
+        ];
+
+        for (previous, pattern, expected) in cases {
+            let previous = FilterPattern::new(previous.to_owned()).unwrap();
+            let pattern = FilterPattern::new(pattern.to_owned()).unwrap();
+            assert_eq!(
+                pattern.narrows(&previous),
+                expected,
+                "{:?} -> {:?}",
+                previous.raw(),
+                pattern.raw()
+            );
+        }
+    }
+
+    #[test]
+    fn applied_filter_narrows_only_from_a_known_view() {
+        let goo = FilterPattern::new("goo".into());
+        let goog = FilterPattern::new("goog".into());
+        let mut applied = AppliedFilter::default();
+        assert!(!applied.apply(goo.as_ref()));
+        assert!(applied.apply(goog.as_ref()));
+        assert!(!applied.apply(None));
+        assert!(!applied.apply(goo.as_ref()));
+
+        applied.extend(goo.as_ref());
+        assert!(applied.apply(goog.as_ref()));
+        applied.extend(goo.as_ref());
+        assert!(!applied.apply(goog.as_ref()));
+        applied.invalidate();
+        assert!(!applied.apply(goog.as_ref()));
+    }
+
+    #[test]
+    fn domain_suffix_matches_domain_and_subdomains() {
+        let domain = DomainSuffix::parse("*.GoogleAPIs.com").unwrap();
+        assert_eq!(domain.domain, "googleapis.com");
+        assert!(domain.matches("googleapis.com"));
+        assert!(domain.matches("www.googleapis.com:443"));
+        assert!(domain.matches("a.b.GOOGLEAPIS.com."));
+        assert!(!domain.matches("notgoogleapis.com:443"));
+        assert!(!domain.matches("googleapis.com.evil.io"));
+        assert!(!domain.matches("com"));
+
+        assert!(DomainSuffix::parse("!*.example.com").unwrap().negative);
+        assert_eq!(DomainSuffix::parse("*.a*.com"), None);
+        assert_eq!(DomainSuffix::parse("example.com"), None);
+    }
 }
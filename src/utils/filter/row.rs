@@ -1,10 +1,9 @@
 use std::borrow::Cow;
 use std::sync::Arc;
 
-use nucleo_matcher::pattern::Atom as NucleoAtom;
 use nucleo_matcher::{Matcher, Utf32Str};
 
-use super::FilterExpr;
+use super::{FilterExpr, TermMatcher};
 use crate::utils::columns::{ColDef, TextResolver};
 
 /// An iterator that filters items based on a fuzzy pattern and column definitions
@@ -19,8 +18,11 @@ where
     text_resolver: Option<&'a dyn TextResolver<T>>,
 }
 
+/// Unfielded host wildcards (`*.example.com`) only look at this column if the table has it.
+const HOST_COLUMN: &str = "Host";
+
 struct RowFilterTerm<'a, T> {
-    atom: &'a NucleoAtom,
+    matcher: &'a TermMatcher,
     cols: Vec<&'a ColDef<T>>,
     require_cols: bool,
 }
@@ -52,29 +54,40 @@ where
 
     fn compile_terms(filter: &'a FilterExpr, cols: &[&'a ColDef<T>]) -> Vec<RowFilterTerm<'a, T>> {
         match filter {
-            FilterExpr::Legacy(atom) => vec![RowFilterTerm {
-                atom,
+            FilterExpr::Legacy(matcher) => vec![RowFilterTerm {
+                matcher,
                 cols: cols.iter().copied().filter(|col| col.filterable).collect(),
                 require_cols: false,
             }],
             FilterExpr::Field { terms, fields } => terms
                 .iter()
-                .map(|term| RowFilterTerm {
-                    atom: &term.atom,
-                    cols: cols
-                        .iter()
-                        .copied()
-                        .filter(|col| match term.field.as_deref() {
-                            Some(field) => col.title.eq_ignore_ascii_case(field),
-                            None => {
-                                col.filterable
-                                    && !fields
-                                        .iter()
-                                        .any(|field| col.title.eq_ignore_ascii_case(field))
-                            }
-                        })
-                        .collect(),
-                    require_cols: true,
+                .map(|term| {
+                    let field = match (&term.field, &term.matcher) {
+                        (Some(field), _) => Some(field.as_str()),
+                        (None, TermMatcher::Domain(_))
+                            if cols.iter().any(|col| col.title == HOST_COLUMN) =>
+                        {
+                            Some(HOST_COLUMN)
+                        }
+                        _ => None,
+                    };
+                    RowFilterTerm {
+                        matcher: &term.matcher,
+                        cols: cols
+                            .iter()
+                            .copied()
+                            .filter(|col| match field {
+                                Some(field) => col.title.eq_ignore_ascii_case(field),
+                                None => {
+                                    col.filterable
+                                        && !fields
+                                            .iter()
+                                            .any(|field| col.title.eq_ignore_ascii_case(field))
+                                }
+                            })
+                            .collect(),
+                        require_cols: true,
+                    }
                 })
                 .collect(),
         }
@@ -89,16 +102,22 @@ where
             return false;
         }
 
-        if term.atom.negative {
-            term.cols.iter().all(|col| self.matches_col(term.atom, col, item))
+        if term.matcher.negative() {
+            term.cols.iter().all(|col| self.matches_col(term.matcher, col, item))
         } else {
-            term.cols.iter().any(|col| self.matches_col(term.atom, col, item))
+            term.cols.iter().any(|col| self.matches_col(term.matcher, col, item))
         }
     }
 
-    fn matches_col(&mut self, atom: &NucleoAtom, col: &ColDef<T>, item: &T) -> bool {
+    fn matches_col(&mut self, matcher: &TermMatcher, col: &ColDef<T>, item: &T) -> bool {
         let text = Self::text(self.text_resolver, col, item);
-        atom.score(Utf32Str::new(&text, &mut self.haystack_buffer), self.matcher).is_some()
+        match matcher {
+            TermMatcher::Fuzzy(atom) => {
+                atom.score(Utf32Str::new(&text, &mut self.haystack_buffer), self.matcher).is_some()
+            }
+            // negative terms must match on all columns, so the result is inverted like nucleo does
+            TermMatcher::Domain(domain) => domain.matches(&text) != domain.negative,
+        }
     }
 
     fn text<'row>(
@@ -175,24 +194,24 @@ mod tests {
     fn row_cols() -> [ColDef<Row>; 3] {
         [
             ColDef {
-                id: "host_id",
-                title: "Host",
+                id: Cow::Borrowed("host_id"),
+                title: Cow::Borrowed("Host"),
                 filterable: true,
                 sortable: false,
                 accessor: |row| Cow::Borrowed(row.host),
                 sort_key: None,
             },
             ColDef {
-                id: "rule_id",
-                title: "Rule",
+                id: Cow::Borrowed("rule_id"),
+                title: Cow::Borrowed("Rule"),
                 filterable: true,
                 sortable: false,
                 accessor: |row| Cow::Borrowed(row.rule),
                 sort_key: None,
             },
             ColDef {
-                id: "hidden_id",
-                title: "Hidden",
+                id: Cow::Borrowed("hidden_id"),
+                title: Cow::Borrowed("Hidden"),
                 filterable: false,
                 sortable: false,
                 accessor: |row| Cow::Borrowed(row.hidden),
@@ -217,6 +236,11 @@ mod tests {
             ("secret", vec![]),
             ("Hidden:secret", vec!["1", "2"]),
             ("Unknown:google", vec![]),
+            ("*.google.com", vec!["1"]),
+            ("!*.google.com", vec!["2"]),
+            ("*.com", vec!["1", "2"]),
+            ("Rule:*.com", vec![]),
+            ("*.gle.com", vec![]),
         ];
 
         for (pattern, expected) in cases {
@@ -1,5 +1,7 @@
 mod formatter;
+mod outline;
 mod schema;
 
 pub use formatter::Json5Formatter;
+pub use outline::{Section, outline};
 pub use schema::{collect_paths, extract_comments};
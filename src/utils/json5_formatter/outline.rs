@@ -0,0 +1,53 @@
+/// A top-level key of the formatted config and the line it starts on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub key: String,
+    /// First line of the section, its schema comment if any
+    pub line: usize,
+}
+
+/// Top-level keys of a config formatted by [`Json5Formatter`](super::Json5Formatter) with a
+/// two-space indent, in the order they appear.
+pub fn outline(content: &str) -> Vec<Section> {
+    let mut sections = Vec::new();
+    let mut comment_start = None;
+    for (idx, line) in content.lines().enumerate() {
+        if line.starts_with("//") {
+            comment_start.get_or_insert(idx);
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("  \"")
+            && let Some((key, _)) = rest.split_once('"')
+        {
+            sections.push(Section { key: key.to_owned(), line: comment_start.unwrap_or(idx) });
+        }
+        comment_start = None;
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outline_lists_top_level_keys_with_comments() {
+        let content = r#"{
+// TUN 配置
+  "tun": {
+// 是否启用
+    "enable": true
+  },
+  "log-level": "info",
+  "rules": [
+    "MATCH,DIRECT"
+  ]
+}"#;
+        let section = |key: &str, line| Section { key: key.into(), line };
+        assert_eq!(
+            outline(content),
+            vec![section("tun", 1), section("log-level", 6), section("rules", 7)]
+        );
+        assert!(outline("").is_empty());
+    }
+}
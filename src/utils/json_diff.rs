@@ -0,0 +1,85 @@
+use serde_json::{Map, Value};
+
+/// A changed leaf of a config, `path` is dot separated, e.g. `dns.enable`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub path: String,
+    pub old: Option<Value>,
+    pub new: Value,
+}
+
+/// Compute a partial update turning `running` into `target`.
+///
+/// Objects are compared key by key, any other value (including arrays) is replaced as a whole.
+/// Keys missing in `target` are left untouched, matching the partial update semantics of the
+/// core config API. Returns `None` if nothing changed.
+pub fn diff(running: &Value, target: &Value, changes: &mut Vec<Change>) -> Option<Value> {
+    diff_at(String::new(), Some(running), target, changes)
+}
+
+fn diff_at(
+    path: String,
+    running: Option<&Value>,
+    target: &Value,
+    changes: &mut Vec<Change>,
+) -> Option<Value> {
+    match (running, target) {
+        (Some(Value::Object(old)), Value::Object(new)) => {
+            let patch = new
+                .iter()
+                .filter_map(|(key, value)| {
+                    let path = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+                    diff_at(path, old.get(key), value, changes).map(|v| (key.clone(), v))
+                })
+                .collect::<Map<_, _>>();
+            (!patch.is_empty()).then_some(Value::Object(patch))
+        }
+        (Some(old), new) if old == new => None,
+        (old, new) => {
+            changes.push(Change { path, old: old.cloned(), new: new.clone() });
+            Some(new.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn diff_keeps_only_changed_fields() {
+        let running = json!({
+            "mode": "rule",
+            "port": 7890,
+            "dns": { "enable": true, "ipv6": false },
+            "tun": { "enable": false, "dns-hijack": ["any:53"] },
+        });
+        let target = json!({
+            "mode": "rule",
+            "dns": { "enable": true, "ipv6": true },
+            "tun": { "dns-hijack": ["any:53", "tcp://any:53"] },
+            "allow-lan": true,
+        });
+
+        let mut changes = vec![];
+        let patch = diff(&running, &target, &mut changes).unwrap();
+        assert_eq!(
+            patch,
+            json!({
+                "dns": { "ipv6": true },
+                "tun": { "dns-hijack": ["any:53", "tcp://any:53"] },
+                "allow-lan": true,
+            })
+        );
+        let paths = changes.iter().map(|c| c.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, ["dns.ipv6", "tun.dns-hijack", "allow-lan"]);
+        assert_eq!(changes[0].old, Some(json!(false)));
+        assert_eq!(changes[2].old, None);
+
+        let mut changes = vec![];
+        assert_eq!(diff(&running, &running, &mut changes), None);
+        assert!(changes.is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+
+use serde_json::Value;
+
+/// A single schema violation, `path` is a JSON pointer to the offending value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() { "/" } else { &self.path };
+        write!(f, "{path}: {}", self.message)
+    }
+}
+
+/// Validate `value` against the subset of JSON schema used by the core config schema:
+/// `type`, `enum`, `minimum`, `maximum`, `properties` and `items`.
+///
+/// Unknown keywords are ignored, properties not described by the schema are accepted.
+pub fn validate(schema: &Value, value: &Value) -> Vec<SchemaViolation> {
+    let mut violations = vec![];
+    validate_at(schema, value, &mut String::new(), &mut violations);
+    violations
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &mut String, out: &mut Vec<SchemaViolation>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    let mut violation = |message: String| {
+        out.push(SchemaViolation { path: path.clone(), message });
+    };
+
+    if let Some(expected) = schema.get("type")
+        && !type_matches(expected, value)
+    {
+        violation(format!("expected type {}, got {}", type_names(expected), type_of(value)));
+        // nested keywords are meaningless for a mismatched type
+        return;
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        let allowed = allowed.iter().map(Value::to_string).collect::<Vec<_>>().join(", ");
+        violation(format!("expected one of [{allowed}], got {value}"));
+    }
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(Value::as_f64)
+            && n < min
+        {
+            violation(format!("{value} is less than minimum {min}"));
+        }
+        if let Some(max) = schema.get("maximum").and_then(Value::as_f64)
+            && n > max
+        {
+            violation(format!("{value} is greater than maximum {max}"));
+        }
+    }
+
+    let len = path.len();
+    if let (Some(properties), Some(object)) =
+        (schema.get("properties").and_then(Value::as_object), value.as_object())
+    {
+        for (key, sub_value) in object {
+            if let Some(sub_schema) = properties.get(key) {
+                path.push('/');
+                path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                validate_at(sub_schema, sub_value, path, out);
+                path.truncate(len);
+            }
+        }
+    }
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (idx, item) in array.iter().enumerate() {
+            path.push('/');
+            path.push_str(&idx.to_string());
+            validate_at(items, item, path, out);
+            path.truncate(len);
+        }
+    }
+}
+
+fn type_matches(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => is_type(name, value),
+        Value::Array(names) => names.iter().filter_map(Value::as_str).any(|n| is_type(n, value)),
+        _ => true,
+    }
+}
+
+fn is_type(name: &str, value: &Value) -> bool {
+    match name {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        "number" => value.is_number(),
+        name => type_of(value) == name,
+    }
+}
+
+fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn type_names(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => {
+            names.iter().filter_map(Value::as_str).collect::<Vec<_>>().join(" | ")
+        }
+        other => other.as_str().unwrap_or_default().to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "mode": { "type": "string", "enum": ["global", "rule", "direct"] },
+                "port": { "type": "integer", "minimum": 0, "maximum": 65535 },
+                "skip-auth-prefixes": { "type": "array", "items": { "type": "string" } },
+                "tun": {
+                    "type": "object",
+                    "properties": { "enable": { "type": "boolean" } }
+                },
+                "bind": { "type": ["string", "null"] }
+            }
+        })
+    }
+
+    #[test]
+    fn valid_partial_config_passes() {
+        let value =
+            json!({ "mode": "rule", "tun": { "enable": true, "stack": "mixed" }, "bind": null });
+        assert!(validate(&schema(), &value).is_empty());
+    }
+
+    #[test]
+    fn violations_are_reported_with_path() {
+        let value = json!({
+            "mode": "auto",
+            "port": 70000,
+            "skip-auth-prefixes": ["127.0.0.1/8", 1],
+            "tun": { "enable": "yes" }
+        });
+        let violations: Vec<String> =
+            validate(&schema(), &value).iter().map(ToString::to_string).collect();
+
+        assert_eq!(
+            violations,
+            vec![
+                r#"/mode: expected one of ["global", "rule", "direct"], got "auto""#,
+                "/port: 70000 is greater than maximum 65535",
+                "/skip-auth-prefixes/1: expected type string, got number",
+                "/tun/enable: expected type boolean, got string",
+            ]
+        );
+    }
+}
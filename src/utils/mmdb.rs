@@ -0,0 +1,114 @@
+use std::net::IpAddr;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use maxminddb::{MaxMindDBError, Reader};
+use serde_json::Value;
+
+/// Country lookups in the GeoIP database used by the core (`Country.mmdb`), a MaxMind DB.
+#[derive(Debug)]
+pub struct Mmdb {
+    reader: Reader<Vec<u8>>,
+}
+
+impl Mmdb {
+    pub fn open(path: &Path) -> Result<Self> {
+        let buf = std::fs::read(path)
+            .with_context(|| format!("Fail to read file `{}`", path.display()))?;
+        Self::from_bytes(buf).with_context(|| format!("Invalid MaxMind DB `{}`", path.display()))
+    }
+
+    pub fn from_bytes(buf: Vec<u8>) -> Result<Self> {
+        Ok(Self { reader: Reader::from_source(buf)? })
+    }
+
+    /// ISO country code of `ip`, `None` if the database has no entry for it.
+    pub fn country(&self, ip: IpAddr) -> Result<Option<String>> {
+        let record = match self.reader.lookup::<Value>(ip) {
+            Ok(record) => record,
+            Err(MaxMindDBError::AddressNotFoundError(_)) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let code = match &record {
+            // databases converted from other formats hold the bare code
+            Value::String(code) => Some(code.as_str()),
+            _ => ["country", "registered_country"]
+                .into_iter()
+                .find_map(|key| record.get(key)?.get("iso_code")?.as_str()),
+        };
+        Ok(code.map(str::to_ascii_uppercase))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA_MARKER: &[u8] = b"\xAB\xCD\xEFMaxMind.com";
+    /// Zero bytes between the search tree and the data section
+    const DATA_SEPARATOR: usize = 16;
+
+    fn string(s: &str) -> Vec<u8> {
+        let mut out = vec![0x40 | s.len() as u8];
+        out.extend_from_slice(s.as_bytes());
+        out
+    }
+
+    fn uint16(v: u16) -> Vec<u8> {
+        let mut out = vec![0xA2];
+        out.extend_from_slice(&v.to_be_bytes());
+        out
+    }
+
+    fn map(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut out = vec![0xE0 | entries.len() as u8];
+        for (key, value) in entries {
+            out.extend(string(key));
+            out.extend_from_slice(value);
+        }
+        out
+    }
+
+    /// A single node IPv4 tree: `0.0.0.0/1` is `US`, `128.0.0.0/1` points into its data.
+    fn db() -> Mmdb {
+        let us = map(&[("country", map(&[("iso_code", string("us"))]))]);
+        // the inner `country` map starts after the map control byte and the `country` key
+        let inner = 1 + string("country").len();
+        let pointed = map(&[("registered_country", vec![0x20, inner as u8])]);
+        let record = |offset: usize| (1 + DATA_SEPARATOR + offset) as u32;
+
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&record(0).to_be_bytes()[1..]);
+        buf.extend_from_slice(&record(us.len()).to_be_bytes()[1..]);
+        buf.extend([0; DATA_SEPARATOR]);
+        buf.extend(us);
+        buf.extend(pointed);
+        buf.extend_from_slice(METADATA_MARKER);
+        buf.extend(map(&[
+            ("binary_format_major_version", uint16(2)),
+            ("binary_format_minor_version", uint16(0)),
+            // empty uint64
+            ("build_epoch", vec![0x00, 0x02]),
+            ("database_type", string("GeoLite2-Country")),
+            ("description", map(&[])),
+            ("ip_version", uint16(4)),
+            // empty array
+            ("languages", vec![0x00, 0x04]),
+            ("node_count", uint16(1)),
+            ("record_size", uint16(24)),
+        ]));
+        Mmdb::from_bytes(buf).unwrap()
+    }
+
+    #[test]
+    fn country_lookup_follows_tree_and_pointers() {
+        let db = db();
+        assert_eq!(db.country("8.8.8.8".parse().unwrap()).unwrap().as_deref(), Some("US"));
+        assert_eq!(db.country("200.1.1.1".parse().unwrap()).unwrap().as_deref(), Some("US"));
+    }
+
+    #[test]
+    fn rejects_files_without_metadata() {
+        assert!(Mmdb::from_bytes(vec![0; 64]).is_err());
+    }
+}
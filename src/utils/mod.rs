@@ -1,11 +1,27 @@
 pub mod axis;
+pub mod browser;
 pub mod byte_size;
+pub mod clipboard;
+pub mod collation;
 pub mod columns;
+pub mod csv;
 pub mod editor;
+pub mod encryption;
+pub mod expr;
 pub mod filter;
 pub mod input;
 pub mod json5_formatter;
+pub mod json_diff;
+pub mod json_schema;
+pub mod mmdb;
+pub mod number;
+pub mod path;
+pub mod redact;
+pub mod rule_match;
+pub mod rule_parser;
+pub mod safe_mode;
 pub mod symbols;
+pub mod tcp_ping;
 #[cfg(test)]
 pub mod test;
 pub mod text_ui;
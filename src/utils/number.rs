@@ -0,0 +1,103 @@
+use std::env;
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+
+/// Digit group separator applied to large numbers in tables.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThousandsSeparator {
+    /// Derived from `LC_ALL` / `LC_NUMERIC` / `LANG`
+    #[default]
+    Locale,
+    Comma,
+    Dot,
+    Space,
+    Apostrophe,
+    None,
+}
+
+static SEPARATOR: RwLock<Option<char>> = RwLock::new(Some(','));
+
+impl ThousandsSeparator {
+    fn resolve(self) -> Option<char> {
+        match self {
+            Self::Locale => locale_separator(&current_locale()),
+            Self::Comma => Some(','),
+            Self::Dot => Some('.'),
+            Self::Space => Some(' '),
+            Self::Apostrophe => Some('\''),
+            Self::None => None,
+        }
+    }
+
+    /// Set the separator used by [`format_thousands`].
+    pub fn apply(self) {
+        *SEPARATOR.write().unwrap() = self.resolve();
+    }
+}
+
+fn current_locale() -> String {
+    ["LC_ALL", "LC_NUMERIC", "LANG"]
+        .into_iter()
+        .filter_map(|key| env::var(key).ok())
+        .find(|v| !v.is_empty())
+        .unwrap_or_default()
+}
+
+/// Separator of common locales, e.g. `de_DE.UTF-8`; unknown locales fall back to comma.
+fn locale_separator(locale: &str) -> Option<char> {
+    let tag = locale.split(['.', '@']).next().unwrap_or_default();
+    let lang = tag.split(['_', '-']).next().unwrap_or_default();
+    match (lang, tag) {
+        (_, "de_CH" | "it_CH" | "fr_CH") => Some('\''),
+        ("de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el", _) => Some('.'),
+        ("fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "uk" | "hu", _) => Some(' '),
+        _ => Some(','),
+    }
+}
+
+/// Format `n` with the configured thousands separator, e.g. `1234567` -> `1,234,567`.
+pub fn format_thousands(n: u64) -> String {
+    group_digits(n, *SEPARATOR.read().unwrap())
+}
+
+fn group_digits(n: u64, separator: Option<char>) -> String {
+    let digits = n.to_string();
+    let Some(sep) = separator else {
+        return digits;
+    };
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(sep);
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_digits_with_configured_separator() {
+        assert_eq!(group_digits(0, Some(',')), "0");
+        assert_eq!(group_digits(999, Some(',')), "999");
+        assert_eq!(group_digits(1_000, Some(',')), "1,000");
+        assert_eq!(group_digits(1_234_567, Some('.')), "1.234.567");
+        assert_eq!(group_digits(123_456, Some(' ')), "123 456");
+        assert_eq!(group_digits(1_234_567, None), "1234567");
+    }
+
+    #[test]
+    fn locale_separator_by_language() {
+        assert_eq!(locale_separator("en_US.UTF-8"), Some(','));
+        assert_eq!(locale_separator("de_DE.UTF-8"), Some('.'));
+        assert_eq!(locale_separator("de_CH.UTF-8"), Some('\''));
+        assert_eq!(locale_separator("fr_FR@euro"), Some(' '));
+        assert_eq!(locale_separator("C"), Some(','));
+        assert_eq!(locale_separator(""), Some(','));
+    }
+}
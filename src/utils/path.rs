@@ -0,0 +1,96 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Expand a leading `~` to the home directory.
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), directories::BaseDirs::new()) {
+        (Some(rest), Some(dirs)) => dirs.home_dir().join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// `{stem}.{ext}` in `dir`, or `{stem}-{n}.{ext}` with the first `n` not taken yet, so a file is
+/// never overwritten.
+pub fn unused_path(dir: &Path, stem: &str, ext: &str) -> PathBuf {
+    let mut path = dir.join(format!("{stem}.{ext}"));
+    let mut n = 1;
+    while path.exists() {
+        path = dir.join(format!("{stem}-{n}.{ext}"));
+        n += 1;
+    }
+    path
+}
+
+/// Complete the last component of a typed path to the longest common prefix of matching entries,
+/// a unique directory match gets a trailing `/`. Returns `None` if nothing can be added.
+///
+/// Hidden entries are only offered when the typed name starts with `.`.
+pub fn complete_path(input: &str) -> Option<String> {
+    let (dir, prefix) = match input.rfind('/') {
+        Some(idx) => input.split_at(idx + 1),
+        None => ("", input),
+    };
+    let lookup = if dir.is_empty() { PathBuf::from(".") } else { expand_home(dir) };
+
+    let mut matches = fs::read_dir(lookup)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let visible = !name.starts_with('.') || prefix.starts_with('.');
+            (visible && name.starts_with(prefix)).then(|| (name, entry.path().is_dir()))
+        })
+        .collect::<Vec<_>>();
+    matches.sort_unstable();
+
+    let completed = match matches.as_slice() {
+        [] => return None,
+        [(name, true)] => format!("{name}/"),
+        [(name, false)] => name.clone(),
+        [(first, _), .., (last, _)] => {
+            // sorted, so the common prefix of the first and last covers all of them
+            let len = first
+                .char_indices()
+                .zip(last.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(first.len().min(last.len()), |((idx, _), _)| idx);
+            first[..len].to_string()
+        }
+    };
+    (completed.len() > prefix.len()).then(|| format!("{dir}{completed}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unused_path_skips_existing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = unused_path(dir.path(), "dump", "csv");
+        assert_eq!(first, dir.path().join("dump.csv"));
+        fs::write(&first, "").unwrap();
+        let second = unused_path(dir.path(), "dump", "csv");
+        assert_eq!(second, dir.path().join("dump-1.csv"));
+        fs::write(&second, "").unwrap();
+        assert_eq!(unused_path(dir.path(), "dump", "csv"), dir.path().join("dump-2.csv"));
+    }
+
+    #[test]
+    fn complete_to_common_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("config-a.yaml"), "").unwrap();
+        fs::write(dir.path().join("config-b.yaml"), "").unwrap();
+        fs::create_dir(dir.path().join("profiles")).unwrap();
+        fs::write(dir.path().join(".hidden"), "").unwrap();
+        let base = format!("{}/", dir.path().display());
+
+        assert_eq!(complete_path(&format!("{base}con")), Some(format!("{base}config-")));
+        assert_eq!(complete_path(&format!("{base}config-")), None);
+        assert_eq!(complete_path(&format!("{base}config-a")), Some(format!("{base}config-a.yaml")));
+        assert_eq!(complete_path(&format!("{base}pro")), Some(format!("{base}profiles/")));
+        assert_eq!(complete_path(&format!("{base}.h")), Some(format!("{base}.hidden")));
+        assert_eq!(complete_path(&format!("{base}x")), None);
+        assert_eq!(complete_path(&format!("{base}missing/")), None);
+    }
+}
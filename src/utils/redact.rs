@@ -0,0 +1,299 @@
+//! Redaction of secrets, hosts and source IPs in data leaving the app: exports and the log file.
+
+use std::borrow::Cow;
+use std::hash::{BuildHasher, RandomState};
+use std::io;
+use std::net::Ipv4Addr;
+use std::sync::{LazyLock, RwLock};
+
+use serde_json::Value;
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::config::RedactConfig;
+
+const REDACTED: &str = "<redacted>";
+/// Normalized key names (lowercase, without `-`/`_`) always treated as secrets.
+const SECRET_KEYS: [&str; 8] = [
+    "uuid",
+    "psk",
+    "auth",
+    "authstr",
+    "authorization",
+    "authentication",
+    "privatekey",
+    "presharedkey",
+];
+/// Keys containing any of these are treated as secrets as well, e.g. `obfs-password`.
+const SECRET_KEY_PARTS: [&str; 4] = ["password", "passwd", "secret", "token"];
+/// Keys holding a host, masked by `mask-hosts`.
+const HOST_KEYS: [&str; 7] =
+    ["server", "servername", "sni", "host", "sniffhost", "destinationip", "remotedestination"];
+/// Keys holding a source IP, hashed by `hash-source-ips`.
+const SOURCE_IP_KEYS: [&str; 2] = ["sourceip", "srcip"];
+
+static CONFIG: LazyLock<RwLock<RedactConfig>> = LazyLock::new(Default::default);
+/// Random keys per process, hashes are stable within a session but can't be brute forced back.
+static HASHER: LazyLock<RandomState> = LazyLock::new(RandomState::new);
+
+/// Applies the configured [`RedactConfig`].
+pub struct Redactor;
+
+impl Redactor {
+    pub fn configure(config: &RedactConfig) {
+        *CONFIG.write().unwrap() = config.clone();
+    }
+
+    /// Redact a JSON document in place, e.g. the core config before it is exported.
+    pub fn json(value: &mut Value) {
+        redact_json(&CONFIG.read().unwrap(), value);
+    }
+
+    /// Redact free text, e.g. a log line.
+    pub fn text(text: &str) -> Cow<'_, str> {
+        redact_text(&CONFIG.read().unwrap(), text)
+    }
+}
+
+fn normalize_key(key: &str) -> String {
+    key.chars().filter(|c| *c != '-' && *c != '_').flat_map(char::to_lowercase).collect()
+}
+
+fn is_secret_key(config: &RedactConfig, key: &str) -> bool {
+    let key = normalize_key(key);
+    (config.strip_secrets
+        && (SECRET_KEYS.contains(&key.as_str())
+            || SECRET_KEY_PARTS.iter().any(|part| key.contains(part))))
+        || config.strip_keys.iter().any(|extra| normalize_key(extra) == key)
+}
+
+fn redact_json(config: &RedactConfig, value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret_key(config, key) {
+                    *value = Value::String(REDACTED.into());
+                    continue;
+                }
+                let normalized = normalize_key(key);
+                if let Value::String(s) = value {
+                    if config.mask_hosts && HOST_KEYS.contains(&normalized.as_str()) {
+                        *s = mask_host(s);
+                    } else if config.hash_source_ips
+                        && SOURCE_IP_KEYS.contains(&normalized.as_str())
+                    {
+                        *s = hash_ip(s);
+                    }
+                    continue;
+                }
+                redact_json(config, value);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redact_json(config, item)),
+        _ => {}
+    }
+}
+
+/// `www.example.com` -> `w**.e******.com`, `10.1.2.3` -> `10.1.*.*`.
+pub fn mask_host(host: &str) -> String {
+    if host.parse::<Ipv4Addr>().is_ok() {
+        let octets = host.split('.').collect::<Vec<_>>();
+        return format!("{}.{}.*.*", octets[0], octets[1]);
+    }
+    if host.contains(':') {
+        // IPv6, keep the first two groups
+        let groups = host.split(':').take(2).collect::<Vec<_>>();
+        return format!("{}:*", groups.join(":"));
+    }
+    let labels = host.split('.').collect::<Vec<_>>();
+    let last = labels.len() - 1;
+    labels
+        .iter()
+        .enumerate()
+        .map(|(idx, label)| {
+            let mut chars = label.chars();
+            match chars.next() {
+                Some(first) if idx < last => {
+                    format!("{first}{}", "*".repeat(chars.count()))
+                }
+                _ => label.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Stable (per session) pseudonym of an IP, e.g. `ip-5f3a9c1e`.
+pub fn hash_ip(ip: &str) -> String {
+    format!("ip-{:08x}", HASHER.hash_one(ip) as u32)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '.' | '-' | '_')
+}
+
+fn looks_like_host(word: &str) -> bool {
+    if word.parse::<Ipv4Addr>().is_ok() {
+        return true;
+    }
+    let labels = word.split('.').collect::<Vec<_>>();
+    labels.len() >= 2
+        && labels.iter().all(|label| !label.is_empty())
+        && labels.last().is_some_and(|tld| tld.len() >= 2 && tld.chars().all(char::is_alphabetic))
+}
+
+/// Redacts values assigned to secret keys (`secret=xxx`, `"token": "xxx"`, `Bearer xxx`) and
+/// masks host-like words.
+fn redact_text<'a>(config: &RedactConfig, text: &'a str) -> Cow<'a, str> {
+    let mut out = String::with_capacity(text.len());
+    let mut changed = false;
+    // after a secret key, `Some(true)` once the value is due (after `=`/`:` or `Bearer`)
+    let mut pending: Option<bool> = None;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let word_len = rest.find(|c| !is_word_char(c)).unwrap_or(rest.len());
+        if word_len == 0 {
+            let c = rest.chars().next().unwrap();
+            pending = match c {
+                '=' | ':' => pending.map(|_| true),
+                ' ' | '"' | '\'' | '(' => pending,
+                _ => None,
+            };
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let word = &rest[..word_len];
+        rest = &rest[word_len..];
+        let is_bearer = config.strip_secrets && word.eq_ignore_ascii_case("bearer");
+        // `Debug` output of an `Option`, e.g. `secret: Some("xxx")`
+        if pending == Some(true) && word == "Some" {
+            out.push_str(word);
+            continue;
+        }
+        if pending == Some(true) {
+            out.push_str(REDACTED);
+            changed = true;
+            pending = is_bearer.then_some(true);
+            continue;
+        }
+        pending = if is_bearer { Some(true) } else { is_secret_key(config, word).then_some(false) };
+
+        // a trailing dot ends the sentence, it is not part of the host
+        let host = word.trim_end_matches('.');
+        if config.mask_hosts && looks_like_host(host) {
+            out.push_str(&mask_host(host));
+            out.push_str(&word[host.len()..]);
+            changed = true;
+        } else {
+            out.push_str(word);
+        }
+    }
+    if changed { Cow::Owned(out) } else { Cow::Borrowed(text) }
+}
+
+/// Log writer redacting every formatted event, an event is written in a single call.
+pub struct RedactingMakeWriter<M>(pub M);
+
+pub struct RedactingWriter<W>(W);
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter(self.0.make_writer())
+    }
+}
+
+impl<W: io::Write> io::Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match std::str::from_utf8(buf) {
+            Ok(text) => self.0.write_all(Redactor::text(text).as_bytes())?,
+            Err(_) => self.0.write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn config(mask_hosts: bool, hash_source_ips: bool) -> RedactConfig {
+        RedactConfig {
+            strip_secrets: true,
+            mask_hosts,
+            hash_source_ips,
+            strip_keys: vec!["Username".into()],
+        }
+    }
+
+    #[test]
+    fn redact_json_document() {
+        let mut value = json!({
+            "secret": "s3cr3t",
+            "proxies": [{
+                "name": "hk",
+                "server": "hk.example.com",
+                "uuid": "0000",
+                "obfs-password": "x",
+                "username": "me",
+                "port": 443,
+            }],
+            "metadata": { "sourceIP": "192.168.1.2", "host": "www.example.com" },
+        });
+        redact_json(&config(true, true), &mut value);
+        assert_eq!(value["secret"], REDACTED);
+        assert_eq!(value["proxies"][0]["server"], "h*.e******.com");
+        assert_eq!(value["proxies"][0]["uuid"], REDACTED);
+        assert_eq!(value["proxies"][0]["obfs-password"], REDACTED);
+        assert_eq!(value["proxies"][0]["username"], REDACTED);
+        assert_eq!(value["proxies"][0]["name"], "hk");
+        assert_eq!(value["proxies"][0]["port"], 443);
+        assert_eq!(value["metadata"]["sourceIP"], hash_ip("192.168.1.2"));
+        assert_eq!(value["metadata"]["host"], "w**.e******.com");
+    }
+
+    #[test]
+    fn redact_log_text() {
+        let text = r#"request failed token="abc" header Authorization: Bearer xyz, mode=rule"#;
+        assert_eq!(
+            redact_text(&config(false, false), text),
+            r#"request failed token="<redacted>" header Authorization: <redacted> <redacted>, mode=rule"#
+        );
+        assert_eq!(
+            redact_text(
+                &config(true, false),
+                "dial www.example.com via 10.1.2.3 v1.2 at example.org."
+            ),
+            "dial w**.e******.com via 10.1.*.* v1.2 at e******.org."
+        );
+        // a secret key without an assignment is just a word
+        assert_eq!(
+            redact_text(&config(false, false), r#"Config { mihomo_secret: Some("abc") }"#),
+            r#"Config { mihomo_secret: Some("<redacted>") }"#
+        );
+        let text = "Entered secret accepted, Bearer abc";
+        assert_eq!(
+            redact_text(&config(false, false), text),
+            "Entered secret accepted, Bearer <redacted>"
+        );
+        let plain = "nothing to hide";
+        assert!(matches!(redact_text(&config(true, true), plain), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn mask_host_forms() {
+        assert_eq!(mask_host("example.com"), "e******.com");
+        assert_eq!(mask_host("localhost"), "localhost");
+        assert_eq!(mask_host("2001:db8::1"), "2001:db8:*");
+        assert_eq!(hash_ip("10.0.0.1"), hash_ip("10.0.0.1"));
+        assert_ne!(hash_ip("10.0.0.1"), hash_ip("10.0.0.2"));
+    }
+}
@@ -0,0 +1,287 @@
+//! Local evaluation of the rule list against a destination, mirroring how the core picks the
+//! rule of a connection.
+//!
+//! Only the destination is known, so rules depending on the source, the process, rule providers
+//! or the GeoSite database cannot be decided and are reported as such rather than guessed.
+
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use crate::models::Rule;
+use crate::utils::rule_parser::{self, LogicOp, RuleNode};
+
+/// Destination to test the rules with, at least one of the fields is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatchTarget {
+    /// Lowercase domain without trailing dot
+    pub host: Option<String>,
+    pub ip: Option<IpAddr>,
+    pub port: Option<u16>,
+}
+
+impl MatchTarget {
+    /// Parse a domain, an IP or a port, optionally joined as `host:port` or `[ipv6]:port`. A URL
+    /// is accepted too, its scheme and path are ignored.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut s = input.trim();
+        if let Some((_, rest)) = s.split_once("://") {
+            s = rest;
+        }
+        s = s.split(['/', '?', '#']).next().unwrap_or_default();
+        if s.is_empty() {
+            return Err("Type a domain, an IP or a port".into());
+        }
+        if let Ok(port) = s.parse::<u16>() {
+            return Ok(Self { port: Some(port), ..Default::default() });
+        }
+        if let Ok(ip) = s.parse::<IpAddr>() {
+            return Ok(Self { ip: Some(ip), ..Default::default() });
+        }
+        let (addr, port) = match s.rsplit_once(':') {
+            Some((addr, port)) if !addr.contains(':') || addr.ends_with(']') => {
+                let port = port.parse::<u16>().map_err(|_| format!("Invalid port `{port}`"))?;
+                (addr.trim_start_matches('[').trim_end_matches(']'), Some(port))
+            }
+            _ => (s, None),
+        };
+        if let Ok(ip) = addr.parse::<IpAddr>() {
+            return Ok(Self { ip: Some(ip), port, ..Default::default() });
+        }
+        let host = addr.trim_end_matches('.').to_ascii_lowercase();
+        if host.is_empty() || host.contains(|c: char| c.is_whitespace() || c == ':') {
+            return Err(format!("Invalid domain `{addr}`"));
+        }
+        Ok(Self { host: Some(host), ip: None, port })
+    }
+}
+
+/// Three-valued result of a rule against a [`MatchTarget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Miss,
+    /// The rule needs more than the destination, or a resolved IP of a domain
+    Unknown,
+}
+
+impl Verdict {
+    fn not(self) -> Self {
+        match self {
+            Self::Match => Self::Miss,
+            Self::Miss => Self::Match,
+            Self::Unknown => Self::Unknown,
+        }
+    }
+
+    fn from_bool(matched: bool) -> Self {
+        if matched { Self::Match } else { Self::Miss }
+    }
+}
+
+/// Outcome of [`first_match`], indices are positions in the tested rules.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatchOutcome {
+    /// The first rule matching for sure
+    pub matched: Option<usize>,
+    /// Rules before it that could not be decided, the core may pick one of them instead
+    pub undecided: Vec<usize>,
+}
+
+/// First enabled rule of `rules` matching `target`. `country` looks up the ISO code of an IP for
+/// `GEOIP` rules, `None` leaves them undecided.
+pub fn first_match(
+    rules: &[Arc<Rule>],
+    target: &MatchTarget,
+    country: impl Fn(IpAddr) -> Option<String>,
+) -> MatchOutcome {
+    let mut outcome = MatchOutcome::default();
+    for (idx, rule) in rules.iter().enumerate() {
+        if rule.extra.as_ref().is_some_and(|extra| extra.disabled) {
+            continue;
+        }
+        let verdict = match rule_parser::parse(&rule.r#type, &rule.payload) {
+            Ok(node) => eval(&node, target, &country),
+            Err(_) => Verdict::Unknown,
+        };
+        match verdict {
+            Verdict::Match => {
+                outcome.matched = Some(idx);
+                break;
+            }
+            Verdict::Unknown => outcome.undecided.push(idx),
+            Verdict::Miss => {}
+        }
+    }
+    outcome
+}
+
+fn eval(
+    node: &RuleNode,
+    target: &MatchTarget,
+    country: &impl Fn(IpAddr) -> Option<String>,
+) -> Verdict {
+    match node {
+        RuleNode::Leaf { r#type, payload } => leaf(r#type, payload, target, country),
+        RuleNode::Logic { op: LogicOp::Not, children } => eval(&children[0], target, country).not(),
+        RuleNode::Logic { op, children } => {
+            // `AND` is decided by its first miss, `OR` by its first match
+            let decisive = if *op == LogicOp::And { Verdict::Miss } else { Verdict::Match };
+            let mut verdict = decisive.not();
+            for child in children {
+                match eval(child, target, country) {
+                    v if v == decisive => return decisive,
+                    Verdict::Unknown => verdict = Verdict::Unknown,
+                    _ => {}
+                }
+            }
+            verdict
+        }
+    }
+}
+
+fn leaf(
+    r#type: &str,
+    payload: &str,
+    target: &MatchTarget,
+    country: &impl Fn(IpAddr) -> Option<String>,
+) -> Verdict {
+    // sub-rules of logic rules keep their params, e.g. `1.0.0.0/8,no-resolve`
+    let payload = payload.split(',').next().unwrap_or_default().trim().trim_matches('"');
+    let host = target.host.as_deref();
+    let domain = |f: &dyn Fn(&str, &str) -> bool| {
+        let payload = payload.to_ascii_lowercase();
+        Verdict::from_bool(host.is_some_and(|host| f(host, &payload)))
+    };
+    // the core resolves the domain for IP rules, which is not done here
+    let ip = |f: &dyn Fn(IpAddr) -> Option<bool>| match target.ip {
+        Some(ip) => f(ip).map_or(Verdict::Unknown, Verdict::from_bool),
+        None if host.is_some() => Verdict::Unknown,
+        None => Verdict::Miss,
+    };
+    match r#type.to_ascii_uppercase().as_str() {
+        "DOMAIN" => domain(&|host, p| host == p),
+        "DOMAIN-SUFFIX" => domain(&|host, p| {
+            host == p || host.strip_suffix(p).is_some_and(|rest| rest.ends_with('.'))
+        }),
+        "DOMAIN-KEYWORD" => domain(&|host, p| host.contains(p)),
+        "IP-CIDR" | "IP-CIDR6" => ip(&|ip| cidr_contains(payload, ip)),
+        "GEOIP" => ip(&|ip| {
+            if payload.eq_ignore_ascii_case("LAN") || payload.eq_ignore_ascii_case("private") {
+                return Some(is_private(ip));
+            }
+            country(ip).map(|code| code.eq_ignore_ascii_case(payload))
+        }),
+        // without a port the core would still know one, the rule can't be decided
+        "DST-PORT" => match target.port {
+            Some(port) => port_matches(payload, port).map_or(Verdict::Unknown, Verdict::from_bool),
+            None => Verdict::Unknown,
+        },
+        "MATCH" | "FINAL" => Verdict::Match,
+        _ => Verdict::Unknown,
+    }
+}
+
+/// Whether `cidr`, e.g. `10.0.0.0/8`, contains `ip`. `None` if it does not parse.
+fn cidr_contains(cidr: &str, ip: IpAddr) -> Option<bool> {
+    let (net, len) = cidr.split_once('/').unwrap_or((cidr, ""));
+    let net = net.parse::<IpAddr>().ok()?;
+    let bits = |ip: IpAddr| match ip {
+        IpAddr::V4(v4) => (u32::from(v4) as u128, 32),
+        IpAddr::V6(v6) => (u128::from(v6), 128),
+    };
+    let ((net, width), (ip, ip_width)) = (bits(net), bits(ip));
+    if width != ip_width {
+        return Some(false);
+    }
+    let len =
+        if len.is_empty() { width } else { len.parse::<u32>().ok().filter(|l| *l <= width)? };
+    let shift = width - len;
+    Some(len == 0 || net >> shift == ip >> shift)
+}
+
+/// `DST-PORT` payloads are ports or ranges joined by `/`, e.g. `80/443/8000-9000`.
+fn port_matches(payload: &str, port: u16) -> Option<bool> {
+    let mut matched = false;
+    for part in payload.split('/') {
+        let (start, end) = part.split_once('-').unwrap_or((part, part));
+        let (start, end) = (start.trim().parse::<u16>().ok()?, end.trim().parse::<u16>().ok()?);
+        matched |= (start..=end).contains(&port);
+    }
+    Some(matched)
+}
+
+fn is_private(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_loopback() || v4.is_link_local(),
+        IpAddr::V6(v6) => {
+            v6.is_loopback() || (v6.segments()[0] & 0xfe00) == 0xfc00 || v6.is_unicast_link_local()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn rule(r#type: &str, payload: &str) -> Arc<Rule> {
+        let value = json!({ "type": r#type, "payload": payload, "proxy": "P", "size": -1 });
+        Arc::new(serde_json::from_value(value).unwrap())
+    }
+
+    fn first(rules: &[Arc<Rule>], input: &str) -> MatchOutcome {
+        let target = MatchTarget::parse(input).unwrap();
+        first_match(rules, &target, |ip| (ip.to_string() == "1.1.1.1").then(|| "US".into()))
+    }
+
+    #[test]
+    fn targets_are_parsed() {
+        let parse = |s| MatchTarget::parse(s).unwrap();
+        assert_eq!(parse("443").port, Some(443));
+        assert_eq!(parse("https://Example.COM./a?b").host.as_deref(), Some("example.com"));
+        let target = parse("[::1]:53");
+        assert_eq!((target.ip, target.port), (Some("::1".parse().unwrap()), Some(53)));
+        assert_eq!(parse("a.com:8080").port, Some(8080));
+        assert_eq!(parse("fe80::1").ip, Some("fe80::1".parse().unwrap()));
+        assert!(MatchTarget::parse(" ").is_err());
+        assert!(MatchTarget::parse("a.com:http").is_err());
+    }
+
+    #[test]
+    fn first_decided_rule_wins() {
+        let rules = [
+            rule("DOMAIN-SUFFIX", "google.com"),
+            rule("PROCESS-NAME", "curl"),
+            rule("IP-CIDR", "10.0.0.0/8"),
+            rule("AND", "((DST-PORT,80/443),(NOT,((DOMAIN-KEYWORD,ads))))"),
+            rule("GEOIP", "US"),
+            rule("MATCH", ""),
+        ];
+        assert_eq!(first(&rules, "www.google.com").matched, Some(0));
+        assert_eq!(first(&rules, "notgoogle.com:22").matched, Some(5));
+        // an unresolved domain cannot be tested against IP rules
+        assert_eq!(first(&rules, "notgoogle.com:22").undecided, [1, 2, 4]);
+        assert_eq!(first(&rules, "10.1.2.3").matched, Some(2));
+        assert_eq!(first(&rules, "a.com:443").matched, Some(3));
+        assert_eq!(first(&rules, "ads.com:443").matched, Some(5));
+        assert_eq!(first(&rules, "1.1.1.1").matched, Some(4));
+        // no port to test `DST-PORT` with
+        assert_eq!(first(&rules, "1.1.1.1").undecided, [1, 3]);
+        assert_eq!(first(&rules, "8.8.8.8").undecided, [1, 3, 4]);
+    }
+
+    #[test]
+    fn cidr_and_ports() {
+        let ip = |s: &str| s.parse().unwrap();
+        assert_eq!(cidr_contains("192.168.0.0/16", ip("192.168.3.4")), Some(true));
+        assert_eq!(cidr_contains("192.168.0.0/16", ip("192.169.0.1")), Some(false));
+        assert_eq!(cidr_contains("0.0.0.0/0", ip("8.8.8.8")), Some(true));
+        assert_eq!(cidr_contains("2001:db8::/32", ip("2001:db8::1")), Some(true));
+        assert_eq!(cidr_contains("2001:db8::/32", ip("10.0.0.1")), Some(false));
+        assert_eq!(cidr_contains("10.0.0.0/33", ip("10.0.0.1")), None);
+        assert_eq!(port_matches("80/8000-9000", 8443), Some(true));
+        assert_eq!(port_matches("80/8000-9000", 443), Some(false));
+        assert_eq!(port_matches("http", 80), None);
+    }
+}
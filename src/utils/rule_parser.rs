@@ -0,0 +1,339 @@
+//! Parser of logic rule payloads (`AND`, `OR`, `NOT`), e.g.
+//! `((DOMAIN,a.com),(NOT,((NETWORK,UDP))))`.
+//!
+//! Payloads come from the core as is, so the parser must not panic on anything. Commas and
+//! parentheses inside quotes or escaped with `\` belong to the payload, e.g.
+//! `(DOMAIN-REGEX,"^(a|b),c$")` or `(PROCESS-NAME,a\,b)`.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicOp {
+    And,
+    Or,
+    Not,
+}
+
+impl LogicOp {
+    pub fn from_type(r#type: &str) -> Option<Self> {
+        match r#type.trim().to_ascii_uppercase().as_str() {
+            "AND" => Some(Self::And),
+            "OR" => Some(Self::Or),
+            "NOT" => Some(Self::Not),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleNode<'a> {
+    Logic {
+        op: LogicOp,
+        children: Vec<RuleNode<'a>>,
+    },
+    /// A plain rule, the payload is kept verbatim (quotes and escapes included)
+    Leaf {
+        r#type: &'a str,
+        payload: &'a str,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnbalancedParen,
+    UnterminatedQuote,
+    /// A sub-rule not wrapped in parentheses
+    ExpectedGroup(String),
+    EmptyRule,
+    /// `NOT` takes exactly one sub-rule
+    NotArity(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnbalancedParen => write!(f, "unbalanced parentheses"),
+            Self::UnterminatedQuote => write!(f, "unterminated quote"),
+            Self::ExpectedGroup(found) => {
+                write!(f, "expected a parenthesized rule, found {found:?}")
+            }
+            Self::EmptyRule => write!(f, "empty rule"),
+            Self::NotArity(n) => write!(f, "NOT takes 1 rule, found {n}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse a rule of `type` with its `payload`, logic rules are parsed recursively.
+pub fn parse<'a>(r#type: &'a str, payload: &'a str) -> Result<RuleNode<'a>, ParseError> {
+    let r#type = r#type.trim();
+    let payload = payload.trim();
+    let Some(op) = LogicOp::from_type(r#type) else {
+        if r#type.is_empty() {
+            return Err(ParseError::EmptyRule);
+        }
+        return Ok(RuleNode::Leaf { r#type, payload });
+    };
+
+    let children = split_top_level(ungroup(payload)?)?
+        .into_iter()
+        .map(|item| {
+            let inner = ungroup(item.trim())?;
+            let (r#type, payload) = split_first(inner)?.unwrap_or((inner, ""));
+            parse(r#type, payload)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    match (op, children.len()) {
+        (_, 0) => Err(ParseError::EmptyRule),
+        (LogicOp::Not, n) if n != 1 => Err(ParseError::NotArity(n)),
+        _ => Ok(RuleNode::Logic { op, children }),
+    }
+}
+
+/// Strip the parentheses wrapping the whole of `s`.
+fn ungroup(s: &str) -> Result<&str, ParseError> {
+    if !s.starts_with('(') || !s.ends_with(')') || s.len() < 2 {
+        return Err(ParseError::ExpectedGroup(s.to_string()));
+    }
+    // `(a),(b)` starts and ends with parentheses without being a single group
+    match scan(s, |depth, idx, _| depth == 0 && idx + 1 < s.len())? {
+        Some(_) => Err(ParseError::ExpectedGroup(s.to_string())),
+        None => Ok(&s[1..s.len() - 1]),
+    }
+}
+
+/// Split `s` at commas outside of parentheses, quotes and escapes.
+fn split_top_level(s: &str) -> Result<Vec<&str>, ParseError> {
+    let mut items = vec![];
+    let mut rest = s;
+    while let Some((item, tail)) = split_first(rest)? {
+        items.push(item);
+        rest = tail;
+    }
+    items.push(rest);
+    Ok(items)
+}
+
+/// Split `s` at its first top-level comma.
+fn split_first(s: &str) -> Result<Option<(&str, &str)>, ParseError> {
+    let comma = scan(s, |depth, _, c| depth == 0 && c == ',')?;
+    Ok(comma.map(|idx| (&s[..idx], &s[idx + 1..])))
+}
+
+/// Walk `s` tracking parentheses depth, returns the byte index of the first char for which
+/// `stop(depth after the char, index, char)` holds. Fails on unbalanced parentheses or quotes.
+fn scan(s: &str, stop: impl Fn(usize, usize, char) -> bool) -> Result<Option<usize>, ParseError> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut chars = s.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match (quote, c) {
+            (_, '\\') => {
+                chars.next();
+                continue;
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => continue,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.checked_sub(1).ok_or(ParseError::UnbalancedParen)?,
+            _ => {}
+        }
+        if quote.is_none() && stop(depth, idx, c) {
+            return Ok(Some(idx));
+        }
+    }
+    match (quote, depth) {
+        (Some(_), _) => Err(ParseError::UnterminatedQuote),
+        (None, 0) => Ok(None),
+        _ => Err(ParseError::UnbalancedParen),
+    }
+}
+
+/// Readable form, e.g. `DOMAIN,a.com && !(NETWORK,UDP)`.
+impl fmt::Display for RuleNode<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Leaf { r#type, payload: "" } => write!(f, "{type}"),
+            Self::Leaf { r#type, payload } => write!(f, "{type},{payload}"),
+            Self::Logic { op: LogicOp::Not, children } => write!(f, "!({})", children[0]),
+            Self::Logic { op, children } => {
+                let sep = if *op == LogicOp::And { " && " } else { " || " };
+                for (idx, child) in children.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(sep)?;
+                    }
+                    match child {
+                        Self::Logic { op: LogicOp::And | LogicOp::Or, .. } => {
+                            write!(f, "({child})")?
+                        }
+                        _ => write!(f, "{child}")?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn leaf<'a>(r#type: &'a str, payload: &'a str) -> RuleNode<'a> {
+        RuleNode::Leaf { r#type, payload }
+    }
+
+    /// Back to the payload syntax of the core.
+    fn to_payload(children: &[RuleNode]) -> String {
+        let items = children
+            .iter()
+            .map(|child| match child {
+                RuleNode::Leaf { r#type, payload } => format!("({type},{payload})"),
+                RuleNode::Logic { op, children } => {
+                    format!("({},{})", type_of(*op), to_payload(children))
+                }
+            })
+            .collect::<Vec<_>>();
+        format!("({})", items.join(","))
+    }
+
+    fn type_of(op: LogicOp) -> String {
+        format!("{op:?}").to_uppercase()
+    }
+
+    fn count_leaves(node: &RuleNode) -> usize {
+        match node {
+            RuleNode::Leaf { .. } => 1,
+            RuleNode::Logic { children, .. } => children.iter().map(count_leaves).sum(),
+        }
+    }
+
+    #[test]
+    fn parse_nested_logic() {
+        let node =
+            parse("AND", "((DOMAIN,baidu.com),(NOT,((NETWORK,UDP))),(OR,((DST-PORT,80),(MATCH))))")
+                .unwrap();
+        assert_eq!(
+            node,
+            RuleNode::Logic {
+                op: LogicOp::And,
+                children: vec![
+                    leaf("DOMAIN", "baidu.com"),
+                    RuleNode::Logic { op: LogicOp::Not, children: vec![leaf("NETWORK", "UDP")] },
+                    RuleNode::Logic {
+                        op: LogicOp::Or,
+                        children: vec![leaf("DST-PORT", "80"), leaf("MATCH", "")],
+                    },
+                ],
+            }
+        );
+        assert_eq!(
+            node.to_string(),
+            "DOMAIN,baidu.com && !(NETWORK,UDP) && (DST-PORT,80 || MATCH)"
+        );
+        assert_eq!(parse("DOMAIN", "a.com").unwrap(), leaf("DOMAIN", "a.com"));
+    }
+
+    #[test]
+    fn parse_quoted_and_escaped_payloads() {
+        let node = parse(
+            "or",
+            r#"((DOMAIN-REGEX,"^(a|b),c$"),(PROCESS-NAME,a\,b),(IP-CIDR,1.1.1.1/32,no-resolve))"#,
+        )
+        .unwrap();
+        assert_eq!(
+            node,
+            RuleNode::Logic {
+                op: LogicOp::Or,
+                children: vec![
+                    leaf("DOMAIN-REGEX", r#""^(a|b),c$""#),
+                    leaf("PROCESS-NAME", r"a\,b"),
+                    leaf("IP-CIDR", "1.1.1.1/32,no-resolve"),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn reject_malformed_payloads() {
+        assert_eq!(parse("AND", "((DOMAIN,a)"), Err(ParseError::UnbalancedParen));
+        assert_eq!(parse("AND", r#"((DOMAIN,"a))"#), Err(ParseError::UnterminatedQuote));
+        assert_eq!(parse("AND", "(DOMAIN,a)"), Err(ParseError::ExpectedGroup("DOMAIN".into())));
+        assert_eq!(parse("AND", "(a),(b)"), Err(ParseError::ExpectedGroup("(a),(b)".into())));
+        assert_eq!(parse("NOT", "((A,a),(B,b))"), Err(ParseError::NotArity(2)));
+        assert_eq!(parse("AND", "(())"), Err(ParseError::EmptyRule));
+    }
+
+    fn leaf_type() -> impl Strategy<Value = String> {
+        "[A-Z][A-Z-]{0,10}".prop_filter("logic types", |t| LogicOp::from_type(t).is_none())
+    }
+
+    /// Payloads with the tricky parts of real configs: escapes, quotes and regex groups.
+    fn leaf_payload() -> impl Strategy<Value = String> {
+        let segment = prop_oneof![
+            "[a-z0-9./*-]{1,8}",
+            Just(r"\,".to_string()),
+            Just(r"\(".to_string()),
+            "\"[a-z,()| ]{0,6}\"",
+            "'[a-z,()]{0,6}'",
+            "\\([a-z|]{1,6}\\)",
+            Just(",no-resolve".to_string()),
+        ];
+        ("[a-z0-9]{1,4}", prop::collection::vec(segment, 0..4))
+            .prop_map(|(head, segments)| head + &segments.concat())
+    }
+
+    #[derive(Debug, Clone)]
+    enum Tree {
+        Leaf(String, String),
+        Logic(LogicOp, Vec<Tree>),
+    }
+
+    fn tree() -> impl Strategy<Value = Tree> {
+        let leaf = (leaf_type(), leaf_payload()).prop_map(|(t, p)| Tree::Leaf(t, p));
+        leaf.prop_recursive(4, 32, 4, |inner| {
+            prop_oneof![
+                inner.clone().prop_map(|child| Tree::Logic(LogicOp::Not, vec![child])),
+                (
+                    prop_oneof![Just(LogicOp::And), Just(LogicOp::Or)],
+                    prop::collection::vec(inner, 1..4)
+                )
+                    .prop_map(|(op, children)| Tree::Logic(op, children)),
+            ]
+        })
+    }
+
+    fn as_node(tree: &Tree) -> RuleNode<'_> {
+        match tree {
+            Tree::Leaf(t, p) => leaf(t, p),
+            Tree::Logic(op, children) => {
+                RuleNode::Logic { op: *op, children: children.iter().map(as_node).collect() }
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(r#type in "(AND|OR|NOT|[A-Z]{1,4})", payload in r#"[(),"'\\a-z ]{0,40}"#) {
+            let _ = parse(&r#type, &payload);
+        }
+
+        #[test]
+        fn round_trips_without_losing_segments(
+            op in prop_oneof![Just(LogicOp::And), Just(LogicOp::Or)],
+            children in prop::collection::vec(tree(), 1..4),
+        ) {
+            let expected = RuleNode::Logic { op, children: children.iter().map(as_node).collect() };
+            let RuleNode::Logic { children: nodes, .. } = &expected else { unreachable!() };
+            let payload = to_payload(nodes);
+            let r#type = type_of(op);
+            let parsed = parse(&r#type, &payload).unwrap();
+            prop_assert_eq!(count_leaves(&parsed), count_leaves(&expected));
+            prop_assert_eq!(parsed, expected);
+        }
+    }
+}
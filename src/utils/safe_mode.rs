@@ -0,0 +1,17 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// `--safe-mode`: no websocket streams and no background timers, only REST calls the user asks
+/// for, to tell whether crashes or hangs come from the streaming paths.
+pub struct SafeMode;
+
+impl SafeMode {
+    pub fn enable() {
+        ENABLED.store(true, Ordering::Relaxed);
+    }
+
+    pub fn enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow, bail};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+/// Split `host:port` (or `[v6]:port`) into host and port.
+pub fn parse_target(target: &str) -> Result<(String, u16)> {
+    let target = target.trim();
+    let (host, port) = target.rsplit_once(':').ok_or_else(|| anyhow!("Expected `host:port`"))?;
+    let host = match host.strip_prefix('[') {
+        Some(v6) => v6.strip_suffix(']').ok_or_else(|| anyhow!("Unclosed `[` in host"))?,
+        None if host.contains(':') => bail!("IPv6 hosts must be enclosed in `[]`"),
+        None => host,
+    };
+    if host.is_empty() {
+        bail!("Host is required");
+    }
+    let port = port.parse::<u16>().with_context(|| format!("Invalid port `{port}`"))?;
+    Ok((host.to_owned(), port))
+}
+
+/// Time to establish a TCP connection to `host:port` directly from this machine, DNS resolution
+/// included.
+pub async fn ping(host: &str, port: u16, limit: Duration) -> Result<Duration> {
+    let started = Instant::now();
+    timeout(limit, TcpStream::connect((host, port)))
+        .await
+        .map_err(|_| anyhow!("timeout"))?
+        .context("connect failed")?;
+    Ok(started.elapsed())
+}
+
+/// Time to the response of an HTTP HEAD request to `url` sent directly from this machine, like
+/// the core's delay test of a proxy, DNS resolution and handshakes included. Any status counts.
+pub async fn http_delay(url: &str, limit: Duration) -> Result<Duration> {
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(limit)
+        .build()
+        .context("Fail to build HTTP client")?;
+    let started = Instant::now();
+    client.head(url).send().await.map_err(|e| match e.is_timeout() {
+        true => anyhow!("timeout"),
+        false => anyhow!(e).context("request failed"),
+    })?;
+    Ok(started.elapsed())
+}
+
+/// Summary of a series of pings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PingStats {
+    pub min: Duration,
+    pub avg: Duration,
+    pub max: Duration,
+    /// Failed attempts in percent
+    pub loss: f64,
+}
+
+impl PingStats {
+    /// `None` if every attempt failed.
+    pub fn from_results<E>(results: &[Result<Duration, E>]) -> Option<Self> {
+        let ok: Vec<Duration> = results.iter().filter_map(|r| r.as_ref().ok().copied()).collect();
+        let min = ok.iter().min().copied()?;
+        let max = ok.iter().max().copied()?;
+        let avg = ok.iter().sum::<Duration>() / ok.len() as u32;
+        let loss = (results.len() - ok.len()) as f64 * 100.0 / results.len() as f64;
+        Some(Self { min, avg, max, loss })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    #[test]
+    fn parse_target_accepts_hosts_and_v6() {
+        assert_eq!(parse_target(" example.com:443 ").unwrap(), ("example.com".into(), 443));
+        assert_eq!(parse_target("1.1.1.1:53").unwrap(), ("1.1.1.1".into(), 53));
+        assert_eq!(parse_target("[::1]:8080").unwrap(), ("::1".into(), 8080));
+        assert!(parse_target("example.com").is_err());
+        assert!(parse_target(":80").is_err());
+        assert!(parse_target("example.com:http").is_err());
+        assert!(parse_target("::1:80").is_err());
+        assert!(parse_target("[::1:80").is_err());
+    }
+
+    #[test]
+    fn stats_skip_failed_attempts() {
+        let ms = Duration::from_millis;
+        let results: Vec<Result<Duration, ()>> = vec![Ok(ms(10)), Err(()), Ok(ms(30)), Ok(ms(20))];
+        assert_eq!(
+            PingStats::from_results(&results),
+            Some(PingStats { min: ms(10), avg: ms(20), max: ms(30), loss: 25.0 })
+        );
+        assert_eq!(PingStats::from_results::<()>(&[Err(()), Err(())]), None);
+    }
+
+    #[tokio::test]
+    async fn ping_local_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(ping("127.0.0.1", port, Duration::from_secs(1)).await.is_ok());
+        drop(listener);
+        assert!(ping("127.0.0.1", port, Duration::from_secs(1)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn http_delay_of_local_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).await;
+            stream.write_all(b"HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\n\r\n").await
+        });
+        assert!(http_delay(&url, Duration::from_secs(1)).await.is_ok());
+        // nothing listens anymore
+        assert!(http_delay(&url, Duration::from_secs(1)).await.is_err());
+    }
+}
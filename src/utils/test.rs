@@ -1,5 +1,11 @@
 use std::sync::Once;
 
+use serde_json::{Value, json};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::models::Connection;
+
 pub fn init_logger() {
     static INIT: Once = Once::new();
     INIT.call_once(|| {
@@ -10,3 +16,76 @@ pub fn init_logger() {
             .try_init();
     });
 }
+
+/// Builder of [`Connection`] fixtures, fields left unset are empty.
+#[derive(Debug)]
+pub struct ConnBuilder(Connection);
+
+/// A connection fixture with `id`, see [`ConnBuilder`].
+pub fn conn(id: &str) -> ConnBuilder {
+    ConnBuilder(Connection {
+        id: id.into(),
+        metadata: json!({}),
+        upload: 0,
+        download: 0,
+        start: None,
+        chains: Vec::new(),
+        rule: String::new(),
+        rule_payload: String::new(),
+        inactive: Default::default(),
+        upload_rate: 0,
+        download_rate: 0,
+    })
+}
+
+/// Empty connections by id.
+pub fn conns(ids: &[&str]) -> Vec<Connection> {
+    ids.iter().map(|id| conn(id).build()).collect()
+}
+
+impl ConnBuilder {
+    pub fn metadata(mut self, metadata: Value) -> Self {
+        self.0.metadata = metadata;
+        self
+    }
+
+    pub fn bytes(mut self, upload: u64, download: u64) -> Self {
+        self.0.upload = upload;
+        self.0.download = download;
+        self
+    }
+
+    /// `start` in RFC 3339, e.g. `2024-01-01T00:00:00Z`
+    pub fn start(mut self, start: &str) -> Self {
+        self.0.start = Some(OffsetDateTime::parse(start, &Rfc3339).unwrap());
+        self
+    }
+
+    pub fn chains(mut self, chains: &[&str]) -> Self {
+        self.0.chains = chains.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    pub fn rule(mut self, rule: &str, payload: &str) -> Self {
+        self.0.rule = rule.into();
+        self.0.rule_payload = payload.into();
+        self
+    }
+
+    pub fn build(self) -> Connection {
+        self.0
+    }
+}
+
+/// Calls `poll` until it returns something, e.g. the output of a [`BlockingJob`] started by a
+/// component.
+///
+/// [`BlockingJob`]: crate::task_manager::BlockingJob
+pub async fn poll_until<T>(mut poll: impl FnMut() -> Option<T>) -> T {
+    loop {
+        if let Some(output) = poll() {
+            return output;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+    }
+}
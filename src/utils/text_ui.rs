@@ -1,8 +1,11 @@
 use const_format::concatcp;
 use ratatui::layout::{Constraint, Flex, Layout, Rect};
-use ratatui::style::Style;
+use ratatui::style::{Color, Style};
 use ratatui::symbols::line::{TOP_LEFT, TOP_RIGHT};
 use ratatui::text::{Line, Span};
+use time::{Duration, OffsetDateTime};
+
+use crate::utils::time::format_time_from_now;
 
 pub const TOP_TITLE_LEFT: &str = concatcp!(TOP_RIGHT, " ");
 pub const TOP_TITLE_RIGHT: &str = concatcp!(" ", TOP_LEFT);
@@ -29,6 +32,20 @@ pub fn dashed_title_line<'a, S: Into<Span<'a>>>(title: S, width: u16) -> Line<'a
     ])
 }
 
+/// Data older than this is highlighted as stale in panel titles.
+const STALE_AFTER: Duration = Duration::minutes(5);
+
+/// Title span telling how old the data of a panel is, e.g. ` updated 42s ago`.
+pub fn freshness_span(updated_at: Option<OffsetDateTime>) -> Option<Span<'static>> {
+    let updated_at = updated_at?;
+    let color = if OffsetDateTime::now_utc() - updated_at > STALE_AFTER {
+        Color::Yellow
+    } else {
+        Color::DarkGray
+    };
+    Some(Span::styled(format!(" updated {} ago", format_time_from_now(updated_at)), color))
+}
+
 pub fn top_title_line<S: Into<Style>>(title: &'_ str, title_style: S) -> Line<'_> {
     Line::from(vec![
         Span::raw(TOP_TITLE_LEFT),
@@ -10,6 +10,7 @@ use time::{OffsetDateTime, UtcDateTime};
 pub static DATE_ONLY_FMT: &[FormatItem<'static>] = format_description!("[year]-[month]-[day]");
 pub static DATETIME_FMT: &[FormatItem<'static>] =
     format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+pub static HOUR_MINUTE_FMT: &[FormatItem<'static>] = format_description!("[hour]:[minute]");
 
 /// Format OffsetDateTime as `2006-01-02 15:04:05`
 ///
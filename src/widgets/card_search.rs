@@ -0,0 +1,170 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config as MatcherConfig, Matcher, Utf32Str};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use tui_input::Input;
+
+use crate::utils::input::KeyOutcome;
+use crate::utils::tui_input::input_request;
+
+/// Inline fuzzy search over the node cards of a detail popup, matching names and types.
+///
+/// `/` starts editing, `Enter` keeps the query and returns to navigation, `Esc` clears it.
+#[derive(Debug, Default, Clone)]
+pub struct CardSearch {
+    input: Input,
+    editing: bool,
+}
+
+impl CardSearch {
+    /// Whether the search bar is shown, either being edited or filtering cards.
+    pub fn is_visible(&self) -> bool {
+        self.editing || !self.query().is_empty()
+    }
+
+    pub fn query(&self) -> &str {
+        self.input.value().trim()
+    }
+
+    pub fn clear(&mut self) {
+        self.input.reset();
+        self.editing = false;
+    }
+
+    /// Matcher of the current query, `None` when every card is listed.
+    pub fn matcher(&self) -> Option<CardMatcher> {
+        let query = self.query();
+        if query.is_empty() {
+            return None;
+        }
+        Some(CardMatcher {
+            pattern: Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart),
+            matcher: Matcher::new(MatcherConfig::DEFAULT),
+            buf: Vec::new(),
+            haystack: String::new(),
+        })
+    }
+
+    /// Handles search keys, `changed` is set when the query was edited so the caller can
+    /// reset its navigation.
+    pub fn handle_key_event(&mut self, key: KeyEvent, changed: &mut bool) -> KeyOutcome {
+        if !self.editing {
+            return match key.code {
+                KeyCode::Char('/') => {
+                    self.editing = true;
+                    KeyOutcome::Consumed
+                }
+                KeyCode::Esc if !self.query().is_empty() => {
+                    self.clear();
+                    *changed = true;
+                    KeyOutcome::Consumed
+                }
+                _ => KeyOutcome::Ignored,
+            };
+        }
+        match key.code {
+            KeyCode::Enter => self.editing = false,
+            KeyCode::Esc => {
+                *changed = !self.query().is_empty();
+                self.clear();
+            }
+            // cards stay navigable while typing
+            KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown => {
+                return KeyOutcome::Ignored;
+            }
+            _ => {
+                if let Some(req) = input_request(key) {
+                    let before = self.query().to_string();
+                    let _ = self.input.handle(req);
+                    *changed = before != self.query();
+                }
+            }
+        }
+        KeyOutcome::Consumed
+    }
+
+    /// Renders a one-line search bar with `matched` out of `total` cards.
+    pub fn render(&self, matched: usize, total: usize, frame: &mut Frame, area: Rect) {
+        let prefix = "/ ";
+        let count = format!(" {matched}/{total}");
+        let width = area.width.saturating_sub((prefix.len() + count.len()) as u16) as usize;
+        let scroll = self.input.visual_scroll(width);
+        let style = if self.editing { Color::LightBlue } else { Color::default() };
+        let line = Line::from(vec![
+            Span::styled(prefix, style),
+            Span::raw(self.input.value().chars().skip(scroll).take(width).collect::<String>()),
+        ]);
+        frame.render_widget(Paragraph::new(line), area);
+        frame.render_widget(
+            Paragraph::new(Span::styled(count, Style::default().fg(Color::DarkGray)))
+                .right_aligned(),
+            area,
+        );
+        if self.editing {
+            let x = (self.input.visual_cursor().max(scroll) - scroll + prefix.len()) as u16;
+            frame.set_cursor_position((area.x + x, area.y));
+        }
+    }
+}
+
+/// Matches cards against a [`CardSearch`] query.
+pub struct CardMatcher {
+    pattern: Pattern,
+    matcher: Matcher,
+    buf: Vec<char>,
+    haystack: String,
+}
+
+impl CardMatcher {
+    pub fn matches(&mut self, name: &str, r#type: &str) -> bool {
+        self.haystack.clear();
+        self.haystack.push_str(name);
+        self.haystack.push(' ');
+        self.haystack.push_str(r#type);
+        self.pattern
+            .score(Utf32Str::new(&self.haystack, &mut self.buf), &mut self.matcher)
+            .is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn press(search: &mut CardSearch, code: KeyCode) -> bool {
+        let mut changed = false;
+        search.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE), &mut changed);
+        changed
+    }
+
+    #[test]
+    fn search_matches_names_and_types() {
+        let mut search = CardSearch::default();
+        assert!(search.matcher().is_none());
+        // typing before `/` is not a search
+        assert!(!press(&mut search, KeyCode::Char('h')));
+        press(&mut search, KeyCode::Char('/'));
+        assert!(search.editing);
+        for c in "hkvm".chars() {
+            assert!(press(&mut search, KeyCode::Char(c)));
+        }
+        // navigation keys are left to the cards
+        assert!(!press(&mut search, KeyCode::Down));
+        press(&mut search, KeyCode::Enter);
+        assert!(!search.editing && search.is_visible());
+
+        let mut matcher = search.matcher().unwrap();
+        assert!(matcher.matches("HK 01", "Vmess"));
+        assert!(!matcher.matches("JP 01", "Vmess"));
+
+        assert!(press(&mut search, KeyCode::Esc));
+        assert!(!search.is_visible());
+        assert!(search.matcher().is_none());
+    }
+}
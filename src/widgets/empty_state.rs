@@ -0,0 +1,83 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use throbber_widgets_tui::{BRAILLE_SIX, Throbber, ThrobberState, WhichUse};
+
+use crate::store::load_state::LoadState;
+
+/// Placeholder rendered in place of an empty table or list.
+#[derive(Debug, Clone)]
+pub struct EmptyState<'a> {
+    state: LoadState,
+    subject: &'a str,
+    filtered: bool,
+}
+
+impl<'a> EmptyState<'a> {
+    /// `subject` is the plural of the listed records, e.g. `rules`.
+    pub fn new(state: LoadState, subject: &'a str) -> Self {
+        Self { state, subject, filtered: false }
+    }
+
+    /// Whether a filter pattern is active, an empty view then means nothing matched.
+    pub fn filtered(mut self, filtered: bool) -> Self {
+        self.filtered = filtered;
+        self
+    }
+
+    fn message(&self) -> String {
+        match (self.state, self.filtered) {
+            (LoadState::Loading, _) => format!("loading {}…", self.subject),
+            (LoadState::Failed, _) => "core unreachable".to_string(),
+            (LoadState::Loaded, true) => format!("no {} match filter", self.subject),
+            (LoadState::Loaded, false) => format!("no {}", self.subject),
+        }
+    }
+
+    /// Render vertically centered in `area`, the throbber only spins while loading.
+    pub fn render(self, frame: &mut Frame, area: Rect, throbber: &mut ThrobberState) {
+        let [_, line_area, _] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1), Constraint::Fill(1)])
+                .areas(area);
+        let message = self.message();
+        match self.state {
+            LoadState::Loading => {
+                let width = message.chars().count() as u16 + 2;
+                let [_, centered, _] = Layout::horizontal([
+                    Constraint::Fill(1),
+                    Constraint::Length(width),
+                    Constraint::Fill(1),
+                ])
+                .areas(line_area);
+                let throbber_widget = Throbber::default()
+                    .label(message)
+                    .style(Style::default().fg(Color::DarkGray))
+                    .throbber_style(Style::default().fg(Color::Cyan))
+                    .throbber_set(BRAILLE_SIX)
+                    .use_type(WhichUse::Spin);
+                frame.render_stateful_widget(throbber_widget, centered, throbber);
+            }
+            LoadState::Failed => {
+                frame.render_widget(Line::styled(message, Color::Red).centered(), line_area)
+            }
+            LoadState::Loaded => {
+                frame.render_widget(Line::styled(message, Color::DarkGray).centered(), line_area)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_by_state() {
+        let msg = |state, filtered| EmptyState::new(state, "rules").filtered(filtered).message();
+        assert_eq!(msg(LoadState::Loading, true), "loading rules…");
+        assert_eq!(msg(LoadState::Failed, false), "core unreachable");
+        assert_eq!(msg(LoadState::Loaded, true), "no rules match filter");
+        assert_eq!(msg(LoadState::Loaded, false), "no rules");
+    }
+}
@@ -8,7 +8,7 @@ use crate::config::LatencyThreshold;
 pub struct Latency(pub Option<i64>);
 
 #[repr(usize)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LatencyQuality {
     Fast = 0,
     Medium = 1,
@@ -24,6 +24,12 @@ impl Latency {
         self.0.is_none()
     }
 
+    /// Orders measured delays ascending, untested and timed out nodes last.
+    pub fn fastest_first(&self, other: &Self) -> std::cmp::Ordering {
+        let key = |latency: &Self| latency.0.filter(|v| *v > 0).map_or((true, 0), |v| (false, v));
+        key(self).cmp(&key(other))
+    }
+
     pub fn as_span<'a>(&self, threshold: LatencyThreshold) -> Span<'a> {
         Span::styled(
             self.0.filter(|v| *v > 0).map(|v| format!("{}", v)).unwrap_or("-".into()),
@@ -71,6 +77,11 @@ impl LatencyQuality {
         }
     }
 
+    /// Red and gray segments of the quality bar, i.e. timed out or above the high threshold.
+    pub fn is_failing(&self) -> bool {
+        matches!(self, LatencyQuality::Slow | LatencyQuality::NotConnected)
+    }
+
     pub fn from(latency: Latency, threshold: LatencyThreshold) -> Self {
         match latency.0 {
             None => LatencyQuality::NotConnected,
@@ -107,6 +118,11 @@ impl QualityStats {
         QualityStats(stats)
     }
 
+    /// Number of nodes in the failing buckets, see [`LatencyQuality::is_failing`].
+    pub fn failing(&self) -> usize {
+        self.0[LatencyQuality::Slow as usize] + self.0[LatencyQuality::NotConnected as usize]
+    }
+
     pub fn as_line<'a>(&self, width: u16, total: usize) -> Line<'a> {
         // `total == 0` would make `exact` NaN below and panic in the comparator
         if total == 0 {
@@ -159,4 +175,18 @@ mod tests {
         let line = QualityStats::new([1, 1, 1, 0]).as_line(90, 3);
         assert_eq!(line.width(), 90);
     }
+
+    #[test]
+    fn test_failing_counts_slow_and_not_connected() {
+        assert_eq!(QualityStats::new([3, 2, 1, 4]).failing(), 5);
+        assert!(LatencyQuality::NotConnected.is_failing());
+        assert!(!LatencyQuality::Medium.is_failing());
+    }
+
+    #[test]
+    fn test_fastest_first_puts_timeouts_last() {
+        let mut latencies = [Some(300), None, Some(0), Some(20), Some(150)].map(Latency);
+        latencies.sort_by(Latency::fastest_first);
+        assert_eq!(latencies.map(|l| l.0), [Some(20), Some(150), Some(300), None, Some(0)]);
+    }
 }
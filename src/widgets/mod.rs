@@ -1,5 +1,8 @@
 pub mod button;
+pub mod card_search;
+pub mod empty_state;
 pub mod latency;
 pub mod scrollable_navigator;
 pub mod scrollbar;
 pub mod shortcut;
+pub mod table_style;
@@ -53,11 +53,22 @@ pub enum ShortcutMode {
 pub struct Shortcut {
     full: Vec<Fragment>,
     compact: Option<Vec<Fragment>>,
+    pinned: bool,
 }
 
 impl Shortcut {
     pub fn new(parts: Vec<Fragment>) -> Self {
-        Self { full: parts, compact: None }
+        Self { full: parts, compact: None, pinned: false }
+    }
+
+    /// Keep the shortcut visible on every footer page when the footer overflows.
+    pub fn pinned(mut self) -> Self {
+        self.pinned = true;
+        self
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
     }
 
     pub fn compact(mut self, parts: Vec<Fragment>) -> Self {
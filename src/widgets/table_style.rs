@@ -0,0 +1,125 @@
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols::line;
+use serde::{Deserialize, Serialize};
+
+/// Look of the header row of data tables.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HeaderStyle {
+    #[default]
+    Bold,
+    Underlined,
+    Reversed,
+}
+
+/// Table look of the Connections, Rules and Rule Providers tabs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableStyle {
+    #[serde(default)]
+    pub header: HeaderStyle,
+    /// Vertical lines between columns
+    #[serde(default)]
+    pub separators: bool,
+}
+
+static HEADER: AtomicU8 = AtomicU8::new(HeaderStyle::Bold as u8);
+static SEPARATORS: AtomicBool = AtomicBool::new(false);
+
+impl TableStyle {
+    /// Set the style used by [`header_style`] and [`render_separators`].
+    pub fn apply(self) {
+        HEADER.store(self.header as u8, Ordering::Relaxed);
+        SEPARATORS.store(self.separators, Ordering::Relaxed);
+    }
+
+    fn current() -> Self {
+        let header = match HEADER.load(Ordering::Relaxed) {
+            v if v == HeaderStyle::Underlined as u8 => HeaderStyle::Underlined,
+            v if v == HeaderStyle::Reversed as u8 => HeaderStyle::Reversed,
+            _ => HeaderStyle::Bold,
+        };
+        Self { header, separators: SEPARATORS.load(Ordering::Relaxed) }
+    }
+
+    fn header_style(self) -> Style {
+        let modifier = match self.header {
+            HeaderStyle::Bold => Modifier::BOLD,
+            HeaderStyle::Underlined => Modifier::BOLD | Modifier::UNDERLINED,
+            HeaderStyle::Reversed => Modifier::BOLD | Modifier::REVERSED,
+        };
+        Style::default().add_modifier(modifier)
+    }
+
+    fn render_separators(
+        self,
+        frame: &mut Frame,
+        area: Rect,
+        widths: &[Constraint],
+        flex: Flex,
+        spacing: u16,
+    ) {
+        if !self.separators || spacing == 0 {
+            return;
+        }
+        let columns = Layout::horizontal(widths).flex(flex).spacing(spacing).split(area);
+        let buf = frame.buffer_mut();
+        for pair in columns.windows(2) {
+            let (left, right) = (pair[0], pair[1]);
+            if left.width == 0 || right.width == 0 {
+                continue;
+            }
+            let x = left.right() + right.x.saturating_sub(left.right()).saturating_sub(1) / 2;
+            for y in area.top()..area.bottom() {
+                if let Some(cell) = buf.cell_mut((x, y)) {
+                    cell.set_symbol(line::VERTICAL).set_fg(Color::DarkGray);
+                }
+            }
+        }
+    }
+}
+
+/// Style of the header row with the configured [`HeaderStyle`].
+pub fn header_style() -> Style {
+    TableStyle::current().header_style()
+}
+
+/// Draw vertical lines in the spacing between the columns of a table rendered in `area`, if
+/// enabled. `widths`, `flex` and `spacing` must match the table's.
+pub fn render_separators(
+    frame: &mut Frame,
+    area: Rect,
+    widths: &[Constraint],
+    flex: Flex,
+    spacing: u16,
+) {
+    TableStyle::current().render_separators(frame, area, widths, flex, spacing);
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+
+    #[test]
+    fn separators_are_drawn_between_columns() {
+        let style = TableStyle { header: HeaderStyle::Reversed, separators: true };
+        assert!(style.header_style().add_modifier.contains(Modifier::REVERSED));
+        let mut terminal = Terminal::new(TestBackend::new(12, 2)).unwrap();
+        terminal
+            .draw(|frame| {
+                let widths = [Constraint::Length(3), Constraint::Length(3), Constraint::Fill(1)];
+                style.render_separators(frame, frame.area(), &widths, Flex::Start, 2);
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let line: String = (0..12).map(|x| buffer[(x, 1)].symbol()).collect();
+        assert_eq!(line, "   │    │   ");
+    }
+}